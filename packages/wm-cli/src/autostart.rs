@@ -0,0 +1,173 @@
+use std::process::{Command, Output};
+
+use anyhow::{bail, Context};
+use wm_common::AutostartCommand;
+
+use crate::resolve_main_exe_path;
+
+/// Name of the `Run` registry value and the scheduled task.
+const AUTOSTART_NAME: &str = "GlazeWM";
+
+/// Registry key for programs that are started on login.
+const RUN_KEY: &str =
+  r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+/// Enables, disables, or outputs the status of starting the WM on login.
+pub fn autostart(command: &AutostartCommand) -> anyhow::Result<()> {
+  match command {
+    AutostartCommand::Enable { elevated, delay } => {
+      enable_autostart(*elevated, *delay)
+    }
+    AutostartCommand::Disable => {
+      remove_run_key()?;
+      remove_scheduled_task()?;
+      println!("Autostart disabled.");
+      Ok(())
+    }
+    AutostartCommand::Status => {
+      if has_run_key()? {
+        println!("Autostart is enabled via the `Run` registry key.");
+      } else if has_scheduled_task()? {
+        println!("Autostart is enabled via a scheduled task.");
+      } else {
+        println!("Autostart is disabled.");
+      }
+
+      Ok(())
+    }
+  }
+}
+
+/// Adds either a `Run` registry key or a scheduled task for the WM.
+///
+/// Any existing entry of the other kind is removed, so that the WM isn't
+/// started twice.
+fn enable_autostart(elevated: bool, delay: u32) -> anyhow::Result<()> {
+  let exe_path = resolve_main_exe_path()?;
+  let exe_command = format!("\"{}\"", exe_path.display());
+
+  // A `Run` registry key can't start programs elevated or after a
+  // delay, so a scheduled task is used instead.
+  if !elevated && delay == 0 {
+    remove_scheduled_task()?;
+
+    run_checked(Command::new("reg").args([
+      "add",
+      RUN_KEY,
+      "/v",
+      AUTOSTART_NAME,
+      "/t",
+      "REG_SZ",
+      "/d",
+      &exe_command,
+      "/f",
+    ]))
+    .context("Failed to add `Run` registry key.")?;
+
+    println!("Autostart enabled via the `Run` registry key.");
+    return Ok(());
+  }
+
+  remove_run_key()?;
+
+  let delay_arg = format!("{:04}:{:02}", delay / 60, delay % 60);
+
+  let mut command = Command::new("schtasks");
+  command.args([
+    "/create",
+    "/tn",
+    AUTOSTART_NAME,
+    "/tr",
+    &exe_command,
+    "/sc",
+    "onlogon",
+    "/delay",
+    &delay_arg,
+    "/f",
+  ]);
+
+  if elevated {
+    command.args(["/rl", "highest"]);
+  }
+
+  run_checked(&mut command).context(if elevated {
+    "Failed to create scheduled task. Creating an elevated task requires \
+     running this command as administrator."
+  } else {
+    "Failed to create scheduled task."
+  })?;
+
+  println!("Autostart enabled via a scheduled task.");
+  Ok(())
+}
+
+fn has_run_key() -> anyhow::Result<bool> {
+  Ok(
+    run(Command::new("reg").args([
+      "query",
+      RUN_KEY,
+      "/v",
+      AUTOSTART_NAME,
+    ]))?
+    .status
+    .success(),
+  )
+}
+
+fn has_scheduled_task() -> anyhow::Result<bool> {
+  Ok(
+    run(Command::new("schtasks").args(["/query", "/tn", AUTOSTART_NAME]))?
+      .status
+      .success(),
+  )
+}
+
+fn remove_run_key() -> anyhow::Result<()> {
+  if has_run_key()? {
+    run_checked(Command::new("reg").args([
+      "delete",
+      RUN_KEY,
+      "/v",
+      AUTOSTART_NAME,
+      "/f",
+    ]))
+    .context("Failed to remove `Run` registry key.")?;
+  }
+
+  Ok(())
+}
+
+fn remove_scheduled_task() -> anyhow::Result<()> {
+  if has_scheduled_task()? {
+    run_checked(Command::new("schtasks").args([
+      "/delete",
+      "/tn",
+      AUTOSTART_NAME,
+      "/f",
+    ]))
+    .context("Failed to remove scheduled task.")?;
+  }
+
+  Ok(())
+}
+
+/// Runs the command and captures its output.
+pub(crate) fn run(command: &mut Command) -> anyhow::Result<Output> {
+  command.output().with_context(|| {
+    format!(
+      "Failed to run `{}`.",
+      command.get_program().to_string_lossy()
+    )
+  })
+}
+
+/// Runs the command and fails if it exits with a non-zero code.
+pub(crate) fn run_checked(command: &mut Command) -> anyhow::Result<()> {
+  let output = run(command)?;
+
+  if !output.status.success() {
+    bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+  }
+
+  Ok(())
+}
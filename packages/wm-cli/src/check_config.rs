@@ -0,0 +1,55 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use wm_common::{resolve_config_path, validate_config, ConfigFormat};
+use wm_platform::KeyboardHook;
+
+/// Parses and validates the user config and prints any diagnostics.
+///
+/// Returns an error if the config is invalid, so that the process exits
+/// with a non-zero code.
+pub fn check_config(config_path: Option<PathBuf>) -> anyhow::Result<()> {
+  let config_path = resolve_config_path(config_path)?;
+
+  let diagnostics = config_diagnostics(&config_path)?;
+
+  if !diagnostics.is_empty() {
+    for diagnostic in &diagnostics {
+      eprintln!("{diagnostic}");
+    }
+
+    bail!(
+      "Config file '{}' has {} error(s).",
+      config_path.display(),
+      diagnostics.len()
+    );
+  }
+
+  println!("Config file '{}' is valid.", config_path.display());
+  Ok(())
+}
+
+/// Parses and validates the config at the given path.
+///
+/// Returns an error if the config cannot be read or parsed, otherwise the
+/// list of problems found in the config.
+pub fn config_diagnostics(
+  config_path: &Path,
+) -> anyhow::Result<Vec<String>> {
+  let config_str = fs::read_to_string(config_path).with_context(|| {
+    format!("Unable to read config file '{}'.", config_path.display())
+  })?;
+
+  let config_value = ConfigFormat::from_path(config_path)
+    .parse(&config_str)
+    .with_context(|| {
+      format!("Failed to parse config file '{}'.", config_path.display())
+    })?;
+
+  Ok(validate_config(&config_value, |key| {
+    KeyboardHook::key_to_vk_code(key).is_some()
+  }))
+}
@@ -0,0 +1,208 @@
+use std::{
+  fs::{self, File},
+  io::Write,
+  path::{Path, PathBuf},
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use serde_json::json;
+use wm_common::{foreign_window_managers, resolve_config_path};
+use wm_ipc_client::IpcClient;
+use wm_platform::Platform;
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::check_config::config_diagnostics;
+
+/// IPC queries to include in the state snapshot.
+const STATE_QUERIES: [&str; 6] = [
+  "query app-metadata",
+  "query monitors",
+  "query workspaces",
+  "query windows",
+  "query binding-modes",
+  "query paused",
+];
+
+/// Number of recent log messages to request from a running WM.
+const LOG_TAIL: usize = 1000;
+
+/// Collects environment info, config validation results, logs, and a
+/// snapshot of the WM state into a zip archive for bug reports.
+pub async fn doctor(
+  config_path: Option<PathBuf>,
+  output_path: Option<PathBuf>,
+) -> anyhow::Result<()> {
+  let config_path = resolve_config_path(config_path)?;
+
+  let output_path = output_path.unwrap_or_else(|| {
+    let timestamp = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|duration| duration.as_secs())
+      .unwrap_or_default();
+
+    PathBuf::from(format!("glazewm-diagnostics-{timestamp}.zip"))
+  });
+
+  let file = File::create(&output_path).with_context(|| {
+    format!("Unable to create file '{}'.", output_path.display())
+  })?;
+
+  let mut zip = ZipWriter::new(file);
+  let options = SimpleFileOptions::default();
+
+  // State snapshot and recent logs are only available if the WM is
+  // running.
+  let mut client = IpcClient::connect().await;
+
+  // Elevation is queried from the WM, since the CLI may be run with
+  // different privileges.
+  let is_wm_elevated = match &mut client {
+    Ok(client) => ipc_query(client, "query app-metadata").await["data"]
+      ["isElevated"]
+      .as_bool(),
+    Err(_) => None,
+  };
+
+  zip.start_file("environment.json", options)?;
+  zip.write_all(
+    serde_json::to_string_pretty(&environment_info(is_wm_elevated))?
+      .as_bytes(),
+  )?;
+
+  zip.start_file("config-check.txt", options)?;
+  zip.write_all(config_check(&config_path).as_bytes())?;
+
+  if let Ok(config_str) = fs::read_to_string(&config_path) {
+    let config_name = config_path
+      .file_name()
+      .and_then(|name| name.to_str())
+      .unwrap_or("config.yaml");
+
+    zip.start_file(format!("config/{config_name}"), options)?;
+    zip.write_all(config_str.as_bytes())?;
+  }
+
+  match client {
+    Ok(mut client) => {
+      let mut state = serde_json::Map::new();
+
+      for query in STATE_QUERIES {
+        state
+          .insert(query.to_string(), ipc_query(&mut client, query).await);
+      }
+
+      zip.start_file("state.json", options)?;
+      zip.write_all(serde_json::to_string_pretty(&state)?.as_bytes())?;
+
+      let logs_query = format!("query logs --tail {LOG_TAIL}");
+      let logs = ipc_query(&mut client, &logs_query).await;
+
+      zip.start_file("logs/recent.json", options)?;
+      zip.write_all(serde_json::to_string_pretty(&logs)?.as_bytes())?;
+    }
+    Err(err) => {
+      zip.start_file("state.json", options)?;
+      zip.write_all(
+        serde_json::to_string_pretty(&json!({
+          "error": format!("WM is not running: {err}"),
+        }))?
+        .as_bytes(),
+      )?;
+    }
+  }
+
+  // Include the error log and the most recent rotated log file.
+  if let Some(log_dir) =
+    home::home_dir().map(|dir| dir.join(".glzr/glazewm/"))
+  {
+    let latest_log = fs::read_dir(log_dir.join("logs"))
+      .into_iter()
+      .flatten()
+      .filter_map(Result::ok)
+      .map(|entry| entry.path())
+      .max();
+
+    for path in latest_log.into_iter().chain([log_dir.join("errors.log")])
+    {
+      if let (Ok(contents), Some(name)) = (
+        fs::read(&path),
+        path.file_name().and_then(|name| name.to_str()),
+      ) {
+        zip.start_file(format!("logs/{name}"), options)?;
+        zip.write_all(&contents)?;
+      }
+    }
+  }
+
+  zip.finish()?;
+
+  println!("Saved diagnostics to '{}'.", output_path.display());
+  Ok(())
+}
+
+/// Gets info about the OS environment (monitors, elevation of the WM,
+/// and conflicting software).
+fn environment_info(is_wm_elevated: Option<bool>) -> serde_json::Value {
+  let monitors = Platform::sorted_monitors()
+    .map(|monitors| {
+      monitors
+        .iter()
+        .map(|monitor| {
+          json!({
+            "deviceName": monitor.device_name().ok(),
+            "hardwareId": monitor.hardware_id().ok().flatten(),
+            "rect": monitor.rect().ok(),
+            "workingRect": monitor.working_rect().ok(),
+            "dpi": monitor.dpi().ok(),
+            "scaleFactor": monitor.scale_factor().ok(),
+          })
+        })
+        .collect::<Vec<_>>()
+    })
+    .map_err(|err| err.to_string());
+
+  let process_names =
+    Platform::running_process_names().unwrap_or_default();
+
+  let conflicting_software = foreign_window_managers(&process_names);
+
+  json!({
+    "cliVersion": env!("VERSION_NUMBER"),
+    "os": std::env::consts::OS,
+    "arch": std::env::consts::ARCH,
+    "isElevated": is_wm_elevated,
+    "isWmRunning": process_names
+      .iter()
+      .any(|name| name.eq_ignore_ascii_case("glazewm.exe")),
+    "monitors": monitors.unwrap_or_else(|err| vec![json!({ "error": err })]),
+    "conflictingSoftware": conflicting_software,
+  })
+}
+
+/// Gets the config validation results as human-readable text.
+fn config_check(config_path: &Path) -> String {
+  match config_diagnostics(config_path) {
+    Ok(diagnostics) if diagnostics.is_empty() => {
+      format!("Config file '{}' is valid.\n", config_path.display())
+    }
+    Ok(diagnostics) => diagnostics.join("\n") + "\n",
+    Err(err) => format!("{err:?}\n"),
+  }
+}
+
+/// Sends a query to the IPC server and returns the response as JSON.
+async fn ipc_query(
+  client: &mut IpcClient,
+  query: &str,
+) -> serde_json::Value {
+  if let Err(err) = client.send(query).await {
+    return json!({ "error": err.to_string() });
+  }
+
+  match client.client_response(query).await {
+    Some(response) => serde_json::to_value(response)
+      .unwrap_or_else(|err| json!({ "error": err.to_string() })),
+    None => json!({ "error": "No response from IPC server." }),
+  }
+}
@@ -0,0 +1,158 @@
+use anyhow::{bail, Context};
+use wm_common::{
+  ClientResponseData, ExplainWindowData, WindowMatchResult,
+  WindowRuleEvent, WindowState,
+};
+use wm_ipc_client::IpcClient;
+use wm_platform::Platform;
+
+/// Outputs the properties of a window and whether each window rule
+/// matches it.
+///
+/// If `pick` is set, the window is chosen by clicking on it.
+pub async fn explain_window(
+  hwnd: Option<isize>,
+  pick: bool,
+) -> anyhow::Result<()> {
+  let handle = if pick {
+    println!(
+      "Click on a window to explain it, or press escape to cancel."
+    );
+
+    match Platform::pick_window()? {
+      Some(window) => window.handle,
+      None => return Ok(()),
+    }
+  } else {
+    hwnd.context("Either `--hwnd` or `--pick` is required.")?
+  };
+
+  let mut client = IpcClient::connect().await?;
+  let message = format!("explain-window --hwnd {handle}");
+
+  client
+    .send(&message)
+    .await
+    .context("Failed to send command to IPC server.")?;
+
+  let client_response = client
+    .client_response(&message)
+    .await
+    .context("Failed to receive response from IPC server.")?;
+
+  match client_response.data {
+    Some(ClientResponseData::ExplainWindow(data)) => {
+      print_explanation(&data);
+      Ok(())
+    }
+    _ => bail!(
+      "{}",
+      client_response
+        .error
+        .unwrap_or("Unexpected response from IPC server.".to_string())
+    ),
+  }
+}
+
+fn print_explanation(data: &ExplainWindowData) {
+  println!("Window {:#x} ({})", data.handle, data.handle);
+  println!("  Title:          {}", data.title);
+  println!("  Class:          {}", data.class_name);
+  println!("  Process:        {}", data.process_name);
+  println!("  Path:           {}", data.process_path);
+  println!("  Styles:         {}", data.styles.join(" "));
+  println!("  Extended:       {}", data.extended_styles.join(" "));
+  println!(
+    "  Elevated:       {}",
+    data.is_elevated.map_or("unknown", yes_no)
+  );
+  println!("  Manageable:     {}", yes_no(data.is_manageable));
+  println!("  Managed:        {}", managed_status(data));
+
+  if data.is_elevated == Some(true) {
+    println!(
+      "\nElevated windows can only be managed if GlazeWM is run as \
+       administrator."
+    );
+  }
+
+  println!("\nWindow rules:");
+
+  for rule in &data.window_rules {
+    let status = match (rule.is_match, rule.is_done) {
+      (true, true) => "MATCH (already run)",
+      (true, false) => "MATCH",
+      (false, _) => "no match",
+    };
+
+    println!(
+      "  [{status}] {} (on {}){}",
+      rule.commands.join("; "),
+      rule
+        .on
+        .iter()
+        .map(rule_event_name)
+        .collect::<Vec<_>>()
+        .join(", "),
+      if rule.is_default { " [default]" } else { "" },
+    );
+
+    for (index, result) in rule.match_results.iter().enumerate() {
+      println!("      match #{}: {}", index + 1, format_match(result));
+    }
+  }
+}
+
+fn managed_status(data: &ExplainWindowData) -> String {
+  match &data.window_state {
+    Some(state) => format!("yes ({})", window_state_name(state)),
+    None if data.is_ignored => "no (ignored)".to_string(),
+    None => "no".to_string(),
+  }
+}
+
+fn format_match(result: &WindowMatchResult) -> String {
+  [
+    ("process", result.window_process),
+    ("class", result.window_class),
+    ("title", result.window_title),
+    ("audio", result.is_playing_audio),
+    ("microphone", result.is_using_microphone),
+    ("camera", result.is_using_camera),
+    ("screen recording", result.is_recording_screen),
+  ]
+  .into_iter()
+  .filter_map(|(property, is_match)| {
+    is_match.map(|is_match| {
+      format!("{property} {}", if is_match { "ok" } else { "FAIL" })
+    })
+  })
+  .collect::<Vec<_>>()
+  .join(", ")
+}
+
+fn window_state_name(state: &WindowState) -> &'static str {
+  match state {
+    WindowState::Floating(_) => "floating",
+    WindowState::Fullscreen(_) => "fullscreen",
+    WindowState::Minimized => "minimized",
+    WindowState::Tiling => "tiling",
+  }
+}
+
+fn rule_event_name(event: &WindowRuleEvent) -> &'static str {
+  match event {
+    WindowRuleEvent::Focus => "focus",
+    WindowRuleEvent::Manage => "manage",
+    WindowRuleEvent::TitleChange => "title_change",
+    WindowRuleEvent::MediaUsageChange => "media_usage_change",
+  }
+}
+
+fn yes_no(value: bool) -> &'static str {
+  if value {
+    "yes"
+  } else {
+    "no"
+  }
+}
@@ -0,0 +1,44 @@
+use anyhow::{bail, Context};
+use wm_common::{exported_rules_config, ClientResponseData, ConfigFormat};
+use wm_ipc_client::IpcClient;
+
+/// Outputs window rules that capture the adjustments made to windows
+/// since they were managed, as YAML that can be added to the user
+/// config.
+pub async fn export_rules() -> anyhow::Result<()> {
+  let mut client = IpcClient::connect().await?;
+  let message = "export-rules";
+
+  client
+    .send(message)
+    .await
+    .context("Failed to send command to IPC server.")?;
+
+  let client_response = client
+    .client_response(message)
+    .await
+    .context("Failed to receive response from IPC server.")?;
+
+  let Some(ClientResponseData::ExportRules(data)) = client_response.data
+  else {
+    bail!(
+      "{}",
+      client_response
+        .error
+        .unwrap_or("Unexpected response from IPC server.".to_string())
+    );
+  };
+
+  if data.exported_rules.is_empty() {
+    eprintln!("No windows have been adjusted since they were managed.");
+    return Ok(());
+  }
+
+  print!(
+    "{}",
+    ConfigFormat::Yaml
+      .serialize(&exported_rules_config(&data.exported_rules))?
+  );
+
+  Ok(())
+}
@@ -0,0 +1,121 @@
+use std::{fs, path::Path};
+
+use anyhow::{bail, Context};
+use wm_common::{
+  import_i3_config, import_komorebi_config, ConfigFormat, ConfigImport,
+};
+use wm_platform::KeyboardHook;
+
+/// Converts an i3 config to a user config and prints the directives that
+/// couldn't be converted.
+///
+/// The generated config is written to `output_path` if set, and is
+/// otherwise printed as YAML.
+pub fn import_i3(
+  i3_config_path: &Path,
+  output_path: Option<&Path>,
+) -> anyhow::Result<()> {
+  let i3_config_str = read_config_file(i3_config_path)?;
+
+  let import = import_i3_config(&i3_config_str, |key| {
+    KeyboardHook::key_to_vk_code(key).is_some()
+  });
+
+  output_config_import(&import, output_path)
+}
+
+/// Converts a komorebi config, and optionally its app-specific config and
+/// whkd config, to a user config and prints the options that couldn't be
+/// converted.
+///
+/// The generated config is written to `output_path` if set, and is
+/// otherwise printed as YAML.
+pub fn import_komorebi(
+  komorebi_config_path: &Path,
+  applications_path: Option<&Path>,
+  whkdrc_path: Option<&Path>,
+  output_path: Option<&Path>,
+) -> anyhow::Result<()> {
+  let komorebi_config = ConfigFormat::from_path(komorebi_config_path)
+    .parse(&read_config_file(komorebi_config_path)?)
+    .with_context(|| {
+      format!(
+        "Failed to parse komorebi config '{}'.",
+        komorebi_config_path.display()
+      )
+    })?;
+
+  let applications_config = applications_path
+    .map(|applications_path| {
+      ConfigFormat::from_path(applications_path)
+        .parse(&read_config_file(applications_path)?)
+        .with_context(|| {
+          format!(
+            "Failed to parse app-specific config '{}'.",
+            applications_path.display()
+          )
+        })
+    })
+    .transpose()?;
+
+  let whkdrc = whkdrc_path.map(read_config_file).transpose()?;
+
+  let import = import_komorebi_config(
+    &komorebi_config,
+    applications_config.as_ref(),
+    whkdrc.as_deref(),
+    |key| KeyboardHook::key_to_vk_code(key).is_some(),
+  );
+
+  output_config_import(&import, output_path)
+}
+
+fn read_config_file(path: &Path) -> anyhow::Result<String> {
+  fs::read_to_string(path).with_context(|| {
+    format!("Unable to read config file '{}'.", path.display())
+  })
+}
+
+/// Writes the generated config to `output_path`, or prints it as YAML if
+/// not set. Options that couldn't be converted are printed afterwards.
+fn output_config_import(
+  import: &ConfigImport,
+  output_path: Option<&Path>,
+) -> anyhow::Result<()> {
+  match output_path {
+    Some(output_path) => {
+      if output_path.exists() {
+        bail!("File '{}' already exists.", output_path.display());
+      }
+
+      let config_str =
+        ConfigFormat::from_path(output_path).serialize(&import.config)?;
+
+      fs::write(output_path, config_str).with_context(|| {
+        format!("Unable to write to {}.", output_path.display())
+      })?;
+
+      eprintln!("Generated config at '{}'.", output_path.display());
+    }
+    None => print!("{}", ConfigFormat::Yaml.serialize(&import.config)?),
+  }
+
+  // Report to stderr, so that the printed config can be redirected to a
+  // file.
+  if !import.unsupported.is_empty() {
+    eprintln!(
+      "{} option(s) could not be converted:",
+      import.unsupported.len()
+    );
+
+    for unsupported in &import.unsupported {
+      eprintln!("  - {unsupported}");
+    }
+  }
+
+  for note in &import.notes {
+    eprintln!("Note: {note}");
+  }
+
+  Ok(())
+}
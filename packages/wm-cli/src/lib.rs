@@ -1,14 +1,109 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 
-use anyhow::Context;
-use wm_common::ClientResponseData;
+use std::{env, path::PathBuf};
+
+use anyhow::{bail, Context};
+use wm_common::{AppCommand, ClientResponseData};
 use wm_ipc_client::IpcClient;
 
+use crate::{
+  autostart::autostart,
+  check_config::check_config,
+  doctor::doctor,
+  explain_window::explain_window,
+  export_rules::export_rules,
+  import_config::{import_i3, import_komorebi},
+  migrate_config::migrate_config,
+  pick::pick,
+  protocol::{open_url, protocol},
+};
+
+mod autostart;
+mod check_config;
+mod doctor;
+mod explain_window;
+mod export_rules;
+mod import_config;
+mod migrate_config;
+mod pick;
+mod protocol;
+
 pub async fn start(args: Vec<String>) -> anyhow::Result<()> {
+  let app_command = AppCommand::parse_with_default(&args);
+
+  match app_command {
+    // Commands that run locally and don't require an already running
+    // instance of the window manager.
+    AppCommand::MigrateConfig { config_path } => {
+      migrate_config(config_path)
+    }
+    AppCommand::CheckConfig { config_path } => check_config(config_path),
+    AppCommand::ImportI3 {
+      i3_config_path,
+      output_path,
+    } => import_i3(&i3_config_path, output_path.as_deref()),
+    AppCommand::ImportKomorebi {
+      komorebi_config_path,
+      applications_path,
+      whkdrc_path,
+      output_path,
+    } => import_komorebi(
+      &komorebi_config_path,
+      applications_path.as_deref(),
+      whkdrc_path.as_deref(),
+      output_path.as_deref(),
+    ),
+    AppCommand::Doctor {
+      config_path,
+      output_path,
+    } => doctor(config_path, output_path).await,
+    AppCommand::Autostart { command } => autostart(&command),
+    AppCommand::Pick { json } => pick(json),
+    AppCommand::Protocol { command } => protocol(&command),
+    AppCommand::OpenUrl { url } => open_url(&url).await,
+    // Explaining a window requires the WM, but the window can be picked
+    // by clicking on it, and the output is formatted for reading.
+    AppCommand::ExplainWindow { hwnd, pick } => {
+      explain_window(hwnd, pick).await
+    }
+    // Exported rules are formatted as YAML rather than JSON.
+    AppCommand::ExportRules => export_rules().await,
+    _ => send_ipc_message(&args).await,
+  }
+}
+
+/// Resolves the path to the main executable (`glazewm.exe`).
+///
+/// The main executable is either in the same directory (when running
+/// debug/release builds), or in the parent directory when packaged.
+pub fn resolve_main_exe_path() -> anyhow::Result<PathBuf> {
+  let exe_path = env::current_exe()?;
+  let exe_dir = exe_path
+    .parent()
+    .context("Failed to resolve path to the current executable.")?;
+
+  // The CLI executable is also named `glazewm.exe` when packaged, so it
+  // needs to be skipped.
+  [exe_dir.join("glazewm.exe"), exe_dir.join("../glazewm.exe")]
+    .into_iter()
+    .find(|path| path.exists() && *path != exe_path)
+    .context("Failed to resolve path to the main executable.")
+}
+
+/// Sends the CLI arguments as a message to the IPC server and outputs
+/// the response(s).
+async fn send_ipc_message(args: &[String]) -> anyhow::Result<()> {
   let mut client = IpcClient::connect().await?;
 
-  let message = args[1..].join(" ");
+  // Mark commands as originating from the CLI, so that they can be told
+  // apart from those of other IPC clients in `command_invoked` events.
+  if matches!(args.get(1).map(String::as_str), Some("command" | "c")) {
+    set_command_source(&mut client, "cli").await?;
+  }
+
+  let message = args.get(1..).unwrap_or_default().join(" ");
+
   client
     .send(&message)
     .await
@@ -22,14 +117,20 @@ pub async fn start(args: Vec<String>) -> anyhow::Result<()> {
   match client_response.data {
     // For event subscriptions, omit the initial response message and
     // continuously output subsequent event messages.
-    Some(ClientResponseData::EventSubscribe(data)) => loop {
-      let event_subscription = client
-        .event_subscription(&data.subscription_id)
-        .await
-        .context("Failed to receive response from IPC server.")?;
-
-      println!("{}", serde_json::to_string(&event_subscription)?);
-    },
+    Some(ClientResponseData::EventSubscribe(data)) => {
+      for replayed_event in &data.replayed_events {
+        println!("{}", serde_json::to_string(replayed_event)?);
+      }
+
+      loop {
+        let event_subscription = client
+          .event_subscription(&data.subscription_id)
+          .await
+          .context("Failed to receive response from IPC server.")?;
+
+        println!("{}", serde_json::to_string(&event_subscription)?);
+      }
+    }
     // For all other messages, output and exit when the first response
     // message is received.
     _ => {
@@ -39,3 +140,31 @@ pub async fn start(args: Vec<String>) -> anyhow::Result<()> {
 
   Ok(())
 }
+
+/// Sets the source that the IPC server reports for commands sent over
+/// the connection (e.g. `cli`).
+///
+/// The source is fixed for the lifetime of the connection, so this needs
+/// to be called before any commands are sent.
+async fn set_command_source(
+  client: &mut IpcClient,
+  source: &str,
+) -> anyhow::Result<()> {
+  let message = format!("hello --source {source}");
+
+  client
+    .send(&message)
+    .await
+    .context("Failed to send command to IPC server.")?;
+
+  let client_response = client
+    .client_response(&message)
+    .await
+    .context("Failed to receive response from IPC server.")?;
+
+  if !client_response.success {
+    bail!(client_response.error.unwrap_or_default());
+  }
+
+  Ok(())
+}
@@ -1,8 +1,8 @@
-use std::{env, process::Command};
+use std::process::Command;
 
 use anyhow::Context;
-use wm_cli::start;
-use wm_common::AppCommand;
+use wm_cli::{resolve_main_exe_path, start};
+use wm_common::{create_config_file, resolve_config_path, AppCommand};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -10,21 +10,19 @@ async fn main() -> anyhow::Result<()> {
   let app_command = AppCommand::parse_with_default(&args);
 
   match app_command {
-    AppCommand::Start { .. } => {
-      let exe_path = env::current_exe()?;
-      let exe_dir = exe_path
-        .parent()
-        .context("Failed to resolve path to the current executable.")?
-        .to_owned();
+    AppCommand::Start { config_path, .. } => {
+      // Generate the config here rather than in the main executable, so
+      // that the config wizard can prompt in the current console.
+      let config_path = resolve_config_path(config_path)?;
 
-      // Main executable is either in the current directory (when running
-      // debug/release builds) or in the parent directory when packaged.
-      let main_path =
-        [exe_dir.join("glazewm.exe"), exe_dir.join("../glazewm.exe")]
-          .into_iter()
-          .find(|path| path.exists() && *path != exe_path)
-          .and_then(|path| path.to_str().map(ToString::to_string))
-          .context("Failed to resolve path to the main executable.")?;
+      if !config_path.exists() {
+        create_config_file(&config_path)?;
+      }
+
+      let main_path = resolve_main_exe_path()?
+        .to_str()
+        .map(ToString::to_string)
+        .context("Failed to resolve path to the main executable.")?;
 
       // UIAccess applications can't be started directly, so we need to use
       // CMD to start it. The start command is used to avoid a long-running
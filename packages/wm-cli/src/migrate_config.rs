@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use anyhow::bail;
+use wm_common::{migrate_config_file, resolve_config_path};
+
+/// Migrates the user config to the current config schema and prints a
+/// summary of the applied changes.
+pub fn migrate_config(config_path: Option<PathBuf>) -> anyhow::Result<()> {
+  let config_path = resolve_config_path(config_path)?;
+
+  if !config_path.exists() {
+    bail!("No config file found at '{}'.", config_path.display());
+  }
+
+  let migration = migrate_config_file(&config_path)?;
+
+  match migration.backup_path {
+    None => {
+      println!("Config at '{}' is up to date.", config_path.display());
+    }
+    Some(backup_path) => {
+      println!(
+        "Migrated config at '{}'. Backup saved to '{}'.",
+        config_path.display(),
+        backup_path.display()
+      );
+
+      for change in &migration.changes {
+        println!("  - {change}");
+      }
+    }
+  }
+
+  Ok(())
+}
@@ -0,0 +1,68 @@
+use serde_json::json;
+use wm_platform::{NativeWindow, Platform};
+
+/// Lets the user click on a window and outputs its properties, along
+/// with a window rule snippet that matches it.
+pub fn pick(json: bool) -> anyhow::Result<()> {
+  if !json {
+    println!("Click on a window to pick it, or press escape to cancel.");
+  }
+
+  let Some(window) = Platform::pick_window()? else {
+    return Ok(());
+  };
+
+  let process_name = window.process_name()?;
+  let class_name = window.class_name()?;
+  let title = window.title()?;
+  let window_rule = window_rule_snippet(&process_name, &class_name);
+
+  if json {
+    let output = json!({
+      "handle": window.handle,
+      "processName": process_name,
+      "className": class_name,
+      "title": title,
+      "windowRule": window_rule,
+    });
+
+    println!("{output}");
+    return Ok(());
+  }
+
+  print_window(&window, &process_name, &class_name, &title);
+  println!("\nWindow rule:\n\n{window_rule}");
+
+  Ok(())
+}
+
+fn print_window(
+  window: &NativeWindow,
+  process_name: &str,
+  class_name: &str,
+  title: &str,
+) {
+  println!("\nHandle:  {:#x}", window.handle);
+  println!("Process: {process_name}");
+  println!("Class:   {class_name}");
+  println!("Title:   {title}");
+}
+
+/// Creates a YAML window rule that matches the window by its process and
+/// class. The title is left out, since it typically changes.
+fn window_rule_snippet(process_name: &str, class_name: &str) -> String {
+  format!(
+    "window_rules:\n  \
+     - commands: ['set-floating']\n    \
+     match:\n      \
+     - window_process: {{ equals: {} }}\n        \
+     window_class: {{ equals: {} }}",
+    yaml_quote(process_name),
+    yaml_quote(class_name),
+  )
+}
+
+/// Wraps a string in single quotes, escaping any single quotes within.
+fn yaml_quote(value: &str) -> String {
+  format!("'{}'", value.replace('\'', "''"))
+}
@@ -0,0 +1,165 @@
+use std::process::Command;
+
+use anyhow::{bail, Context};
+use percent_encoding::percent_decode_str;
+use wm_common::ProtocolCommand;
+use wm_ipc_client::IpcClient;
+use wm_platform::Platform;
+
+use crate::{
+  autostart::{run, run_checked},
+  resolve_main_exe_path, set_command_source,
+};
+
+/// Registry key of the `glazewm://` URL protocol.
+const PROTOCOL_KEY: &str = r"HKCU\Software\Classes\glazewm";
+
+/// Registry key of the command that URLs are opened with.
+const PROTOCOL_COMMAND_KEY: &str =
+  r"HKCU\Software\Classes\glazewm\shell\open\command";
+
+/// Registers, unregisters, or outputs the status of the `glazewm://` URL
+/// protocol handler.
+pub fn protocol(command: &ProtocolCommand) -> anyhow::Result<()> {
+  match command {
+    ProtocolCommand::Register => {
+      register_protocol()?;
+      println!("Registered handler for `glazewm://` URLs.");
+      Ok(())
+    }
+    ProtocolCommand::Unregister => {
+      if is_protocol_registered()? {
+        run_checked(Command::new("reg").args([
+          "delete",
+          PROTOCOL_KEY,
+          "/f",
+        ]))
+        .context("Failed to remove protocol registry key.")?;
+      }
+
+      println!("Unregistered handler for `glazewm://` URLs.");
+      Ok(())
+    }
+    ProtocolCommand::Status => {
+      if is_protocol_registered()? {
+        println!("Handler for `glazewm://` URLs is registered.");
+      } else {
+        println!("Handler for `glazewm://` URLs is not registered.");
+      }
+
+      Ok(())
+    }
+  }
+}
+
+/// Runs the WM command in a `glazewm://command/<command>` URL.
+///
+/// Since URLs are opened without a console, errors are shown in a
+/// dialog.
+pub async fn open_url(url: &str) -> anyhow::Result<()> {
+  let res = run_url_command(url).await;
+
+  if let Err(err) = &res {
+    Platform::show_error_dialog(
+      "Failed to open URL",
+      &format!("{url}\n\n{err}"),
+    );
+  }
+
+  res
+}
+
+fn register_protocol() -> anyhow::Result<()> {
+  let exe_path = resolve_main_exe_path()?;
+  let open_command = format!("\"{}\" open-url \"%1\"", exe_path.display());
+
+  run_checked(Command::new("reg").args([
+    "add",
+    PROTOCOL_KEY,
+    "/ve",
+    "/d",
+    "URL:GlazeWM",
+    "/f",
+  ]))
+  .and_then(|()| {
+    // An empty `URL Protocol` value marks the key as a URL protocol.
+    run_checked(Command::new("reg").args([
+      "add",
+      PROTOCOL_KEY,
+      "/v",
+      "URL Protocol",
+      "/d",
+      "",
+      "/f",
+    ]))
+  })
+  .and_then(|()| {
+    run_checked(Command::new("reg").args([
+      "add",
+      PROTOCOL_COMMAND_KEY,
+      "/ve",
+      "/d",
+      &open_command,
+      "/f",
+    ]))
+  })
+  .context("Failed to add protocol registry keys.")
+}
+
+fn is_protocol_registered() -> anyhow::Result<bool> {
+  Ok(
+    run(Command::new("reg").args(["query", PROTOCOL_COMMAND_KEY, "/ve"]))?
+      .status
+      .success(),
+  )
+}
+
+async fn run_url_command(url: &str) -> anyhow::Result<()> {
+  let command = command_from_url(url)?;
+  let message = format!("command {command}");
+
+  let mut client = IpcClient::connect().await?;
+
+  // Lets the WM restrict which commands can be run from URLs.
+  set_command_source(&mut client, "url").await?;
+
+  client
+    .send(&message)
+    .await
+    .context("Failed to send command to IPC server.")?;
+
+  let client_response = client
+    .client_response(&message)
+    .await
+    .context("Failed to receive response from IPC server.")?;
+
+  if !client_response.success {
+    bail!(client_response.error.unwrap_or_default());
+  }
+
+  Ok(())
+}
+
+/// Gets the decoded WM command from a `glazewm://command/<command>` URL
+/// (e.g. `glazewm://command/focus%20--workspace%202`).
+fn command_from_url(url: &str) -> anyhow::Result<String> {
+  let command = url
+    .split_once("://")
+    .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("glazewm"))
+    .and_then(|(_, path)| path.strip_prefix("command/"))
+    .context(
+      "Expected a URL of the form `glazewm://command/<command>`.",
+    )?;
+
+  // Browsers might add a trailing slash to the URL.
+  let command = percent_decode_str(command.trim_end_matches('/'))
+    .decode_utf8()
+    .context("URL is not valid UTF-8.")?
+    .into_owned();
+
+  if command.trim().is_empty() {
+    bail!("No command given in URL.");
+  }
+
+  Ok(command)
+}
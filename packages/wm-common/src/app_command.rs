@@ -1,16 +1,26 @@
-use std::{iter, path::PathBuf};
+use std::{iter, path::PathBuf, str::FromStr};
 
-use clap::{error::KindFormatter, Args, Parser, ValueEnum};
+use anyhow::{bail, Context};
+use clap::{
+  error::KindFormatter, ArgAction, Args, CommandFactory, Parser, ValueEnum,
+};
 use serde::{Deserialize, Deserializer, Serialize};
 use tracing::Level;
 use uuid::Uuid;
 
 use crate::{
-  Delta, Direction, LengthValue, OpacityValue, TilingDirection,
+  CommandSource, Delta, Direction, FocusAssistMode, GapKind, GapValue,
+  HideMethod, IpcEncoding, LengthValue, OpacityValue, SizeConstraints,
+  TilingDirection, ZoomScope,
 };
 
 const VERSION: &str = env!("VERSION_NUMBER");
 
+/// Argument values that are tried when listing runnable commands for
+/// arguments that don't define their possible values (e.g. directions).
+const COMMON_ARG_VALUES: [&str; 6] =
+  ["left", "right", "up", "down", "horizontal", "vertical"];
+
 #[derive(Clone, Debug, Parser)]
 #[clap(name = "glazewm", author, version = VERSION, about, long_about = None)]
 pub enum AppCommand {
@@ -18,10 +28,24 @@ pub enum AppCommand {
   Start {
     /// Custom path to user config file.
     ///
-    /// The default path is `%userprofile%/.glzr/glazewm/config.yaml`
+    /// The default path is `%userprofile%/.glzr/glazewm/config.yaml`.
+    /// Files with a `.toml` or `.json` extension are parsed as TOML or
+    /// JSON respectively.
     #[clap(short = 'c', long = "config", value_hint = clap::ValueHint::FilePath)]
     config_path: Option<PathBuf>,
 
+    /// Replaces an already running instance of the window manager.
+    ///
+    /// The running instance is asked to exit and release all its windows
+    /// before this instance takes over.
+    #[clap(long, action)]
+    replace: bool,
+
+    /// Runs the window manager under a watchdog process, which restarts
+    /// it if it crashes.
+    #[clap(long, action)]
+    with_watchdog: bool,
+
     #[clap(flatten)]
     verbosity: Verbosity,
   },
@@ -55,6 +79,11 @@ pub enum AppCommand {
     /// WM event(s) to subscribe to.
     #[clap(short = 'e', long, value_enum, num_args = 1..)]
     events: Vec<SubscribableEvent>,
+
+    /// Sequence number of the first event to replay from the WM's buffer
+    /// of recent events.
+    #[clap(long)]
+    replay_from: Option<u64>,
   },
 
   /// Unsubscribes from a prior event subscription.
@@ -65,24 +94,214 @@ pub enum AppCommand {
     #[clap(long = "id")]
     subscription_id: Uuid,
   },
+
+  /// Performs a handshake with the window manager, outputting its IPC
+  /// protocol version and supported capabilities.
+  ///
+  /// Requires an already running instance of the window manager.
+  Hello {
+    /// Name of the client (e.g. `zebar`). Used for diagnostics.
+    #[clap(long)]
+    client_name: Option<String>,
+
+    /// Version of the client. Used for diagnostics.
+    #[clap(long)]
+    client_version: Option<String>,
+
+    /// Encoding of subsequent messages sent to the client. Defaults to
+    /// JSON.
+    #[clap(long, value_enum)]
+    encoding: Option<IpcEncoding>,
+
+    /// Source reported in `command_invoked` events for commands sent
+    /// over the connection (`cli` or `url`). Defaults to `ipc`.
+    ///
+    /// Can only be set once per connection.
+    #[clap(long, value_enum, hide = true)]
+    source: Option<CommandSource>,
+  },
+
+  /// Broadcasts a user-defined event to all subscribers of
+  /// `custom_event`, e.g. to pass messages between scripts and status
+  /// bar widgets.
+  ///
+  /// Requires an already running instance of the window manager.
+  EmitCustomEvent {
+    /// Name of the event (e.g. `music_changed`).
+    name: String,
+
+    /// Payload of the event. Parsed as JSON if valid, and otherwise
+    /// sent as a string.
+    #[clap(trailing_var_arg = true, allow_hyphen_values = true)]
+    payload: Vec<String>,
+  },
+
+  /// Changes the log level of the window manager at runtime.
+  ///
+  /// Requires an already running instance of the window manager.
+  SetLogLevel {
+    /// Log level (e.g. `debug`) or comma-separated filter directives for
+    /// per-module levels (e.g. `info,wm_platform=trace`).
+    filter: String,
+  },
+
+  /// Outputs the properties of a window and evaluates every window rule
+  /// against it, to help debug why a rule does or doesn't apply.
+  ///
+  /// Requires an already running instance of the window manager.
+  ExplainWindow {
+    /// Handle of the window, either in decimal or hex (e.g. `0x1a2b`).
+    #[clap(
+      long,
+      value_parser = parse_window_handle,
+      required_unless_present = "pick"
+    )]
+    hwnd: Option<isize>,
+
+    /// Shows a crosshair cursor and explains the clicked window.
+    #[clap(long, action, conflicts_with = "hwnd")]
+    pick: bool,
+  },
+
+  /// Outputs window rules that capture adjustments made to windows since
+  /// they were managed: windows that were made floating, tiling, or
+  /// ignored, and windows that were moved to another workspace.
+  ///
+  /// Requires an already running instance of the window manager.
+  ExportRules,
+
+  /// Shows a crosshair cursor and outputs the process, class, and title
+  /// of the clicked window, along with a window rule that matches it.
+  Pick {
+    /// Outputs the window's properties as JSON.
+    #[clap(long, action)]
+    json: bool,
+  },
+
+  /// Rewrites a user config that uses outdated options to the current
+  /// config schema.
+  ///
+  /// A backup of the original config is saved next to it.
+  MigrateConfig {
+    /// Custom path to user config file.
+    ///
+    /// The default path is `%userprofile%/.glzr/glazewm/config.yaml`.
+    #[clap(short = 'c', long = "config", value_hint = clap::ValueHint::FilePath)]
+    config_path: Option<PathBuf>,
+  },
+
+  /// Converts an i3 config to an equivalent user config. Directives that
+  /// can't be converted are reported.
+  ImportI3 {
+    /// Path to the i3 config file.
+    #[clap(value_hint = clap::ValueHint::FilePath)]
+    i3_config_path: PathBuf,
+
+    /// Path to write the generated config to. The format is based on the
+    /// file extension. Outputs the config as YAML if not set.
+    #[clap(short = 'o', long = "output", value_hint = clap::ValueHint::FilePath)]
+    output_path: Option<PathBuf>,
+  },
+
+  /// Converts a komorebi config to an equivalent user config. Options
+  /// that can't be converted are reported.
+  ImportKomorebi {
+    /// Path to the komorebi config file (`komorebi.json`).
+    #[clap(value_hint = clap::ValueHint::FilePath)]
+    komorebi_config_path: PathBuf,
+
+    /// Path to komorebi's app-specific config file (e.g.
+    /// `applications.json`), to also convert its float and ignore rules.
+    #[clap(long = "applications", value_hint = clap::ValueHint::FilePath)]
+    applications_path: Option<PathBuf>,
+
+    /// Path to the whkd config file (`whkdrc`), to also convert its
+    /// keybindings.
+    #[clap(long = "whkdrc", value_hint = clap::ValueHint::FilePath)]
+    whkdrc_path: Option<PathBuf>,
+
+    /// Path to write the generated config to. The format is based on the
+    /// file extension. Outputs the config as YAML if not set.
+    #[clap(short = 'o', long = "output", value_hint = clap::ValueHint::FilePath)]
+    output_path: Option<PathBuf>,
+  },
+
+  /// Collects environment info, config validation results, recent logs,
+  /// and a snapshot of the WM state into a zip archive for bug reports.
+  Doctor {
+    /// Custom path to user config file.
+    ///
+    /// The default path is `%userprofile%/.glzr/glazewm/config.yaml`.
+    #[clap(short = 'c', long = "config", value_hint = clap::ValueHint::FilePath)]
+    config_path: Option<PathBuf>,
+
+    /// Path to write the zip archive to. Defaults to a timestamped file
+    /// in the current directory.
+    #[clap(short = 'o', long = "output", value_hint = clap::ValueHint::FilePath)]
+    output_path: Option<PathBuf>,
+  },
+
+  /// Manages whether the window manager is started automatically on
+  /// login.
+  Autostart {
+    #[clap(subcommand)]
+    command: AutostartCommand,
+  },
+
+  /// Manages whether `glazewm://` URLs are opened by the window manager.
+  Protocol {
+    #[clap(subcommand)]
+    command: ProtocolCommand,
+  },
+
+  /// Runs a WM command from a `glazewm://command/<command>` URL, where
+  /// the command is percent-encoded. Used by the registered protocol
+  /// handler.
+  ///
+  /// Requires an already running instance of the window manager.
+  OpenUrl { url: String },
+
+  /// Parses and validates a user config without starting the WM.
+  ///
+  /// Exits with a non-zero code if the config is invalid.
+  CheckConfig {
+    /// Custom path to user config file.
+    ///
+    /// The default path is `%userprofile%/.glzr/glazewm/config.yaml`.
+    #[clap(value_hint = clap::ValueHint::FilePath)]
+    config_path: Option<PathBuf>,
+  },
 }
 
 impl AppCommand {
   /// Parses `AppCommand` from command line arguments.
   ///
-  /// Defaults to `AppCommand::Start` if no arguments are provided.
+  /// Defaults to `AppCommand::Start` if no arguments are provided. Flags
+  /// of the start command can also be passed without the subcommand (e.g.
+  /// `glazewm --replace`).
   #[must_use]
   pub fn parse_with_default(args: &Vec<String>) -> Self {
-    if args.len() == 1 {
-      AppCommand::Start {
+    match args.get(1).map(String::as_str) {
+      None => AppCommand::Start {
         config_path: None,
+        replace: false,
+        with_watchdog: false,
         verbosity: Verbosity {
           verbose: false,
           quiet: false,
         },
+      },
+      Some(arg)
+        if arg.starts_with('-')
+          && !["-h", "--help", "-V", "--version"].contains(&arg) =>
+      {
+        AppCommand::parse_from(
+          iter::once(args[0].as_str())
+            .chain(iter::once("start"))
+            .chain(args[1..].iter().map(String::as_str)),
+        )
       }
-    } else {
-      AppCommand::parse_from(args)
+      Some(_) => AppCommand::parse_from(args),
     }
   }
 }
@@ -121,6 +340,11 @@ pub enum QueryCommand {
   /// Outputs the focused container (either a window or an empty
   /// workspace).
   Focused,
+  /// Outputs identifying info of the focused window (e.g. its process
+  /// name and executable path).
+  FocusedWindow,
+  /// Outputs the gaps of the focused workspace and the global gaps.
+  Gaps,
   /// Outputs the tiling direction of the focused container.
   TilingDirection,
   /// Outputs all monitors.
@@ -131,6 +355,70 @@ pub enum QueryCommand {
   Workspaces,
   /// Outputs whether the window manager is paused.
   Paused,
+  /// Outputs the active Focus Assist profile.
+  FocusAssist,
+  /// Outputs the keyboard layout of the foreground window.
+  KeyboardLayout,
+  /// Outputs recent log messages.
+  Logs {
+    /// Number of most recent log messages to output.
+    #[clap(long)]
+    tail: Option<usize>,
+  },
+  /// Outputs performance counters for the event loop (event handling
+  /// times, layout and redraw times, and event queue depth).
+  Stats,
+  /// Outputs the timers from the user config, whether they're enabled,
+  /// and when they're next due to run.
+  Timers,
+  /// Outputs all windows ordered by workspace, each with a label and a
+  /// command for focusing it. Meant for window menus of bars.
+  WindowListForMenu,
+  /// Outputs a snapshot of a window as a base64-encoded PNG, along with
+  /// its handle for registering a live DWM thumbnail.
+  WindowThumbnail {
+    /// ID of the window to capture.
+    #[clap(long)]
+    container_id: Uuid,
+
+    /// Maximum width and height of the snapshot in pixels.
+    #[clap(long, default_value_t = 320)]
+    max_size: u32,
+  },
+}
+
+#[derive(Clone, Debug, Parser)]
+pub enum AutostartCommand {
+  /// Starts the window manager on login.
+  ///
+  /// Uses a `Run` registry key by default, or a scheduled task if
+  /// `--elevated` or `--delay` is passed.
+  Enable {
+    /// Starts the window manager with administrator privileges, so that
+    /// elevated windows can be managed. Requires running this command as
+    /// administrator.
+    #[clap(long, action)]
+    elevated: bool,
+
+    /// Delay in seconds after login before starting the window manager.
+    #[clap(long, default_value_t = 0)]
+    delay: u32,
+  },
+  /// Stops the window manager from starting on login.
+  Disable,
+  /// Outputs whether the window manager is started on login.
+  Status,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub enum ProtocolCommand {
+  /// Registers the window manager as the handler of `glazewm://` URLs
+  /// for the current user.
+  Register,
+  /// Removes the handler of `glazewm://` URLs.
+  Unregister,
+  /// Outputs whether the window manager handles `glazewm://` URLs.
+  Status,
 }
 
 #[derive(Clone, Debug, PartialEq, ValueEnum)]
@@ -138,35 +426,178 @@ pub enum QueryCommand {
 pub enum SubscribableEvent {
   All,
   ApplicationExiting,
+  BindingModeEntered,
+  BindingModeExited,
   BindingModesChanged,
+  CommandInvoked,
+  CustomEvent,
+  ForeignWmDetected,
   FocusChanged,
   FocusedContainerMoved,
+  GameModeChanged,
+  KeyboardLayoutChanged,
   MonitorAdded,
   MonitorUpdated,
   MonitorRemoved,
   TilingDirectionChanged,
+  UserActive,
   UserConfigChanged,
+  UserIdle,
+  WindowAudioChanged,
   WindowManaged,
   WindowUnmanaged,
+  WindowUnresponsive,
+  WindowResponsive,
   WorkspaceActivated,
   WorkspaceDeactivated,
   WorkspaceUpdated,
   PauseChanged,
+  MouseEventsChanged,
+  Trace,
 }
 
 #[derive(Clone, Debug, Parser, PartialEq, Serialize)]
 pub enum InvokeCommand {
+  /// Focuses the workspace at the given position on a monitor, with
+  /// workspaces ordered as in the user config. Meant for click handlers
+  /// of bars.
+  ActivateWorkspaceAtIndex {
+    /// Position of the workspace on the monitor, starting at 0.
+    #[clap(required = true)]
+    index: usize,
+
+    /// Index of the monitor. Defaults to the focused monitor.
+    #[clap(long)]
+    monitor: Option<usize>,
+  },
   AdjustBorders(InvokeAdjustBordersCommand),
-  Close,
+  /// Manages a window that is currently unmanaged or ignored.
+  AdoptWindow(InvokeWindowTargetCommand),
+  AdjustGaps {
+    #[clap(required = true, value_enum)]
+    gap: GapKind,
+
+    #[clap(required = true, allow_hyphen_values = true)]
+    amount: Delta<LengthValue>,
+
+    /// Whether to adjust the gaps of all workspaces instead of only the
+    /// focused workspace.
+    #[clap(long, action)]
+    global: bool,
+  },
+  /// Rearranges the windows of a workspace into its template, and shows
+  /// placeholder frames for the slots that haven't been filled.
+  ApplyWorkspaceTemplate {
+    /// Name of the workspace to apply the template of. Defaults to the
+    /// focused workspace.
+    #[clap(long)]
+    workspace: Option<String>,
+  },
+  Center,
+  Close {
+    /// Terminates the window's process if the window is still open and
+    /// not responding after a few seconds.
+    #[clap(long, action)]
+    force: bool,
+  },
+  /// Cycles the share of the focused container within its parent
+  /// through a list of ratios (e.g. 33% -> 50% -> 67%).
+  CycleRatio {
+    /// Ratios to cycle through (e.g. `--ratios 33% 50% 67%`). Defaults
+    /// to `general.cycle_ratios`.
+    #[clap(long, num_args = 1..)]
+    ratios: Option<Vec<LengthValue>>,
+  },
+  /// Closes all windows on the focused workspace.
+  CloseWorkspaceWindows {
+    /// Terminates the processes of windows that are still open and not
+    /// responding after a few seconds.
+    #[clap(long, action)]
+    force: bool,
+  },
   Focus(InvokeFocusCommand),
+  FocusOverflowNext,
+  /// Moves all windows on the focused workspace to their home workspace
+  /// from the window rules.
+  GatherWindows,
+  /// Shows a label over every visible window. Typing a window's label
+  /// focuses it.
+  HintWindows,
+  /// Runs commands depending on the state of the subject container
+  /// (e.g. `if --floating then set-tiling else set-floating`).
+  If(InvokeIfCommand),
   Ignore,
   Move(InvokeMoveCommand),
+  MoveToMonitor {
+    /// Direction of the monitor (e.g. `left`) or its index.
+    #[clap(required = true)]
+    target: MonitorTarget,
+
+    /// Whether to move focus along with the window.
+    #[clap(long, action)]
+    focus: bool,
+  },
   MoveWorkspace {
     #[clap(long)]
     direction: Direction,
   },
+  /// Does nothing. Can be used to block a key from reaching other
+  /// applications.
+  Noop,
+  Overview,
+  Peek,
   Position(InvokePositionCommand),
+  /// Unmanages a window and ignores it from then on. Defaults to the
+  /// subject window.
+  ReleaseWindow(InvokeWindowTargetCommand),
   Resize(InvokeResizeCommand),
+  ResizeTo {
+    #[clap(required = true, allow_hyphen_values = true)]
+    width: LengthValue,
+
+    #[clap(required = true, allow_hyphen_values = true)]
+    height: LengthValue,
+  },
+  /// Focuses the next or previous workspace on a monitor, wrapping
+  /// around at either end. Meant for scroll handlers of bars.
+  ScrollWorkspace {
+    #[clap(required = true, value_enum)]
+    direction: ScrollDirection,
+
+    /// Index of the monitor. Defaults to the focused monitor.
+    #[clap(long)]
+    monitor: Option<usize>,
+  },
+  SelectAdd,
+  SelectClear,
+  SelectRun {
+    #[clap(
+      required = true,
+      trailing_var_arg = true,
+      allow_hyphen_values = true
+    )]
+    command: Vec<String>,
+  },
+  SetBadge {
+    /// Badge to show in place of the window title. Clears the badge if
+    /// omitted.
+    #[clap(trailing_var_arg = true)]
+    badge: Vec<String>,
+  },
+  SetHideMethod {
+    #[clap(required = true, value_enum)]
+    method: HideMethod,
+  },
+  SetGaps {
+    /// Name of the workspace to change gaps for. Defaults to the focused
+    /// workspace.
+    #[clap(long)]
+    workspace: Option<String>,
+
+    /// Gaps to change, of format `<gap>:<length>` (e.g. `outer:0`).
+    #[clap(required = true)]
+    gaps: Vec<GapValue>,
+  },
   SetFloating {
     #[clap(long, default_missing_value = "true", require_equals = true, num_args = 0..=1)]
     shown_on_top: Option<bool>,
@@ -186,6 +617,10 @@ pub enum InvokeCommand {
     #[clap(long, allow_hyphen_values = true)]
     height: Option<LengthValue>,
   },
+  SetFocusAssist {
+    #[clap(required = true, value_enum)]
+    mode: FocusAssistMode,
+  },
   SetFullscreen {
     #[clap(long, default_missing_value = "true", require_equals = true, num_args = 0..=1)]
     shown_on_top: Option<bool>,
@@ -194,12 +629,27 @@ pub enum InvokeCommand {
     maximized: Option<bool>,
   },
   SetMinimized,
+  /// Constrains the size of the subject window when it's tiling. Clears
+  /// the constraints if none are given.
+  SetSizeConstraints(SizeConstraints),
   SetTiling,
   SetTitleBarVisibility {
     #[clap(required = true, value_enum)]
     visibility: TitleBarVisibility,
   },
   SetTransparency(SetTransparencyCommand),
+  /// Moves the window to its home workspace from the window rules.
+  SendHome,
+  SendKeys {
+    /// Key combinations to send in order (e.g. `ctrl+shift+t`).
+    #[clap(required = true, num_args = 1..)]
+    keys: Vec<String>,
+  },
+  ShowKeybindings,
+  ShowWindowSwitcher {
+    #[clap(long, value_enum, default_value_t = WindowSwitcherScope::Workspace)]
+    scope: WindowSwitcherScope,
+  },
   ShellExec {
     #[clap(long, action)]
     hide_window: bool,
@@ -209,6 +659,10 @@ pub enum InvokeCommand {
   },
   // Reuse `InvokeResizeCommand` struct.
   Size(InvokeResizeCommand),
+  Snap {
+    #[clap(required = true, value_enum)]
+    position: SnapPosition,
+  },
   ToggleFloating {
     #[clap(long, default_missing_value = "true", require_equals = true, num_args = 0..=1)]
     shown_on_top: Option<bool>,
@@ -216,6 +670,12 @@ pub enum InvokeCommand {
     #[clap(long, default_missing_value = "true", require_equals = true, num_args = 0..=1)]
     centered: Option<bool>,
   },
+  /// Toggles Focus Assist between off and the given profile.
+  ToggleFocusAssist {
+    /// Profile to enable. Defaults to `priority_only`.
+    #[clap(long, value_enum)]
+    mode: Option<FocusAssistMode>,
+  },
   ToggleFullscreen {
     #[clap(long, default_missing_value = "true", require_equals = true, num_args = 0..=1)]
     shown_on_top: Option<bool>,
@@ -224,12 +684,34 @@ pub enum InvokeCommand {
     maximized: Option<bool>,
   },
   ToggleMinimized,
+  /// Toggles whether the WM manages a monitor. Defaults to the monitor
+  /// under the cursor.
+  ToggleMonitorManagement {
+    /// Index of the monitor, ordered from left-to-right and
+    /// top-to-bottom. Unmanaged monitors are included.
+    #[clap(long)]
+    index: Option<u32>,
+  },
   ToggleTiling,
   ToggleTilingDirection,
+  ToggleWorkspaceLayout,
+  /// Toggles the screen magnifier, zoomed in on the focused window or
+  /// monitor. The magnified region follows focus changes.
+  ToggleZoom {
+    /// Magnification to use. Defaults to `accessibility.zoom_factor`.
+    #[clap(long)]
+    factor: Option<f32>,
+
+    /// What the magnified region follows. Defaults to
+    /// `accessibility.zoom_scope`.
+    #[clap(long, value_enum)]
+    scope: Option<ZoomScope>,
+  },
   SetTilingDirection {
     #[clap(required = true)]
     tiling_direction: TilingDirection,
   },
+  Unpeek,
   WmCycleFocus {
     #[clap(long, default_value_t = false)]
     omit_floating: bool,
@@ -243,7 +725,13 @@ pub enum InvokeCommand {
     #[clap(long, default_value_t = false)]
     omit_tiling: bool,
   },
+  /// Disables the binding mode with the given name, or all active
+  /// binding modes if no name is given.
   WmDisableBindingMode {
+    #[clap(long)]
+    name: Option<String>,
+  },
+  WmDisableTimer {
     #[clap(long)]
     name: String,
   },
@@ -251,10 +739,252 @@ pub enum InvokeCommand {
     #[clap(long)]
     name: String,
   },
+  WmEnableTimer {
+    #[clap(long)]
+    name: String,
+  },
   WmExit,
   WmRedraw,
   WmReloadConfig,
+  /// Runs the commands of a timer immediately, without changing when
+  /// it's next due.
+  WmRunTimer {
+    #[clap(long)]
+    name: String,
+  },
+  WmShowCommandPalette,
+  /// Shows the menu with the given name from the `menus` config.
+  WmShowMenu {
+    #[clap(long)]
+    name: String,
+  },
+  WmToggleBindingMode {
+    #[clap(long)]
+    name: String,
+  },
+  WmToggleMouseEvents,
   WmTogglePause,
+  WmToggleTimer {
+    #[clap(long)]
+    name: String,
+  },
+  WmToggleTrace,
+}
+
+impl InvokeCommand {
+  /// Lists commands that can be run without any further input (e.g.
+  /// `focus --direction left`). Used for the command palette.
+  ///
+  /// Candidates are generated from the CLI definition of each command,
+  /// and only the candidates that parse successfully are kept.
+  #[must_use]
+  pub fn runnable_commands() -> Vec<String> {
+    let mut commands = Vec::new();
+
+    for subcommand in Self::command().get_subcommands() {
+      let name = subcommand.get_name();
+      let mut candidates = vec![name.to_string()];
+
+      for arg in subcommand.get_arguments() {
+        let prefix = match arg.get_long() {
+          Some(long) => format!("{name} --{long}"),
+          None => name.to_string(),
+        };
+
+        if matches!(arg.get_action(), ArgAction::SetTrue) {
+          candidates.push(prefix);
+          continue;
+        }
+
+        let possible_values = arg
+          .get_possible_values()
+          .iter()
+          .map(|value| value.get_name().to_string())
+          .collect::<Vec<_>>();
+
+        // Skip boolean options, since these are variants of commands
+        // that are already listed.
+        if possible_values == ["true", "false"] {
+          continue;
+        }
+
+        // Skip arguments that accept any value (e.g. workspace names).
+        if possible_values.is_empty()
+          && Self::parse_command(&format!("{prefix} _")).is_some()
+        {
+          continue;
+        }
+
+        let values = if possible_values.is_empty() {
+          COMMON_ARG_VALUES.map(ToString::to_string).to_vec()
+        } else {
+          possible_values
+        };
+
+        candidates
+          .extend(values.iter().map(|value| format!("{prefix} {value}")));
+      }
+
+      commands.extend(
+        candidates
+          .into_iter()
+          .filter(|candidate| Self::parse_command(candidate).is_some()),
+      );
+    }
+
+    commands
+  }
+
+  /// Name of the command in its CLI form (e.g. `focus`).
+  #[must_use]
+  pub fn name(&self) -> String {
+    self
+      .to_command_string()
+      .split_whitespace()
+      .next()
+      .unwrap_or_default()
+      .to_string()
+  }
+
+  /// Parses the commands that are run as part of this command (e.g. the
+  /// branches of an `if` command).
+  pub fn nested_commands(&self) -> anyhow::Result<Vec<InvokeCommand>> {
+    match self {
+      InvokeCommand::If(args) => {
+        let (then_commands, else_commands) = args.parse_branches()?;
+        Ok([then_commands, else_commands].concat())
+      }
+      InvokeCommand::SelectRun { command } => {
+        let command = command.join(" ");
+
+        Ok(vec![InvokeCommand::parse_command(&command)
+          .with_context(|| format!("Invalid command '{command}'."))?])
+      }
+      _ => Ok(vec![]),
+    }
+  }
+
+  /// Formats the command in its CLI form (e.g. `focus --workspace 1`).
+  ///
+  /// Used for displaying commands to the user, so the output isn't
+  /// guaranteed to parse back into the same command.
+  #[must_use]
+  pub fn to_command_string(&self) -> String {
+    let (variant, args) = match serde_json::to_value(self) {
+      Ok(serde_json::Value::String(variant)) => (variant, None),
+      Ok(serde_json::Value::Object(object)) => {
+        match object.into_iter().next() {
+          Some((variant, args)) => (variant, Some(args)),
+          None => return String::new(),
+        }
+      }
+      _ => return String::new(),
+    };
+
+    let name = to_kebab_case(&variant);
+    let subcommand = Self::command().find_subcommand(&name).cloned();
+    let mut parts = vec![name];
+
+    // Positional arguments are added last, since trailing arguments
+    // would otherwise consume any flags that follow them.
+    let mut positional_parts = vec![];
+
+    if let Some(serde_json::Value::Object(args)) = args {
+      for (arg_name, value) in args {
+        let is_positional =
+          subcommand.as_ref().is_some_and(|subcommand| {
+            subcommand
+              .get_arguments()
+              .any(|arg| arg.get_id() == &arg_name && arg.is_positional())
+          });
+
+        let flag = format!("--{}", to_kebab_case(&arg_name));
+
+        match value {
+          serde_json::Value::Null | serde_json::Value::Bool(false) => {}
+          serde_json::Value::Bool(true) => parts.push(flag),
+          value if is_positional => {
+            positional_parts.push(format_arg_value(&value));
+          }
+          value => {
+            parts.push(format!("{flag} {}", format_arg_value(&value)));
+          }
+        }
+      }
+    }
+
+    parts.extend(positional_parts);
+    parts.join(" ")
+  }
+
+  /// Parses a command from a string (e.g. `focus --workspace 1`).
+  #[must_use]
+  pub fn parse_command(unparsed: &str) -> Option<Self> {
+    Self::try_parse_from(iter::once("").chain(unparsed.split_whitespace()))
+      .ok()
+  }
+}
+
+/// Converts a `PascalCase` or `snake_case` identifier to `kebab-case`.
+fn to_kebab_case(identifier: &str) -> String {
+  let mut kebab = String::new();
+
+  for (index, char) in identifier.chars().enumerate() {
+    if char == '_' {
+      kebab.push('-');
+    } else if char.is_uppercase() {
+      if index != 0 {
+        kebab.push('-');
+      }
+
+      kebab.extend(char.to_lowercase());
+    } else {
+      kebab.push(char);
+    }
+  }
+
+  kebab
+}
+
+/// Formats a serialized argument value in its CLI form.
+fn format_arg_value(value: &serde_json::Value) -> String {
+  match value {
+    serde_json::Value::String(string) => string.clone(),
+    serde_json::Value::Number(number) => {
+      let number = number.as_f64().unwrap_or_default();
+      format!("{}", (number * 100.0).round() / 100.0)
+    }
+    serde_json::Value::Array(values) => values
+      .iter()
+      .map(format_arg_value)
+      .collect::<Vec<_>>()
+      .join(" "),
+    // Length values (e.g. `50%` or `20px`).
+    serde_json::Value::Object(object)
+      if object.contains_key("amount") && object.contains_key("unit") =>
+    {
+      let amount = object["amount"].as_f64().unwrap_or_default();
+
+      match object["unit"].as_str() {
+        Some("percentage") => format!("{}%", (amount * 100.0).round()),
+        _ => format!("{}px", amount.round()),
+      }
+    }
+    // Deltas (e.g. `+10%` or `-10%`).
+    serde_json::Value::Object(object)
+      if object.contains_key("inner")
+        && object.contains_key("is_negative") =>
+    {
+      let sign = if object["is_negative"].as_bool().unwrap_or_default() {
+        "-"
+      } else {
+        "+"
+      };
+
+      format!("{sign}{}", format_arg_value(&object["inner"]))
+    }
+    value => value.to_string(),
+  }
 }
 
 impl<'de> Deserialize<'de> for InvokeCommand {
@@ -268,14 +998,44 @@ impl<'de> Deserialize<'de> for InvokeCommand {
     let unparsed = String::deserialize(deserializer)?;
     let unparsed_split = iter::once("").chain(unparsed.split_whitespace());
 
-    InvokeCommand::try_parse_from(unparsed_split).map_err(|err| {
-      // Format the error message and remove the "error: " prefix.
-      let err_msg = err.apply::<KindFormatter>().to_string();
-      serde::de::Error::custom(err_msg.trim_start_matches("error: "))
-    })
+    let command =
+      InvokeCommand::try_parse_from(unparsed_split).map_err(|err| {
+        // Format the error message and remove the "error: " prefix.
+        let err_msg = err.apply::<KindFormatter>().to_string();
+        serde::de::Error::custom(err_msg.trim_start_matches("error: "))
+      })?;
+
+    // Validate the branches of conditional commands upfront, so that
+    // errors are surfaced when the config is loaded.
+    if let InvokeCommand::If(if_command) = &command {
+      if_command
+        .parse_branches()
+        .map_err(|err| serde::de::Error::custom(format!("{err:#}")))?;
+    }
+
+    Ok(command)
   }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, ValueEnum)]
+#[clap(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ScrollDirection {
+  Next,
+  Prev,
+}
+
+/// Which windows are listed in the window switcher.
+#[derive(Clone, Debug, PartialEq, Serialize, ValueEnum)]
+#[clap(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WindowSwitcherScope {
+  /// Windows on the focused workspace.
+  Workspace,
+  /// Windows on all workspaces.
+  All,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, ValueEnum)]
 #[clap(rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
@@ -284,6 +1044,48 @@ pub enum TitleBarVisibility {
   Hidden,
 }
 
+/// Area of the workspace to snap a floating window to.
+#[derive(Clone, Debug, PartialEq, Serialize, ValueEnum)]
+#[clap(rename_all = "kebab_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SnapPosition {
+  Left,
+  Right,
+  Top,
+  Bottom,
+  TopLeft,
+  TopRight,
+  BottomLeft,
+  BottomRight,
+  Full,
+}
+
+/// Monitor to move a window to, either by direction from the current
+/// monitor or by its index.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonitorTarget {
+  Direction(Direction),
+  Index(usize),
+}
+
+impl FromStr for MonitorTarget {
+  type Err = anyhow::Error;
+
+  fn from_str(unparsed: &str) -> anyhow::Result<Self> {
+    match unparsed.parse::<usize>() {
+      Ok(index) => Ok(Self::Index(index)),
+      Err(_) => Direction::from_str(unparsed)
+        .map(Self::Direction)
+        .map_err(|_| {
+          anyhow::anyhow!(
+            "Not a valid monitor '{unparsed}'. Must be a direction or a monitor index."
+          )
+        }),
+    }
+  }
+}
+
 #[derive(Args, Clone, Debug, PartialEq, Serialize)]
 #[group(required = true, multiple = true)]
 pub struct InvokeAdjustBordersCommand {
@@ -300,6 +1102,116 @@ pub struct InvokeAdjustBordersCommand {
   pub left: Option<LengthValue>,
 }
 
+#[derive(Args, Clone, Debug, PartialEq, Serialize)]
+#[clap(group(clap::ArgGroup::new("target").multiple(false)))]
+pub struct InvokeWindowTargetCommand {
+  /// Handle of the window, either in decimal or hex (e.g. `0x1a2b`).
+  #[clap(long, group = "target", value_parser = parse_window_handle)]
+  pub hwnd: Option<isize>,
+
+  /// Process name of the window. The topmost matching window is used.
+  #[clap(long, group = "target")]
+  pub process: Option<String>,
+}
+
+/// Parses a window handle in either decimal or hex format.
+fn parse_window_handle(value: &str) -> anyhow::Result<isize> {
+  let handle = match value
+    .strip_prefix("0x")
+    .or_else(|| value.strip_prefix("0X"))
+  {
+    Some(hex) => isize::from_str_radix(hex, 16),
+    None => value.parse(),
+  };
+
+  handle.with_context(|| format!("Invalid window handle '{value}'."))
+}
+
+#[derive(Args, Clone, Debug, PartialEq, Serialize)]
+#[clap(group(
+  clap::ArgGroup::new("condition").required(true).multiple(true)
+))]
+#[allow(clippy::struct_excessive_bools)]
+pub struct InvokeIfCommand {
+  /// Whether the subject container is a floating window.
+  #[clap(long, group = "condition")]
+  pub floating: bool,
+
+  /// Whether the subject container is a tiling window.
+  #[clap(long, group = "condition")]
+  pub tiling: bool,
+
+  /// Whether the subject container is a fullscreen window.
+  #[clap(long, group = "condition")]
+  pub fullscreen: bool,
+
+  /// Whether the subject container is a minimized window.
+  #[clap(long, group = "condition")]
+  pub minimized: bool,
+
+  /// Whether the subject container is a window of the given process.
+  #[clap(long, group = "condition")]
+  pub process: Option<String>,
+
+  /// Inverts the condition.
+  #[clap(long)]
+  pub not: bool,
+
+  /// Commands of format `then <commands> [else <commands>]`. Multiple
+  /// commands in a branch are separated by `;`.
+  #[clap(
+    required = true,
+    trailing_var_arg = true,
+    allow_hyphen_values = true
+  )]
+  pub branches: Vec<String>,
+}
+
+impl InvokeIfCommand {
+  /// Parses the commands to run when the condition is met, and the
+  /// commands to run otherwise.
+  pub fn parse_branches(
+    &self,
+  ) -> anyhow::Result<(Vec<InvokeCommand>, Vec<InvokeCommand>)> {
+    let mut words = self.branches.iter().map(String::as_str);
+
+    if words.next() != Some("then") {
+      bail!("Expected `then` after the condition.");
+    }
+
+    let words = words.collect::<Vec<_>>();
+    let (then_words, else_words) =
+      match words.iter().position(|word| *word == "else") {
+        Some(index) => (&words[..index], &words[index + 1..]),
+        None => (&words[..], &[][..]),
+      };
+
+    if then_words.is_empty() {
+      bail!("Expected a command after `then`.");
+    }
+
+    Ok((
+      Self::parse_commands(then_words)?,
+      Self::parse_commands(else_words)?,
+    ))
+  }
+
+  /// Parses `;`-separated commands.
+  fn parse_commands(words: &[&str]) -> anyhow::Result<Vec<InvokeCommand>> {
+    let joined = words.join(" ");
+
+    joined
+      .split(';')
+      .map(str::trim)
+      .filter(|command| !command.is_empty())
+      .map(|command| {
+        InvokeCommand::parse_command(command)
+          .with_context(|| format!("Invalid command '{command}'."))
+      })
+      .collect()
+  }
+}
+
 #[derive(Args, Clone, Debug, PartialEq, Serialize)]
 #[group(required = true, multiple = false)]
 #[allow(clippy::struct_excessive_bools)]
@@ -339,6 +1251,14 @@ pub struct InvokeFocusCommand {
 
   #[clap(long)]
   pub recent_workspace: bool,
+
+  /// Focus the parent split container as a whole.
+  #[clap(long)]
+  pub parent: bool,
+
+  /// Focus the most recently focused child of a focused split container.
+  #[clap(long)]
+  pub child: bool,
 }
 
 #[derive(Args, Clone, Debug, PartialEq, Serialize)]
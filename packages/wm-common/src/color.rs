@@ -3,7 +3,7 @@ use std::str::FromStr;
 use anyhow::bail;
 use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Color {
   pub r: u8,
   pub g: u8,
@@ -0,0 +1,123 @@
+use std::{
+  env, fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{apply_config_wizard, run_config_wizard};
+
+/// Resource string for the sample config file.
+pub const SAMPLE_CONFIG: &str =
+  include_str!("../../../resources/assets/sample-config.yaml");
+
+/// File format of the user config.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConfigFormat {
+  Yaml,
+  Toml,
+  Json,
+}
+
+impl ConfigFormat {
+  /// Gets the config format based on the file extension of the given
+  /// path. Defaults to YAML for unrecognized extensions.
+  #[must_use]
+  pub fn from_path(path: &Path) -> Self {
+    match path
+      .extension()
+      .and_then(|ext| ext.to_str())
+      .map(str::to_lowercase)
+      .as_deref()
+    {
+      Some("toml") => ConfigFormat::Toml,
+      Some("json") => ConfigFormat::Json,
+      _ => ConfigFormat::Yaml,
+    }
+  }
+
+  /// Deserializes a value from a string in this format.
+  pub fn parse<T>(self, value_str: &str) -> anyhow::Result<T>
+  where
+    T: DeserializeOwned,
+  {
+    // TODO: Improve error formatting of serde_yaml errors. Something
+    // similar to https://github.com/AlexanderThaller/format_serde_error
+    let value = match self {
+      ConfigFormat::Yaml => serde_yaml::from_str(value_str)?,
+      ConfigFormat::Toml => toml::from_str(value_str)?,
+      ConfigFormat::Json => serde_json::from_str(value_str)?,
+    };
+
+    Ok(value)
+  }
+
+  /// Serializes a value to a string in this format.
+  pub fn serialize<T>(self, value: &T) -> anyhow::Result<String>
+  where
+    T: Serialize,
+  {
+    let value_str = match self {
+      ConfigFormat::Yaml => serde_yaml::to_string(value)?,
+      ConfigFormat::Toml => toml::to_string_pretty(value)?,
+      ConfigFormat::Json => serde_json::to_string_pretty(value)?,
+    };
+
+    Ok(value_str)
+  }
+}
+
+/// Resolves the path to the user config.
+///
+/// Uses the given path if provided, followed by the `GLAZEWM_CONFIG_PATH`
+/// environment variable, and lastly the default path of
+/// `%userprofile%/.glzr/glazewm/config.yaml`.
+pub fn resolve_config_path(
+  config_path: Option<PathBuf>,
+) -> anyhow::Result<PathBuf> {
+  if let Some(config_path) = config_path
+    .or_else(|| env::var("GLAZEWM_CONFIG_PATH").ok().map(PathBuf::from))
+  {
+    return Ok(config_path);
+  }
+
+  let default_config_path = home::home_dir()
+    .context("Unable to get home directory.")?
+    .join(".glzr/glazewm/config.yaml");
+
+  Ok(default_config_path)
+}
+
+/// Initializes a new config file at the given path.
+///
+/// When running in an interactive terminal, the user is prompted for a
+/// few options to tailor the sample config. Otherwise, the sample config
+/// is written as-is. The sample config is converted to TOML or JSON if
+/// the config path has a matching file extension (comments are lost in
+/// the conversion).
+pub fn create_config_file(config_path: &Path) -> anyhow::Result<()> {
+  let parent_dir = config_path.parent().context("Invalid config path.")?;
+
+  fs::create_dir_all(parent_dir).with_context(|| {
+    format!("Unable to create directory {}.", config_path.display())
+  })?;
+
+  let sample_config = match run_config_wizard()? {
+    Some(options) => apply_config_wizard(SAMPLE_CONFIG, &options),
+    None => SAMPLE_CONFIG.to_string(),
+  };
+
+  let config_str = match ConfigFormat::from_path(config_path) {
+    ConfigFormat::Yaml => sample_config,
+    format => format.serialize(
+      &ConfigFormat::Yaml.parse::<serde_yaml::Value>(&sample_config)?,
+    )?,
+  };
+
+  fs::write(config_path, config_str).with_context(|| {
+    format!("Unable to write to {}.", config_path.display())
+  })?;
+
+  Ok(())
+}
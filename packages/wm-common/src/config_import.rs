@@ -0,0 +1,38 @@
+use serde_yaml::{Mapping, Value};
+
+/// Result of converting the config of another window manager to a user
+/// config.
+#[derive(Clone, Debug)]
+pub struct ConfigImport {
+  /// Generated user config.
+  pub config: Value,
+
+  /// Options that couldn't be converted, along with where they're
+  /// located in the original config.
+  pub unsupported: Vec<String>,
+
+  /// Conversions that are approximate and should be checked by hand.
+  pub notes: Vec<String>,
+}
+
+/// Gets the name to use for a workspace or binding mode. Commands are
+/// split on whitespace, so whitespace in imported names is replaced.
+pub(crate) fn command_name(name: &str) -> String {
+  name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+/// Creates a mapping with the given `name`. The original name is kept as
+/// the `display_name` if it isn't usable in commands.
+pub(crate) fn named_mapping(name: &str) -> Mapping {
+  let mut mapping = Mapping::new();
+  let command_name = command_name(name);
+  let is_renamed = command_name != name;
+
+  mapping.insert("name".into(), command_name.into());
+
+  if is_renamed {
+    mapping.insert("display_name".into(), name.into());
+  }
+
+  mapping
+}
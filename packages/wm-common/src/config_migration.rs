@@ -0,0 +1,572 @@
+use std::{
+  fs, iter,
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use clap::Parser;
+use serde_yaml::{Mapping, Value};
+
+use crate::{app_command::InvokeCommand, ConfigFormat};
+
+/// Result of migrating a user config file.
+#[derive(Clone, Debug)]
+pub struct ConfigMigration {
+  /// Human-readable descriptions of the changes that were applied.
+  pub changes: Vec<String>,
+
+  /// Path to the backup of the original config. This is `None` if the
+  /// config was already up to date.
+  pub backup_path: Option<PathBuf>,
+}
+
+/// Whether the config at the given path uses keys or structures from an
+/// older config schema.
+pub fn is_config_outdated(config_path: &Path) -> anyhow::Result<bool> {
+  let mut config = read_config_value(config_path)?;
+  Ok(!migrate_config_value(&mut config).is_empty())
+}
+
+/// Rewrites the config at the given path to the current config schema.
+///
+/// A backup of the original file is written next to it before any
+/// changes are made. Note that comments are not preserved.
+pub fn migrate_config_file(
+  config_path: &Path,
+) -> anyhow::Result<ConfigMigration> {
+  let mut config = read_config_value(config_path)?;
+  let changes = migrate_config_value(&mut config);
+
+  if changes.is_empty() {
+    return Ok(ConfigMigration {
+      changes,
+      backup_path: None,
+    });
+  }
+
+  let backup_path = next_backup_path(config_path);
+  fs::copy(config_path, &backup_path).with_context(|| {
+    format!("Unable to write backup to {}.", backup_path.display())
+  })?;
+
+  let config_str =
+    ConfigFormat::from_path(config_path).serialize(&config)?;
+  fs::write(config_path, config_str).with_context(|| {
+    format!("Unable to write to {}.", config_path.display())
+  })?;
+
+  Ok(ConfigMigration {
+    changes,
+    backup_path: Some(backup_path),
+  })
+}
+
+/// Migrates a parsed config value in-place to the current config schema.
+///
+/// Returns descriptions of the changes that were applied.
+#[must_use]
+pub fn migrate_config_value(config: &mut Value) -> Vec<String> {
+  let mut changes = Vec::new();
+
+  let Some(config) = config.as_mapping_mut() else {
+    return changes;
+  };
+
+  migrate_general(config, &mut changes);
+  migrate_gaps(config, &mut changes);
+  migrate_focus_borders(config, &mut changes);
+
+  if config.remove("bar").is_some() {
+    changes.push(
+      "Removed `bar` (the built-in bar was replaced by Zebar).".into(),
+    );
+  }
+
+  if let Some(keybindings) = config.get_mut("keybindings") {
+    migrate_keybindings(keybindings, "keybindings", &mut changes);
+  }
+
+  if let Some(Value::Sequence(binding_modes)) =
+    config.get_mut("binding_modes")
+  {
+    for (index, binding_mode) in binding_modes.iter_mut().enumerate() {
+      if let Some(keybindings) = binding_mode.get_mut("keybindings") {
+        migrate_keybindings(
+          keybindings,
+          &format!("binding_modes[{index}].keybindings"),
+          &mut changes,
+        );
+      }
+    }
+  }
+
+  if let Some(Value::Sequence(window_rules)) =
+    config.get_mut("window_rules")
+  {
+    for (index, window_rule) in window_rules.iter_mut().enumerate() {
+      if let Some(window_rule) = window_rule.as_mapping_mut() {
+        migrate_window_rule(
+          window_rule,
+          &format!("window_rules[{index}]"),
+          &mut changes,
+        );
+      }
+    }
+  }
+
+  changes
+}
+
+fn migrate_general(config: &mut Mapping, changes: &mut Vec<String>) {
+  let Some(general) =
+    config.get_mut("general").and_then(Value::as_mapping_mut)
+  else {
+    return;
+  };
+
+  if let Some(enabled) = general.remove("cursor_follows_focus") {
+    if !general.contains_key("cursor_jump") {
+      let mut cursor_jump = Mapping::new();
+      cursor_jump.insert("enabled".into(), enabled);
+      cursor_jump.insert("trigger".into(), "window_focus".into());
+      general.insert("cursor_jump".into(), cursor_jump.into());
+    }
+
+    changes.push(
+      "Replaced `general.cursor_follows_focus` with `general.cursor_jump`."
+        .into(),
+    );
+  }
+
+  for key in ["floating_window_move_amount", "window_animations"] {
+    if general.remove(key).is_some() {
+      changes.push(format!("Removed unsupported `general.{key}`."));
+    }
+  }
+
+  for command_key in [
+    "startup_commands",
+    "shutdown_commands",
+    "config_reload_commands",
+  ] {
+    if let Some(commands) = general.get_mut(command_key) {
+      migrate_commands(
+        commands,
+        &format!("general.{command_key}"),
+        changes,
+      );
+    }
+  }
+
+  // Floating defaults were previously set under `general`.
+  let floating_defaults = [
+    ("show_floating_on_top", "shown_on_top"),
+    ("center_new_floating_windows", "centered"),
+  ]
+  .into_iter()
+  .filter_map(|(old_key, new_key)| {
+    general
+      .remove(old_key)
+      .map(|value| (old_key, new_key, value))
+  })
+  .collect::<Vec<_>>();
+
+  for (old_key, new_key, value) in floating_defaults {
+    nested_mapping(
+      config,
+      &["window_behavior", "state_defaults", "floating"],
+    )
+    .entry(new_key.into())
+    .or_insert(value);
+
+    changes.push(format!(
+      "Moved `general.{old_key}` to \
+       `window_behavior.state_defaults.floating.{new_key}`."
+    ));
+  }
+}
+
+fn migrate_gaps(config: &mut Mapping, changes: &mut Vec<String>) {
+  let Some(gaps) = config.get_mut("gaps").and_then(Value::as_mapping_mut)
+  else {
+    return;
+  };
+
+  if let Some(inner_gap) = gaps.get_mut("inner_gap") {
+    if let Some(px) = inner_gap.as_i64() {
+      *inner_gap = format!("{px}px").into();
+      changes.push("Converted `gaps.inner_gap` to a length value.".into());
+    }
+  }
+
+  if let Some(outer_gap) = gaps.get_mut("outer_gap") {
+    let shorthand = match outer_gap {
+      Value::Number(number) => Some(format!("{number}px")),
+      Value::String(string) => Some(string.clone()),
+      _ => None,
+    };
+
+    if let Some(shorthand) = shorthand {
+      if let Some(sides) = expand_shorthand(&shorthand) {
+        let mut outer_gap_map = Mapping::new();
+
+        for (side, value) in
+          ["top", "right", "bottom", "left"].into_iter().zip(sides)
+        {
+          outer_gap_map.insert(side.into(), value.into());
+        }
+
+        *outer_gap = outer_gap_map.into();
+        changes.push(
+          "Expanded `gaps.outer_gap` into `top`, `right`, `bottom`, and \
+           `left` values."
+            .into(),
+        );
+      }
+    }
+  }
+}
+
+/// Expands a CSS-style shorthand (e.g. `10px 20px`) into top, right,
+/// bottom, and left values.
+fn expand_shorthand(shorthand: &str) -> Option<[String; 4]> {
+  let parts = shorthand
+    .split_whitespace()
+    .map(|part| {
+      if part
+        .chars()
+        .all(|char| char.is_ascii_digit() || char == '-')
+      {
+        format!("{part}px")
+      } else {
+        part.to_string()
+      }
+    })
+    .collect::<Vec<_>>();
+
+  match parts.as_slice() {
+    [all] => Some([all.clone(), all.clone(), all.clone(), all.clone()]),
+    [y, x] => Some([y.clone(), x.clone(), y.clone(), x.clone()]),
+    [top, x, bottom] => {
+      Some([top.clone(), x.clone(), bottom.clone(), x.clone()])
+    }
+    [top, right, bottom, left] => {
+      Some([top.clone(), right.clone(), bottom.clone(), left.clone()])
+    }
+    _ => None,
+  }
+}
+
+fn migrate_focus_borders(config: &mut Mapping, changes: &mut Vec<String>) {
+  let Some(Value::Mapping(focus_borders)) = config.remove("focus_borders")
+  else {
+    return;
+  };
+
+  let border_targets =
+    [("active", "focused_window"), ("inactive", "other_windows")];
+
+  for (old_key, new_key) in border_targets {
+    if let Some(border) = focus_borders.get(old_key) {
+      let target = nested_mapping(config, &["window_effects", new_key]);
+
+      if !target.contains_key("border") {
+        target.insert("border".into(), border.clone());
+      }
+    }
+  }
+
+  changes
+    .push("Moved `focus_borders` to `window_effects.*.border`.".into());
+}
+
+fn migrate_keybindings(
+  keybindings: &mut Value,
+  path: &str,
+  changes: &mut Vec<String>,
+) {
+  let Some(keybindings) = keybindings.as_sequence_mut() else {
+    return;
+  };
+
+  for (index, keybinding) in keybindings.iter_mut().enumerate() {
+    let Some(keybinding) = keybinding.as_mapping_mut() else {
+      continue;
+    };
+
+    let path = format!("{path}[{index}]");
+
+    if rename_to_list(keybinding, "command", "commands") {
+      changes.push(format!("Renamed `{path}.command` to `commands`."));
+    }
+
+    if rename_to_list(keybinding, "binding", "bindings") {
+      changes.push(format!("Renamed `{path}.binding` to `bindings`."));
+    }
+
+    if let Some(commands) = keybinding.get_mut("commands") {
+      migrate_commands(commands, &format!("{path}.commands"), changes);
+    }
+  }
+}
+
+fn migrate_window_rule(
+  window_rule: &mut Mapping,
+  path: &str,
+  changes: &mut Vec<String>,
+) {
+  if rename_to_list(window_rule, "command", "commands") {
+    changes.push(format!("Renamed `{path}.command` to `commands`."));
+  }
+
+  let match_keys = [
+    ("match_process_name", "window_process"),
+    ("match_class_name", "window_class"),
+    ("match_title", "window_title"),
+  ];
+
+  let mut match_config = Mapping::new();
+
+  for (old_key, new_key) in match_keys {
+    if let Some(Value::String(pattern)) = window_rule.remove(old_key) {
+      match_config.insert(new_key.into(), match_type_value(&pattern));
+    }
+  }
+
+  if !match_config.is_empty() && !window_rule.contains_key("match") {
+    window_rule
+      .insert("match".into(), Value::Sequence(vec![match_config.into()]));
+
+    changes.push(format!(
+      "Replaced `{path}.match_*` properties with `match`."
+    ));
+  }
+
+  if let Some(commands) = window_rule.get_mut("commands") {
+    migrate_commands(commands, &format!("{path}.commands"), changes);
+  }
+}
+
+/// Converts a legacy match pattern to a match type. Patterns wrapped in
+/// forward slashes (e.g. `/chrome|msedge/`) were treated as regexes.
+fn match_type_value(pattern: &str) -> Value {
+  let mut match_type = Mapping::new();
+
+  match pattern
+    .strip_prefix('/')
+    .and_then(|pattern| pattern.strip_suffix('/'))
+  {
+    Some(regex) => match_type.insert("regex".into(), regex.into()),
+    None => match_type.insert("equals".into(), pattern.into()),
+  };
+
+  match_type.into()
+}
+
+/// Renames a key that previously held a single string to a key holding a
+/// list of strings.
+///
+/// Returns `true` if the key was renamed.
+fn rename_to_list(
+  map: &mut Mapping,
+  old_key: &str,
+  new_key: &str,
+) -> bool {
+  if map.contains_key(new_key) {
+    return false;
+  }
+
+  match map.remove(old_key) {
+    Some(value @ Value::Sequence(_)) => {
+      map.insert(new_key.into(), value);
+      true
+    }
+    Some(value) => {
+      map.insert(new_key.into(), Value::Sequence(vec![value]));
+      true
+    }
+    None => false,
+  }
+}
+
+fn migrate_commands(
+  commands: &mut Value,
+  path: &str,
+  changes: &mut Vec<String>,
+) {
+  let Some(commands) = commands.as_sequence_mut() else {
+    return;
+  };
+
+  for command in commands.iter_mut() {
+    let Some(command_str) = command.as_str() else {
+      continue;
+    };
+
+    if let Some(migrated) = migrate_command(command_str) {
+      changes.push(format!(
+        "Rewrote command `{command_str}` in `{path}` to `{migrated}`."
+      ));
+
+      *command = migrated.into();
+    }
+  }
+}
+
+/// Rewrites a command using the legacy syntax (e.g. `focus workspace 1`)
+/// to the current syntax (e.g. `focus --workspace 1`).
+///
+/// Keywords are matched case-insensitively, whereas names of workspaces
+/// and binding modes keep their casing.
+///
+/// Returns `None` if the command is already valid or isn't recognized.
+fn migrate_command(command: &str) -> Option<String> {
+  let is_valid = InvokeCommand::try_parse_from(
+    iter::once("").chain(command.split_whitespace()),
+  )
+  .is_ok();
+
+  if is_valid {
+    return None;
+  }
+
+  let parts = command.split_whitespace().collect::<Vec<_>>();
+  let keywords = parts
+    .iter()
+    .map(|part| part.to_lowercase())
+    .collect::<Vec<_>>();
+
+  let keywords = keywords.iter().map(String::as_str).collect::<Vec<_>>();
+
+  let migrated = match keywords.as_slice() {
+    ["focus", direction @ ("left" | "right" | "up" | "down")] => {
+      format!("focus --direction {direction}")
+    }
+    ["focus", "workspace", "next"] => "focus --next-workspace".into(),
+    ["focus", "workspace", "prev" | "previous"] => {
+      "focus --prev-workspace".into()
+    }
+    ["focus", "workspace", "recent"] => "focus --recent-workspace".into(),
+    ["focus", "workspace", _] => format!("focus --workspace {}", parts[2]),
+    ["focus", "mode", "toggle"] => "wm-cycle-focus".into(),
+    ["move", direction @ ("left" | "right" | "up" | "down")] => {
+      format!("move --direction {direction}")
+    }
+    ["move", "to", "workspace", _] => {
+      format!("move --workspace {}", parts[3])
+    }
+    ["move", "workspace", direction] => {
+      format!("move-workspace --direction {direction}")
+    }
+    ["resize", dimension @ ("width" | "height"), amount] => {
+      format!("resize --{dimension} {amount}")
+    }
+    ["set", "floating"] => "set-floating".into(),
+    ["set", "tiling"] => "set-tiling".into(),
+    ["set", "minimized"] => "set-minimized".into(),
+    ["set", "maximized"] => "set-fullscreen".into(),
+    ["toggle", "floating"] => "toggle-floating".into(),
+    ["toggle", "minimized"] => "toggle-minimized".into(),
+    ["toggle", "maximized"] => "toggle-fullscreen".into(),
+    ["toggle", "tiling", "direction"]
+    | ["tiling", "direction", "toggle"] => {
+      "toggle-tiling-direction".into()
+    }
+    ["tiling", "direction", direction @ ("horizontal" | "vertical")] => {
+      format!("set-tiling-direction {direction}")
+    }
+    ["binding", "mode", name] if *name != "none" => {
+      format!("wm-enable-binding-mode --name {}", parts[2])
+    }
+    ["exit", "wm"] => "wm-exit".into(),
+    ["reload", "config"] => "wm-reload-config".into(),
+    ["redraw"] => "wm-redraw".into(),
+    ["exec", ..] => {
+      // Preserve the casing of the original program and arguments.
+      let (_, program) = command.trim_start().split_at(4);
+      format!("shell-exec {}", program.trim_start())
+    }
+    _ => return None,
+  };
+
+  Some(migrated)
+}
+
+/// Gets a nested mapping by its path of keys, creating any missing
+/// mappings along the way.
+fn nested_mapping<'a>(
+  config: &'a mut Mapping,
+  keys: &[&str],
+) -> &'a mut Mapping {
+  keys.iter().fold(config, |map, key| {
+    let entry = map
+      .entry((*key).into())
+      .or_insert_with(|| Mapping::new().into());
+
+    if !entry.is_mapping() {
+      *entry = Mapping::new().into();
+    }
+
+    entry.as_mapping_mut().expect("Entry is a mapping.")
+  })
+}
+
+fn read_config_value(config_path: &Path) -> anyhow::Result<Value> {
+  let config_str = fs::read_to_string(config_path)
+    .context("Unable to read config file.")?;
+
+  ConfigFormat::from_path(config_path).parse(&config_str)
+}
+
+/// Gets an unused path for a backup of the given config file (e.g.
+/// `config.yaml.bak`, `config.yaml.bak1`, etc.).
+fn next_backup_path(config_path: &Path) -> PathBuf {
+  let mut backup_path = config_path.as_os_str().to_owned();
+  backup_path.push(".bak");
+
+  let mut index = 1;
+  let mut candidate = PathBuf::from(&backup_path);
+
+  while candidate.exists() {
+    let mut numbered_path = backup_path.clone();
+    numbered_path.push(index.to_string());
+    candidate = PathBuf::from(numbered_path);
+    index += 1;
+  }
+
+  candidate
+}
+
+#[cfg(test)]
+mod tests {
+  use super::migrate_command;
+
+  #[test]
+  fn keeps_casing_of_names() {
+    assert_eq!(
+      migrate_command("Focus Workspace Web").as_deref(),
+      Some("focus --workspace Web")
+    );
+    assert_eq!(
+      migrate_command("move to workspace Code").as_deref(),
+      Some("move --workspace Code")
+    );
+    assert_eq!(
+      migrate_command("BINDING MODE Resize").as_deref(),
+      Some("wm-enable-binding-mode --name Resize")
+    );
+  }
+
+  #[test]
+  fn matches_keywords_case_insensitively() {
+    assert_eq!(
+      migrate_command("Focus Left").as_deref(),
+      Some("focus --direction left")
+    );
+    assert_eq!(
+      migrate_command("Focus Workspace Next").as_deref(),
+      Some("focus --next-workspace")
+    );
+    assert_eq!(migrate_command("Binding Mode None"), None);
+  }
+}
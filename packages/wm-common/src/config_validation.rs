@@ -0,0 +1,365 @@
+use std::collections::HashSet;
+
+use crate::{
+  InvokeCommand, KeybindingConfig, LengthUnit, MatchType, MenuItemConfig,
+  ParsedConfig, WorkspaceTemplateConfig,
+};
+
+/// Checks a parsed user config for problems that aren't caught during
+/// deserialization.
+///
+/// The `is_valid_key` callback is used to check whether a key name in a
+/// keybinding is recognized, since this depends on the platform and the
+/// current keyboard layout.
+///
+/// Returns a list of diagnostics, which is empty if the config is valid.
+pub fn validate_config(
+  config: &ParsedConfig,
+  is_valid_key: impl Fn(&str) -> bool,
+) -> Vec<String> {
+  let mut diagnostics = Vec::new();
+
+  validate_keybindings(
+    "keybindings",
+    &config.keybindings,
+    &is_valid_key,
+    &mut diagnostics,
+  );
+
+  let mut binding_mode_names = HashSet::new();
+
+  for (index, binding_mode) in config.binding_modes.iter().enumerate() {
+    let path = format!("binding_modes[{index}]");
+
+    if !binding_mode_names.insert(&binding_mode.name) {
+      diagnostics.push(format!(
+        "{path}.name: Duplicate binding mode name '{}'.",
+        binding_mode.name
+      ));
+    }
+
+    validate_keybindings(
+      &format!("{path}.keybindings"),
+      &binding_mode.keybindings,
+      &is_valid_key,
+      &mut diagnostics,
+    );
+  }
+
+  let mut workspace_names = HashSet::new();
+
+  for (index, workspace) in config.workspaces.iter().enumerate() {
+    if !workspace_names.insert(&workspace.name) {
+      diagnostics.push(format!(
+        "workspaces[{index}].name: Duplicate workspace name '{}'.",
+        workspace.name
+      ));
+    }
+  }
+
+  let mut timer_names = HashSet::new();
+
+  for (index, timer) in config.timers.iter().enumerate() {
+    let path = format!("timers[{index}]");
+
+    if !timer_names.insert(&timer.name) {
+      diagnostics.push(format!(
+        "{path}.name: Duplicate timer name '{}'.",
+        timer.name
+      ));
+    }
+
+    if timer.commands.is_empty() {
+      diagnostics.push(format!("{path}.commands: No commands specified."));
+    }
+
+    if timer.interval_secs == Some(0) {
+      diagnostics.push(format!(
+        "{path}.interval_secs: Interval must be greater than 0."
+      ));
+    }
+
+    if timer.interval_secs.is_none() && timer.at.is_empty() {
+      diagnostics.push(format!(
+        "{path}: Either `interval_secs` or `at` must be specified."
+      ));
+    }
+  }
+
+  validate_workspace_templates(config, &mut diagnostics);
+  validate_accessibility(config, &mut diagnostics);
+  validate_controllers(config, &mut diagnostics);
+  validate_menus(config, &mut diagnostics);
+
+  for (rule_index, rule) in config.window_rules.iter().enumerate() {
+    let path = format!("window_rules[{rule_index}]");
+
+    // Rules that only define a home workspace don't need commands.
+    if rule.commands.is_empty() && rule.home_workspace.is_none() {
+      diagnostics.push(format!("{path}.commands: No commands specified."));
+    }
+
+    if let Some(home_workspace) = &rule.home_workspace {
+      if !workspace_names.contains(home_workspace) {
+        diagnostics.push(format!(
+          "{path}.home_workspace: Workspace '{home_workspace}' doesn't exist."
+        ));
+      }
+    }
+
+    if rule.match_window.is_empty() {
+      diagnostics.push(format!("{path}.match: No match specified."));
+    }
+
+    for (match_index, match_window) in rule.match_window.iter().enumerate()
+    {
+      let match_types = [
+        ("window_process", &match_window.window_process),
+        ("window_class", &match_window.window_class),
+        ("window_title", &match_window.window_title),
+      ];
+
+      for (name, match_type) in match_types {
+        let pattern = match match_type {
+          Some(MatchType::Regex { regex }) => regex,
+          Some(MatchType::NotRegex { not_regex }) => not_regex,
+          _ => continue,
+        };
+
+        if let Err(err) = regex::Regex::new(pattern) {
+          diagnostics.push(format!(
+            "{path}.match[{match_index}].{name}: Invalid regex '{pattern}'. {err}"
+          ));
+        }
+      }
+    }
+  }
+
+  diagnostics
+}
+
+/// Checks that the keybindings have valid key combinations and at least
+/// one command.
+fn validate_keybindings(
+  path: &str,
+  keybindings: &[KeybindingConfig],
+  is_valid_key: &impl Fn(&str) -> bool,
+  diagnostics: &mut Vec<String>,
+) {
+  for (index, keybinding) in keybindings.iter().enumerate() {
+    let path = format!("{path}[{index}]");
+
+    if keybinding.commands.is_empty() {
+      diagnostics.push(format!("{path}.commands: No commands specified."));
+    }
+
+    if keybinding.bindings.is_empty() {
+      diagnostics.push(format!("{path}.bindings: No bindings specified."));
+    }
+
+    for binding in &keybinding.bindings {
+      for key in binding.split('+') {
+        if key.trim().is_empty() {
+          diagnostics.push(format!(
+            "{path}.bindings: Empty key in binding '{binding}'."
+          ));
+        } else if !is_valid_key(key) {
+          diagnostics.push(format!(
+            "{path}.bindings: Unrecognized key '{key}' in binding \
+             '{binding}'. Ensure that alt or shift isn't required for \
+             the key."
+          ));
+        }
+      }
+    }
+  }
+}
+
+/// Checks the menus config for duplicate names and problems in the
+/// menus' items.
+fn validate_menus(config: &ParsedConfig, diagnostics: &mut Vec<String>) {
+  let mut menu_names = HashSet::new();
+
+  for (index, menu) in config.menus.iter().enumerate() {
+    let path = format!("menus[{index}]");
+
+    if !menu_names.insert(&menu.name) {
+      diagnostics.push(format!(
+        "{path}.name: Duplicate menu name '{}'.",
+        menu.name
+      ));
+    }
+
+    validate_menu_items(
+      &format!("{path}.items"),
+      &menu.items,
+      diagnostics,
+    );
+  }
+}
+
+/// Checks the items of a menu and its nested menus for duplicate keys,
+/// invalid commands, and items that do nothing.
+fn validate_menu_items(
+  path: &str,
+  items: &[MenuItemConfig],
+  diagnostics: &mut Vec<String>,
+) {
+  let mut keys = HashSet::new();
+
+  for (index, item) in items.iter().enumerate() {
+    let path = format!("{path}[{index}]");
+
+    if !keys.insert(item.key) {
+      diagnostics
+        .push(format!("{path}.key: Duplicate key '{}'.", item.key));
+    }
+
+    if item.commands.is_empty() && item.items.is_empty() {
+      diagnostics.push(format!(
+        "{path}: Either `commands` or `items` must be specified."
+      ));
+    }
+
+    for command in &item.commands {
+      if InvokeCommand::parse_command(command).is_none() {
+        diagnostics
+          .push(format!("{path}.commands: Invalid command '{command}'."));
+      }
+    }
+
+    validate_menu_items(
+      &format!("{path}.items"),
+      &item.items,
+      diagnostics,
+    );
+  }
+}
+
+/// Checks the templates of all workspaces.
+fn validate_workspace_templates(
+  config: &ParsedConfig,
+  diagnostics: &mut Vec<String>,
+) {
+  for (index, workspace) in config.workspaces.iter().enumerate() {
+    if let Some(template) = &workspace.template {
+      validate_workspace_template(
+        &format!("workspaces[{index}].template"),
+        template,
+        diagnostics,
+      );
+    }
+  }
+}
+
+/// Checks a workspace template and its children for empty slots and
+/// sizes that aren't percentages.
+fn validate_workspace_template(
+  path: &str,
+  template: &WorkspaceTemplateConfig,
+  diagnostics: &mut Vec<String>,
+) {
+  if template
+    .size
+    .as_ref()
+    .is_some_and(|size| size.unit != LengthUnit::Percentage)
+  {
+    diagnostics.push(format!("{path}.size: Size must be a percentage."));
+  }
+
+  if template.children.is_empty() && template.match_window.is_empty() {
+    diagnostics.push(format!(
+      "{path}: Either `match` or `children` must be specified."
+    ));
+  }
+
+  if !template.children.is_empty() && !template.match_window.is_empty() {
+    diagnostics.push(format!(
+      "{path}: Only one of `match` or `children` can be specified."
+    ));
+  }
+
+  for (index, child) in template.children.iter().enumerate() {
+    validate_workspace_template(
+      &format!("{path}.children[{index}]"),
+      child,
+      diagnostics,
+    );
+  }
+}
+
+/// Checks the controllers config for mappings without commands, and for
+/// MIDI channels, notes and controls that are out of range.
+fn validate_accessibility(
+  config: &ParsedConfig,
+  diagnostics: &mut Vec<String>,
+) {
+  if config.general.accessibility.zoom_factor < 1. {
+    diagnostics.push(
+      "general.accessibility.zoom_factor: Zoom factor must be at least 1."
+        .to_string(),
+    );
+  }
+}
+
+fn validate_controllers(
+  config: &ParsedConfig,
+  diagnostics: &mut Vec<String>,
+) {
+  for (index, controller) in config.controllers.hid.iter().enumerate() {
+    for (button_index, button) in controller.buttons.iter().enumerate() {
+      let path =
+        format!("controllers.hid[{index}].buttons[{button_index}]");
+
+      if button.commands.is_empty() {
+        diagnostics
+          .push(format!("{path}.commands: No commands specified."));
+      }
+
+      if button.mask == 0 {
+        diagnostics.push(format!("{path}.mask: Mask must not be 0."));
+      }
+    }
+  }
+
+  for (index, controller) in config.controllers.midi.iter().enumerate() {
+    let path = format!("controllers.midi[{index}]");
+
+    let channels = controller
+      .notes
+      .iter()
+      .map(|note| ("notes", note.channel))
+      .chain(
+        controller
+          .controls
+          .iter()
+          .map(|control| ("controls", control.channel)),
+      );
+
+    for (name, channel) in channels {
+      if channel.is_some_and(|channel| !(1..=16).contains(&channel)) {
+        diagnostics.push(format!(
+          "{path}.{name}: Channel must be between 1 and 16."
+        ));
+      }
+    }
+
+    for note in &controller.notes {
+      if note.note > 127 {
+        diagnostics.push(format!(
+          "{path}.notes: Note {} must be between 0 and 127.",
+          note.note
+        ));
+      }
+    }
+
+    for control in &controller.controls {
+      if control.control > 127 {
+        diagnostics.push(format!(
+          "{path}.controls: Control {} must be between 0 and 127.",
+          control.control
+        ));
+      }
+    }
+  }
+}
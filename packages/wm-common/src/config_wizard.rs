@@ -0,0 +1,185 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Options chosen in the first-run config wizard.
+#[derive(Clone, Debug)]
+pub struct ConfigWizardOptions {
+  /// Modifier key used for keybindings (e.g. `alt`, `lwin`).
+  pub mod_key: String,
+
+  /// Number of workspaces to define (between 1 and 9).
+  pub workspace_count: u32,
+
+  /// Whether to have gaps between windows and the screen edge.
+  pub gaps_enabled: bool,
+
+  /// Whether to automatically focus windows underneath the cursor.
+  pub focus_follows_cursor: bool,
+}
+
+impl Default for ConfigWizardOptions {
+  fn default() -> Self {
+    Self {
+      mod_key: "alt".to_string(),
+      workspace_count: 9,
+      gaps_enabled: true,
+      focus_follows_cursor: false,
+    }
+  }
+}
+
+/// Interactively prompts for config options via stdin.
+///
+/// Returns `None` if stdin or stdout is not an interactive terminal
+/// (e.g. when launched without a console window).
+pub fn run_config_wizard() -> anyhow::Result<Option<ConfigWizardOptions>> {
+  if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+    return Ok(None);
+  }
+
+  let defaults = ConfigWizardOptions::default();
+
+  println!(
+    "No config file found. Answer a few questions to generate one \
+     (press enter to use the default)."
+  );
+
+  let mod_key = prompt(
+    "Modifier key for keybindings [alt/lwin/ctrl/ctrl+alt]",
+    &defaults.mod_key,
+  )?;
+
+  let mod_key = mod_key.to_lowercase();
+  let mod_key = if ["alt", "lwin", "ctrl", "ctrl+alt"].contains(&&*mod_key)
+  {
+    mod_key
+  } else {
+    println!("Unrecognized modifier key. Using '{}'.", defaults.mod_key);
+    defaults.mod_key
+  };
+
+  let workspace_count = prompt(
+    "Number of workspaces [1-9]",
+    &defaults.workspace_count.to_string(),
+  )?
+  .parse::<u32>()
+  .ok()
+  .filter(|count| (1..=9).contains(count))
+  .unwrap_or(defaults.workspace_count);
+
+  let gaps_enabled =
+    prompt_bool("Enable gaps between windows?", defaults.gaps_enabled)?;
+
+  let focus_follows_cursor = prompt_bool(
+    "Focus windows underneath the cursor?",
+    defaults.focus_follows_cursor,
+  )?;
+
+  Ok(Some(ConfigWizardOptions {
+    mod_key,
+    workspace_count,
+    gaps_enabled,
+    focus_follows_cursor,
+  }))
+}
+
+/// Tailors the given sample config to the chosen wizard options.
+///
+/// This operates on the raw lines of the sample config so that its
+/// comments are preserved.
+#[must_use]
+pub fn apply_config_wizard(
+  sample_config: &str,
+  options: &ConfigWizardOptions,
+) -> String {
+  let mut output = Vec::new();
+  let mut lines = sample_config.lines();
+  let mut section = "";
+
+  while let Some(line) = lines.next() {
+    if !line.starts_with(' ') && line.ends_with(':') {
+      section = line.trim_end_matches(':');
+    }
+
+    // Drop workspaces beyond the chosen count, along with keybindings
+    // that reference them (the following line holds the bindings).
+    if let Some(index) = workspace_index(line) {
+      if index > options.workspace_count {
+        if line.trim_start().starts_with("- commands:") {
+          lines.next();
+        }
+
+        continue;
+      }
+    }
+
+    let line = match (section, line.trim_start()) {
+      ("general", "focus_follows_cursor: false") => {
+        line.replace("false", &options.focus_follows_cursor.to_string())
+      }
+      ("gaps", trimmed)
+        if !options.gaps_enabled
+          && ["inner_gap:", "top:", "right:", "bottom:", "left:"]
+            .iter()
+            .any(|key| trimmed.starts_with(key)) =>
+      {
+        let (key, _) = line.split_once(':').unwrap_or((line, ""));
+        format!("{key}: '0px'")
+      }
+      ("keybindings", trimmed) if trimmed.starts_with("bindings:") => {
+        line.replace("'alt+", &format!("'{}+", options.mod_key))
+      }
+      _ => line.to_string(),
+    };
+
+    output.push(line);
+  }
+
+  output.join("\n") + "\n"
+}
+
+/// Gets the workspace number referenced by a workspace definition (e.g.
+/// `- name: '3'`) or a workspace keybinding (e.g. `--workspace 3`).
+fn workspace_index(line: &str) -> Option<u32> {
+  let trimmed = line.trim_start();
+
+  if let Some(name) = trimmed.strip_prefix("- name: ") {
+    return name.trim_matches('\'').parse().ok();
+  }
+
+  trimmed.split("--workspace ").nth(1).and_then(|rest| {
+    rest
+      .chars()
+      .take_while(char::is_ascii_digit)
+      .collect::<String>()
+      .parse()
+      .ok()
+  })
+}
+
+/// Prompts for a line of input, falling back to the default if empty.
+fn prompt(question: &str, default: &str) -> anyhow::Result<String> {
+  print!("{question} ({default}): ");
+  io::stdout().flush()?;
+
+  let mut input = String::new();
+  io::stdin().lock().read_line(&mut input)?;
+
+  let input = input.trim();
+  Ok(if input.is_empty() { default } else { input }.to_string())
+}
+
+/// Prompts for a yes/no answer, falling back to the default if empty.
+fn prompt_bool(question: &str, default: bool) -> anyhow::Result<bool> {
+  let hint = if default { "Y/n" } else { "y/N" };
+  print!("{question} [{hint}]: ");
+  io::stdout().flush()?;
+
+  let mut input = String::new();
+  io::stdin().lock().read_line(&mut input)?;
+
+  Ok(match input.trim().to_lowercase().as_str() {
+    "y" | "yes" => true,
+    "n" | "no" => false,
+    _ => default,
+  })
+}
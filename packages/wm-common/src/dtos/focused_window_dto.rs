@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Identifying info of the focused window.
+///
+/// Included in focus change events so that clients don't need to query
+/// for the window's details on every focus change.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusedWindowDto {
+  pub id: Uuid,
+  pub handle: isize,
+  pub title: String,
+  pub class_name: String,
+  pub process_name: String,
+  pub process_path: String,
+  pub workspace_name: Option<String>,
+}
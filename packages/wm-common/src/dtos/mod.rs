@@ -1,4 +1,5 @@
 mod container_dto;
+mod focused_window_dto;
 mod monitor_dto;
 mod root_container_dto;
 mod split_container_dto;
@@ -6,6 +7,7 @@ mod window_dto;
 mod workspace_dto;
 
 pub use container_dto::*;
+pub use focused_window_dto::*;
 pub use monitor_dto::*;
 pub use root_container_dto::*;
 pub use split_container_dto::*;
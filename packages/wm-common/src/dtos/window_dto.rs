@@ -1,11 +1,14 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{ActiveDrag, DisplayState, Rect, RectDelta, WindowState};
+use crate::{
+  ActiveDrag, DisplayState, ProcessStats, Rect, RectDelta, WindowState,
+};
 
 /// User-friendly representation of a tiling or non-tiling window.
 ///
 /// Used for IPC and debug logging.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WindowDto {
@@ -27,4 +30,24 @@ pub struct WindowDto {
   pub class_name: String,
   pub process_name: String,
   pub active_drag: Option<ActiveDrag>,
+  pub badge: Option<String>,
+
+  /// Resource usage of the window's process. Only included in IPC
+  /// queries if `general.window_process_stats` is enabled.
+  #[serde(default)]
+  pub process_stats: Option<ProcessStats>,
+
+  /// Whether the window's process is currently playing audio. Media
+  /// usage is always `false` if `general.media_poll_interval_ms` is 0.
+  #[serde(default)]
+  pub is_playing_audio: bool,
+
+  #[serde(default)]
+  pub is_using_microphone: bool,
+
+  #[serde(default)]
+  pub is_using_camera: bool,
+
+  #[serde(default)]
+  pub is_recording_screen: bool,
 }
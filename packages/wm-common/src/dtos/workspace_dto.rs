@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::ContainerDto;
-use crate::TilingDirection;
+use crate::{TilingDirection, WorkspaceLayout};
 
 /// User-friendly representation of a workspace.
 ///
@@ -18,9 +18,20 @@ pub struct WorkspaceDto {
   pub child_focus_order: Vec<Uuid>,
   pub has_focus: bool,
   pub is_displayed: bool,
+
+  /// Number of windows on the workspace, including floating and
+  /// minimized windows.
+  pub window_count: usize,
+
+  /// Number of tiling windows on the workspace.
+  pub tiling_window_count: usize,
+
+  /// Whether the workspace has no windows.
+  pub is_empty: bool,
   pub width: i32,
   pub height: i32,
   pub x: i32,
   pub y: i32,
   pub tiling_direction: TilingDirection,
+  pub layout: WorkspaceLayout,
 }
@@ -0,0 +1,18 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Profile of Windows Focus Assist (called "Do not disturb" on Windows
+/// 11), which suppresses notifications.
+#[derive(
+  Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize, ValueEnum,
+)]
+#[clap(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FocusAssistMode {
+  /// All notifications are shown.
+  Off,
+  /// Only notifications from the priority list are shown.
+  PriorityOnly,
+  /// Only alarms are shown.
+  AlarmsOnly,
+}
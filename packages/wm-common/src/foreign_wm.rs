@@ -0,0 +1,27 @@
+/// Window managers and window snapping tools that conflict with the WM,
+/// as tuples of process names and display names.
+pub const FOREIGN_WINDOW_MANAGERS: [(&str, &str); 6] = [
+  ("PowerToys.FancyZones.exe", "PowerToys FancyZones"),
+  ("komorebi.exe", "komorebi"),
+  ("DisplayFusion.exe", "DisplayFusion"),
+  ("AquaSnap.Daemon.exe", "AquaSnap"),
+  ("WindowGrid.exe", "WindowGrid"),
+  ("Divvy.exe", "Divvy"),
+];
+
+/// Gets the display names of foreign window managers that are among the
+/// given running processes.
+#[must_use]
+pub fn foreign_window_managers(
+  process_names: &[String],
+) -> Vec<&'static str> {
+  FOREIGN_WINDOW_MANAGERS
+    .iter()
+    .filter(|(process_name, _)| {
+      process_names
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(process_name))
+    })
+    .map(|(_, display_name)| *display_name)
+    .collect()
+}
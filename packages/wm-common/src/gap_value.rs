@@ -0,0 +1,116 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{Delta, GapsConfig, LengthValue};
+
+/// A gap to change via the `set-gaps` command.
+///
+/// Parsed from a string of format `<gap>:<length>` (e.g. `outer:0` or
+/// `inner:10px`).
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GapValue {
+  pub gap: GapKind,
+  pub length: LengthValue,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, ValueEnum)]
+#[clap(rename_all = "kebab_case")]
+#[serde(rename_all = "snake_case")]
+pub enum GapKind {
+  Inner,
+  Outer,
+  OuterTop,
+  OuterRight,
+  OuterBottom,
+  OuterLeft,
+}
+
+impl GapKind {
+  /// Gets the lengths in the gaps config that the gap refers to.
+  fn lengths_mut<'a>(
+    &self,
+    gaps_config: &'a mut GapsConfig,
+  ) -> Vec<&'a mut LengthValue> {
+    let outer_gap = &mut gaps_config.outer_gap;
+
+    match self {
+      GapKind::Inner => vec![&mut gaps_config.inner_gap],
+      GapKind::Outer => vec![
+        &mut outer_gap.top,
+        &mut outer_gap.right,
+        &mut outer_gap.bottom,
+        &mut outer_gap.left,
+      ],
+      GapKind::OuterTop => vec![&mut outer_gap.top],
+      GapKind::OuterRight => vec![&mut outer_gap.right],
+      GapKind::OuterBottom => vec![&mut outer_gap.bottom],
+      GapKind::OuterLeft => vec![&mut outer_gap.left],
+    }
+  }
+
+  /// Increases or decreases the gap in the given gaps config. Gaps are
+  /// clamped to a minimum of 0.
+  pub fn adjust(
+    &self,
+    gaps_config: &mut GapsConfig,
+    delta: &Delta<LengthValue>,
+  ) -> anyhow::Result<()> {
+    for length in self.lengths_mut(gaps_config) {
+      if length.unit != delta.inner.unit {
+        bail!(
+          "Cannot adjust gap of unit '{:?}' by a value of unit '{:?}'.",
+          length.unit,
+          delta.inner.unit
+        );
+      }
+
+      let amount = if delta.is_negative {
+        length.amount - delta.inner.amount
+      } else {
+        length.amount + delta.inner.amount
+      };
+
+      length.amount = amount.max(0.);
+    }
+
+    Ok(())
+  }
+}
+
+impl GapValue {
+  /// Applies the gap value to the given gaps config.
+  pub fn apply(&self, gaps_config: &mut GapsConfig) {
+    for length in self.gap.lengths_mut(gaps_config) {
+      *length = self.length.clone();
+    }
+  }
+}
+
+impl FromStr for GapValue {
+  type Err = anyhow::Error;
+
+  fn from_str(unparsed: &str) -> anyhow::Result<Self> {
+    let (gap_str, length_str) =
+      unparsed.split_once(':').with_context(|| {
+        format!(
+          "Not a valid gap value '{unparsed}'. Must be of format '<gap>:<length>'."
+        )
+      })?;
+
+    let gap = GapKind::from_str(gap_str, false).map_err(|_| {
+      anyhow::anyhow!(
+        "Not a valid gap '{gap_str}'. Must be one of 'inner', 'outer', \
+         'outer-top', 'outer-right', 'outer-bottom' or 'outer-left'."
+      )
+    })?;
+
+    Ok(Self {
+      gap,
+      length: LengthValue::from_str(length_str)?,
+    })
+  }
+}
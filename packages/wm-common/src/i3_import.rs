@@ -0,0 +1,1009 @@
+use serde_yaml::{Mapping, Value};
+
+use crate::{command_name, named_mapping, ConfigImport};
+
+/// Converts an i3 config to an equivalent user config.
+///
+/// Supports variables, `bindsym` (including within binding modes),
+/// `assign`, `for_window`, `workspace <name> output`, `gaps`,
+/// `smart_gaps`, `focus_follows_mouse`, and `exec`. Any other directives
+/// are reported as unsupported.
+///
+/// `is_valid_key` is used to check whether key names of keybindings are
+/// recognized.
+pub fn import_i3_config(
+  config_str: &str,
+  is_valid_key: impl Fn(&str) -> bool,
+) -> ConfigImport {
+  let mut importer = I3Importer::default();
+
+  for (line_number, line) in logical_lines(config_str) {
+    importer.import_line(line_number, &line, &is_valid_key);
+  }
+
+  importer.finish()
+}
+
+/// Accumulated state while converting an i3 config.
+#[derive(Default)]
+struct I3Importer {
+  variables: Vec<(String, String)>,
+  general: Mapping,
+  gaps: Mapping,
+  workspaces: Vec<String>,
+  workspace_monitors: Vec<(String, u32)>,
+  outputs: Vec<String>,
+  window_rules: Vec<Value>,
+  keybindings: Vec<Value>,
+  binding_modes: Vec<(String, Vec<Value>)>,
+  unsupported: Vec<String>,
+  notes: Vec<String>,
+
+  /// Name of the `mode` block that's currently being parsed.
+  current_mode: Option<String>,
+
+  /// Nesting depth of a block that's being skipped (e.g. `bar`).
+  skipped_depth: usize,
+}
+
+impl I3Importer {
+  fn import_line(
+    &mut self,
+    line_number: usize,
+    line: &str,
+    is_valid_key: &impl Fn(&str) -> bool,
+  ) {
+    let line = self.substitute_variables(line);
+
+    if self.skipped_depth > 0 {
+      if line.ends_with('{') {
+        self.skipped_depth += 1;
+      } else if line == "}" {
+        self.skipped_depth -= 1;
+      }
+      return;
+    }
+
+    if line == "}" {
+      self.current_mode = None;
+      return;
+    }
+
+    let args = split_args(&line);
+    let Some(directive) = args.first().map(String::as_str) else {
+      return;
+    };
+
+    let result = match directive {
+      "set" | "set_from_resource" => {
+        self.import_variable(directive, &line)
+      }
+      "bindsym" => self.import_bindsym(&line, is_valid_key),
+      "mode" if line.ends_with('{') => self.import_mode(&args),
+      "assign" => self.import_assign(&line),
+      "for_window" => self.import_for_window(&line),
+      "workspace" => self.import_workspace_output(&args),
+      "gaps" => self.import_gaps(&args),
+      "smart_gaps" => self.import_smart_gaps(&args),
+      "focus_follows_mouse" => self.import_focus_follows_mouse(&args),
+      "exec" | "exec_always" => self.import_exec(directive, &line),
+      "bar" => Err(Some("use Zebar for a status bar")),
+      _ => Err(None),
+    };
+
+    if let Err(reason) = result {
+      if line.ends_with('{') {
+        self.skipped_depth = 1;
+      }
+
+      let reason = reason.map(|reason| format!(" ({reason})"));
+      self.unsupported.push(format!(
+        "line {line_number}: {line}{}",
+        reason.unwrap_or_default()
+      ));
+    }
+  }
+
+  /// Replaces variables with their values. Longer names are replaced
+  /// first, so that e.g. `$mod` doesn't replace part of `$mod2`.
+  fn substitute_variables(&self, line: &str) -> String {
+    // Variables can't be substituted in their own definition.
+    if line.starts_with("set ") || line.starts_with("set_from_resource ") {
+      return line.to_string();
+    }
+
+    let mut variables = self.variables.iter().collect::<Vec<_>>();
+    variables.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+
+    variables
+      .into_iter()
+      .fold(line.to_string(), |line, (name, value)| {
+        line.replace(name, value)
+      })
+  }
+
+  fn import_variable(
+    &mut self,
+    directive: &str,
+    line: &str,
+  ) -> ImportResult {
+    let (name, value) = line
+      .trim_start_matches(directive)
+      .trim_start()
+      .split_once(char::is_whitespace)
+      .ok_or(Some("missing value"))?;
+
+    // Values of `set_from_resource` come from X resources, so only the
+    // fallback value can be used.
+    let value = if directive == "set_from_resource" {
+      value
+        .trim_start()
+        .split_once(char::is_whitespace)
+        .map(|(_, fallback)| fallback)
+        .ok_or(Some("missing value"))?
+    } else {
+      value
+    };
+
+    let value = value.trim();
+
+    if value.is_empty() {
+      return Err(Some("missing value"));
+    }
+
+    if !name.starts_with('$') {
+      return Err(Some("variable names must start with `$`"));
+    }
+
+    // Values are substituted as written (including any quotes), the same
+    // as in i3. Arguments are only split after substitution.
+    let value = self.substitute_variables(value);
+    self.variables.retain(|(existing, _)| existing != name);
+    self.variables.push((name.to_string(), value));
+    Ok(())
+  }
+
+  fn import_bindsym(
+    &mut self,
+    line: &str,
+    is_valid_key: &impl Fn(&str) -> bool,
+  ) -> ImportResult {
+    let mut rest = line.trim_start_matches("bindsym").trim_start();
+
+    // Flags like `--release` and `--whole-window` have no equivalent.
+    while rest.starts_with("--") {
+      let (flag, remainder) =
+        rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+
+      if flag == "--release" {
+        return Err(Some("release bindings are not supported"));
+      }
+
+      rest = remainder.trim_start();
+    }
+
+    let (combo, command) = rest
+      .split_once(char::is_whitespace)
+      .ok_or(Some("missing command"))?;
+
+    let binding = convert_key_combo(combo, is_valid_key)
+      .ok_or(Some("unrecognized key"))?;
+
+    let commands = self.convert_commands(command.trim())?;
+
+    let mut keybinding = Mapping::new();
+    keybinding.insert("commands".into(), commands.into());
+    keybinding.insert("bindings".into(), vec![binding].into());
+
+    match &self.current_mode {
+      Some(mode) => {
+        if let Some((_, keybindings)) =
+          self.binding_modes.iter_mut().find(|(name, _)| name == mode)
+        {
+          keybindings.push(keybinding.into());
+        }
+      }
+      None => self.keybindings.push(keybinding.into()),
+    }
+
+    Ok(())
+  }
+
+  fn import_mode(&mut self, args: &[String]) -> ImportResult {
+    let name = match args {
+      [_, name, brace] if brace == "{" => name,
+      _ => return Err(Some("unsupported mode options")),
+    };
+
+    self.binding_modes.push((name.clone(), Vec::new()));
+    self.current_mode = Some(name.clone());
+    Ok(())
+  }
+
+  fn import_assign(&mut self, line: &str) -> ImportResult {
+    let rest = line.trim_start_matches("assign").trim_start();
+    let (match_config, target) = parse_criteria(rest)?;
+
+    let target = target.trim().trim_start_matches('→').trim();
+    let args = split_args(target);
+
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let workspace = match args.as_slice() {
+      ["output", ..] | [] => {
+        return Err(Some("only workspace assignments are supported"))
+      }
+      ["workspace", "number", name @ ..]
+      | ["workspace", name @ ..]
+      | name => name.join(" "),
+    };
+
+    self.add_workspace(&workspace);
+
+    let mut window_rule = Mapping::new();
+    window_rule.insert(
+      "commands".into(),
+      vec![format!("move --workspace {}", command_name(&workspace))]
+        .into(),
+    );
+    window_rule.insert("match".into(), vec![match_config].into());
+    window_rule.insert("on".into(), vec!["manage"].into());
+
+    self.window_rules.push(window_rule.into());
+    Ok(())
+  }
+
+  fn import_for_window(&mut self, line: &str) -> ImportResult {
+    let rest = line.trim_start_matches("for_window").trim_start();
+    let (match_config, command) = parse_criteria(rest)?;
+    let commands = self.convert_commands(command.trim())?;
+
+    let mut window_rule = Mapping::new();
+    window_rule.insert("commands".into(), commands.into());
+    window_rule.insert("match".into(), vec![match_config].into());
+
+    self.window_rules.push(window_rule.into());
+    Ok(())
+  }
+
+  /// Converts `workspace <name> output <output>`. Output names don't
+  /// exist in the user config, so outputs are mapped to monitor indices
+  /// in the order they appear.
+  fn import_workspace_output(&mut self, args: &[String]) -> ImportResult {
+    let output_index = args
+      .iter()
+      .position(|arg| arg == "output")
+      .filter(|index| *index > 1 && *index + 1 < args.len())
+      .ok_or(Some("only `workspace <name> output` is supported"))?;
+
+    let name_args = match &args[1..output_index] {
+      [number, name_args @ ..] if number == "number" => name_args,
+      name_args => name_args,
+    };
+
+    let workspace = name_args.join(" ");
+    let output = &args[output_index + 1];
+
+    if !self.outputs.contains(output) {
+      self.outputs.push(output.clone());
+      self.notes.push(format!(
+        "Output `{output}` was mapped to monitor index {}. Monitors are \
+         ordered from left-to-right and top-to-bottom.",
+        self.outputs.len() - 1
+      ));
+    }
+
+    let monitor_index = self
+      .outputs
+      .iter()
+      .position(|existing| existing == output)
+      .unwrap_or_default();
+
+    self.add_workspace(&workspace);
+    self
+      .workspace_monitors
+      .push((workspace, u32::try_from(monitor_index).unwrap_or_default()));
+
+    Ok(())
+  }
+
+  fn import_gaps(&mut self, args: &[String]) -> ImportResult {
+    let (kind, amount) = match args {
+      [_, kind, amount] => (kind.as_str(), amount),
+      _ => return Err(Some("workspace-specific gaps are not supported")),
+    };
+
+    let amount = amount
+      .trim_end_matches("px")
+      .parse::<i32>()
+      .map_err(|_| Some("invalid gap amount"))?;
+
+    let amount = Value::from(format!("{amount}px"));
+
+    let sides: &[&str] = match kind {
+      "inner" => {
+        self.gaps.insert("inner_gap".into(), amount);
+        return Ok(());
+      }
+      "outer" => &["top", "right", "bottom", "left"],
+      "horizontal" => &["right", "left"],
+      "vertical" => &["top", "bottom"],
+      "top" | "right" | "bottom" | "left" => &[kind],
+      _ => return Err(Some("unknown gap type")),
+    };
+
+    // All sides of the outer gap are required, so sides that aren't set
+    // in the i3 config default to 0.
+    let outer_gap =
+      self.gaps.entry("outer_gap".into()).or_insert_with(|| {
+        ["top", "right", "bottom", "left"]
+          .into_iter()
+          .map(|side| (side.into(), "0px".into()))
+          .collect::<Mapping>()
+          .into()
+      });
+
+    if let Some(outer_gap) = outer_gap.as_mapping_mut() {
+      for side in sides {
+        outer_gap.insert((*side).into(), amount.clone());
+      }
+    }
+
+    Ok(())
+  }
+
+  fn import_smart_gaps(&mut self, args: &[String]) -> ImportResult {
+    match args.get(1).map(String::as_str) {
+      Some("on" | "yes" | "true") => {
+        self.gaps.insert("smart".into(), true.into());
+        Ok(())
+      }
+      Some("off" | "no" | "false") => Ok(()),
+      _ => Err(Some("only `on` and `off` are supported")),
+    }
+  }
+
+  fn import_focus_follows_mouse(
+    &mut self,
+    args: &[String],
+  ) -> ImportResult {
+    let is_enabled = match args.get(1).map(String::as_str) {
+      Some("yes" | "true" | "on") => true,
+      Some("no" | "false" | "off") => false,
+      _ => return Err(Some("expected `yes` or `no`")),
+    };
+
+    self
+      .general
+      .insert("focus_follows_cursor".into(), is_enabled.into());
+
+    Ok(())
+  }
+
+  /// Converts `exec` to a startup command. `exec_always` is additionally
+  /// run on config reload, since i3 runs it on restart.
+  fn import_exec(&mut self, directive: &str, line: &str) -> ImportResult {
+    let command = convert_exec(line.trim_start_matches(directive))
+      .ok_or(Some("missing command"))?;
+
+    let keys: &[&str] = if directive == "exec_always" {
+      &["startup_commands", "config_reload_commands"]
+    } else {
+      &["startup_commands"]
+    };
+
+    for key in keys {
+      let commands = self
+        .general
+        .entry((*key).into())
+        .or_insert_with(|| Vec::<Value>::new().into());
+
+      if let Value::Sequence(commands) = commands {
+        commands.push(command.clone().into());
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Converts a list of i3 commands separated by `;` or `,`. Fails if any
+  /// of the commands can't be converted.
+  fn convert_commands(
+    &mut self,
+    commands: &str,
+  ) -> Result<Vec<String>, Option<&'static str>> {
+    let mut converted = Vec::new();
+
+    for command in split_command_list(commands) {
+      let command = command.trim();
+
+      if command.is_empty() {
+        continue;
+      }
+
+      let mut workspaces = Vec::new();
+      converted.push(convert_command(command, &mut workspaces)?);
+
+      // Workspaces that are switched to need to be defined in the config.
+      for workspace in workspaces {
+        self.add_workspace(&workspace);
+      }
+    }
+
+    if converted.is_empty() {
+      return Err(Some("missing command"));
+    }
+
+    Ok(converted)
+  }
+
+  fn add_workspace(&mut self, name: &str) {
+    if !self.workspaces.iter().any(|existing| existing == name) {
+      self.workspaces.push(name.to_string());
+    }
+  }
+
+  fn finish(self) -> ConfigImport {
+    let mut config = Mapping::new();
+
+    if !self.general.is_empty() {
+      config.insert("general".into(), self.general.into());
+    }
+
+    if !self.gaps.is_empty() {
+      config.insert("gaps".into(), self.gaps.into());
+    }
+
+    let workspaces = self
+      .workspaces
+      .iter()
+      .map(|name| {
+        let mut workspace = named_mapping(name);
+
+        if let Some((_, monitor)) = self
+          .workspace_monitors
+          .iter()
+          .find(|(workspace, _)| workspace == name)
+        {
+          workspace.insert("bind_to_monitor".into(), (*monitor).into());
+        }
+
+        workspace.into()
+      })
+      .collect::<Vec<Value>>();
+
+    if !workspaces.is_empty() {
+      config.insert("workspaces".into(), workspaces.into());
+    }
+
+    if !self.window_rules.is_empty() {
+      config.insert("window_rules".into(), self.window_rules.into());
+    }
+
+    let binding_modes = self
+      .binding_modes
+      .into_iter()
+      .map(|(name, keybindings)| {
+        let mut binding_mode = named_mapping(&name);
+        binding_mode.insert("keybindings".into(), keybindings.into());
+        binding_mode.into()
+      })
+      .collect::<Vec<Value>>();
+
+    if !binding_modes.is_empty() {
+      config.insert("binding_modes".into(), binding_modes.into());
+    }
+
+    if !self.keybindings.is_empty() {
+      config.insert("keybindings".into(), self.keybindings.into());
+    }
+
+    ConfigImport {
+      config: config.into(),
+      unsupported: self.unsupported,
+      notes: self.notes,
+    }
+  }
+}
+
+/// Reason that a directive couldn't be converted, if there's more to say
+/// than it being unsupported.
+type ImportResult = Result<(), Option<&'static str>>;
+
+/// Gets the non-empty lines of the config along with their line numbers,
+/// with comments removed and continuation lines (ending in `\`) joined.
+fn logical_lines(config_str: &str) -> Vec<(usize, String)> {
+  let mut lines = Vec::new();
+  let mut pending: Option<(usize, String)> = None;
+
+  for (index, line) in config_str.lines().enumerate() {
+    let line = line.trim();
+
+    let (line_number, mut joined) = match pending.take() {
+      Some((line_number, joined)) => (line_number, joined),
+      None if line.starts_with('#') => continue,
+      None => (index + 1, String::new()),
+    };
+
+    if let Some(line) = line.strip_suffix('\\') {
+      joined.push_str(line);
+      pending = Some((line_number, joined));
+      continue;
+    }
+
+    joined.push_str(line);
+
+    if !joined.is_empty() {
+      lines.push((line_number, joined));
+    }
+  }
+
+  lines.extend(pending);
+  lines
+}
+
+/// Splits a line into whitespace-separated arguments. Quoted arguments
+/// can contain whitespace, and the quotes are removed.
+fn split_args(line: &str) -> Vec<String> {
+  let mut args = Vec::new();
+  let mut current = String::new();
+  let mut in_quotes = false;
+  let mut is_arg = false;
+
+  for character in line.chars() {
+    match character {
+      '"' => {
+        in_quotes = !in_quotes;
+        is_arg = true;
+      }
+      character if character.is_whitespace() && !in_quotes => {
+        if is_arg {
+          args.push(std::mem::take(&mut current));
+          is_arg = false;
+        }
+      }
+      character => {
+        current.push(character);
+        is_arg = true;
+      }
+    }
+  }
+
+  if is_arg {
+    args.push(current);
+  }
+
+  args
+}
+
+/// Splits a list of i3 commands on `;` and `,` outside of quotes.
+fn split_command_list(commands: &str) -> Vec<&str> {
+  let mut parts = Vec::new();
+  let mut start = 0;
+  let mut in_quotes = false;
+
+  for (index, character) in commands.char_indices() {
+    match character {
+      '"' => in_quotes = !in_quotes,
+      ';' | ',' if !in_quotes => {
+        parts.push(&commands[start..index]);
+        start = index + 1;
+      }
+      _ => {}
+    }
+  }
+
+  parts.push(&commands[start..]);
+  parts
+}
+
+/// Parses criteria of format `[key="value" ...]` to a window match
+/// config. Returns the match config and the remainder of the line.
+fn parse_criteria(
+  line: &str,
+) -> Result<(Value, &str), Option<&'static str>> {
+  let (criteria, rest) = line
+    .strip_prefix('[')
+    .and_then(|line| line.split_once(']'))
+    .ok_or(Some("missing criteria"))?;
+
+  let mut match_config = Mapping::new();
+
+  for criterion in split_args(criteria) {
+    let (key, value) =
+      criterion.split_once('=').ok_or(Some("invalid criteria"))?;
+
+    let key = match key {
+      "class" => "window_class",
+      "title" => "window_title",
+      _ => {
+        return Err(Some(
+          "only `class` and `title` criteria are supported",
+        ))
+      }
+    };
+
+    let mut match_type = Mapping::new();
+    match_type.insert("regex".into(), value.trim_matches('"').into());
+    match_config.insert(key.into(), match_type.into());
+  }
+
+  if match_config.is_empty() {
+    return Err(Some("missing criteria"));
+  }
+
+  Ok((match_config.into(), rest))
+}
+
+/// Converts a key combination like `$mod+Shift+Return` to the key names
+/// of the user config. Returns `None` if a key isn't recognized.
+fn convert_key_combo(
+  combo: &str,
+  is_valid_key: &impl Fn(&str) -> bool,
+) -> Option<String> {
+  combo
+    .split('+')
+    .map(|key| {
+      let key = match key.to_lowercase().as_str() {
+        "mod1" => "alt".to_string(),
+        "mod4" => "lwin".to_string(),
+        "control" | "ctrl" => "ctrl".to_string(),
+        "return" => "enter".to_string(),
+        "prior" => "page_up".to_string(),
+        "next" => "page_down".to_string(),
+        "minus" => "oem_minus".to_string(),
+        "plus" | "equal" => "oem_plus".to_string(),
+        "comma" => "oem_comma".to_string(),
+        "period" => "oem_period".to_string(),
+        key => key.to_string(),
+      };
+
+      is_valid_key(&key).then_some(key)
+    })
+    .collect::<Option<Vec<_>>>()
+    .map(|keys| keys.join("+"))
+}
+
+/// Converts a single i3 command to a WM command.
+///
+/// Names of workspaces that the command targets are added to
+/// `workspaces`.
+fn convert_command(
+  command: &str,
+  workspaces: &mut Vec<String>,
+) -> Result<String, Option<&'static str>> {
+  if let Some(exec) = command.strip_prefix("exec") {
+    return convert_exec(exec).ok_or(Some("missing command"));
+  }
+
+  let args = split_args(command);
+  let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+  let converted = match args.as_slice() {
+    ["kill"] => "close".into(),
+    ["nop", ..] => "noop".into(),
+    ["reload" | "restart"] => "wm-reload-config".into(),
+    ["exit"] => "wm-exit".into(),
+    ["focus", direction @ ("left" | "right" | "up" | "down")] => {
+      format!("focus --direction {direction}")
+    }
+    ["focus", "parent"] => "focus --parent".into(),
+    ["focus", "child"] => "focus --child".into(),
+    ["focus", "mode_toggle"] => "wm-cycle-focus".into(),
+    ["focus", "output", direction @ ("left" | "right" | "up" | "down")] => {
+      format!("focus --workspace-in-direction {direction}")
+    }
+    ["workspace", rest @ ..] => {
+      format!("focus {}", workspace_target(rest, workspaces)?)
+    }
+    ["move", direction @ ("left" | "right" | "up" | "down"), ..] => {
+      format!("move --direction {direction}")
+    }
+    ["move", "workspace", "to", "output", direction] => {
+      format!("move-workspace --direction {direction}")
+    }
+    ["move", rest @ ..] => convert_move(rest, workspaces)?,
+    ["fullscreen"] | ["fullscreen", "toggle"] => {
+      "toggle-fullscreen".into()
+    }
+    ["fullscreen", "enable"] => "set-fullscreen".into(),
+    ["fullscreen" | "floating", "disable"] => "set-tiling".into(),
+    ["floating", "toggle"] => "toggle-floating".into(),
+    ["floating", "enable"] => "set-floating".into(),
+    ["split", "h" | "horizontal"] | ["layout", "splith"] => {
+      "set-tiling-direction horizontal".into()
+    }
+    ["split", "v" | "vertical"] | ["layout", "splitv"] => {
+      "set-tiling-direction vertical".into()
+    }
+    ["split", "t" | "toggle"] | ["layout", "toggle", "split"] => {
+      "toggle-tiling-direction".into()
+    }
+    ["resize", rest @ ..] => convert_resize(rest)?,
+    ["mode", "default"] => "wm-disable-binding-mode".into(),
+    ["mode", name] => {
+      format!("wm-enable-binding-mode --name {}", command_name(name))
+    }
+    _ => return Err(None),
+  };
+
+  Ok(converted)
+}
+
+/// Converts the arguments of `exec` to a `shell-exec` command.
+fn convert_exec(exec: &str) -> Option<String> {
+  let mut command = exec.trim();
+
+  if let Some(rest) = command.strip_prefix("--no-startup-id") {
+    command = rest.trim();
+  }
+
+  // Commands can be wrapped in quotes in i3, which aren't needed here.
+  if command.len() > 1
+    && command.starts_with('"')
+    && command.ends_with('"')
+  {
+    command = &command[1..command.len() - 1];
+  }
+
+  (!command.is_empty()).then(|| format!("shell-exec {command}"))
+}
+
+/// Converts the arguments of a `move` command that targets a workspace
+/// or output.
+fn convert_move(
+  args: &[&str],
+  workspaces: &mut Vec<String>,
+) -> Result<String, Option<&'static str>> {
+  let args = args
+    .iter()
+    .copied()
+    .skip_while(|arg| matches!(*arg, "container" | "window" | "to"))
+    .collect::<Vec<_>>();
+
+  match args.as_slice() {
+    ["workspace", rest @ ..] => {
+      Ok(format!("move {}", workspace_target(rest, workspaces)?))
+    }
+    ["output", direction @ ("left" | "right" | "up" | "down")] => {
+      Ok(format!("move-to-monitor {direction}"))
+    }
+    _ => Err(None),
+  }
+}
+
+/// Converts the target of a `workspace` command to the flags of the
+/// `focus` and `move` commands.
+fn workspace_target(
+  args: &[&str],
+  workspaces: &mut Vec<String>,
+) -> Result<String, Option<&'static str>> {
+  let target = match args {
+    ["next"] => "--next-workspace".into(),
+    ["prev"] => "--prev-workspace".into(),
+    ["next_on_output"] => "--next-active-workspace-on-monitor".into(),
+    ["prev_on_output"] => "--prev-active-workspace-on-monitor".into(),
+    ["back_and_forth"] => "--recent-workspace".into(),
+    ["number", name @ ..] | name if !name.is_empty() => {
+      let name = name.join(" ");
+      let target = format!("--workspace {}", command_name(&name));
+      workspaces.push(name);
+      target
+    }
+    _ => return Err(Some("unsupported workspace target")),
+  };
+
+  Ok(target)
+}
+
+/// Converts the arguments of `resize grow|shrink width|height`. Amounts
+/// in `ppt` are preferred, since they're relative like in the WM.
+fn convert_resize(args: &[&str]) -> Result<String, Option<&'static str>> {
+  let (sign, dimension, amount) = match args {
+    [action @ ("grow" | "shrink"), dimension @ ("width" | "height"), amount @ ..] =>
+    {
+      let sign = if *action == "grow" { '+' } else { '-' };
+      (sign, *dimension, amount)
+    }
+    _ => {
+      return Err(Some(
+        "only `resize grow` and `resize shrink` are supported",
+      ))
+    }
+  };
+
+  let amount = match amount {
+    [] => "10ppt".to_string(),
+    [.., ppt, "ppt"] => format!("{ppt}ppt"),
+    [px, "px", ..] => format!("{px}px"),
+    [amount] => format!("{amount}px"),
+    _ => return Err(Some("invalid resize amount")),
+  };
+
+  let amount = match amount.strip_suffix("ppt") {
+    Some(ppt) => format!("{ppt}%"),
+    None => amount,
+  };
+
+  Ok(format!("resize --{dimension} {sign}{amount}"))
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_yaml::Value;
+
+  use super::import_i3_config;
+
+  /// Imports the config with all key names being recognized, and checks
+  /// that every line was converted.
+  fn import(config_str: &str) -> Value {
+    let import = import_i3_config(config_str, |_| true);
+    assert_eq!(import.unsupported, Vec::<String>::new());
+    import.config
+  }
+
+  fn yaml(yaml_str: &str) -> Value {
+    serde_yaml::from_str(yaml_str).unwrap()
+  }
+
+  #[test]
+  fn substitutes_variables() {
+    let config = import(
+      r#"
+        set $mod Mod4
+        set $left h
+        set $term wt -p "Windows PowerShell"
+        bindsym $mod+$left focus left
+        bindsym $mod+Return exec $term
+      "#,
+    );
+
+    assert_eq!(
+      config["keybindings"],
+      yaml(
+        r#"
+          - commands: ["focus --direction left"]
+            bindings: ["lwin+h"]
+          - commands: ['shell-exec wt -p "Windows PowerShell"']
+            bindings: ["lwin+enter"]
+        "#
+      )
+    );
+  }
+
+  #[test]
+  fn converts_bindsym_commands() {
+    let config = import(
+      "bindsym Mod1+Shift+q kill; floating toggle\n\
+       bindsym --whole-window Mod1+r resize grow width 5 px or 5 ppt",
+    );
+
+    assert_eq!(
+      config["keybindings"],
+      yaml(
+        r#"
+          - commands: ["close", "toggle-floating"]
+            bindings: ["alt+shift+q"]
+          - commands: ["resize --width +5%"]
+            bindings: ["alt+r"]
+        "#
+      )
+    );
+  }
+
+  #[test]
+  fn reports_unsupported_bindsym() {
+    let import = import_i3_config(
+      "bindsym --release Mod4+x kill\nbindsym Mod4+y sticky toggle",
+      |_| true,
+    );
+
+    assert_eq!(import.unsupported.len(), 2);
+    assert!(import.unsupported[0].starts_with("line 1:"));
+    assert!(import.unsupported[1].starts_with("line 2:"));
+  }
+
+  #[test]
+  fn converts_binding_modes() {
+    let config = import(
+      r#"
+        set $mode_system System (l) lock, (e) exit
+        mode "$mode_system" {
+          bindsym e exit
+          bindsym Escape mode "default"
+        }
+        bindsym Mod4+Pause mode "$mode_system"
+      "#,
+    );
+
+    assert_eq!(
+      config["binding_modes"],
+      yaml(
+        r#"
+          - name: System_(l)_lock,_(e)_exit
+            display_name: System (l) lock, (e) exit
+            keybindings:
+              - commands: ["wm-exit"]
+                bindings: ["e"]
+              - commands: ["wm-disable-binding-mode"]
+                bindings: ["escape"]
+        "#
+      )
+    );
+
+    assert_eq!(
+      config["keybindings"][0]["commands"],
+      yaml(
+        r#"["wm-enable-binding-mode --name System_(l)_lock,_(e)_exit"]"#
+      )
+    );
+  }
+
+  #[test]
+  fn converts_gaps() {
+    let config =
+      import("gaps inner 10\ngaps horizontal 5px\nsmart_gaps on");
+
+    assert_eq!(
+      config["gaps"],
+      yaml(
+        "
+          inner_gap: 10px
+          outer_gap:
+            top: 0px
+            right: 5px
+            bottom: 0px
+            left: 5px
+          smart: true
+        "
+      )
+    );
+  }
+
+  #[test]
+  fn converts_workspace_names_with_spaces() {
+    let config = import(
+      r#"
+        set $ws1 "1: web"
+        set $ws2 2: code
+        workspace $ws1 output HDMI-1
+        bindsym Mod4+1 workspace $ws1
+        bindsym Mod4+2 workspace number "$ws2"
+        bindsym Mod4+Shift+1 move container to workspace $ws1
+        assign [class="^Firefox$"] $ws1
+      "#,
+    );
+
+    assert_eq!(
+      config["workspaces"],
+      yaml(
+        r#"
+          - name: "1:_web"
+            display_name: "1: web"
+            bind_to_monitor: 0
+          - name: "2:_code"
+            display_name: "2: code"
+        "#
+      )
+    );
+
+    assert_eq!(
+      config["keybindings"],
+      yaml(
+        r#"
+          - commands: ["focus --workspace 1:_web"]
+            bindings: ["lwin+1"]
+          - commands: ["focus --workspace 2:_code"]
+            bindings: ["lwin+2"]
+          - commands: ["move --workspace 1:_web"]
+            bindings: ["lwin+shift+1"]
+        "#
+      )
+    );
+
+    assert_eq!(
+      config["window_rules"][0]["commands"],
+      yaml(r#"["move --workspace 1:_web"]"#)
+    );
+  }
+}
@@ -1,10 +1,16 @@
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{BindingModeConfig, ContainerDto, TilingDirection, WmEvent};
+use crate::{
+  BindingModeConfig, CommandSource, ContainerDto, FocusAssistMode,
+  FocusedWindowDto, GapsConfig, PerfStats, TilingDirection,
+  WindowRuleEvent, WindowState, WmEvent,
+};
 
 pub const DEFAULT_IPC_PORT: u32 = 6123;
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(tag = "messageType", rename_all = "snake_case")]
 pub enum ServerMessage {
@@ -21,26 +27,103 @@ pub struct ClientResponseMessage {
   pub success: bool,
 }
 
+/// Version of the IPC protocol. Incremented on breaking changes to the
+/// format of messages.
+pub const IPC_PROTOCOL_VERSION: u32 = 1;
+
+/// Optional features of the IPC server that clients can check for in
+/// the `hello` handshake.
+pub const IPC_CAPABILITIES: &[&str] = &[
+  "bar-commands",
+  "binding-mode-events",
+  "command-invoked-events",
+  "custom-events",
+  "event-replay",
+  "explain-window",
+  "export-rules",
+  "focused-window-query",
+  "focused-window-events",
+  "focus-assist-query",
+  "gaps-query",
+  "keyboard-layout",
+  "logs-query",
+  "msgpack-encoding",
+  "set-log-level",
+  "stats-query",
+  "timers",
+  "window-audio",
+  "window-media-usage",
+  "window-process-stats",
+  "window-thumbnails",
+];
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum ClientResponseData {
+  // Comes before `AppMetadata`, since untagged variants are matched in
+  // order and both include the WM version.
+  Hello(HelloData),
   AppMetadata(AppMetadataData),
   BindingModes(BindingModesData),
   Command(CommandData),
   EventSubscribe(EventSubscribeData),
   EventUnsubscribe,
   Focused(FocusedData),
+  FocusedWindow(FocusedWindowData),
+  Gaps(GapsData),
   Monitors(MonitorsData),
   TilingDirection(TilingDirectionData),
   Windows(WindowsData),
   Workspaces(WorkspacesData),
   Paused(bool),
+  Logs(LogsData),
+  LogLevel(LogLevelData),
+  Stats(StatsData),
+  FocusAssist(FocusAssistData),
+  ExplainWindow(ExplainWindowData),
+  ExportRules(ExportRulesData),
+  CustomEvent(CustomEventData),
+  Timers(TimersData),
+  KeyboardLayout(KeyboardLayoutData),
+  WindowListForMenu(WindowListForMenuData),
+  WindowThumbnail(WindowThumbnailData),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppMetadataData {
   pub version: String,
+
+  /// Whether the WM is running with elevated (admin) privileges.
+  pub is_elevated: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HelloData {
+  pub version: String,
+  pub protocol_version: u32,
+  pub capabilities: Vec<String>,
+
+  /// Encoding of subsequent messages sent to the client.
+  pub encoding: IpcEncoding,
+
+  /// Source of commands sent over the connection.
+  pub source: CommandSource,
+}
+
+/// Encoding of messages sent from the IPC server to a client.
+#[derive(
+  Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize, ValueEnum,
+)]
+#[clap(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum IpcEncoding {
+  /// JSON in text frames.
+  #[default]
+  Json,
+  /// `MessagePack` in binary frames.
+  Msgpack,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -59,6 +142,13 @@ pub struct CommandData {
 #[serde(rename_all = "camelCase")]
 pub struct EventSubscribeData {
   pub subscription_id: Uuid,
+
+  /// Buffered events that were requested with `--replay-from`.
+  pub replayed_events: Vec<EventSubscriptionMessage>,
+
+  /// Whether some of the events requested with `--replay-from` are no
+  /// longer buffered. Clients should then re-query the WM state.
+  pub has_missed_events: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -67,12 +157,173 @@ pub struct FocusedData {
   pub focused: ContainerDto,
 }
 
+/// Unknown fields are denied, since the only field is optional and the
+/// variant would otherwise match any response when deserializing.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FocusedWindowData {
+  pub focused_window: Option<FocusedWindowDto>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GapsData {
+  pub workspace_name: String,
+  pub workspace_gaps: GapsConfig,
+  pub global_gaps: GapsConfig,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainWindowData {
+  pub handle: isize,
+  pub title: String,
+  pub class_name: String,
+  pub process_name: String,
+  pub process_path: String,
+
+  /// Names of the window's styles (e.g. `WS_CAPTION`).
+  pub styles: Vec<String>,
+
+  /// Names of the window's extended styles (e.g. `WS_EX_TOOLWINDOW`).
+  pub extended_styles: Vec<String>,
+
+  /// Whether the window's process runs with elevated (admin)
+  /// privileges. `None` if it couldn't be determined.
+  pub is_elevated: Option<bool>,
+
+  /// Whether the window passes the checks for being managed (e.g. it's
+  /// visible and isn't a tool window).
+  pub is_manageable: bool,
+
+  /// Whether the window has been ignored (e.g. via the `ignore`
+  /// command) and is therefore left alone until it's closed.
+  pub is_ignored: bool,
+
+  /// ID of the window's container if it's managed.
+  pub managed_id: Option<Uuid>,
+
+  /// State of the window if it's managed.
+  pub window_state: Option<WindowState>,
+
+  /// Results of evaluating each window rule against the window,
+  /// including the built-in default rules.
+  pub window_rules: Vec<WindowRuleMatchData>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowRuleMatchData {
+  /// Commands of the rule as they'd be written in the user config.
+  pub commands: Vec<String>,
+  pub on: Vec<WindowRuleEvent>,
+
+  /// Whether the rule is one of the built-in default rules.
+  pub is_default: bool,
+
+  /// Whether the window matches any of the rule's match conditions.
+  pub is_match: bool,
+
+  /// Whether the rule has already run for the window, and won't run
+  /// again since `run_once` is enabled.
+  pub is_done: bool,
+
+  /// Results of each of the rule's match conditions.
+  pub match_results: Vec<WindowMatchResult>,
+}
+
+/// Result of a single match condition of a window rule. Properties are
+/// `None` if the condition doesn't check them.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowMatchResult {
+  pub window_process: Option<bool>,
+  pub window_class: Option<bool>,
+  pub window_title: Option<bool>,
+  pub is_playing_audio: Option<bool>,
+  pub is_using_microphone: Option<bool>,
+  pub is_using_camera: Option<bool>,
+  pub is_recording_screen: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportRulesData {
+  /// Adjustments made to windows since they were managed, each as the
+  /// command of a window rule that would reproduce it.
+  pub exported_rules: Vec<ExportedWindowRule>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedWindowRule {
+  /// Command as it'd be written in the user config (e.g.
+  /// `set-floating`).
+  pub command: String,
+  pub window_process: String,
+  pub window_class: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomEventData {
+  pub event_name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusAssistData {
+  pub focus_assist_mode: FocusAssistMode,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimersData {
+  pub timers: Vec<TimerStatus>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerStatus {
+  pub name: String,
+  pub is_enabled: bool,
+
+  /// Milliseconds until the timer is next due. `None` if the timer is
+  /// disabled.
+  pub next_run_in_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyboardLayoutData {
+  pub locale_name: String,
+  pub layout_handle: isize,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLevelData {
+  pub filter: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsData {
+  pub logs: Vec<String>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MonitorsData {
   pub monitors: Vec<ContainerDto>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsData {
+  pub stats: PerfStats,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TilingDirectionData {
@@ -92,11 +343,60 @@ pub struct WorkspacesData {
   pub workspaces: Vec<ContainerDto>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowListForMenuData {
+  /// Windows ordered by workspace, in the order of the workspaces in the
+  /// user config.
+  pub menu_windows: Vec<MenuWindowEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuWindowEntry {
+  pub container_id: Uuid,
+  pub title: String,
+  pub process_name: String,
+  pub workspace_name: String,
+
+  /// Display name of the window's workspace, or its name if it has none.
+  pub workspace_label: String,
+  pub is_focused: bool,
+  pub is_minimized: bool,
+
+  /// Command that focuses the window, switching to its workspace if it
+  /// isn't displayed.
+  pub focus_command: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowThumbnailData {
+  pub container_id: Uuid,
+
+  /// Handle of the window. Can be passed to `DwmRegisterThumbnail` for a
+  /// live preview instead of a snapshot.
+  pub thumbnail_handle: isize,
+
+  /// Width of the snapshot in pixels.
+  pub thumbnail_width: u32,
+
+  /// Height of the snapshot in pixels.
+  pub thumbnail_height: u32,
+
+  /// Snapshot of the window's contents as a base64-encoded PNG.
+  pub png_base64: String,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EventSubscriptionMessage {
   pub data: Option<WmEvent>,
   pub error: Option<String>,
+
+  /// Sequence number of the event, which increments by one for every
+  /// event emitted by the WM.
+  pub sequence: u64,
   pub subscription_id: Uuid,
   pub success: bool,
 }
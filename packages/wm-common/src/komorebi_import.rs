@@ -0,0 +1,910 @@
+use serde_yaml::{Mapping, Value};
+
+use crate::{command_name, named_mapping, ConfigImport};
+
+/// Converts a komorebi config (`komorebi.json`) to an equivalent user
+/// config.
+///
+/// Supports gaps, focus options, workspaces along with their layouts and
+/// workspace rules, and ignore and float rules. Rules from komorebi's
+/// app-specific config (`applications.json`, or `applications.yaml` of
+/// older versions) are additionally converted if provided, as are the
+/// keybindings of a whkd config (`whkdrc`).
+///
+/// `is_valid_key` is used to check whether key names of keybindings are
+/// recognized.
+pub fn import_komorebi_config(
+  komorebi_config: &Value,
+  applications_config: Option<&Value>,
+  whkdrc: Option<&str>,
+  is_valid_key: impl Fn(&str) -> bool,
+) -> ConfigImport {
+  let mut importer = KomorebiImporter::default();
+
+  match komorebi_config.as_mapping() {
+    Some(komorebi_config) => {
+      importer
+        .import_komorebi_config(komorebi_config, applications_config);
+    }
+    None => importer
+      .unsupported
+      .push("komorebi config is not an object".into()),
+  }
+
+  if let Some(applications_config) = applications_config {
+    importer.import_applications_config(applications_config);
+  }
+
+  // Converted last, since workspaces are targeted by their index.
+  if let Some(whkdrc) = whkdrc {
+    importer.import_whkdrc(whkdrc, &is_valid_key);
+  }
+
+  importer.finish()
+}
+
+/// Accumulated state while converting a komorebi config.
+#[derive(Default)]
+struct KomorebiImporter {
+  general: Mapping,
+  gaps: Mapping,
+  workspaces: Vec<Value>,
+  window_rules: Vec<WindowRule>,
+  keybindings: Vec<Value>,
+  unsupported: Vec<String>,
+  notes: Vec<String>,
+
+  /// Options of the app-specific config that have no equivalent, along
+  /// with the number of applications that use them.
+  skipped_options: Vec<(String, usize)>,
+
+  /// Names of the converted workspaces, along with the index of their
+  /// monitor.
+  workspace_names: Vec<(usize, String)>,
+}
+
+/// Window rule with the match configs of all komorebi rules that result
+/// in the same command.
+struct WindowRule {
+  command: String,
+  match_configs: Vec<Value>,
+  is_manage_only: bool,
+}
+
+impl KomorebiImporter {
+  fn import_komorebi_config(
+    &mut self,
+    config: &Mapping,
+    applications_config: Option<&Value>,
+  ) {
+    for (key, value) in config {
+      let key = key.as_str().unwrap_or_default();
+
+      let result = match key {
+        "$schema" | "monitors" => Ok(()),
+        "default_workspace_padding" => {
+          insert_padding(&mut self.gaps, "outer_gap", value)
+        }
+        "default_container_padding" => {
+          insert_padding(&mut self.gaps, "inner_gap", value)
+        }
+        "mouse_follows_focus" => self.import_mouse_follows_focus(value),
+        "focus_follows_mouse" => {
+          self.general.insert(
+            "focus_follows_cursor".into(),
+            (!value.is_null()).into(),
+          );
+          Ok(())
+        }
+        "window_hiding_behaviour" => self.import_hiding_behaviour(value),
+        "ignore_rules" | "float_rules" => {
+          self.import_rules(key, value, "ignore", false);
+          Ok(())
+        }
+        "floating_applications" => {
+          self.import_rules(key, value, "set-floating", false);
+          Ok(())
+        }
+        "manage_rules" => Err(Some("windows are managed by default")),
+        "app_specific_configuration_path" => {
+          if applications_config.is_none() {
+            self.notes.push(
+              "The app-specific config can be converted by passing it \
+               via `--applications`."
+                .into(),
+            );
+          }
+          Ok(())
+        }
+        "display_index_preferences" | "monitor_index_preferences" => Err(
+          Some("workspaces are bound to monitors by their index instead"),
+        ),
+        _ => Err(None),
+      };
+
+      if let Err(reason) = result {
+        self.push_unsupported(&format!("`{key}`"), reason);
+      }
+    }
+
+    // Monitors are converted last, since workspace gaps are based on the
+    // default padding.
+    if let Some(monitors) = config.get("monitors") {
+      match monitors.as_sequence() {
+        Some(monitors) => {
+          for (index, monitor) in monitors.iter().enumerate() {
+            self.import_monitor(index, monitor);
+          }
+        }
+        None => self.push_unsupported("`monitors`", Some("not an array")),
+      }
+    }
+  }
+
+  fn import_mouse_follows_focus(&mut self, value: &Value) -> ImportResult {
+    let is_enabled = value.as_bool().ok_or(Some("expected a boolean"))?;
+
+    let mut cursor_jump = Mapping::new();
+    cursor_jump.insert("enabled".into(), is_enabled.into());
+    cursor_jump.insert("trigger".into(), "window_focus".into());
+    self
+      .general
+      .insert("cursor_jump".into(), cursor_jump.into());
+
+    Ok(())
+  }
+
+  fn import_hiding_behaviour(&mut self, value: &Value) -> ImportResult {
+    let hide_method = match value.as_str() {
+      Some("Hide") => "hide",
+      Some("Cloak") => "cloak",
+      _ => return Err(Some("only `Hide` and `Cloak` are supported")),
+    };
+
+    self
+      .general
+      .insert("hide_method".into(), hide_method.into());
+    Ok(())
+  }
+
+  fn import_monitor(&mut self, monitor_index: usize, monitor: &Value) {
+    let Some(monitor) = monitor.as_mapping() else {
+      self.push_unsupported(
+        &format!("`monitors[{monitor_index}]`"),
+        Some("not an object"),
+      );
+      return;
+    };
+
+    for (key, value) in monitor {
+      let key = key.as_str().unwrap_or_default();
+      let path = format!("monitors[{monitor_index}].{key}");
+
+      match (key, value.as_sequence()) {
+        ("workspaces", Some(workspaces)) => {
+          for (index, workspace) in workspaces.iter().enumerate() {
+            self.import_workspace(
+              monitor_index,
+              &format!("{path}[{index}]"),
+              workspace,
+            );
+          }
+        }
+        _ => self.push_unsupported(&format!("`{path}`"), None),
+      }
+    }
+  }
+
+  fn import_workspace(
+    &mut self,
+    monitor_index: usize,
+    path: &str,
+    workspace: &Value,
+  ) {
+    let Some(name) = workspace.get("name").and_then(Value::as_str) else {
+      self.push_unsupported(&format!("`{path}`"), Some("missing name"));
+      return;
+    };
+
+    let mut workspace_config = named_mapping(name);
+    workspace_config.insert(
+      "bind_to_monitor".into(),
+      u32::try_from(monitor_index).unwrap_or_default().into(),
+    );
+
+    // Workspace gaps replace the top-level gaps, so they start out as a
+    // copy of them.
+    let mut workspace_gaps = self.gaps.clone();
+
+    for (key, value) in workspace.as_mapping().into_iter().flatten() {
+      let key = key.as_str().unwrap_or_default();
+
+      let result = match key {
+        "name" => Ok(()),
+        "layout" => import_layout(&mut workspace_config, value),
+        "workspace_padding" => {
+          insert_padding(&mut workspace_gaps, "outer_gap", value)
+        }
+        "container_padding" => {
+          insert_padding(&mut workspace_gaps, "inner_gap", value)
+        }
+        "workspace_rules" | "initial_workspace_rules" => {
+          let command = format!("move --workspace {}", command_name(name));
+          self.import_rules(
+            &format!("{path}.{key}"),
+            value,
+            &command,
+            true,
+          );
+          Ok(())
+        }
+        _ => Err(None),
+      };
+
+      if let Err(reason) = result {
+        self.push_unsupported(&format!("`{path}.{key}`"), reason);
+      }
+    }
+
+    if workspace_gaps != self.gaps {
+      workspace_config.insert("gaps".into(), workspace_gaps.into());
+    }
+
+    self.workspaces.push(workspace_config.into());
+    self.workspace_names.push((monitor_index, name.to_string()));
+  }
+
+  fn import_applications_config(&mut self, applications_config: &Value) {
+    match applications_config {
+      // Older versions of komorebi use a list of applications with
+      // `float_identifiers` and compatibility `options`.
+      Value::Sequence(applications) => {
+        for application in applications {
+          self.import_legacy_application(application);
+        }
+      }
+      Value::Mapping(applications) => {
+        for (name, application) in applications {
+          let name = name.as_str().unwrap_or_default();
+
+          for (category, rules) in
+            application.as_mapping().into_iter().flatten()
+          {
+            let category = category.as_str().unwrap_or_default();
+            let path = format!("{name}.{category}");
+
+            match category {
+              "ignore" => self.import_rules(&path, rules, "ignore", false),
+              "float" | "floating" => {
+                self.import_rules(&path, rules, "set-floating", false);
+              }
+              _ => self.skip_option(category),
+            }
+          }
+        }
+      }
+      _ => self
+        .unsupported
+        .push("app-specific config is not a list or object".into()),
+    }
+  }
+
+  fn import_legacy_application(&mut self, application: &Value) {
+    let name = application
+      .get("name")
+      .and_then(Value::as_str)
+      .unwrap_or_default();
+
+    if let Some(rules) = application.get("float_identifiers") {
+      self.import_rules(
+        &format!("{name}.float_identifiers"),
+        rules,
+        "set-floating",
+        false,
+      );
+    }
+
+    for option in application
+      .get("options")
+      .and_then(Value::as_sequence)
+      .into_iter()
+      .flatten()
+      .filter_map(Value::as_str)
+    {
+      self.skip_option(option);
+    }
+  }
+
+  /// Converts the keybindings of a whkd config. Bindings that run
+  /// `komorebic` are converted to the equivalent commands, and any other
+  /// bindings to `shell-exec`.
+  fn import_whkdrc(
+    &mut self,
+    whkdrc: &str,
+    is_valid_key: &impl Fn(&str) -> bool,
+  ) {
+    let mut is_in_app_list = false;
+
+    for (index, line) in whkdrc.lines().enumerate() {
+      let line = match line.split_once(" #") {
+        Some((line, _)) => line.trim(),
+        None => line.trim(),
+      };
+
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+
+      // App-specific bindings are listed within a `[ ... ]` block.
+      if is_in_app_list {
+        is_in_app_list = line != "]";
+        continue;
+      }
+
+      let result = if line.starts_with('.') {
+        Err(Some("no equivalent"))
+      } else if line.ends_with('[') {
+        is_in_app_list = true;
+        Err(Some("app-specific bindings are not supported"))
+      } else {
+        self.import_whkd_binding(line, is_valid_key)
+      };
+
+      if let Err(reason) = result {
+        self.push_unsupported(
+          &format!("whkdrc line {}: `{line}`", index + 1),
+          reason,
+        );
+      }
+    }
+  }
+
+  fn import_whkd_binding(
+    &mut self,
+    line: &str,
+    is_valid_key: &impl Fn(&str) -> bool,
+  ) -> ImportResult {
+    let (combo, command) =
+      line.split_once(':').ok_or(Some("missing command"))?;
+
+    let binding = convert_key_combo(combo, is_valid_key)
+      .ok_or(Some("unrecognized key"))?;
+
+    let commands = self.convert_whkd_command(command.trim())?;
+
+    let mut keybinding = Mapping::new();
+    keybinding.insert("commands".into(), commands.into());
+    keybinding.insert("bindings".into(), vec![binding].into());
+
+    self.keybindings.push(keybinding.into());
+    Ok(())
+  }
+
+  /// Converts the command of a whkd binding to WM commands.
+  fn convert_whkd_command(
+    &self,
+    command: &str,
+  ) -> Result<Vec<String>, Option<&'static str>> {
+    let Some(komorebic_args) = command.strip_prefix("komorebic ") else {
+      return Ok(vec![format!("shell-exec {command}")]);
+    };
+
+    let args = komorebic_args
+      .split_whitespace()
+      .map(|arg| arg.trim_matches('"'))
+      .collect::<Vec<_>>();
+
+    let command = match args.as_slice() {
+      ["focus", direction @ ("left" | "right" | "up" | "down")] => {
+        format!("focus --direction {direction}")
+      }
+      ["move", direction @ ("left" | "right" | "up" | "down")] => {
+        format!("move --direction {direction}")
+      }
+      ["close"] => "close".into(),
+      ["minimize"] => "set-minimized".into(),
+      ["toggle-float"] => "toggle-floating".into(),
+      ["focus-workspace", index] => {
+        format!("activate-workspace-at-index {}", parse_index(index)?)
+      }
+      ["focus-monitor-workspace", monitor, index] => format!(
+        "activate-workspace-at-index {} --monitor {}",
+        parse_index(index)?,
+        parse_index(monitor)?
+      ),
+      ["focus-named-workspace", name] => {
+        format!("focus --workspace {}", command_name(name))
+      }
+      ["cycle-workspace", "next"] => "focus --next-workspace".into(),
+      ["cycle-workspace", "previous"] => "focus --prev-workspace".into(),
+      // Unlike sending, moving to a workspace also focuses it.
+      ["move-to-workspace", index] => {
+        let name = self.workspace_name(index)?;
+        return Ok(vec![
+          format!("move --workspace {name}"),
+          format!("focus --workspace {name}"),
+        ]);
+      }
+      ["move-to-named-workspace", name] => {
+        let name = command_name(name);
+        return Ok(vec![
+          format!("move --workspace {name}"),
+          format!("focus --workspace {name}"),
+        ]);
+      }
+      ["send-to-workspace", index] => {
+        format!("move --workspace {}", self.workspace_name(index)?)
+      }
+      ["send-to-named-workspace", name] => {
+        format!("move --workspace {}", command_name(name))
+      }
+      ["retile"] => "wm-redraw".into(),
+      ["toggle-pause"] => "wm-toggle-pause".into(),
+      ["reload-configuration"] => "wm-reload-config".into(),
+      ["stop"] => "wm-exit".into(),
+      _ => return Err(None),
+    };
+
+    Ok(vec![command])
+  }
+
+  /// Gets the command name of the workspace at the given index.
+  ///
+  /// komorebi targets the workspaces of the focused monitor by index, so
+  /// this only has an equivalent if all workspaces are on one monitor.
+  fn workspace_name(
+    &self,
+    index: &str,
+  ) -> Result<String, Option<&'static str>> {
+    if self
+      .workspace_names
+      .iter()
+      .any(|(monitor, _)| *monitor != 0)
+    {
+      return Err(Some(
+        "workspaces of multiple monitors can only be moved to by name",
+      ));
+    }
+
+    self
+      .workspace_names
+      .get(parse_index(index)?)
+      .map(|(_, name)| command_name(name))
+      .ok_or(Some("no workspace at index"))
+  }
+
+  /// Converts a list of komorebi rules to match configs of the window
+  /// rule with the given command.
+  fn import_rules(
+    &mut self,
+    path: &str,
+    rules: &Value,
+    command: &str,
+    is_manage_only: bool,
+  ) {
+    let Some(rules) = rules.as_sequence() else {
+      self.push_unsupported(&format!("`{path}`"), Some("not an array"));
+      return;
+    };
+
+    for (index, rule) in rules.iter().enumerate() {
+      match convert_rule(rule) {
+        Ok(match_config) => {
+          self.add_rule(command, match_config, is_manage_only);
+        }
+        Err(reason) => {
+          self.push_unsupported(&format!("`{path}[{index}]`"), reason);
+        }
+      }
+    }
+  }
+
+  fn add_rule(
+    &mut self,
+    command: &str,
+    match_config: Value,
+    is_manage_only: bool,
+  ) {
+    match self
+      .window_rules
+      .iter_mut()
+      .find(|window_rule| window_rule.command == command)
+    {
+      Some(window_rule) => window_rule.match_configs.push(match_config),
+      None => self.window_rules.push(WindowRule {
+        command: command.to_string(),
+        match_configs: vec![match_config],
+        is_manage_only,
+      }),
+    }
+  }
+
+  fn skip_option(&mut self, option: &str) {
+    match self
+      .skipped_options
+      .iter_mut()
+      .find(|(skipped, _)| skipped == option)
+    {
+      Some((_, count)) => *count += 1,
+      None => self.skipped_options.push((option.to_string(), 1)),
+    }
+  }
+
+  fn push_unsupported(&mut self, path: &str, reason: Option<&str>) {
+    let reason = reason.map(|reason| format!(" ({reason})"));
+    self
+      .unsupported
+      .push(format!("{path}{}", reason.unwrap_or_default()));
+  }
+
+  fn finish(mut self) -> ConfigImport {
+    for (option, count) in &self.skipped_options {
+      self.unsupported.push(format!(
+        "`{option}` of {count} application(s) (no equivalent)"
+      ));
+    }
+
+    let mut config = Mapping::new();
+
+    if !self.general.is_empty() {
+      config.insert("general".into(), self.general.into());
+    }
+
+    if !self.gaps.is_empty() {
+      config.insert("gaps".into(), self.gaps.into());
+    }
+
+    if !self.workspaces.is_empty() {
+      config.insert("workspaces".into(), self.workspaces.into());
+    }
+
+    let window_rules = self
+      .window_rules
+      .into_iter()
+      .map(|window_rule| {
+        let mut mapping = Mapping::new();
+        mapping
+          .insert("commands".into(), vec![window_rule.command].into());
+        mapping.insert("match".into(), window_rule.match_configs.into());
+
+        if window_rule.is_manage_only {
+          mapping.insert("on".into(), vec!["manage"].into());
+        }
+
+        mapping.into()
+      })
+      .collect::<Vec<Value>>();
+
+    if !window_rules.is_empty() {
+      config.insert("window_rules".into(), window_rules.into());
+    }
+
+    if !self.keybindings.is_empty() {
+      config.insert("keybindings".into(), self.keybindings.into());
+    }
+
+    ConfigImport {
+      config: config.into(),
+      unsupported: self.unsupported,
+      notes: self.notes,
+    }
+  }
+}
+
+/// Reason that an option couldn't be converted, if there's more to say
+/// than it being unsupported.
+type ImportResult = Result<(), Option<&'static str>>;
+
+/// Converts padding in pixels to the given key of a gaps config.
+fn insert_padding(
+  gaps: &mut Mapping,
+  gaps_key: &str,
+  value: &Value,
+) -> ImportResult {
+  let padding = value.as_i64().ok_or(Some("expected a number"))?;
+  let padding = Value::from(format!("{padding}px"));
+
+  let gap = if gaps_key == "outer_gap" {
+    ["top", "right", "bottom", "left"]
+      .into_iter()
+      .map(|side| (side.into(), padding.clone()))
+      .collect::<Mapping>()
+      .into()
+  } else {
+    padding
+  };
+
+  gaps.insert(gaps_key.into(), gap);
+  Ok(())
+}
+
+/// Parses the index of a workspace or monitor in a `komorebic` command.
+fn parse_index(index: &str) -> Result<usize, Option<&'static str>> {
+  index.parse().map_err(|_| Some("invalid index"))
+}
+
+/// Converts a key combination like `alt + shift + h` to the key names of
+/// the user config. Returns `None` if a key isn't recognized.
+fn convert_key_combo(
+  combo: &str,
+  is_valid_key: &impl Fn(&str) -> bool,
+) -> Option<String> {
+  combo
+    .split('+')
+    .map(|key| {
+      let key = match key.trim().to_lowercase().as_str() {
+        "win" => "lwin".to_string(),
+        "return" => "enter".to_string(),
+        key => key.to_string(),
+      };
+
+      is_valid_key(&key).then_some(key)
+    })
+    .collect::<Option<Vec<_>>>()
+    .map(|keys| keys.join("+"))
+}
+
+/// Converts a workspace layout to a tiling direction. Layouts other than
+/// columns, rows, and BSP have no equivalent.
+fn import_layout(
+  workspace_config: &mut Mapping,
+  value: &Value,
+) -> ImportResult {
+  let tiling_direction = match value.as_str() {
+    Some("Columns") => "horizontal",
+    Some("Rows") => "vertical",
+    Some("BSP") => "auto",
+    _ => {
+      return Err(Some("only `Columns`, `Rows`, and `BSP` are supported"))
+    }
+  };
+
+  workspace_config
+    .insert("tiling_direction".into(), tiling_direction.into());
+  Ok(())
+}
+
+/// Converts a komorebi rule to a window match config. A rule is either a
+/// single identifier, or a list of identifiers that all have to match.
+fn convert_rule(rule: &Value) -> Result<Value, Option<&'static str>> {
+  let identifiers = match rule {
+    Value::Sequence(identifiers) => identifiers.iter().collect(),
+    identifier => vec![identifier],
+  };
+
+  let mut match_config = Mapping::new();
+
+  for identifier in identifiers {
+    let (key, match_type) = convert_identifier(identifier)?;
+
+    if match_config.insert(key.into(), match_type).is_some() {
+      return Err(Some(
+        "matching a property more than once is not supported",
+      ));
+    }
+  }
+
+  Ok(match_config.into())
+}
+
+/// Converts a komorebi identifier (e.g. `{ "kind": "Exe", "id":
+/// "firefox.exe" }`) to a match config key and match type.
+fn convert_identifier(
+  identifier: &Value,
+) -> Result<(&'static str, Value), Option<&'static str>> {
+  let kind = identifier.get("kind").and_then(Value::as_str);
+  let id = identifier
+    .get("id")
+    .and_then(Value::as_str)
+    .ok_or(Some("missing id"))?;
+
+  // Rules without a matching strategy use the legacy behavior, which
+  // matches titles partially and other kinds exactly.
+  let strategy = identifier
+    .get("matching_strategy")
+    .and_then(Value::as_str)
+    .unwrap_or("Legacy");
+
+  let key = match kind {
+    Some("Exe") => "window_process",
+    Some("Class") => "window_class",
+    Some("Title") => "window_title",
+    Some("Path") => return Err(Some("matching by path is not supported")),
+    _ => return Err(Some("unknown kind")),
+  };
+
+  // Process names don't include the file extension.
+  let id = match kind {
+    Some("Exe") if strategy != "Regex" => id
+      .get(..id.len().saturating_sub(4))
+      .filter(|_| id.to_lowercase().ends_with(".exe"))
+      .unwrap_or(id),
+    _ => id,
+  };
+
+  let escaped_id = regex::escape(id);
+
+  let (match_type, value) = match strategy {
+    "Legacy" if key == "window_title" => ("includes", id.to_string()),
+    "Legacy" | "Equals" => ("equals", id.to_string()),
+    "Contains" => ("includes", id.to_string()),
+    "DoesNotEqual" => ("not_equals", id.to_string()),
+    "Regex" => ("regex", id.to_string()),
+    "StartsWith" => ("regex", format!("^{escaped_id}")),
+    "EndsWith" => ("regex", format!("{escaped_id}$")),
+    "DoesNotStartWith" => ("not_regex", format!("^{escaped_id}")),
+    "DoesNotEndWith" => ("not_regex", format!("{escaped_id}$")),
+    "DoesNotContain" => ("not_regex", escaped_id),
+    _ => return Err(Some("unknown matching strategy")),
+  };
+
+  let mut match_type_config = Mapping::new();
+  match_type_config.insert(match_type.into(), value.into());
+
+  Ok((key, match_type_config.into()))
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_yaml::Value;
+
+  use super::{import_komorebi_config, ConfigImport};
+
+  const KOMOREBI_CONFIG: &str = r#"
+    {
+      "default_workspace_padding": 10,
+      "default_container_padding": 5,
+      "ignore_rules": [
+        { "kind": "Exe", "id": "Flow.Launcher.exe" },
+        [
+          { "kind": "Class", "id": "Chrome_WidgetWin_1" },
+          { "kind": "Title", "id": "Picture", "matching_strategy": "StartsWith" }
+        ]
+      ],
+      "floating_applications": [
+        { "kind": "Title", "id": "Settings", "matching_strategy": "Equals" },
+        { "kind": "Path", "id": "C:\\Tools\\tool.exe" }
+      ],
+      "monitors": [
+        {
+          "workspaces": [
+            { "name": "I", "layout": "BSP" },
+            {
+              "name": "web browsing",
+              "layout": "Columns",
+              "workspace_padding": 0,
+              "workspace_rules": [{ "kind": "Exe", "id": "firefox.exe" }]
+            }
+          ]
+        }
+      ]
+    }
+  "#;
+
+  const WHKDRC: &str = r#"
+    .shell pwsh
+
+    # Focus windows.
+    alt + h : komorebic focus left
+    alt + shift + l : komorebic move right # Move windows.
+    alt + t : komorebic toggle-float
+    alt + return : wt.exe
+    alt + 1 : komorebic focus-workspace 0
+    alt + shift + 2 : komorebic move-to-workspace 1
+    alt + shift + f : komorebic toggle-monocle
+
+    alt + n [
+      Firefox : echo "firefox"
+    ]
+  "#;
+
+  fn yaml(yaml_str: &str) -> Value {
+    serde_yaml::from_str(yaml_str).unwrap()
+  }
+
+  fn import(whkdrc: Option<&str>) -> ConfigImport {
+    import_komorebi_config(&yaml(KOMOREBI_CONFIG), None, whkdrc, |_| true)
+  }
+
+  #[test]
+  fn converts_rules() {
+    let import = import(None);
+
+    assert_eq!(
+      import.config["window_rules"],
+      yaml(
+        r#"
+          - commands: ["ignore"]
+            match:
+              - window_process: { equals: Flow.Launcher }
+              - window_class: { equals: Chrome_WidgetWin_1 }
+                window_title: { regex: ^Picture }
+          - commands: ["set-floating"]
+            match:
+              - window_title: { equals: Settings }
+          - commands: ["move --workspace web_browsing"]
+            match:
+              - window_process: { equals: firefox }
+            on: ["manage"]
+        "#
+      )
+    );
+
+    assert_eq!(
+      import.unsupported,
+      vec![
+        "`floating_applications[1]` (matching by path is not supported)"
+      ]
+    );
+  }
+
+  #[test]
+  fn converts_workspaces() {
+    let import = import(None);
+
+    assert_eq!(
+      import.config["gaps"],
+      yaml(
+        "
+          outer_gap: { top: 10px, right: 10px, bottom: 10px, left: 10px }
+          inner_gap: 5px
+        "
+      )
+    );
+
+    assert_eq!(
+      import.config["workspaces"],
+      yaml(
+        "
+          - name: I
+            bind_to_monitor: 0
+            tiling_direction: auto
+          - name: web_browsing
+            display_name: web browsing
+            bind_to_monitor: 0
+            tiling_direction: horizontal
+            gaps:
+              outer_gap: { top: 0px, right: 0px, bottom: 0px, left: 0px }
+              inner_gap: 5px
+        "
+      )
+    );
+  }
+
+  #[test]
+  fn converts_whkdrc_bindings() {
+    let import = import(Some(WHKDRC));
+
+    assert_eq!(
+      import.config["keybindings"],
+      yaml(
+        r#"
+          - commands: ["focus --direction left"]
+            bindings: ["alt+h"]
+          - commands: ["move --direction right"]
+            bindings: ["alt+shift+l"]
+          - commands: ["toggle-floating"]
+            bindings: ["alt+t"]
+          - commands: ["shell-exec wt.exe"]
+            bindings: ["alt+enter"]
+          - commands: ["activate-workspace-at-index 0"]
+            bindings: ["alt+1"]
+          - commands:
+              - "move --workspace web_browsing"
+              - "focus --workspace web_browsing"
+            bindings: ["alt+shift+2"]
+        "#
+      )
+    );
+
+    assert_eq!(
+      import.unsupported[1..],
+      [
+        "whkdrc line 2: `.shell pwsh` (no equivalent)",
+        "whkdrc line 11: `alt + shift + f : komorebic toggle-monocle`",
+        "whkdrc line 13: `alt + n [` (app-specific bindings are not \
+         supported)",
+      ]
+    );
+  }
+}
@@ -4,37 +4,71 @@
 mod active_drag;
 mod app_command;
 mod color;
+mod config_file;
+mod config_import;
+mod config_migration;
+mod config_validation;
+mod config_wizard;
 mod delta;
 mod direction;
 mod display_state;
 mod dtos;
+mod focus_assist_mode;
+mod foreign_wm;
+mod gap_value;
+mod i3_import;
 mod ipc;
+mod komorebi_import;
 mod length_value;
+mod media_usage;
 mod opacity_value;
 mod parsed_config;
+mod perf_stats;
 mod point;
+mod process_stats;
 mod rect;
 mod rect_delta;
+mod rule_export;
+mod size_constraints;
 mod tiling_direction;
+mod time_of_day;
 mod utils;
 mod window_state;
 mod wm_event;
+mod zoom_scope;
 
 pub use active_drag::*;
 pub use app_command::*;
 pub use color::*;
+pub use config_file::*;
+pub use config_import::*;
+pub use config_migration::*;
+pub use config_validation::*;
+pub use config_wizard::*;
 pub use delta::*;
 pub use direction::*;
 pub use display_state::*;
 pub use dtos::*;
+pub use focus_assist_mode::*;
+pub use foreign_wm::*;
+pub use gap_value::*;
+pub use i3_import::*;
 pub use ipc::*;
+pub use komorebi_import::*;
 pub use length_value::*;
+pub use media_usage::*;
 pub use opacity_value::*;
 pub use parsed_config::*;
+pub use perf_stats::*;
 pub use point::*;
+pub use process_stats::*;
 pub use rect::*;
 pub use rect_delta::*;
+pub use rule_export::*;
+pub use size_constraints::*;
 pub use tiling_direction::*;
+pub use time_of_day::*;
 pub use utils::*;
 pub use window_state::*;
 pub use wm_event::*;
+pub use zoom_scope::*;
@@ -0,0 +1,12 @@
+/// Media that a window's process is currently playing or capturing.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MediaUsage {
+  pub is_playing_audio: bool,
+  pub is_using_microphone: bool,
+  pub is_using_camera: bool,
+
+  /// Whether the screen or a window is being captured (e.g. when sharing
+  /// the screen in a call).
+  pub is_recording_screen: bool,
+}
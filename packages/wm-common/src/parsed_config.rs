@@ -1,22 +1,49 @@
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-  app_command::InvokeCommand, Color, LengthValue, OpacityValue, RectDelta,
+  app_command::InvokeCommand, Color, FocusAssistMode, LengthUnit,
+  LengthValue, MediaUsage, OpacityValue, RectDelta, TilingDirection,
+  TimeOfDay, ZoomScope,
 };
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct ParsedConfig {
   pub binding_modes: Vec<BindingModeConfig>,
+  pub controllers: ControllersConfig,
   pub gaps: GapsConfig,
   pub general: GeneralConfig,
   pub keybindings: Vec<KeybindingConfig>,
+  pub menus: Vec<MenuConfig>,
+  pub monitor_rules: Vec<MonitorRuleConfig>,
+  pub status_bar: StatusBarConfig,
+  pub timers: Vec<TimerConfig>,
   pub window_behavior: WindowBehaviorConfig,
   pub window_effects: WindowEffectsConfig,
   pub window_rules: Vec<WindowRuleConfig>,
   pub workspaces: Vec<WorkspaceConfig>,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct MonitorRuleConfig {
+  /// Device ID of monitors to match. Compared against the monitor's
+  /// hardware ID, device path, and device name (e.g. `\\.\DISPLAY2`).
+  #[serde(default)]
+  pub match_device_id: Option<String>,
+
+  /// Index of monitors to match, ordered from left-to-right and
+  /// top-to-bottom.
+  #[serde(default)]
+  pub match_index: Option<u32>,
+
+  /// Whether the WM should manage matching monitors. Unmanaged monitors
+  /// have no workspaces, and windows on them are ignored.
+  #[serde(default)]
+  pub manage: bool,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct BindingModeConfig {
@@ -30,9 +57,235 @@ pub struct BindingModeConfig {
   /// Keybindings that will be active when the binding mode is active.
   #[serde(default)]
   pub keybindings: Vec<KeybindingConfig>,
+
+  /// Whether to show the sizes of the focused container and its
+  /// siblings, along with the binding mode's keybindings, while the
+  /// binding mode is active.
+  #[serde(default)]
+  pub resize_guides: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct MenuConfig {
+  /// Unique name of the menu. Used to show the menu via the
+  /// `wm-show-menu` command.
+  pub name: String,
+
+  /// Title shown at the top of the menu. Defaults to the name.
+  #[serde(default)]
+  pub title: Option<String>,
+
+  pub items: Vec<MenuItemConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct MenuItemConfig {
+  /// Key that selects the item.
+  pub key: char,
+
+  pub label: String,
+
+  /// WM commands to run when the item is selected. Shell commands can be
+  /// run via `shell-exec`.
+  ///
+  /// Kept as strings, since they're run the same way as commands from
+  /// the command palette.
+  #[serde(default)]
+  pub commands: Vec<String>,
+
+  /// Items of a nested menu to open when the item is selected.
+  #[serde(default)]
+  pub items: Vec<MenuItemConfig>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct ControllersConfig {
+  /// HID devices (e.g. Stream Decks and macro pads) to map buttons of.
+  pub hid: Vec<HidControllerConfig>,
+
+  /// MIDI input devices to map notes and controls of.
+  pub midi: Vec<MidiControllerConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct HidControllerConfig {
+  /// USB vendor ID of the device (e.g. `0x0fd9`).
+  pub vendor_id: u16,
+
+  /// USB product ID of the device. Matches any product of the vendor if
+  /// not set.
+  #[serde(default)]
+  pub product_id: Option<u16>,
+
+  /// HID usage page of the device's top-level collection.
+  pub usage_page: u16,
+
+  /// HID usage of the device's top-level collection.
+  pub usage: u16,
+
+  #[serde(default)]
+  pub buttons: Vec<HidButtonConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct HidButtonConfig {
+  /// Index of the byte in the input report that holds the button state.
+  /// The first byte is the report ID.
+  pub byte: usize,
+
+  /// Bits of the byte that are set while the button is pressed.
+  #[serde(default = "default_hid_button_mask")]
+  pub mask: u8,
+
+  /// WM commands to run when the button is pressed.
+  pub commands: Vec<InvokeCommand>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct MidiControllerConfig {
+  /// Case-insensitive substring of the MIDI input device's name.
+  pub device: String,
+
+  #[serde(default)]
+  pub notes: Vec<MidiNoteConfig>,
+
+  #[serde(default)]
+  pub controls: Vec<MidiControlConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct MidiNoteConfig {
+  /// Note number (0-127).
+  pub note: u8,
+
+  /// MIDI channel (1-16). Matches any channel if not set.
+  #[serde(default)]
+  pub channel: Option<u8>,
+
+  /// WM commands to run when the note is pressed (i.e. on note on).
+  pub commands: Vec<InvokeCommand>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct MidiControlConfig {
+  /// Control change number (0-127) of the fader or knob.
+  pub control: u8,
+
+  /// MIDI channel (1-16). Matches any channel if not set.
+  #[serde(default)]
+  pub channel: Option<u8>,
+
+  /// WM commands to run each time the control's value increases.
+  #[serde(default)]
+  pub increase_commands: Vec<InvokeCommand>,
+
+  /// WM commands to run each time the control's value decreases.
+  #[serde(default)]
+  pub decrease_commands: Vec<InvokeCommand>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct TimerConfig {
+  /// Unique name of the timer. Used to control the timer via commands.
+  pub name: String,
+
+  /// WM commands to run when the timer fires. Shell commands can be run
+  /// via `shell-exec`.
+  pub commands: Vec<InvokeCommand>,
+
+  /// Interval in seconds between runs of the timer.
+  #[serde(default)]
+  pub interval_secs: Option<u64>,
+
+  /// Local times of day to run the timer at.
+  #[serde(default)]
+  pub at: Vec<TimeOfDay>,
+
+  /// Whether the timer is enabled on startup. Disabled timers can be
+  /// enabled via the `wm-enable-timer` command.
+  #[serde(default = "default_bool::<true>")]
+  pub enabled: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct StatusBarConfig {
+  /// Whether to show the built-in status bar on each monitor.
+  pub enabled: bool,
+
+  /// Edge of the monitor to show the bar at. The bar reserves space on
+  /// this edge, so windows aren't placed behind it.
+  pub position: StatusBarPosition,
+
+  /// Height of the bar. Scaled with the DPI of the monitor.
+  pub height: LengthValue,
+
+  /// Font family of the bar's text.
+  pub font_family: String,
+
+  /// Font size of the bar's text. Scaled with the DPI of the monitor.
+  pub font_size: LengthValue,
+
+  /// Background color of the bar.
+  pub background: Color,
+
+  /// Color of the bar's text.
+  pub foreground: Color,
+
+  /// Color of the displayed workspace and the active binding mode.
+  pub accent: Color,
+}
+
+impl Default for StatusBarConfig {
+  fn default() -> Self {
+    StatusBarConfig {
+      enabled: false,
+      position: StatusBarPosition::Top,
+      height: LengthValue::from_px(28),
+      font_family: "Segoe UI".to_string(),
+      font_size: LengthValue::from_px(13),
+      background: Color {
+        r: 24,
+        g: 24,
+        b: 24,
+        a: 255,
+      },
+      foreground: Color {
+        r: 220,
+        g: 220,
+        b: 220,
+        a: 255,
+      },
+      accent: Color {
+        r: 140,
+        g: 180,
+        b: 250,
+        a: 255,
+      },
+    }
+  }
+}
+
+#[derive(
+  Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusBarPosition {
+  #[default]
+  Top,
+  Bottom,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct GapsConfig {
   /// Whether to scale the gaps with the DPI of the monitor.
@@ -47,6 +300,14 @@ pub struct GapsConfig {
   /// Gap between window and the screen edge if there is only one window
   /// in the workspace
   pub single_window_outer_gap: Option<RectDelta>,
+
+  /// Whether to remove gaps when there is only one tiling window in the
+  /// workspace. Takes precedence over `single_window_outer_gap`.
+  pub smart: bool,
+
+  /// Whether to also remove the window border effect when gaps are
+  /// removed by `smart`.
+  pub smart_borders: bool,
 }
 
 impl Default for GapsConfig {
@@ -61,10 +322,33 @@ impl Default for GapsConfig {
         LengthValue::from_px(0),
       ),
       single_window_outer_gap: None,
+      smart: false,
+      smart_borders: false,
     }
   }
 }
 
+impl GapsConfig {
+  /// Gets the gap between windows and the screen edge for a workspace,
+  /// depending on whether it has a single tiling window.
+  #[must_use]
+  pub fn effective_outer_gap(&self, is_single_window: bool) -> RectDelta {
+    match (is_single_window, &self.single_window_outer_gap) {
+      (true, _) if self.smart => RectDelta::new(
+        LengthValue::from_px(0),
+        LengthValue::from_px(0),
+        LengthValue::from_px(0),
+        LengthValue::from_px(0),
+      ),
+      (true, Some(single_window_outer_gap)) => {
+        single_window_outer_gap.clone()
+      }
+      _ => self.outer_gap.clone(),
+    }
+  }
+}
+
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct GeneralConfig {
@@ -74,6 +358,10 @@ pub struct GeneralConfig {
   /// Whether to automatically focus windows underneath the cursor.
   pub focus_follows_cursor: bool,
 
+  /// Where directional focus wraps to when there's no monitor in the
+  /// given direction.
+  pub focus_wrapping: FocusWrapping,
+
   /// Whether to switch back and forth between the previously focused
   /// workspace when focusing the current workspace.
   pub toggle_workspace_on_refocus: bool,
@@ -93,6 +381,75 @@ pub struct GeneralConfig {
 
   /// Affects which windows get shown in the native Windows taskbar.
   pub show_all_in_taskbar: bool,
+
+  /// What to do when a window on a hidden workspace is activated by
+  /// something other than the WM (e.g. clicking its taskbar button or a
+  /// notification).
+  pub hidden_window_activation: HiddenWindowActivation,
+
+  /// Whether to replace the built-in alt-tab with the window switcher,
+  /// which only lists windows on the focused workspace.
+  pub replace_alt_tab: bool,
+
+  /// Whether new windows are moved to a workspace of their own
+  /// application, which is created on demand and named after the
+  /// application's process.
+  pub workspace_per_app: bool,
+
+  /// Delay in milliseconds to wait for further display changes before
+  /// updating monitors. Docking and undocking often trigger a burst of
+  /// display changes, which are coalesced into a single update. Set to 0
+  /// to update monitors immediately.
+  pub display_change_debounce_ms: u64,
+
+  /// Config for on-screen notifications shown by the WM.
+  pub notifications: NotificationsConfig,
+
+  /// Config for the localhost HTTP server that exposes the WM state for
+  /// debugging and visualization tools.
+  pub http_server: HttpServerConfig,
+
+  /// Number of recent WM events to keep for replaying to IPC clients
+  /// that subscribe with `--replay-from`.
+  pub ipc_event_buffer_size: usize,
+
+  /// Config for running alongside other window managers.
+  pub coexistence: CoexistenceConfig,
+
+  /// Config for pausing parts of the WM while a game is focused.
+  pub game_mode: GameModeConfig,
+
+  /// Which workspaces windows that are already open when the WM starts
+  /// are assigned to.
+  pub startup_window_assignment: StartupWindowAssignment,
+
+  /// Whether to ask for confirmation before closing all windows on a
+  /// workspace via `close-workspace-windows`.
+  pub confirm_close_workspace_windows: bool,
+
+  /// Config for detecting when the user is away from the keyboard.
+  pub idle: IdleConfig,
+
+  /// Config for screen reader announcements and for adapting to the
+  /// system high-contrast and reduced-motion settings.
+  pub accessibility: AccessibilityConfig,
+
+  /// Ratios that `cycle-ratio` cycles the focused container's share of
+  /// its parent through, in order.
+  pub cycle_ratios: Vec<LengthValue>,
+
+  /// Whether to include the CPU and memory usage of each window's
+  /// process in IPC window queries. Stats are gathered on each query.
+  pub window_process_stats: bool,
+
+  /// Interval in milliseconds for checking which windows are playing
+  /// audio or using the microphone, camera, or screen capture. Set to 0
+  /// to disable media detection.
+  pub media_poll_interval_ms: u64,
+
+  /// Names of commands that can be run via `glazewm://command/...` URLs
+  /// (e.g. `focus`). No commands can be run via URLs by default.
+  pub url_commands: Vec<String>,
 }
 
 impl Default for GeneralConfig {
@@ -100,12 +457,257 @@ impl Default for GeneralConfig {
     GeneralConfig {
       cursor_jump: CursorJumpConfig::default(),
       focus_follows_cursor: false,
+      focus_wrapping: FocusWrapping::None,
       toggle_workspace_on_refocus: true,
       startup_commands: vec![],
       shutdown_commands: vec![],
       config_reload_commands: vec![],
       hide_method: HideMethod::Cloak,
       show_all_in_taskbar: false,
+      hidden_window_activation: HiddenWindowActivation::default(),
+      replace_alt_tab: false,
+      workspace_per_app: false,
+      display_change_debounce_ms: 250,
+      notifications: NotificationsConfig::default(),
+      http_server: HttpServerConfig::default(),
+      ipc_event_buffer_size: 100,
+      coexistence: CoexistenceConfig::default(),
+      game_mode: GameModeConfig::default(),
+      startup_window_assignment: StartupWindowAssignment::default(),
+      confirm_close_workspace_windows: true,
+      idle: IdleConfig::default(),
+      accessibility: AccessibilityConfig::default(),
+      cycle_ratios: [0.33, 0.5, 0.67]
+        .into_iter()
+        .map(|amount| LengthValue {
+          amount,
+          unit: LengthUnit::Percentage,
+        })
+        .collect(),
+      window_process_stats: false,
+      media_poll_interval_ms: 1000,
+      url_commands: vec![],
+    }
+  }
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct AccessibilityConfig {
+  /// Whether to announce WM actions. Announcements are read by the
+  /// running screen reader, or spoken with the system voice if there is
+  /// none.
+  pub announcements: bool,
+
+  /// Whether to announce the newly focused window.
+  pub announce_focus_changes: bool,
+
+  /// Whether to announce the newly focused workspace when switching
+  /// workspaces.
+  pub announce_workspace_switches: bool,
+
+  /// Whether to announce when a binding mode is enabled or disabled.
+  pub announce_binding_mode_changes: bool,
+
+  /// Whether to adapt window effects to high-contrast themes. Borders
+  /// use the theme's colors, windows are kept opaque, and a thicker
+  /// frame is drawn around the focused window.
+  pub high_contrast: SystemSettingOverride,
+
+  /// Whether to disable window animations for reduced motion.
+  pub reduced_motion: SystemSettingOverride,
+
+  /// Thickness of the frame around the focused window while adapting to
+  /// a high-contrast theme. Set to 0 to only use the window border.
+  pub focus_frame_thickness: LengthValue,
+
+  /// Magnification used by `toggle-zoom` (e.g. 2.0 for 200%).
+  pub zoom_factor: f32,
+
+  /// What the magnified region follows while zoomed in via
+  /// `toggle-zoom`.
+  pub zoom_scope: ZoomScope,
+}
+
+impl Default for AccessibilityConfig {
+  fn default() -> Self {
+    AccessibilityConfig {
+      announcements: false,
+      announce_focus_changes: true,
+      announce_workspace_switches: true,
+      announce_binding_mode_changes: true,
+      high_contrast: SystemSettingOverride::Auto,
+      reduced_motion: SystemSettingOverride::Auto,
+      focus_frame_thickness: LengthValue::from_px(4),
+      zoom_factor: 2.,
+      zoom_scope: ZoomScope::Window,
+    }
+  }
+}
+
+/// Whether to apply an accessibility adjustment, either following the
+/// corresponding system setting or overriding it.
+#[derive(
+  Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemSettingOverride {
+  /// Follow the system setting.
+  #[default]
+  Auto,
+  Enabled,
+  Disabled,
+}
+
+impl SystemSettingOverride {
+  /// Resolves whether the adjustment applies, given whether the system
+  /// setting is currently on.
+  #[must_use]
+  pub fn resolve(self, is_system_enabled: bool) -> bool {
+    match self {
+      Self::Auto => is_system_enabled,
+      Self::Enabled => true,
+      Self::Disabled => false,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct IdleConfig {
+  /// Whether to emit `user_idle` and `user_active` events and run the
+  /// idle commands.
+  pub enabled: bool,
+
+  /// Number of seconds without keyboard or mouse input after which the
+  /// user is considered idle.
+  pub threshold_secs: u64,
+
+  /// Commands to run when the user becomes idle.
+  pub idle_commands: Vec<InvokeCommand>,
+
+  /// Commands to run when the user returns after being idle.
+  pub active_commands: Vec<InvokeCommand>,
+}
+
+impl Default for IdleConfig {
+  fn default() -> Self {
+    IdleConfig {
+      enabled: false,
+      threshold_secs: 300,
+      idle_commands: Vec::new(),
+      active_commands: Vec::new(),
+    }
+  }
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct GameModeConfig {
+  /// Whether to automatically enter game mode when a game is focused.
+  pub enabled: bool,
+
+  /// Process names (without the `.exe` extension) that are always
+  /// treated as games.
+  pub process_names: Vec<String>,
+
+  /// Whether to treat borderless windows that cover their entire monitor
+  /// as games.
+  pub detect_fullscreen: bool,
+
+  /// Whether to pause repositioning and focusing windows.
+  pub pause_tiling: bool,
+
+  /// Whether to pause updating window effects (e.g. borders).
+  pub pause_effects: bool,
+
+  /// Whether to pause keybindings, except for `wm-toggle-pause`.
+  pub pause_keybindings: bool,
+}
+
+impl Default for GameModeConfig {
+  fn default() -> Self {
+    GameModeConfig {
+      enabled: false,
+      process_names: Vec::new(),
+      detect_fullscreen: true,
+      pause_tiling: true,
+      pause_effects: true,
+      pause_keybindings: true,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct CoexistenceConfig {
+  /// Whether to warn on startup when another window manager or window
+  /// snapping tool (e.g. komorebi) is running.
+  pub detect_foreign_wms: bool,
+
+  /// Indices of monitors to manage windows on. Windows on other monitors
+  /// are left to other window managers. Empty to manage all monitors.
+  pub managed_monitors: Vec<u32>,
+
+  /// Names of workspaces to manage windows on. Windows that open on
+  /// other workspaces are left to other window managers. Empty to manage
+  /// all workspaces.
+  pub managed_workspaces: Vec<String>,
+}
+
+impl Default for CoexistenceConfig {
+  fn default() -> Self {
+    CoexistenceConfig {
+      detect_foreign_wms: true,
+      managed_monitors: Vec::new(),
+      managed_workspaces: Vec::new(),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct HttpServerConfig {
+  /// Whether to start the HTTP server.
+  pub enabled: bool,
+
+  /// Port to listen on. Only connections from localhost are accepted.
+  pub port: u16,
+}
+
+impl Default for HttpServerConfig {
+  fn default() -> Self {
+    HttpServerConfig {
+      enabled: false,
+      port: 6124,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct NotificationsConfig {
+  /// Whether to show a notification when the config has problems (e.g.
+  /// unrecognized keys or invalid regexes).
+  pub config_errors: bool,
+
+  /// Whether to show a notification when a binding mode is enabled or
+  /// disabled.
+  pub binding_mode_changes: bool,
+
+  /// Whether to show a notification whenever a window rule matches. Meant
+  /// for debugging window rules.
+  pub debug_window_rules: bool,
+}
+
+impl Default for NotificationsConfig {
+  fn default() -> Self {
+    NotificationsConfig {
+      config_errors: true,
+      binding_mode_changes: false,
+      debug_window_rules: false,
     }
   }
 }
@@ -130,10 +732,65 @@ pub enum CursorJumpTrigger {
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
+pub enum HiddenWindowActivation {
+  /// Switch to the workspace of the activated window.
+  #[default]
+  SwitchWorkspace,
+
+  /// Move the activated window to the focused workspace.
+  MoveWindow,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusWrapping {
+  /// Focus stays put at the edges of the outermost monitors.
+  #[default]
+  None,
+
+  /// Wrap around to the opposite side of the current workspace.
+  Workspace,
+
+  /// Wrap around to the furthest monitor in the opposite direction that
+  /// is in line with the current monitor.
+  Monitor,
+
+  /// Wrap around to the furthest monitor in the opposite direction, even
+  /// if it's not in line with the current monitor.
+  Global,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupWindowAssignment {
+  /// Keep windows on the displayed workspace of their current monitor.
+  /// Window rules are still run, but can't move windows to another
+  /// workspace.
+  Monitor,
+
+  /// Run window rules as if the windows were newly created, so that rules
+  /// can move them to other workspaces. Windows that aren't moved by a
+  /// rule stay on their current monitor.
+  #[default]
+  Rules,
+
+  /// Gather all windows onto the displayed workspace of the monitor with
+  /// the focused window.
+  ActiveWorkspace,
+}
+
+#[derive(
+  Clone, Debug, Default, Deserialize, PartialEq, Serialize, ValueEnum,
+)]
+#[clap(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
 pub enum HideMethod {
   Hide,
   #[default]
   Cloak,
+  /// Moves the window just past the edge of the screen, so that it
+  /// keeps rendering and can still be found via alt-tab.
+  Offscreen,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -144,9 +801,17 @@ pub struct KeybindingConfig {
 
   /// WM commands to run when the keybinding is triggered.
   pub commands: Vec<InvokeCommand>,
+
+  /// WM commands to run when the keybinding is released after being
+  /// held down (e.g. `unpeek`).
+  pub release_commands: Vec<InvokeCommand>,
+
+  /// Whether the key press should still be sent to the focused
+  /// application after the commands are run.
+  pub passthrough: bool,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct WindowBehaviorConfig {
   /// New windows are created in this state whenever possible.
@@ -156,6 +821,26 @@ pub struct WindowBehaviorConfig {
   /// changes the defaults for when the state change commands, like
   /// `set_floating`, are used without any flags.
   pub state_defaults: WindowStateDefaultsConfig,
+
+  /// Whether to restore floating windows to the last position and size
+  /// of the same application.
+  pub remember_floating_geometry: bool,
+
+  /// Whether windows of processes launched via `shell-exec` are opened
+  /// on the workspace that was focused at launch, even if another
+  /// workspace has since been focused.
+  pub open_on_launch_workspace: bool,
+}
+
+impl Default for WindowBehaviorConfig {
+  fn default() -> Self {
+    WindowBehaviorConfig {
+      initial_state: InitialWindowState::default(),
+      state_defaults: WindowStateDefaultsConfig::default(),
+      remember_floating_geometry: true,
+      open_on_launch_workspace: false,
+    }
+  }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -211,7 +896,7 @@ impl Default for FullscreenStateConfig {
   }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct WindowEffectsConfig {
   /// Visual effects to apply to the focused window.
@@ -219,9 +904,63 @@ pub struct WindowEffectsConfig {
 
   /// Visual effects to apply to non-focused windows.
   pub other_windows: WindowEffectConfig,
+
+  /// Visual effects to apply to windows selected via `select-add`. Takes
+  /// precedence over the focused and non-focused window effects.
+  pub selected_windows: SelectedWindowsEffectConfig,
+
+  /// Visual effects to apply to windows that are playing audio. Takes
+  /// precedence over the focused and non-focused window effects.
+  pub audio_windows: AudioWindowsEffectConfig,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct SelectedWindowsEffectConfig {
+  /// Config for applying a colored border.
+  pub border: BorderEffectConfig,
+}
+
+impl Default for SelectedWindowsEffectConfig {
+  fn default() -> Self {
+    SelectedWindowsEffectConfig {
+      border: BorderEffectConfig {
+        enabled: true,
+        color: Color {
+          r: 255,
+          g: 184,
+          b: 108,
+          a: 255,
+        },
+      },
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct AudioWindowsEffectConfig {
+  /// Config for optionally applying a colored border.
+  pub border: BorderEffectConfig,
+}
+
+impl Default for AudioWindowsEffectConfig {
+  fn default() -> Self {
+    AudioWindowsEffectConfig {
+      border: BorderEffectConfig {
+        enabled: false,
+        color: Color {
+          r: 80,
+          g: 250,
+          b: 123,
+          a: 255,
+        },
+      },
+    }
+  }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct WindowEffectConfig {
   /// Config for optionally applying a colored border.
@@ -237,7 +976,7 @@ pub struct WindowEffectConfig {
   pub transparency: TransparencyEffectConfig,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct BorderEffectConfig {
   /// Whether to enable the effect.
@@ -261,14 +1000,14 @@ impl Default for BorderEffectConfig {
   }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct HideTitleBarEffectConfig {
   /// Whether to enable the effect.
   pub enabled: bool,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct CornerEffectConfig {
   /// Whether to enable the effect.
@@ -288,7 +1027,7 @@ pub enum CornerStyle {
   SmallRounded,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct TransparencyEffectConfig {
   /// Whether to enable the effect.
@@ -301,6 +1040,7 @@ pub struct TransparencyEffectConfig {
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct WindowRuleConfig {
+  #[serde(default)]
   pub commands: Vec<InvokeCommand>,
 
   #[serde(rename = "match")]
@@ -311,6 +1051,16 @@ pub struct WindowRuleConfig {
 
   #[serde(default = "default_bool::<true>")]
   pub run_once: bool,
+
+  /// Keybindings to disable while a matching window is focused, or
+  /// `all` to disable all keybindings.
+  #[serde(default)]
+  pub disable_keybindings: Vec<String>,
+
+  /// Name of the workspace that matching windows are moved back to via
+  /// the `send-home` and `gather-windows` commands.
+  #[serde(default)]
+  pub home_workspace: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -319,6 +1069,62 @@ pub struct WindowMatchConfig {
   pub window_process: Option<MatchType>,
   pub window_class: Option<MatchType>,
   pub window_title: Option<MatchType>,
+
+  /// Whether the window's process has to be playing audio (or not).
+  pub is_playing_audio: Option<bool>,
+
+  /// Whether the window's process has to be using the microphone (or
+  /// not).
+  pub is_using_microphone: Option<bool>,
+
+  /// Whether the window's process has to be using the camera (or not).
+  pub is_using_camera: Option<bool>,
+
+  /// Whether the window's process has to be capturing the screen (or
+  /// not).
+  pub is_recording_screen: Option<bool>,
+}
+
+impl WindowMatchConfig {
+  /// Whether a window with the given process, class, and title matches.
+  #[must_use]
+  pub fn is_match(
+    &self,
+    window_process: &str,
+    window_class: &str,
+    window_title: &str,
+  ) -> bool {
+    let is_process_match = self
+      .window_process
+      .as_ref()
+      .is_none_or(|match_type| match_type.is_match(window_process));
+
+    let is_class_match = self
+      .window_class
+      .as_ref()
+      .is_none_or(|match_type| match_type.is_match(window_class));
+
+    let is_title_match = self
+      .window_title
+      .as_ref()
+      .is_none_or(|match_type| match_type.is_match(window_title));
+
+    is_process_match && is_class_match && is_title_match
+  }
+
+  /// Whether a window with the given media usage matches. Always `true`
+  /// if there are no media usage conditions.
+  #[must_use]
+  pub fn is_media_usage_match(&self, media_usage: MediaUsage) -> bool {
+    [
+      (self.is_playing_audio, media_usage.is_playing_audio),
+      (self.is_using_microphone, media_usage.is_using_microphone),
+      (self.is_using_camera, media_usage.is_using_camera),
+      (self.is_recording_screen, media_usage.is_recording_screen),
+    ]
+    .into_iter()
+    .all(|(condition, value)| condition.is_none_or(|cond| cond == value))
+  }
 }
 
 /// Due to limitations in `serde_yaml`, we need to use an untagged enum
@@ -341,13 +1147,13 @@ impl MatchType {
     match self {
       MatchType::Equals { equals } => value == equals,
       MatchType::Includes { includes } => value.contains(includes),
-      MatchType::Regex { regex } => regex::Regex::new(regex)
-        .map(|re| re.is_match(value))
-        .unwrap_or(false),
+      MatchType::Regex { regex } => {
+        regex::Regex::new(regex).is_ok_and(|re| re.is_match(value))
+      }
       MatchType::NotEquals { not_equals } => value != not_equals,
-      MatchType::NotRegex { not_regex } => regex::Regex::new(not_regex)
-        .map(|re| !re.is_match(value))
-        .unwrap_or(false),
+      MatchType::NotRegex { not_regex } => {
+        regex::Regex::new(not_regex).is_ok_and(|re| !re.is_match(value))
+      }
     }
   }
 }
@@ -363,9 +1169,13 @@ pub enum WindowRuleEvent {
 
   /// When the title of a window changes.
   TitleChange,
+
+  /// When a window starts or stops playing audio or using the
+  /// microphone, camera, or screen capture.
+  MediaUsageChange,
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct WorkspaceConfig {
   pub name: String,
@@ -378,6 +1188,227 @@ pub struct WorkspaceConfig {
 
   #[serde(default = "default_bool::<false>")]
   pub keep_alive: bool,
+
+  /// Overrides the gaps config for the workspace.
+  #[serde(default)]
+  pub gaps: Option<GapsConfig>,
+
+  /// Overrides the window effects config for windows in the workspace.
+  #[serde(default)]
+  pub window_effects: Option<WindowEffectsConfig>,
+
+  /// Tiling direction of the workspace when it's activated. Defaults to
+  /// the orientation of the monitor.
+  #[serde(default)]
+  pub tiling_direction: Option<WorkspaceTilingDirection>,
+
+  /// Whether new windows in the workspace are tiled or floating.
+  #[serde(default)]
+  pub layout: WorkspaceLayout,
+
+  /// Area of the monitor to keep unused by the workspace.
+  #[serde(default)]
+  pub reserve: Option<WorkspaceReserveConfig>,
+
+  /// Arrangement of containers that matching windows are placed into
+  /// when they're added to the workspace.
+  #[serde(default)]
+  pub template: Option<WorkspaceTemplateConfig>,
+
+  /// Column on an edge of the workspace that is reserved for a specific
+  /// application.
+  #[serde(default)]
+  pub dock: Option<WorkspaceDockConfig>,
+
+  /// Maximum width of each column when tiling horizontally. Columns
+  /// narrower than the workspace are centered. Percentages are relative
+  /// to the monitor's width.
+  #[serde(default)]
+  pub max_column_width: Option<LengthValue>,
+
+  /// Whether columns are laid out on a horizontally scrolling strip,
+  /// where the view pans to keep the focused column visible.
+  #[serde(default = "default_bool::<false>")]
+  pub scrolling: bool,
+
+  /// Maximum number of tiling windows shown in the workspace. Additional
+  /// windows are minimized into an overflow stack.
+  #[serde(default)]
+  pub max_visible_tiles: Option<usize>,
+
+  /// Keybindings to disable while the workspace is focused, or `all` to
+  /// disable all keybindings.
+  #[serde(default)]
+  pub disable_keybindings: Vec<String>,
+
+  /// Focus Assist profile to enable while the workspace is focused. The
+  /// previous profile is restored once another workspace is focused.
+  #[serde(default)]
+  pub focus_assist: Option<FocusAssistMode>,
+
+  /// Whether windows in the workspace keep their taskbar buttons while
+  /// the workspace is hidden. Overrides `general.show_all_in_taskbar`.
+  #[serde(default)]
+  pub show_all_in_taskbar: Option<bool>,
+
+  /// Whether the workspace is displayed on its monitor when the WM is
+  /// started, instead of the first available workspace.
+  #[serde(default = "default_bool::<false>")]
+  pub active_on_startup: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct WorkspaceReserveConfig {
+  /// Side of the monitor to keep unused.
+  pub side: ReserveSide,
+
+  /// Size of the unused area. Percentages are relative to the monitor's
+  /// width or height.
+  pub amount: LengthValue,
+}
+
+impl WorkspaceReserveConfig {
+  /// Gets the reserved area as a delta to shrink the workspace by.
+  #[must_use]
+  pub fn to_rect_delta(&self) -> RectDelta {
+    let none = LengthValue {
+      amount: 0.,
+      unit: self.amount.unit.clone(),
+    };
+
+    let half = LengthValue {
+      amount: self.amount.amount / 2.,
+      unit: self.amount.unit.clone(),
+    };
+
+    match self.side {
+      ReserveSide::Left => RectDelta::new(
+        self.amount.clone(),
+        none.clone(),
+        none.clone(),
+        none,
+      ),
+      ReserveSide::Right => RectDelta::new(
+        none.clone(),
+        none.clone(),
+        self.amount.clone(),
+        none,
+      ),
+      ReserveSide::Top => RectDelta::new(
+        none.clone(),
+        self.amount.clone(),
+        none.clone(),
+        none,
+      ),
+      ReserveSide::Bottom => RectDelta::new(
+        none.clone(),
+        none.clone(),
+        none,
+        self.amount.clone(),
+      ),
+      ReserveSide::Center => {
+        RectDelta::new(half.clone(), none.clone(), half, none)
+      }
+    }
+  }
+}
+
+/// Node of a workspace template. Nodes without children are slots that
+/// are filled by the first matching window.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct WorkspaceTemplateConfig {
+  /// Tiling direction of the children. Defaults to the workspace's
+  /// direction for the root node, and to the inverse of the parent's
+  /// direction otherwise.
+  pub direction: Option<TilingDirection>,
+
+  /// Size of the node as a percentage of its parent. Defaults to an
+  /// equal share of the space left over by its siblings.
+  pub size: Option<LengthValue>,
+
+  /// Windows that can fill the slot.
+  #[serde(rename = "match")]
+  pub match_window: Vec<WindowMatchConfig>,
+
+  /// Label of an empty frame that holds the slot until a matching window
+  /// opens. Frames are only shown once the template is applied via the
+  /// `apply-workspace-template` command.
+  pub placeholder: Option<String>,
+
+  pub children: Vec<WorkspaceTemplateConfig>,
+}
+
+impl WorkspaceTemplateConfig {
+  /// Gets the slots of the template in depth-first order.
+  #[must_use]
+  pub fn slots(&self) -> Vec<&WorkspaceTemplateConfig> {
+    if self.children.is_empty() {
+      return vec![self];
+    }
+
+    self
+      .children
+      .iter()
+      .flat_map(WorkspaceTemplateConfig::slots)
+      .collect()
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct WorkspaceDockConfig {
+  /// Edge of the workspace that the dock is on.
+  pub side: DockSide,
+
+  /// Width of the dock. Percentages are relative to the monitor's width.
+  pub width: LengthValue,
+
+  /// Windows that can be docked. Only the first matching window is
+  /// docked.
+  #[serde(rename = "match")]
+  pub match_window: Vec<WindowMatchConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DockSide {
+  Left,
+  Right,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReserveSide {
+  Left,
+  Right,
+  Top,
+  Bottom,
+  /// Splits the reserved area evenly between the left and right sides,
+  /// so that windows are centered on the monitor.
+  Center,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceLayout {
+  /// New windows are tiled.
+  #[default]
+  Tiling,
+  /// New windows are floating, and are restored to their last-known
+  /// floating geometry.
+  Floating,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceTilingDirection {
+  Horizontal,
+  Vertical,
+  /// New tiling windows split the focused window along its longer axis.
+  /// The workspace itself starts out in the orientation of the monitor.
+  Auto,
 }
 
 /// Helper function for setting a default value for a boolean field.
@@ -385,6 +1416,11 @@ const fn default_bool<const V: bool>() -> bool {
   V
 }
 
+/// Helper function for setting the default mask of HID buttons.
+const fn default_hid_button_mask() -> u8 {
+  0xff
+}
+
 /// Helper function for setting a default value for window rule events.
 fn default_window_rule_on() -> Vec<WindowRuleEvent> {
   vec![WindowRuleEvent::Manage, WindowRuleEvent::TitleChange]
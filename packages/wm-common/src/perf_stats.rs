@@ -0,0 +1,98 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// Performance counters for the WM event loop, accumulated since the WM
+/// was started.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfStats {
+  /// Time taken to handle platform events, keyed by event type (e.g.
+  /// `WindowFocused`).
+  pub platform_events: BTreeMap<String, TimingStats>,
+
+  /// Time taken to run commands (from keybindings, IPC, etc.), including
+  /// the subsequent platform sync.
+  pub commands: TimingStats,
+
+  /// Time taken to sync the container tree to the OS (focus, redraws, and
+  /// window effects).
+  pub platform_sync: TimingStats,
+
+  /// Time taken to compute the positions of windows to redraw.
+  pub layout: TimingStats,
+
+  /// Time taken to redraw windows, including calls to `SetWindowPos`.
+  pub redraw: TimingStats,
+
+  /// Number of windows repositioned per batch. Position changes are
+  /// batched per monitor on each redraw.
+  pub set_position_batch_size: CountStats,
+
+  /// Number of platform events waiting to be handled, sampled whenever
+  /// an event is received.
+  pub event_queue_depth: CountStats,
+}
+
+impl PerfStats {
+  /// Records the time taken to handle a platform event.
+  pub fn record_platform_event(
+    &mut self,
+    event_type: &str,
+    elapsed: Duration,
+  ) {
+    self
+      .platform_events
+      .entry(event_type.to_string())
+      .or_default()
+      .record(elapsed);
+  }
+}
+
+/// Aggregated durations of a recurring operation, in milliseconds.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimingStats {
+  pub count: u64,
+  pub total_ms: f64,
+  pub avg_ms: f64,
+  pub max_ms: f64,
+  pub last_ms: f64,
+}
+
+impl TimingStats {
+  pub fn record(&mut self, elapsed: Duration) {
+    let elapsed_ms = elapsed.as_secs_f64() * 1000.;
+
+    self.count += 1;
+    self.total_ms += elapsed_ms;
+    #[allow(clippy::cast_precision_loss)]
+    let count = self.count as f64;
+    self.avg_ms = self.total_ms / count;
+    self.max_ms = self.max_ms.max(elapsed_ms);
+    self.last_ms = elapsed_ms;
+  }
+}
+
+/// Aggregated values of a recurring count (e.g. a batch size).
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CountStats {
+  pub samples: u64,
+  pub total: u64,
+  pub avg: f64,
+  pub max: usize,
+  pub last: usize,
+}
+
+impl CountStats {
+  pub fn record(&mut self, value: usize) {
+    self.samples += 1;
+    self.total += value as u64;
+    #[allow(clippy::cast_precision_loss)]
+    let avg = self.total as f64 / self.samples as f64;
+    self.avg = avg;
+    self.max = self.max.max(value);
+    self.last = value;
+  }
+}
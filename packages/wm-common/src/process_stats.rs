@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Resource usage of the process that a window belongs to.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessStats {
+  pub process_id: u32,
+
+  /// CPU usage as a percentage of all logical processors. Averaged
+  /// since the process was last sampled, or since it was started if
+  /// this is the first sample.
+  pub cpu_percent: f32,
+
+  /// Physical memory used by the process in bytes.
+  pub working_set_bytes: u64,
+}
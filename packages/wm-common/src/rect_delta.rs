@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use super::LengthValue;
 
-#[derive(Debug, Deserialize, Clone, Serialize)]
+#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct RectDelta {
   /// The delta in x-coordinates on the left of the rectangle.
   pub left: LengthValue,
@@ -0,0 +1,63 @@
+use serde_yaml::{Mapping, Value};
+
+use crate::ExportedWindowRule;
+
+/// Creates a config snippet with the `window_rules` for the given
+/// exported rules.
+///
+/// Rules with the same command are combined into a single window rule
+/// with a match condition for each window.
+#[must_use]
+pub fn exported_rules_config(
+  exported_rules: &[ExportedWindowRule],
+) -> Value {
+  let mut window_rules: Vec<(&str, Vec<Value>)> = Vec::new();
+
+  for exported_rule in exported_rules {
+    let match_config = exported_match_config(exported_rule);
+
+    match window_rules
+      .iter_mut()
+      .find(|(command, _)| *command == exported_rule.command)
+    {
+      Some((_, match_configs)) => {
+        if !match_configs.contains(&match_config) {
+          match_configs.push(match_config);
+        }
+      }
+      None => window_rules
+        .push((exported_rule.command.as_str(), vec![match_config])),
+    }
+  }
+
+  let window_rules = window_rules
+    .into_iter()
+    .map(|(command, match_configs)| {
+      let mut window_rule = Mapping::new();
+      window_rule.insert("commands".into(), vec![command].into());
+      window_rule.insert("match".into(), match_configs.into());
+      window_rule.into()
+    })
+    .collect::<Vec<Value>>();
+
+  let mut config = Mapping::new();
+  config.insert("window_rules".into(), window_rules.into());
+  config.into()
+}
+
+/// Creates a match condition for the exact process and class of the
+/// window.
+fn exported_match_config(exported_rule: &ExportedWindowRule) -> Value {
+  let mut match_config = Mapping::new();
+
+  for (key, value) in [
+    ("window_process", &exported_rule.window_process),
+    ("window_class", &exported_rule.window_class),
+  ] {
+    let mut match_type = Mapping::new();
+    match_type.insert("equals".into(), value.as_str().into());
+    match_config.insert(key.into(), match_type.into());
+  }
+
+  match_config.into()
+}
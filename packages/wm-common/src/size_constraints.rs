@@ -0,0 +1,123 @@
+use anyhow::Context;
+use clap::Args;
+use serde::Serialize;
+
+use crate::{LengthValue, TilingDirection};
+
+/// Size constraints of a tiling window, set via the
+/// `set-size-constraints` command.
+///
+/// Percentages are relative to the size of the window's parent.
+#[derive(Args, Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeConstraints {
+  #[clap(long)]
+  pub min_width: Option<LengthValue>,
+
+  #[clap(long)]
+  pub max_width: Option<LengthValue>,
+
+  #[clap(long)]
+  pub min_height: Option<LengthValue>,
+
+  #[clap(long)]
+  pub max_height: Option<LengthValue>,
+
+  /// Ratio of width to height, either as a number (e.g. `1.78`) or of
+  /// format `<width>:<height>` (e.g. `16:9`).
+  #[clap(long, value_parser = parse_aspect_ratio)]
+  pub aspect_ratio: Option<f32>,
+}
+
+impl SizeConstraints {
+  /// Whether no constraints are set.
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    *self == Self::default()
+  }
+
+  /// Gets the minimum and maximum length of the window along the given
+  /// tiling direction.
+  ///
+  /// `length` is the length available to the window's parent along the
+  /// tiling direction, and `cross_length` is the length in the other
+  /// axis.
+  #[must_use]
+  pub fn length_bounds(
+    &self,
+    tiling_direction: &TilingDirection,
+    length: i32,
+    cross_length: i32,
+  ) -> (i32, i32) {
+    let (min, max) = match tiling_direction {
+      TilingDirection::Horizontal => (&self.min_width, &self.max_width),
+      TilingDirection::Vertical => (&self.min_height, &self.max_height),
+    };
+
+    // A fixed aspect ratio takes precedence over the min and max lengths,
+    // since the window's other side is already determined by its parent.
+    if let Some(aspect_ratio) = self.aspect_ratio {
+      #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation
+      )]
+      let fixed_length = {
+        let cross_length =
+          self.cross_length(tiling_direction, cross_length) as f32;
+
+        match tiling_direction {
+          TilingDirection::Horizontal => cross_length * aspect_ratio,
+          TilingDirection::Vertical => cross_length / aspect_ratio,
+        }
+        .round() as i32
+      };
+
+      return (fixed_length, fixed_length);
+    }
+
+    let min = min.as_ref().map_or(0, |min| min.to_px(length, None));
+    let max = max
+      .as_ref()
+      .map_or(i32::MAX, |max| max.to_px(length, None))
+      .max(min);
+
+    (min, max)
+  }
+
+  /// Gets the length of the window in the axis opposite to the given
+  /// tiling direction, which is capped by its max width or height.
+  #[must_use]
+  pub fn cross_length(
+    &self,
+    tiling_direction: &TilingDirection,
+    cross_length: i32,
+  ) -> i32 {
+    let max = match tiling_direction {
+      TilingDirection::Horizontal => &self.max_height,
+      TilingDirection::Vertical => &self.max_width,
+    };
+
+    max
+      .as_ref()
+      .map_or(cross_length, |max| max.to_px(cross_length, None))
+      .min(cross_length)
+  }
+}
+
+/// Parses an aspect ratio of format `<width>:<height>` or a plain
+/// number.
+fn parse_aspect_ratio(unparsed: &str) -> anyhow::Result<f32> {
+  let aspect_ratio = match unparsed.split_once(':') {
+    Some((width, height)) => {
+      let width = width.trim().parse::<f32>().ok();
+      let height = height.trim().parse::<f32>().ok();
+
+      width.zip(height).map(|(width, height)| width / height)
+    }
+    None => unparsed.trim().parse::<f32>().ok(),
+  };
+
+  aspect_ratio
+    .filter(|ratio| ratio.is_finite() && *ratio > 0.)
+    .with_context(|| format!("Not a valid aspect ratio: {unparsed}"))
+}
@@ -0,0 +1,74 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{bail, Context};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Local time of day with minute precision (e.g. `17:30`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeOfDay {
+  pub hour: u8,
+  pub minute: u8,
+}
+
+impl TimeOfDay {
+  /// Number of seconds between midnight and this time of day.
+  #[must_use]
+  pub fn secs_since_midnight(&self) -> u32 {
+    u32::from(self.hour) * 3600 + u32::from(self.minute) * 60
+  }
+}
+
+impl FromStr for TimeOfDay {
+  type Err = anyhow::Error;
+
+  /// Parses a string for a time of day in 24-hour `HH:MM` format.
+  ///
+  /// Example:
+  /// ```
+  /// # use wm_common::TimeOfDay;
+  /// # use std::str::FromStr;
+  /// let check = TimeOfDay { hour: 9, minute: 5 };
+  /// let parsed = TimeOfDay::from_str("09:05");
+  /// assert_eq!(parsed.unwrap(), check);
+  /// ```
+  fn from_str(unparsed: &str) -> anyhow::Result<Self> {
+    let (hour, minute) = unparsed
+      .trim()
+      .split_once(':')
+      .context("Time of day must be in HH:MM format.")?;
+
+    let hour = hour.parse::<u8>().context("Invalid hour.")?;
+    let minute = minute.parse::<u8>().context("Invalid minute.")?;
+
+    if hour > 23 || minute > 59 {
+      bail!("Time of day '{}' is out of range.", unparsed.trim());
+    }
+
+    Ok(Self { hour, minute })
+  }
+}
+
+impl fmt::Display for TimeOfDay {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:02}:{:02}", self.hour, self.minute)
+  }
+}
+
+impl Serialize for TimeOfDay {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_str(self)
+  }
+}
+
+impl<'de> Deserialize<'de> for TimeOfDay {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let str = String::deserialize(deserializer)?;
+    Self::from_str(&str).map_err(serde::de::Error::custom)
+  }
+}
@@ -1,9 +1,11 @@
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
-  dtos::ContainerDto,
-  parsed_config::{BindingModeConfig, ParsedConfig},
+  app_command::InvokeCommand,
+  dtos::{ContainerDto, FocusedWindowDto},
+  parsed_config::{BindingModeConfig, ParsedConfig, WindowRuleEvent},
   TilingDirection,
 };
 
@@ -15,15 +17,51 @@ use crate::{
 )]
 pub enum WmEvent {
   ApplicationExiting,
+  BindingModeEntered {
+    entered_binding_mode: BindingModeConfig,
+  },
+  BindingModeExited {
+    exited_binding_mode: BindingModeConfig,
+  },
   BindingModesChanged {
     new_binding_modes: Vec<BindingModeConfig>,
   },
+  CustomEvent {
+    name: String,
+    payload: serde_json::Value,
+  },
+  CommandInvoked {
+    source: CommandSource,
+    command: String,
+    subject_container_id: Uuid,
+    success: bool,
+    error: Option<String>,
+  },
+  ForeignWmDetected {
+    /// Display names of the detected window managers.
+    names: Vec<String>,
+  },
   FocusChanged {
     focused_container: ContainerDto,
+    focused_window: Option<FocusedWindowDto>,
   },
   FocusedContainerMoved {
     focused_container: ContainerDto,
   },
+  GameModeChanged {
+    is_active: bool,
+
+    /// Process name of the game window if game mode is active.
+    process_name: Option<String>,
+  },
+  KeyboardLayoutChanged {
+    /// Locale name of the layout's input language (e.g. `en-US`).
+    locale_name: String,
+
+    /// Handle to the keyboard layout (e.g. `0x04090409` for US
+    /// English).
+    layout_handle: isize,
+  },
   MonitorAdded {
     added_monitor: ContainerDto,
   },
@@ -38,10 +76,22 @@ pub enum WmEvent {
     direction_container: ContainerDto,
     new_tiling_direction: TilingDirection,
   },
+  UserActive {
+    /// Number of seconds the user was idle for.
+    idle_secs: u64,
+  },
   UserConfigChanged {
     config_path: String,
     config_string: String,
-    parsed_config: ParsedConfig,
+    parsed_config: Box<ParsedConfig>,
+  },
+  UserIdle {
+    /// Number of seconds since the last user input.
+    idle_secs: u64,
+  },
+  WindowAudioChanged {
+    updated_window: ContainerDto,
+    is_playing_audio: bool,
   },
   WindowManaged {
     managed_window: ContainerDto,
@@ -50,6 +100,12 @@ pub enum WmEvent {
     unmanaged_id: Uuid,
     unmanaged_handle: isize,
   },
+  WindowUnresponsive {
+    unresponsive_window: ContainerDto,
+  },
+  WindowResponsive {
+    responsive_window: ContainerDto,
+  },
   WorkspaceActivated {
     activated_workspace: ContainerDto,
   },
@@ -63,4 +119,68 @@ pub enum WmEvent {
   PauseChanged {
     is_paused: bool,
   },
+  MouseEventsChanged {
+    is_enabled: bool,
+  },
+  Trace {
+    trace: TraceEvent,
+  },
+}
+
+/// Origin of a command that was run by the WM.
+#[derive(
+  Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize, ValueEnum,
+)]
+#[clap(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CommandSource {
+  Keybinding,
+  #[default]
+  Ipc,
+  Cli,
+  Tray,
+  Overlay,
+  Startup,
+  Shutdown,
+  ConfigReload,
+  Timer,
+  Idle,
+  Url,
+  Controller,
+}
+
+/// Debug info about the decisions made by the WM. Only emitted while
+/// trace mode is enabled via the `wm-toggle-trace` command.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(
+  tag = "traceType",
+  rename_all = "snake_case",
+  rename_all_fields = "camelCase"
+)]
+pub enum TraceEvent {
+  /// A platform event was received (e.g. a window was shown).
+  PlatformEventReceived { event: String },
+
+  /// A shown window was ignored because it can't be managed (e.g. it's a
+  /// child window, a tool window, or it's unavailable in alt+tab).
+  WindowNotManageable {
+    handle: isize,
+    process_name: Option<String>,
+    class_name: Option<String>,
+    title: Option<String>,
+  },
+
+  /// A window rule matched a window and its commands are about to run.
+  WindowRuleMatched {
+    handle: isize,
+    process_name: Option<String>,
+    rule_event: WindowRuleEvent,
+    commands: Vec<InvokeCommand>,
+  },
+
+  /// A WM command was run.
+  CommandRun {
+    command: InvokeCommand,
+    subject_container_id: Uuid,
+  },
 }
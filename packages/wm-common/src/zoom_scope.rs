@@ -0,0 +1,25 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// What the screen magnifier is centered on while zoomed in via
+/// `toggle-zoom`.
+#[derive(
+  Clone,
+  Copy,
+  Debug,
+  Default,
+  Deserialize,
+  PartialEq,
+  Eq,
+  Serialize,
+  ValueEnum,
+)]
+#[clap(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ZoomScope {
+  /// Follow the focused window.
+  #[default]
+  Window,
+  /// Center on the monitor of the focused window.
+  Monitor,
+}
@@ -0,0 +1,183 @@
+use std::{
+  cell::OnceCell,
+  sync::{Mutex, OnceLock},
+};
+
+use anyhow::Context;
+use tracing::warn;
+use windows::{
+  core::{w, BSTR, HSTRING},
+  Win32::{
+    Foundation::{BOOL, HWND, LPARAM, LRESULT, WPARAM},
+    Media::Speech::{
+      ISpVoice, SpVoice, SPF_ASYNC, SPF_IS_NOT_XML, SPF_PURGEBEFORESPEAK,
+    },
+    System::Com::{
+      CoCreateInstance, CoInitializeEx, CLSCTX_ALL,
+      COINIT_APARTMENTTHREADED,
+    },
+    UI::{
+      Accessibility::{
+        NotificationKind_ActionCompleted,
+        NotificationProcessing_ImportantMostRecent,
+        UiaHostProviderFromHwnd, UiaRaiseNotificationEvent,
+      },
+      WindowsAndMessaging::{
+        DefWindowProcW, PostMessageW, SystemParametersInfoW,
+        SPI_GETSCREENREADER, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, WM_APP,
+        WNDCLASSW, WS_EX_NOACTIVATE, WS_POPUP,
+      },
+    },
+  },
+};
+
+use crate::overlay::{create_overlay_window, spawn_overlay_thread};
+
+/// Custom message for speaking the pending announcement.
+const WM_ANNOUNCE: u32 = WM_APP + 1;
+
+/// Handle to the hidden window that announcements are raised from.
+/// Created on first use.
+static ANNOUNCER_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// Announcement that is about to be spoken. Only the most recent one is
+/// kept, so that rapid changes (e.g. cycling through workspaces) don't
+/// queue up stale announcements.
+///
+/// For use with window procedure.
+static ANNOUNCEMENT: Mutex<Option<String>> = Mutex::new(None);
+
+thread_local! {
+  /// Speech synthesizer used when no screen reader is running. Created
+  /// on first use on the announcer thread.
+  static VOICE: OnceCell<Option<ISpVoice>> = const { OnceCell::new() };
+}
+
+/// Announces a message to screen readers, interrupting any announcement
+/// that is still being spoken.
+///
+/// The message is raised as a UI Automation notification if a screen
+/// reader is running, so that it's read with the user's screen reader
+/// voice and settings. Otherwise, it's spoken with the system speech
+/// synthesizer.
+pub(crate) fn announce(message: &str) -> anyhow::Result<()> {
+  let handle = *ANNOUNCER_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Announcer window", create_announcer_window)
+  })?;
+
+  *ANNOUNCEMENT.lock().unwrap() = Some(message.to_string());
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_ANNOUNCE,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_announcer_window() -> anyhow::Result<isize> {
+  // COM is needed for both UI Automation and speech synthesis.
+  unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }
+    .context("Failed to initialize COM.")?;
+
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("AnnouncerWindow"),
+    lpfnWndProc: Some(announcer_window_proc),
+    ..Default::default()
+  };
+
+  // Window is never shown. It's not a message-only window, since UI
+  // Automation only raises events for windows in the desktop tree.
+  let handle =
+    create_overlay_window(&wnd_class, WS_EX_NOACTIVATE, WS_POPUP)?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the announcer window.
+extern "system" fn announcer_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_ANNOUNCE => {
+      let announcement = ANNOUNCEMENT.lock().unwrap().take();
+
+      if let Some(announcement) = announcement {
+        let result = if is_screen_reader_running() {
+          raise_notification(handle, &announcement)
+        } else {
+          speak(&announcement)
+        };
+
+        if let Err(err) = result {
+          warn!("Failed to announce '{}': {}", announcement, err);
+        }
+      }
+
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+/// Whether a screen reader (e.g. Narrator or NVDA) is running.
+fn is_screen_reader_running() -> bool {
+  let mut is_running = BOOL::default();
+
+  let result = unsafe {
+    SystemParametersInfoW(
+      SPI_GETSCREENREADER,
+      0,
+      Some(std::ptr::from_mut(&mut is_running).cast()),
+      SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+    )
+  };
+
+  result.is_ok() && is_running.as_bool()
+}
+
+/// Raises a UI Automation notification from the announcer window, which
+/// screen readers read out regardless of which window has focus.
+fn raise_notification(handle: HWND, message: &str) -> anyhow::Result<()> {
+  let provider = unsafe { UiaHostProviderFromHwnd(handle) }?;
+
+  unsafe {
+    UiaRaiseNotificationEvent(
+      &provider,
+      NotificationKind_ActionCompleted,
+      NotificationProcessing_ImportantMostRecent,
+      &BSTR::from(message),
+      &BSTR::from("GlazeWM"),
+    )
+  }?;
+
+  Ok(())
+}
+
+/// Speaks the message with the default system voice.
+fn speak(message: &str) -> anyhow::Result<()> {
+  VOICE.with(|voice| {
+    let voice = voice
+      .get_or_init(|| {
+        unsafe { CoCreateInstance(&SpVoice, None, CLSCTX_ALL) }.ok()
+      })
+      .as_ref()
+      .context("Speech synthesis is unavailable.")?;
+
+    // Purge anything that's still being spoken, since it's outdated.
+    #[allow(clippy::cast_sign_loss)]
+    let flags =
+      (SPF_ASYNC.0 | SPF_PURGEBEFORESPEAK.0 | SPF_IS_NOT_XML.0) as u32;
+
+    unsafe { voice.Speak(&HSTRING::from(message), flags, None) }?;
+
+    Ok(())
+  })
+}
@@ -0,0 +1,152 @@
+use std::collections::{HashMap, HashSet};
+
+use windows::{
+  core::ComInterface,
+  Win32::{
+    Foundation::CloseHandle,
+    Media::Audio::{
+      eRender, AudioSessionStateActive, IAudioSessionControl2,
+      IAudioSessionManager2, IMMDevice, IMMDeviceEnumerator,
+      MMDeviceEnumerator, DEVICE_STATE_ACTIVE,
+    },
+    System::{
+      Com::{CoCreateInstance, CLSCTX_ALL},
+      Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW,
+        PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+      },
+    },
+  },
+};
+
+use crate::COM_INIT;
+
+/// Gets the IDs of processes that are currently playing audio, based on
+/// the active audio sessions of all output devices.
+///
+/// Some applications play audio from a helper process (e.g. the audio
+/// service of Chromium-based browsers), so ancestors of such a process
+/// that share its executable name are included as well.
+pub(crate) fn audio_process_ids() -> anyhow::Result<HashSet<u32>> {
+  // COM has to be initialized on the calling thread.
+  COM_INIT.with(|_| ());
+
+  let enumerator: IMMDeviceEnumerator =
+    unsafe { CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL) }?;
+
+  let devices = unsafe {
+    enumerator.EnumAudioEndpoints(eRender, DEVICE_STATE_ACTIVE)
+  }?;
+
+  let mut session_process_ids = HashSet::new();
+
+  for index in 0..unsafe { devices.GetCount() }? {
+    let device = unsafe { devices.Item(index) }?;
+    session_process_ids.extend(active_session_process_ids(&device)?);
+  }
+
+  if session_process_ids.is_empty() {
+    return Ok(session_process_ids);
+  }
+
+  let processes = process_entries()?;
+  let mut process_ids = HashSet::new();
+
+  for process_id in session_process_ids {
+    process_ids.insert(process_id);
+
+    let Some((parent_id, name)) = processes.get(&process_id) else {
+      continue;
+    };
+
+    let mut parent_id = *parent_id;
+
+    // Walk up the process tree while the executable is the same. The
+    // check against visited IDs guards against cycles from reused IDs.
+    while let Some((grandparent_id, parent_name)) =
+      processes.get(&parent_id)
+    {
+      if parent_name != name || !process_ids.insert(parent_id) {
+        break;
+      }
+
+      parent_id = *grandparent_id;
+    }
+  }
+
+  Ok(process_ids)
+}
+
+/// Gets the process IDs of the active audio sessions of an output
+/// device. System sounds are excluded.
+fn active_session_process_ids(
+  device: &IMMDevice,
+) -> anyhow::Result<Vec<u32>> {
+  let session_manager: IAudioSessionManager2 =
+    unsafe { device.Activate(CLSCTX_ALL, None) }?;
+
+  let sessions = unsafe { session_manager.GetSessionEnumerator() }?;
+  let mut process_ids = Vec::new();
+
+  for index in 0..unsafe { sessions.GetCount() }? {
+    let session = unsafe { sessions.GetSession(index) }?;
+
+    if unsafe { session.GetState() }? != AudioSessionStateActive {
+      continue;
+    }
+
+    let session = session.cast::<IAudioSessionControl2>()?;
+
+    // Returns `S_OK` for the system sounds session.
+    if unsafe { session.IsSystemSoundsSession() }.0 == 0 {
+      continue;
+    }
+
+    let process_id = unsafe { session.GetProcessId() }?;
+
+    if process_id != 0 {
+      process_ids.push(process_id);
+    }
+  }
+
+  Ok(process_ids)
+}
+
+/// Gets the parent process ID and executable name of all running
+/// processes, keyed by process ID.
+fn process_entries() -> anyhow::Result<HashMap<u32, (u32, String)>> {
+  let snapshot =
+    unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }?;
+
+  let mut entry = PROCESSENTRY32W {
+    dwSize: u32::try_from(std::mem::size_of::<PROCESSENTRY32W>())?,
+    ..Default::default()
+  };
+
+  let mut entries = HashMap::new();
+  let mut has_entry =
+    unsafe { Process32FirstW(snapshot, &raw mut entry) }.is_ok();
+
+  while has_entry {
+    let name_length = entry
+      .szExeFile
+      .iter()
+      .position(|&char| char == 0)
+      .unwrap_or(entry.szExeFile.len());
+
+    entries.insert(
+      entry.th32ProcessID,
+      (
+        entry.th32ParentProcessID,
+        String::from_utf16_lossy(&entry.szExeFile[..name_length]),
+      ),
+    );
+
+    has_entry =
+      unsafe { Process32NextW(snapshot, &raw mut entry) }.is_ok();
+  }
+
+  unsafe { CloseHandle(snapshot) }?;
+
+  Ok(entries)
+}
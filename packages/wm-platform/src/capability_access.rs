@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+
+use windows::{
+  core::{w, HSTRING, PCWSTR, PWSTR},
+  Win32::System::Registry::{
+    RegCloseKey, RegEnumKeyExW, RegGetValueW, RegOpenKeyExW, HKEY,
+    HKEY_CURRENT_USER, KEY_READ, RRF_RT_REG_QWORD,
+  },
+};
+
+/// Registry key where Windows records when apps last used a
+/// privacy-sensitive capability. The taskbar's microphone and camera
+/// indicators are based on the same data.
+const CONSENT_STORE_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore";
+
+/// Maximum length of a registry key name, including the null
+/// terminator.
+const MAX_KEY_NAME_LENGTH: usize = 256;
+
+/// Capability whose usage by apps is tracked by Windows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Capability {
+  Microphone,
+  Camera,
+
+  /// Capturing the screen or a window via the graphics capture API.
+  /// Only tracked on Windows 11.
+  ScreenCapture,
+}
+
+impl Capability {
+  /// Name of the capability's key in the consent store.
+  fn key_name(self) -> &'static str {
+    match self {
+      Capability::Microphone => "microphone",
+      Capability::Camera => "webcam",
+      Capability::ScreenCapture => "graphicsCaptureProgrammatic",
+    }
+  }
+}
+
+/// Apps that are currently using a capability.
+#[derive(Clone, Debug, Default)]
+pub struct CapabilityUsers {
+  /// Lowercase executable paths of non-packaged apps.
+  executable_paths: HashSet<String>,
+
+  /// Lowercase name and publisher ID of packaged apps (e.g. `msteams`
+  /// and `8wekyb3d8bbwe`).
+  packages: Vec<(String, String)>,
+}
+
+impl CapabilityUsers {
+  /// Whether the process with the given executable path is one of the
+  /// users.
+  ///
+  /// Packaged apps are matched by their install directory, which is
+  /// named after the package (e.g.
+  /// `MSTeams_1.0.0.0_x64__8wekyb3d8bbwe`).
+  #[must_use]
+  pub fn contains(&self, process_path: &str) -> bool {
+    let process_path = process_path.to_lowercase();
+
+    if self.executable_paths.contains(&process_path) {
+      return true;
+    }
+
+    self.packages.iter().any(|(name, publisher_id)| {
+      process_path.split('\\').any(|dir| {
+        dir.starts_with(&format!("{name}_"))
+          && dir.ends_with(&format!("_{publisher_id}"))
+      })
+    })
+  }
+}
+
+/// Gets the apps that are currently using a capability, based on the
+/// consent store of the current user.
+pub(crate) fn capability_users(
+  capability: Capability,
+) -> anyhow::Result<CapabilityUsers> {
+  let capability_key_path =
+    format!(r"{CONSENT_STORE_KEY}\{}", capability.key_name());
+
+  with_key(HKEY_CURRENT_USER, &capability_key_path, |capability_key| {
+    let mut users = CapabilityUsers::default();
+
+    for key_name in subkey_names(capability_key) {
+      // Non-packaged apps are nested under a separate key, with `#` in
+      // place of backslashes in their executable path.
+      if key_name == "NonPackaged" {
+        let executable_paths =
+          with_key(capability_key, &key_name, |non_packaged_key| {
+            Ok(
+              subkey_names(non_packaged_key)
+                .into_iter()
+                .filter(|key_name| is_in_use(non_packaged_key, key_name))
+                .map(|key_name| key_name.replace('#', "\\").to_lowercase())
+                .collect::<Vec<_>>(),
+            )
+          })?;
+
+        users.executable_paths.extend(executable_paths);
+      } else if is_in_use(capability_key, &key_name) {
+        // Packaged apps are keyed by their package family name, which
+        // consists of the package name and publisher ID.
+        if let Some((name, publisher_id)) =
+          key_name.to_lowercase().rsplit_once('_')
+        {
+          users
+            .packages
+            .push((name.to_string(), publisher_id.to_string()));
+        }
+      }
+    }
+
+    Ok(users)
+  })
+}
+
+/// Opens a registry key for reading, and closes it after running the
+/// given function on it.
+fn with_key<T>(
+  parent_key: HKEY,
+  subkey_path: &str,
+  f: impl FnOnce(HKEY) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+  let mut key = HKEY::default();
+
+  unsafe {
+    RegOpenKeyExW(
+      parent_key,
+      &HSTRING::from(subkey_path),
+      0,
+      KEY_READ,
+      &raw mut key,
+    )
+  }?;
+
+  let result = f(key);
+  unsafe { RegCloseKey(key) }?;
+
+  result
+}
+
+/// Gets the names of the direct subkeys of a registry key.
+fn subkey_names(key: HKEY) -> Vec<String> {
+  let mut names = Vec::new();
+  let mut buffer = [0u16; MAX_KEY_NAME_LENGTH];
+
+  for index in 0.. {
+    #[allow(clippy::cast_possible_truncation)]
+    let mut name_length = MAX_KEY_NAME_LENGTH as u32;
+
+    let res = unsafe {
+      RegEnumKeyExW(
+        key,
+        index,
+        PWSTR(buffer.as_mut_ptr()),
+        &raw mut name_length,
+        None,
+        PWSTR::null(),
+        None,
+        None,
+      )
+    };
+
+    // Fails with `ERROR_NO_MORE_ITEMS` once all subkeys are enumerated.
+    if res.is_err() {
+      break;
+    }
+
+    names.push(String::from_utf16_lossy(&buffer[..name_length as usize]));
+  }
+
+  names
+}
+
+/// Whether the app of a consent store entry is using the capability.
+///
+/// The stop time is reset to 0 while the capability is in use.
+fn is_in_use(key: HKEY, subkey_name: &str) -> bool {
+  let start_time = qword_value(key, subkey_name, w!("LastUsedTimeStart"));
+  let stop_time = qword_value(key, subkey_name, w!("LastUsedTimeStop"));
+
+  matches!(
+    (start_time, stop_time),
+    (Some(start_time), Some(0)) if start_time != 0
+  )
+}
+
+/// Reads a `REG_QWORD` value of a registry key's subkey.
+fn qword_value(
+  key: HKEY,
+  subkey_name: &str,
+  value_name: PCWSTR,
+) -> Option<u64> {
+  let mut value = 0u64;
+  let mut value_size = u32::try_from(std::mem::size_of::<u64>()).ok()?;
+
+  unsafe {
+    RegGetValueW(
+      key,
+      &HSTRING::from(subkey_name),
+      value_name,
+      RRF_RT_REG_QWORD,
+      None,
+      Some((&raw mut value).cast()),
+      Some(&raw mut value_size),
+    )
+  }
+  .ok()?;
+
+  Some(value)
+}
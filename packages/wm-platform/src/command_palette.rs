@@ -0,0 +1,400 @@
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::bail;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::Gdi::{
+      BeginPaint, EndPaint, GetMonitorInfoW, InvalidateRect,
+      MonitorFromWindow, DT_LEFT, FW_NORMAL, MONITORINFO,
+      MONITOR_DEFAULTTOPRIMARY, PAINTSTRUCT,
+    },
+    UI::{
+      Input::KeyboardAndMouse::{
+        VK_BACK, VK_DOWN, VK_ESCAPE, VK_RETURN, VK_UP,
+      },
+      WindowsAndMessaging::{
+        DefWindowProcW, GetClientRect, GetForegroundWindow, PostMessageW,
+        SetWindowPos, ShowWindow, HWND_TOPMOST, SWP_SHOWWINDOW, SW_HIDE,
+        WA_INACTIVE, WM_ACTIVATE, WM_APP, WM_CHAR, WM_KEYDOWN, WM_PAINT,
+        WNDCLASSW, WS_EX_TOPMOST, WS_POPUP,
+      },
+    },
+  },
+};
+
+use crate::{
+  overlay::{
+    create_overlay_window, draw_text, fill_rect, rgb,
+    spawn_overlay_thread, TextStyle,
+  },
+  NativeWindow,
+};
+
+/// Custom message for showing the command palette.
+const WM_SHOW_PALETTE: u32 = WM_APP + 1;
+
+/// Width of the command palette window in pixels.
+const PALETTE_WIDTH: i32 = 560;
+
+/// Height of the search field and of each entry row in pixels.
+const ROW_HEIGHT: i32 = 28;
+
+/// Max number of entries that are shown at once.
+const MAX_VISIBLE_ENTRIES: usize = 12;
+
+/// Padding around the contents of the palette in pixels.
+const PALETTE_PADDING: i32 = 8;
+
+/// Handle to the command palette window. Created on first use.
+static PALETTE_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// State of the command palette while it's shown.
+///
+/// For use with window procedure.
+static PALETTE: Mutex<Option<PaletteState>> = Mutex::new(None);
+
+/// Entry that can be selected in the command palette.
+#[derive(Clone, Debug)]
+pub struct CommandPaletteEntry {
+  /// Text that is shown and searched in the palette.
+  pub label: String,
+
+  /// Command that is sent when the entry is selected.
+  pub command: String,
+}
+
+struct PaletteState {
+  entries: Vec<CommandPaletteEntry>,
+  query: String,
+
+  /// Indices of the entries that match the query, best match first.
+  matches: Vec<usize>,
+
+  /// Index into `matches` of the highlighted entry.
+  selected: usize,
+
+  /// Sender for the command of the selected entry.
+  selection_tx: UnboundedSender<String>,
+}
+
+impl PaletteState {
+  fn update_matches(&mut self) {
+    let mut scored_matches = self
+      .entries
+      .iter()
+      .enumerate()
+      .filter_map(|(index, entry)| {
+        fuzzy_score(&self.query, &entry.label).map(|score| (index, score))
+      })
+      .collect::<Vec<_>>();
+
+    // Sorting is stable, so entries with equal scores keep their order.
+    scored_matches.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+    self.matches =
+      scored_matches.into_iter().map(|(index, _)| index).collect();
+    self.selected = 0;
+  }
+}
+
+/// Shows the command palette on the monitor of the foreground window.
+///
+/// The command of the selected entry is sent via `selection_tx`. Nothing
+/// is sent if the palette is dismissed.
+pub(crate) fn show_command_palette(
+  entries: Vec<CommandPaletteEntry>,
+  selection_tx: UnboundedSender<String>,
+) -> anyhow::Result<()> {
+  let handle = *PALETTE_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Command palette", create_palette_window)
+  })?;
+
+  let mut state = PaletteState {
+    entries,
+    query: String::new(),
+    matches: Vec::new(),
+    selected: 0,
+    selection_tx,
+  };
+
+  state.update_matches();
+  *PALETTE.lock().unwrap() = Some(state);
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_SHOW_PALETTE,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_palette_window() -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("CommandPaletteWindow"),
+    lpfnWndProc: Some(palette_window_proc),
+    ..Default::default()
+  };
+
+  let handle = create_overlay_window(&wnd_class, WS_EX_TOPMOST, WS_POPUP)?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the command palette window.
+extern "system" fn palette_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_SHOW_PALETTE => {
+      if let Err(err) = position_palette_window(handle) {
+        warn!("Failed to show command palette: {}", err);
+      }
+
+      LRESULT(0)
+    }
+    // Dismiss the palette when it loses focus.
+    WM_ACTIVATE if wparam.0 & 0xffff == WA_INACTIVE as usize => {
+      hide_palette(handle);
+      LRESULT(0)
+    }
+    WM_CHAR => {
+      let char = char::from_u32(u32::try_from(wparam.0).unwrap_or(0));
+
+      if let Some(char) = char.filter(|char| !char.is_control()) {
+        if let Some(state) = PALETTE.lock().unwrap().as_mut() {
+          state.query.push(char);
+          state.update_matches();
+        }
+
+        unsafe { InvalidateRect(handle, None, true) };
+      }
+
+      LRESULT(0)
+    }
+    WM_KEYDOWN => {
+      handle_key_down(handle, wparam);
+      LRESULT(0)
+    }
+    WM_PAINT => {
+      paint_palette_window(handle);
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+fn handle_key_down(handle: HWND, wparam: WPARAM) {
+  #[allow(clippy::cast_possible_truncation)]
+  let key = wparam.0 as u16;
+
+  let mut palette = PALETTE.lock().unwrap();
+  let Some(state) = palette.as_mut() else {
+    return;
+  };
+
+  match key {
+    _ if key == VK_ESCAPE.0 => {
+      drop(palette);
+      hide_palette(handle);
+      return;
+    }
+    _ if key == VK_RETURN.0 => {
+      if let Some(entry) = state
+        .matches
+        .get(state.selected)
+        .map(|index| &state.entries[*index])
+      {
+        let _ = state.selection_tx.send(entry.command.clone());
+      }
+
+      drop(palette);
+      hide_palette(handle);
+      return;
+    }
+    _ if key == VK_BACK.0 => {
+      state.query.pop();
+      state.update_matches();
+    }
+    _ if key == VK_UP.0 => {
+      state.selected = state.selected.saturating_sub(1);
+    }
+    _ if key == VK_DOWN.0 => {
+      state.selected =
+        (state.selected + 1).min(state.matches.len().saturating_sub(1));
+    }
+    _ => return,
+  }
+
+  unsafe { InvalidateRect(handle, None, true) };
+}
+
+fn hide_palette(handle: HWND) {
+  *PALETTE.lock().unwrap() = None;
+  unsafe { ShowWindow(handle, SW_HIDE) };
+}
+
+/// Centers the palette horizontally near the top of the work area of the
+/// foreground window's monitor, and brings it to the foreground.
+fn position_palette_window(handle: HWND) -> anyhow::Result<()> {
+  let monitor = unsafe {
+    MonitorFromWindow(GetForegroundWindow(), MONITOR_DEFAULTTOPRIMARY)
+  };
+
+  let mut monitor_info = MONITORINFO {
+    #[allow(clippy::cast_possible_truncation)]
+    cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+    ..Default::default()
+  };
+
+  if !unsafe { GetMonitorInfoW(monitor, &raw mut monitor_info) }.as_bool()
+  {
+    bail!("Failed to get monitor info.");
+  }
+
+  let work_area = monitor_info.rcWork;
+
+  #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+  let height =
+    ROW_HEIGHT * (MAX_VISIBLE_ENTRIES as i32 + 1) + PALETTE_PADDING * 2;
+
+  unsafe {
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      work_area.left
+        + (work_area.right - work_area.left - PALETTE_WIDTH) / 2,
+      work_area.top + (work_area.bottom - work_area.top) / 5,
+      PALETTE_WIDTH,
+      height,
+      SWP_SHOWWINDOW,
+    )?;
+
+    InvalidateRect(handle, None, true);
+  };
+
+  NativeWindow::new(handle.0).set_foreground()
+}
+
+fn paint_palette_window(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  let hdc = unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  if let Some(state) = PALETTE.lock().unwrap().as_ref() {
+    let mut bounds = RECT::default();
+    let _ = unsafe { GetClientRect(handle, &raw mut bounds) };
+
+    fill_rect(hdc, &bounds, rgb(0x20, 0x20, 0x20));
+
+    let text_style = TextStyle {
+      height: 18,
+      weight: FW_NORMAL.0,
+      color: rgb(0xf0, 0xf0, 0xf0),
+      alignment: DT_LEFT,
+    };
+
+    let query_bounds = RECT {
+      left: PALETTE_PADDING,
+      top: PALETTE_PADDING,
+      right: bounds.right - PALETTE_PADDING,
+      bottom: PALETTE_PADDING + ROW_HEIGHT,
+    };
+
+    draw_text(
+      hdc,
+      &format!("> {}", state.query),
+      query_bounds,
+      &text_style,
+    );
+
+    // Scroll the list so that the selected entry is visible.
+    let first_visible =
+      (state.selected + 1).saturating_sub(MAX_VISIBLE_ENTRIES);
+
+    for (row, match_position) in state
+      .matches
+      .iter()
+      .enumerate()
+      .skip(first_visible)
+      .take(MAX_VISIBLE_ENTRIES)
+      .map(|(index, _)| index)
+      .enumerate()
+    {
+      #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap
+      )]
+      let top = query_bounds.bottom + ROW_HEIGHT * row as i32;
+
+      let row_bounds = RECT {
+        top,
+        bottom: top + ROW_HEIGHT,
+        ..query_bounds
+      };
+
+      if match_position == state.selected {
+        fill_rect(hdc, &row_bounds, rgb(0x42, 0x8b, 0xf5));
+      }
+
+      let entry = &state.entries[state.matches[match_position]];
+      draw_text(
+        hdc,
+        &entry.label,
+        RECT {
+          left: row_bounds.left + PALETTE_PADDING,
+          ..row_bounds
+        },
+        &text_style,
+      );
+    }
+  }
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
+
+/// Scores how well the query matches the text, or `None` if the
+/// characters of the query don't appear in order in the text.
+///
+/// Consecutive matches and matches at the start of words score higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+  let text = text.to_lowercase().chars().collect::<Vec<_>>();
+  let mut score = 0;
+  let mut text_index = 0;
+  let mut prev_match_index = None;
+
+  for query_char in query.to_lowercase().chars() {
+    if query_char.is_whitespace() {
+      continue;
+    }
+
+    let match_index = text_index
+      + text[text_index..]
+        .iter()
+        .position(|char| *char == query_char)?;
+
+    score += 1;
+
+    if prev_match_index.is_some_and(|index| index + 1 == match_index) {
+      score += 4;
+    }
+
+    if match_index == 0 || !text[match_index - 1].is_alphanumeric() {
+      score += 2;
+    }
+
+    prev_match_index = Some(match_index);
+    text_index = match_index + 1;
+  }
+
+  Some(score)
+}
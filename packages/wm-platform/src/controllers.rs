@@ -0,0 +1,489 @@
+use std::{
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicIsize, Ordering},
+    Mutex,
+  },
+};
+
+use tracing::{info, warn};
+use windows::Win32::{
+  Devices::HumanInterfaceDevice::{
+    HID_USAGE_GENERIC_MOUSE, HID_USAGE_PAGE_GENERIC,
+  },
+  Foundation::{HANDLE, HWND, LPARAM, WPARAM},
+  Media::{
+    Audio::{
+      midiInClose, midiInGetDevCapsW, midiInGetNumDevs, midiInOpen,
+      midiInReset, midiInStart, midiInStop, CALLBACK_FUNCTION, HMIDIIN,
+      MIDIINCAPSW,
+    },
+    MMSYSERR_NOERROR, MM_MIM_DATA,
+  },
+  UI::{
+    Input::{
+      GetRawInputData, GetRawInputDeviceInfoW, RegisterRawInputDevices,
+      HRAWINPUT, RAWINPUT, RAWINPUTDEVICE, RAWINPUTHEADER,
+      RIDEV_INPUTSINK, RIDEV_REMOVE, RIDI_DEVICEINFO, RID_DEVICE_INFO,
+      RID_INPUT,
+    },
+    WindowsAndMessaging::{PostMessageW, WM_APP},
+  },
+};
+use wm_common::{ControllersConfig, InvokeCommand};
+
+use crate::{PlatformEvent, PlatformEventSender};
+
+/// Custom message for (re)registering the controllers from the latest
+/// config. Sent to the event window, since raw input registration and
+/// MIDI devices are tied to its thread.
+pub(crate) const WM_UPDATE_CONTROLLERS: u32 = WM_APP + 1;
+
+/// Custom message for a short MIDI message that was received. The
+/// message is in `wparam` and the index of the MIDI controller config in
+/// `lparam`.
+pub(crate) const WM_MIDI_INPUT: u32 = WM_APP + 2;
+
+/// Handle to the event window that controller input is received on.
+static EVENT_WINDOW: AtomicIsize = AtomicIsize::new(0);
+
+/// Controllers config that is currently applied.
+///
+/// For use with window procedure.
+static CONTROLLERS_CONFIG: Mutex<Option<ControllersConfig>> =
+  Mutex::new(None);
+
+/// HID usages that are currently registered for raw input, as tuples of
+/// usage page and usage.
+static REGISTERED_USAGES: Mutex<Vec<(u16, u16)>> = Mutex::new(Vec::new());
+
+/// Last input report of each HID device, keyed by device handle. Used to
+/// only trigger buttons when they're first pressed.
+static LAST_HID_REPORTS: Mutex<Option<HashMap<isize, Vec<u8>>>> =
+  Mutex::new(None);
+
+/// Handles of the MIDI input devices that are currently open.
+static MIDI_HANDLES: Mutex<Vec<isize>> = Mutex::new(Vec::new());
+
+/// Identifies a MIDI control by the index of its MIDI controller config,
+/// its channel and its control number.
+type MidiControlKey = (usize, u8, u8);
+
+/// Last value of each MIDI control.
+static LAST_CONTROL_VALUES: Mutex<Option<HashMap<MidiControlKey, u8>>> =
+  Mutex::new(None);
+
+/// Stores the controllers config, and lets the event window know to
+/// re-register the controllers if the config has changed.
+///
+/// The controllers are registered once the event window is created if it
+/// doesn't exist yet.
+pub(crate) fn update_controllers(config: &ControllersConfig) {
+  let mut current_config = CONTROLLERS_CONFIG.lock().unwrap();
+
+  if current_config.as_ref() == Some(config) {
+    return;
+  }
+
+  *current_config = Some(config.clone());
+
+  let handle = EVENT_WINDOW.load(Ordering::Relaxed);
+
+  if handle != 0 {
+    let _ = unsafe {
+      PostMessageW(
+        HWND(handle),
+        WM_UPDATE_CONTROLLERS,
+        WPARAM::default(),
+        LPARAM::default(),
+      )
+    };
+  }
+}
+
+/// Registers the HID devices and opens the MIDI devices from the current
+/// controllers config. Needs to be called on the event window's thread.
+pub(crate) fn apply_controllers(handle: HWND) {
+  EVENT_WINDOW.store(handle.0, Ordering::Relaxed);
+
+  let config = CONTROLLERS_CONFIG.lock().unwrap().clone();
+  let config = config.unwrap_or_default();
+
+  if let Err(err) = register_hid_usages(handle, &config) {
+    warn!("Failed to register HID controllers: {}", err);
+  }
+
+  close_midi_devices();
+  open_midi_devices(&config);
+}
+
+/// Closes all open MIDI devices. Needs to be called on the event window's
+/// thread.
+pub(crate) fn close_midi_devices() {
+  for handle in MIDI_HANDLES.lock().unwrap().drain(..) {
+    unsafe {
+      midiInStop(HMIDIIN(handle));
+      midiInReset(HMIDIIN(handle));
+      midiInClose(HMIDIIN(handle));
+    }
+  }
+
+  *LAST_CONTROL_VALUES.lock().unwrap() = None;
+}
+
+/// Registers the event window to receive raw input from the HID usages
+/// in the config, and unregisters usages that are no longer needed.
+fn register_hid_usages(
+  handle: HWND,
+  config: &ControllersConfig,
+) -> anyhow::Result<()> {
+  let mut usages = config
+    .hid
+    .iter()
+    .map(|controller| (controller.usage_page, controller.usage))
+    // The mouse is registered separately by the event window.
+    .filter(|&usage| {
+      usage != (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_MOUSE)
+    })
+    .collect::<Vec<_>>();
+
+  usages.sort_unstable();
+  usages.dedup();
+
+  let mut registered_usages = REGISTERED_USAGES.lock().unwrap();
+
+  let devices = registered_usages
+    .iter()
+    .filter(|usage| !usages.contains(usage))
+    .map(|&(usage_page, usage)| RAWINPUTDEVICE {
+      usUsagePage: usage_page,
+      usUsage: usage,
+      dwFlags: RIDEV_REMOVE,
+      hwndTarget: HWND(0),
+    })
+    .chain(usages.iter().map(|&(usage_page, usage)| RAWINPUTDEVICE {
+      usUsagePage: usage_page,
+      usUsage: usage,
+      dwFlags: RIDEV_INPUTSINK,
+      hwndTarget: handle,
+    }))
+    .collect::<Vec<_>>();
+
+  *registered_usages = usages;
+  *LAST_HID_REPORTS.lock().unwrap() = None;
+
+  if devices.is_empty() {
+    return Ok(());
+  }
+
+  unsafe {
+    #[allow(clippy::cast_possible_truncation)]
+    RegisterRawInputDevices(
+      &devices,
+      std::mem::size_of::<RAWINPUTDEVICE>() as u32,
+    )
+  }?;
+
+  Ok(())
+}
+
+/// Opens and starts the MIDI input devices whose names match a MIDI
+/// controller in the config.
+fn open_midi_devices(config: &ControllersConfig) {
+  if config.midi.is_empty() {
+    return;
+  }
+
+  let mut handles = MIDI_HANDLES.lock().unwrap();
+
+  for device_id in 0..unsafe { midiInGetNumDevs() } {
+    let mut caps = MIDIINCAPSW::default();
+
+    #[allow(clippy::cast_possible_truncation)]
+    let res = unsafe {
+      midiInGetDevCapsW(
+        device_id as usize,
+        &raw mut caps,
+        std::mem::size_of::<MIDIINCAPSW>() as u32,
+      )
+    };
+
+    if res != MMSYSERR_NOERROR {
+      continue;
+    }
+
+    // Struct is packed, so the name is copied out before being read.
+    let name_wide = caps.szPname;
+    let name_len = name_wide
+      .iter()
+      .position(|&char| char == 0)
+      .unwrap_or(name_wide.len());
+
+    let name = String::from_utf16_lossy(&name_wide[..name_len]);
+
+    let Some(config_index) = config.midi.iter().position(|controller| {
+      name
+        .to_lowercase()
+        .contains(&controller.device.to_lowercase())
+    }) else {
+      continue;
+    };
+
+    let mut handle = HMIDIIN::default();
+
+    let res = unsafe {
+      midiInOpen(
+        &raw mut handle,
+        device_id,
+        midi_in_proc as *const () as usize,
+        config_index,
+        CALLBACK_FUNCTION,
+      )
+    };
+
+    if res != MMSYSERR_NOERROR {
+      warn!("Failed to open MIDI device '{}'.", name);
+      continue;
+    }
+
+    unsafe { midiInStart(handle) };
+    handles.push(handle.0);
+    info!("Listening to MIDI device '{}'.", name);
+  }
+}
+
+/// Callback for MIDI input devices. Runs on a system thread, and is only
+/// allowed to call a limited set of functions, so received messages are
+/// forwarded to the event window.
+extern "system" fn midi_in_proc(
+  _handle: HMIDIIN,
+  message: u32,
+  instance: usize,
+  param1: usize,
+  _param2: usize,
+) {
+  if message != MM_MIM_DATA {
+    return;
+  }
+
+  let _ = unsafe {
+    #[allow(clippy::cast_possible_wrap)]
+    PostMessageW(
+      HWND(EVENT_WINDOW.load(Ordering::Relaxed)),
+      WM_MIDI_INPUT,
+      WPARAM(param1),
+      LPARAM(instance as isize),
+    )
+  };
+}
+
+/// Handles a short MIDI message forwarded by `midi_in_proc`, and emits
+/// the commands of matching notes and controls.
+pub(crate) fn handle_midi_input(
+  wparam: WPARAM,
+  lparam: LPARAM,
+  event_tx: &PlatformEventSender,
+) -> anyhow::Result<()> {
+  let [status, data1, data2, ..] = wparam.0.to_le_bytes();
+  let channel = (status & 0x0f) + 1;
+
+  let config = CONTROLLERS_CONFIG.lock().unwrap();
+
+  #[allow(clippy::cast_sign_loss)]
+  let config_index = lparam.0 as usize;
+
+  let Some(controller) = config
+    .as_ref()
+    .and_then(|config| config.midi.get(config_index))
+  else {
+    return Ok(());
+  };
+
+  let is_channel_match = |config_channel: Option<u8>| {
+    config_channel.is_none_or(|c| c == channel)
+  };
+
+  match status & 0xf0 {
+    // Note on. A velocity of 0 is equivalent to note off.
+    0x90 if data2 > 0 => {
+      for note in &controller.notes {
+        if note.note == data1 && is_channel_match(note.channel) {
+          emit_commands(&note.commands, event_tx)?;
+        }
+      }
+    }
+    // Control change.
+    0xb0 => {
+      let last_value = LAST_CONTROL_VALUES
+        .lock()
+        .unwrap()
+        .get_or_insert_default()
+        .insert((config_index, channel, data1), data2);
+
+      let Some(last_value) = last_value else {
+        return Ok(());
+      };
+
+      for control in &controller.controls {
+        if control.control != data1 || !is_channel_match(control.channel) {
+          continue;
+        }
+
+        if data2 > last_value {
+          emit_commands(&control.increase_commands, event_tx)?;
+        } else if data2 < last_value {
+          emit_commands(&control.decrease_commands, event_tx)?;
+        }
+      }
+    }
+    _ => {}
+  }
+
+  Ok(())
+}
+
+/// Handles raw input from a HID device, and emits the commands of buttons
+/// that were pressed since the device's last input report.
+pub(crate) fn handle_hid_input(
+  lparam: LPARAM,
+  device: HANDLE,
+  event_tx: &PlatformEventSender,
+) -> anyhow::Result<()> {
+  #[allow(clippy::cast_possible_truncation)]
+  let header_size = std::mem::size_of::<RAWINPUTHEADER>() as u32;
+  let mut data_size = 0;
+
+  unsafe {
+    GetRawInputData(
+      HRAWINPUT(lparam.0),
+      RID_INPUT,
+      None,
+      &raw mut data_size,
+      header_size,
+    )
+  };
+
+  // HID input is variable-sized, so is read into a buffer that's aligned
+  // for `RAWINPUT`.
+  let mut buffer = vec![0u64; (data_size as usize).div_ceil(8)];
+
+  let res_size = unsafe {
+    GetRawInputData(
+      HRAWINPUT(lparam.0),
+      RID_INPUT,
+      Some(buffer.as_mut_ptr().cast()),
+      &raw mut data_size,
+      header_size,
+    )
+  };
+
+  if res_size == 0 || res_size == u32::MAX {
+    return Ok(());
+  }
+
+  let Some((vendor_id, product_id, usage_page, usage)) =
+    hid_device_info(device)
+  else {
+    return Ok(());
+  };
+
+  let config = CONTROLLERS_CONFIG.lock().unwrap();
+
+  let Some(controller) = config.as_ref().and_then(|config| {
+    config.hid.iter().find(|controller| {
+      controller.vendor_id == vendor_id
+        && controller.product_id.is_none_or(|id| id == product_id)
+        && controller.usage_page == usage_page
+        && controller.usage == usage
+    })
+  }) else {
+    return Ok(());
+  };
+
+  let raw_input = buffer.as_ptr().cast::<RAWINPUT>();
+  let hid = unsafe { (*raw_input).data.hid };
+  let report_size = hid.dwSizeHid as usize;
+  let total_size = report_size * hid.dwCount as usize;
+
+  let reports_ptr =
+    unsafe { (&raw const (*raw_input).data.hid.bRawData) }.cast::<u8>();
+
+  // Ensure the reports don't extend past the data that was read.
+  let reports_offset = reports_ptr as usize - buffer.as_ptr() as usize;
+  if report_size == 0 || reports_offset + total_size > res_size as usize {
+    return Ok(());
+  }
+
+  let reports =
+    unsafe { std::slice::from_raw_parts(reports_ptr, total_size) };
+
+  let mut last_reports = LAST_HID_REPORTS.lock().unwrap();
+  let last_reports = last_reports.get_or_insert_default();
+
+  for report in reports.chunks_exact(report_size) {
+    let last_report = last_reports
+      .insert(device.0, report.to_vec())
+      .unwrap_or_default();
+
+    for button in &controller.buttons {
+      let is_pressed = |report: &[u8]| {
+        report
+          .get(button.byte)
+          .is_some_and(|byte| byte & button.mask != 0)
+      };
+
+      if is_pressed(report) && !is_pressed(&last_report) {
+        emit_commands(&button.commands, event_tx)?;
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Gets the vendor ID, product ID, usage page and usage of a HID device.
+fn hid_device_info(device: HANDLE) -> Option<(u16, u16, u16, u16)> {
+  #[allow(clippy::cast_possible_truncation)]
+  let mut device_info = RID_DEVICE_INFO {
+    cbSize: std::mem::size_of::<RID_DEVICE_INFO>() as u32,
+    ..Default::default()
+  };
+
+  let mut size = device_info.cbSize;
+
+  let res_size = unsafe {
+    GetRawInputDeviceInfoW(
+      device,
+      RIDI_DEVICEINFO,
+      Some(std::ptr::from_mut(&mut device_info).cast()),
+      &raw mut size,
+    )
+  };
+
+  if res_size == 0 || res_size == u32::MAX {
+    return None;
+  }
+
+  let hid = unsafe { device_info.Anonymous.hid };
+
+  // Vendor and product IDs are 16-bit for USB and Bluetooth devices.
+  #[allow(clippy::cast_possible_truncation)]
+  Some((
+    hid.dwVendorId as u16,
+    hid.dwProductId as u16,
+    hid.usUsagePage,
+    hid.usUsage,
+  ))
+}
+
+fn emit_commands(
+  commands: &[InvokeCommand],
+  event_tx: &PlatformEventSender,
+) -> anyhow::Result<()> {
+  if !commands.is_empty() {
+    event_tx
+      .send(PlatformEvent::ControllerTriggered(commands.to_vec()))?;
+  }
+
+  Ok(())
+}
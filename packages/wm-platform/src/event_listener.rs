@@ -1,14 +1,28 @@
-use tokio::sync::mpsc::{self, UnboundedReceiver};
+use std::{
+  collections::VecDeque,
+  sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use anyhow::bail;
+use tokio::sync::Notify;
+use tracing::warn;
 use wm_common::{
   BindingModeConfig, InvokeCommand, KeybindingConfig, ParsedConfig, Point,
 };
 
-use super::{EventWindow, NativeWindow};
+use super::{EventWindow, KeyboardLayout, NativeWindow};
 
 #[derive(Debug)]
 pub enum PlatformEvent {
+  /// The system high-contrast or animation setting changed.
+  AccessibilitySettingsChanged,
   DisplaySettingsChanged,
+  KeyboardLayoutChanged(KeyboardLayout),
   KeybindingTriggered(KeybindingConfig),
+  KeybindingReleased(KeybindingConfig),
+  /// A button, note or control of a HID or MIDI controller from the
+  /// user config was triggered.
+  ControllerTriggered(Vec<InvokeCommand>),
   MouseMove(MouseMoveEvent),
   WindowDestroyed(NativeWindow),
   WindowFocused(NativeWindow),
@@ -22,6 +36,74 @@ pub enum PlatformEvent {
   WindowTitleChanged(NativeWindow),
 }
 
+/// Priority lane that a platform event is queued in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventPriority {
+  /// User input (keybindings, controllers and mouse movement).
+  High,
+  /// Window and display changes.
+  Normal,
+  /// Cosmetic changes (e.g. window title changes), which some apps emit
+  /// in large bursts.
+  Low,
+}
+
+impl PlatformEvent {
+  #[must_use]
+  pub fn priority(&self) -> EventPriority {
+    match self {
+      Self::KeybindingTriggered(_)
+      | Self::KeybindingReleased(_)
+      | Self::ControllerTriggered(_)
+      | Self::MouseMove(_) => EventPriority::High,
+      Self::WindowTitleChanged(_) => EventPriority::Low,
+      _ => EventPriority::Normal,
+    }
+  }
+
+  /// Window that the event is about, if any.
+  #[must_use]
+  pub fn window(&self) -> Option<&NativeWindow> {
+    match self {
+      Self::WindowDestroyed(window)
+      | Self::WindowFocused(window)
+      | Self::WindowHidden(window)
+      | Self::WindowLocationChanged(window)
+      | Self::WindowMinimized(window)
+      | Self::WindowMinimizeEnded(window)
+      | Self::WindowMovedOrResizedEnd(window)
+      | Self::WindowMovedOrResizedStart(window)
+      | Self::WindowShown(window)
+      | Self::WindowTitleChanged(window) => Some(window),
+      _ => None,
+    }
+  }
+
+  /// Name of the event variant (e.g. `WindowFocused`).
+  #[must_use]
+  pub fn event_type(&self) -> &'static str {
+    match self {
+      Self::AccessibilitySettingsChanged => "AccessibilitySettingsChanged",
+      Self::DisplaySettingsChanged => "DisplaySettingsChanged",
+      Self::KeyboardLayoutChanged(_) => "KeyboardLayoutChanged",
+      Self::KeybindingTriggered(_) => "KeybindingTriggered",
+      Self::KeybindingReleased(_) => "KeybindingReleased",
+      Self::ControllerTriggered(_) => "ControllerTriggered",
+      Self::MouseMove(_) => "MouseMove",
+      Self::WindowDestroyed(_) => "WindowDestroyed",
+      Self::WindowFocused(_) => "WindowFocused",
+      Self::WindowHidden(_) => "WindowHidden",
+      Self::WindowLocationChanged(_) => "WindowLocationChanged",
+      Self::WindowMinimized(_) => "WindowMinimized",
+      Self::WindowMinimizeEnded(_) => "WindowMinimizeEnded",
+      Self::WindowMovedOrResizedEnd(_) => "WindowMovedOrResizedEnd",
+      Self::WindowMovedOrResizedStart(_) => "WindowMovedOrResizedStart",
+      Self::WindowShown(_) => "WindowShown",
+      Self::WindowTitleChanged(_) => "WindowTitleChanged",
+    }
+  }
+}
+
 #[derive(Debug, Clone)]
 pub struct MouseMoveEvent {
   /// Location of mouse with 0,0 being the top-left corner of the primary
@@ -32,8 +114,185 @@ pub struct MouseMoveEvent {
   pub is_mouse_down: bool,
 }
 
+/// Capacity of the queue for low priority events. The oldest event is
+/// dropped once it's full.
+const LOW_PRIORITY_CAPACITY: usize = 1024;
+
+/// Platform events that are waiting to be handled, with a separate queue
+/// per priority lane.
+#[derive(Debug, Default)]
+struct EventLanes {
+  high: VecDeque<(u64, PlatformEvent)>,
+  normal: VecDeque<(u64, PlatformEvent)>,
+  low: VecDeque<(u64, PlatformEvent)>,
+
+  /// Sequence number to assign to the next queued event. Used to handle
+  /// events across lanes in the order they arrived in.
+  next_sequence: u64,
+
+  /// Whether the `EventListener` has been dropped.
+  is_closed: bool,
+}
+
+impl EventLanes {
+  fn lane_mut(
+    &mut self,
+    priority: EventPriority,
+  ) -> &mut VecDeque<(u64, PlatformEvent)> {
+    match priority {
+      EventPriority::High => &mut self.high,
+      EventPriority::Normal => &mut self.normal,
+      EventPriority::Low => &mut self.low,
+    }
+  }
+
+  /// Merges the event into an already queued event that it supersedes.
+  ///
+  /// Returns the event back if there's no such event.
+  fn coalesce(&mut self, event: PlatformEvent) -> Option<PlatformEvent> {
+    let last_sequence = self.next_sequence.checked_sub(1);
+
+    match &event {
+      // Titles are read when the event is handled, so a queued title
+      // change of the same window already covers this one.
+      PlatformEvent::WindowTitleChanged(window) => {
+        let is_queued = self.low.iter().any(|(_, queued)| {
+          matches!(queued, PlatformEvent::WindowTitleChanged(queued) if queued == window)
+        });
+
+        (!is_queued).then_some(event)
+      }
+      // Consecutive mouse moves are merged into the latest position, as
+      // long as no other event arrived in between.
+      PlatformEvent::MouseMove(mouse_move) => match self.high.back_mut() {
+        Some((sequence, PlatformEvent::MouseMove(queued)))
+          if Some(*sequence) == last_sequence
+            && queued.is_mouse_down == mouse_move.is_mouse_down =>
+        {
+          *queued = mouse_move.clone();
+          None
+        }
+        _ => Some(event),
+      },
+      _ => Some(event),
+    }
+  }
+
+  /// Takes the next event to handle.
+  ///
+  /// High and normal priority events are taken in the order they arrived
+  /// in, since commands run via keybindings depend on the window events
+  /// before them. Low priority events are deferred until the other lanes
+  /// are empty, except when an event of the same window comes up, in
+  /// which case they're taken first to keep the order of events per
+  /// window.
+  fn pop(&mut self) -> Option<PlatformEvent> {
+    let head_sequence = |lane: &VecDeque<(u64, PlatformEvent)>| {
+      lane.front().map(|(sequence, _)| *sequence)
+    };
+
+    let lane =
+      match (head_sequence(&self.high), head_sequence(&self.normal)) {
+        (Some(high), Some(normal)) if normal < high => &mut self.normal,
+        (Some(_), _) => &mut self.high,
+        (None, Some(_)) => &mut self.normal,
+        (None, None) => {
+          return self.low.pop_front().map(|(_, event)| event);
+        }
+      };
+
+    let (sequence, event) = lane.pop_front()?;
+
+    let earlier_low_index = event.window().and_then(|window| {
+      self.low.iter().position(|(low_sequence, low_event)| {
+        *low_sequence < sequence && low_event.window() == Some(window)
+      })
+    });
+
+    match earlier_low_index {
+      Some(index) => {
+        // Put the event back, and handle it after the earlier event.
+        lane.push_front((sequence, event));
+        self.low.remove(index).map(|(_, event)| event)
+      }
+      None => Some(event),
+    }
+  }
+
+  fn len(&self) -> usize {
+    self.high.len() + self.normal.len() + self.low.len()
+  }
+}
+
+/// Queue of platform events that is shared between the event listener
+/// and its senders.
+#[derive(Debug, Default)]
+struct EventQueue {
+  lanes: Mutex<EventLanes>,
+
+  /// Notified when an event is queued.
+  has_event: Notify,
+}
+
+impl EventQueue {
+  /// Locks the queue. Poisoning is ignored, since the lanes are never
+  /// left in an invalid state by a panic.
+  fn lanes(&self) -> MutexGuard<'_, EventLanes> {
+    self.lanes.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+/// Sender for platform events. Events are queued in a separate queue per
+/// priority lane, so that a flood of low priority events can't delay the
+/// handling of user input.
+#[derive(Clone, Debug)]
+pub struct PlatformEventSender {
+  queue: Arc<EventQueue>,
+}
+
+impl PlatformEventSender {
+  /// Queues the event in the lane for its priority.
+  ///
+  /// This never blocks, since events are sent from within hook callbacks
+  /// that Windows removes if they take too long. Events that are
+  /// superseded by an already queued event are coalesced. The high and
+  /// normal priority lanes are unbounded, whereas the low priority lane
+  /// drops its oldest event once full.
+  pub fn send(&self, event: PlatformEvent) -> anyhow::Result<()> {
+    let priority = event.priority();
+    let mut lanes = self.queue.lanes();
+
+    if lanes.is_closed {
+      bail!("Platform event receiver has been dropped.");
+    }
+
+    let Some(event) = lanes.coalesce(event) else {
+      return Ok(());
+    };
+
+    if priority == EventPriority::Low
+      && lanes.low.len() >= LOW_PRIORITY_CAPACITY
+    {
+      if let Some((_, dropped)) = lanes.low.pop_front() {
+        warn!(
+          "Dropped {} event due to a full queue.",
+          dropped.event_type()
+        );
+      }
+    }
+
+    let sequence = lanes.next_sequence;
+    lanes.next_sequence += 1;
+    lanes.lane_mut(priority).push_back((sequence, event));
+    drop(lanes);
+
+    self.queue.has_event.notify_one();
+    Ok(())
+  }
+}
+
 pub struct EventListener {
-  pub event_rx: UnboundedReceiver<PlatformEvent>,
+  queue: Arc<EventQueue>,
   event_window: EventWindow,
 }
 
@@ -42,27 +301,58 @@ impl EventListener {
   ///
   /// Returns an instance of `EventListener`.
   pub fn start(config: &ParsedConfig) -> anyhow::Result<Self> {
-    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let queue = Arc::new(EventQueue::default());
+
+    let event_tx = PlatformEventSender {
+      queue: queue.clone(),
+    };
 
     let event_window = EventWindow::new(
       &event_tx,
       &config.keybindings,
       config.general.focus_follows_cursor,
+      &config.controllers,
     )?;
 
     Ok(Self {
-      event_rx,
+      queue,
       event_window,
     })
   }
 
-  /// Updates the event listener with the latest user config and the
-  /// currently active binding modes.
+  /// Waits for the next platform event. Low priority events are deferred
+  /// while there are other events to handle.
+  ///
+  /// This is cancellation safe.
+  pub async fn next_event(&mut self) -> PlatformEvent {
+    loop {
+      let event = self.queue.lanes().pop();
+
+      if let Some(event) = event {
+        return event;
+      }
+
+      self.queue.has_event.notified().await;
+    }
+  }
+
+  /// Number of platform events that are waiting to be handled across all
+  /// priority lanes.
+  #[must_use]
+  pub fn pending_count(&self) -> usize {
+    self.queue.lanes().len()
+  }
+
+  /// Updates the event listener with the latest user config, the
+  /// currently active binding modes, the paused and mouse events state,
+  /// and the keybindings disabled for the focused window.
   pub fn update(
     &mut self,
     config: &ParsedConfig,
     binding_modes: &[BindingModeConfig],
     paused: bool,
+    mouse_events_enabled: bool,
+    disabled_keybindings: &[String],
   ) {
     // Modify keybindings based on active binding modes and paused state.
     let keybindings = if paused {
@@ -81,8 +371,59 @@ impl EventListener {
       }
     };
 
-    self
-      .event_window
-      .update(keybindings, config.general.focus_follows_cursor && !paused);
+    let keybindings = Self::without_disabled_keybindings(
+      keybindings,
+      disabled_keybindings,
+    );
+
+    self.event_window.update(
+      &keybindings,
+      config.general.focus_follows_cursor
+        && mouse_events_enabled
+        && !paused,
+      &config.controllers,
+    );
+  }
+
+  /// Removes disabled key combinations from the keybindings. If `all` is
+  /// disabled, no keybindings are returned.
+  fn without_disabled_keybindings(
+    keybindings: &[KeybindingConfig],
+    disabled_keybindings: &[String],
+  ) -> Vec<KeybindingConfig> {
+    let normalize = |binding: &str| {
+      binding.to_lowercase().replace(char::is_whitespace, "")
+    };
+
+    let disabled_keybindings = disabled_keybindings
+      .iter()
+      .map(|binding| normalize(binding))
+      .collect::<Vec<_>>();
+
+    if disabled_keybindings.iter().any(|binding| binding == "all") {
+      return Vec::new();
+    }
+
+    keybindings
+      .iter()
+      .map(|keybinding| KeybindingConfig {
+        bindings: keybinding
+          .bindings
+          .iter()
+          .filter(|binding| {
+            !disabled_keybindings.contains(&normalize(binding))
+          })
+          .cloned()
+          .collect(),
+        ..keybinding.clone()
+      })
+      .filter(|keybinding| !keybinding.bindings.is_empty())
+      .collect()
+  }
+}
+
+impl Drop for EventListener {
+  fn drop(&mut self) {
+    self.queue.lanes().is_closed = true;
   }
 }
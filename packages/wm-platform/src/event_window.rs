@@ -1,47 +1,60 @@
 use std::{
   sync::{
-    atomic::{AtomicBool, AtomicU64, Ordering},
+    atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering},
     Arc, OnceLock,
   },
   thread::{self, JoinHandle},
   time::SystemTime,
 };
 
-use tokio::sync::mpsc;
 use tracing::{info, warn};
 use windows::Win32::{
   Devices::HumanInterfaceDevice::{
     HID_USAGE_GENERIC_MOUSE, HID_USAGE_PAGE_GENERIC,
   },
   Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM},
+  Graphics::Gdi::{MonitorFromPoint, MONITOR_DEFAULTTONEAREST},
   UI::{
     Input::{
       GetRawInputData, RegisterRawInputDevices, HRAWINPUT, RAWINPUT,
-      RAWINPUTDEVICE, RAWINPUTHEADER, RIDEV_INPUTSINK, RID_INPUT,
-      RIM_TYPEMOUSE,
+      RAWINPUTDEVICE, RAWINPUTHEADER, RIDEV_INPUTSINK, RID_HEADER,
+      RID_INPUT, RIM_TYPEHID, RIM_TYPEMOUSE,
     },
     WindowsAndMessaging::{
-      DefWindowProcW, DestroyWindow, GetCursorPos, DBT_DEVNODES_CHANGED,
+      DefWindowProcW, DestroyWindow, GetAncestor, GetCursorPos, SetTimer,
+      WindowFromPoint, DBT_DEVNODES_CHANGED, GA_ROOT,
       PBT_APMRESUMEAUTOMATIC, PBT_APMRESUMESUSPEND, PBT_APMSUSPEND,
       RI_MOUSE_LEFT_BUTTON_DOWN, RI_MOUSE_LEFT_BUTTON_UP,
       RI_MOUSE_RIGHT_BUTTON_DOWN, RI_MOUSE_RIGHT_BUTTON_UP,
-      SPI_ICONVERTICALSPACING, SPI_SETWORKAREA, WM_DEVICECHANGE,
-      WM_DISPLAYCHANGE, WM_INPUT, WM_POWERBROADCAST, WM_SETTINGCHANGE,
+      SPI_ICONVERTICALSPACING, SPI_SETCLIENTAREAANIMATION,
+      SPI_SETHIGHCONTRAST, SPI_SETWORKAREA, SYSTEM_PARAMETERS_INFO_ACTION,
+      WM_DEVICECHANGE, WM_DISPLAYCHANGE, WM_INPUT, WM_INPUTLANGCHANGE,
+      WM_POWERBROADCAST, WM_SETTINGCHANGE, WM_SYSCOLORCHANGE, WM_TIMER,
     },
   },
 };
-use wm_common::{KeybindingConfig, Point};
+use wm_common::{ControllersConfig, KeybindingConfig, Point};
 
 use super::{
-  KeyboardHook, MouseMoveEvent, Platform, PlatformEvent, WindowEventHook,
-  FOREGROUND_INPUT_IDENTIFIER,
+  controllers::{self, WM_MIDI_INPUT, WM_UPDATE_CONTROLLERS},
+  KeyboardHook, MouseMoveEvent, Platform, PlatformEvent,
+  PlatformEventSender, WindowEventHook, FOREGROUND_INPUT_IDENTIFIER,
+  KEYBOARD_HOOK,
 };
 
+/// ID of the timer for checking the keyboard layout of the foreground
+/// window.
+const KEYBOARD_LAYOUT_TIMER_ID: usize = 1;
+
+/// Interval at which the keyboard layout of the foreground window is
+/// checked. The layout can be switched within a window (e.g. via
+/// win+space), which only notifies that window's thread.
+const KEYBOARD_LAYOUT_CHECK_INTERVAL_MS: u32 = 250;
+
 /// Global instance of sender for platform events.
 ///
 /// For use with window procedure.
-static PLATFORM_EVENT_TX: OnceLock<mpsc::UnboundedSender<PlatformEvent>> =
-  OnceLock::new();
+static PLATFORM_EVENT_TX: OnceLock<PlatformEventSender> = OnceLock::new();
 
 /// Whether mouse hook is currently enabled.
 ///
@@ -68,6 +81,28 @@ static IS_R_MOUSE_DOWN: AtomicBool = AtomicBool::new(false);
 /// For use with window procedure.
 static LAST_MOUSE_EVENT_TIME: AtomicU64 = AtomicU64::new(0);
 
+/// Root window that was under the cursor on the last mouse event
+/// emission.
+///
+/// For use with window procedure.
+static LAST_HIT_WINDOW: AtomicIsize = AtomicIsize::new(0);
+
+/// Monitor that the cursor was on during the last mouse event emission.
+///
+/// For use with window procedure.
+static LAST_HIT_MONITOR: AtomicIsize = AtomicIsize::new(0);
+
+/// Clears the window and monitor that were last under the cursor, so that
+/// the next mouse move is emitted regardless of whether the cursor has
+/// left the window.
+///
+/// Called when windows are moved or focus changes, since a different
+/// window might then be under the cursor.
+pub(crate) fn clear_last_mouse_hit() {
+  LAST_HIT_WINDOW.store(0, Ordering::Relaxed);
+  LAST_HIT_MONITOR.store(0, Ordering::Relaxed);
+}
+
 #[derive(Debug)]
 pub struct EventWindow {
   keyboard_hook: Arc<KeyboardHook>,
@@ -81,9 +116,10 @@ impl EventWindow {
   /// Uses global state (e.g. `PLATFORM_EVENT_TX`) and should thus only
   /// ever be instantiated once in the application's lifetime.
   pub fn new(
-    event_tx: &mpsc::UnboundedSender<PlatformEvent>,
+    event_tx: &PlatformEventSender,
     keybindings: &Vec<KeybindingConfig>,
     enable_mouse_events: bool,
+    controllers_config: &ControllersConfig,
   ) -> anyhow::Result<Self> {
     let keyboard_hook = KeyboardHook::new(keybindings, event_tx.clone())?;
     let window_event_hook = WindowEventHook::new(event_tx.clone())?;
@@ -95,6 +131,7 @@ impl EventWindow {
     })?;
 
     ENABLE_MOUSE_EVENTS.store(enable_mouse_events, Ordering::Relaxed);
+    controllers::update_controllers(controllers_config);
 
     let window_thread = thread::spawn(move || {
       // Start hooks for listening to platform events.
@@ -121,9 +158,22 @@ impl EventWindow {
         )
       }?;
 
+      // Register HID and MIDI controllers from the user config.
+      controllers::apply_controllers(HWND(handle));
+
+      unsafe {
+        SetTimer(
+          HWND(handle),
+          KEYBOARD_LAYOUT_TIMER_ID,
+          KEYBOARD_LAYOUT_CHECK_INTERVAL_MS,
+          None,
+        )
+      };
+
       Platform::run_message_loop();
 
       // Clean-up on message loop exit.
+      controllers::close_midi_devices();
       unsafe { DestroyWindow(HWND(handle)) }?;
       keyboard_hook_clone.stop()?;
       window_event_hook.stop()?;
@@ -141,9 +191,12 @@ impl EventWindow {
     &mut self,
     keybindings: &Vec<KeybindingConfig>,
     enable_mouse_events: bool,
+    controllers_config: &ControllersConfig,
   ) {
     self.keyboard_hook.update(keybindings);
     ENABLE_MOUSE_EVENTS.store(enable_mouse_events, Ordering::Relaxed);
+    controllers::update_controllers(controllers_config);
+    clear_last_mouse_hit();
   }
 
   /// Destroys the event window and stops the message loop.
@@ -199,7 +252,8 @@ pub extern "system" fn event_window_proc(
 
         LRESULT(0)
       }
-      WM_DISPLAYCHANGE | WM_SETTINGCHANGE | WM_DEVICECHANGE => {
+      WM_DISPLAYCHANGE | WM_SETTINGCHANGE | WM_SYSCOLORCHANGE
+      | WM_DEVICECHANGE => {
         // Ignore display change messages if the system hasn't fully
         // resumed from sleep.
         if !IS_SYSTEM_SUSPENDED.load(Ordering::Relaxed) {
@@ -212,13 +266,40 @@ pub extern "system" fn event_window_proc(
 
         LRESULT(0)
       }
-      WM_INPUT if ENABLE_MOUSE_EVENTS.load(Ordering::Relaxed) => {
+      WM_INPUTLANGCHANGE => {
+        if let Some(keyboard_hook) = KEYBOARD_HOOK.get() {
+          keyboard_hook.update_keyboard_layout();
+        }
+
+        unsafe { DefWindowProcW(handle, message, wparam, lparam) }
+      }
+      WM_TIMER if wparam.0 == KEYBOARD_LAYOUT_TIMER_ID => {
+        if let Some(keyboard_hook) = KEYBOARD_HOOK.get() {
+          keyboard_hook.update_keyboard_layout();
+        }
+
+        LRESULT(0)
+      }
+      WM_INPUT => {
         if let Err(err) = handle_input_msg(wparam, lparam, event_tx) {
           warn!("Failed to handle input message: {}", err);
         }
 
         LRESULT(0)
       }
+      WM_UPDATE_CONTROLLERS => {
+        controllers::apply_controllers(handle);
+        LRESULT(0)
+      }
+      WM_MIDI_INPUT => {
+        if let Err(err) =
+          controllers::handle_midi_input(wparam, lparam, event_tx)
+        {
+          warn!("Failed to handle MIDI input: {}", err);
+        }
+
+        LRESULT(0)
+      }
       _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
     };
   }
@@ -226,36 +307,84 @@ pub extern "system" fn event_window_proc(
   LRESULT(0)
 }
 
-/// Handles display change messages and emits the corresponding platform
-/// event through an MPSC channel.
+/// Handles display and system setting change messages and emits the
+/// corresponding platform event through an MPSC channel.
 fn handle_display_change_msg(
   message: u32,
   wparam: WPARAM,
-  event_tx: &mpsc::UnboundedSender<PlatformEvent>,
+  event_tx: &PlatformEventSender,
 ) -> anyhow::Result<()> {
   #[allow(clippy::cast_possible_truncation)]
-  let should_emit_event = match message {
+  let event = match message {
     WM_SETTINGCHANGE => {
-      wparam.0 as u32 == SPI_SETWORKAREA.0
-        || wparam.0 as u32 == SPI_ICONVERTICALSPACING.0
+      match SYSTEM_PARAMETERS_INFO_ACTION(wparam.0 as u32) {
+        SPI_SETWORKAREA | SPI_ICONVERTICALSPACING => {
+          Some(PlatformEvent::DisplaySettingsChanged)
+        }
+        SPI_SETHIGHCONTRAST | SPI_SETCLIENTAREAANIMATION => {
+          Some(PlatformEvent::AccessibilitySettingsChanged)
+        }
+        _ => None,
+      }
     }
-    WM_DEVICECHANGE => wparam.0 as u32 == DBT_DEVNODES_CHANGED,
-    _ => true,
+    // System colors change when switching between high-contrast themes.
+    WM_SYSCOLORCHANGE => Some(PlatformEvent::AccessibilitySettingsChanged),
+    WM_DEVICECHANGE => (wparam.0 as u32 == DBT_DEVNODES_CHANGED)
+      .then_some(PlatformEvent::DisplaySettingsChanged),
+    _ => Some(PlatformEvent::DisplaySettingsChanged),
   };
 
-  if should_emit_event {
-    event_tx.send(PlatformEvent::DisplaySettingsChanged)?;
+  if let Some(event) = event {
+    event_tx.send(event)?;
   }
 
   Ok(())
 }
 
-/// Handles raw input messages for mouse events and emits the corresponding
-/// platform event through an MPSC channel.
+/// Handles raw input messages, which are either from the mouse or from
+/// HID controllers in the user config.
 fn handle_input_msg(
   _wparam: WPARAM,
   lparam: LPARAM,
-  event_tx: &mpsc::UnboundedSender<PlatformEvent>,
+  event_tx: &PlatformEventSender,
+) -> anyhow::Result<()> {
+  let mut header = RAWINPUTHEADER::default();
+  #[allow(clippy::cast_possible_truncation)]
+  let mut header_size = std::mem::size_of::<RAWINPUTHEADER>() as u32;
+
+  let res_size = unsafe {
+    GetRawInputData(
+      HRAWINPUT(lparam.0),
+      RID_HEADER,
+      Some(std::ptr::from_mut(&mut header).cast()),
+      &raw mut header_size,
+      header_size,
+    )
+  };
+
+  if res_size == 0 || res_size == u32::MAX {
+    return Ok(());
+  }
+
+  match header.dwType {
+    dw_type if dw_type == RIM_TYPEHID.0 => {
+      controllers::handle_hid_input(lparam, header.hDevice, event_tx)
+    }
+    dw_type
+      if dw_type == RIM_TYPEMOUSE.0
+        && ENABLE_MOUSE_EVENTS.load(Ordering::Relaxed) =>
+    {
+      handle_mouse_input(lparam, event_tx)
+    }
+    _ => Ok(()),
+  }
+}
+
+/// Handles raw input from the mouse and emits the corresponding platform
+/// event through an MPSC channel.
+fn handle_mouse_input(
+  lparam: LPARAM,
+  event_tx: &PlatformEventSender,
 ) -> anyhow::Result<()> {
   let mut raw_input: RAWINPUT = unsafe { std::mem::zeroed() };
   #[allow(clippy::cast_possible_truncation)]
@@ -276,7 +405,7 @@ fn handle_input_msg(
   // process are ignored, which would cause issues since
   // `NativeWindow::set_foreground` simulates a mouse input.
   if res_size == 0
-    || raw_input_size == u32::MAX
+    || res_size == u32::MAX
     || raw_input.header.dwType != RIM_TYPEMOUSE.0
     || unsafe { raw_input.data.mouse.ulExtraInformation }
       == FOREGROUND_INPUT_IDENTIFIER
@@ -327,6 +456,25 @@ fn handle_input_msg(
   let mut point = POINT { x: 0, y: 0 };
   unsafe { GetCursorPos(&raw mut point) }?;
 
+  // Skip the event if the cursor is still over the same window (and on
+  // the same monitor) as on the last emission. Hit-testing is cheap
+  // compared to sending the event to the WM, which would do the same
+  // hit-test and then ignore it.
+  let hit_window =
+    unsafe { GetAncestor(WindowFromPoint(point), GA_ROOT) }.0;
+  let hit_monitor =
+    unsafe { MonitorFromPoint(point, MONITOR_DEFAULTTONEAREST) }.0;
+
+  if !has_state_change
+    && hit_window == LAST_HIT_WINDOW.load(Ordering::Relaxed)
+    && hit_monitor == LAST_HIT_MONITOR.load(Ordering::Relaxed)
+  {
+    return Ok(());
+  }
+
+  LAST_HIT_WINDOW.store(hit_window, Ordering::Relaxed);
+  LAST_HIT_MONITOR.store(hit_monitor, Ordering::Relaxed);
+
   event_tx.send(PlatformEvent::MouseMove(MouseMoveEvent {
     point: Point {
       x: point.x,
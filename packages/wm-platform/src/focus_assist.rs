@@ -0,0 +1,121 @@
+use std::ffi::c_void;
+
+use anyhow::{bail, Context};
+use windows::{
+  core::{s, w, PCSTR},
+  Win32::System::LibraryLoader::{GetModuleHandleW, GetProcAddress},
+};
+use wm_common::FocusAssistMode;
+
+/// WNF state name that holds the active Focus Assist profile (i.e.
+/// `WNF_SHEL_QUIETHOURS_ACTIVE_PROFILE_CHANGED`).
+static QUIET_HOURS_STATE_NAME: u64 = 0x0d83_063e_a3bf_1c75;
+
+/// Untyped pointer to an exported function of `ntdll.dll`.
+type NtFunction = unsafe extern "system" fn() -> isize;
+
+/// Signature of `NtQueryWnfStateData` from `ntdll.dll`.
+type NtQueryWnfStateData = unsafe extern "system" fn(
+  state_name: *const u64,
+  type_id: *const c_void,
+  explicit_scope: *const c_void,
+  change_stamp: *mut u32,
+  buffer: *mut c_void,
+  buffer_size: *mut u32,
+) -> i32;
+
+/// Signature of `NtUpdateWnfStateData` from `ntdll.dll`.
+type NtUpdateWnfStateData = unsafe extern "system" fn(
+  state_name: *const u64,
+  buffer: *const c_void,
+  length: u32,
+  type_id: *const c_void,
+  explicit_scope: *const c_void,
+  matching_change_stamp: u32,
+  check_stamp: u32,
+) -> i32;
+
+/// Gets the active Focus Assist profile.
+///
+/// There's no public API for Focus Assist, so this reads the
+/// undocumented WNF state that the shell uses.
+pub(crate) fn focus_assist_mode() -> anyhow::Result<FocusAssistMode> {
+  let query_state = unsafe {
+    std::mem::transmute::<NtFunction, NtQueryWnfStateData>(ntdll_function(
+      s!("NtQueryWnfStateData"),
+    )?)
+  };
+
+  let mut change_stamp = 0u32;
+  let mut profile = 0u32;
+  let mut buffer_size = u32::try_from(std::mem::size_of::<u32>())?;
+
+  let status = unsafe {
+    query_state(
+      &raw const QUIET_HOURS_STATE_NAME,
+      std::ptr::null(),
+      std::ptr::null(),
+      &raw mut change_stamp,
+      (&raw mut profile).cast(),
+      &raw mut buffer_size,
+    )
+  };
+
+  if status < 0 {
+    bail!("Failed to query Focus Assist state (status {status:#x}).");
+  }
+
+  match profile {
+    0 => Ok(FocusAssistMode::Off),
+    1 => Ok(FocusAssistMode::PriorityOnly),
+    2 => Ok(FocusAssistMode::AlarmsOnly),
+    _ => bail!("Unknown Focus Assist profile {profile}."),
+  }
+}
+
+/// Sets the active Focus Assist profile.
+///
+/// Like `focus_assist_mode`, this relies on undocumented WNF state, so it
+/// might stop working on future versions of Windows.
+pub(crate) fn set_focus_assist_mode(
+  mode: FocusAssistMode,
+) -> anyhow::Result<()> {
+  let update_state = unsafe {
+    std::mem::transmute::<NtFunction, NtUpdateWnfStateData>(
+      ntdll_function(s!("NtUpdateWnfStateData"))?,
+    )
+  };
+
+  let profile: u32 = match mode {
+    FocusAssistMode::Off => 0,
+    FocusAssistMode::PriorityOnly => 1,
+    FocusAssistMode::AlarmsOnly => 2,
+  };
+
+  let status = unsafe {
+    update_state(
+      &raw const QUIET_HOURS_STATE_NAME,
+      (&raw const profile).cast(),
+      u32::try_from(std::mem::size_of::<u32>())?,
+      std::ptr::null(),
+      std::ptr::null(),
+      0,
+      0,
+    )
+  };
+
+  if status < 0 {
+    bail!("Failed to update Focus Assist state (status {status:#x}).");
+  }
+
+  Ok(())
+}
+
+/// Gets a pointer to an exported function of `ntdll.dll`.
+fn ntdll_function(name: PCSTR) -> anyhow::Result<NtFunction> {
+  let ntdll = unsafe { GetModuleHandleW(w!("ntdll.dll")) }?;
+
+  unsafe { GetProcAddress(ntdll, name) }.with_context(|| {
+    format!("Failed to resolve `{}`.", unsafe { name.display() })
+  })
+}
@@ -0,0 +1,180 @@
+use std::sync::{Mutex, OnceLock};
+
+use tracing::warn;
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::Gdi::{
+      BeginPaint, CombineRgn, CreateRectRgn, DeleteObject, EndPaint,
+      InvalidateRect, SetWindowRgn, PAINTSTRUCT, RGN_DIFF,
+    },
+    UI::WindowsAndMessaging::{
+      DefWindowProcW, GetClientRect, PostMessageW,
+      SetLayeredWindowAttributes, SetWindowPos, ShowWindow, HWND_TOPMOST,
+      LWA_ALPHA, SWP_NOACTIVATE, SWP_SHOWWINDOW, SW_HIDE, WM_APP,
+      WM_PAINT, WNDCLASSW, WS_POPUP,
+    },
+  },
+};
+use wm_common::{Color, Rect};
+
+use crate::overlay::{
+  create_overlay_window, fill_rect, spawn_overlay_thread,
+  CLICK_THROUGH_EX_STYLE,
+};
+
+/// Custom message for updating the frame to match `FOCUS_FRAME`.
+const WM_UPDATE_FOCUS_FRAME: u32 = WM_APP + 1;
+
+/// Handle to the focus frame window. Created on first use.
+static FOCUS_FRAME_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// Frame that is currently shown, or `None` if it's hidden.
+///
+/// For use with window procedure.
+static FOCUS_FRAME: Mutex<Option<FocusFrame>> = Mutex::new(None);
+
+/// Frame drawn around the focused window, for a more visible focus
+/// indicator than the window border.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FocusFrame {
+  /// Rect to draw the frame around.
+  pub rect: Rect,
+
+  /// Thickness of the frame in pixels.
+  pub thickness: i32,
+
+  /// Color of the frame.
+  pub color: Color,
+}
+
+/// Shows the focus frame around the given rect, or hides it if `None`.
+///
+/// Does nothing if the frame is unchanged.
+pub(crate) fn update_focus_frame(
+  frame: Option<FocusFrame>,
+) -> anyhow::Result<()> {
+  {
+    let mut current_frame = FOCUS_FRAME.lock().unwrap();
+
+    if *current_frame == frame {
+      return Ok(());
+    }
+
+    *current_frame = frame;
+  }
+
+  let handle = *FOCUS_FRAME_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Focus frame", create_focus_frame_window)
+  })?;
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_UPDATE_FOCUS_FRAME,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_focus_frame_window() -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("FocusFrameWindow"),
+    lpfnWndProc: Some(focus_frame_window_proc),
+    ..Default::default()
+  };
+
+  let handle =
+    create_overlay_window(&wnd_class, CLICK_THROUGH_EX_STYLE, WS_POPUP)?;
+
+  unsafe {
+    SetLayeredWindowAttributes(handle, COLORREF(0), 255, LWA_ALPHA)
+  }?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the focus frame window.
+extern "system" fn focus_frame_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_UPDATE_FOCUS_FRAME => {
+      if let Err(err) = reposition_focus_frame(handle) {
+        warn!("Failed to update focus frame: {}", err);
+      }
+
+      LRESULT(0)
+    }
+    WM_PAINT => {
+      paint_focus_frame(handle);
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+/// Positions the window around the rect of the current frame, with a
+/// region that cuts out the area inside the frame.
+fn reposition_focus_frame(handle: HWND) -> anyhow::Result<()> {
+  let Some(frame) = FOCUS_FRAME.lock().unwrap().clone() else {
+    unsafe { ShowWindow(handle, SW_HIDE) };
+    return Ok(());
+  };
+
+  let width = frame.rect.width() + frame.thickness * 2;
+  let height = frame.rect.height() + frame.thickness * 2;
+
+  unsafe {
+    let region = CreateRectRgn(0, 0, width, height);
+    let inner_region = CreateRectRgn(
+      frame.thickness,
+      frame.thickness,
+      width - frame.thickness,
+      height - frame.thickness,
+    );
+
+    CombineRgn(region, region, inner_region, RGN_DIFF);
+    DeleteObject(inner_region);
+
+    // The system takes ownership of the region.
+    SetWindowRgn(handle, region, true);
+
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      frame.rect.x() - frame.thickness,
+      frame.rect.y() - frame.thickness,
+      width,
+      height,
+      SWP_NOACTIVATE | SWP_SHOWWINDOW,
+    )?;
+
+    InvalidateRect(handle, None, true);
+  };
+
+  Ok(())
+}
+
+fn paint_focus_frame(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  let hdc = unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  if let Some(frame) = FOCUS_FRAME.lock().unwrap().as_ref() {
+    let mut bounds = RECT::default();
+    let _ = unsafe { GetClientRect(handle, &raw mut bounds) };
+
+    let color = COLORREF(frame.color.to_bgr().unwrap_or_default());
+
+    fill_rect(hdc, &bounds, color);
+  }
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
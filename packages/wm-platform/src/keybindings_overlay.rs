@@ -0,0 +1,276 @@
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::bail;
+use tracing::warn;
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::Gdi::{
+      BeginPaint, EndPaint, GetMonitorInfoW, InvalidateRect,
+      MonitorFromWindow, DT_LEFT, FW_NORMAL, FW_SEMIBOLD, MONITORINFO,
+      MONITOR_DEFAULTTOPRIMARY, PAINTSTRUCT,
+    },
+    UI::WindowsAndMessaging::{
+      DefWindowProcW, GetClientRect, GetForegroundWindow, PostMessageW,
+      SetLayeredWindowAttributes, SetWindowPos, ShowWindow, HWND_TOPMOST,
+      LWA_ALPHA, SWP_SHOWWINDOW, SW_HIDE, WA_INACTIVE, WM_ACTIVATE,
+      WM_APP, WM_KEYDOWN, WM_LBUTTONDOWN, WM_PAINT, WM_RBUTTONDOWN,
+      WM_SYSKEYDOWN, WNDCLASSW, WS_EX_LAYERED, WS_EX_TOPMOST, WS_POPUP,
+    },
+  },
+};
+
+use crate::{
+  overlay::{
+    create_overlay_window, draw_text, fill_rect, rgb,
+    spawn_overlay_thread, TextStyle,
+  },
+  NativeWindow,
+};
+
+/// Custom message for showing the keybindings overlay.
+const WM_SHOW_OVERLAY: u32 = WM_APP + 1;
+
+/// Height of each line of text in pixels.
+const LINE_HEIGHT: i32 = 22;
+
+/// Width of each column of keybindings in pixels.
+const COLUMN_WIDTH: i32 = 440;
+
+/// Width of the keyboard shortcut part of a keybinding in pixels.
+const BINDINGS_WIDTH: i32 = 170;
+
+/// Padding around the contents of the overlay in pixels.
+const OVERLAY_PADDING: i32 = 24;
+
+/// Handle to the overlay window. Created on first use.
+static OVERLAY_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// Sections that are currently shown in the overlay.
+///
+/// For use with window procedure.
+static SECTIONS: Mutex<Vec<KeybindingSection>> = Mutex::new(Vec::new());
+
+/// Group of keybindings shown under a common title in the overlay.
+#[derive(Clone, Debug)]
+pub struct KeybindingSection {
+  pub title: String,
+
+  /// Keybindings as tuples of keyboard shortcuts and commands.
+  pub keybindings: Vec<(String, String)>,
+}
+
+/// Shows the keybindings overlay on the monitor of the foreground window.
+/// The overlay is dismissed on any key press or click.
+pub(crate) fn show_keybindings_overlay(
+  sections: Vec<KeybindingSection>,
+) -> anyhow::Result<()> {
+  let handle = *OVERLAY_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Keybindings overlay", create_keybindings_window)
+  })?;
+
+  *SECTIONS.lock().unwrap() = sections;
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_SHOW_OVERLAY,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_keybindings_window() -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("KeybindingsOverlayWindow"),
+    lpfnWndProc: Some(overlay_window_proc),
+    ..Default::default()
+  };
+
+  let handle = create_overlay_window(
+    &wnd_class,
+    WS_EX_LAYERED | WS_EX_TOPMOST,
+    WS_POPUP,
+  )?;
+
+  unsafe {
+    SetLayeredWindowAttributes(handle, COLORREF(0), 240, LWA_ALPHA)
+  }?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the keybindings overlay window.
+extern "system" fn overlay_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_SHOW_OVERLAY => {
+      if let Err(err) = position_overlay_window(handle) {
+        warn!("Failed to show keybindings overlay: {}", err);
+      }
+
+      LRESULT(0)
+    }
+    // Dismiss the overlay on any key press, click, or when it loses
+    // focus.
+    WM_KEYDOWN | WM_SYSKEYDOWN | WM_LBUTTONDOWN | WM_RBUTTONDOWN => {
+      unsafe { ShowWindow(handle, SW_HIDE) };
+      LRESULT(0)
+    }
+    WM_ACTIVATE if wparam.0 & 0xffff == WA_INACTIVE as usize => {
+      unsafe { ShowWindow(handle, SW_HIDE) };
+      LRESULT(0)
+    }
+    WM_PAINT => {
+      paint_overlay_window(handle);
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+/// Sizes the overlay to cover most of the work area of the foreground
+/// window's monitor, and brings it to the foreground.
+fn position_overlay_window(handle: HWND) -> anyhow::Result<()> {
+  let monitor = unsafe {
+    MonitorFromWindow(GetForegroundWindow(), MONITOR_DEFAULTTOPRIMARY)
+  };
+
+  let mut monitor_info = MONITORINFO {
+    #[allow(clippy::cast_possible_truncation)]
+    cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+    ..Default::default()
+  };
+
+  if !unsafe { GetMonitorInfoW(monitor, &raw mut monitor_info) }.as_bool()
+  {
+    bail!("Failed to get monitor info.");
+  }
+
+  let work_area = monitor_info.rcWork;
+  let margin_x = (work_area.right - work_area.left) / 10;
+  let margin_y = (work_area.bottom - work_area.top) / 10;
+
+  unsafe {
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      work_area.left + margin_x,
+      work_area.top + margin_y,
+      work_area.right - work_area.left - margin_x * 2,
+      work_area.bottom - work_area.top - margin_y * 2,
+      SWP_SHOWWINDOW,
+    )?;
+
+    InvalidateRect(handle, None, true);
+  };
+
+  NativeWindow::new(handle.0).set_foreground()
+}
+
+/// Draws the sections top to bottom, wrapping into a new column when the
+/// bottom of the overlay is reached.
+fn paint_overlay_window(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  let hdc = unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  let mut bounds = RECT::default();
+  let _ = unsafe { GetClientRect(handle, &raw mut bounds) };
+
+  fill_rect(hdc, &bounds, rgb(0x20, 0x20, 0x20));
+
+  let mut left = OVERLAY_PADDING;
+  let mut top = OVERLAY_PADDING;
+
+  // Moves to the next line, wrapping into a new column if needed.
+  // Returns `false` if there's no space left for another line.
+  let next_line = |left: &mut i32, top: &mut i32| {
+    *top += LINE_HEIGHT;
+
+    if *top + LINE_HEIGHT > bounds.bottom - OVERLAY_PADDING {
+      *top = OVERLAY_PADDING;
+      *left += COLUMN_WIDTH;
+    }
+
+    *left + COLUMN_WIDTH <= bounds.right
+  };
+
+  'sections: for section in SECTIONS.lock().unwrap().iter() {
+    draw_text(
+      hdc,
+      &section.title,
+      RECT {
+        left,
+        top,
+        right: left + COLUMN_WIDTH - OVERLAY_PADDING,
+        bottom: top + LINE_HEIGHT,
+      },
+      &TextStyle {
+        height: 17,
+        weight: FW_SEMIBOLD.0,
+        color: rgb(0x42, 0x8b, 0xf5),
+        alignment: DT_LEFT,
+      },
+    );
+
+    if !next_line(&mut left, &mut top) {
+      break;
+    }
+
+    for (bindings, commands) in &section.keybindings {
+      draw_text(
+        hdc,
+        bindings,
+        RECT {
+          left,
+          top,
+          right: left + BINDINGS_WIDTH,
+          bottom: top + LINE_HEIGHT,
+        },
+        &TextStyle {
+          height: 17,
+          weight: FW_SEMIBOLD.0,
+          color: rgb(0xf0, 0xf0, 0xf0),
+          alignment: DT_LEFT,
+        },
+      );
+
+      draw_text(
+        hdc,
+        commands,
+        RECT {
+          left: left + BINDINGS_WIDTH,
+          top,
+          right: left + COLUMN_WIDTH - OVERLAY_PADDING,
+          bottom: top + LINE_HEIGHT,
+        },
+        &TextStyle {
+          height: 17,
+          weight: FW_NORMAL.0,
+          color: rgb(0xc0, 0xc0, 0xc0),
+          alignment: DT_LEFT,
+        },
+      );
+
+      if !next_line(&mut left, &mut top) {
+        break 'sections;
+      }
+    }
+
+    // Leave an empty line between sections, unless at the top of a
+    // column.
+    if top != OVERLAY_PADDING && !next_line(&mut left, &mut top) {
+      break;
+    }
+  }
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
@@ -1,49 +1,58 @@
 use std::{
   collections::HashMap,
-  sync::{Arc, Mutex, OnceLock},
+  sync::{
+    atomic::{AtomicIsize, Ordering},
+    Arc, Mutex, OnceLock,
+  },
 };
 
-use tokio::sync::mpsc;
 use tracing::warn;
 use windows::Win32::{
-  Foundation::{LPARAM, LRESULT, WPARAM},
+  Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+  Globalization::LCIDToLocaleName,
   UI::{
     Input::KeyboardAndMouse::{
-      GetKeyState, GetKeyboardLayout, VkKeyScanExW, VIRTUAL_KEY, VK_0,
-      VK_1, VK_2, VK_3, VK_4, VK_5, VK_6, VK_7, VK_8, VK_9, VK_A, VK_ADD,
-      VK_B, VK_BACK, VK_C, VK_CAPITAL, VK_CONTROL, VK_CONVERT, VK_D,
-      VK_DECIMAL, VK_DELETE, VK_DIVIDE, VK_DOWN, VK_E, VK_END, VK_ESCAPE,
-      VK_F, VK_F1, VK_F10, VK_F11, VK_F12, VK_F13, VK_F14, VK_F15, VK_F16,
-      VK_F17, VK_F18, VK_F19, VK_F2, VK_F20, VK_F21, VK_F22, VK_F23,
-      VK_F24, VK_F3, VK_F4, VK_F5, VK_F6, VK_F7, VK_F8, VK_F9, VK_G, VK_H,
-      VK_HOME, VK_I, VK_INSERT, VK_J, VK_K, VK_L, VK_LCONTROL, VK_LEFT,
-      VK_LMENU, VK_LSHIFT, VK_LWIN, VK_M, VK_MEDIA_NEXT_TRACK,
-      VK_MEDIA_PLAY_PAUSE, VK_MEDIA_PREV_TRACK, VK_MEDIA_STOP, VK_MENU,
-      VK_MULTIPLY, VK_N, VK_NEXT, VK_NONCONVERT, VK_NUMLOCK, VK_NUMPAD0,
-      VK_NUMPAD1, VK_NUMPAD2, VK_NUMPAD3, VK_NUMPAD4, VK_NUMPAD5,
-      VK_NUMPAD6, VK_NUMPAD7, VK_NUMPAD8, VK_NUMPAD9, VK_O, VK_OEM_1,
-      VK_OEM_2, VK_OEM_3, VK_OEM_4, VK_OEM_5, VK_OEM_6, VK_OEM_7,
-      VK_OEM_COMMA, VK_OEM_MINUS, VK_OEM_PERIOD, VK_OEM_PLUS, VK_P,
-      VK_PRIOR, VK_Q, VK_R, VK_RCONTROL, VK_RETURN, VK_RIGHT, VK_RMENU,
-      VK_RSHIFT, VK_RWIN, VK_S, VK_SCROLL, VK_SHIFT, VK_SNAPSHOT,
-      VK_SPACE, VK_SUBTRACT, VK_T, VK_TAB, VK_U, VK_UP, VK_V,
-      VK_VOLUME_DOWN, VK_VOLUME_MUTE, VK_VOLUME_UP, VK_W, VK_X, VK_Y,
-      VK_Z,
+      GetKeyState, GetKeyboardLayout, MapVirtualKeyExW, VkKeyScanExW,
+      MAPVK_VSC_TO_VK_EX, VIRTUAL_KEY, VK_0, VK_1, VK_2, VK_3, VK_4, VK_5,
+      VK_6, VK_7, VK_8, VK_9, VK_A, VK_ADD, VK_B, VK_BACK, VK_C,
+      VK_CAPITAL, VK_CONTROL, VK_CONVERT, VK_D, VK_DECIMAL, VK_DELETE,
+      VK_DIVIDE, VK_DOWN, VK_E, VK_END, VK_ESCAPE, VK_F, VK_F1, VK_F10,
+      VK_F11, VK_F12, VK_F13, VK_F14, VK_F15, VK_F16, VK_F17, VK_F18,
+      VK_F19, VK_F2, VK_F20, VK_F21, VK_F22, VK_F23, VK_F24, VK_F3, VK_F4,
+      VK_F5, VK_F6, VK_F7, VK_F8, VK_F9, VK_G, VK_H, VK_HOME, VK_I,
+      VK_INSERT, VK_J, VK_K, VK_L, VK_LCONTROL, VK_LEFT, VK_LMENU,
+      VK_LSHIFT, VK_LWIN, VK_M, VK_MEDIA_NEXT_TRACK, VK_MEDIA_PLAY_PAUSE,
+      VK_MEDIA_PREV_TRACK, VK_MEDIA_STOP, VK_MENU, VK_MULTIPLY, VK_N,
+      VK_NEXT, VK_NONCONVERT, VK_NUMLOCK, VK_NUMPAD0, VK_NUMPAD1,
+      VK_NUMPAD2, VK_NUMPAD3, VK_NUMPAD4, VK_NUMPAD5, VK_NUMPAD6,
+      VK_NUMPAD7, VK_NUMPAD8, VK_NUMPAD9, VK_O, VK_OEM_1, VK_OEM_2,
+      VK_OEM_3, VK_OEM_4, VK_OEM_5, VK_OEM_6, VK_OEM_7, VK_OEM_COMMA,
+      VK_OEM_MINUS, VK_OEM_PERIOD, VK_OEM_PLUS, VK_P, VK_PRIOR, VK_Q,
+      VK_R, VK_RCONTROL, VK_RETURN, VK_RIGHT, VK_RMENU, VK_RSHIFT,
+      VK_RWIN, VK_S, VK_SCROLL, VK_SHIFT, VK_SNAPSHOT, VK_SPACE,
+      VK_SUBTRACT, VK_T, VK_TAB, VK_U, VK_UP, VK_V, VK_VOLUME_DOWN,
+      VK_VOLUME_MUTE, VK_VOLUME_UP, VK_W, VK_X, VK_Y, VK_Z,
     },
+    TextServices::HKL,
     WindowsAndMessaging::{
-      CallNextHookEx, SetWindowsHookExW, UnhookWindowsHookEx, HHOOK,
-      KBDLLHOOKSTRUCT, WH_KEYBOARD_LL, WM_KEYDOWN, WM_SYSKEYDOWN,
+      CallNextHookEx, GetForegroundWindow, GetWindowThreadProcessId,
+      PostMessageW, SetWindowsHookExW, UnhookWindowsHookEx, HHOOK,
+      KBDLLHOOKSTRUCT, LLKHF_EXTENDED, WH_KEYBOARD_LL, WM_KEYDOWN,
+      WM_KEYUP, WM_SYSKEYDOWN, WM_SYSKEYUP,
     },
   },
 };
 use wm_common::KeybindingConfig;
 
-use super::PlatformEvent;
+use super::{
+  PlatformEvent, PlatformEventSender, SEND_KEYS_INPUT_IDENTIFIER,
+};
 
 /// Global instance of `KeyboardHook`.
 ///
 /// For use with hook procedure.
-static KEYBOARD_HOOK: OnceLock<Arc<KeyboardHook>> = OnceLock::new();
+pub(crate) static KEYBOARD_HOOK: OnceLock<Arc<KeyboardHook>> =
+  OnceLock::new();
 
 /// Available modifier keys.
 const MODIFIER_KEYS: [u16; 6] = [
@@ -55,16 +64,95 @@ const MODIFIER_KEYS: [u16; 6] = [
   VK_RMENU.0,
 ];
 
-#[derive(Debug)]
+/// Keyboard layout of the foreground window.
+#[derive(Clone, Debug)]
+pub struct KeyboardLayout {
+  /// Handle to the keyboard layout (e.g. `0x04090409` for US English).
+  pub handle: isize,
+
+  /// Locale name of the layout's input language (e.g. `en-US`).
+  pub locale_name: String,
+}
+
+/// Scan codes of keys on a US QWERTY keyboard, for binding keys by
+/// their physical position via `qwerty:<key>`.
+const QWERTY_SCAN_CODES: [(&str, u16); 47] = [
+  ("1", 0x02),
+  ("2", 0x03),
+  ("3", 0x04),
+  ("4", 0x05),
+  ("5", 0x06),
+  ("6", 0x07),
+  ("7", 0x08),
+  ("8", 0x09),
+  ("9", 0x0A),
+  ("0", 0x0B),
+  ("-", 0x0C),
+  ("=", 0x0D),
+  ("q", 0x10),
+  ("w", 0x11),
+  ("e", 0x12),
+  ("r", 0x13),
+  ("t", 0x14),
+  ("y", 0x15),
+  ("u", 0x16),
+  ("i", 0x17),
+  ("o", 0x18),
+  ("p", 0x19),
+  ("[", 0x1A),
+  ("]", 0x1B),
+  ("a", 0x1E),
+  ("s", 0x1F),
+  ("d", 0x20),
+  ("f", 0x21),
+  ("g", 0x22),
+  ("h", 0x23),
+  ("j", 0x24),
+  ("k", 0x25),
+  ("l", 0x26),
+  (";", 0x27),
+  ("'", 0x28),
+  ("`", 0x29),
+  ("\\", 0x2B),
+  ("z", 0x2C),
+  ("x", 0x2D),
+  ("c", 0x2E),
+  ("v", 0x2F),
+  ("b", 0x30),
+  ("n", 0x31),
+  ("m", 0x32),
+  (",", 0x33),
+  (".", 0x34),
+  ("/", 0x35),
+];
+
+#[derive(Clone, Debug)]
 pub struct ActiveKeybinding {
   pub vk_codes: Vec<u16>,
+
+  /// Scan code of the trigger key if it's bound by physical position
+  /// (e.g. `sc:0x23` or `qwerty:h`). Such keybindings are only
+  /// triggered by the key at that position, regardless of layout.
+  pub trigger_scan_code: Option<u16>,
+
   pub config: KeybindingConfig,
 }
 
+/// Keybinding with release commands that is currently held down.
+#[derive(Debug)]
+struct HeldKeybinding {
+  keybinding: ActiveKeybinding,
+
+  /// Whether the release commands have already been run, which happens
+  /// when another key of the keybinding is released before the trigger
+  /// key.
+  is_released: bool,
+}
+
 #[derive(Debug)]
 pub struct KeyboardHook {
   /// Sender to emit platform events.
-  event_tx: mpsc::UnboundedSender<PlatformEvent>,
+  event_tx: PlatformEventSender,
 
   /// Handle to the keyboard hook.
   hook: Arc<Mutex<HHOOK>>,
@@ -73,20 +161,41 @@ pub struct KeyboardHook {
   /// final key in a key combination.
   keybindings_by_trigger_key:
     Arc<Mutex<HashMap<u16, Vec<ActiveKeybinding>>>>,
+
+  /// Keybinding with release commands that is currently held down.
+  held_keybinding: Arc<Mutex<Option<HeldKeybinding>>>,
+
+  /// Keybindings from the user config. Kept for re-resolving the
+  /// virtual key codes when the keyboard layout changes.
+  keybindings: Arc<Mutex<Vec<KeybindingConfig>>>,
+
+  /// Handle to the keyboard layout that the virtual key codes were
+  /// resolved with.
+  keyboard_layout: AtomicIsize,
+
+  /// Window and message that key presses are redirected to while keys
+  /// are being captured (e.g. for typing window hints).
+  key_capture: Mutex<Option<(isize, u32)>>,
 }
 
 impl KeyboardHook {
   /// Creates an instance of `KeyboardHook`.
   pub fn new(
     keybindings: &Vec<KeybindingConfig>,
-    event_tx: mpsc::UnboundedSender<PlatformEvent>,
+    event_tx: PlatformEventSender,
   ) -> anyhow::Result<Arc<Self>> {
+    let keyboard_layout = Self::foreground_keyboard_layout();
+
     let keyboard_hook = Arc::new(Self {
       event_tx,
       hook: Arc::new(Mutex::new(HHOOK::default())),
       keybindings_by_trigger_key: Arc::new(Mutex::new(
-        Self::keybindings_by_trigger_key(keybindings),
+        Self::keybindings_by_trigger_key(keybindings, keyboard_layout),
       )),
+      held_keybinding: Arc::new(Mutex::new(None)),
+      keybindings: Arc::new(Mutex::new(keybindings.clone())),
+      keyboard_layout: AtomicIsize::new(keyboard_layout.0),
+      key_capture: Mutex::new(None),
     });
 
     KEYBOARD_HOOK
@@ -117,8 +226,98 @@ impl KeyboardHook {
   ///
   /// If the internal mutex is poisoned.
   pub fn update(&self, keybindings: &Vec<KeybindingConfig>) {
+    let keyboard_layout =
+      HKL(self.keyboard_layout.load(Ordering::Relaxed));
+
     *self.keybindings_by_trigger_key.lock().unwrap() =
-      Self::keybindings_by_trigger_key(keybindings);
+      Self::keybindings_by_trigger_key(keybindings, keyboard_layout);
+
+    keybindings.clone_into(&mut self.keybindings.lock().unwrap());
+  }
+
+  /// Re-resolves the virtual key codes of keybindings if the keyboard
+  /// layout of the foreground window has changed, so that keys defined
+  /// by character (e.g. `;`) keep working across layouts.
+  ///
+  /// Emits a `KeyboardLayoutChanged` event on change.
+  ///
+  /// # Panics
+  ///
+  /// If the internal mutex is poisoned.
+  pub fn update_keyboard_layout(&self) {
+    let keyboard_layout = Self::foreground_keyboard_layout();
+
+    if self
+      .keyboard_layout
+      .swap(keyboard_layout.0, Ordering::Relaxed)
+      == keyboard_layout.0
+    {
+      return;
+    }
+
+    *self.keybindings_by_trigger_key.lock().unwrap() =
+      Self::keybindings_by_trigger_key(
+        &self.keybindings.lock().unwrap(),
+        keyboard_layout,
+      );
+
+    let _ = self.event_tx.send(PlatformEvent::KeyboardLayoutChanged(
+      Self::keyboard_layout_info(keyboard_layout),
+    ));
+  }
+
+  /// Redirects key presses to the given window until `release_keys` is
+  /// called. Each key press is posted as `message` with the virtual key
+  /// code as `wparam`, and is neither sent to other applications nor
+  /// able to trigger keybindings.
+  ///
+  /// Modifier keys are not captured.
+  ///
+  /// # Panics
+  ///
+  /// If the internal mutex is poisoned.
+  pub(crate) fn capture_keys(&self, handle: isize, message: u32) {
+    *self.key_capture.lock().unwrap() = Some((handle, message));
+  }
+
+  /// Stops redirecting key presses that were captured via
+  /// `capture_keys`.
+  ///
+  /// # Panics
+  ///
+  /// If the internal mutex is poisoned.
+  pub(crate) fn release_keys(&self) {
+    *self.key_capture.lock().unwrap() = None;
+  }
+
+  /// Gets the keyboard layout of the foreground window.
+  ///
+  /// Keyboard layouts are per-thread, so the layout of the WM's own
+  /// threads doesn't reflect the layout that the user is typing with.
+  pub(crate) fn foreground_keyboard_layout() -> HKL {
+    let thread_id =
+      unsafe { GetWindowThreadProcessId(GetForegroundWindow(), None) };
+
+    unsafe { GetKeyboardLayout(thread_id) }
+  }
+
+  pub(crate) fn keyboard_layout_info(
+    keyboard_layout: HKL,
+  ) -> KeyboardLayout {
+    // The low-order word contains the language identifier.
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    let language_id = (keyboard_layout.0 as usize & 0xffff) as u32;
+
+    let mut locale_name = [0; 85];
+    let length =
+      unsafe { LCIDToLocaleName(language_id, Some(&mut locale_name), 0) };
+
+    KeyboardLayout {
+      handle: keyboard_layout.0,
+      locale_name: String::from_utf16_lossy(
+        &locale_name[..usize::try_from(length - 1).unwrap_or(0)],
+      ),
+    }
   }
 
   /// Stops the low-level keyboard hook.
@@ -133,6 +332,7 @@ impl KeyboardHook {
 
   fn keybindings_by_trigger_key(
     keybindings: &Vec<KeybindingConfig>,
+    keyboard_layout: HKL,
   ) -> HashMap<u16, Vec<ActiveKeybinding>> {
     let mut keybinding_map = HashMap::new();
 
@@ -141,7 +341,8 @@ impl KeyboardHook {
         let vk_codes = binding
           .split('+')
           .filter_map(|key| {
-            let vk_code = Self::key_to_vk_code(key);
+            let vk_code =
+              Self::key_to_vk_code_for_layout(key, keyboard_layout);
 
             if vk_code.is_none() {
               warn!(
@@ -157,11 +358,17 @@ impl KeyboardHook {
         // Safety: A split string always has at least one element.
         let trigger_key = *vk_codes.last().unwrap();
 
+        let trigger_scan_code = binding
+          .split('+')
+          .next_back()
+          .and_then(|key| Self::key_to_scan_code(&key.to_lowercase()));
+
         keybinding_map
           .entry(trigger_key)
           .or_insert_with(Vec::new)
           .push(ActiveKeybinding {
             vk_codes,
+            trigger_scan_code,
             config: keybinding.clone(),
           });
       }
@@ -170,8 +377,25 @@ impl KeyboardHook {
     keybinding_map
   }
 
+  /// Gets the virtual key code for a key name (e.g. `lwin`, `a`).
+  ///
+  /// Returns `None` if the key isn't recognized on the keyboard layout
+  /// of the foreground window.
+  #[must_use]
+  pub fn key_to_vk_code(key: &str) -> Option<u16> {
+    Self::key_to_vk_code_for_layout(
+      key,
+      Self::foreground_keyboard_layout(),
+    )
+  }
+
+  /// Gets the virtual key code for a key name on the given keyboard
+  /// layout.
   #[allow(clippy::too_many_lines)]
-  fn key_to_vk_code(key: &str) -> Option<u16> {
+  fn key_to_vk_code_for_layout(
+    key: &str,
+    keyboard_layout: HKL,
+  ) -> Option<u16> {
     match key.to_lowercase().as_str() {
       "a" => Some(VK_A.0),
       "b" => Some(VK_B.0),
@@ -298,11 +522,28 @@ impl KeyboardHook {
       "oem_period" => Some(VK_OEM_PERIOD.0),
       "muhenkan" => Some(VK_NONCONVERT.0),
       "henkan" => Some(VK_CONVERT.0),
+      // Keys bound by physical position are resolved to whichever
+      // virtual key is at that position on the given layout.
+      key if key.starts_with("sc:") || key.starts_with("qwerty:") => {
+        let scan_code = Self::key_to_scan_code(key)?;
+
+        let vk_code = unsafe {
+          MapVirtualKeyExW(
+            u32::from(scan_code),
+            MAPVK_VSC_TO_VK_EX,
+            keyboard_layout,
+          )
+        };
+
+        match vk_code {
+          0 => None,
+          vk_code => u16::try_from(vk_code).ok(),
+        }
+      }
       _ => {
         // Check if the key exists on the current keyboard layout.
         let utf16_key = key.encode_utf16().next()?;
-        let layout = unsafe { GetKeyboardLayout(0) };
-        let vk_code = unsafe { VkKeyScanExW(utf16_key, layout) };
+        let vk_code = unsafe { VkKeyScanExW(utf16_key, keyboard_layout) };
 
         if vk_code == -1 {
           return None;
@@ -321,11 +562,80 @@ impl KeyboardHook {
     }
   }
 
+  /// Gets the scan code for a key that's bound by physical position,
+  /// either directly (e.g. `sc:0x1e`, with an `e0` prefix for extended
+  /// keys) or by its position on a QWERTY keyboard (e.g. `qwerty:a`).
+  ///
+  /// Expects the key name in lowercase.
+  fn key_to_scan_code(key: &str) -> Option<u16> {
+    if let Some(scan_code) = key.strip_prefix("sc:") {
+      return match scan_code.strip_prefix("0x") {
+        Some(hex) => u16::from_str_radix(hex, 16).ok(),
+        None => scan_code.parse::<u16>().ok(),
+      };
+    }
+
+    let qwerty_key = key.strip_prefix("qwerty:")?;
+
+    QWERTY_SCAN_CODES
+      .iter()
+      .find(|(name, _)| *name == qwerty_key)
+      .map(|(_, scan_code)| *scan_code)
+  }
+
+  /// Redirects the key event to the capturing window if keys are
+  /// currently being captured.
+  ///
+  /// Returns `true` if the event should be blocked and not sent to other
+  /// applications.
+  fn handle_captured_key_event(
+    &self,
+    vk_code: u16,
+    is_key_down: bool,
+  ) -> bool {
+    let Some((handle, message)) = *self.key_capture.lock().unwrap() else {
+      return false;
+    };
+
+    let is_modifier_key = MODIFIER_KEYS.contains(&vk_code)
+      || vk_code == VK_LWIN.0
+      || vk_code == VK_RWIN.0;
+
+    if is_modifier_key {
+      return false;
+    }
+
+    // Key up events are blocked as well, since the corresponding key down
+    // events never reached other applications.
+    if is_key_down {
+      if let Err(err) = unsafe {
+        PostMessageW(
+          HWND(handle),
+          message,
+          WPARAM(vk_code.into()),
+          LPARAM::default(),
+        )
+      } {
+        warn!("Failed to redirect captured key: {}", err);
+      }
+    }
+
+    true
+  }
+
   /// Emits a platform event if a keybinding should be triggered.
   ///
   /// Returns `true` if the event should be blocked and not sent to other
   /// applications.
-  fn handle_key_event(&self, vk_code: u16) -> bool {
+  fn handle_key_event(&self, vk_code: u16, scan_code: u16) -> bool {
+    // Key repeats of a held trigger key are handled the same as its
+    // initial key press, even if the other keys have been released.
+    if let Some(held) = self.held_keybinding.lock().unwrap().as_ref() {
+      if held.keybinding.vk_codes.last() == Some(&vk_code) {
+        return !held.keybinding.config.passthrough;
+      }
+    }
+
     match self
       .keybindings_by_trigger_key
       .lock()
@@ -339,21 +649,28 @@ impl KeyboardHook {
         let mut cached_key_states = HashMap::new();
 
         // Find the matching keybindings based on the pressed keys.
+        // Keybindings bound by position additionally require the key at
+        // that position to be the one pressed.
         let matched_keybindings =
           keybindings.iter().filter(|keybinding| {
-            keybinding.vk_codes.iter().all(|&key| {
-              if key == vk_code {
-                return true;
-              }
-
-              if let Some(&is_key_down) = cached_key_states.get(&key) {
-                return is_key_down;
-              }
-
-              let is_key_down = Self::is_key_down(key);
-              cached_key_states.insert(key, is_key_down);
-              is_key_down
-            })
+            keybinding
+              .trigger_scan_code
+              .is_none_or(|trigger_scan_code| {
+                trigger_scan_code == scan_code
+              })
+              && keybinding.vk_codes.iter().all(|&key| {
+                if key == vk_code {
+                  return true;
+                }
+
+                if let Some(&is_key_down) = cached_key_states.get(&key) {
+                  return is_key_down;
+                }
+
+                let is_key_down = Self::is_key_down(key);
+                cached_key_states.insert(key, is_key_down);
+                is_key_down
+              })
           });
 
         // Find the longest matching keybinding.
@@ -392,17 +709,80 @@ impl KeyboardHook {
           return false;
         }
 
+        // Keybindings with release commands are only triggered once
+        // while held, so that key repeats are ignored.
+        if !longest_keybinding.config.release_commands.is_empty() {
+          let mut held_keybinding = self.held_keybinding.lock().unwrap();
+
+          if held_keybinding.is_some() {
+            return !longest_keybinding.config.passthrough;
+          }
+
+          *held_keybinding = Some(HeldKeybinding {
+            keybinding: longest_keybinding.clone(),
+            is_released: false,
+          });
+        }
+
         // Invoke the callback function for the longest matching
         // keybinding.
         let _ = self.event_tx.send(PlatformEvent::KeybindingTriggered(
           longest_keybinding.config.clone(),
         ));
 
-        true
+        // Passthrough keybindings forward the key press to the focused
+        // application.
+        !longest_keybinding.config.passthrough
       }
     }
   }
 
+  /// Emits a platform event if a held keybinding is released.
+  ///
+  /// Returns `true` if the event should be blocked and not sent to other
+  /// applications.
+  fn handle_key_up_event(&self, vk_code: u16) -> bool {
+    let mut held_keybinding = self.held_keybinding.lock().unwrap();
+
+    let Some(held) = held_keybinding.as_mut() else {
+      return false;
+    };
+
+    let vk_codes = &held.keybinding.vk_codes;
+    let is_trigger_key = vk_codes.last() == Some(&vk_code);
+
+    let is_held_key = vk_codes
+      .iter()
+      .any(|&key| key == vk_code || key == Self::generic_key(vk_code));
+
+    if !is_held_key {
+      return false;
+    }
+
+    // Release commands are run once the first key of the keybinding is
+    // released.
+    if !held.is_released {
+      held.is_released = true;
+
+      let _ = self.event_tx.send(PlatformEvent::KeybindingReleased(
+        held.keybinding.config.clone(),
+      ));
+    }
+
+    // Releasing modifier keys is forwarded to other applications, since
+    // their key down events weren't blocked. The keybinding is tracked
+    // until the trigger key is released, so that its key up event gets
+    // blocked as well.
+    if !is_trigger_key {
+      return false;
+    }
+
+    let is_passthrough = held.keybinding.config.passthrough;
+    *held_keybinding = None;
+
+    !is_passthrough
+  }
+
   /// Gets the generic key code for a given key code.
   fn generic_key(key: u16) -> u16 {
     match VIRTUAL_KEY(key) {
@@ -445,23 +825,48 @@ extern "system" fn keyboard_hook_proc(
   lparam: LPARAM,
 ) -> LRESULT {
   #[allow(clippy::cast_possible_truncation)]
-  let should_ignore = code != 0
-    || !(wparam.0 as u32 == WM_KEYDOWN
-      || wparam.0 as u32 == WM_SYSKEYDOWN);
+  let message = wparam.0 as u32;
+
+  let is_key_down = message == WM_KEYDOWN || message == WM_SYSKEYDOWN;
+  let is_key_up = message == WM_KEYUP || message == WM_SYSKEYUP;
 
   // If the code is less than zero, the hook procedure must pass the hook
   // notification directly to other applications. We also only care about
-  // keydown events.
-  if should_ignore {
+  // keydown and keyup events.
+  if code != 0 || !(is_key_down || is_key_up) {
     return unsafe { CallNextHookEx(None, code, wparam, lparam) };
   }
 
   // Get struct with keyboard input event.
   let input = unsafe { *(lparam.0 as *const KBDLLHOOKSTRUCT) };
 
+  // Ignore keys simulated via the `send-keys` command, so that they
+  // don't trigger keybindings.
+  if input.dwExtraInfo == SEND_KEYS_INPUT_IDENTIFIER as usize {
+    return unsafe { CallNextHookEx(None, code, wparam, lparam) };
+  }
+
   if let Some(hook) = KEYBOARD_HOOK.get() {
     #[allow(clippy::cast_possible_truncation)]
-    let should_block = hook.handle_key_event(input.vkCode as u16);
+    let vk_code = input.vkCode as u16;
+
+    // Extended keys (e.g. arrow keys) have an `e0` prefix.
+    #[allow(clippy::cast_possible_truncation)]
+    let scan_code = if input.flags.contains(LLKHF_EXTENDED) {
+      0xE000 | input.scanCode as u16
+    } else {
+      input.scanCode as u16
+    };
+
+    if hook.handle_captured_key_event(vk_code, is_key_down) {
+      return LRESULT(1);
+    }
+
+    let should_block = if is_key_down {
+      hook.handle_key_event(vk_code, scan_code)
+    } else {
+      hook.handle_key_up_event(vk_code)
+    };
 
     if should_block {
       return LRESULT(1);
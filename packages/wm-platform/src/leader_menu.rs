@@ -0,0 +1,391 @@
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::bail;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::Gdi::{
+      BeginPaint, EndPaint, GetMonitorInfoW, InvalidateRect,
+      MonitorFromWindow, DT_LEFT, FW_NORMAL, FW_SEMIBOLD, MONITORINFO,
+      MONITOR_DEFAULTTOPRIMARY, PAINTSTRUCT,
+    },
+    UI::{
+      Input::KeyboardAndMouse::{VK_BACK, VK_ESCAPE},
+      WindowsAndMessaging::{
+        DefWindowProcW, GetClientRect, GetForegroundWindow, PostMessageW,
+        SetWindowPos, ShowWindow, HWND_TOPMOST, SWP_NOMOVE,
+        SWP_SHOWWINDOW, SW_HIDE, WA_INACTIVE, WM_ACTIVATE, WM_APP,
+        WM_CHAR, WM_KEYDOWN, WM_PAINT, WNDCLASSW, WS_EX_TOPMOST, WS_POPUP,
+      },
+    },
+  },
+};
+
+use crate::{
+  overlay::{
+    create_overlay_window, draw_text, fill_rect, rgb,
+    spawn_overlay_thread, TextStyle,
+  },
+  NativeWindow,
+};
+
+/// Custom message for showing the leader menu.
+const WM_SHOW_MENU: u32 = WM_APP + 1;
+
+/// Width of the leader menu window in pixels.
+const MENU_WIDTH: i32 = 360;
+
+/// Height of the title and of each entry row in pixels.
+const ROW_HEIGHT: i32 = 28;
+
+/// Width of the key part of an entry row in pixels.
+const KEY_WIDTH: i32 = 36;
+
+/// Padding around the contents of the menu in pixels.
+const MENU_PADDING: i32 = 8;
+
+/// Handle to the leader menu window. Created on first use.
+static MENU_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// State of the leader menu while it's shown.
+///
+/// For use with window procedure.
+static MENU: Mutex<Option<MenuState>> = Mutex::new(None);
+
+/// Entry that can be selected in the leader menu by pressing its key.
+#[derive(Clone, Debug)]
+pub struct LeaderMenuEntry {
+  pub key: char,
+  pub label: String,
+
+  /// Commands that are sent when the entry is selected.
+  pub commands: Vec<String>,
+
+  /// Entries of the nested menu that is opened when the entry is
+  /// selected. Takes precedence over `commands`.
+  pub entries: Vec<LeaderMenuEntry>,
+}
+
+struct MenuState {
+  title: String,
+  entries: Vec<LeaderMenuEntry>,
+
+  /// Indices of the nested menus that are currently open, from the
+  /// outermost to the innermost menu.
+  path: Vec<usize>,
+
+  /// Sender for the commands of the selected entry.
+  selection_tx: UnboundedSender<String>,
+}
+
+impl MenuState {
+  /// Title and entries of the innermost open menu.
+  fn current_menu(&self) -> (&str, &[LeaderMenuEntry]) {
+    self.path.iter().fold(
+      (self.title.as_str(), self.entries.as_slice()),
+      |(_, entries), index| {
+        let entry = &entries[*index];
+        (entry.label.as_str(), entry.entries.as_slice())
+      },
+    )
+  }
+}
+
+/// Shows the leader menu on the monitor of the foreground window.
+///
+/// The commands of the selected entry are sent via `selection_tx`.
+/// Nothing is sent if the menu is dismissed.
+pub(crate) fn show_leader_menu(
+  title: String,
+  entries: Vec<LeaderMenuEntry>,
+  selection_tx: UnboundedSender<String>,
+) -> anyhow::Result<()> {
+  let handle = *MENU_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Leader menu", create_menu_window)
+  })?;
+
+  *MENU.lock().unwrap() = Some(MenuState {
+    title,
+    entries,
+    path: Vec::new(),
+    selection_tx,
+  });
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_SHOW_MENU,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_menu_window() -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("LeaderMenuWindow"),
+    lpfnWndProc: Some(menu_window_proc),
+    ..Default::default()
+  };
+
+  let handle = create_overlay_window(&wnd_class, WS_EX_TOPMOST, WS_POPUP)?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the leader menu window.
+extern "system" fn menu_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_SHOW_MENU => {
+      if let Err(err) = position_menu_window(handle) {
+        warn!("Failed to show leader menu: {}", err);
+      }
+
+      LRESULT(0)
+    }
+    // Dismiss the menu when it loses focus.
+    WM_ACTIVATE if wparam.0 & 0xffff == WA_INACTIVE as usize => {
+      hide_menu(handle);
+      LRESULT(0)
+    }
+    WM_CHAR => {
+      let char = char::from_u32(u32::try_from(wparam.0).unwrap_or(0));
+
+      if let Some(char) = char.filter(|char| !char.is_control()) {
+        select_entry(handle, char);
+      }
+
+      LRESULT(0)
+    }
+    WM_KEYDOWN => {
+      #[allow(clippy::cast_possible_truncation)]
+      let key = wparam.0 as u16;
+
+      if key == VK_ESCAPE.0 {
+        hide_menu(handle);
+      } else if key == VK_BACK.0 {
+        // Go back to the parent menu, or dismiss if at the top level.
+        let mut menu = MENU.lock().unwrap();
+        let is_nested = menu
+          .as_mut()
+          .is_some_and(|state| state.path.pop().is_some());
+
+        drop(menu);
+
+        if is_nested {
+          reposition_menu_window(handle);
+        } else {
+          hide_menu(handle);
+        }
+      }
+
+      LRESULT(0)
+    }
+    WM_PAINT => {
+      paint_menu_window(handle);
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+/// Selects the entry of the current menu with the given key. Opens the
+/// entry's nested menu if it has one, and otherwise sends its commands
+/// and dismisses the menu.
+fn select_entry(handle: HWND, key: char) {
+  let mut menu = MENU.lock().unwrap();
+  let Some(state) = menu.as_mut() else {
+    return;
+  };
+
+  let (_, entries) = state.current_menu();
+
+  let Some(index) = entries.iter().position(|entry| entry.key == key)
+  else {
+    return;
+  };
+
+  let entry = &entries[index];
+
+  if !entry.entries.is_empty() {
+    state.path.push(index);
+    drop(menu);
+    reposition_menu_window(handle);
+    return;
+  }
+
+  for command in &entry.commands {
+    let _ = state.selection_tx.send(command.clone());
+  }
+
+  drop(menu);
+  hide_menu(handle);
+}
+
+fn hide_menu(handle: HWND) {
+  *MENU.lock().unwrap() = None;
+  unsafe { ShowWindow(handle, SW_HIDE) };
+}
+
+/// Height of the menu window for the current menu.
+fn menu_height() -> i32 {
+  let entry_count = MENU
+    .lock()
+    .unwrap()
+    .as_ref()
+    .map_or(0, |state| state.current_menu().1.len());
+
+  #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+  let height = ROW_HEIGHT * (entry_count as i32 + 1) + MENU_PADDING * 2;
+
+  height
+}
+
+/// Centers the menu horizontally near the top of the work area of the
+/// foreground window's monitor, and brings it to the foreground.
+fn position_menu_window(handle: HWND) -> anyhow::Result<()> {
+  let monitor = unsafe {
+    MonitorFromWindow(GetForegroundWindow(), MONITOR_DEFAULTTOPRIMARY)
+  };
+
+  let mut monitor_info = MONITORINFO {
+    #[allow(clippy::cast_possible_truncation)]
+    cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+    ..Default::default()
+  };
+
+  if !unsafe { GetMonitorInfoW(monitor, &raw mut monitor_info) }.as_bool()
+  {
+    bail!("Failed to get monitor info.");
+  }
+
+  let work_area = monitor_info.rcWork;
+
+  unsafe {
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      work_area.left + (work_area.right - work_area.left - MENU_WIDTH) / 2,
+      work_area.top + (work_area.bottom - work_area.top) / 5,
+      MENU_WIDTH,
+      menu_height(),
+      SWP_SHOWWINDOW,
+    )?;
+
+    InvalidateRect(handle, None, true);
+  };
+
+  NativeWindow::new(handle.0).set_foreground()
+}
+
+/// Resizes the menu to fit the entries of the current menu, keeping its
+/// position.
+fn reposition_menu_window(handle: HWND) {
+  let _ = unsafe {
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      0,
+      0,
+      MENU_WIDTH,
+      menu_height(),
+      SWP_NOMOVE,
+    )
+  };
+
+  unsafe { InvalidateRect(handle, None, true) };
+}
+
+fn paint_menu_window(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  let hdc = unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  if let Some(state) = MENU.lock().unwrap().as_ref() {
+    let mut bounds = RECT::default();
+    let _ = unsafe { GetClientRect(handle, &raw mut bounds) };
+
+    fill_rect(hdc, &bounds, rgb(0x20, 0x20, 0x20));
+
+    let (title, entries) = state.current_menu();
+
+    let title_bounds = RECT {
+      left: MENU_PADDING,
+      top: MENU_PADDING,
+      right: bounds.right - MENU_PADDING,
+      bottom: MENU_PADDING + ROW_HEIGHT,
+    };
+
+    draw_text(
+      hdc,
+      title,
+      title_bounds,
+      &TextStyle {
+        height: 18,
+        weight: FW_SEMIBOLD.0,
+        color: rgb(0x42, 0x8b, 0xf5),
+        alignment: DT_LEFT,
+      },
+    );
+
+    for (row, entry) in entries.iter().enumerate() {
+      #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap
+      )]
+      let top = title_bounds.bottom + ROW_HEIGHT * row as i32;
+
+      draw_text(
+        hdc,
+        &entry.key.to_string(),
+        RECT {
+          top,
+          bottom: top + ROW_HEIGHT,
+          right: title_bounds.left + KEY_WIDTH,
+          ..title_bounds
+        },
+        &TextStyle {
+          height: 18,
+          weight: FW_SEMIBOLD.0,
+          color: rgb(0xf0, 0xf0, 0xf0),
+          alignment: DT_LEFT,
+        },
+      );
+
+      // Entries with a nested menu are suffixed with an ellipsis.
+      let label = if entry.entries.is_empty() {
+        entry.label.clone()
+      } else {
+        format!("{}…", entry.label)
+      };
+
+      draw_text(
+        hdc,
+        &label,
+        RECT {
+          top,
+          bottom: top + ROW_HEIGHT,
+          left: title_bounds.left + KEY_WIDTH,
+          ..title_bounds
+        },
+        &TextStyle {
+          height: 18,
+          weight: FW_NORMAL.0,
+          color: rgb(0xc0, 0xc0, 0xc0),
+          alignment: DT_LEFT,
+        },
+      );
+    }
+  }
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
@@ -3,22 +3,60 @@
 #![feature(iterator_try_collect)]
 #![feature(once_cell_try)]
 
+mod announcer;
+mod audio_sessions;
+mod capability_access;
 mod com;
+mod command_palette;
+mod controllers;
 mod event_listener;
 mod event_window;
+mod focus_assist;
+mod focus_frame;
+mod keybindings_overlay;
 mod keyboard_hook;
+mod leader_menu;
+mod magnifier;
 mod native_monitor;
 mod native_window;
+mod notification_window;
+mod overlay;
+mod overview;
+mod placeholder_window;
 mod platform;
+mod process_stats;
+mod resize_guides;
+mod send_keys;
 mod single_instance;
+mod status_bar;
 mod window_event_hook;
+mod window_hints;
+mod window_picker;
+mod window_pos_batch;
+mod window_switcher;
+mod window_thumbnail;
 
+pub use capability_access::*;
 pub use com::*;
+pub use command_palette::*;
 pub use event_listener::*;
 pub use event_window::*;
+pub use focus_frame::*;
+pub use keybindings_overlay::*;
 pub use keyboard_hook::*;
+pub use leader_menu::*;
+pub use magnifier::*;
 pub use native_monitor::*;
 pub use native_window::*;
+pub use notification_window::*;
+pub use overview::*;
 pub use platform::*;
+pub use resize_guides::*;
+pub use send_keys::*;
 pub use single_instance::*;
+pub use status_bar::*;
 pub use window_event_hook::*;
+pub use window_hints::*;
+pub use window_pos_batch::*;
+pub use window_switcher::*;
+pub use window_thumbnail::*;
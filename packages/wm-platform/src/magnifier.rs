@@ -0,0 +1,124 @@
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::bail;
+use tracing::warn;
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+    UI::{
+      Magnification::{MagInitialize, MagSetFullscreenTransform},
+      WindowsAndMessaging::{
+        DefWindowProcW, PostMessageW, WM_APP, WNDCLASSW, WS_EX_NOACTIVATE,
+        WS_POPUP,
+      },
+    },
+  },
+};
+use wm_common::Point;
+
+use crate::overlay::{create_overlay_window, spawn_overlay_thread};
+
+/// Custom message for applying the zoom in `ZOOM`.
+const WM_UPDATE_ZOOM: u32 = WM_APP + 1;
+
+/// Handle to the hidden window that the magnifier is controlled from.
+/// Created on first use.
+static MAGNIFIER_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// Zoom that is currently applied, or `None` if zoomed out.
+///
+/// For use with window procedure.
+static ZOOM: Mutex<Option<Zoom>> = Mutex::new(None);
+
+/// Full-screen magnification of a region of the desktop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Zoom {
+  /// Magnification factor (e.g. 2.0 for 200%). Must be at least 1.
+  pub factor: f32,
+
+  /// Top-left corner of the magnified region in screen coordinates.
+  pub offset: Point,
+}
+
+/// Magnifies the screen to show the given region, or restores the
+/// normal view if `None`.
+///
+/// Does nothing if the zoom is unchanged.
+pub(crate) fn update_zoom(zoom: Option<Zoom>) -> anyhow::Result<()> {
+  {
+    let mut current_zoom = ZOOM.lock().unwrap();
+
+    if *current_zoom == zoom {
+      return Ok(());
+    }
+
+    *current_zoom = zoom;
+  }
+
+  let handle = *MAGNIFIER_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Magnifier", create_magnifier_window)
+  })?;
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_UPDATE_ZOOM,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_magnifier_window() -> anyhow::Result<isize> {
+  // The magnification runtime is initialized on the thread that applies
+  // the transforms.
+  if !unsafe { MagInitialize() }.as_bool() {
+    bail!("Failed to initialize magnifier.");
+  }
+
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("MagnifierWindow"),
+    lpfnWndProc: Some(magnifier_window_proc),
+    ..Default::default()
+  };
+
+  // Window is never shown and only receives messages for updating the
+  // zoom.
+  let handle =
+    create_overlay_window(&wnd_class, WS_EX_NOACTIVATE, WS_POPUP)?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the magnifier window.
+extern "system" fn magnifier_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_UPDATE_ZOOM => {
+      let zoom = ZOOM.lock().unwrap().clone();
+
+      let (factor, offset) = zoom
+        .map_or((1., Point { x: 0, y: 0 }), |zoom| {
+          (zoom.factor.max(1.), zoom.offset)
+        });
+
+      let is_applied =
+        unsafe { MagSetFullscreenTransform(factor, offset.x, offset.y) }
+          .as_bool();
+
+      if !is_applied {
+        warn!("Failed to set magnifier zoom to {}.", factor);
+      }
+
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
@@ -6,52 +6,96 @@ use tracing::warn;
 use windows::{
   core::PWSTR,
   Win32::{
-    Foundation::{CloseHandle, BOOL, HWND, LPARAM, RECT},
+    Foundation::{CloseHandle, BOOL, HWND, LPARAM, RECT, WPARAM},
     Graphics::Dwm::{
       DwmGetWindowAttribute, DwmSetWindowAttribute, DWMWA_BORDER_COLOR,
       DWMWA_CLOAKED, DWMWA_COLOR_NONE, DWMWA_EXTENDED_FRAME_BOUNDS,
-      DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DEFAULT, DWMWCP_DONOTROUND,
-      DWMWCP_ROUND, DWMWCP_ROUNDSMALL,
+      DWMWA_TRANSITIONS_FORCEDISABLED, DWMWA_WINDOW_CORNER_PREFERENCE,
+      DWMWCP_DEFAULT, DWMWCP_DONOTROUND, DWMWCP_ROUND, DWMWCP_ROUNDSMALL,
     },
     System::Threading::{
-      OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
-      PROCESS_QUERY_LIMITED_INFORMATION,
+      OpenProcess, QueryFullProcessImageNameW, TerminateProcess,
+      PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+      PROCESS_TERMINATE,
     },
     UI::{
       Input::KeyboardAndMouse::{
         SendInput, INPUT, INPUT_0, INPUT_MOUSE, MOUSEINPUT,
       },
       WindowsAndMessaging::{
-        EnumWindows, GetClassNameW, GetLayeredWindowAttributes, GetWindow,
-        GetWindowLongPtrW, GetWindowRect, GetWindowTextW,
-        GetWindowThreadProcessId, IsIconic, IsWindowVisible, IsZoomed,
-        SendNotifyMessageW, SetForegroundWindow,
+        EnumWindows, GetClassNameW, GetLayeredWindowAttributes,
+        GetSystemMetrics, GetWindow, GetWindowLongPtrW, GetWindowRect,
+        GetWindowTextW, GetWindowThreadProcessId, IsHungAppWindow,
+        IsIconic, IsWindow, IsWindowVisible, IsZoomed,
+        SendMessageTimeoutW, SendNotifyMessageW, SetForegroundWindow,
         SetLayeredWindowAttributes, SetWindowLongPtrW, SetWindowPlacement,
-        SetWindowPos, ShowWindowAsync, GWL_EXSTYLE, GWL_STYLE, GW_OWNER,
-        HWND_NOTOPMOST, HWND_TOP, HWND_TOPMOST,
+        SetWindowPos, ShowWindowAsync, GWL_EXSTYLE, GWL_STYLE,
+        GW_HWNDPREV, GW_OWNER, HWND_NOTOPMOST, HWND_TOP, HWND_TOPMOST,
         LAYERED_WINDOW_ATTRIBUTES_FLAGS, LWA_ALPHA, LWA_COLORKEY,
+        SMTO_ABORTIFHUNG, SM_CXVIRTUALSCREEN, SM_XVIRTUALSCREEN,
         SWP_ASYNCWINDOWPOS, SWP_FRAMECHANGED, SWP_NOACTIVATE,
         SWP_NOCOPYBITS, SWP_NOMOVE, SWP_NOOWNERZORDER, SWP_NOSENDCHANGING,
         SWP_NOSIZE, SWP_NOZORDER, SWP_SHOWWINDOW, SW_HIDE, SW_MAXIMIZE,
         SW_MINIMIZE, SW_RESTORE, SW_SHOWNA, WINDOWPLACEMENT,
-        WINDOW_EX_STYLE, WINDOW_STYLE, WM_CLOSE, WPF_ASYNCWINDOWPLACEMENT,
-        WS_CAPTION, WS_CHILD, WS_DLGFRAME, WS_EX_LAYERED,
-        WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_MAXIMIZEBOX, WS_THICKFRAME,
+        WINDOW_EX_STYLE, WINDOW_STYLE, WM_CLOSE, WM_NULL,
+        WPF_ASYNCWINDOWPLACEMENT, WS_BORDER, WS_CAPTION, WS_CHILD,
+        WS_DISABLED, WS_DLGFRAME, WS_EX_APPWINDOW, WS_EX_CLIENTEDGE,
+        WS_EX_COMPOSITED, WS_EX_DLGMODALFRAME, WS_EX_LAYERED,
+        WS_EX_NOACTIVATE, WS_EX_NOREDIRECTIONBITMAP, WS_EX_TOOLWINDOW,
+        WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_EX_WINDOWEDGE, WS_HSCROLL,
+        WS_MAXIMIZE, WS_MAXIMIZEBOX, WS_MINIMIZE, WS_MINIMIZEBOX,
+        WS_POPUP, WS_SYSMENU, WS_THICKFRAME, WS_VISIBLE, WS_VSCROLL,
       },
     },
   },
 };
 use wm_common::{
   Color, CornerStyle, Delta, HideMethod, LengthValue, Memo, OpacityValue,
-  Rect, RectDelta, WindowState,
+  ProcessStats, Rect, RectDelta, WindowState,
 };
 
-use super::COM_INIT;
+use super::{
+  platform::is_process_elevated, process_stats::process_stats, Platform,
+  WindowPosBatch, COM_INIT,
+};
 
 /// Magic number used to identify programmatic mouse inputs from our own
 /// process.
 pub const FOREGROUND_INPUT_IDENTIFIER: u32 = 6379;
 
+/// Window styles and their names, for use in diagnostics.
+const WINDOW_STYLE_NAMES: [(WINDOW_STYLE, &str); 14] = [
+  (WS_POPUP, "WS_POPUP"),
+  (WS_CHILD, "WS_CHILD"),
+  (WS_MINIMIZE, "WS_MINIMIZE"),
+  (WS_VISIBLE, "WS_VISIBLE"),
+  (WS_DISABLED, "WS_DISABLED"),
+  (WS_MAXIMIZE, "WS_MAXIMIZE"),
+  (WS_BORDER, "WS_BORDER"),
+  (WS_DLGFRAME, "WS_DLGFRAME"),
+  (WS_VSCROLL, "WS_VSCROLL"),
+  (WS_HSCROLL, "WS_HSCROLL"),
+  (WS_SYSMENU, "WS_SYSMENU"),
+  (WS_THICKFRAME, "WS_THICKFRAME"),
+  (WS_MINIMIZEBOX, "WS_MINIMIZEBOX"),
+  (WS_MAXIMIZEBOX, "WS_MAXIMIZEBOX"),
+];
+
+/// Extended window styles and their names, for use in diagnostics.
+const WINDOW_EX_STYLE_NAMES: [(WINDOW_EX_STYLE, &str); 11] = [
+  (WS_EX_DLGMODALFRAME, "WS_EX_DLGMODALFRAME"),
+  (WS_EX_TOPMOST, "WS_EX_TOPMOST"),
+  (WS_EX_TRANSPARENT, "WS_EX_TRANSPARENT"),
+  (WS_EX_TOOLWINDOW, "WS_EX_TOOLWINDOW"),
+  (WS_EX_WINDOWEDGE, "WS_EX_WINDOWEDGE"),
+  (WS_EX_CLIENTEDGE, "WS_EX_CLIENTEDGE"),
+  (WS_EX_APPWINDOW, "WS_EX_APPWINDOW"),
+  (WS_EX_LAYERED, "WS_EX_LAYERED"),
+  (WS_EX_NOACTIVATE, "WS_EX_NOACTIVATE"),
+  (WS_EX_NOREDIRECTIONBITMAP, "WS_EX_NOREDIRECTIONBITMAP"),
+  (WS_EX_COMPOSITED, "WS_EX_COMPOSITED"),
+];
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ZOrder {
   Normal,
@@ -65,6 +109,7 @@ pub struct NativeWindow {
   pub handle: isize,
   title: Memo<String>,
   process_name: Memo<String>,
+  process_path: Memo<String>,
   class_name: Memo<String>,
   frame_position: Memo<Rect>,
   border_position: Memo<Rect>,
@@ -80,6 +125,7 @@ impl NativeWindow {
       handle,
       title: Memo::new(),
       process_name: Memo::new(),
+      process_path: Memo::new(),
       class_name: Memo::new(),
       frame_position: Memo::new(),
       border_position: Memo::new(),
@@ -123,6 +169,29 @@ impl NativeWindow {
 
   /// Gets the process name associated with the window.
   fn updated_process_name(&self) -> anyhow::Result<String> {
+    self
+      .process_path()?
+      .split('\\')
+      .next_back()
+      .map(|file_name| {
+        file_name.split('.').next().unwrap_or(file_name).to_string()
+      })
+      .context("Failed to parse process name.")
+  }
+
+  /// Gets the path to the executable of the process associated with the
+  /// window.
+  ///
+  /// This value is lazily retrieved and cached after first retrieval.
+  pub fn process_path(&self) -> anyhow::Result<String> {
+    self
+      .process_path
+      .get_or_init(Self::updated_process_path, self)
+  }
+
+  /// Gets the ID of the process associated with the window.
+  #[must_use]
+  pub fn process_id(&self) -> u32 {
     let mut process_id = 0u32;
     unsafe {
       GetWindowThreadProcessId(
@@ -131,8 +200,24 @@ impl NativeWindow {
       );
     }
 
+    process_id
+  }
+
+  /// Gets the CPU and memory usage of the process associated with the
+  /// window.
+  pub fn process_stats(&self) -> anyhow::Result<ProcessStats> {
+    process_stats(self.process_id())
+  }
+
+  /// Gets the path to the executable of the process associated with the
+  /// window.
+  fn updated_process_path(&self) -> anyhow::Result<String> {
     let process_handle = unsafe {
-      OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id)
+      OpenProcess(
+        PROCESS_QUERY_LIMITED_INFORMATION,
+        false,
+        self.process_id(),
+      )
     }?;
 
     let mut buffer = [0u16; 256];
@@ -148,15 +233,7 @@ impl NativeWindow {
       CloseHandle(process_handle)?;
     };
 
-    let exe_path = String::from_utf16_lossy(&buffer[..length as usize]);
-
-    exe_path
-      .split('\\')
-      .next_back()
-      .map(|file_name| {
-        file_name.split('.').next().unwrap_or(file_name).to_string()
-      })
-      .context("Failed to parse process name.")
+    Ok(String::from_utf16_lossy(&buffer[..length as usize]))
   }
 
   /// Gets the class name of the window.
@@ -188,6 +265,43 @@ impl NativeWindow {
     Ok(is_visible && !self.is_cloaked()?)
   }
 
+  /// Whether the window handle still refers to an existing window.
+  #[must_use]
+  pub fn is_valid(&self) -> bool {
+    unsafe { IsWindow(HWND(self.handle)) }.as_bool()
+  }
+
+  /// Whether the window is considered hung by the OS (i.e. it hasn't
+  /// processed messages for several seconds). Doesn't block.
+  #[must_use]
+  pub fn is_hung(&self) -> bool {
+    unsafe { IsHungAppWindow(HWND(self.handle)) }.as_bool()
+  }
+
+  /// Whether the window processes a message within the given timeout.
+  ///
+  /// Blocks for up to the timeout, so shouldn't be called from the main
+  /// thread.
+  #[must_use]
+  pub fn is_responsive(&self, timeout: Duration) -> bool {
+    let timeout_ms =
+      u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX);
+
+    let res = unsafe {
+      SendMessageTimeoutW(
+        HWND(self.handle),
+        WM_NULL,
+        WPARAM::default(),
+        LPARAM::default(),
+        SMTO_ABORTIFHUNG,
+        timeout_ms,
+        None,
+      )
+    };
+
+    res.0 != 0
+  }
+
   /// Whether the window is cloaked. For some UWP apps, `WS_VISIBLE` will
   /// be present even if the window isn't actually visible. The
   /// `DWMWA_CLOAKED` attribute is used to check whether these apps are
@@ -289,6 +403,12 @@ impl NativeWindow {
     Ok(unsafe { IsZoomed(HWND(self.handle)) }.as_bool())
   }
 
+  /// Whether the window is shown above all non-topmost windows.
+  #[must_use]
+  pub fn is_topmost(&self) -> bool {
+    self.has_window_style_ex(WS_EX_TOPMOST)
+  }
+
   /// Whether the window has resize handles.
   #[must_use]
   pub fn is_resizable(&self) -> bool {
@@ -387,6 +507,27 @@ impl NativeWindow {
     Ok(())
   }
 
+  /// Enables or disables the DWM animations of the window (e.g. when it's
+  /// shown, hidden or minimized).
+  pub fn set_transitions_enabled(
+    &self,
+    enabled: bool,
+  ) -> anyhow::Result<()> {
+    let is_disabled = BOOL::from(!enabled);
+
+    unsafe {
+      #[allow(clippy::cast_possible_truncation)]
+      DwmSetWindowAttribute(
+        HWND(self.handle),
+        DWMWA_TRANSITIONS_FORCEDISABLED,
+        std::ptr::from_ref(&is_disabled).cast(),
+        std::mem::size_of::<BOOL>() as u32,
+      )?;
+    }
+
+    Ok(())
+  }
+
   pub fn set_title_bar_visibility(
     &self,
     visible: bool,
@@ -588,6 +729,44 @@ impl NativeWindow {
     ))
   }
 
+  /// Gets the names of the window's styles (e.g. `WS_CAPTION`).
+  #[must_use]
+  pub fn style_names(&self) -> Vec<String> {
+    WINDOW_STYLE_NAMES
+      .iter()
+      .filter(|(style, _)| self.has_window_style(*style))
+      .map(|(_, name)| (*name).to_string())
+      .collect()
+  }
+
+  /// Gets the names of the window's extended styles (e.g.
+  /// `WS_EX_TOOLWINDOW`).
+  #[must_use]
+  pub fn ex_style_names(&self) -> Vec<String> {
+    WINDOW_EX_STYLE_NAMES
+      .iter()
+      .filter(|(style, _)| self.has_window_style_ex(*style))
+      .map(|(_, name)| (*name).to_string())
+      .collect()
+  }
+
+  /// Whether the process associated with the window is running with
+  /// elevated (admin) privileges.
+  pub fn is_elevated(&self) -> anyhow::Result<bool> {
+    let process_handle = unsafe {
+      OpenProcess(
+        PROCESS_QUERY_LIMITED_INFORMATION,
+        false,
+        self.process_id(),
+      )
+    }?;
+
+    let is_elevated = is_process_elevated(process_handle);
+    unsafe { CloseHandle(process_handle) }?;
+
+    is_elevated
+  }
+
   fn has_window_style(&self, style: WINDOW_STYLE) -> bool {
     let current_style =
       unsafe { GetWindowLongPtrW(HWND(self.handle), GWL_STYLE) };
@@ -646,6 +825,51 @@ impl NativeWindow {
     Ok(())
   }
 
+  /// Gets the visible top-level windows of the window's process,
+  /// excluding this window. Cloaked windows are included.
+  pub fn sibling_process_windows(
+    &self,
+  ) -> anyhow::Result<Vec<NativeWindow>> {
+    let process_id = self.process_id();
+
+    Ok(
+      available_windows()?
+        .into_iter()
+        .filter(|window| {
+          window.handle != self.handle
+            && window.has_window_style(WS_VISIBLE)
+            && window.process_id() == process_id
+        })
+        .collect(),
+    )
+  }
+
+  /// Forcefully terminates the process associated with the window.
+  ///
+  /// This also closes any other windows of the process, so callers should
+  /// check `sibling_process_windows` first. Terminating the WM's own
+  /// process or the Windows shell is refused.
+  pub fn terminate_process(&self) -> anyhow::Result<()> {
+    let process_id = self.process_id();
+    let shell_process_id = Platform::desktop_window().process_id();
+
+    if process_id == 0
+      || process_id == std::process::id()
+      || process_id == shell_process_id
+    {
+      bail!("Refusing to terminate protected process {process_id}.");
+    }
+
+    let process_handle =
+      unsafe { OpenProcess(PROCESS_TERMINATE, false, process_id) }?;
+
+    let res = unsafe { TerminateProcess(process_handle, 1) };
+    unsafe { CloseHandle(process_handle) }?;
+    res?;
+
+    Ok(())
+  }
+
   pub fn set_visible(
     &self,
     visible: bool,
@@ -654,15 +878,80 @@ impl NativeWindow {
     match hide_method {
       HideMethod::Hide => {
         if visible {
+          // The window might've previously been cloaked with a different
+          // hide method.
+          if self.is_cloaked().unwrap_or(false) {
+            _ = self.set_cloaked(false);
+          }
+
           self.show()
         } else {
           self.hide()
         }
       }
-      HideMethod::Cloak => self.set_cloaked(!visible),
+      // The window is moved off-screen when it's positioned, so it only
+      // needs to be revealed if it was hidden with a different method.
+      HideMethod::Offscreen => {
+        if visible {
+          if self.is_cloaked().unwrap_or(false) {
+            _ = self.set_cloaked(false);
+          }
+
+          if !unsafe { IsWindowVisible(HWND(self.handle)) }.as_bool() {
+            self.show()?;
+          }
+        }
+
+        Ok(())
+      }
+      HideMethod::Cloak => {
+        // Cloaking relies on an undocumented COM interface, so fall back
+        // to hiding the window if it's not supported.
+        if let Err(err) = self.set_cloaked(!visible) {
+          warn!("Failed to cloak window. Falling back to hiding: {err}");
+          return self.set_visible(visible, &HideMethod::Hide);
+        }
+
+        // The window might've previously been hidden as a fallback or
+        // with a different hide method.
+        if visible
+          && !unsafe { IsWindowVisible(HWND(self.handle)) }.as_bool()
+        {
+          self.show()?;
+        }
+
+        Ok(())
+      }
     }
   }
 
+  /// Whether the window lies past the right edge of the virtual screen,
+  /// as is the case for windows hidden via `HideMethod::Offscreen`.
+  pub fn is_offscreen(&self) -> anyhow::Result<bool> {
+    let virtual_screen_right = unsafe {
+      GetSystemMetrics(SM_XVIRTUALSCREEN)
+        + GetSystemMetrics(SM_CXVIRTUALSCREEN)
+    };
+
+    Ok(self.refresh_frame_position()?.left >= virtual_screen_right)
+  }
+
+  /// Gets a rect of the same size as the given rect, placed just past
+  /// the right edge of the virtual screen (i.e. all monitors combined).
+  fn offscreen_rect(rect: &Rect) -> Rect {
+    let virtual_screen_right = unsafe {
+      GetSystemMetrics(SM_XVIRTUALSCREEN)
+        + GetSystemMetrics(SM_CXVIRTUALSCREEN)
+    };
+
+    Rect::from_xy(
+      virtual_screen_right,
+      rect.y(),
+      rect.width(),
+      rect.height(),
+    )
+  }
+
   pub fn show(&self) -> anyhow::Result<()> {
     unsafe { ShowWindowAsync(HWND(self.handle), SW_SHOWNA) }.ok()?;
     Ok(())
@@ -715,8 +1004,15 @@ impl NativeWindow {
     })
   }
 
+  /// Queues the window to be moved and resized in the given batch, and
+  /// to then be shown or hidden.
+  ///
+  /// Minimizing, maximizing, and restoring the window is done
+  /// immediately, since these can't be part of a batch.
+  #[allow(clippy::too_many_arguments)]
   pub fn set_position(
     &self,
+    batch: &mut WindowPosBatch,
     state: &WindowState,
     rect: &Rect,
     z_order: &ZOrder,
@@ -724,6 +1020,14 @@ impl NativeWindow {
     hide_method: &HideMethod,
     has_pending_dpi_adjustment: bool,
   ) -> anyhow::Result<()> {
+    let rect = if !is_visible && *hide_method == HideMethod::Offscreen {
+      Self::offscreen_rect(rect)
+    } else {
+      rect.clone()
+    };
+
+    let rect = &rect;
+
     // Restore window if it's minimized/maximized and shouldn't be. This is
     // needed to be able to move and resize it.
     match state {
@@ -746,10 +1050,8 @@ impl NativeWindow {
       }
     }
 
-    let mut swp_flags = SWP_NOACTIVATE
-      | SWP_NOCOPYBITS
-      | SWP_NOSENDCHANGING
-      | SWP_ASYNCWINDOWPOS;
+    let mut swp_flags =
+      SWP_NOACTIVATE | SWP_NOCOPYBITS | SWP_NOSENDCHANGING;
 
     let z_order = match z_order {
       ZOrder::TopMost => HWND_TOPMOST,
@@ -763,6 +1065,9 @@ impl NativeWindow {
         if !self.is_minimized()? {
           self.minimize()?;
         }
+
+        // Whether to hide or show the window.
+        return self.set_visible(is_visible, hide_method);
       }
       WindowState::Fullscreen(config)
         if config.maximized && self.has_window_style(WS_MAXIMIZEBOX) =>
@@ -771,56 +1076,31 @@ impl NativeWindow {
           self.maximize()?;
         }
 
-        unsafe {
-          SetWindowPos(
-            HWND(self.handle),
-            z_order,
-            rect.x(),
-            rect.y(),
-            rect.width(),
-            rect.height(),
-            swp_flags,
-          )
-        }?;
+        batch.push(
+          self,
+          rect,
+          z_order,
+          swp_flags,
+          false,
+          is_visible,
+          hide_method,
+        );
       }
       _ => {
         swp_flags |= SWP_FRAMECHANGED;
 
-        unsafe {
-          SetWindowPos(
-            HWND(self.handle),
-            z_order,
-            rect.x(),
-            rect.y(),
-            rect.width(),
-            rect.height(),
-            swp_flags,
-          )
-        }?;
-
-        // When there's a mismatch between the DPI of the monitor and the
-        // window, the window might be sized incorrectly after the first
-        // move. If we set the position twice, inconsistencies after the
-        // first move are resolved.
-        if has_pending_dpi_adjustment {
-          unsafe {
-            SetWindowPos(
-              HWND(self.handle),
-              z_order,
-              rect.x(),
-              rect.y(),
-              rect.width(),
-              rect.height(),
-              swp_flags,
-            )
-          }?;
-        }
+        batch.push(
+          self,
+          rect,
+          z_order,
+          swp_flags,
+          has_pending_dpi_adjustment,
+          is_visible,
+          hide_method,
+        );
       }
     }
 
-    // Whether to hide or show the window.
-    self.set_visible(is_visible, hide_method)?;
-
     Ok(())
   }
 
@@ -840,6 +1120,18 @@ impl NativeWindow {
     })
   }
 
+  /// Gets the handle of the window directly above this window in the
+  /// z-order, if there is one.
+  #[must_use]
+  pub fn window_above(&self) -> Option<isize> {
+    let handle = unsafe { GetWindow(HWND(self.handle), GW_HWNDPREV) };
+
+    match handle.0 {
+      0 => None,
+      handle => Some(handle),
+    }
+  }
+
   pub fn set_z_order(&self, z_order: &ZOrder) -> anyhow::Result<()> {
     let z_order = match z_order {
       ZOrder::TopMost => HWND_TOPMOST,
@@ -0,0 +1,264 @@
+use std::sync::{Mutex, OnceLock};
+
+use tracing::warn;
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::Gdi::{
+      BeginPaint, DeleteObject, DrawTextW, EndPaint, GetDC,
+      InvalidateRect, ReleaseDC, SelectObject, SetBkMode, SetTextColor,
+      DRAW_TEXT_FORMAT, DT_CALCRECT, DT_END_ELLIPSIS, DT_LEFT,
+      DT_WORDBREAK, FW_NORMAL, FW_SEMIBOLD, HDC, PAINTSTRUCT, TRANSPARENT,
+    },
+    UI::WindowsAndMessaging::{
+      DefWindowProcW, GetClientRect, KillTimer, PostMessageW,
+      SetLayeredWindowAttributes, SetTimer, SetWindowPos, ShowWindow,
+      SystemParametersInfoW, HWND_TOPMOST, LWA_ALPHA, SPI_GETWORKAREA,
+      SWP_NOACTIVATE, SWP_SHOWWINDOW, SW_HIDE,
+      SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, WM_APP, WM_PAINT, WM_TIMER,
+      WNDCLASSW, WS_POPUP,
+    },
+  },
+};
+
+use crate::overlay::{
+  create_font, create_overlay_window, fill_rect, rgb,
+  spawn_overlay_thread, CLICK_THROUGH_EX_STYLE,
+};
+
+/// Custom message for showing the pending notification.
+const WM_SHOW_NOTIFICATION: u32 = WM_APP + 1;
+
+/// ID of the timer for hiding the notification.
+const HIDE_TIMER_ID: usize = 1;
+
+/// Duration in milliseconds that a notification is shown for.
+const NOTIFICATION_DURATION_MS: u32 = 3000;
+
+/// Width of the notification window in pixels.
+const NOTIFICATION_WIDTH: i32 = 360;
+
+/// Padding around the notification text in pixels.
+const NOTIFICATION_PADDING: i32 = 12;
+
+/// Handle to the notification window. Created on first use.
+static NOTIFICATION_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// Notification that is currently shown (or about to be shown).
+///
+/// For use with window procedure.
+static NOTIFICATION: Mutex<Option<Notification>> = Mutex::new(None);
+
+/// Severity of a notification, which determines its accent color.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotificationLevel {
+  Info,
+  Warning,
+  Error,
+}
+
+#[derive(Clone, Debug)]
+struct Notification {
+  title: String,
+  message: String,
+  level: NotificationLevel,
+}
+
+/// Shows a non-blocking notification in the bottom-right corner of the
+/// primary monitor. Replaces any notification that is currently shown.
+pub(crate) fn show_notification(
+  title: &str,
+  message: &str,
+  level: NotificationLevel,
+) -> anyhow::Result<()> {
+  let handle = *NOTIFICATION_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Notification window", create_notification_window)
+  })?;
+
+  *NOTIFICATION.lock().unwrap() = Some(Notification {
+    title: title.to_string(),
+    message: message.to_string(),
+    level,
+  });
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_SHOW_NOTIFICATION,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_notification_window() -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("NotificationWindow"),
+    lpfnWndProc: Some(notification_window_proc),
+    ..Default::default()
+  };
+
+  let handle =
+    create_overlay_window(&wnd_class, CLICK_THROUGH_EX_STYLE, WS_POPUP)?;
+
+  unsafe {
+    SetLayeredWindowAttributes(handle, COLORREF(0), 235, LWA_ALPHA)
+  }?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the notification window.
+extern "system" fn notification_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_SHOW_NOTIFICATION => {
+      if let Err(err) = reposition_notification_window(handle) {
+        warn!("Failed to show notification: {}", err);
+      }
+
+      LRESULT(0)
+    }
+    WM_TIMER if wparam.0 == HIDE_TIMER_ID => {
+      unsafe {
+        let _ = KillTimer(handle, HIDE_TIMER_ID);
+        ShowWindow(handle, SW_HIDE);
+      };
+
+      LRESULT(0)
+    }
+    WM_PAINT => {
+      paint_notification_window(handle);
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+/// Sizes the window to fit the current notification, positions it above
+/// the bottom-right corner of the work area, and (re)starts the timer for
+/// hiding it.
+fn reposition_notification_window(handle: HWND) -> anyhow::Result<()> {
+  let Some(notification) = NOTIFICATION.lock().unwrap().clone() else {
+    return Ok(());
+  };
+
+  let mut work_area = RECT::default();
+  unsafe {
+    SystemParametersInfoW(
+      SPI_GETWORKAREA,
+      0,
+      Some(std::ptr::from_mut(&mut work_area).cast()),
+      SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+    )
+  }?;
+
+  let hdc = unsafe { GetDC(handle) };
+  let text_height = layout_text(hdc, &notification, false);
+  unsafe { ReleaseDC(handle, hdc) };
+
+  let height = text_height + NOTIFICATION_PADDING * 2;
+
+  unsafe {
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      work_area.right - NOTIFICATION_WIDTH - NOTIFICATION_PADDING,
+      work_area.bottom - height - NOTIFICATION_PADDING,
+      NOTIFICATION_WIDTH,
+      height,
+      SWP_NOACTIVATE | SWP_SHOWWINDOW,
+    )?;
+
+    InvalidateRect(handle, None, true);
+    SetTimer(handle, HIDE_TIMER_ID, NOTIFICATION_DURATION_MS, None);
+  };
+
+  Ok(())
+}
+
+fn paint_notification_window(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  let hdc = unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  if let Some(notification) = NOTIFICATION.lock().unwrap().as_ref() {
+    let accent_color = match notification.level {
+      NotificationLevel::Info => rgb(0x42, 0x8b, 0xf5),
+      NotificationLevel::Warning => rgb(0xf5, 0xb0, 0x42),
+      NotificationLevel::Error => rgb(0xe0, 0x4b, 0x4b),
+    };
+
+    let mut bounds = RECT::default();
+    let _ = unsafe { GetClientRect(handle, &raw mut bounds) };
+
+    let accent_bounds = RECT { right: 4, ..bounds };
+
+    fill_rect(hdc, &bounds, rgb(0x20, 0x20, 0x20));
+    fill_rect(hdc, &accent_bounds, accent_color);
+
+    layout_text(hdc, notification, true);
+  }
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
+
+/// Measures or draws the title and message of a notification.
+///
+/// Returns the total height of the text in pixels.
+fn layout_text(
+  hdc: HDC,
+  notification: &Notification,
+  should_draw: bool,
+) -> i32 {
+  let calc_flag = if should_draw {
+    DRAW_TEXT_FORMAT::default()
+  } else {
+    DT_CALCRECT
+  };
+
+  let mut height = 0;
+
+  for (text, weight) in [
+    (&notification.title, FW_SEMIBOLD),
+    (&notification.message, FW_NORMAL),
+  ] {
+    if text.is_empty() {
+      continue;
+    }
+
+    let mut rect = RECT {
+      left: NOTIFICATION_PADDING,
+      top: NOTIFICATION_PADDING + height,
+      right: NOTIFICATION_WIDTH - NOTIFICATION_PADDING,
+      bottom: NOTIFICATION_PADDING + height + 1000,
+    };
+
+    let mut text_wide = text.encode_utf16().collect::<Vec<_>>();
+
+    unsafe {
+      let font = create_font(18, weight.0);
+      let prev_font = SelectObject(hdc, font);
+      SetBkMode(hdc, TRANSPARENT);
+      SetTextColor(hdc, rgb(0xf0, 0xf0, 0xf0));
+
+      height += DrawTextW(
+        hdc,
+        &mut text_wide,
+        &raw mut rect,
+        DT_LEFT | DT_WORDBREAK | DT_END_ELLIPSIS | calc_flag,
+      );
+
+      SelectObject(hdc, prev_font);
+      DeleteObject(font);
+    }
+  }
+
+  height
+}
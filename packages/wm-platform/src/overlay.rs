@@ -0,0 +1,177 @@
+use std::{sync::mpsc, thread};
+
+use anyhow::{bail, Context};
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{COLORREF, HWND, RECT},
+    Graphics::Gdi::{
+      CreateFontW, CreateSolidBrush, DeleteObject, DrawTextW, FillRect,
+      SelectObject, SetBkMode, SetTextColor, DRAW_TEXT_FORMAT,
+      DT_END_ELLIPSIS, DT_SINGLELINE, DT_VCENTER, HDC, HFONT, TRANSPARENT,
+    },
+    UI::WindowsAndMessaging::{
+      CreateWindowExW, RegisterClassW, WINDOW_EX_STYLE, WINDOW_STYLE,
+      WNDCLASSW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
+      WS_EX_TOPMOST, WS_EX_TRANSPARENT,
+    },
+  },
+};
+
+use crate::Platform;
+
+/// Extended window styles for overlays that are shown above all other
+/// windows without taking focus. Clicks pass through to the windows
+/// underneath.
+pub(crate) const CLICK_THROUGH_EX_STYLE: WINDOW_EX_STYLE = WINDOW_EX_STYLE(
+  WS_EX_LAYERED.0
+    | WS_EX_NOACTIVATE.0
+    | WS_EX_TOPMOST.0
+    | WS_EX_TRANSPARENT.0,
+);
+
+/// Spawns a thread that creates a window via `create_window`, and then
+/// runs the message loop of the thread until the window is destroyed
+/// (i.e. usually for the remainder of the process).
+///
+/// Returns a handle to the created window.
+pub(crate) fn spawn_overlay_thread(
+  name: &str,
+  create_window: impl FnOnce() -> anyhow::Result<isize> + Send + 'static,
+) -> anyhow::Result<isize> {
+  let (handle_tx, handle_rx) = mpsc::channel();
+
+  thread::spawn(move || {
+    let handle = create_window();
+    let is_created = handle.is_ok();
+    let _ = handle_tx.send(handle);
+
+    if is_created {
+      Platform::run_message_loop();
+    }
+  });
+
+  handle_rx
+    .recv()
+    .with_context(|| format!("{name} thread exited unexpectedly."))?
+}
+
+/// Registers the window class and creates a hidden window of it, which
+/// is initially positioned at 0,0 with zero size.
+///
+/// The window is always created as a tool window, which excludes it
+/// from management by the WM and hides it from the taskbar.
+pub(crate) fn create_overlay_window(
+  wnd_class: &WNDCLASSW,
+  ex_style: WINDOW_EX_STYLE,
+  style: WINDOW_STYLE,
+) -> anyhow::Result<HWND> {
+  unsafe { RegisterClassW(wnd_class) };
+
+  let handle = unsafe {
+    CreateWindowExW(
+      ex_style | WS_EX_TOOLWINDOW,
+      wnd_class.lpszClassName,
+      wnd_class.lpszClassName,
+      style,
+      0,
+      0,
+      0,
+      0,
+      None,
+      None,
+      wnd_class.hInstance,
+      None,
+    )
+  };
+
+  if handle.0 == 0 {
+    let class_name =
+      unsafe { wnd_class.lpszClassName.to_string() }.unwrap_or_default();
+
+    bail!("Creation of {class_name} window failed.");
+  }
+
+  Ok(handle)
+}
+
+/// Font and color of text drawn via `draw_text`.
+pub(crate) struct TextStyle {
+  /// Height of the font in pixels.
+  pub height: i32,
+
+  /// Weight of the font (e.g. `FW_NORMAL`).
+  pub weight: u32,
+
+  pub color: COLORREF,
+
+  /// Horizontal alignment of the text (e.g. `DT_LEFT`).
+  pub alignment: DRAW_TEXT_FORMAT,
+}
+
+/// Draws a single line of text that is vertically centered within the
+/// bounds, and truncated with an ellipsis if it doesn't fit.
+pub(crate) fn draw_text(
+  hdc: HDC,
+  text: &str,
+  mut bounds: RECT,
+  style: &TextStyle,
+) {
+  let mut text_wide = text.encode_utf16().collect::<Vec<_>>();
+
+  unsafe {
+    let font = create_font(style.height, style.weight);
+    let prev_font = SelectObject(hdc, font);
+    SetBkMode(hdc, TRANSPARENT);
+    SetTextColor(hdc, style.color);
+
+    DrawTextW(
+      hdc,
+      &mut text_wide,
+      &raw mut bounds,
+      style.alignment | DT_SINGLELINE | DT_VCENTER | DT_END_ELLIPSIS,
+    );
+
+    SelectObject(hdc, prev_font);
+    DeleteObject(font);
+  }
+}
+
+/// Creates a Segoe UI font of the given height and weight. The font has
+/// to be deleted via `DeleteObject` once it's no longer needed.
+pub(crate) fn create_font(height: i32, weight: u32) -> HFONT {
+  unsafe {
+    CreateFontW(
+      height,
+      0,
+      0,
+      0,
+      i32::try_from(weight).unwrap_or(400),
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      w!("Segoe UI"),
+    )
+  }
+}
+
+/// Fills the rect with a solid color.
+pub(crate) fn fill_rect(hdc: HDC, rect: &RECT, color: COLORREF) {
+  unsafe {
+    let brush = CreateSolidBrush(color);
+    FillRect(hdc, rect, brush);
+    DeleteObject(brush);
+  }
+}
+
+/// Creates a `COLORREF` from RGB components.
+pub(crate) fn rgb(red: u8, green: u8, blue: u8) -> COLORREF {
+  COLORREF(
+    u32::from(red) | (u32::from(green) << 8) | (u32::from(blue) << 16),
+  )
+}
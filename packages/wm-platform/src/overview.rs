@@ -0,0 +1,695 @@
+use std::sync::{
+  atomic::{AtomicBool, Ordering},
+  Mutex, OnceLock,
+};
+
+use anyhow::Context;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::{
+      Dwm::{
+        DwmRegisterThumbnail, DwmUnregisterThumbnail,
+        DwmUpdateThumbnailProperties, DWM_THUMBNAIL_PROPERTIES,
+        DWM_TNP_RECTDESTINATION, DWM_TNP_VISIBLE,
+      },
+      Gdi::{
+        BeginPaint, EndPaint, InvalidateRect, DT_CENTER, FW_NORMAL,
+        PAINTSTRUCT,
+      },
+    },
+    UI::{
+      Input::KeyboardAndMouse::{
+        GetKeyState, ReleaseCapture, SetCapture, VK_DOWN, VK_ESCAPE,
+        VK_LEFT, VK_RETURN, VK_RIGHT, VK_SHIFT, VK_TAB, VK_UP,
+      },
+      WindowsAndMessaging::{
+        DefWindowProcW, PostMessageW, SetWindowPos, ShowWindow,
+        HWND_TOPMOST, SWP_SHOWWINDOW, SW_HIDE, WA_INACTIVE, WM_ACTIVATE,
+        WM_APP, WM_KEYDOWN, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MOUSEMOVE,
+        WM_PAINT, WM_SYSKEYDOWN, WNDCLASSW, WS_EX_TOPMOST, WS_POPUP,
+      },
+    },
+  },
+};
+use wm_common::Rect;
+
+use crate::{
+  overlay::{
+    create_overlay_window, draw_text, fill_rect, rgb,
+    spawn_overlay_thread, TextStyle,
+  },
+  NativeWindow,
+};
+
+/// Custom message for showing the overview.
+const WM_SHOW_OVERVIEW: u32 = WM_APP + 1;
+
+/// Custom message for closing the overview when it's invoked while
+/// already shown.
+const WM_CLOSE_OVERVIEW: u32 = WM_APP + 2;
+
+/// Height of the workspace name below each preview in pixels.
+const TITLE_HEIGHT: i32 = 28;
+
+/// Padding around and between previews in pixels.
+const OVERVIEW_PADDING: i32 = 32;
+
+/// Distance in pixels the cursor has to move while pressed before a
+/// window thumbnail starts being dragged.
+const DRAG_THRESHOLD: i32 = 4;
+
+/// Handle to the overview window. Created on first use.
+static OVERVIEW_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// Whether the overview is currently shown.
+static IS_OVERVIEW_SHOWN: AtomicBool = AtomicBool::new(false);
+
+/// State of the overview while it's shown.
+///
+/// For use with window procedure.
+static OVERVIEW: Mutex<Option<OverviewState>> = Mutex::new(None);
+
+/// Workspace that is previewed in the overview.
+#[derive(Clone, Debug)]
+pub struct OverviewWorkspace {
+  pub title: String,
+
+  /// Windows of the workspace, ordered from topmost to bottommost.
+  pub windows: Vec<OverviewWindow>,
+
+  /// Command that is sent when the workspace is selected.
+  pub focus_command: String,
+
+  /// Command that is sent when a window is dropped onto the workspace.
+  /// It's run with the dropped window as its subject.
+  pub move_command: String,
+}
+
+/// Window that is shown within a workspace preview.
+#[derive(Clone, Debug)]
+pub struct OverviewWindow {
+  pub window: NativeWindow,
+
+  /// Position of the window on the monitor, as if its workspace were
+  /// displayed.
+  pub rect: Rect,
+
+  /// ID of the window's container, used as the subject of the move
+  /// command when the window is dropped onto another workspace.
+  pub container_id: String,
+}
+
+struct OverviewState {
+  workspaces: Vec<OverviewWorkspace>,
+
+  /// Rect of the monitor that the workspaces are previewed for.
+  monitor_rect: Rect,
+
+  /// Index of the highlighted workspace.
+  selected: usize,
+
+  /// Bounds of each workspace preview, in the same order as
+  /// `workspaces`.
+  preview_rects: Vec<RECT>,
+
+  /// Window thumbnails across all previews, ordered from bottommost to
+  /// topmost.
+  thumbnails: Vec<WindowThumbnail>,
+
+  /// Window thumbnail that is currently pressed or being dragged.
+  drag: Option<DragState>,
+
+  /// Sender for the command of the selected workspace or dropped window.
+  selection_tx: UnboundedSender<String>,
+}
+
+struct WindowThumbnail {
+  workspace_index: usize,
+  window_index: usize,
+
+  /// Current bounds of the thumbnail within the overview.
+  rect: RECT,
+
+  /// Registered DWM thumbnail. Is `None` if registration failed, in
+  /// which case only a placeholder is drawn.
+  thumbnail: Option<isize>,
+}
+
+struct DragState {
+  /// Index into `OverviewState::thumbnails`.
+  thumbnail_index: usize,
+
+  /// Cursor position when the thumbnail was pressed.
+  start_point: (i32, i32),
+
+  /// Bounds of the thumbnail when it was pressed.
+  start_rect: RECT,
+
+  /// Whether the cursor has moved past `DRAG_THRESHOLD`.
+  is_dragging: bool,
+}
+
+/// Shows the overview with previews of the given workspaces on the
+/// monitor with the given rect. If the overview is already shown, it's
+/// closed instead.
+///
+/// Clicking a preview or confirming it with enter sends its focus
+/// command. Dropping a window onto another preview sends that
+/// workspace's move command, prefixed with `command --id <ID>` of the
+/// dropped window.
+pub(crate) fn show_overview(
+  monitor_rect: Rect,
+  workspaces: Vec<OverviewWorkspace>,
+  selected: usize,
+  selection_tx: UnboundedSender<String>,
+) -> anyhow::Result<()> {
+  let handle = *OVERVIEW_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Overview", create_overview_window)
+  })?;
+
+  if IS_OVERVIEW_SHOWN.load(Ordering::Relaxed) {
+    unsafe {
+      PostMessageW(
+        HWND(handle),
+        WM_CLOSE_OVERVIEW,
+        WPARAM::default(),
+        LPARAM::default(),
+      )
+    }?;
+
+    return Ok(());
+  }
+
+  if workspaces.is_empty() {
+    return Ok(());
+  }
+
+  *OVERVIEW.lock().unwrap() = Some(OverviewState {
+    selected: selected.min(workspaces.len() - 1),
+    workspaces,
+    monitor_rect,
+    preview_rects: Vec::new(),
+    thumbnails: Vec::new(),
+    drag: None,
+    selection_tx,
+  });
+
+  IS_OVERVIEW_SHOWN.store(true, Ordering::Relaxed);
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_SHOW_OVERVIEW,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_overview_window() -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("OverviewWindow"),
+    lpfnWndProc: Some(overview_window_proc),
+    ..Default::default()
+  };
+
+  // Window isn't layered, since DWM thumbnails aren't drawn on layered
+  // windows.
+  let handle = create_overlay_window(&wnd_class, WS_EX_TOPMOST, WS_POPUP)?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the overview window.
+extern "system" fn overview_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_SHOW_OVERVIEW => {
+      if let Err(err) = layout_overview_window(handle) {
+        warn!("Failed to show overview: {}", err);
+        close_overview(handle, None);
+      }
+
+      LRESULT(0)
+    }
+    WM_CLOSE_OVERVIEW => {
+      close_overview(handle, None);
+      LRESULT(0)
+    }
+    WM_ACTIVATE if wparam.0 & 0xffff == WA_INACTIVE as usize => {
+      close_overview(handle, None);
+      LRESULT(0)
+    }
+    WM_KEYDOWN | WM_SYSKEYDOWN => {
+      #[allow(clippy::cast_possible_truncation)]
+      let key = wparam.0 as u16;
+
+      if key == VK_TAB.0 {
+        let is_shift_down =
+          unsafe { GetKeyState(i32::from(VK_SHIFT.0)) } < 0;
+        move_selection(handle, if is_shift_down { -1 } else { 1 }, 0);
+      } else if key == VK_RIGHT.0 {
+        move_selection(handle, 1, 0);
+      } else if key == VK_LEFT.0 {
+        move_selection(handle, -1, 0);
+      } else if key == VK_DOWN.0 {
+        move_selection(handle, 0, 1);
+      } else if key == VK_UP.0 {
+        move_selection(handle, 0, -1);
+      } else if key == VK_RETURN.0 {
+        let command =
+          OVERVIEW.lock().unwrap().as_ref().and_then(|state| {
+            state
+              .workspaces
+              .get(state.selected)
+              .map(|workspace| workspace.focus_command.clone())
+          });
+
+        close_overview(handle, command);
+      } else if key == VK_ESCAPE.0 {
+        close_overview(handle, None);
+      }
+
+      LRESULT(0)
+    }
+    WM_LBUTTONDOWN => {
+      on_mouse_down(handle, cursor_point(lparam));
+      LRESULT(0)
+    }
+    WM_MOUSEMOVE => {
+      on_mouse_move(handle, cursor_point(lparam));
+      LRESULT(0)
+    }
+    WM_LBUTTONUP => {
+      on_mouse_up(handle, cursor_point(lparam));
+      LRESULT(0)
+    }
+    WM_PAINT => {
+      paint_overview_window(handle);
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+/// Gets the cursor position in client coordinates from the `LPARAM` of
+/// a mouse message.
+#[allow(clippy::cast_possible_truncation)]
+fn cursor_point(lparam: LPARAM) -> (i32, i32) {
+  (
+    i32::from((lparam.0 & 0xffff) as i16),
+    i32::from(((lparam.0 >> 16) & 0xffff) as i16),
+  )
+}
+
+fn contains_point(rect: &RECT, (x, y): (i32, i32)) -> bool {
+  x >= rect.left && x < rect.right && y >= rect.top && y < rect.bottom
+}
+
+/// Moves the selection by the given number of columns and rows in the
+/// grid of previews. Moving by columns wraps across rows.
+fn move_selection(handle: HWND, column_delta: isize, row_delta: isize) {
+  if let Some(state) = OVERVIEW.lock().unwrap().as_mut() {
+    let count = state.workspaces.len().cast_signed();
+    let columns = grid_columns(state.workspaces.len()).cast_signed();
+    let selected = state.selected.cast_signed();
+
+    let next = if row_delta == 0 {
+      (selected + column_delta).rem_euclid(count)
+    } else {
+      let next = selected + row_delta * columns;
+
+      if (0..count).contains(&next) {
+        next
+      } else {
+        selected
+      }
+    };
+
+    state.selected = next.cast_unsigned();
+  }
+
+  unsafe { InvalidateRect(handle, None, true) };
+}
+
+fn on_mouse_down(handle: HWND, point: (i32, i32)) {
+  let mut overview = OVERVIEW.lock().unwrap();
+  let Some(state) = overview.as_mut() else {
+    return;
+  };
+
+  // Thumbnails are ordered bottommost first, so search from the end.
+  state.drag = state
+    .thumbnails
+    .iter()
+    .rposition(|thumbnail| contains_point(&thumbnail.rect, point))
+    .map(|thumbnail_index| DragState {
+      thumbnail_index,
+      start_point: point,
+      start_rect: state.thumbnails[thumbnail_index].rect,
+      is_dragging: false,
+    });
+
+  unsafe { SetCapture(handle) };
+}
+
+fn on_mouse_move(handle: HWND, (x, y): (i32, i32)) {
+  let mut overview = OVERVIEW.lock().unwrap();
+  let Some(state) = overview.as_mut() else {
+    return;
+  };
+
+  let Some(drag) = state.drag.as_mut() else {
+    return;
+  };
+
+  let delta_x = x - drag.start_point.0;
+  let delta_y = y - drag.start_point.1;
+
+  if !drag.is_dragging
+    && delta_x.abs() < DRAG_THRESHOLD
+    && delta_y.abs() < DRAG_THRESHOLD
+  {
+    return;
+  }
+
+  drag.is_dragging = true;
+
+  let thumbnail = &mut state.thumbnails[drag.thumbnail_index];
+  thumbnail.rect = RECT {
+    left: drag.start_rect.left + delta_x,
+    top: drag.start_rect.top + delta_y,
+    right: drag.start_rect.right + delta_x,
+    bottom: drag.start_rect.bottom + delta_y,
+  };
+
+  if let Some(dwm_thumbnail) = thumbnail.thumbnail {
+    if let Err(err) = update_thumbnail(dwm_thumbnail, &thumbnail.rect) {
+      warn!("Failed to move thumbnail: {}", err);
+    }
+  }
+
+  unsafe { InvalidateRect(handle, None, true) };
+}
+
+/// Either moves the dragged window to the workspace it's dropped on, or
+/// switches to the clicked workspace.
+fn on_mouse_up(handle: HWND, point: (i32, i32)) {
+  let _ = unsafe { ReleaseCapture() };
+
+  let command = {
+    let mut overview = OVERVIEW.lock().unwrap();
+    let Some(state) = overview.as_mut() else {
+      return;
+    };
+
+    let target_index = state
+      .preview_rects
+      .iter()
+      .position(|rect| contains_point(rect, point));
+
+    match (state.drag.take(), target_index) {
+      (Some(drag), target_index) if drag.is_dragging => {
+        let thumbnail = &state.thumbnails[drag.thumbnail_index];
+
+        match target_index {
+          Some(target_index)
+            if target_index != thumbnail.workspace_index =>
+          {
+            let window = &state.workspaces[thumbnail.workspace_index]
+              .windows[thumbnail.window_index];
+
+            Some(format!(
+              "command --id {} {}",
+              window.container_id,
+              state.workspaces[target_index].move_command
+            ))
+          }
+          // Snap the thumbnail back if it's not dropped onto another
+          // workspace.
+          _ => {
+            let thumbnail = &mut state.thumbnails[drag.thumbnail_index];
+            thumbnail.rect = drag.start_rect;
+
+            if let Some(dwm_thumbnail) = thumbnail.thumbnail {
+              let _ = update_thumbnail(dwm_thumbnail, &drag.start_rect);
+            }
+
+            unsafe { InvalidateRect(handle, None, true) };
+            return;
+          }
+        }
+      }
+      (_, Some(target_index)) => {
+        Some(state.workspaces[target_index].focus_command.clone())
+      }
+      // Close on clicks outside of the previews.
+      (_, None) => None,
+    }
+  };
+
+  close_overview(handle, command);
+}
+
+/// Hides the overview and unregisters its thumbnails. Sends the given
+/// command if there is one.
+fn close_overview(handle: HWND, command: Option<String>) {
+  if let Some(state) = OVERVIEW.lock().unwrap().take() {
+    for thumbnail in state.thumbnails {
+      if let Some(dwm_thumbnail) = thumbnail.thumbnail {
+        let _ = unsafe { DwmUnregisterThumbnail(dwm_thumbnail) };
+      }
+    }
+
+    if let Some(command) = command {
+      let _ = state.selection_tx.send(command);
+    }
+  }
+
+  IS_OVERVIEW_SHOWN.store(false, Ordering::Relaxed);
+  unsafe { ShowWindow(handle, SW_HIDE) };
+}
+
+/// Number of columns in the grid of previews.
+fn grid_columns(count: usize) -> usize {
+  count.isqrt() + usize::from(count.isqrt().pow(2) < count)
+}
+
+/// Arranges the previews in a grid covering the monitor, registers a
+/// thumbnail for each window, and brings the overview to the foreground.
+fn layout_overview_window(handle: HWND) -> anyhow::Result<()> {
+  let mut overview = OVERVIEW.lock().unwrap();
+  let state = overview.as_mut().context("Overview not shown.")?;
+
+  let monitor_rect = state.monitor_rect.clone();
+  let columns = grid_columns(state.workspaces.len());
+  let rows = state.workspaces.len().div_ceil(columns);
+
+  let columns = i32::try_from(columns)?;
+  let rows = i32::try_from(rows)?;
+
+  // Scale previews to fit the grid while preserving the monitor's aspect
+  // ratio.
+  let available_width =
+    (monitor_rect.width() - OVERVIEW_PADDING * (columns + 1)) / columns;
+
+  let available_height = (monitor_rect.height()
+    - OVERVIEW_PADDING * (rows + 1)
+    - TITLE_HEIGHT * rows)
+    / rows;
+
+  let scale = f64::min(
+    f64::from(available_width) / f64::from(monitor_rect.width().max(1)),
+    f64::from(available_height) / f64::from(monitor_rect.height().max(1)),
+  )
+  .max(0.0);
+
+  #[allow(clippy::cast_possible_truncation)]
+  let scaled = |length: i32| (f64::from(length) * scale).round() as i32;
+
+  let preview_width = scaled(monitor_rect.width());
+  let preview_height = scaled(monitor_rect.height());
+
+  let grid_width =
+    columns * (preview_width + OVERVIEW_PADDING) - OVERVIEW_PADDING;
+  let grid_height = rows
+    * (preview_height + TITLE_HEIGHT + OVERVIEW_PADDING)
+    - OVERVIEW_PADDING;
+
+  let grid_left = (monitor_rect.width() - grid_width) / 2;
+  let grid_top = (monitor_rect.height() - grid_height) / 2;
+
+  state.preview_rects = (0..i32::try_from(state.workspaces.len())?)
+    .map(|index| {
+      let left =
+        grid_left + (index % columns) * (preview_width + OVERVIEW_PADDING);
+      let top = grid_top
+        + (index / columns)
+          * (preview_height + TITLE_HEIGHT + OVERVIEW_PADDING);
+
+      RECT {
+        left,
+        top,
+        right: left + preview_width,
+        bottom: top + preview_height,
+      }
+    })
+    .collect();
+
+  let mut thumbnails = Vec::new();
+
+  for (workspace_index, workspace) in state.workspaces.iter().enumerate() {
+    let preview_rect = state.preview_rects[workspace_index];
+
+    // Register bottommost windows first, so that topmost windows are
+    // drawn above them.
+    for (window_index, window) in
+      workspace.windows.iter().enumerate().rev()
+    {
+      let rect = RECT {
+        left: preview_rect.left
+          + scaled(window.rect.left - monitor_rect.left),
+        top: preview_rect.top + scaled(window.rect.top - monitor_rect.top),
+        right: preview_rect.left
+          + scaled(window.rect.right - monitor_rect.left),
+        bottom: preview_rect.top
+          + scaled(window.rect.bottom - monitor_rect.top),
+      };
+
+      let thumbnail = register_thumbnail(handle, &window.window, &rect)
+        .inspect_err(|err| warn!("Failed to register thumbnail: {}", err))
+        .ok();
+
+      thumbnails.push(WindowThumbnail {
+        workspace_index,
+        window_index,
+        rect,
+        thumbnail,
+      });
+    }
+  }
+
+  state.thumbnails = thumbnails;
+  drop(overview);
+
+  unsafe {
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      monitor_rect.left,
+      monitor_rect.top,
+      monitor_rect.width(),
+      monitor_rect.height(),
+      SWP_SHOWWINDOW,
+    )?;
+
+    InvalidateRect(handle, None, true);
+  };
+
+  NativeWindow::new(handle.0).set_foreground()
+}
+
+/// Registers a DWM thumbnail of the window, stretched to fill the given
+/// rect.
+fn register_thumbnail(
+  handle: HWND,
+  window: &NativeWindow,
+  rect: &RECT,
+) -> anyhow::Result<isize> {
+  let thumbnail =
+    unsafe { DwmRegisterThumbnail(handle, HWND(window.handle)) }?;
+
+  update_thumbnail(thumbnail, rect)?;
+  Ok(thumbnail)
+}
+
+fn update_thumbnail(thumbnail: isize, rect: &RECT) -> anyhow::Result<()> {
+  let properties = DWM_THUMBNAIL_PROPERTIES {
+    dwFlags: DWM_TNP_RECTDESTINATION | DWM_TNP_VISIBLE,
+    rcDestination: *rect,
+    fVisible: true.into(),
+    ..Default::default()
+  };
+
+  unsafe {
+    DwmUpdateThumbnailProperties(thumbnail, &raw const properties)
+  }?;
+
+  Ok(())
+}
+
+/// Draws the workspace previews and a placeholder with the title of each
+/// window. The placeholders are covered by the window thumbnails, but
+/// remain visible for windows that DWM can't render a thumbnail of (e.g.
+/// windows on hidden workspaces).
+fn paint_overview_window(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  let hdc = unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  if let Some(state) = OVERVIEW.lock().unwrap().as_ref() {
+    fill_rect(hdc, &paint_struct.rcPaint, rgb(0x18, 0x18, 0x18));
+
+    let text_style = TextStyle {
+      height: 17,
+      weight: FW_NORMAL.0,
+      color: rgb(0xf0, 0xf0, 0xf0),
+      alignment: DT_CENTER,
+    };
+
+    for (index, (workspace, preview_rect)) in state
+      .workspaces
+      .iter()
+      .zip(&state.preview_rects)
+      .enumerate()
+    {
+      if index == state.selected {
+        let highlight_rect = RECT {
+          left: preview_rect.left - 4,
+          top: preview_rect.top - 4,
+          right: preview_rect.right + 4,
+          bottom: preview_rect.bottom + 4,
+        };
+
+        fill_rect(hdc, &highlight_rect, rgb(0x42, 0x8b, 0xf5));
+      }
+
+      fill_rect(hdc, preview_rect, rgb(0x30, 0x30, 0x30));
+
+      draw_text(
+        hdc,
+        &workspace.title,
+        RECT {
+          top: preview_rect.bottom + 4,
+          bottom: preview_rect.bottom + TITLE_HEIGHT,
+          ..*preview_rect
+        },
+        &text_style,
+      );
+    }
+
+    for thumbnail in &state.thumbnails {
+      let window = &state.workspaces[thumbnail.workspace_index].windows
+        [thumbnail.window_index];
+
+      fill_rect(hdc, &thumbnail.rect, rgb(0x50, 0x50, 0x50));
+      draw_text(
+        hdc,
+        &window.window.title().unwrap_or_default(),
+        thumbnail.rect,
+        &text_style,
+      );
+    }
+  }
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
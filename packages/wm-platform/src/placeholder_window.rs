@@ -0,0 +1,141 @@
+use std::sync::Mutex;
+
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::Gdi::{
+      BeginPaint, CreateSolidBrush, DeleteObject, EndPaint, FrameRect,
+      InvalidateRect, DT_CENTER, FW_NORMAL, PAINTSTRUCT,
+    },
+    UI::WindowsAndMessaging::{
+      DefWindowProcW, GetClientRect, PostQuitMessage,
+      SetLayeredWindowAttributes, LWA_ALPHA, WM_DESTROY, WM_PAINT,
+      WM_SIZE, WNDCLASSW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_POPUP,
+      WS_VISIBLE,
+    },
+  },
+};
+
+use crate::{
+  overlay::{
+    create_overlay_window, draw_text, fill_rect, rgb,
+    spawn_overlay_thread, TextStyle,
+  },
+  NativeWindow,
+};
+
+/// Labels of the placeholder windows that currently exist, keyed by
+/// window handle.
+///
+/// For use with window procedure.
+static PLACEHOLDER_LABELS: Mutex<Vec<(isize, String)>> =
+  Mutex::new(Vec::new());
+
+/// Creates a window that draws an empty frame with the given label, for
+/// marking where an expected window will be placed.
+///
+/// Each placeholder runs its message loop on its own thread, which exits
+/// once the window is closed.
+pub(crate) fn create_placeholder_window(
+  label: &str,
+) -> anyhow::Result<NativeWindow> {
+  let label = label.to_string();
+  let handle = spawn_overlay_thread("Placeholder window", move || {
+    create_window(label)
+  })?;
+
+  Ok(NativeWindow::new(handle))
+}
+
+fn create_window(label: String) -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("PlaceholderWindow"),
+    lpfnWndProc: Some(placeholder_window_proc),
+    ..Default::default()
+  };
+
+  // Window is excluded from management, and is instead added to the tree
+  // as a stand-in by the WM.
+  let handle = create_overlay_window(
+    &wnd_class,
+    WS_EX_LAYERED | WS_EX_NOACTIVATE,
+    WS_POPUP | WS_VISIBLE,
+  )?;
+
+  unsafe {
+    SetLayeredWindowAttributes(handle, COLORREF(0), 160, LWA_ALPHA)
+  }?;
+
+  PLACEHOLDER_LABELS.lock().unwrap().push((handle.0, label));
+
+  Ok(handle.0)
+}
+
+/// Window procedure for placeholder windows.
+extern "system" fn placeholder_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_PAINT => {
+      paint_placeholder_window(handle);
+      LRESULT(0)
+    }
+    // Repaint the whole window, since the label is centered.
+    WM_SIZE => {
+      unsafe { InvalidateRect(handle, None, true) };
+      LRESULT(0)
+    }
+    WM_DESTROY => {
+      PLACEHOLDER_LABELS
+        .lock()
+        .unwrap()
+        .retain(|(label_handle, _)| *label_handle != handle.0);
+
+      unsafe { PostQuitMessage(0) };
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+fn paint_placeholder_window(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  let hdc = unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  let label = PLACEHOLDER_LABELS
+    .lock()
+    .unwrap()
+    .iter()
+    .find(|(label_handle, _)| *label_handle == handle.0)
+    .map(|(_, label)| label.clone())
+    .unwrap_or_default();
+
+  let mut bounds = RECT::default();
+  let _ = unsafe { GetClientRect(handle, &raw mut bounds) };
+
+  fill_rect(hdc, &bounds, rgb(0x18, 0x18, 0x18));
+
+  unsafe {
+    let frame_brush = CreateSolidBrush(rgb(0x50, 0x50, 0x50));
+    FrameRect(hdc, &raw const bounds, frame_brush);
+    DeleteObject(frame_brush);
+  }
+
+  draw_text(
+    hdc,
+    &label,
+    bounds,
+    &TextStyle {
+      height: 18,
+      weight: FW_NORMAL.0,
+      color: rgb(0x90, 0x90, 0x90),
+      alignment: DT_CENTER,
+    },
+  );
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
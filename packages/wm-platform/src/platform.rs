@@ -1,18 +1,37 @@
 use std::{
+  collections::HashSet,
+  num::NonZeroUsize,
   os::windows::io::AsRawHandle,
   path::{Path, PathBuf},
-  thread::JoinHandle,
+  thread::{self, JoinHandle},
+  time::Duration,
 };
 
 use anyhow::{bail, Context};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
 use windows::{
   core::{w, PCWSTR},
   Win32::{
-    Foundation::{HANDLE, HWND, LPARAM, POINT, WPARAM},
+    Foundation::{CloseHandle, BOOL, HANDLE, HWND, LPARAM, POINT, WPARAM},
+    Graphics::Gdi::{GetSysColor, COLOR_GRAYTEXT, COLOR_HIGHLIGHT},
+    Security::{
+      GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY,
+    },
     System::{
-      Environment::ExpandEnvironmentStringsW, Threading::GetThreadId,
+      Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW,
+        PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+      },
+      Environment::ExpandEnvironmentStringsW,
+      SystemInformation::{GetLocalTime, GetTickCount},
+      Threading::{
+        GetCurrentProcess, GetProcessId, GetThreadId, OpenProcessToken,
+      },
     },
     UI::{
+      Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW},
+      Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO},
       Shell::{
         ShellExecuteExW, SEE_MASK_NOASYNC, SEE_MASK_NOCLOSEPROCESS,
         SHELLEXECUTEINFOW,
@@ -23,20 +42,44 @@ use windows::{
         GetShellWindow, MessageBoxW, PeekMessageW, PostThreadMessageW,
         RegisterClassW, SetCursorPos, SystemParametersInfoW,
         TranslateMessage, WindowFromPoint, ANIMATIONINFO, CS_HREDRAW,
-        CS_VREDRAW, CW_USEDEFAULT, GA_ROOT, MB_ICONERROR, MB_OK,
-        MB_SYSTEMMODAL, MSG, PM_REMOVE, SPIF_SENDCHANGE,
-        SPIF_UPDATEINIFILE, SPI_GETANIMATION, SPI_SETANIMATION, SW_HIDE,
-        SW_NORMAL, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, WINDOW_EX_STYLE,
-        WM_QUIT, WNDCLASSW, WNDPROC, WS_OVERLAPPEDWINDOW,
+        CS_VREDRAW, CW_USEDEFAULT, GA_ROOT, IDYES, MB_ICONERROR,
+        MB_ICONQUESTION, MB_OK, MB_SYSTEMMODAL, MB_YESNO, MSG, PM_REMOVE,
+        SPIF_SENDCHANGE, SPIF_UPDATEINIFILE, SPI_GETANIMATION,
+        SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST, SPI_SETANIMATION,
+        SW_HIDE, SW_NORMAL, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+        WINDOW_EX_STYLE, WM_QUIT, WNDCLASSW, WNDPROC, WS_OVERLAPPEDWINDOW,
       },
     },
   },
 };
-use wm_common::{ParsedConfig, Point};
+use wm_common::{Color, FocusAssistMode, ParsedConfig, Point, Rect};
 
 use super::{
-  native_monitor, native_window, EventListener, NativeMonitor,
-  NativeWindow, SingleInstance,
+  announcer::announce,
+  audio_sessions::audio_process_ids,
+  capability_access::capability_users,
+  command_palette::show_command_palette,
+  focus_assist::{focus_assist_mode, set_focus_assist_mode},
+  focus_frame::update_focus_frame,
+  keybindings_overlay::show_keybindings_overlay,
+  leader_menu::show_leader_menu,
+  magnifier::update_zoom,
+  native_monitor, native_window,
+  notification_window::show_notification,
+  overview::show_overview,
+  placeholder_window::create_placeholder_window,
+  resize_guides::update_resize_guides,
+  send_keys::send_keys,
+  status_bar::{close_status_bars, update_status_bars},
+  window_hints::show_window_hints,
+  window_picker::pick_window,
+  window_switcher::show_window_switcher,
+  window_thumbnail::capture_window_thumbnail,
+  Capability, CapabilityUsers, CommandPaletteEntry, EventListener,
+  FocusFrame, KeybindingSection, KeyboardHook, KeyboardLayout,
+  LeaderMenuEntry, NativeMonitor, NativeWindow, NotificationLevel,
+  OverviewWorkspace, ResizeGuides, SingleInstance, StatusBarState,
+  WindowHint, WindowSwitcherEntry, WindowThumbnail, Zoom,
 };
 
 pub type WindowProcedure = WNDPROC;
@@ -111,13 +154,46 @@ impl Platform {
   /// Manageable windows are visible windows that the WM is most likely
   /// able to manage. Windows are returned in z-order (top to bottom),
   /// although the order is not guaranteed by the underlying API.
+  ///
+  /// Windows are queried in parallel, since querying each window (e.g.
+  /// its process name) is slow with many windows open. Queried info is
+  /// cached on the returned instances.
   pub fn manageable_windows() -> anyhow::Result<Vec<NativeWindow>> {
-    Ok(
-      native_window::available_windows()?
+    let windows = native_window::available_windows()?;
+
+    let thread_count =
+      thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk_size = windows.len().div_ceil(thread_count).max(1);
+
+    let manageable_windows = thread::scope(|scope| {
+      let handles = windows
+        .chunks(chunk_size)
+        .map(|chunk| {
+          scope.spawn(|| {
+            chunk
+              .iter()
+              .filter(|window| window.is_manageable().unwrap_or(false))
+              .inspect(|window| {
+                // Prefetch window state that's needed for managing the
+                // window.
+                let _ = window.is_minimized();
+                let _ = window.is_maximized();
+              })
+              .cloned()
+              .collect::<Vec<_>>()
+          })
+        })
+        .collect::<Vec<_>>();
+
+      // Join in order of the chunks to preserve the z-order.
+      handles
         .into_iter()
-        .filter(|window| window.is_manageable().unwrap_or(false))
-        .collect(),
-    )
+        .filter_map(|handle| handle.join().ok())
+        .flatten()
+        .collect()
+    });
+
+    Ok(manageable_windows)
   }
 
   /// Creates a new `EventListener` for the specified user config.
@@ -319,6 +395,57 @@ impl Platform {
     Ok(())
   }
 
+  /// Gets whether a high-contrast theme is active.
+  pub fn is_high_contrast_enabled() -> anyhow::Result<bool> {
+    let mut high_contrast = HIGHCONTRASTW {
+      #[allow(clippy::cast_possible_truncation)]
+      cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+      ..Default::default()
+    };
+
+    unsafe {
+      SystemParametersInfoW(
+        SPI_GETHIGHCONTRAST,
+        high_contrast.cbSize,
+        Some(std::ptr::from_mut(&mut high_contrast).cast()),
+        SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+      )
+    }?;
+
+    Ok(high_contrast.dwFlags.contains(HCF_HIGHCONTRASTON))
+  }
+
+  /// Gets whether animations are turned off via the "Animation effects"
+  /// accessibility setting.
+  pub fn is_reduced_motion_enabled() -> anyhow::Result<bool> {
+    let mut is_animation_enabled = BOOL::default();
+
+    unsafe {
+      SystemParametersInfoW(
+        SPI_GETCLIENTAREAANIMATION,
+        0,
+        Some(std::ptr::from_mut(&mut is_animation_enabled).cast()),
+        SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+      )
+    }?;
+
+    Ok(!is_animation_enabled.as_bool())
+  }
+
+  /// Gets the border colors for focused and unfocused windows from the
+  /// active theme, which are its highlight and disabled text colors.
+  ///
+  /// Meant for use with high-contrast themes.
+  #[must_use]
+  pub fn high_contrast_border_colors() -> (Color, Color) {
+    let to_color = |index| {
+      let [r, g, b, _] = unsafe { GetSysColor(index) }.to_le_bytes();
+      Color { r, g, b, a: 255 }
+    };
+
+    (to_color(COLOR_HIGHLIGHT), to_color(COLOR_GRAYTEXT))
+  }
+
   /// Opens File Explorer at the specified path.
   pub fn open_file_explorer(path: &PathBuf) -> anyhow::Result<()> {
     let normalized_path = std::fs::canonicalize(path)?;
@@ -429,11 +556,15 @@ impl Platform {
   }
 
   /// Runs the specified program with the given arguments.
+  ///
+  /// Returns the ID of the launched process. This is `None` if no new
+  /// process was started (e.g. when a document is opened in an already
+  /// running application).
   pub fn run_command(
     program: &str,
     args: &str,
     hide_window: bool,
-  ) -> anyhow::Result<()> {
+  ) -> anyhow::Result<Option<u32>> {
     let home_dir = home::home_dir()
       .context("Unable to get home directory.")?
       .to_str()
@@ -464,7 +595,15 @@ impl Platform {
     };
 
     unsafe { ShellExecuteExW(&raw mut exec_info) }?;
-    Ok(())
+
+    if exec_info.hProcess.is_invalid() {
+      return Ok(None);
+    }
+
+    let process_id = unsafe { GetProcessId(exec_info.hProcess) };
+    unsafe { CloseHandle(exec_info.hProcess) }?;
+
+    Ok((process_id != 0).then_some(process_id))
   }
 
   pub fn show_error_dialog(title: &str, message: &str) {
@@ -480,6 +619,351 @@ impl Platform {
       );
     }
   }
+
+  /// Whether the current process is running with elevated (admin)
+  /// privileges.
+  pub fn is_elevated() -> anyhow::Result<bool> {
+    is_process_elevated(unsafe { GetCurrentProcess() })
+  }
+
+  /// Gets the time elapsed since midnight in the local time zone.
+  #[must_use]
+  pub fn local_time_of_day() -> Duration {
+    let time = unsafe { GetLocalTime() };
+
+    Duration::from_secs(
+      u64::from(time.wHour) * 3600
+        + u64::from(time.wMinute) * 60
+        + u64::from(time.wSecond),
+    ) + Duration::from_millis(u64::from(time.wMilliseconds))
+  }
+
+  /// Gets the time elapsed since the last keyboard or mouse input in
+  /// the current session.
+  pub fn idle_duration() -> anyhow::Result<Duration> {
+    let mut last_input_info = LASTINPUTINFO {
+      cbSize: u32::try_from(std::mem::size_of::<LASTINPUTINFO>())?,
+      ..Default::default()
+    };
+
+    if !unsafe { GetLastInputInfo(&raw mut last_input_info) }.as_bool() {
+      bail!("Failed to get time of last input.");
+    }
+
+    // Tick count wraps around after ~49.7 days of uptime.
+    let idle_ms =
+      unsafe { GetTickCount() }.wrapping_sub(last_input_info.dwTime);
+
+    Ok(Duration::from_millis(u64::from(idle_ms)))
+  }
+
+  /// Gets the ID of the parent process of the given process.
+  pub fn parent_process_id(
+    process_id: u32,
+  ) -> anyhow::Result<Option<u32>> {
+    let snapshot =
+      unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }?;
+
+    let mut entry = PROCESSENTRY32W {
+      dwSize: u32::try_from(std::mem::size_of::<PROCESSENTRY32W>())?,
+      ..Default::default()
+    };
+
+    let mut parent_process_id = None;
+    let mut has_entry =
+      unsafe { Process32FirstW(snapshot, &raw mut entry) }.is_ok();
+
+    while has_entry {
+      if entry.th32ProcessID == process_id {
+        parent_process_id = Some(entry.th32ParentProcessID);
+        break;
+      }
+
+      has_entry =
+        unsafe { Process32NextW(snapshot, &raw mut entry) }.is_ok();
+    }
+
+    unsafe { CloseHandle(snapshot) }?;
+
+    Ok(parent_process_id)
+  }
+
+  /// Gets the executable names of all running processes (e.g.
+  /// `explorer.exe`).
+  pub fn running_process_names() -> anyhow::Result<Vec<String>> {
+    let snapshot =
+      unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }?;
+
+    let mut entry = PROCESSENTRY32W {
+      dwSize: u32::try_from(std::mem::size_of::<PROCESSENTRY32W>())?,
+      ..Default::default()
+    };
+
+    let mut process_names = Vec::new();
+    let mut has_entry =
+      unsafe { Process32FirstW(snapshot, &raw mut entry) }.is_ok();
+
+    while has_entry {
+      let name_length = entry
+        .szExeFile
+        .iter()
+        .position(|&char| char == 0)
+        .unwrap_or(entry.szExeFile.len());
+
+      process_names
+        .push(String::from_utf16_lossy(&entry.szExeFile[..name_length]));
+
+      has_entry =
+        unsafe { Process32NextW(snapshot, &raw mut entry) }.is_ok();
+    }
+
+    unsafe { CloseHandle(snapshot) }?;
+
+    Ok(process_names)
+  }
+
+  /// Shows a non-blocking notification that is automatically dismissed
+  /// after a few seconds.
+  pub fn show_notification(
+    title: &str,
+    message: &str,
+    level: NotificationLevel,
+  ) {
+    if let Err(err) = show_notification(title, message, level) {
+      warn!("Failed to show notification: {}", err);
+    }
+  }
+
+  /// Announces a message to screen readers, or speaks it with the system
+  /// voice if no screen reader is running.
+  pub fn announce(message: &str) {
+    if let Err(err) = announce(message) {
+      warn!("Failed to announce message: {}", err);
+    }
+  }
+
+  /// Shows a searchable list of commands. The command of the selected
+  /// entry is sent via `selection_tx`.
+  pub fn show_command_palette(
+    entries: Vec<CommandPaletteEntry>,
+    selection_tx: UnboundedSender<String>,
+  ) {
+    if let Err(err) = show_command_palette(entries, selection_tx) {
+      warn!("Failed to show command palette: {}", err);
+    }
+  }
+
+  /// Shows a menu of entries that are selected by pressing their key.
+  /// The commands of the selected entry are sent via `selection_tx`.
+  pub fn show_leader_menu(
+    title: String,
+    entries: Vec<LeaderMenuEntry>,
+    selection_tx: UnboundedSender<String>,
+  ) {
+    if let Err(err) = show_leader_menu(title, entries, selection_tx) {
+      warn!("Failed to show leader menu: {}", err);
+    }
+  }
+
+  /// Shows an overlay listing the given keybindings, which is dismissed
+  /// on any key press.
+  pub fn show_keybindings_overlay(sections: Vec<KeybindingSection>) {
+    if let Err(err) = show_keybindings_overlay(sections) {
+      warn!("Failed to show keybindings overlay: {}", err);
+    }
+  }
+
+  /// Shows an overlay with thumbnails of the given windows for switching
+  /// between them. The command of the selected entry is sent via
+  /// `selection_tx`.
+  pub fn show_window_switcher(
+    entries: Vec<WindowSwitcherEntry>,
+    selection_tx: UnboundedSender<String>,
+  ) {
+    if let Err(err) = show_window_switcher(entries, selection_tx) {
+      warn!("Failed to show window switcher: {}", err);
+    }
+  }
+
+  /// Shows a label over each of the given windows. The command of the
+  /// hint whose label is typed is sent via `selection_tx`.
+  pub fn show_window_hints(
+    hints: Vec<WindowHint>,
+    selection_tx: UnboundedSender<String>,
+  ) {
+    if let Err(err) = show_window_hints(hints, selection_tx) {
+      warn!("Failed to show window hints: {}", err);
+    }
+  }
+
+  /// Shows an overlay with previews of the given workspaces covering the
+  /// monitor with the given rect. Commands for switching workspaces and
+  /// moving windows between them are sent via `selection_tx`.
+  pub fn show_overview(
+    monitor_rect: Rect,
+    workspaces: Vec<OverviewWorkspace>,
+    selected: usize,
+    selection_tx: UnboundedSender<String>,
+  ) {
+    if let Err(err) =
+      show_overview(monitor_rect, workspaces, selected, selection_tx)
+    {
+      warn!("Failed to show overview: {}", err);
+    }
+  }
+
+  /// Simulates pressing a combination of keys (e.g. `ctrl+shift+t`) in
+  /// the foreground window.
+  pub fn send_keys(keys: &str) -> anyhow::Result<()> {
+    send_keys(keys)
+  }
+
+  /// Gets the keyboard layout of the foreground window.
+  #[must_use]
+  pub fn keyboard_layout() -> KeyboardLayout {
+    KeyboardHook::keyboard_layout_info(
+      KeyboardHook::foreground_keyboard_layout(),
+    )
+  }
+
+  /// Gets the IDs of processes that are currently playing audio.
+  pub fn audio_process_ids() -> anyhow::Result<HashSet<u32>> {
+    audio_process_ids()
+  }
+
+  /// Gets the apps that are currently using a capability (e.g. the
+  /// microphone).
+  pub fn capability_users(
+    capability: Capability,
+  ) -> anyhow::Result<CapabilityUsers> {
+    capability_users(capability)
+  }
+
+  /// Gets the active Focus Assist profile.
+  pub fn focus_assist_mode() -> anyhow::Result<FocusAssistMode> {
+    focus_assist_mode()
+  }
+
+  /// Sets the active Focus Assist profile.
+  pub fn set_focus_assist_mode(
+    mode: FocusAssistMode,
+  ) -> anyhow::Result<()> {
+    set_focus_assist_mode(mode)
+  }
+
+  /// Shows a crosshair cursor and blocks until a window is clicked.
+  ///
+  /// Returns the clicked root window, or `None` if the pick was
+  /// cancelled via escape or right-click.
+  pub fn pick_window() -> anyhow::Result<Option<NativeWindow>> {
+    pick_window()
+  }
+
+  /// Creates a window that shows an empty frame with the given label,
+  /// which can be added to the tree in place of an expected window.
+  ///
+  /// The window is destroyed once it's closed.
+  pub fn create_placeholder_window(
+    label: &str,
+  ) -> anyhow::Result<NativeWindow> {
+    create_placeholder_window(label)
+  }
+
+  /// Shows a status bar for each of the given states, and closes the
+  /// bars of monitors that no longer have a state. Clicking a workspace
+  /// in a bar sends its focus command via `command_tx`.
+  pub fn update_status_bars(
+    states: Vec<StatusBarState>,
+    command_tx: &UnboundedSender<String>,
+  ) {
+    if let Err(err) = update_status_bars(states, command_tx) {
+      warn!("Failed to update status bars: {}", err);
+    }
+  }
+
+  /// Shows a frame around the focused window, or hides it if `None`.
+  pub fn update_focus_frame(frame: Option<FocusFrame>) {
+    if let Err(err) = update_focus_frame(frame) {
+      warn!("Failed to update focus frame: {}", err);
+    }
+  }
+
+  /// Shows guides for resizing with the keyboard, or hides them if
+  /// `None`.
+  pub fn update_resize_guides(guides: Option<ResizeGuides>) {
+    if let Err(err) = update_resize_guides(guides) {
+      warn!("Failed to update resize guides: {}", err);
+    }
+  }
+
+  /// Magnifies the screen to show the given region, or restores the
+  /// normal view if `None`.
+  pub fn update_zoom(zoom: Option<Zoom>) {
+    if let Err(err) = update_zoom(zoom) {
+      warn!("Failed to update zoom: {}", err);
+    }
+  }
+
+  /// Closes all status bars, releasing the space they reserved.
+  pub fn close_status_bars() {
+    close_status_bars();
+  }
+
+  /// Captures the contents of a window as a PNG thumbnail, downscaled so
+  /// that neither side exceeds `max_size` pixels.
+  pub fn capture_window_thumbnail(
+    window: &NativeWindow,
+    max_size: u32,
+  ) -> anyhow::Result<WindowThumbnail> {
+    capture_window_thumbnail(window, max_size)
+  }
+
+  /// Shows a blocking dialog with "Yes" and "No" buttons.
+  ///
+  /// Returns `true` if "Yes" was selected.
+  #[must_use]
+  pub fn show_confirm_dialog(title: &str, message: &str) -> bool {
+    let title_wide = to_wide(title);
+    let message_wide = to_wide(message);
+
+    let result = unsafe {
+      MessageBoxW(
+        None,
+        PCWSTR(message_wide.as_ptr()),
+        PCWSTR(title_wide.as_ptr()),
+        MB_ICONQUESTION | MB_YESNO | MB_SYSTEMMODAL,
+      )
+    };
+
+    result == IDYES
+  }
+}
+
+/// Whether the given process is running with elevated (admin)
+/// privileges.
+pub(crate) fn is_process_elevated(
+  process: HANDLE,
+) -> anyhow::Result<bool> {
+  let mut token = HANDLE::default();
+  unsafe { OpenProcessToken(process, TOKEN_QUERY, &raw mut token) }?;
+
+  let mut elevation = TOKEN_ELEVATION::default();
+  let mut return_length = 0;
+
+  let res = unsafe {
+    GetTokenInformation(
+      token,
+      TokenElevation,
+      Some(std::ptr::from_mut(&mut elevation).cast()),
+      u32::try_from(std::mem::size_of::<TOKEN_ELEVATION>())?,
+      &raw mut return_length,
+    )
+  };
+
+  unsafe { CloseHandle(token) }?;
+  res?;
+
+  Ok(elevation.TokenIsElevated != 0)
 }
 
 /// Utility function to convert a string to a null-terminated wide string.
@@ -0,0 +1,143 @@
+use std::{collections::HashMap, sync::Mutex, thread};
+
+use windows::Win32::{
+  Foundation::{CloseHandle, FILETIME, HANDLE},
+  System::{
+    ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
+    SystemInformation::GetSystemTimeAsFileTime,
+    Threading::{
+      GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    },
+  },
+};
+use wm_common::ProcessStats;
+
+/// Minimum time between CPU samples of a process, in 100-nanosecond
+/// intervals (500ms). Shorter intervals give unreliable percentages, so
+/// the previous result is reused instead (e.g. for several windows of the
+/// same process in one query).
+const MIN_SAMPLE_INTERVAL: u64 = 5_000_000;
+
+/// Time after which unused CPU samples are discarded, in 100-nanosecond
+/// intervals (10 minutes).
+const SAMPLE_EXPIRY: u64 = 6_000_000_000;
+
+/// Most recent CPU sample of each process, keyed by process ID.
+static CPU_SAMPLES: Mutex<Option<HashMap<u32, CpuSample>>> =
+  Mutex::new(None);
+
+#[derive(Clone, Copy, Debug)]
+struct CpuSample {
+  /// Time at which the sample was taken.
+  sampled_at: u64,
+
+  /// Total kernel and user time of the process when it was sampled.
+  cpu_time: u64,
+
+  /// CPU usage as of the sample.
+  cpu_percent: f32,
+}
+
+/// Gets the CPU and memory usage of a process.
+///
+/// CPU usage is measured between calls, so stats are only gathered on
+/// demand rather than by polling in the background.
+pub(crate) fn process_stats(
+  process_id: u32,
+) -> anyhow::Result<ProcessStats> {
+  let process_handle = unsafe {
+    OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id)
+  }?;
+
+  let stats = query_process_stats(process_handle, process_id);
+  unsafe { CloseHandle(process_handle) }?;
+
+  stats
+}
+
+fn query_process_stats(
+  process_handle: HANDLE,
+  process_id: u32,
+) -> anyhow::Result<ProcessStats> {
+  let mut creation_time = FILETIME::default();
+  let mut exit_time = FILETIME::default();
+  let mut kernel_time = FILETIME::default();
+  let mut user_time = FILETIME::default();
+
+  unsafe {
+    GetProcessTimes(
+      process_handle,
+      &raw mut creation_time,
+      &raw mut exit_time,
+      &raw mut kernel_time,
+      &raw mut user_time,
+    )
+  }?;
+
+  let mut memory_counters = PROCESS_MEMORY_COUNTERS::default();
+
+  #[allow(clippy::cast_possible_truncation)]
+  unsafe {
+    GetProcessMemoryInfo(
+      process_handle,
+      &raw mut memory_counters,
+      std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+    )
+  }?;
+
+  let now = to_u64(unsafe { GetSystemTimeAsFileTime() });
+  let cpu_time = to_u64(kernel_time) + to_u64(user_time);
+
+  let mut samples = CPU_SAMPLES.lock().unwrap();
+  let samples = samples.get_or_insert_with(HashMap::new);
+
+  samples.retain(|_, sample| {
+    now.saturating_sub(sample.sampled_at) < SAMPLE_EXPIRY
+  });
+
+  // Measure since the process was started if it hasn't been sampled
+  // before.
+  let prev_sample =
+    samples.get(&process_id).copied().unwrap_or(CpuSample {
+      sampled_at: to_u64(creation_time),
+      cpu_time: 0,
+      cpu_percent: 0.,
+    });
+
+  let elapsed = now.saturating_sub(prev_sample.sampled_at);
+
+  let cpu_percent = if elapsed < MIN_SAMPLE_INTERVAL {
+    prev_sample.cpu_percent
+  } else {
+    let processor_count =
+      thread::available_parallelism().map_or(1, usize::from);
+
+    #[allow(clippy::cast_precision_loss)]
+    let cpu_percent = cpu_time.saturating_sub(prev_sample.cpu_time) as f32
+      / (elapsed as f32 * processor_count as f32)
+      * 100.;
+
+    samples.insert(
+      process_id,
+      CpuSample {
+        sampled_at: now,
+        cpu_time,
+        cpu_percent,
+      },
+    );
+
+    cpu_percent
+  };
+
+  Ok(ProcessStats {
+    process_id,
+    cpu_percent,
+    working_set_bytes: memory_counters.WorkingSetSize as u64,
+  })
+}
+
+/// Converts a `FILETIME` to a count of 100-nanosecond intervals.
+fn to_u64(file_time: FILETIME) -> u64 {
+  (u64::from(file_time.dwHighDateTime) << 32)
+    | u64::from(file_time.dwLowDateTime)
+}
@@ -0,0 +1,357 @@
+use std::sync::{Mutex, OnceLock};
+
+use tracing::warn;
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::Gdi::{
+      BeginPaint, CreateSolidBrush, DeleteObject, EndPaint, FrameRect,
+      InvalidateRect, DT_CENTER, DT_LEFT, FW_NORMAL, FW_SEMIBOLD, HDC,
+      PAINTSTRUCT,
+    },
+    UI::WindowsAndMessaging::{
+      DefWindowProcW, GetClientRect, GetSystemMetrics, PostMessageW,
+      SetLayeredWindowAttributes, SetWindowPos, ShowWindow, HWND_TOPMOST,
+      LWA_COLORKEY, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN,
+      SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SWP_NOACTIVATE,
+      SWP_SHOWWINDOW, SW_HIDE, WM_APP, WM_PAINT, WNDCLASSW, WS_POPUP,
+    },
+  },
+};
+use wm_common::Rect;
+
+use crate::overlay::{
+  create_overlay_window, draw_text, fill_rect, rgb, spawn_overlay_thread,
+  TextStyle, CLICK_THROUGH_EX_STYLE,
+};
+
+/// Custom message for updating the overlay to match `RESIZE_GUIDES`.
+const WM_UPDATE_RESIZE_GUIDES: u32 = WM_APP + 1;
+
+/// Background color that is made fully transparent, so that only the
+/// guides are visible. Not used anywhere else in the overlay.
+const TRANSPARENCY_KEY: COLORREF = COLORREF(0x00FF_00FF);
+
+/// Width of the outline around each container in pixels.
+const OUTLINE_WIDTH: i32 = 2;
+
+/// Size of the size label at the center of each container in pixels.
+const SIZE_LABEL_WIDTH: i32 = 88;
+const SIZE_LABEL_HEIGHT: i32 = 40;
+
+/// Dimensions of the keybinding legend in pixels.
+const LEGEND_WIDTH: i32 = 440;
+const LEGEND_PADDING: i32 = 12;
+const LEGEND_ROW_HEIGHT: i32 = 24;
+const LEGEND_KEY_WIDTH: i32 = 140;
+
+/// Distance between the legend and the bottom of the monitor in pixels.
+const LEGEND_MARGIN: i32 = 48;
+
+/// Handle to the resize guides window. Created on first use.
+static RESIZE_GUIDES_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// Guides that are currently shown, or `None` if they're hidden.
+///
+/// For use with window procedure.
+static RESIZE_GUIDES: Mutex<Option<ResizeGuides>> = Mutex::new(None);
+
+/// Guides shown while resizing with the keyboard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResizeGuides {
+  /// Title of the keybinding legend (e.g. the binding mode's name).
+  pub title: String,
+
+  /// Containers whose sizes are adjusted by resizing, labeled with their
+  /// current size.
+  pub sizes: Vec<ResizeGuideSize>,
+
+  /// Keybindings for resizing as tuples of their keyboard shortcuts and
+  /// commands.
+  pub keybindings: Vec<(String, String)>,
+
+  /// Rect of the monitor to show the keybinding legend on.
+  pub monitor_rect: Rect,
+}
+
+/// Container labeled with its current size.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResizeGuideSize {
+  pub rect: Rect,
+
+  /// Size to show (e.g. `50%`).
+  pub label: String,
+
+  /// Whether this is the container that is being resized.
+  pub is_focused: bool,
+}
+
+/// Shows the given resize guides, or hides them if `None`.
+///
+/// Does nothing if the guides are unchanged.
+pub(crate) fn update_resize_guides(
+  guides: Option<ResizeGuides>,
+) -> anyhow::Result<()> {
+  {
+    let mut current_guides = RESIZE_GUIDES.lock().unwrap();
+
+    if *current_guides == guides {
+      return Ok(());
+    }
+
+    *current_guides = guides;
+  }
+
+  let handle = *RESIZE_GUIDES_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Resize guides", create_resize_guides_window)
+  })?;
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_UPDATE_RESIZE_GUIDES,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_resize_guides_window() -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("ResizeGuidesWindow"),
+    lpfnWndProc: Some(resize_guides_window_proc),
+    ..Default::default()
+  };
+
+  let handle =
+    create_overlay_window(&wnd_class, CLICK_THROUGH_EX_STYLE, WS_POPUP)?;
+
+  unsafe {
+    SetLayeredWindowAttributes(handle, TRANSPARENCY_KEY, 0, LWA_COLORKEY)
+  }?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the resize guides window.
+extern "system" fn resize_guides_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_UPDATE_RESIZE_GUIDES => {
+      if let Err(err) = reposition_resize_guides(handle) {
+        warn!("Failed to update resize guides: {}", err);
+      }
+
+      LRESULT(0)
+    }
+    WM_PAINT => {
+      paint_resize_guides(handle);
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+/// Covers the virtual screen with the window while guides are shown,
+/// and hides it otherwise.
+fn reposition_resize_guides(handle: HWND) -> anyhow::Result<()> {
+  if RESIZE_GUIDES.lock().unwrap().is_none() {
+    unsafe { ShowWindow(handle, SW_HIDE) };
+    return Ok(());
+  }
+
+  unsafe {
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      GetSystemMetrics(SM_XVIRTUALSCREEN),
+      GetSystemMetrics(SM_YVIRTUALSCREEN),
+      GetSystemMetrics(SM_CXVIRTUALSCREEN),
+      GetSystemMetrics(SM_CYVIRTUALSCREEN),
+      SWP_NOACTIVATE | SWP_SHOWWINDOW,
+    )?;
+
+    InvalidateRect(handle, None, true);
+  };
+
+  Ok(())
+}
+
+fn paint_resize_guides(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  let hdc = unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  let mut bounds = RECT::default();
+  let _ = unsafe { GetClientRect(handle, &raw mut bounds) };
+
+  fill_rect(hdc, &bounds, TRANSPARENCY_KEY);
+
+  if let Some(guides) = RESIZE_GUIDES.lock().unwrap().as_ref() {
+    // Rects are in screen coordinates, whereas the window's client area
+    // starts at the top-left of the virtual screen.
+    let (origin_x, origin_y) = unsafe {
+      (
+        GetSystemMetrics(SM_XVIRTUALSCREEN),
+        GetSystemMetrics(SM_YVIRTUALSCREEN),
+      )
+    };
+
+    for size in &guides.sizes {
+      let color = if size.is_focused {
+        rgb(0x42, 0x8b, 0xf5)
+      } else {
+        rgb(0x80, 0x80, 0x80)
+      };
+
+      let rect = RECT {
+        left: size.rect.left - origin_x,
+        top: size.rect.top - origin_y,
+        right: size.rect.right - origin_x,
+        bottom: size.rect.bottom - origin_y,
+      };
+
+      draw_outline(hdc, rect, color);
+
+      let center = size.rect.center_point();
+      let center_x = center.x - origin_x;
+      let center_y = center.y - origin_y;
+
+      let label_rect = RECT {
+        left: center_x - SIZE_LABEL_WIDTH / 2,
+        top: center_y - SIZE_LABEL_HEIGHT / 2,
+        right: center_x + SIZE_LABEL_WIDTH / 2,
+        bottom: center_y + SIZE_LABEL_HEIGHT / 2,
+      };
+
+      fill_rect(hdc, &label_rect, color);
+      draw_text(
+        hdc,
+        &size.label,
+        label_rect,
+        &TextStyle {
+          height: 26,
+          weight: FW_SEMIBOLD.0,
+          color: rgb(0xff, 0xff, 0xff),
+          alignment: DT_CENTER,
+        },
+      );
+    }
+
+    draw_legend(
+      hdc,
+      guides,
+      guides.monitor_rect.left - origin_x,
+      guides.monitor_rect.bottom - origin_y,
+    );
+  }
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
+
+/// Draws the keybinding legend centered at the bottom of the monitor.
+fn draw_legend(
+  hdc: HDC,
+  guides: &ResizeGuides,
+  monitor_left: i32,
+  monitor_bottom: i32,
+) {
+  #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+  let height = LEGEND_PADDING * 2
+    + LEGEND_ROW_HEIGHT * (guides.keybindings.len() as i32 + 1);
+
+  let left =
+    monitor_left + (guides.monitor_rect.width() - LEGEND_WIDTH).max(0) / 2;
+
+  let legend_rect = RECT {
+    left,
+    top: monitor_bottom - LEGEND_MARGIN - height,
+    right: left + LEGEND_WIDTH,
+    bottom: monitor_bottom - LEGEND_MARGIN,
+  };
+
+  fill_rect(hdc, &legend_rect, rgb(0x20, 0x20, 0x20));
+
+  let title_rect = RECT {
+    left: legend_rect.left + LEGEND_PADDING,
+    top: legend_rect.top + LEGEND_PADDING,
+    right: legend_rect.right - LEGEND_PADDING,
+    bottom: legend_rect.top + LEGEND_PADDING + LEGEND_ROW_HEIGHT,
+  };
+
+  draw_text(
+    hdc,
+    &guides.title,
+    title_rect,
+    &TextStyle {
+      height: 18,
+      weight: FW_SEMIBOLD.0,
+      color: rgb(0x42, 0x8b, 0xf5),
+      alignment: DT_LEFT,
+    },
+  );
+
+  for (row, (keys, commands)) in guides.keybindings.iter().enumerate() {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let top = title_rect.bottom + LEGEND_ROW_HEIGHT * row as i32;
+
+    draw_text(
+      hdc,
+      keys,
+      RECT {
+        top,
+        bottom: top + LEGEND_ROW_HEIGHT,
+        right: title_rect.left + LEGEND_KEY_WIDTH,
+        ..title_rect
+      },
+      &TextStyle {
+        height: 18,
+        weight: FW_SEMIBOLD.0,
+        color: rgb(0xf0, 0xf0, 0xf0),
+        alignment: DT_LEFT,
+      },
+    );
+
+    draw_text(
+      hdc,
+      commands,
+      RECT {
+        top,
+        bottom: top + LEGEND_ROW_HEIGHT,
+        left: title_rect.left + LEGEND_KEY_WIDTH,
+        ..title_rect
+      },
+      &TextStyle {
+        height: 18,
+        weight: FW_NORMAL.0,
+        color: rgb(0xc0, 0xc0, 0xc0),
+        alignment: DT_LEFT,
+      },
+    );
+  }
+}
+
+fn draw_outline(hdc: HDC, rect: RECT, color: COLORREF) {
+  unsafe {
+    let brush = CreateSolidBrush(color);
+
+    for inset in 0..OUTLINE_WIDTH {
+      let inset_rect = RECT {
+        left: rect.left + inset,
+        top: rect.top + inset,
+        right: rect.right - inset,
+        bottom: rect.bottom - inset,
+      };
+
+      FrameRect(hdc, &raw const inset_rect, brush);
+    }
+
+    DeleteObject(brush);
+  }
+}
@@ -0,0 +1,137 @@
+use anyhow::{bail, Context};
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+  GetAsyncKeyState, SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT,
+  KEYBD_EVENT_FLAGS, KEYEVENTF_EXTENDEDKEY, KEYEVENTF_KEYUP, VIRTUAL_KEY,
+  VK_APPS, VK_DELETE, VK_DIVIDE, VK_DOWN, VK_END, VK_HOME, VK_INSERT,
+  VK_LCONTROL, VK_LEFT, VK_LMENU, VK_LSHIFT, VK_LWIN, VK_NEXT, VK_NUMLOCK,
+  VK_PRIOR, VK_RCONTROL, VK_RIGHT, VK_RMENU, VK_RSHIFT, VK_RWIN,
+  VK_SNAPSHOT, VK_UP,
+};
+
+use crate::KeyboardHook;
+
+/// Magic number used to identify keyboard inputs that are simulated via
+/// `send_keys`, so that they aren't handled as keybindings.
+pub const SEND_KEYS_INPUT_IDENTIFIER: u32 = 6380;
+
+/// Unassigned virtual key. Pressing it while alt or win is held prevents
+/// the later release of those keys from opening the menu bar or start
+/// menu.
+const VK_MASK: u16 = 0xE8;
+
+/// Modifier keys that are released while keys are being sent.
+const MODIFIER_KEYS: [VIRTUAL_KEY; 8] = [
+  VK_LSHIFT,
+  VK_RSHIFT,
+  VK_LCONTROL,
+  VK_RCONTROL,
+  VK_LMENU,
+  VK_RMENU,
+  VK_LWIN,
+  VK_RWIN,
+];
+
+/// Keys that need the extended key flag to not be interpreted as their
+/// numpad or left-side counterparts.
+const EXTENDED_KEYS: [VIRTUAL_KEY; 18] = [
+  VK_LEFT,
+  VK_RIGHT,
+  VK_UP,
+  VK_DOWN,
+  VK_INSERT,
+  VK_DELETE,
+  VK_HOME,
+  VK_END,
+  VK_PRIOR,
+  VK_NEXT,
+  VK_RCONTROL,
+  VK_RMENU,
+  VK_LWIN,
+  VK_RWIN,
+  VK_NUMLOCK,
+  VK_DIVIDE,
+  VK_APPS,
+  VK_SNAPSHOT,
+];
+
+/// Simulates pressing a combination of keys (e.g. `ctrl+shift+t`) in the
+/// foreground window. Keys are pressed in the given order and released
+/// in reverse order.
+///
+/// Modifier keys that are physically held down (e.g. from the keybinding
+/// that invoked this) are released beforehand and restored afterwards,
+/// so that they don't get combined with the sent keys.
+pub(crate) fn send_keys(keys: &str) -> anyhow::Result<()> {
+  let vk_codes = keys
+    .split('+')
+    .map(|key| {
+      KeyboardHook::key_to_vk_code(&key.to_lowercase())
+        .with_context(|| format!("Unknown key '{key}' in '{keys}'."))
+    })
+    .try_collect::<Vec<_>>()?;
+
+  let held_modifiers = MODIFIER_KEYS
+    .iter()
+    .map(|key| key.0)
+    .filter(|vk_code| !vk_codes.contains(vk_code) && is_key_down(*vk_code))
+    .collect::<Vec<_>>();
+
+  let mut inputs = Vec::new();
+
+  inputs.extend(held_modifiers.iter().map(|&key| key_input(key, true)));
+  inputs.extend(vk_codes.iter().map(|&key| key_input(key, false)));
+  inputs.extend(vk_codes.iter().rev().map(|&key| key_input(key, true)));
+  inputs.extend(held_modifiers.iter().map(|&key| key_input(key, false)));
+
+  let has_held_alt_or_win = held_modifiers.iter().any(|&key| {
+    [VK_LMENU, VK_RMENU, VK_LWIN, VK_RWIN]
+      .iter()
+      .any(|modifier| modifier.0 == key)
+  });
+
+  if has_held_alt_or_win {
+    inputs.push(key_input(VK_MASK, false));
+    inputs.push(key_input(VK_MASK, true));
+  }
+
+  #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+  let sent_count =
+    unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+
+  if sent_count as usize != inputs.len() {
+    bail!("Failed to send keys '{keys}'. Input may be blocked by UIPI.");
+  }
+
+  Ok(())
+}
+
+/// Gets whether the key is physically held down.
+fn is_key_down(vk_code: u16) -> bool {
+  // The most significant bit is set if the key is down.
+  let state = unsafe { GetAsyncKeyState(i32::from(vk_code)) };
+  state < 0
+}
+
+fn key_input(vk_code: u16, is_key_up: bool) -> INPUT {
+  let mut flags = KEYBD_EVENT_FLAGS::default();
+
+  if is_key_up {
+    flags |= KEYEVENTF_KEYUP;
+  }
+
+  if EXTENDED_KEYS.iter().any(|key| key.0 == vk_code) {
+    flags |= KEYEVENTF_EXTENDEDKEY;
+  }
+
+  INPUT {
+    r#type: INPUT_KEYBOARD,
+    Anonymous: INPUT_0 {
+      ki: KEYBDINPUT {
+        wVk: VIRTUAL_KEY(vk_code),
+        dwFlags: flags,
+        dwExtraInfo: SEND_KEYS_INPUT_IDENTIFIER as usize,
+        ..Default::default()
+      },
+    },
+  }
+}
@@ -30,6 +30,12 @@ impl SingleInstance {
 
     if let Err(err) = unsafe { GetLastError() } {
       if err == ERROR_ALREADY_EXISTS.into() {
+        // Close the handle to the existing mutex, so that it's destroyed
+        // once the other instance exits.
+        unsafe {
+          let _ = CloseHandle(handle);
+        }
+
         bail!("Another instance of the application is already running.");
       }
     }
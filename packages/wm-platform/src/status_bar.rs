@@ -0,0 +1,745 @@
+use std::{cell::RefCell, sync::Mutex};
+
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+use windows::{
+  core::{w, HSTRING},
+  Win32::{
+    Foundation::{HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::{
+      Direct2D::{
+        Common::{D2D1_COLOR_F, D2D_POINT_2F, D2D_RECT_F, D2D_SIZE_U},
+        D2D1CreateFactory, ID2D1Factory, ID2D1HwndRenderTarget,
+        ID2D1SolidColorBrush, D2D1_DRAW_TEXT_OPTIONS_CLIP,
+        D2D1_DRAW_TEXT_OPTIONS_NONE, D2D1_FACTORY_TYPE_SINGLE_THREADED,
+        D2D1_HWND_RENDER_TARGET_PROPERTIES, D2D1_PRESENT_OPTIONS_NONE,
+        D2D1_RENDER_TARGET_PROPERTIES,
+      },
+      DirectWrite::{
+        DWriteCreateFactory, IDWriteFactory, IDWriteTextFormat,
+        IDWriteTextLayout, DWRITE_FACTORY_TYPE_SHARED,
+        DWRITE_FONT_STRETCH_NORMAL, DWRITE_FONT_STYLE_NORMAL,
+        DWRITE_FONT_WEIGHT_NORMAL, DWRITE_PARAGRAPH_ALIGNMENT_CENTER,
+        DWRITE_TEXT_METRICS, DWRITE_TRIMMING,
+        DWRITE_TRIMMING_GRANULARITY_CHARACTER,
+        DWRITE_WORD_WRAPPING_NO_WRAP,
+      },
+      Gdi::{BeginPaint, EndPaint, InvalidateRect, PAINTSTRUCT},
+    },
+    UI::{
+      Shell::{
+        SHAppBarMessage, ABE_BOTTOM, ABE_TOP, ABM_NEW, ABM_QUERYPOS,
+        ABM_REMOVE, ABM_SETPOS, ABN_POSCHANGED, APPBARDATA,
+      },
+      WindowsAndMessaging::{
+        DefWindowProcW, GetClientRect, PostMessageW, PostQuitMessage,
+        SendMessageW, SetWindowPos, SWP_NOACTIVATE, SWP_NOZORDER,
+        SWP_SHOWWINDOW, WM_APP, WM_CLOSE, WM_DESTROY, WM_LBUTTONDOWN,
+        WM_PAINT, WM_SIZE, WNDCLASSW, WS_EX_NOACTIVATE, WS_POPUP,
+      },
+    },
+  },
+};
+use wm_common::{Color, Rect, StatusBarConfig, StatusBarPosition};
+
+use crate::overlay::{create_overlay_window, spawn_overlay_thread};
+
+/// Custom message for repainting a status bar after its state has
+/// changed. A non-zero `WPARAM` indicates that its size or position has
+/// changed as well.
+const WM_UPDATE_STATUS_BAR: u32 = WM_APP + 1;
+
+/// Custom message that the shell sends appbar notifications with.
+const WM_APPBAR_NOTIFY: u32 = WM_APP + 2;
+
+/// Custom message for registering a status bar as an appbar after its
+/// window has been created.
+const WM_REGISTER_APPBAR: u32 = WM_APP + 3;
+
+/// Status bars that currently exist, one per monitor.
+///
+/// For use with window procedure.
+static STATUS_BARS: Mutex<Vec<StatusBar>> = Mutex::new(Vec::new());
+
+thread_local! {
+  /// Direct2D resources of the status bar that runs on the current
+  /// thread. Created on first paint, and recreated if the render target
+  /// is lost (e.g. on a GPU reset).
+  static RENDERER: RefCell<Option<Renderer>> = const { RefCell::new(None) };
+}
+
+/// What to show in the status bar of a monitor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusBarState {
+  /// Rect of the monitor that the bar is shown on.
+  pub monitor_rect: Rect,
+
+  /// Height of the bar in physical pixels.
+  pub height: i32,
+
+  /// Font size of the bar's text in physical pixels.
+  pub font_size: f32,
+
+  /// Position, font, and colors of the bar.
+  pub config: StatusBarConfig,
+
+  /// Workspaces of the monitor, in the order they're shown.
+  pub workspaces: Vec<StatusBarWorkspace>,
+
+  /// Display name of the active binding mode.
+  pub binding_mode: Option<String>,
+
+  /// Title of the last focused window in the monitor's displayed
+  /// workspace.
+  pub window_title: Option<String>,
+}
+
+/// Workspace that is listed in a status bar.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusBarWorkspace {
+  pub label: String,
+
+  /// Whether the workspace is displayed on its monitor.
+  pub is_displayed: bool,
+
+  /// Whether the workspace has focus.
+  pub is_focused: bool,
+
+  /// Command that is sent when the workspace is clicked.
+  pub focus_command: String,
+}
+
+struct StatusBar {
+  handle: isize,
+  state: StatusBarState,
+
+  /// Horizontal bounds of each workspace label, in the same order as
+  /// `state.workspaces`. Updated on each paint.
+  workspace_bounds: Vec<(f32, f32)>,
+
+  /// Sender for the command of a clicked workspace.
+  command_tx: UnboundedSender<String>,
+}
+
+struct Renderer {
+  write_factory: IDWriteFactory,
+  target: ID2D1HwndRenderTarget,
+}
+
+/// Shows a status bar for each of the given states. Bars are matched to
+/// the states by their monitor rect, and are only repainted if their
+/// state has changed. Bars of monitors without a state are closed.
+///
+/// Each bar is registered as an appbar, so that the working area of its
+/// monitor excludes the bar.
+pub(crate) fn update_status_bars(
+  states: Vec<StatusBarState>,
+  command_tx: &UnboundedSender<String>,
+) -> anyhow::Result<()> {
+  let mut closed_handles = Vec::new();
+  let mut updated_handles = Vec::new();
+  let mut new_states = Vec::new();
+
+  {
+    let mut status_bars = STATUS_BARS.lock().unwrap();
+
+    status_bars.retain(|status_bar| {
+      let is_kept = states
+        .iter()
+        .any(|state| state.monitor_rect == status_bar.state.monitor_rect);
+
+      if !is_kept {
+        closed_handles.push(status_bar.handle);
+      }
+
+      is_kept
+    });
+
+    for state in states {
+      match status_bars.iter_mut().find(|status_bar| {
+        status_bar.state.monitor_rect == state.monitor_rect
+      }) {
+        Some(status_bar) if status_bar.state != state => {
+          let is_moved = status_bar.state.height != state.height
+            || status_bar.state.config.position != state.config.position;
+
+          status_bar.state = state;
+          updated_handles.push((status_bar.handle, is_moved));
+        }
+        Some(_) => {}
+        None => new_states.push(state),
+      }
+    }
+  }
+
+  // The lock is released before messaging the bars, since their window
+  // procedures acquire it as well.
+  for handle in closed_handles {
+    close_status_bar(handle);
+  }
+
+  for (handle, is_moved) in updated_handles {
+    unsafe {
+      PostMessageW(
+        HWND(handle),
+        WM_UPDATE_STATUS_BAR,
+        WPARAM(usize::from(is_moved)),
+        LPARAM::default(),
+      )
+    }?;
+  }
+
+  for state in new_states {
+    create_status_bar(state, command_tx.clone())?;
+  }
+
+  Ok(())
+}
+
+/// Closes all status bars and removes their appbar registrations.
+pub(crate) fn close_status_bars() {
+  let handles = std::mem::take(&mut *STATUS_BARS.lock().unwrap())
+    .into_iter()
+    .map(|status_bar| status_bar.handle)
+    .collect::<Vec<_>>();
+
+  for handle in handles {
+    close_status_bar(handle);
+  }
+}
+
+/// Closes a status bar and blocks until it's been destroyed, so that its
+/// appbar registration is removed before returning.
+fn close_status_bar(handle: isize) {
+  unsafe {
+    SendMessageW(
+      HWND(handle),
+      WM_CLOSE,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  };
+}
+
+/// Creates a status bar window for the given state.
+///
+/// Each bar runs its message loop on its own thread, which exits once
+/// the window is closed.
+fn create_status_bar(
+  state: StatusBarState,
+  command_tx: UnboundedSender<String>,
+) -> anyhow::Result<()> {
+  spawn_overlay_thread("Status bar", move || {
+    create_window(state, command_tx)
+  })?;
+
+  Ok(())
+}
+
+fn create_window(
+  state: StatusBarState,
+  command_tx: UnboundedSender<String>,
+) -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("StatusBarWindow"),
+    lpfnWndProc: Some(status_bar_window_proc),
+    ..Default::default()
+  };
+
+  // Window is shown once it has been positioned as an appbar.
+  let handle =
+    create_overlay_window(&wnd_class, WS_EX_NOACTIVATE, WS_POPUP)?;
+
+  STATUS_BARS.lock().unwrap().push(StatusBar {
+    handle: handle.0,
+    state,
+    workspace_bounds: Vec::new(),
+    command_tx,
+  });
+
+  // The appbar is registered from the message loop, i.e. once the caller
+  // is no longer waiting, since the shell notifies all top-level windows
+  // of the change in working area.
+  unsafe {
+    PostMessageW(
+      handle,
+      WM_REGISTER_APPBAR,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(handle.0)
+}
+
+fn register_appbar(handle: HWND) {
+  let mut appbar_data = APPBARDATA {
+    #[allow(clippy::cast_possible_truncation)]
+    cbSize: std::mem::size_of::<APPBARDATA>() as u32,
+    hWnd: handle,
+    uCallbackMessage: WM_APPBAR_NOTIFY,
+    ..Default::default()
+  };
+
+  unsafe { SHAppBarMessage(ABM_NEW, &raw mut appbar_data) };
+  update_appbar_position(handle);
+}
+
+/// Reserves the edge of the monitor for the status bar, and moves the
+/// window into the reserved space.
+fn update_appbar_position(handle: HWND) {
+  let Some(state) = status_bar_state(handle) else {
+    return;
+  };
+
+  let monitor_rect = &state.monitor_rect;
+  let mut appbar_data = APPBARDATA {
+    #[allow(clippy::cast_possible_truncation)]
+    cbSize: std::mem::size_of::<APPBARDATA>() as u32,
+    hWnd: handle,
+    uEdge: match state.config.position {
+      StatusBarPosition::Top => ABE_TOP,
+      StatusBarPosition::Bottom => ABE_BOTTOM,
+    },
+    rc: RECT {
+      left: monitor_rect.left,
+      top: monitor_rect.top,
+      right: monitor_rect.right,
+      bottom: monitor_rect.bottom,
+    },
+    ..Default::default()
+  };
+
+  // The proposed rect is adjusted by the shell to make room for other
+  // appbars (e.g. the taskbar) on the same edge.
+  unsafe { SHAppBarMessage(ABM_QUERYPOS, &raw mut appbar_data) };
+
+  match state.config.position {
+    StatusBarPosition::Top => {
+      appbar_data.rc.bottom = appbar_data.rc.top + state.height;
+    }
+    StatusBarPosition::Bottom => {
+      appbar_data.rc.top = appbar_data.rc.bottom - state.height;
+    }
+  }
+
+  unsafe { SHAppBarMessage(ABM_SETPOS, &raw mut appbar_data) };
+
+  let rect = appbar_data.rc;
+
+  if let Err(err) = unsafe {
+    SetWindowPos(
+      handle,
+      None,
+      rect.left,
+      rect.top,
+      rect.right - rect.left,
+      rect.bottom - rect.top,
+      SWP_NOACTIVATE | SWP_NOZORDER | SWP_SHOWWINDOW,
+    )
+  } {
+    warn!("Failed to position status bar: {}", err);
+  }
+}
+
+/// Window procedure for status bar windows.
+extern "system" fn status_bar_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_PAINT => {
+      paint_status_bar(handle);
+      LRESULT(0)
+    }
+    WM_SIZE => {
+      RENDERER.with_borrow(|renderer| {
+        if let Some(renderer) = renderer {
+          let _ = renderer.resize(handle);
+        }
+      });
+
+      unsafe { InvalidateRect(handle, None, false) };
+      LRESULT(0)
+    }
+    WM_REGISTER_APPBAR => {
+      register_appbar(handle);
+      LRESULT(0)
+    }
+    WM_UPDATE_STATUS_BAR => {
+      if wparam.0 != 0 {
+        update_appbar_position(handle);
+      }
+
+      unsafe { InvalidateRect(handle, None, false) };
+      LRESULT(0)
+    }
+    WM_APPBAR_NOTIFY => {
+      #[allow(clippy::cast_possible_truncation)]
+      if wparam.0 as u32 == ABN_POSCHANGED {
+        update_appbar_position(handle);
+      }
+
+      LRESULT(0)
+    }
+    WM_LBUTTONDOWN => {
+      #[allow(clippy::cast_possible_truncation)]
+      let x = f32::from((lparam.0 & 0xffff) as i16);
+      on_click(handle, x);
+      LRESULT(0)
+    }
+    WM_DESTROY => {
+      let mut appbar_data = APPBARDATA {
+        #[allow(clippy::cast_possible_truncation)]
+        cbSize: std::mem::size_of::<APPBARDATA>() as u32,
+        hWnd: handle,
+        ..Default::default()
+      };
+
+      unsafe { SHAppBarMessage(ABM_REMOVE, &raw mut appbar_data) };
+
+      STATUS_BARS
+        .lock()
+        .unwrap()
+        .retain(|status_bar| status_bar.handle != handle.0);
+
+      RENDERER.take();
+      unsafe { PostQuitMessage(0) };
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+/// Sends the focus command of the workspace at the given x-coordinate.
+fn on_click(handle: HWND, x: f32) {
+  let status_bars = STATUS_BARS.lock().unwrap();
+
+  let Some(status_bar) = status_bars
+    .iter()
+    .find(|status_bar| status_bar.handle == handle.0)
+  else {
+    return;
+  };
+
+  let workspace = status_bar
+    .workspace_bounds
+    .iter()
+    .position(|(left, right)| x >= *left && x < *right)
+    .and_then(|index| status_bar.state.workspaces.get(index));
+
+  if let Some(workspace) = workspace {
+    if let Err(err) =
+      status_bar.command_tx.send(workspace.focus_command.clone())
+    {
+      warn!("Failed to send status bar command: {}", err);
+    }
+  }
+}
+
+fn status_bar_state(handle: HWND) -> Option<StatusBarState> {
+  STATUS_BARS
+    .lock()
+    .unwrap()
+    .iter()
+    .find(|status_bar| status_bar.handle == handle.0)
+    .map(|status_bar| status_bar.state.clone())
+}
+
+fn paint_status_bar(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  if let Some(state) = status_bar_state(handle) {
+    let result = RENDERER.with_borrow_mut(|renderer| {
+      if renderer.is_none() {
+        *renderer = Some(Renderer::new(handle)?);
+      }
+
+      let result = renderer.as_ref().map(|renderer| renderer.draw(&state));
+
+      // Discard the renderer on failure, so that its resources get
+      // recreated on the next paint.
+      if let Some(Err(_)) = result {
+        *renderer = None;
+      }
+
+      result.unwrap_or(Ok(Vec::new()))
+    });
+
+    match result {
+      Ok(workspace_bounds) => {
+        if let Some(status_bar) = STATUS_BARS
+          .lock()
+          .unwrap()
+          .iter_mut()
+          .find(|status_bar| status_bar.handle == handle.0)
+        {
+          status_bar.workspace_bounds = workspace_bounds;
+        }
+      }
+      Err(err) => warn!("Failed to paint status bar: {}", err),
+    }
+  }
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
+
+impl Renderer {
+  fn new(handle: HWND) -> anyhow::Result<Self> {
+    let factory: ID2D1Factory = unsafe {
+      D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, None)
+    }?;
+
+    let write_factory: IDWriteFactory =
+      unsafe { DWriteCreateFactory(DWRITE_FACTORY_TYPE_SHARED) }?;
+
+    // Drawing is done in physical pixels, since the bar's height and
+    // font size are already scaled to the monitor's DPI.
+    let target_properties = D2D1_RENDER_TARGET_PROPERTIES {
+      dpiX: 96.,
+      dpiY: 96.,
+      ..Default::default()
+    };
+
+    let hwnd_target_properties = D2D1_HWND_RENDER_TARGET_PROPERTIES {
+      hwnd: handle,
+      pixelSize: client_size(handle)?,
+      presentOptions: D2D1_PRESENT_OPTIONS_NONE,
+    };
+
+    let target = unsafe {
+      factory.CreateHwndRenderTarget(
+        &raw const target_properties,
+        &raw const hwnd_target_properties,
+      )
+    }?;
+
+    Ok(Self {
+      write_factory,
+      target,
+    })
+  }
+
+  fn resize(&self, handle: HWND) -> anyhow::Result<()> {
+    let size = client_size(handle)?;
+    unsafe { self.target.Resize(&raw const size) }?;
+    Ok(())
+  }
+
+  /// Draws the workspaces and binding mode from the left edge, followed
+  /// by the window title in the remaining space.
+  ///
+  /// Returns the horizontal bounds of each workspace label.
+  fn draw(
+    &self,
+    state: &StatusBarState,
+  ) -> anyhow::Result<Vec<(f32, f32)>> {
+    let size = unsafe { self.target.GetSize() };
+    let padding = (state.font_size * 0.75).round();
+    let config = &state.config;
+
+    let text_format = self.text_format(state)?;
+    let foreground_brush = self.brush(&config.foreground)?;
+    let accent_brush = self.brush(&config.accent)?;
+    let inverted_brush = self.brush(&config.background)?;
+
+    let background = to_color_f(&config.background);
+
+    unsafe {
+      self.target.BeginDraw();
+      self.target.Clear(Some(&raw const background));
+    }
+
+    let mut workspace_bounds = Vec::new();
+    let mut x = 0.;
+
+    for workspace in &state.workspaces {
+      let layout =
+        self.text_layout(&workspace.label, &text_format, size.height)?;
+      let width = text_width(&layout)? + padding * 2.;
+
+      // The displayed workspace of an unfocused monitor is dimmed.
+      let text_brush = if workspace.is_displayed {
+        let rect = D2D_RECT_F {
+          left: x,
+          top: 0.,
+          right: x + width,
+          bottom: size.height,
+        };
+
+        unsafe {
+          accent_brush.SetOpacity(if workspace.is_focused {
+            1.
+          } else {
+            0.5
+          });
+
+          self.target.FillRectangle(&raw const rect, &accent_brush);
+        }
+
+        &inverted_brush
+      } else {
+        &foreground_brush
+      };
+
+      unsafe {
+        self.target.DrawTextLayout(
+          D2D_POINT_2F {
+            x: x + padding,
+            y: 0.,
+          },
+          &layout,
+          text_brush,
+          D2D1_DRAW_TEXT_OPTIONS_NONE,
+        );
+      }
+
+      workspace_bounds.push((x, x + width));
+      x += width;
+    }
+
+    unsafe { accent_brush.SetOpacity(1.) };
+
+    if let Some(binding_mode) = &state.binding_mode {
+      let layout =
+        self.text_layout(binding_mode, &text_format, size.height)?;
+
+      unsafe {
+        self.target.DrawTextLayout(
+          D2D_POINT_2F {
+            x: x + padding,
+            y: 0.,
+          },
+          &layout,
+          &accent_brush,
+          D2D1_DRAW_TEXT_OPTIONS_NONE,
+        );
+      }
+
+      x += text_width(&layout)? + padding * 2.;
+    }
+
+    if let Some(window_title) = &state.window_title {
+      let max_width = size.width - x - padding * 2.;
+
+      if max_width > 0. {
+        let layout =
+          self.text_layout(window_title, &text_format, size.height)?;
+
+        unsafe {
+          layout.SetMaxWidth(max_width)?;
+
+          self.target.DrawTextLayout(
+            D2D_POINT_2F {
+              x: x + padding,
+              y: 0.,
+            },
+            &layout,
+            &foreground_brush,
+            D2D1_DRAW_TEXT_OPTIONS_CLIP,
+          );
+        }
+      }
+    }
+
+    unsafe { self.target.EndDraw(None, None) }?;
+
+    Ok(workspace_bounds)
+  }
+
+  /// Creates a single-line text format that is vertically centered and
+  /// trimmed with an ellipsis.
+  fn text_format(
+    &self,
+    state: &StatusBarState,
+  ) -> anyhow::Result<IDWriteTextFormat> {
+    let text_format = unsafe {
+      self.write_factory.CreateTextFormat(
+        &HSTRING::from(state.config.font_family.as_str()),
+        None,
+        DWRITE_FONT_WEIGHT_NORMAL,
+        DWRITE_FONT_STYLE_NORMAL,
+        DWRITE_FONT_STRETCH_NORMAL,
+        state.font_size,
+        w!(""),
+      )
+    }?;
+
+    unsafe {
+      text_format
+        .SetParagraphAlignment(DWRITE_PARAGRAPH_ALIGNMENT_CENTER)?;
+      text_format.SetWordWrapping(DWRITE_WORD_WRAPPING_NO_WRAP)?;
+
+      let ellipsis = self
+        .write_factory
+        .CreateEllipsisTrimmingSign(&text_format)?;
+
+      let trimming = DWRITE_TRIMMING {
+        granularity: DWRITE_TRIMMING_GRANULARITY_CHARACTER,
+        delimiter: 0,
+        delimiterCount: 0,
+      };
+
+      text_format.SetTrimming(&raw const trimming, &ellipsis)?;
+    }
+
+    Ok(text_format)
+  }
+
+  fn text_layout(
+    &self,
+    text: &str,
+    text_format: &IDWriteTextFormat,
+    height: f32,
+  ) -> anyhow::Result<IDWriteTextLayout> {
+    let text_wide = text.encode_utf16().collect::<Vec<_>>();
+
+    let layout = unsafe {
+      self.write_factory.CreateTextLayout(
+        &text_wide,
+        text_format,
+        f32::MAX,
+        height,
+      )
+    }?;
+
+    Ok(layout)
+  }
+
+  fn brush(&self, color: &Color) -> anyhow::Result<ID2D1SolidColorBrush> {
+    let color = to_color_f(color);
+    let brush = unsafe {
+      self.target.CreateSolidColorBrush(&raw const color, None)
+    }?;
+
+    Ok(brush)
+  }
+}
+
+fn text_width(layout: &IDWriteTextLayout) -> anyhow::Result<f32> {
+  let mut metrics = DWRITE_TEXT_METRICS::default();
+  unsafe { layout.GetMetrics(&raw mut metrics) }?;
+  Ok(metrics.widthIncludingTrailingWhitespace)
+}
+
+fn client_size(handle: HWND) -> anyhow::Result<D2D_SIZE_U> {
+  let mut rect = RECT::default();
+  unsafe { GetClientRect(handle, &raw mut rect) }?;
+
+  Ok(D2D_SIZE_U {
+    width: u32::try_from(rect.right - rect.left).unwrap_or(0),
+    height: u32::try_from(rect.bottom - rect.top).unwrap_or(0),
+  })
+}
+
+fn to_color_f(color: &Color) -> D2D1_COLOR_F {
+  D2D1_COLOR_F {
+    r: f32::from(color.r) / 255.,
+    g: f32::from(color.g) / 255.,
+    b: f32::from(color.b) / 255.,
+    a: f32::from(color.a) / 255.,
+  }
+}
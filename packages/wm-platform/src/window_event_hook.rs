@@ -1,7 +1,6 @@
 use std::sync::{Arc, Mutex, OnceLock};
 
 use anyhow::Result;
-use tokio::sync::mpsc;
 use tracing::warn;
 use windows::Win32::{
   Foundation::HWND,
@@ -18,7 +17,10 @@ use windows::Win32::{
   },
 };
 
-use super::{NativeWindow, PlatformEvent};
+use super::{
+  clear_last_mouse_hit, NativeWindow, PlatformEvent, PlatformEventSender,
+  KEYBOARD_HOOK,
+};
 
 /// Global instance of `WindowEventHook`.
 ///
@@ -27,15 +29,13 @@ static WIN_EVENT_HOOK: OnceLock<Arc<WindowEventHook>> = OnceLock::new();
 
 #[derive(Debug)]
 pub struct WindowEventHook {
-  event_tx: mpsc::UnboundedSender<PlatformEvent>,
+  event_tx: PlatformEventSender,
   hook_handles: Arc<Mutex<Vec<HWINEVENTHOOK>>>,
 }
 
 impl WindowEventHook {
   /// Creates an instance of `WindowEventHook`.
-  pub fn new(
-    event_tx: mpsc::UnboundedSender<PlatformEvent>,
-  ) -> anyhow::Result<Arc<Self>> {
+  pub fn new(event_tx: PlatformEventSender) -> anyhow::Result<Arc<Self>> {
     let win_event_hook = Arc::new(Self {
       event_tx,
       hook_handles: Arc::new(Mutex::new(Vec::new())),
@@ -138,6 +138,27 @@ impl WindowEventHook {
       _ => return,
     };
 
+    // A different window might now be under the cursor.
+    if matches!(
+      platform_event,
+      PlatformEvent::WindowFocused(_)
+        | PlatformEvent::WindowLocationChanged(_)
+        | PlatformEvent::WindowShown(_)
+        | PlatformEvent::WindowHidden(_)
+        | PlatformEvent::WindowMinimized(_)
+        | PlatformEvent::WindowDestroyed(_)
+    ) {
+      clear_last_mouse_hit();
+    }
+
+    // Keyboard layouts are per-thread, so the focused window determines
+    // which layout keybindings are resolved for.
+    if event_type == EVENT_SYSTEM_FOREGROUND {
+      if let Some(keyboard_hook) = KEYBOARD_HOOK.get() {
+        keyboard_hook.update_keyboard_layout();
+      }
+    }
+
     if let Err(err) = self.event_tx.send(platform_event) {
       warn!("Failed to send platform event '{}'.", err);
     }
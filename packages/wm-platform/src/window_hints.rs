@@ -0,0 +1,326 @@
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::bail;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{COLORREF, HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::Gdi::{
+      BeginPaint, EndPaint, InvalidateRect, DT_CENTER, FW_BOLD, HDC,
+      PAINTSTRUCT,
+    },
+    UI::{
+      Input::KeyboardAndMouse::{VK_BACK, VK_ESCAPE},
+      WindowsAndMessaging::{
+        DefWindowProcW, GetClientRect, GetSystemMetrics, PostMessageW,
+        SetLayeredWindowAttributes, SetWindowPos, ShowWindow,
+        HWND_TOPMOST, LWA_COLORKEY, SM_CXVIRTUALSCREEN,
+        SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+        SWP_NOACTIVATE, SWP_SHOWWINDOW, SW_HIDE, WM_APP, WM_PAINT,
+        WNDCLASSW, WS_POPUP,
+      },
+    },
+  },
+};
+use wm_common::Rect;
+
+use crate::{
+  overlay::{
+    create_overlay_window, draw_text, fill_rect, rgb,
+    spawn_overlay_thread, TextStyle, CLICK_THROUGH_EX_STYLE,
+  },
+  KEYBOARD_HOOK,
+};
+
+/// Custom message for showing the hints in `HINTS`.
+const WM_SHOW_HINTS: u32 = WM_APP + 1;
+
+/// Custom message for a key press captured by the keyboard hook. The
+/// `wparam` is the virtual key code.
+const WM_HINT_KEY: u32 = WM_APP + 2;
+
+/// Background color that is made fully transparent, so that only the
+/// hint labels are visible. Not used anywhere else in the overlay.
+const TRANSPARENCY_KEY: COLORREF = COLORREF(0x00FF_00FF);
+
+/// Height of each hint label in pixels.
+const HINT_HEIGHT: i32 = 36;
+
+/// Width per character of a hint label in pixels.
+const HINT_CHAR_WIDTH: i32 = 20;
+
+/// Horizontal padding of each hint label in pixels.
+const HINT_PADDING: i32 = 12;
+
+/// Handle to the hints window. Created on first use.
+static HINTS_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// Hints that are currently shown, or `None` if the overlay is hidden.
+///
+/// For use with window procedure.
+static HINTS: Mutex<Option<HintsState>> = Mutex::new(None);
+
+/// Label shown over a window for jumping to it.
+#[derive(Clone, Debug)]
+pub struct WindowHint {
+  /// Characters to type for selecting the window (e.g. `as`).
+  pub label: String,
+
+  /// Rect of the window that the label is centered over.
+  pub rect: Rect,
+
+  /// WM command to run when the hint is selected.
+  pub command: String,
+}
+
+struct HintsState {
+  hints: Vec<WindowHint>,
+
+  /// Characters typed so far.
+  typed: String,
+
+  /// Sender for the command of the selected hint.
+  selection_tx: UnboundedSender<String>,
+}
+
+/// Shows a label over each of the given windows. Typing a label sends
+/// the command of its hint via `selection_tx`.
+///
+/// Key presses are captured by the keyboard hook while the hints are
+/// shown, so the foreground window keeps focus. Escape or a key that
+/// doesn't match any label dismisses the hints, and backspace removes
+/// the last typed character.
+pub(crate) fn show_window_hints(
+  hints: Vec<WindowHint>,
+  selection_tx: UnboundedSender<String>,
+) -> anyhow::Result<()> {
+  if KEYBOARD_HOOK.get().is_none() {
+    bail!("Keyboard hook is not running.");
+  }
+
+  let handle = *HINTS_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Window hints", create_hints_window)
+  })?;
+
+  *HINTS.lock().unwrap() = Some(HintsState {
+    hints,
+    typed: String::new(),
+    selection_tx,
+  });
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_SHOW_HINTS,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_hints_window() -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("WindowHintsWindow"),
+    lpfnWndProc: Some(hints_window_proc),
+    ..Default::default()
+  };
+
+  let handle =
+    create_overlay_window(&wnd_class, CLICK_THROUGH_EX_STYLE, WS_POPUP)?;
+
+  unsafe {
+    SetLayeredWindowAttributes(handle, TRANSPARENCY_KEY, 0, LWA_COLORKEY)
+  }?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the hints window.
+extern "system" fn hints_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_SHOW_HINTS => {
+      if let Err(err) = show_hints_window(handle) {
+        warn!("Failed to show window hints: {}", err);
+        hide_hints(handle);
+      }
+
+      LRESULT(0)
+    }
+    WM_HINT_KEY => {
+      #[allow(clippy::cast_possible_truncation)]
+      handle_hint_key(handle, wparam.0 as u16);
+      LRESULT(0)
+    }
+    WM_PAINT => {
+      paint_hints_window(handle);
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+/// Covers the virtual screen with the hints window and starts capturing
+/// key presses.
+fn show_hints_window(handle: HWND) -> anyhow::Result<()> {
+  unsafe {
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      GetSystemMetrics(SM_XVIRTUALSCREEN),
+      GetSystemMetrics(SM_YVIRTUALSCREEN),
+      GetSystemMetrics(SM_CXVIRTUALSCREEN),
+      GetSystemMetrics(SM_CYVIRTUALSCREEN),
+      SWP_NOACTIVATE | SWP_SHOWWINDOW,
+    )?;
+
+    InvalidateRect(handle, None, true);
+  };
+
+  if let Some(hook) = KEYBOARD_HOOK.get() {
+    hook.capture_keys(handle.0, WM_HINT_KEY);
+  }
+
+  Ok(())
+}
+
+/// Hides the hints window and stops capturing key presses.
+fn hide_hints(handle: HWND) {
+  if let Some(hook) = KEYBOARD_HOOK.get() {
+    hook.release_keys();
+  }
+
+  *HINTS.lock().unwrap() = None;
+  unsafe { ShowWindow(handle, SW_HIDE) };
+}
+
+/// Narrows down the shown hints by the typed key, and selects a hint
+/// once its full label has been typed.
+fn handle_hint_key(handle: HWND, vk_code: u16) {
+  let mut hints = HINTS.lock().unwrap();
+
+  let Some(state) = hints.as_mut() else {
+    return;
+  };
+
+  if vk_code == VK_ESCAPE.0 {
+    drop(hints);
+    hide_hints(handle);
+    return;
+  }
+
+  if vk_code == VK_BACK.0 {
+    state.typed.pop();
+    unsafe { InvalidateRect(handle, None, true) };
+    return;
+  }
+
+  // Letter and digit keys have the same virtual key code as their
+  // uppercase ASCII character.
+  let Some(key) = char::from_u32(vk_code.into())
+    .filter(char::is_ascii_alphanumeric)
+    .map(|key| key.to_ascii_lowercase())
+  else {
+    drop(hints);
+    hide_hints(handle);
+    return;
+  };
+
+  state.typed.push(key);
+
+  let selected = state
+    .hints
+    .iter()
+    .find(|hint| hint.label == state.typed)
+    .map(|hint| hint.command.clone());
+
+  if let Some(command) = selected {
+    let _ = state.selection_tx.send(command);
+    drop(hints);
+    hide_hints(handle);
+    return;
+  }
+
+  let has_matches = state
+    .hints
+    .iter()
+    .any(|hint| hint.label.starts_with(&state.typed));
+
+  drop(hints);
+
+  if has_matches {
+    unsafe { InvalidateRect(handle, None, true) };
+  } else {
+    hide_hints(handle);
+  }
+}
+
+fn paint_hints_window(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  let hdc = unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  let mut bounds = RECT::default();
+  let _ = unsafe { GetClientRect(handle, &raw mut bounds) };
+
+  fill_rect(hdc, &bounds, TRANSPARENCY_KEY);
+
+  if let Some(state) = HINTS.lock().unwrap().as_ref() {
+    // Window rects are in screen coordinates, whereas the window's
+    // client area starts at the top-left of the virtual screen.
+    let (origin_x, origin_y) = unsafe {
+      (
+        GetSystemMetrics(SM_XVIRTUALSCREEN),
+        GetSystemMetrics(SM_YVIRTUALSCREEN),
+      )
+    };
+
+    for hint in &state.hints {
+      if let Some(remaining) = hint.label.strip_prefix(&state.typed) {
+        let center = hint.rect.center_point();
+
+        #[allow(
+          clippy::cast_possible_truncation,
+          clippy::cast_possible_wrap
+        )]
+        let width =
+          HINT_CHAR_WIDTH * hint.label.len() as i32 + HINT_PADDING * 2;
+
+        let hint_bounds = RECT {
+          left: center.x - origin_x - width / 2,
+          top: center.y - origin_y - HINT_HEIGHT / 2,
+          right: center.x - origin_x + width / 2,
+          bottom: center.y - origin_y + HINT_HEIGHT / 2,
+        };
+
+        draw_hint(hdc, remaining, hint_bounds);
+      }
+    }
+  }
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
+
+/// Draws the remaining characters of a hint label in a box.
+fn draw_hint(hdc: HDC, label: &str, bounds: RECT) {
+  fill_rect(hdc, &bounds, rgb(0xf5, 0xc2, 0x42));
+
+  draw_text(
+    hdc,
+    &label.to_uppercase(),
+    bounds,
+    &TextStyle {
+      height: 26,
+      weight: FW_BOLD.0,
+      color: rgb(0x20, 0x20, 0x20),
+      alignment: DT_CENTER,
+    },
+  );
+}
@@ -0,0 +1,122 @@
+use std::sync::Mutex;
+
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{COLORREF, HWND, LPARAM, LRESULT, POINT, WPARAM},
+    Graphics::Gdi::{GetStockObject, BLACK_BRUSH, HBRUSH},
+    UI::{
+      Input::KeyboardAndMouse::VK_ESCAPE,
+      WindowsAndMessaging::{
+        DefWindowProcW, DestroyWindow, GetAncestor, GetCursorPos,
+        GetSystemMetrics, LoadCursorW, PostQuitMessage,
+        SetLayeredWindowAttributes, SetWindowPos, ShowWindow,
+        WindowFromPoint, GA_ROOT, HWND_TOPMOST, IDC_CROSS, LWA_ALPHA,
+        SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+        SM_YVIRTUALSCREEN, SWP_SHOWWINDOW, SW_HIDE, WM_DESTROY,
+        WM_KEYDOWN, WM_LBUTTONDOWN, WM_RBUTTONDOWN, WNDCLASSW,
+        WS_EX_LAYERED, WS_EX_TOPMOST, WS_POPUP,
+      },
+    },
+  },
+};
+
+use crate::{overlay::create_overlay_window, NativeWindow, Platform};
+
+/// Handle of the window that was clicked during the current pick.
+///
+/// For use with window procedure.
+static PICKED_WINDOW: Mutex<Option<isize>> = Mutex::new(None);
+
+/// Shows a crosshair cursor over all monitors and blocks until a window
+/// is clicked.
+///
+/// Returns `None` if the pick was cancelled via escape or right-click.
+pub(crate) fn pick_window() -> anyhow::Result<Option<NativeWindow>> {
+  *PICKED_WINDOW.lock().unwrap() = None;
+
+  let handle = create_picker_window()?;
+
+  // Needs to be in the foreground to receive the escape key.
+  let _ = NativeWindow::new(handle).set_foreground();
+
+  // Runs until the picker window is destroyed.
+  Platform::run_message_loop();
+
+  Ok(PICKED_WINDOW.lock().unwrap().take().map(NativeWindow::new))
+}
+
+/// Creates a nearly transparent window that covers the virtual screen,
+/// so that the click on the target window is captured rather than
+/// received by the window itself.
+fn create_picker_window() -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("WindowPickerWindow"),
+    lpfnWndProc: Some(picker_window_proc),
+    hCursor: unsafe { LoadCursorW(None, IDC_CROSS) }?,
+    hbrBackground: HBRUSH(unsafe { GetStockObject(BLACK_BRUSH) }.0),
+    ..Default::default()
+  };
+
+  let handle = create_overlay_window(
+    &wnd_class,
+    WS_EX_LAYERED | WS_EX_TOPMOST,
+    WS_POPUP,
+  )?;
+
+  // Fully transparent windows don't receive mouse input, so the lowest
+  // non-zero opacity is used instead.
+  unsafe {
+    SetLayeredWindowAttributes(handle, COLORREF(0), 1, LWA_ALPHA)?;
+
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      GetSystemMetrics(SM_XVIRTUALSCREEN),
+      GetSystemMetrics(SM_YVIRTUALSCREEN),
+      GetSystemMetrics(SM_CXVIRTUALSCREEN),
+      GetSystemMetrics(SM_CYVIRTUALSCREEN),
+      SWP_SHOWWINDOW,
+    )?;
+  }
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the window picker.
+extern "system" fn picker_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_LBUTTONDOWN => {
+      // Hide the picker so that the window underneath is found.
+      unsafe { ShowWindow(handle, SW_HIDE) };
+
+      let mut point = POINT::default();
+      if unsafe { GetCursorPos(&raw mut point) }.is_ok() {
+        let clicked =
+          unsafe { GetAncestor(WindowFromPoint(point), GA_ROOT) };
+        *PICKED_WINDOW.lock().unwrap() = Some(clicked.0);
+      }
+
+      let _ = unsafe { DestroyWindow(handle) };
+      LRESULT(0)
+    }
+    WM_RBUTTONDOWN => {
+      let _ = unsafe { DestroyWindow(handle) };
+      LRESULT(0)
+    }
+    WM_KEYDOWN if wparam.0 == usize::from(VK_ESCAPE.0) => {
+      let _ = unsafe { DestroyWindow(handle) };
+      LRESULT(0)
+    }
+    WM_DESTROY => {
+      unsafe { PostQuitMessage(0) };
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
@@ -0,0 +1,182 @@
+use tracing::warn;
+use windows::Win32::{
+  Foundation::HWND,
+  UI::WindowsAndMessaging::{
+    BeginDeferWindowPos, DeferWindowPos, EndDeferWindowPos, SetWindowPos,
+    SET_WINDOW_POS_FLAGS, SWP_ASYNCWINDOWPOS,
+  },
+};
+use wm_common::{HideMethod, Rect};
+
+use crate::NativeWindow;
+
+/// Window position change that is queued in a `WindowPosBatch`.
+#[derive(Clone, Debug)]
+struct QueuedPosition {
+  window: NativeWindow,
+  rect: Rect,
+  z_order: HWND,
+  flags: SET_WINDOW_POS_FLAGS,
+
+  /// Whether to set the position a second time after the batch is
+  /// applied. Used for windows with a pending DPI adjustment.
+  should_repeat: bool,
+
+  /// Whether to show or hide the window once it's been positioned.
+  is_visible: bool,
+  hide_method: HideMethod,
+}
+
+/// Collection of window position changes that are applied together in a
+/// single `BeginDeferWindowPos`/`EndDeferWindowPos` transaction.
+///
+/// Applying the changes at once avoids windows visibly cascading into
+/// place one after another when many windows reflow.
+#[derive(Debug, Default)]
+pub struct WindowPosBatch {
+  positions: Vec<QueuedPosition>,
+}
+
+impl WindowPosBatch {
+  #[must_use]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Number of queued position changes.
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.positions.len()
+  }
+
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.positions.is_empty()
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  pub(crate) fn push(
+    &mut self,
+    window: &NativeWindow,
+    rect: &Rect,
+    z_order: HWND,
+    flags: SET_WINDOW_POS_FLAGS,
+    should_repeat: bool,
+    is_visible: bool,
+    hide_method: &HideMethod,
+  ) {
+    self.positions.push(QueuedPosition {
+      window: window.clone(),
+      rect: rect.clone(),
+      z_order,
+      flags,
+      should_repeat,
+      is_visible,
+      hide_method: hide_method.clone(),
+    });
+  }
+
+  /// Applies all queued position changes, and then shows or hides the
+  /// windows.
+  ///
+  /// Windows that are not responding are positioned asynchronously
+  /// outside of the transaction, since `EndDeferWindowPos` would
+  /// otherwise block until they respond. If the transaction fails, the
+  /// positions are instead set one window at a time.
+  pub fn apply(self) {
+    let (hung_positions, positions): (Vec<_>, Vec<_>) = self
+      .positions
+      .into_iter()
+      .partition(|position| position.window.is_hung());
+
+    Self::apply_deferred(&positions);
+
+    // When there's a mismatch between the DPI of the monitor and the
+    // window, the window might be sized incorrectly after the first
+    // move. If we set the position twice, inconsistencies after the
+    // first move are resolved.
+    let repeat_positions = positions
+      .iter()
+      .filter(|position| position.should_repeat)
+      .cloned()
+      .collect::<Vec<_>>();
+
+    Self::apply_deferred(&repeat_positions);
+
+    for position in &hung_positions {
+      Self::apply_single(position);
+    }
+
+    for position in positions.iter().chain(&hung_positions) {
+      if let Err(err) = position
+        .window
+        .set_visible(position.is_visible, &position.hide_method)
+      {
+        warn!("Failed to set window visibility: {}", err);
+      }
+    }
+  }
+
+  /// Sets the given positions in a single transaction. Falls back to
+  /// setting them individually if the transaction fails.
+  fn apply_deferred(positions: &[QueuedPosition]) {
+    if positions.is_empty() {
+      return;
+    }
+
+    let res = (|| -> anyhow::Result<()> {
+      let mut hdwp =
+        unsafe { BeginDeferWindowPos(i32::try_from(positions.len())?) }?;
+
+      // If `DeferWindowPos` fails, the system has already freed the
+      // handle, so `EndDeferWindowPos` shouldn't be called.
+      for position in positions {
+        hdwp = unsafe {
+          DeferWindowPos(
+            hdwp,
+            HWND(position.window.handle),
+            position.z_order,
+            position.rect.x(),
+            position.rect.y(),
+            position.rect.width(),
+            position.rect.height(),
+            position.flags,
+          )
+        }?;
+      }
+
+      unsafe { EndDeferWindowPos(hdwp) }?;
+      Ok(())
+    })();
+
+    if let Err(err) = res {
+      warn!(
+        "Failed to batch window positions, falling back to individual \
+         updates: {}",
+        err
+      );
+
+      for position in positions {
+        Self::apply_single(position);
+      }
+    }
+  }
+
+  fn apply_single(position: &QueuedPosition) {
+    let res = unsafe {
+      SetWindowPos(
+        HWND(position.window.handle),
+        position.z_order,
+        position.rect.x(),
+        position.rect.y(),
+        position.rect.width(),
+        position.rect.height(),
+        position.flags | SWP_ASYNCWINDOWPOS,
+      )
+    };
+
+    if let Err(err) = res {
+      warn!("Failed to set window position: {}", err);
+    }
+  }
+}
@@ -0,0 +1,460 @@
+use std::sync::{
+  atomic::{AtomicBool, Ordering},
+  Mutex, OnceLock,
+};
+
+use anyhow::{bail, Context};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+use windows::{
+  core::w,
+  Win32::{
+    Foundation::{HWND, LPARAM, LRESULT, RECT, WPARAM},
+    Graphics::{
+      Dwm::{
+        DwmQueryThumbnailSourceSize, DwmRegisterThumbnail,
+        DwmUnregisterThumbnail, DwmUpdateThumbnailProperties,
+        DWM_THUMBNAIL_PROPERTIES, DWM_TNP_RECTDESTINATION,
+        DWM_TNP_VISIBLE,
+      },
+      Gdi::{
+        BeginPaint, EndPaint, GetMonitorInfoW, InvalidateRect,
+        MonitorFromWindow, DT_CENTER, FW_NORMAL, MONITORINFO,
+        MONITOR_DEFAULTTOPRIMARY, PAINTSTRUCT,
+      },
+    },
+    UI::{
+      Input::KeyboardAndMouse::{
+        GetKeyState, VK_CONTROL, VK_DOWN, VK_ESCAPE, VK_LCONTROL, VK_LEFT,
+        VK_LMENU, VK_LWIN, VK_MENU, VK_RCONTROL, VK_RETURN, VK_RIGHT,
+        VK_RMENU, VK_RWIN, VK_SHIFT, VK_TAB, VK_UP,
+      },
+      WindowsAndMessaging::{
+        DefWindowProcW, GetForegroundWindow, PostMessageW, SetWindowPos,
+        ShowWindow, HWND_TOPMOST, SWP_SHOWWINDOW, SW_HIDE, WA_INACTIVE,
+        WM_ACTIVATE, WM_APP, WM_KEYDOWN, WM_KEYUP, WM_PAINT,
+        WM_SYSKEYDOWN, WM_SYSKEYUP, WNDCLASSW, WS_EX_TOPMOST, WS_POPUP,
+      },
+    },
+  },
+};
+
+use crate::{
+  overlay::{
+    create_overlay_window, draw_text, fill_rect, rgb,
+    spawn_overlay_thread, TextStyle,
+  },
+  NativeWindow,
+};
+
+/// Custom message for showing the window switcher.
+const WM_SHOW_SWITCHER: u32 = WM_APP + 1;
+
+/// Custom message for selecting the next window while the switcher is
+/// shown.
+const WM_SELECT_NEXT: u32 = WM_APP + 2;
+
+/// Width and height of the thumbnail of each window in pixels.
+const THUMBNAIL_WIDTH: i32 = 220;
+const THUMBNAIL_HEIGHT: i32 = 140;
+
+/// Height of the window title below each thumbnail in pixels.
+const TITLE_HEIGHT: i32 = 24;
+
+/// Padding around and between tiles in pixels.
+const SWITCHER_PADDING: i32 = 12;
+
+/// Handle to the window switcher window. Created on first use.
+static SWITCHER_WINDOW: OnceLock<isize> = OnceLock::new();
+
+/// Whether the window switcher is currently shown.
+static IS_SWITCHER_SHOWN: AtomicBool = AtomicBool::new(false);
+
+/// State of the window switcher while it's shown.
+///
+/// For use with window procedure.
+static SWITCHER: Mutex<Option<SwitcherState>> = Mutex::new(None);
+
+/// Window that can be selected in the window switcher.
+#[derive(Clone, Debug)]
+pub struct WindowSwitcherEntry {
+  pub window: NativeWindow,
+  pub title: String,
+
+  /// Command that is sent when the window is selected.
+  pub command: String,
+}
+
+struct SwitcherState {
+  entries: Vec<WindowSwitcherEntry>,
+
+  /// Index of the highlighted entry.
+  selected: usize,
+
+  /// Registered DWM thumbnails, in the same order as `entries`.
+  thumbnails: Vec<Option<isize>>,
+
+  /// Bounds of each tile, in the same order as `entries`.
+  tile_rects: Vec<RECT>,
+
+  /// Sender for the command of the selected entry.
+  selection_tx: UnboundedSender<String>,
+}
+
+/// Shows the window switcher with the given windows, most recently used
+/// first. If the switcher is already shown, the next window is selected
+/// instead.
+///
+/// The switcher is confirmed when the modifier key of the invoking
+/// keybinding (e.g. `alt`) is released, or on enter.
+pub(crate) fn show_window_switcher(
+  entries: Vec<WindowSwitcherEntry>,
+  selection_tx: UnboundedSender<String>,
+) -> anyhow::Result<()> {
+  let handle = *SWITCHER_WINDOW.get_or_try_init(|| {
+    spawn_overlay_thread("Window switcher", create_switcher_window)
+  })?;
+
+  if IS_SWITCHER_SHOWN.load(Ordering::Relaxed) {
+    unsafe {
+      PostMessageW(
+        HWND(handle),
+        WM_SELECT_NEXT,
+        WPARAM::default(),
+        LPARAM::default(),
+      )
+    }?;
+
+    return Ok(());
+  }
+
+  if entries.is_empty() {
+    return Ok(());
+  }
+
+  // Preselect the previously focused window, as with alt-tab.
+  let selected = usize::from(entries.len() > 1);
+
+  *SWITCHER.lock().unwrap() = Some(SwitcherState {
+    entries,
+    selected,
+    thumbnails: Vec::new(),
+    tile_rects: Vec::new(),
+    selection_tx,
+  });
+
+  IS_SWITCHER_SHOWN.store(true, Ordering::Relaxed);
+
+  unsafe {
+    PostMessageW(
+      HWND(handle),
+      WM_SHOW_SWITCHER,
+      WPARAM::default(),
+      LPARAM::default(),
+    )
+  }?;
+
+  Ok(())
+}
+
+fn create_switcher_window() -> anyhow::Result<isize> {
+  let wnd_class = WNDCLASSW {
+    lpszClassName: w!("WindowSwitcherWindow"),
+    lpfnWndProc: Some(switcher_window_proc),
+    ..Default::default()
+  };
+
+  // Window isn't layered, since DWM thumbnails aren't drawn on layered
+  // windows.
+  let handle = create_overlay_window(&wnd_class, WS_EX_TOPMOST, WS_POPUP)?;
+
+  Ok(handle.0)
+}
+
+/// Window procedure for the window switcher window.
+extern "system" fn switcher_window_proc(
+  handle: HWND,
+  message: u32,
+  wparam: WPARAM,
+  lparam: LPARAM,
+) -> LRESULT {
+  match message {
+    WM_SHOW_SWITCHER => {
+      if let Err(err) = layout_switcher_window(handle) {
+        warn!("Failed to show window switcher: {}", err);
+        close_switcher(handle, false);
+      }
+
+      LRESULT(0)
+    }
+    WM_SELECT_NEXT => {
+      move_selection(handle, true);
+      LRESULT(0)
+    }
+    WM_ACTIVATE if wparam.0 & 0xffff == WA_INACTIVE as usize => {
+      close_switcher(handle, false);
+      LRESULT(0)
+    }
+    WM_KEYDOWN | WM_SYSKEYDOWN => {
+      #[allow(clippy::cast_possible_truncation)]
+      let key = wparam.0 as u16;
+
+      if key == VK_TAB.0 {
+        let is_shift_down =
+          unsafe { GetKeyState(i32::from(VK_SHIFT.0)) } < 0;
+        move_selection(handle, !is_shift_down);
+      } else if key == VK_RIGHT.0 || key == VK_DOWN.0 {
+        move_selection(handle, true);
+      } else if key == VK_LEFT.0 || key == VK_UP.0 {
+        move_selection(handle, false);
+      } else if key == VK_RETURN.0 {
+        close_switcher(handle, true);
+      } else if key == VK_ESCAPE.0 {
+        close_switcher(handle, false);
+      }
+
+      LRESULT(0)
+    }
+    // Confirm the selection when the modifier of the invoking keybinding
+    // is released.
+    WM_KEYUP | WM_SYSKEYUP => {
+      #[allow(clippy::cast_possible_truncation)]
+      let key = wparam.0 as u16;
+
+      let is_modifier = [
+        VK_MENU,
+        VK_LMENU,
+        VK_RMENU,
+        VK_CONTROL,
+        VK_LCONTROL,
+        VK_RCONTROL,
+        VK_LWIN,
+        VK_RWIN,
+      ]
+      .iter()
+      .any(|modifier| modifier.0 == key);
+
+      if is_modifier {
+        close_switcher(handle, true);
+      }
+
+      LRESULT(0)
+    }
+    WM_PAINT => {
+      paint_switcher_window(handle);
+      LRESULT(0)
+    }
+    _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
+  }
+}
+
+fn move_selection(handle: HWND, is_forward: bool) {
+  if let Some(state) = SWITCHER.lock().unwrap().as_mut() {
+    let count = state.entries.len();
+
+    state.selected = if is_forward {
+      (state.selected + 1) % count
+    } else {
+      (state.selected + count - 1) % count
+    };
+  }
+
+  unsafe { InvalidateRect(handle, None, true) };
+}
+
+/// Hides the switcher and unregisters its thumbnails. Sends the command
+/// of the selected entry if `should_confirm` is `true`.
+fn close_switcher(handle: HWND, should_confirm: bool) {
+  if let Some(state) = SWITCHER.lock().unwrap().take() {
+    for thumbnail in state.thumbnails.into_iter().flatten() {
+      let _ = unsafe { DwmUnregisterThumbnail(thumbnail) };
+    }
+
+    if should_confirm {
+      if let Some(entry) = state.entries.get(state.selected) {
+        let _ = state.selection_tx.send(entry.command.clone());
+      }
+    }
+  }
+
+  IS_SWITCHER_SHOWN.store(false, Ordering::Relaxed);
+  unsafe { ShowWindow(handle, SW_HIDE) };
+}
+
+/// Arranges the tiles in rows centered on the foreground window's
+/// monitor, registers a thumbnail for each window, and brings the
+/// switcher to the foreground.
+fn layout_switcher_window(handle: HWND) -> anyhow::Result<()> {
+  let monitor = unsafe {
+    MonitorFromWindow(GetForegroundWindow(), MONITOR_DEFAULTTOPRIMARY)
+  };
+
+  let mut monitor_info = MONITORINFO {
+    #[allow(clippy::cast_possible_truncation)]
+    cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+    ..Default::default()
+  };
+
+  if !unsafe { GetMonitorInfoW(monitor, &raw mut monitor_info) }.as_bool()
+  {
+    bail!("Failed to get monitor info.");
+  }
+
+  let work_area = monitor_info.rcWork;
+
+  let mut switcher = SWITCHER.lock().unwrap();
+  let state = switcher.as_mut().context("Window switcher not shown.")?;
+
+  let tile_width = THUMBNAIL_WIDTH + SWITCHER_PADDING;
+  let tile_height = THUMBNAIL_HEIGHT + TITLE_HEIGHT + SWITCHER_PADDING;
+
+  // Fit as many tiles per row as the monitor allows.
+  let max_columns = ((work_area.right - work_area.left) * 9 / 10
+    - SWITCHER_PADDING)
+    / tile_width;
+
+  let entry_count = i32::try_from(state.entries.len())?;
+  let columns = entry_count.clamp(1, max_columns.max(1));
+  let rows = (entry_count + columns - 1) / columns;
+
+  let width = columns * tile_width + SWITCHER_PADDING;
+  let height = rows * tile_height + SWITCHER_PADDING;
+
+  state.tile_rects = (0..entry_count)
+    .map(|index| {
+      let left = SWITCHER_PADDING + (index % columns) * tile_width;
+      let top = SWITCHER_PADDING + (index / columns) * tile_height;
+
+      RECT {
+        left,
+        top,
+        right: left + THUMBNAIL_WIDTH,
+        bottom: top + THUMBNAIL_HEIGHT + TITLE_HEIGHT,
+      }
+    })
+    .collect();
+
+  state.thumbnails = state
+    .entries
+    .iter()
+    .zip(&state.tile_rects)
+    .map(|(entry, tile_rect)| {
+      register_thumbnail(handle, &entry.window, tile_rect)
+        .inspect_err(|err| warn!("Failed to register thumbnail: {}", err))
+        .ok()
+    })
+    .collect();
+
+  drop(switcher);
+
+  unsafe {
+    SetWindowPos(
+      handle,
+      HWND_TOPMOST,
+      work_area.left + (work_area.right - work_area.left - width) / 2,
+      work_area.top + (work_area.bottom - work_area.top - height) / 2,
+      width,
+      height,
+      SWP_SHOWWINDOW,
+    )?;
+
+    InvalidateRect(handle, None, true);
+  };
+
+  NativeWindow::new(handle.0).set_foreground()
+}
+
+/// Registers a DWM thumbnail of the window, scaled to fit the thumbnail
+/// area of the tile while preserving its aspect ratio.
+fn register_thumbnail(
+  handle: HWND,
+  window: &NativeWindow,
+  tile_rect: &RECT,
+) -> anyhow::Result<isize> {
+  let thumbnail =
+    unsafe { DwmRegisterThumbnail(handle, HWND(window.handle)) }?;
+
+  let source_size =
+    unsafe { DwmQueryThumbnailSourceSize(thumbnail) }.unwrap_or_default();
+
+  #[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_lossless
+  )]
+  let (width, height) = if source_size.cx > 0 && source_size.cy > 0 {
+    let scale = f64::min(
+      f64::from(THUMBNAIL_WIDTH) / f64::from(source_size.cx),
+      f64::from(THUMBNAIL_HEIGHT) / f64::from(source_size.cy),
+    );
+
+    (
+      (f64::from(source_size.cx) * scale) as i32,
+      (f64::from(source_size.cy) * scale) as i32,
+    )
+  } else {
+    (THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT)
+  };
+
+  let left = tile_rect.left + (THUMBNAIL_WIDTH - width) / 2;
+  let top = tile_rect.top + (THUMBNAIL_HEIGHT - height) / 2;
+
+  let properties = DWM_THUMBNAIL_PROPERTIES {
+    dwFlags: DWM_TNP_RECTDESTINATION | DWM_TNP_VISIBLE,
+    rcDestination: RECT {
+      left,
+      top,
+      right: left + width,
+      bottom: top + height,
+    },
+    fVisible: true.into(),
+    ..Default::default()
+  };
+
+  unsafe {
+    DwmUpdateThumbnailProperties(thumbnail, &raw const properties)
+  }?;
+
+  Ok(thumbnail)
+}
+
+fn paint_switcher_window(handle: HWND) {
+  let mut paint_struct = PAINTSTRUCT::default();
+  let hdc = unsafe { BeginPaint(handle, &raw mut paint_struct) };
+
+  if let Some(state) = SWITCHER.lock().unwrap().as_ref() {
+    fill_rect(hdc, &paint_struct.rcPaint, rgb(0x20, 0x20, 0x20));
+
+    for (index, (entry, tile_rect)) in
+      state.entries.iter().zip(&state.tile_rects).enumerate()
+    {
+      if index == state.selected {
+        let highlight_rect = RECT {
+          left: tile_rect.left - SWITCHER_PADDING / 2,
+          top: tile_rect.top - SWITCHER_PADDING / 2,
+          right: tile_rect.right + SWITCHER_PADDING / 2,
+          bottom: tile_rect.bottom + SWITCHER_PADDING / 2,
+        };
+
+        fill_rect(hdc, &highlight_rect, rgb(0x42, 0x8b, 0xf5));
+      }
+
+      draw_text(
+        hdc,
+        &entry.title,
+        RECT {
+          top: tile_rect.bottom - TITLE_HEIGHT,
+          ..*tile_rect
+        },
+        &TextStyle {
+          height: 17,
+          weight: FW_NORMAL.0,
+          color: rgb(0xf0, 0xf0, 0xf0),
+          alignment: DT_CENTER,
+        },
+      );
+    }
+  }
+
+  unsafe { EndPaint(handle, &raw const paint_struct) };
+}
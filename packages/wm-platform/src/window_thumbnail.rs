@@ -0,0 +1,208 @@
+use anyhow::{bail, Context};
+use windows::Win32::{
+  Foundation::{HWND, RECT},
+  Graphics::Gdi::{
+    CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, GetDC,
+    ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB,
+    DIB_RGB_COLORS,
+  },
+  Storage::Xps::{PrintWindow, PRINT_WINDOW_FLAGS},
+  UI::WindowsAndMessaging::{GetWindowRect, PW_RENDERFULLCONTENT},
+};
+
+use crate::NativeWindow;
+
+/// Snapshot of a window's contents.
+#[derive(Clone, Debug)]
+pub struct WindowThumbnail {
+  /// Width of the thumbnail in pixels.
+  pub width: u32,
+
+  /// Height of the thumbnail in pixels.
+  pub height: u32,
+
+  /// Thumbnail encoded as a PNG image.
+  pub png: Vec<u8>,
+}
+
+/// Captures the contents of a window, downscaled so that neither side
+/// exceeds `max_size` pixels.
+///
+/// Windows that are cloaked or moved offscreen are still rendered, and
+/// can therefore be captured while their workspace isn't displayed.
+/// Windows that are hidden or minimized might come out blank.
+pub(crate) fn capture_window_thumbnail(
+  window: &NativeWindow,
+  max_size: u32,
+) -> anyhow::Result<WindowThumbnail> {
+  // Capturing sends a paint message to the window, which would block
+  // until a hung window recovers.
+  if window.is_hung() {
+    bail!("Window is not responding.");
+  }
+
+  let handle = HWND(window.handle);
+  let mut rect = RECT::default();
+  unsafe { GetWindowRect(handle, &raw mut rect) }?;
+
+  let width = u32::try_from(rect.right - rect.left).unwrap_or(0);
+  let height = u32::try_from(rect.bottom - rect.top).unwrap_or(0);
+
+  if width == 0 || height == 0 {
+    bail!("Window has no size.");
+  }
+
+  let pixels = capture_pixels(handle, width, height)?;
+
+  // Downscale by the same factor on both sides to keep the aspect ratio.
+  let scale =
+    (f64::from(max_size.max(1)) / f64::from(width.max(height))).min(1.);
+
+  #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+  let (thumbnail_width, thumbnail_height) = (
+    ((f64::from(width) * scale).round() as u32).max(1),
+    ((f64::from(height) * scale).round() as u32).max(1),
+  );
+
+  let rgb = downscale_to_rgb(
+    &pixels,
+    (width, height),
+    (thumbnail_width, thumbnail_height),
+  );
+
+  Ok(WindowThumbnail {
+    width: thumbnail_width,
+    height: thumbnail_height,
+    png: encode_png(&rgb, thumbnail_width, thumbnail_height)?,
+  })
+}
+
+/// Renders the window into a 32-bit BGRA bitmap of the given size.
+fn capture_pixels(
+  handle: HWND,
+  width: u32,
+  height: u32,
+) -> anyhow::Result<Vec<u8>> {
+  let bitmap_info = BITMAPINFO {
+    bmiHeader: BITMAPINFOHEADER {
+      #[allow(clippy::cast_possible_truncation)]
+      biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+      biWidth: i32::try_from(width)?,
+      // Negative height for a top-down bitmap.
+      biHeight: -i32::try_from(height)?,
+      biPlanes: 1,
+      biBitCount: 32,
+      biCompression: BI_RGB.0,
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let screen_dc = unsafe { GetDC(None) };
+  let memory_dc = unsafe { CreateCompatibleDC(screen_dc) };
+  let mut bits = std::ptr::null_mut();
+
+  let result = unsafe {
+    CreateDIBSection(
+      memory_dc,
+      &raw const bitmap_info,
+      DIB_RGB_COLORS,
+      &raw mut bits,
+      None,
+      0,
+    )
+  }
+  .context("Failed to create bitmap.")
+  .and_then(|bitmap| {
+    let prev_bitmap = unsafe { SelectObject(memory_dc, bitmap) };
+
+    let is_printed = unsafe {
+      PrintWindow(
+        handle,
+        memory_dc,
+        PRINT_WINDOW_FLAGS(PW_RENDERFULLCONTENT),
+      )
+    }
+    .as_bool();
+
+    let pixels = is_printed.then(|| {
+      let length = width as usize * height as usize * 4;
+      unsafe { std::slice::from_raw_parts(bits.cast::<u8>(), length) }
+        .to_vec()
+    });
+
+    unsafe {
+      SelectObject(memory_dc, prev_bitmap);
+      DeleteObject(bitmap);
+    }
+
+    pixels.context("Failed to capture window contents.")
+  });
+
+  unsafe {
+    DeleteDC(memory_dc);
+    ReleaseDC(None, screen_dc);
+  }
+
+  result
+}
+
+/// Downscales a BGRA bitmap by averaging the source pixels that each
+/// target pixel covers. The alpha channel is dropped, since it's not
+/// reliably set by windows.
+fn downscale_to_rgb(
+  pixels: &[u8],
+  (width, height): (u32, u32),
+  (target_width, target_height): (u32, u32),
+) -> Vec<u8> {
+  let mut rgb =
+    Vec::with_capacity(target_width as usize * target_height as usize * 3);
+
+  for target_y in 0..target_height {
+    let start_y = target_y * height / target_height;
+    let end_y = ((target_y + 1) * height / target_height).max(start_y + 1);
+
+    for target_x in 0..target_width {
+      let start_x = target_x * width / target_width;
+      let end_x = ((target_x + 1) * width / target_width).max(start_x + 1);
+
+      let mut sums = [0u64; 3];
+
+      for y in start_y..end_y {
+        for x in start_x..end_x {
+          let index = (y as usize * width as usize + x as usize) * 4;
+
+          // Pixels are stored as BGRA.
+          sums[0] += u64::from(pixels[index + 2]);
+          sums[1] += u64::from(pixels[index + 1]);
+          sums[2] += u64::from(pixels[index]);
+        }
+      }
+
+      let count = u64::from((end_y - start_y) * (end_x - start_x));
+
+      #[allow(clippy::cast_possible_truncation)]
+      rgb.extend(sums.map(|sum| (sum / count) as u8));
+    }
+  }
+
+  rgb
+}
+
+fn encode_png(
+  rgb: &[u8],
+  width: u32,
+  height: u32,
+) -> anyhow::Result<Vec<u8>> {
+  let mut png = Vec::new();
+
+  let mut encoder = png::Encoder::new(&mut png, width, height);
+  encoder.set_color(png::ColorType::Rgb);
+  encoder.set_depth(png::BitDepth::Eight);
+
+  let mut writer = encoder.write_header()?;
+  writer.write_image_data(rgb)?;
+  writer.finish()?;
+
+  Ok(png)
+}
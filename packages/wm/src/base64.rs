@@ -0,0 +1,26 @@
+const ALPHABET: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes as standard base64 (with padding).
+pub fn to_base64(bytes: &[u8]) -> String {
+  let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+  for chunk in bytes.chunks(3) {
+    let block = (u32::from(chunk[0]) << 16)
+      | (u32::from(chunk.get(1).copied().unwrap_or(0)) << 8)
+      | u32::from(chunk.get(2).copied().unwrap_or(0));
+
+    // A chunk of N bytes is encoded as N + 1 characters, and then padded
+    // to 4 characters.
+    for index in 0..4 {
+      if index <= chunk.len() {
+        let sextet = (block >> (18 - index * 6)) & 0x3f;
+        output.push(char::from(ALPHABET[sextet as usize]));
+      } else {
+        output.push('=');
+      }
+    }
+  }
+
+  output
+}
@@ -0,0 +1,71 @@
+use anyhow::Context;
+use wm_common::{LengthValue, TilingDirection};
+
+use super::resize_tiling_container;
+use crate::{
+  models::TilingContainer,
+  traits::{
+    CommonGetters, PositionGetters, TilingDirectionGetters,
+    TilingSizeGetters,
+  },
+  wm_state::WmState,
+};
+
+/// How close the container's share has to be to a ratio for it to be
+/// considered the current ratio.
+const RATIO_TOLERANCE: f32 = 0.01;
+
+/// Cycles the share of a tiling container within its parent through the
+/// given ratios, along the parent's tiling direction.
+///
+/// Moves on to the ratio after the one that the container is currently
+/// at, and starts from the first ratio if it's at none of them.
+pub fn cycle_ratio(
+  container: &TilingContainer,
+  ratios: &[LengthValue],
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  if ratios.is_empty() || container.tiling_siblings().count() == 0 {
+    return Ok(());
+  }
+
+  let parent = container
+    .parent()
+    .and_then(|parent| parent.direction_container())
+    .context("No parent.")?;
+
+  let (horizontal_gap, vertical_gap) = container.inner_gaps()?;
+  let parent_rect = parent.to_rect()?;
+
+  #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+  let parent_length = match parent.tiling_direction() {
+    TilingDirection::Horizontal => {
+      parent_rect.width()
+        - horizontal_gap * container.tiling_siblings().count() as i32
+    }
+    TilingDirection::Vertical => {
+      parent_rect.height()
+        - vertical_gap * container.tiling_siblings().count() as i32
+    }
+  };
+
+  let tiling_sizes = ratios
+    .iter()
+    .map(|ratio| ratio.to_percentage(parent_length))
+    .collect::<Vec<_>>();
+
+  let current_index = tiling_sizes.iter().position(|tiling_size| {
+    (tiling_size - container.tiling_size()).abs() < RATIO_TOLERANCE
+  });
+
+  let next_index =
+    current_index.map_or(0, |index| (index + 1) % tiling_sizes.len());
+
+  resize_tiling_container(container, tiling_sizes[next_index]);
+
+  state
+    .pending_sync
+    .queue_containers_to_redraw(parent.tiling_children());
+
+  Ok(())
+}
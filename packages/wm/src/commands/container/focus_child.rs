@@ -0,0 +1,33 @@
+use super::{focus_parent::set_focused_split, set_focused_descendant};
+use crate::{models::Container, traits::CommonGetters, wm_state::WmState};
+
+/// Moves focus from a split container to its most recently focused child.
+/// This reverses `focus --parent`.
+///
+/// Has no effect if the given container isn't a split container.
+pub fn focus_child(
+  origin_container: &Container,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  if !matches!(origin_container, Container::Split(_)) {
+    return Ok(());
+  }
+
+  let Some(child) = origin_container.child_focus_order().next() else {
+    return Ok(());
+  };
+
+  if matches!(child, Container::Split(_)) {
+    return set_focused_split(&child, state);
+  }
+
+  set_focused_descendant(&child, None);
+  state.split_focus = None;
+
+  state
+    .pending_sync
+    .queue_focus_change()
+    .queue_focused_effect_update();
+
+  Ok(())
+}
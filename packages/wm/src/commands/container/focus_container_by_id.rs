@@ -1,8 +1,8 @@
 use anyhow::Context;
 use uuid::Uuid;
 
-use super::set_focused_descendant;
-use crate::wm_state::WmState;
+use super::{focus_parent::set_focused_split, set_focused_descendant};
+use crate::{models::Container, wm_state::WmState};
 
 pub fn focus_container_by_id(
   container_id: &Uuid,
@@ -12,6 +12,13 @@ pub fn focus_container_by_id(
     .container_by_id(*container_id)
     .context("No container with given id")?;
 
+  // Split containers are focused as a whole.
+  if matches!(focus_target, Container::Split(_)) {
+    set_focused_split(&focus_target, state)?;
+    state.pending_sync.queue_cursor_jump();
+    return Ok(());
+  }
+
   // Set focus to the target container.
   set_focused_descendant(&focus_target, None);
   state.pending_sync.queue_focus_change().queue_cursor_jump();
@@ -1,10 +1,11 @@
 use anyhow::Context;
-use wm_common::{Direction, TilingDirection, WindowState};
+use wm_common::{Direction, FocusWrapping, TilingDirection, WindowState};
 
 use super::set_focused_descendant;
 use crate::{
-  models::{Container, TilingContainer},
+  models::{Container, Monitor, TilingContainer},
   traits::{CommonGetters, TilingDirectionGetters, WindowGetters},
+  user_config::UserConfig,
   wm_state::WmState,
 };
 
@@ -12,13 +13,21 @@ pub fn focus_in_direction(
   origin_container: &Container,
   direction: &Direction,
   state: &mut WmState,
+  config: &UserConfig,
 ) -> anyhow::Result<()> {
   let focus_target = match origin_container {
-    Container::TilingWindow(_) => {
+    Container::TilingWindow(_) | Container::Split(_) => {
       // If a suitable focus target isn't found in the current workspace,
       // attempt to find a workspace in the given direction.
       tiling_focus_target(origin_container, direction)?.map_or_else(
-        || workspace_focus_target(origin_container, direction, state),
+        || {
+          workspace_focus_target(
+            origin_container,
+            direction,
+            state,
+            config,
+          )
+        },
         |container| Ok(Some(container)),
       )?
     }
@@ -27,14 +36,17 @@ pub fn focus_in_direction(
         WindowState::Floating(_) => {
           floating_focus_target(origin_container, direction)
         }
-        WindowState::Fullscreen(_) => {
-          workspace_focus_target(origin_container, direction, state)?
-        }
+        WindowState::Fullscreen(_) => workspace_focus_target(
+          origin_container,
+          direction,
+          state,
+          config,
+        )?,
         _ => None,
       }
     }
     Container::Workspace(_) => {
-      workspace_focus_target(origin_container, direction, state)?
+      workspace_focus_target(origin_container, direction, state, config)?
     }
     _ => None,
   };
@@ -52,9 +64,12 @@ fn floating_focus_target(
   origin_container: &Container,
   direction: &Direction,
 ) -> Option<Container> {
+  // Docked windows are skipped, since they're only focused when
+  // targeted explicitly.
   let is_floating = |sibling: &Container| {
     sibling.as_non_tiling_window().is_some_and(|window| {
       matches!(window.state(), WindowState::Floating(_))
+        && !window.is_docked()
     })
   };
 
@@ -127,7 +142,8 @@ fn tiling_focus_target(
 }
 
 /// Gets a focus target outside of the current workspace in the given
-/// direction.
+/// direction. If there's no monitor in the given direction, the focus
+/// target depends on the `focus_wrapping` config.
 ///
 /// This will descend into the workspace in the given direction, and will
 /// always return a tiling container. This makes it different from the
@@ -136,12 +152,18 @@ fn workspace_focus_target(
   origin_container: &Container,
   direction: &Direction,
   state: &WmState,
+  config: &UserConfig,
 ) -> anyhow::Result<Option<Container>> {
   let monitor = origin_container.monitor().context("No monitor.")?;
 
-  let target_workspace = state
-    .monitor_in_direction(&monitor, direction)?
-    .and_then(|monitor| monitor.displayed_workspace());
+  let target_monitor =
+    match state.monitor_in_direction(&monitor, direction)? {
+      Some(target_monitor) => Some(target_monitor),
+      None => wrapping_target_monitor(&monitor, direction, state, config)?,
+    };
+
+  let target_workspace =
+    target_monitor.and_then(|monitor| monitor.displayed_workspace());
 
   let focused_fullscreen = target_workspace
     .as_ref()
@@ -165,3 +187,27 @@ fn workspace_focus_target(
 
   Ok(focus_target)
 }
+
+/// Gets the monitor to wrap around to based on the `focus_wrapping`
+/// config.
+///
+/// Wrapping within the workspace is the same as wrapping to the origin
+/// monitor, since focus then descends into the opposite side of its
+/// displayed workspace.
+fn wrapping_target_monitor(
+  origin_monitor: &Monitor,
+  direction: &Direction,
+  state: &WmState,
+  config: &UserConfig,
+) -> anyhow::Result<Option<Monitor>> {
+  match config.value.general.focus_wrapping {
+    FocusWrapping::None => Ok(None),
+    FocusWrapping::Workspace => Ok(Some(origin_monitor.clone())),
+    FocusWrapping::Monitor => {
+      state.wrapping_monitor_in_direction(origin_monitor, direction, false)
+    }
+    FocusWrapping::Global => {
+      state.wrapping_monitor_in_direction(origin_monitor, direction, true)
+    }
+  }
+}
@@ -0,0 +1,49 @@
+use anyhow::Context;
+
+use super::set_focused_descendant;
+use crate::{models::Container, traits::CommonGetters, wm_state::WmState};
+
+/// Focuses the parent split container of the given container as a whole,
+/// so that subsequent commands (e.g. `move` or `close`) apply to all of
+/// its descendants.
+///
+/// Has no effect if the parent is a workspace.
+pub fn focus_parent(
+  origin_container: &Container,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  let Some(parent) = origin_container
+    .parent()
+    .and_then(|parent| parent.as_split().cloned())
+  else {
+    return Ok(());
+  };
+
+  set_focused_split(&parent.into(), state)
+}
+
+/// Focuses the given split container as a whole. Focus on the platform
+/// side is held by its most recently focused descendant.
+pub(crate) fn set_focused_split(
+  split: &Container,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  let split = split
+    .as_split()
+    .cloned()
+    .context("Not a split container.")?;
+
+  set_focused_descendant(&split.clone().into(), None);
+
+  let focused_container =
+    state.focused_container().context("No focused container.")?;
+
+  state.split_focus = Some((focused_container.id(), split));
+
+  state
+    .pending_sync
+    .queue_focus_change()
+    .queue_focused_effect_update();
+
+  Ok(())
+}
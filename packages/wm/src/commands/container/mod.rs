@@ -1,24 +1,36 @@
 mod attach_container;
+mod cycle_ratio;
 mod detach_container;
 mod flatten_child_split_containers;
 mod flatten_split_container;
+mod focus_child;
 mod focus_container_by_id;
 mod focus_in_direction;
+mod focus_parent;
 mod move_container_within_tree;
+mod move_split_in_direction;
+mod move_split_to_workspace;
 mod replace_container;
+mod resize_split_container;
 mod resize_tiling_container;
 mod set_focused_descendant;
 mod toggle_tiling_direction;
 mod wrap_in_split_container;
 
 pub use attach_container::*;
+pub use cycle_ratio::*;
 pub use detach_container::*;
 pub use flatten_child_split_containers::*;
 pub use flatten_split_container::*;
+pub use focus_child::*;
 pub use focus_container_by_id::*;
 pub use focus_in_direction::*;
+pub use focus_parent::*;
 pub use move_container_within_tree::*;
+pub use move_split_in_direction::*;
+pub use move_split_to_workspace::*;
 pub use replace_container::*;
+pub use resize_split_container::*;
 pub use resize_tiling_container::*;
 pub use set_focused_descendant::*;
 pub use toggle_tiling_direction::*;
@@ -0,0 +1,113 @@
+use anyhow::Context;
+use wm_common::{Direction, TilingDirection};
+
+use super::{move_container_within_tree, move_split_to_workspace};
+use crate::{
+  models::{SplitContainer, WorkspaceTarget},
+  traits::{CommonGetters, TilingDirectionGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Moves a split container in the given direction.
+///
+/// This follows the same rules as moving a tiling window: the split
+/// container is swapped with an adjacent sibling, moved into an ancestor
+/// with a matching tiling direction, or moved to the workspace on the
+/// monitor in the given direction.
+pub fn move_split_in_direction(
+  split: &SplitContainer,
+  direction: &Direction,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let parent = split
+    .parent()
+    .and_then(|parent| parent.as_direction_container().ok())
+    .context("No direction container.")?;
+
+  let has_matching_tiling_direction = parent.tiling_direction()
+    == TilingDirection::from_direction(direction);
+
+  // Attempt to swap with a sibling in the given direction.
+  if has_matching_tiling_direction {
+    let sibling = match direction {
+      Direction::Up | Direction::Left => split
+        .prev_siblings()
+        .find_map(|sibling| sibling.as_tiling_container().ok()),
+      _ => split
+        .next_siblings()
+        .find_map(|sibling| sibling.as_tiling_container().ok()),
+    };
+
+    if let Some(sibling) = sibling {
+      move_container_within_tree(
+        &split.clone().into(),
+        &parent.clone().into(),
+        sibling.index(),
+        state,
+      )?;
+
+      state
+        .pending_sync
+        .queue_containers_to_redraw(parent.tiling_children());
+
+      return Ok(());
+    }
+  }
+
+  // Attempt to move the split container to the workspace in the given
+  // direction.
+  if (has_matching_tiling_direction
+    || split.tiling_siblings().count() == 0)
+    && parent.is_workspace()
+  {
+    return move_split_to_workspace(
+      split,
+      WorkspaceTarget::Direction(direction.clone()),
+      state,
+      config,
+    );
+  }
+
+  // Otherwise, traverse upwards to find an ancestor that has the correct
+  // tiling direction, and insert the split container next to the child
+  // of that ancestor containing it.
+  let target_ancestor = parent.ancestors().find_map(|ancestor| {
+    ancestor.as_direction_container().ok().filter(|ancestor| {
+      ancestor.tiling_direction()
+        == TilingDirection::from_direction(direction)
+    })
+  });
+
+  let Some(target_ancestor) = target_ancestor else {
+    return Ok(());
+  };
+
+  let split_ancestor = split
+    .ancestors()
+    .find(|container| {
+      container
+        .parent()
+        .is_some_and(|parent| parent == target_ancestor.clone().into())
+    })
+    .context("Split container ancestor not found.")?;
+
+  let target_index = match direction {
+    Direction::Up | Direction::Left => split_ancestor.index(),
+    _ => split_ancestor.index() + 1,
+  };
+
+  move_container_within_tree(
+    &split.clone().into(),
+    &target_ancestor.clone().into(),
+    target_index,
+    state,
+  )?;
+
+  state
+    .pending_sync
+    .queue_containers_to_redraw(target_ancestor.tiling_children());
+
+  Ok(())
+}
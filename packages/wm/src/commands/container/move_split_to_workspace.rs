@@ -0,0 +1,113 @@
+use anyhow::Context;
+use tracing::info;
+
+use super::{move_container_within_tree, set_focused_descendant};
+use crate::{
+  commands::workspace::activate_workspace,
+  models::{SplitContainer, WorkspaceTarget},
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Moves a split container with all its descendants to the given
+/// workspace. It's appended to the end of the target workspace.
+///
+/// If the split container has focus, focus is retained within the
+/// workspace from where it was moved.
+pub fn move_split_to_workspace(
+  split: &SplitContainer,
+  target: WorkspaceTarget,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let current_workspace = split.workspace().context("No workspace.")?;
+  let current_monitor =
+    current_workspace.monitor().context("No monitor.")?;
+
+  let (target_workspace_name, target_workspace) =
+    state.workspace_by_target(&current_workspace, target, config)?;
+
+  // Retrieve or activate the target workspace by its name.
+  let target_workspace = match (target_workspace, target_workspace_name) {
+    (Some(target_workspace), _) => target_workspace,
+    (None, Some(name)) => {
+      activate_workspace(Some(&name), None, state, config)?;
+      state
+        .workspace_by_name(&name)
+        .context("Failed to activate workspace.")?
+    }
+    (None, None) => return Ok(()),
+  };
+
+  if target_workspace.id() == current_workspace.id() {
+    return Ok(());
+  }
+
+  info!(
+    "Moving split container to workspace: '{}'.",
+    target_workspace.config().name
+  );
+
+  let target_monitor =
+    target_workspace.monitor().context("No monitor.")?;
+
+  // Since target workspace could be on a different monitor, adjustments
+  // might need to be made because of DPI.
+  if current_monitor.has_dpi_difference(&target_monitor.clone().into())? {
+    for window in split
+      .descendants()
+      .filter_map(|descendant| descendant.as_window_container().ok())
+    {
+      window.set_has_pending_dpi_adjustment(true);
+    }
+  }
+
+  // Focus the next container in the current workspace that isn't part of
+  // the split container.
+  let focus_target = split.has_focus(None).then(|| {
+    current_workspace
+      .descendant_focus_order()
+      .find(|descendant| {
+        !descendant
+          .ancestors()
+          .any(|ancestor| ancestor.id() == split.id())
+      })
+      .unwrap_or_else(|| current_workspace.clone().into())
+  });
+
+  let focus_reset_target = if target_workspace.is_displayed() {
+    None
+  } else {
+    target_monitor.descendant_focus_order().next()
+  };
+
+  move_container_within_tree(
+    &split.clone().into(),
+    &target_workspace.clone().into(),
+    target_workspace.child_count(),
+    state,
+  )?;
+
+  // Moving a focused container to a hidden workspace would otherwise
+  // display it, so reset focus on the target monitor.
+  if let Some(focus_reset_target) = focus_reset_target {
+    set_focused_descendant(
+      &focus_reset_target,
+      Some(&target_monitor.into()),
+    );
+  }
+
+  if let Some(focus_target) = focus_target {
+    set_focused_descendant(&focus_target, None);
+    state.pending_sync.queue_focus_change();
+  }
+
+  state
+    .pending_sync
+    .queue_containers_to_redraw(current_workspace.tiling_children())
+    .queue_containers_to_redraw(target_workspace.tiling_children())
+    .queue_workspace_to_reorder(target_workspace);
+
+  Ok(())
+}
@@ -0,0 +1,100 @@
+use anyhow::Context;
+use wm_common::LengthValue;
+
+use super::resize_tiling_container;
+use crate::{
+  models::{SplitContainer, TilingContainer},
+  traits::{CommonGetters, PositionGetters, TilingSizeGetters},
+  wm_state::WmState,
+};
+
+/// Resizes a split container by the given deltas. As with tiling
+/// windows, the container to resize can be an ancestor split container
+/// if the resize is in the inverse of the parent's tiling direction.
+pub fn resize_split_container(
+  split: &SplitContainer,
+  width_delta: Option<LengthValue>,
+  height_delta: Option<LengthValue>,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  for (delta, is_width_resize) in
+    [(width_delta, true), (height_delta, false)]
+  {
+    if let Some(delta) = delta {
+      set_split_container_length(
+        split,
+        is_width_resize,
+        |container_to_resize, parent_length| {
+          container_to_resize.tiling_size()
+            + delta.to_percentage(parent_length)
+        },
+        state,
+      )?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Sets the width and/or height of a split container.
+pub fn set_split_container_size(
+  split: &SplitContainer,
+  target_width: Option<LengthValue>,
+  target_height: Option<LengthValue>,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  for (target_length, is_width_resize) in
+    [(target_width, true), (target_height, false)]
+  {
+    if let Some(target_length) = target_length {
+      set_split_container_length(
+        split,
+        is_width_resize,
+        |_, parent_length| target_length.to_percentage(parent_length),
+        state,
+      )?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Updates either the width or height of a split container. The target
+/// tiling size is derived from the container to resize and the length of
+/// its parent in pixels.
+fn set_split_container_length(
+  split: &SplitContainer,
+  is_width_resize: bool,
+  target_size: impl FnOnce(&TilingContainer, i32) -> f32,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  let Some(container_to_resize) =
+    split.container_to_resize(is_width_resize)?
+  else {
+    return Ok(());
+  };
+
+  let parent = container_to_resize.parent().context("No parent.")?;
+  let (horizontal_gap, vertical_gap) = container_to_resize.inner_gaps()?;
+
+  #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+  let parent_length = if is_width_resize {
+    parent.to_rect()?.width()
+      - horizontal_gap
+        * container_to_resize.tiling_siblings().count() as i32
+  } else {
+    parent.to_rect()?.height()
+      - vertical_gap * container_to_resize.tiling_siblings().count() as i32
+  };
+
+  resize_tiling_container(
+    &container_to_resize,
+    target_size(&container_to_resize, parent_length),
+  );
+
+  state
+    .pending_sync
+    .queue_containers_to_redraw(parent.tiling_children());
+
+  Ok(())
+}
@@ -24,7 +24,16 @@ pub fn toggle_tiling_direction(
 
       Ok(workspace.into())
     }
-    // Can only toggle tiling direction from a tiling window or workspace.
+    // Toggle the direction of a focused split container itself, rather
+    // than wrapping it in a new split container.
+    Container::Split(split) => {
+      split.set_tiling_direction(split.tiling_direction().inverse());
+      state.pending_sync.queue_container_to_redraw(split.clone());
+
+      Ok(split.into())
+    }
+    // Can only toggle tiling direction from a tiling window, split
+    // container, or workspace.
     _ => return Ok(()),
   }?;
 
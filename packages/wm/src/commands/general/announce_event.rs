@@ -0,0 +1,111 @@
+use wm_common::{WindowState, WmEvent};
+use wm_platform::Platform;
+
+use crate::{
+  models::Container,
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Announces focus changes, workspace switches, and binding mode changes
+/// to screen readers if enabled in the user config.
+pub fn announce_event(
+  event: &WmEvent,
+  state: &mut WmState,
+  config: &UserConfig,
+) {
+  let accessibility_config = &config.value.general.accessibility;
+
+  let message = match event {
+    WmEvent::FocusChanged { .. } => {
+      let Some(focused_container) = state.focused_container() else {
+        return;
+      };
+
+      let workspace = focused_container.workspace();
+      let workspace_id = workspace.as_ref().map(CommonGetters::id);
+
+      // Track the focused workspace even while announcements are
+      // disabled, so that enabling them doesn't announce a stale switch.
+      let is_workspace_switch = workspace_id.is_some()
+        && workspace_id != state.announced_workspace_id;
+
+      state.announced_workspace_id = workspace_id;
+
+      if !accessibility_config.announcements {
+        return;
+      }
+
+      let workspace_label = workspace
+        .filter(|_| {
+          is_workspace_switch
+            && accessibility_config.announce_workspace_switches
+        })
+        .map(|workspace| {
+          let config = workspace.config();
+          format!(
+            "Workspace {}",
+            config.display_name.unwrap_or(config.name)
+          )
+        });
+
+      let container_label = accessibility_config
+        .announce_focus_changes
+        .then(|| container_label(&focused_container));
+
+      match (workspace_label, container_label) {
+        (Some(workspace_label), Some(container_label)) => {
+          format!("{workspace_label}, {container_label}")
+        }
+        (Some(label), None) | (None, Some(label)) => label,
+        (None, None) => return,
+      }
+    }
+    WmEvent::BindingModesChanged { new_binding_modes } => {
+      if !accessibility_config.announcements
+        || !accessibility_config.announce_binding_mode_changes
+      {
+        return;
+      }
+
+      match new_binding_modes.first() {
+        Some(binding_mode) => format!(
+          "Binding mode {}",
+          binding_mode
+            .display_name
+            .as_ref()
+            .unwrap_or(&binding_mode.name)
+        ),
+        None => "Binding mode off".to_string(),
+      }
+    }
+    _ => return,
+  };
+
+  Platform::announce(&message);
+}
+
+/// Describes the focused container, i.e. the title of a window along with
+/// its state if it's not tiling, or that the workspace is empty.
+fn container_label(container: &Container) -> String {
+  let Ok(window) = container.as_window_container() else {
+    return "empty".to_string();
+  };
+
+  let native = window.native();
+
+  let title = native
+    .title()
+    .ok()
+    .filter(|title| !title.is_empty())
+    .or_else(|| native.process_name().ok())
+    .unwrap_or("Untitled window".to_string());
+
+  match window.state() {
+    WindowState::Tiling => title,
+    WindowState::Floating(_) => format!("{title}, floating"),
+    WindowState::Fullscreen(_) => format!("{title}, fullscreen"),
+    WindowState::Minimized => format!("{title}, minimized"),
+  }
+}
@@ -0,0 +1,46 @@
+use tracing::warn;
+use wm_common::{foreign_window_managers, WmEvent};
+use wm_platform::{NotificationLevel, Platform};
+
+use crate::{user_config::UserConfig, wm_state::WmState};
+
+/// Warns if other window managers are running, since they'd otherwise
+/// fight over window positions.
+///
+/// Emits a `ForeignWmDetected` event and shows a notification pointing
+/// to the coexistence config.
+pub fn detect_foreign_wms(state: &WmState, config: &UserConfig) {
+  if !config.value.general.coexistence.detect_foreign_wms {
+    return;
+  }
+
+  let process_names = match Platform::running_process_names() {
+    Ok(process_names) => process_names,
+    Err(err) => {
+      warn!("Failed to get running processes: {}", err);
+      return;
+    }
+  };
+
+  let names = foreign_window_managers(&process_names);
+
+  if names.is_empty() {
+    return;
+  }
+
+  warn!("Detected other window managers: {}.", names.join(", "));
+
+  Platform::show_notification(
+    "Other window manager detected",
+    &format!(
+      "{} might conflict with GlazeWM. Use `general.coexistence` to \
+       restrict GlazeWM to specific monitors or workspaces.",
+      names.join(", ")
+    ),
+    NotificationLevel::Warning,
+  );
+
+  state.emit_event(WmEvent::ForeignWmDetected {
+    names: names.into_iter().map(ToString::to_string).collect(),
+  });
+}
@@ -2,13 +2,23 @@ use wm_common::WmEvent;
 
 use crate::wm_state::WmState;
 
-pub fn disable_binding_mode(name: &str, state: &mut WmState) {
-  state.binding_modes = state
+/// Disables the binding mode with the given name, or all active binding
+/// modes if no name is given.
+pub fn disable_binding_mode(name: Option<&str>, state: &mut WmState) {
+  let (exited_binding_modes, binding_modes) = state
     .binding_modes
-    .iter()
-    .filter(|config| config.name != name)
-    .cloned()
-    .collect::<Vec<_>>();
+    .drain(..)
+    .partition::<Vec<_>, _>(|config| {
+      name.is_none_or(|name| config.name == name)
+    });
+
+  state.binding_modes = binding_modes;
+
+  for exited_binding_mode in exited_binding_modes {
+    state.emit_event(WmEvent::BindingModeExited {
+      exited_binding_mode,
+    });
+  }
 
   state.emit_event(WmEvent::BindingModesChanged {
     new_binding_modes: state.binding_modes.clone(),
@@ -17,7 +17,24 @@ pub fn enable_binding_mode(
       format!("No binding mode found with the name '{name}'.")
     })?;
 
-  state.binding_modes = vec![binding_mode.clone()];
+  // Only one binding mode can be active at a time, so any other active
+  // binding modes are exited.
+  let prev_binding_modes = std::mem::replace(
+    &mut state.binding_modes,
+    vec![binding_mode.clone()],
+  );
+
+  for prev_binding_mode in prev_binding_modes {
+    if prev_binding_mode.name != name {
+      state.emit_event(WmEvent::BindingModeExited {
+        exited_binding_mode: prev_binding_mode,
+      });
+    }
+  }
+
+  state.emit_event(WmEvent::BindingModeEntered {
+    entered_binding_mode: binding_mode.clone(),
+  });
 
   state.emit_event(WmEvent::BindingModesChanged {
     new_binding_modes: state.binding_modes.clone(),
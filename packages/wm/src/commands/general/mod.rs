@@ -1,15 +1,61 @@
+mod announce_event;
 mod cycle_focus;
+mod detect_foreign_wms;
 mod disable_binding_mode;
 mod enable_binding_mode;
 mod platform_sync;
 mod reload_config;
+mod run_timer;
+mod send_keys;
+mod set_focus_assist;
+mod set_timer_enabled;
 mod shell_exec;
+mod show_command_palette;
+mod show_keybindings;
+mod show_menu;
+mod show_overview;
+mod show_window_hints;
+mod show_window_switcher;
+mod sync_focus_frame;
+mod sync_resize_guides;
+mod sync_status_bars;
+mod toggle_binding_mode;
+mod toggle_mouse_events;
 mod toggle_pause;
+mod toggle_trace;
+mod toggle_zoom;
+mod update_accessibility;
+mod update_game_mode;
+mod update_idle_state;
+mod update_media_usage;
 
+pub use announce_event::*;
 pub use cycle_focus::*;
+pub use detect_foreign_wms::*;
 pub use disable_binding_mode::*;
 pub use enable_binding_mode::*;
 pub use platform_sync::*;
 pub use reload_config::*;
+pub use run_timer::*;
+pub use send_keys::*;
+pub use set_focus_assist::*;
+pub use set_timer_enabled::*;
 pub use shell_exec::*;
+pub use show_command_palette::*;
+pub use show_keybindings::*;
+pub use show_menu::*;
+pub use show_overview::*;
+pub use show_window_hints::*;
+pub use show_window_switcher::*;
+pub use sync_focus_frame::*;
+pub use sync_resize_guides::*;
+pub use sync_status_bars::*;
+pub use toggle_binding_mode::*;
+pub use toggle_mouse_events::*;
 pub use toggle_pause::*;
+pub use toggle_trace::*;
+pub use toggle_zoom::*;
+pub use update_accessibility::*;
+pub use update_game_mode::*;
+pub use update_idle_state::*;
+pub use update_media_usage::*;
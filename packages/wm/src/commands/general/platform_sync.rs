@@ -1,77 +1,136 @@
-use std::time::Duration;
+use std::{
+  collections::HashMap,
+  time::{Duration, Instant},
+};
 
 use anyhow::Context;
 use tokio::task;
 use tracing::{info, warn};
+use uuid::Uuid;
 use wm_common::{
-  CornerStyle, CursorJumpTrigger, DisplayState, HideMethod, OpacityValue,
-  UniqueExt, WindowEffectConfig, WindowState, WmEvent,
+  BorderEffectConfig, CornerStyle, CursorJumpTrigger, DisplayState,
+  HideMethod, OpacityValue, TransparencyEffectConfig, UniqueExt,
+  WindowEffectConfig, WindowState, WmEvent,
 };
-use wm_platform::{Platform, ZOrder};
+use wm_platform::{Platform, WindowPosBatch, ZOrder};
 
 use crate::{
-  models::{Container, WindowContainer},
-  traits::{CommonGetters, PositionGetters, WindowGetters},
+  commands::workspace::scroll_to_container,
+  models::{Container, WindowContainer, Workspace},
+  traits::{CommonGetters, LayoutCache, PositionGetters, WindowGetters},
   user_config::UserConfig,
-  wm_state::WmState,
+  wm_state::{AccessibilityState, WindowPlacement, WmState},
 };
 
 pub fn platform_sync(
   state: &mut WmState,
   config: &UserConfig,
 ) -> anyhow::Result<()> {
+  let start_time = Instant::now();
   let focused_container =
     state.focused_container().context("No focused container.")?;
 
-  if state.pending_sync.needs_focus_update() {
+  // Skipped changes are applied in full when game mode is exited.
+  let game_mode_config = &config.value.general.game_mode;
+  let is_tiling_paused =
+    state.game_window.is_some() && game_mode_config.pause_tiling;
+  let is_effects_paused =
+    state.game_window.is_some() && game_mode_config.pause_effects;
+
+  if state.pending_sync.needs_focus_update() && !is_tiling_paused {
+    scroll_to_container(&focused_container, state)?;
     sync_focus(&focused_container, state)?;
   }
 
-  if !state.pending_sync.containers_to_redraw().is_empty()
-    || !state.pending_sync.workspaces_to_reorder().is_empty()
+  if (!state.pending_sync.containers_to_redraw().is_empty()
+    || !state.pending_sync.workspaces_to_reorder().is_empty())
+    && !is_tiling_paused
   {
     redraw_containers(&focused_container, state, config)?;
   }
 
   if state.pending_sync.needs_cursor_jump()
     && config.value.general.cursor_jump.enabled
+    && !is_tiling_paused
   {
     jump_cursor(focused_container.clone(), state, config)?;
   }
 
-  if state.pending_sync.needs_focused_effect_update()
-    || state.pending_sync.needs_all_effects_update()
+  if (state.pending_sync.needs_focused_effect_update()
+    || state.pending_sync.needs_all_effects_update())
+    && !is_effects_paused
   {
-    // Keep reference to the previous window that had focus effects
+    // Keep reference to the previous windows that had focus effects
     // applied.
-    let prev_effects_window = state.prev_effects_window.clone();
+    let prev_effects_windows =
+      std::mem::take(&mut state.prev_effects_windows);
+
+    // Apply focus effects to all windows of a focused split container,
+    // which serves as its selection indicator.
+    let focused_windows = match state.focused_split() {
+      Some(split) => split
+        .descendants()
+        .filter_map(|descendant| descendant.as_window_container().ok())
+        .collect::<Vec<_>>(),
+      None => focused_container
+        .as_window_container()
+        .into_iter()
+        .collect(),
+    };
 
-    if let Ok(window) = focused_container.as_window_container() {
-      apply_window_effects(&window, true, config);
-      state.prev_effects_window = Some(window.clone());
-    } else {
-      state.prev_effects_window = None;
+    for window in &focused_windows {
+      apply_window_effects(window, true, state.accessibility, config);
     }
 
     // Get windows that should have the unfocused border applied to them.
     // For the sake of performance, we only update the border of the
-    // previously focused window. If the `reset_window_effects` flag is
+    // previously focused windows. If the `reset_window_effects` flag is
     // passed, the unfocused border is applied to all unfocused windows.
     let unfocused_windows =
       if state.pending_sync.needs_all_effects_update() {
         state.windows()
       } else {
-        prev_effects_window.into_iter().collect()
+        prev_effects_windows
       }
       .into_iter()
-      .filter(|window| window.id() != focused_container.id());
+      .filter(|window| {
+        !focused_windows
+          .iter()
+          .any(|focused_window| focused_window.id() == window.id())
+      });
 
     for window in unfocused_windows {
-      apply_window_effects(&window, false, config);
+      apply_window_effects(&window, false, state.accessibility, config);
+    }
+
+    state.prev_effects_windows = focused_windows;
+
+    // Windows that are playing audio have their border applied after
+    // the focused and non-focused borders, so that it takes precedence.
+    let audio_border = &config.value.window_effects.audio_windows.border;
+
+    if audio_border.enabled {
+      for window in state.windows() {
+        if window.media_usage().is_playing_audio {
+          apply_border_effect(&window, audio_border);
+        }
+      }
+    }
+
+    // Selected windows have their border applied last, so that it takes
+    // precedence over the focused and non-focused borders.
+    let selected_border =
+      &config.value.window_effects.selected_windows.border;
+
+    if selected_border.enabled {
+      for window in state.selected_windows() {
+        apply_border_effect(&window, selected_border);
+      }
     }
   }
 
   state.pending_sync.clear();
+  state.perf_stats.platform_sync.record(start_time.elapsed());
 
   Ok(())
 }
@@ -99,8 +158,20 @@ fn sync_focus(
     }
   }
 
+  // Emit the focused split container instead if there is one.
+  let focused_subject = state
+    .focused_split()
+    .map_or_else(|| focused_container.clone(), Into::into);
+
+  let focused_window = focused_container
+    .as_window_container()
+    .ok()
+    .map(|window| window.to_focused_window_dto())
+    .transpose()?;
+
   state.emit_event(WmEvent::FocusChanged {
-    focused_container: focused_container.to_dto()?,
+    focused_container: focused_subject.to_dto()?,
+    focused_window,
   });
 
   Ok(())
@@ -171,6 +242,16 @@ fn redraw_containers(
   state: &mut WmState,
   config: &UserConfig,
 ) -> anyhow::Result<()> {
+  let start_time = Instant::now();
+  let mut layout_duration = Duration::ZERO;
+
+  // Memoize container rects for the duration of the redraw.
+  let _layout_cache = LayoutCache::enable();
+
+  // Position changes are applied in a single transaction per monitor to
+  // avoid windows visibly cascading into place.
+  let mut batches = HashMap::<Uuid, WindowPosBatch>::new();
+
   let windows_to_redraw = state.windows_to_redraw();
   let windows_to_bring_to_front =
     windows_to_bring_to_front(focused_container, state)?;
@@ -199,6 +280,17 @@ fn redraw_containers(
   };
 
   for window in windows_to_update.iter().rev() {
+    // Skip windows that aren't responding, since Win32 calls on them can
+    // stall the WM. These are redrawn once they respond again.
+    if state.unresponsive_windows.contains(&window.native()) {
+      continue;
+    }
+
+    if window.native().is_hung() {
+      state.mark_window_unresponsive(window)?;
+      continue;
+    }
+
     let should_bring_to_front = windows_to_bring_to_front.contains(window);
 
     let workspace =
@@ -244,22 +336,67 @@ fn redraw_containers(
     }
 
     // Transition display state depending on whether window will be
-    // shown or hidden.
-    window.set_display_state(
-      match (window.display_state(), workspace.is_displayed()) {
-        (DisplayState::Hidden | DisplayState::Hiding, true) => {
+    // shown or hidden. Tiling windows that are scrolled out of view are
+    // hidden as well.
+    let is_shown = workspace.is_displayed()
+      && !is_scrolled_out_of_view(window, &workspace)?;
+
+    let hide_method = window
+      .hide_method()
+      .unwrap_or_else(|| config.value.general.hide_method.clone());
+
+    // Windows that are moved off-screen don't receive show or hide
+    // events, so their display state transitions complete immediately.
+    let display_state = match (window.display_state(), is_shown) {
+      (DisplayState::Hidden | DisplayState::Hiding, true) => {
+        if hide_method == HideMethod::Offscreen {
+          DisplayState::Shown
+        } else {
           DisplayState::Showing
         }
-        (DisplayState::Shown | DisplayState::Showing, false) => {
+      }
+      (DisplayState::Shown | DisplayState::Showing, false) => {
+        if hide_method == HideMethod::Offscreen {
+          DisplayState::Hidden
+        } else {
           DisplayState::Hiding
         }
-        _ => window.display_state(),
-      },
-    );
+      }
+      _ => window.display_state(),
+    };
+
+    let is_display_transition = display_state != window.display_state()
+      || matches!(
+        display_state,
+        DisplayState::Showing | DisplayState::Hiding
+      );
 
+    if display_state != window.display_state() {
+      state.display_transition_timestamp = Some(Instant::now());
+    }
+
+    window.set_display_state(display_state);
+
+    let layout_start_time = Instant::now();
     let rect = window
       .to_rect()?
       .apply_delta(&window.total_border_delta()?, None);
+    layout_duration += layout_start_time.elapsed();
+
+    let placement = WindowPlacement {
+      rect: rect.clone(),
+      state: window.state(),
+      display_state: window.display_state(),
+      z_order: z_order.clone(),
+    };
+
+    // Skip repositioning the window if its placement hasn't changed since
+    // it was last repositioned.
+    if !window.has_pending_dpi_adjustment()
+      && state.window_placements.get(&window.id()) == Some(&placement)
+    {
+      continue;
+    }
 
     let is_visible = matches!(
       window.display_state(),
@@ -268,15 +405,29 @@ fn redraw_containers(
 
     info!("Updating window position: {window}");
 
+    let monitor = window.monitor().context("Window has no monitor.")?;
+    let batch = batches.entry(monitor.id()).or_default();
+
     if let Err(err) = window.native().set_position(
+      batch,
       &window.state(),
       &rect,
       &z_order,
       is_visible,
-      &config.value.general.hide_method,
+      &hide_method,
       window.has_pending_dpi_adjustment(),
     ) {
       warn!("Failed to set window position: {}", err);
+    } else {
+      state.window_placements.insert(window.id(), placement);
+
+      // Keep track of off-screen windows, so that they can be restored
+      // on the next startup if the WM exits unexpectedly.
+      if !is_visible && hide_method == HideMethod::Offscreen {
+        state.offscreen_windows.insert(&window.native(), rect);
+      } else {
+        state.offscreen_windows.remove(&window.native());
+      }
     }
 
     // Whether the window is either transitioning to or from fullscreen.
@@ -298,27 +449,53 @@ fn redraw_containers(
       }
     }
 
-    // Skip setting taskbar visibility if the window is hidden (has no
-    // effect). Since cloaked windows are normally always visible in the
-    // taskbar, we only need to set visibility if `show_all_in_taskbar` is
-    // `false`.
-    if config.value.general.hide_method == HideMethod::Cloak
-      && !config.value.general.show_all_in_taskbar
-      && matches!(
-        window.display_state(),
-        DisplayState::Showing | DisplayState::Hiding
-      )
-    {
-      if let Err(err) = window.native().set_taskbar_visibility(is_visible)
+    // Skip setting taskbar visibility if the window is hidden via
+    // `SW_HIDE` (has no effect). Windows that are cloaked or moved
+    // off-screen otherwise keep their taskbar buttons, so they're only
+    // removed if `show_all_in_taskbar` is `false` for the workspace.
+    if hide_method != HideMethod::Hide && is_display_transition {
+      let show_all_in_taskbar = workspace
+        .config()
+        .show_all_in_taskbar
+        .unwrap_or(config.value.general.show_all_in_taskbar);
+
+      if let Err(err) = window
+        .native()
+        .set_taskbar_visibility(is_visible || show_all_in_taskbar)
       {
         warn!("Failed to set taskbar visibility: {}", err);
       }
     }
   }
 
+  for batch in batches.into_values() {
+    state.perf_stats.set_position_batch_size.record(batch.len());
+    batch.apply();
+  }
+
+  // Written once all windows have been moved, rather than per window.
+  state.offscreen_windows.save_changes();
+
+  state.perf_stats.layout.record(layout_duration);
+  state.perf_stats.redraw.record(start_time.elapsed());
+
   Ok(())
 }
 
+/// Whether the window is a tiling window on a scrolling workspace that
+/// lies entirely outside of the workspace's bounds.
+fn is_scrolled_out_of_view(
+  window: &WindowContainer,
+  workspace: &Workspace,
+) -> anyhow::Result<bool> {
+  if !workspace.config().scrolling || window.state() != WindowState::Tiling
+  {
+    return Ok(false);
+  }
+
+  Ok(!window.to_rect()?.has_overlap_x(&workspace.to_rect()?))
+}
+
 fn jump_cursor(
   focused_container: Container,
   state: &WmState,
@@ -357,9 +534,44 @@ fn jump_cursor(
 fn apply_window_effects(
   window: &WindowContainer,
   is_focused: bool,
+  accessibility: AccessibilityState,
   config: &UserConfig,
 ) {
-  let window_effects = &config.value.window_effects;
+  let workspace = window.workspace();
+
+  // Window effects can be overridden per workspace.
+  let mut window_effects = match &workspace {
+    Some(workspace) => {
+      config.window_effects_for_workspace(&workspace.config())
+    }
+    None => config.value.window_effects.clone(),
+  };
+
+  // Borders use the colors of the high-contrast theme, and windows are
+  // kept opaque so that their contents stay legible.
+  if accessibility.is_high_contrast {
+    let (focused_color, unfocused_color) =
+      Platform::high_contrast_border_colors();
+
+    for (effect_config, color) in [
+      (&mut window_effects.focused_window, focused_color),
+      (&mut window_effects.other_windows, unfocused_color),
+    ] {
+      effect_config.border = BorderEffectConfig {
+        enabled: true,
+        color,
+      };
+
+      effect_config.transparency = TransparencyEffectConfig {
+        enabled: true,
+        opacity: OpacityValue::from_alpha(u8::MAX),
+      };
+    }
+  }
+
+  _ = window
+    .native()
+    .set_transitions_enabled(!accessibility.is_reduced_motion);
 
   let effect_config = if is_focused {
     &window_effects.focused_window
@@ -371,7 +583,28 @@ fn apply_window_effects(
   if window_effects.focused_window.border.enabled
     || window_effects.other_windows.border.enabled
   {
-    apply_border_effect(window, effect_config);
+    // Remove the border of a window that's alone in its workspace if
+    // smart borders are enabled.
+    let has_smart_border = window.is_tiling_window()
+      && workspace.as_ref().is_some_and(|workspace| {
+        let gaps_config = workspace.gaps_config();
+
+        gaps_config.smart
+          && gaps_config.smart_borders
+          && workspace.has_single_tiling_window()
+      });
+
+    if has_smart_border {
+      apply_border_effect(
+        window,
+        &BorderEffectConfig {
+          enabled: false,
+          ..effect_config.border.clone()
+        },
+      );
+    } else {
+      apply_border_effect(window, &effect_config.border);
+    }
   }
 
   if window_effects.focused_window.hide_title_bar.enabled
@@ -395,10 +628,10 @@ fn apply_window_effects(
 
 fn apply_border_effect(
   window: &WindowContainer,
-  effect_config: &WindowEffectConfig,
+  border_config: &BorderEffectConfig,
 ) {
-  let border_color = if effect_config.border.enabled {
-    Some(&effect_config.border.color)
+  let border_color = if border_config.enabled {
+    Some(&border_config.color)
   } else {
     None
   };
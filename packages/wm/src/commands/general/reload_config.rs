@@ -1,9 +1,16 @@
 use anyhow::Context;
 use tracing::{info, warn};
-use wm_common::{HideMethod, ParsedConfig, WindowRuleEvent, WmEvent};
+use wm_common::{
+  CommandSource, HideMethod, ParsedConfig, WindowRuleEvent, WmEvent,
+};
 
 use crate::{
-  commands::{window::run_window_rules, workspace::sort_workspaces},
+  commands::{
+    general::{update_accessibility, update_game_mode},
+    window::run_window_rules,
+    workspace::sort_workspaces,
+  },
+  events::handle_display_settings_changed,
   traits::{CommonGetters, TilingSizeGetters, WindowGetters},
   user_config::UserConfig,
   wm::WindowManager,
@@ -28,6 +35,11 @@ pub fn reload_config(
     run_window_rules(window, &WindowRuleEvent::Manage, state, config)?;
   }
 
+  // Add or remove monitors that are affected by changed monitor rules.
+  if old_config.monitor_rules != config.value.monitor_rules {
+    handle_display_settings_changed(state, config)?;
+  }
+
   update_workspace_configs(state, config)?;
 
   update_container_gaps(state, config);
@@ -43,24 +55,46 @@ pub fn reload_config(
     }
   }
 
-  // Ensure all windows are shown in taskbar when `show_all_in_taskbar` is
-  // changed.
+  // Ensure windows are shown in taskbar when `show_all_in_taskbar` is
+  // changed, either globally or for their workspace.
   if old_config.general.show_all_in_taskbar
     != config.value.general.show_all_in_taskbar
-    && config.value.general.show_all_in_taskbar
+    || old_config.workspaces != config.value.workspaces
   {
     for window in state.windows() {
-      let _ = window.native().set_taskbar_visibility(true);
+      let show_all_in_taskbar = window
+        .workspace()
+        .and_then(|workspace| workspace.config().show_all_in_taskbar)
+        .unwrap_or(config.value.general.show_all_in_taskbar);
+
+      if show_all_in_taskbar {
+        let _ = window.native().set_taskbar_visibility(true);
+      }
     }
   }
 
   // Clear active binding modes.
   state.binding_modes = Vec::new();
 
-  // Redraw full container tree.
-  state
-    .pending_sync
-    .queue_container_to_redraw(state.root_container.clone());
+  // Reschedule timers, since they might have changed.
+  state.scheduler.update(&config.value.timers);
+
+  // Check idle state on the next tick, since the threshold might have
+  // changed.
+  state.idle_check_deadline = Some(std::time::Instant::now());
+
+  // Same for the media poll interval.
+  state.media_check_deadline = Some(std::time::Instant::now());
+
+  // Exit game mode if it has been disabled.
+  update_game_mode(state, config);
+
+  // Overrides for high-contrast and reduced motion might have changed.
+  update_accessibility(state, config);
+
+  // Redraw full container tree. All windows are repositioned, since
+  // options like the hide method might have changed.
+  state.mark_container_dirty(&state.root_container.clone().into());
 
   // Emit the updated config.
   state.emit_event(WmEvent::UserConfigChanged {
@@ -70,13 +104,14 @@ pub fn reload_config(
       .context("Invalid config path.")?
       .to_string(),
     config_string: config.value_str.clone(),
-    parsed_config: config.value.clone(),
+    parsed_config: Box::new(config.value.clone()),
   });
 
   // Run config reload commands.
   WindowManager::run_commands(
     &config.value.general.config_reload_commands.clone(),
     state.focused_container().context("No focused container.")?,
+    Some(CommandSource::ConfigReload),
     state,
     config,
   )?;
@@ -92,6 +127,11 @@ fn update_workspace_configs(
   let workspaces = state.workspaces();
 
   for workspace in &workspaces {
+    // App workspaces aren't in the config, so there's nothing to update.
+    if config.is_app_workspace(workspace) {
+      continue;
+    }
+
     let monitor = workspace.monitor().context("No monitor.")?;
 
     let workspace_config = config
@@ -116,6 +156,10 @@ fn update_workspace_configs(
       }
       Some(workspace_config) => {
         if *workspace_config != workspace.config() {
+          if workspace_config.layout != workspace.config().layout {
+            workspace.set_layout(workspace_config.layout.clone());
+          }
+
           workspace.set_config(workspace_config.clone());
 
           sort_workspaces(&monitor, config)?;
@@ -143,7 +187,8 @@ fn update_container_gaps(state: &mut WmState, config: &UserConfig) {
   }
 
   for workspace in state.workspaces() {
-    workspace.set_gaps_config(config.value.gaps.clone());
+    workspace
+      .set_gaps_config(config.gaps_for_workspace(&workspace.config()));
   }
 }
 
@@ -0,0 +1,36 @@
+use anyhow::bail;
+use wm_common::{CommandSource, InvokeCommand};
+
+use crate::{
+  models::Container, user_config::UserConfig, wm::WindowManager,
+  wm_state::WmState,
+};
+
+/// Runs the commands of the timer with the given name immediately. The
+/// timer's schedule is left unchanged.
+pub fn run_timer(
+  name: &str,
+  subject_container: Container,
+  state: &mut WmState,
+  config: &mut UserConfig,
+) -> anyhow::Result<()> {
+  let commands = state.scheduler.timer_commands(name)?;
+
+  // Prevent infinite recursion from a timer that runs itself.
+  if commands
+    .iter()
+    .any(|command| matches!(command, InvokeCommand::WmRunTimer { .. }))
+  {
+    bail!("Timer '{name}' can't run other timers.");
+  }
+
+  WindowManager::run_commands(
+    &commands,
+    subject_container,
+    Some(CommandSource::Timer),
+    state,
+    config,
+  )?;
+
+  Ok(())
+}
@@ -0,0 +1,11 @@
+use wm_platform::Platform;
+
+/// Simulates pressing each of the given key combinations (e.g.
+/// `ctrl+shift+t`) in order in the foreground window.
+pub fn send_keys(keys: &[String]) -> anyhow::Result<()> {
+  for key_combination in keys {
+    Platform::send_keys(key_combination)?;
+  }
+
+  Ok(())
+}
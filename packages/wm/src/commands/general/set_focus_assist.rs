@@ -0,0 +1,65 @@
+use tracing::info;
+use wm_common::FocusAssistMode;
+use wm_platform::Platform;
+
+use crate::{traits::CommonGetters, wm_state::WmState};
+
+/// Sets the Focus Assist profile.
+///
+/// Overrides any profile set by a workspace's `focus_assist` option, so
+/// the previous profile is no longer restored on leaving the workspace.
+pub fn set_focus_assist(
+  mode: FocusAssistMode,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  info!("Setting Focus Assist to {:?}.", mode);
+
+  state.focus_assist_restore_mode = None;
+  Platform::set_focus_assist_mode(mode)
+}
+
+/// Toggles Focus Assist between off and the given profile.
+pub fn toggle_focus_assist(
+  mode: FocusAssistMode,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  let new_mode = match Platform::focus_assist_mode()? {
+    FocusAssistMode::Off => mode,
+    _ => FocusAssistMode::Off,
+  };
+
+  set_focus_assist(new_mode, state)
+}
+
+/// Applies the `focus_assist` option of the focused workspace, or
+/// restores the previous profile if the focused workspace doesn't have
+/// one.
+pub fn update_focus_assist(state: &mut WmState) -> anyhow::Result<()> {
+  let workspace_mode = state
+    .focused_container()
+    .and_then(|container| container.workspace())
+    .and_then(|workspace| workspace.config().focus_assist);
+
+  match (workspace_mode, state.focus_assist_restore_mode) {
+    (Some(mode), restore_mode) => {
+      let current_mode = Platform::focus_assist_mode()?;
+
+      if restore_mode.is_none() {
+        state.focus_assist_restore_mode = Some(current_mode);
+      }
+
+      if current_mode != mode {
+        info!("Setting Focus Assist to {:?} for workspace.", mode);
+        Platform::set_focus_assist_mode(mode)?;
+      }
+    }
+    (None, Some(restore_mode)) => {
+      info!("Restoring Focus Assist to {:?}.", restore_mode);
+      state.focus_assist_restore_mode = None;
+      Platform::set_focus_assist_mode(restore_mode)?;
+    }
+    (None, None) => {}
+  }
+
+  Ok(())
+}
@@ -0,0 +1,11 @@
+use crate::wm_state::WmState;
+
+/// Enables or disables the timer with the given name. Toggles the timer
+/// if `is_enabled` is `None`.
+pub fn set_timer_enabled(
+  name: &str,
+  is_enabled: Option<bool>,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  state.scheduler.set_enabled(name, is_enabled)
+}
@@ -1,15 +1,102 @@
+use std::time::{Duration, Instant};
+
 use tracing::info;
-use wm_platform::Platform;
+use wm_platform::{NativeWindow, Platform};
+
+use crate::{
+  commands::workspace::activate_workspace,
+  models::Workspace,
+  traits::CommonGetters,
+  user_config::UserConfig,
+  wm_state::{LaunchOrigin, WmState},
+};
 
-pub fn shell_exec(command: &str, hide_window: bool) -> anyhow::Result<()> {
+/// Max duration after a launch that windows of the process are opened on
+/// the workspace it was launched from.
+const LAUNCH_ORIGIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub fn shell_exec(
+  command: &str,
+  hide_window: bool,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
   let (program, args) = Platform::parse_command(command)?;
   info!("Parsed command program: '{}', args: '{}'.", program, args);
 
-  Platform::run_command(&program, &args, hide_window).map_err(|err| {
-    anyhow::anyhow!(format!(
-      "Failed to execute '{command}'.\n\nError: {err}"
-    ))
-  })?;
+  let process_id = Platform::run_command(&program, &args, hide_window)
+    .map_err(|err| {
+      anyhow::anyhow!(format!(
+        "Failed to execute '{command}'.\n\nError: {err}"
+      ))
+    })?;
+
+  // Remember the focused workspace, so that slow-starting applications
+  // don't open on whichever workspace is focused once their window
+  // appears.
+  let focused_workspace = state
+    .focused_container()
+    .and_then(|focused| focused.workspace());
+
+  if let (Some(process_id), Some(workspace)) =
+    (process_id, focused_workspace)
+  {
+    if config.value.window_behavior.open_on_launch_workspace {
+      state.launch_origins.push(LaunchOrigin {
+        process_id,
+        workspace_name: workspace.config().name,
+        launched_at: Instant::now(),
+      });
+    }
+  }
 
   Ok(())
 }
+
+/// Gets the workspace that the window's process (or its parent process)
+/// was launched from via `shell-exec`.
+///
+/// The workspace is activated if it has since been deactivated.
+pub fn launch_origin_workspace(
+  native_window: &NativeWindow,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<Option<Workspace>> {
+  state
+    .launch_origins
+    .retain(|origin| origin.launched_at.elapsed() < LAUNCH_ORIGIN_TIMEOUT);
+
+  if state.launch_origins.is_empty() {
+    return Ok(None);
+  }
+
+  // Launchers commonly start the actual application as a child process,
+  // so the parent process is checked as well. A failed lookup of the
+  // parent is treated as there being none, so that it doesn't prevent
+  // the window from being managed.
+  let process_id = native_window.process_id();
+  let parent_process_id =
+    Platform::parent_process_id(process_id).ok().flatten();
+
+  let origin = state.launch_origins.iter().find(|origin| {
+    origin.process_id == process_id
+      || Some(origin.process_id) == parent_process_id
+  });
+
+  let Some(workspace_name) =
+    origin.map(|origin| origin.workspace_name.clone())
+  else {
+    return Ok(None);
+  };
+
+  if state.workspace_by_name(&workspace_name).is_none() {
+    // Skip if the workspace has been removed from the config.
+    if config.workspace_config_index(&workspace_name).is_none() {
+      return Ok(None);
+    }
+
+    activate_workspace(Some(&workspace_name), None, state, config)?;
+  }
+
+  Ok(state.workspace_by_name(&workspace_name))
+}
@@ -0,0 +1,38 @@
+use wm_common::InvokeCommand;
+use wm_platform::{CommandPaletteEntry, Platform};
+
+use crate::{user_config::UserConfig, wm_state::WmState};
+
+/// Shows the command palette with all runnable WM commands, as well as
+/// commands for focusing and moving to each workspace.
+pub fn show_command_palette(state: &WmState, config: &UserConfig) {
+  let command_entries = InvokeCommand::runnable_commands()
+    .into_iter()
+    .filter(|command| command != "wm-show-command-palette")
+    .map(|command| CommandPaletteEntry {
+      label: command.clone(),
+      command,
+    });
+
+  let workspace_entries =
+    config.value.workspaces.iter().flat_map(|workspace| {
+      let display_name =
+        workspace.display_name.as_ref().unwrap_or(&workspace.name);
+
+      [
+        CommandPaletteEntry {
+          label: format!("Focus workspace: {display_name}"),
+          command: format!("focus --workspace {}", workspace.name),
+        },
+        CommandPaletteEntry {
+          label: format!("Move window to workspace: {display_name}"),
+          command: format!("move --workspace {}", workspace.name),
+        },
+      ]
+    });
+
+  // List workspaces first, since they're most commonly used.
+  let entries = workspace_entries.chain(command_entries).collect();
+
+  Platform::show_command_palette(entries, state.overlay_command_tx());
+}
@@ -0,0 +1,159 @@
+use wm_common::{InvokeCommand, KeybindingConfig};
+use wm_platform::{KeybindingSection, Platform};
+
+use crate::{user_config::UserConfig, wm_state::WmState};
+
+/// Titles of the sections that keybindings are grouped into, in the
+/// order that they're shown.
+const SECTION_TITLES: [&str; 7] = [
+  "Focus",
+  "Move",
+  "Resize",
+  "Window state",
+  "Launch",
+  "General",
+  "Other",
+];
+
+/// Shows an overlay listing the active keybindings grouped by category.
+///
+/// Keybindings of the active binding mode are listed first, since they
+/// take precedence over the other keybindings.
+pub fn show_keybindings(state: &WmState, config: &UserConfig) {
+  let mut sections = state
+    .binding_modes
+    .iter()
+    .map(|binding_mode| KeybindingSection {
+      title: format!(
+        "Binding mode: {}",
+        binding_mode
+          .display_name
+          .as_ref()
+          .unwrap_or(&binding_mode.name)
+      ),
+      keybindings: binding_mode
+        .keybindings
+        .iter()
+        .map(format_keybinding)
+        .collect(),
+    })
+    .collect::<Vec<_>>();
+
+  for title in SECTION_TITLES {
+    let keybindings = config
+      .value
+      .keybindings
+      .iter()
+      .filter(|keybinding| keybinding_category(keybinding) == title)
+      .map(format_keybinding)
+      .collect::<Vec<_>>();
+
+    if !keybindings.is_empty() {
+      sections.push(KeybindingSection {
+        title: title.to_string(),
+        keybindings,
+      });
+    }
+  }
+
+  Platform::show_keybindings_overlay(sections);
+}
+
+/// Gets the section title for a keybinding based on its first command.
+fn keybinding_category(keybinding: &KeybindingConfig) -> &'static str {
+  match keybinding.commands.first() {
+    Some(
+      InvokeCommand::ActivateWorkspaceAtIndex { .. }
+      | InvokeCommand::Focus(_)
+      | InvokeCommand::FocusOverflowNext
+      | InvokeCommand::HintWindows
+      | InvokeCommand::ScrollWorkspace { .. }
+      | InvokeCommand::WmCycleFocus { .. },
+    ) => "Focus",
+    Some(
+      InvokeCommand::Move(_)
+      | InvokeCommand::MoveToMonitor { .. }
+      | InvokeCommand::MoveWorkspace { .. }
+      | InvokeCommand::GatherWindows
+      | InvokeCommand::SendHome
+      | InvokeCommand::Position(_)
+      | InvokeCommand::Center
+      | InvokeCommand::Snap { .. },
+    ) => "Move",
+    Some(
+      InvokeCommand::CycleRatio { .. }
+      | InvokeCommand::Resize(_)
+      | InvokeCommand::ResizeTo { .. }
+      | InvokeCommand::Size(_),
+    ) => "Resize",
+    Some(
+      InvokeCommand::AdoptWindow(_)
+      | InvokeCommand::Close { .. }
+      | InvokeCommand::CloseWorkspaceWindows { .. }
+      | InvokeCommand::Ignore
+      | InvokeCommand::ReleaseWindow(_)
+      | InvokeCommand::SelectAdd
+      | InvokeCommand::SelectClear
+      | InvokeCommand::SelectRun { .. }
+      | InvokeCommand::SetFloating { .. }
+      | InvokeCommand::SetFullscreen { .. }
+      | InvokeCommand::SetMinimized
+      | InvokeCommand::SetSizeConstraints(_)
+      | InvokeCommand::SetTiling
+      | InvokeCommand::SetTilingDirection { .. }
+      | InvokeCommand::SetBadge { .. }
+      | InvokeCommand::SetHideMethod { .. }
+      | InvokeCommand::SetTitleBarVisibility { .. }
+      | InvokeCommand::SetTransparency(_)
+      | InvokeCommand::ToggleFloating { .. }
+      | InvokeCommand::ToggleFullscreen { .. }
+      | InvokeCommand::ToggleMinimized
+      | InvokeCommand::ToggleTiling
+      | InvokeCommand::ToggleTilingDirection
+      | InvokeCommand::ToggleWorkspaceLayout,
+    ) => "Window state",
+    Some(
+      InvokeCommand::SendKeys { .. } | InvokeCommand::ShellExec { .. },
+    ) => "Launch",
+    Some(
+      InvokeCommand::AdjustGaps { .. }
+      | InvokeCommand::ApplyWorkspaceTemplate { .. }
+      | InvokeCommand::Overview
+      | InvokeCommand::Peek
+      | InvokeCommand::SetGaps { .. }
+      | InvokeCommand::ShowKeybindings
+      | InvokeCommand::ShowWindowSwitcher { .. }
+      | InvokeCommand::Unpeek
+      | InvokeCommand::WmDisableBindingMode { .. }
+      | InvokeCommand::WmDisableTimer { .. }
+      | InvokeCommand::WmEnableBindingMode { .. }
+      | InvokeCommand::WmEnableTimer { .. }
+      | InvokeCommand::WmExit
+      | InvokeCommand::WmRedraw
+      | InvokeCommand::WmReloadConfig
+      | InvokeCommand::WmRunTimer { .. }
+      | InvokeCommand::WmShowCommandPalette
+      | InvokeCommand::WmShowMenu { .. }
+      | InvokeCommand::WmToggleBindingMode { .. }
+      | InvokeCommand::WmToggleMouseEvents
+      | InvokeCommand::WmTogglePause
+      | InvokeCommand::WmToggleTimer { .. }
+      | InvokeCommand::WmToggleTrace,
+    ) => "General",
+    _ => "Other",
+  }
+}
+
+/// Formats a keybinding as a tuple of its keyboard shortcuts and
+/// commands.
+pub fn format_keybinding(
+  keybinding: &KeybindingConfig,
+) -> (String, String) {
+  let commands = keybinding
+    .commands
+    .iter()
+    .map(InvokeCommand::to_command_string)
+    .collect::<Vec<_>>();
+
+  (keybinding.bindings.join(", "), commands.join("; "))
+}
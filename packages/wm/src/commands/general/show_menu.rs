@@ -0,0 +1,39 @@
+use anyhow::Context;
+use wm_common::MenuItemConfig;
+use wm_platform::{LeaderMenuEntry, Platform};
+
+use crate::{user_config::UserConfig, wm_state::WmState};
+
+/// Shows the menu with the given name from the `menus` config.
+pub fn show_menu(
+  name: &str,
+  state: &WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let menu = config
+    .value
+    .menus
+    .iter()
+    .find(|menu| menu.name == name)
+    .with_context(|| format!("Menu '{name}' doesn't exist."))?;
+
+  Platform::show_leader_menu(
+    menu.title.clone().unwrap_or_else(|| menu.name.clone()),
+    menu_entries(&menu.items),
+    state.overlay_command_tx(),
+  );
+
+  Ok(())
+}
+
+fn menu_entries(items: &[MenuItemConfig]) -> Vec<LeaderMenuEntry> {
+  items
+    .iter()
+    .map(|item| LeaderMenuEntry {
+      key: item.key,
+      label: item.label.clone(),
+      commands: item.commands.clone(),
+      entries: menu_entries(&item.items),
+    })
+    .collect()
+}
@@ -0,0 +1,62 @@
+use anyhow::Context;
+use wm_common::WindowState;
+use wm_platform::{OverviewWindow, OverviewWorkspace, Platform};
+
+use crate::{
+  traits::{CommonGetters, PositionGetters, WindowGetters},
+  wm_state::WmState,
+};
+
+/// Shows the overview with previews of every workspace on the focused
+/// monitor. Minimized windows are excluded from the previews.
+pub fn show_overview(state: &WmState) -> anyhow::Result<()> {
+  let focused_workspace = state
+    .focused_container()
+    .and_then(|focused| focused.workspace())
+    .context("No focused workspace.")?;
+
+  let monitor = focused_workspace.monitor().context("No monitor.")?;
+  let workspaces = monitor.workspaces();
+
+  let selected = workspaces
+    .iter()
+    .position(|workspace| workspace.id() == focused_workspace.id())
+    .unwrap_or(0);
+
+  let workspaces = workspaces
+    .into_iter()
+    .map(|workspace| {
+      let config = workspace.config();
+
+      // Windows are ordered by focus, which approximates their z-order.
+      let windows = workspace
+        .descendant_focus_order()
+        .filter_map(|descendant| descendant.as_window_container().ok())
+        .filter(|window| !matches!(window.state(), WindowState::Minimized))
+        .filter_map(|window| {
+          Some(OverviewWindow {
+            rect: window.to_rect().ok()?,
+            window: window.native().clone(),
+            container_id: window.id().to_string(),
+          })
+        })
+        .collect();
+
+      OverviewWorkspace {
+        title: config.display_name.unwrap_or(config.name.clone()),
+        windows,
+        focus_command: format!("focus --workspace {}", config.name),
+        move_command: format!("move --workspace {}", config.name),
+      }
+    })
+    .collect();
+
+  Platform::show_overview(
+    monitor.to_rect()?,
+    workspaces,
+    selected,
+    state.overlay_command_tx(),
+  );
+
+  Ok(())
+}
@@ -0,0 +1,79 @@
+use wm_common::WindowState;
+use wm_platform::{Platform, WindowHint};
+
+use crate::{
+  traits::{CommonGetters, WindowGetters},
+  wm_state::WmState,
+};
+
+/// Characters that hint labels are made up of, ordered by how easy they
+/// are to reach from the home row.
+const HINT_CHARS: &str = "asdfghjklqwertyuiopzxcvbnm";
+
+/// Shows a label over every visible window on the displayed workspaces.
+/// Typing a label focuses its window.
+pub fn show_window_hints(state: &WmState) {
+  let mut windows = Vec::new();
+
+  for monitor in state.monitors() {
+    let Some(workspace) = monitor.displayed_workspace() else {
+      continue;
+    };
+
+    for window in workspace
+      .descendants()
+      .filter_map(|descendant| descendant.as_window_container().ok())
+      .filter(|window| !matches!(window.state(), WindowState::Minimized))
+    {
+      let native = window.native().clone();
+
+      if !native.is_visible().unwrap_or(false) {
+        continue;
+      }
+
+      if let Ok(rect) = native.frame_position() {
+        windows.push((window, rect));
+      }
+    }
+  }
+
+  // Label windows in reading order, so that labels are predictable for a
+  // given layout.
+  windows.sort_by_key(|(_, rect)| (rect.y(), rect.x()));
+
+  let hints = hint_labels(windows.len())
+    .into_iter()
+    .zip(windows)
+    .map(|(label, (window, rect))| WindowHint {
+      label,
+      rect,
+      command: format!("focus --container-id {}", window.id()),
+    })
+    .collect::<Vec<_>>();
+
+  if hints.is_empty() {
+    return;
+  }
+
+  Platform::show_window_hints(hints, state.overlay_command_tx());
+}
+
+/// Gets `count` distinct labels, none of which is a prefix of another.
+///
+/// Single characters are used if there are enough of them, and pairs of
+/// characters otherwise.
+fn hint_labels(count: usize) -> Vec<String> {
+  let chars = HINT_CHARS.chars().collect::<Vec<_>>();
+
+  if count <= chars.len() {
+    return chars[..count].iter().map(ToString::to_string).collect();
+  }
+
+  chars
+    .iter()
+    .flat_map(|first| {
+      chars.iter().map(move |second| format!("{first}{second}"))
+    })
+    .take(count)
+    .collect()
+}
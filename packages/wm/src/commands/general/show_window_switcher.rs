@@ -0,0 +1,47 @@
+use anyhow::Context;
+use wm_common::WindowSwitcherScope;
+use wm_platform::{Platform, WindowSwitcherEntry};
+
+use crate::{
+  models::Container,
+  traits::{CommonGetters, WindowGetters},
+  wm_state::WmState,
+};
+
+/// Shows the window switcher with windows ordered by most recently
+/// focused. Minimized windows are listed last.
+pub fn show_window_switcher(
+  scope: &WindowSwitcherScope,
+  state: &WmState,
+) -> anyhow::Result<()> {
+  let root: Container = match scope {
+    WindowSwitcherScope::Workspace => state
+      .focused_container()
+      .and_then(|focused| focused.workspace())
+      .context("No focused workspace.")?
+      .into(),
+    WindowSwitcherScope::All => state.root_container.clone().into(),
+  };
+
+  let mut windows = root
+    .descendant_focus_order()
+    .filter_map(|descendant| descendant.as_window_container().ok())
+    .collect::<Vec<_>>();
+
+  // Sorting is stable, so the focus order is otherwise kept.
+  windows
+    .sort_by_key(|window| window.native().is_minimized().unwrap_or(false));
+
+  let entries = windows
+    .into_iter()
+    .map(|window| WindowSwitcherEntry {
+      title: window.native().title().unwrap_or_default(),
+      window: window.native().clone(),
+      command: format!("focus --container-id {}", window.id()),
+    })
+    .collect();
+
+  Platform::show_window_switcher(entries, state.overlay_command_tx());
+
+  Ok(())
+}
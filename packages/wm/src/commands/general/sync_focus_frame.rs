@@ -0,0 +1,72 @@
+use wm_common::WindowState;
+use wm_platform::{FocusFrame, Platform};
+
+use crate::{
+  traits::{CommonGetters, PositionGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Shows a frame around the focused window while adapting to a
+/// high-contrast theme, so that focus is easier to spot than with the
+/// window border alone.
+///
+/// The frame is hidden if there's no focused window, or if it's
+/// fullscreen or minimized.
+pub fn sync_focus_frame(state: &WmState, config: &UserConfig) {
+  Platform::update_focus_frame(focus_frame(state, config));
+}
+
+fn focus_frame(
+  state: &WmState,
+  config: &UserConfig,
+) -> Option<FocusFrame> {
+  let is_effects_paused = state.game_window.is_some()
+    && config.value.general.game_mode.pause_effects;
+
+  if !state.accessibility.is_high_contrast || is_effects_paused {
+    return None;
+  }
+
+  // Frame the focused split container as a whole if there is one.
+  let rect = if let Some(split) = state.focused_split() {
+    split.to_rect().ok()?
+  } else {
+    let window = state.focused_container()?.as_window_container().ok()?;
+    let native = window.native().clone();
+
+    if matches!(
+      window.state(),
+      WindowState::Fullscreen(_) | WindowState::Minimized
+    ) || !native.is_visible().unwrap_or(false)
+    {
+      return None;
+    }
+
+    native.frame_position().ok()?
+  };
+
+  let scale_factor = state
+    .focused_container()
+    .and_then(|focused| focused.monitor())
+    .and_then(|monitor| monitor.native().scale_factor().ok());
+
+  let thickness = config
+    .value
+    .general
+    .accessibility
+    .focus_frame_thickness
+    .to_px(0, scale_factor);
+
+  if thickness <= 0 {
+    return None;
+  }
+
+  let (color, _) = Platform::high_contrast_border_colors();
+
+  Some(FocusFrame {
+    rect,
+    thickness,
+    color,
+  })
+}
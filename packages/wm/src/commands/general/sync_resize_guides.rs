@@ -0,0 +1,65 @@
+use wm_platform::{Platform, ResizeGuideSize, ResizeGuides};
+
+use super::format_keybinding;
+use crate::{
+  models::TilingContainer,
+  traits::{CommonGetters, PositionGetters, TilingSizeGetters},
+  wm_state::WmState,
+};
+
+/// Shows guides for resizing while a binding mode with `resize_guides`
+/// is active. Each tiling sibling of the focused container is labeled
+/// with its share of the parent, and the binding mode's keybindings are
+/// listed at the bottom of the monitor.
+///
+/// The guides are hidden once no such binding mode is active.
+pub fn sync_resize_guides(state: &WmState) {
+  Platform::update_resize_guides(resize_guides(state));
+}
+
+fn resize_guides(state: &WmState) -> Option<ResizeGuides> {
+  let binding_mode = state
+    .binding_modes
+    .iter()
+    .find(|binding_mode| binding_mode.resize_guides)?;
+
+  let focused = state.focused_container()?;
+  let monitor_rect = focused.monitor()?.to_rect().ok()?;
+
+  // Resize the focused split container as a whole if there is one.
+  let focused_tiling: Option<TilingContainer> = match state.focused_split()
+  {
+    Some(split) => Some(split.into()),
+    None => focused.as_tiling_container().ok(),
+  };
+
+  let sizes = focused_tiling
+    .and_then(|tiling| Some((tiling.parent()?, tiling.id())))
+    .map(|(parent, focused_id)| {
+      parent
+        .tiling_children()
+        .filter_map(|sibling| {
+          Some(ResizeGuideSize {
+            rect: sibling.to_rect().ok()?,
+            label: format!("{:.0}%", sibling.tiling_size() * 100.),
+            is_focused: sibling.id() == focused_id,
+          })
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+
+  Some(ResizeGuides {
+    title: binding_mode
+      .display_name
+      .clone()
+      .unwrap_or(binding_mode.name.clone()),
+    sizes,
+    keybindings: binding_mode
+      .keybindings
+      .iter()
+      .map(format_keybinding)
+      .collect(),
+    monitor_rect,
+  })
+}
@@ -0,0 +1,97 @@
+use anyhow::Context;
+use wm_common::WindowState;
+use wm_platform::{Platform, StatusBarState, StatusBarWorkspace};
+
+use crate::{
+  traits::{CommonGetters, PositionGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Updates the built-in status bar of each monitor to show its
+/// workspaces, the active binding mode, and the title of the last
+/// focused window in its displayed workspace.
+///
+/// Bars are only repainted if what they show has changed, and are closed
+/// if the status bar is disabled.
+pub fn sync_status_bars(
+  state: &WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let bar_config = &config.value.status_bar;
+
+  if !bar_config.enabled {
+    Platform::close_status_bars();
+    return Ok(());
+  }
+
+  let focused_workspace = state
+    .focused_container()
+    .and_then(|focused| focused.workspace())
+    .context("No focused workspace.")?;
+
+  let binding_mode = state.binding_modes.first().map(|binding_mode| {
+    binding_mode
+      .display_name
+      .clone()
+      .unwrap_or(binding_mode.name.clone())
+  });
+
+  let mut states = Vec::new();
+
+  for monitor in state.monitors() {
+    let monitor_rect = monitor.to_rect()?;
+    let scale_factor = monitor.native().scale_factor()?;
+
+    // Percentage heights are relative to the monitor, and percentage font
+    // sizes are relative to the bar.
+    let height = bar_config
+      .height
+      .to_px(monitor_rect.height(), Some(scale_factor));
+
+    #[allow(clippy::cast_precision_loss)]
+    let font_size =
+      bar_config.font_size.to_px(height, Some(scale_factor)) as f32;
+
+    let mut workspaces = monitor.workspaces();
+    config.sort_workspaces(&mut workspaces);
+
+    let workspaces = workspaces
+      .into_iter()
+      .map(|workspace| {
+        let config = workspace.config();
+
+        StatusBarWorkspace {
+          label: config.display_name.unwrap_or(config.name.clone()),
+          is_displayed: workspace.is_displayed(),
+          is_focused: workspace.id() == focused_workspace.id(),
+          focus_command: format!("focus --workspace {}", config.name),
+        }
+      })
+      .collect();
+
+    let window_title = monitor
+      .displayed_workspace()
+      .and_then(|workspace| {
+        workspace
+          .descendant_focus_order()
+          .filter_map(|descendant| descendant.as_window_container().ok())
+          .find(|window| !matches!(window.state(), WindowState::Minimized))
+      })
+      .and_then(|window| window.native().title().ok());
+
+    states.push(StatusBarState {
+      monitor_rect,
+      height,
+      font_size,
+      config: bar_config.clone(),
+      workspaces,
+      binding_mode: binding_mode.clone(),
+      window_title,
+    });
+  }
+
+  Platform::update_status_bars(states, &state.overlay_command_tx());
+
+  Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::{
+  commands::general::{disable_binding_mode, enable_binding_mode},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Disables the binding mode with the given name if it's active, and
+/// enables it otherwise.
+pub fn toggle_binding_mode(
+  name: &str,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let is_active =
+    state.binding_modes.iter().any(|config| config.name == name);
+
+  if is_active {
+    disable_binding_mode(Some(name), state);
+    Ok(())
+  } else {
+    enable_binding_mode(name, state, config)
+  }
+}
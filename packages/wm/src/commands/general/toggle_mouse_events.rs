@@ -0,0 +1,11 @@
+use wm_common::WmEvent;
+
+use crate::wm_state::WmState;
+
+/// Enables or disables mouse events (e.g. for focus follows cursor).
+pub fn toggle_mouse_events(state: &mut WmState) {
+  let is_enabled = !state.is_mouse_events_enabled;
+  state.is_mouse_events_enabled = is_enabled;
+
+  state.emit_event(WmEvent::MouseEventsChanged { is_enabled });
+}
@@ -0,0 +1,9 @@
+use tracing::info;
+
+use crate::wm_state::WmState;
+
+/// Enables or disables emitting trace events for debugging.
+pub fn toggle_trace(state: &mut WmState) {
+  state.is_trace_enabled = !state.is_trace_enabled;
+  info!("Trace mode enabled: {}.", state.is_trace_enabled);
+}
@@ -0,0 +1,88 @@
+use tracing::info;
+use wm_common::{Point, Rect, ZoomScope};
+use wm_platform::{Platform, Zoom};
+
+use crate::{
+  traits::{CommonGetters, PositionGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::{WmState, ZoomState},
+};
+
+/// Toggles the screen magnifier. The factor and scope default to
+/// `general.accessibility` in the user config.
+pub fn toggle_zoom(
+  factor: Option<f32>,
+  scope: Option<ZoomScope>,
+  state: &mut WmState,
+  config: &UserConfig,
+) {
+  let accessibility_config = &config.value.general.accessibility;
+
+  state.zoom = match state.zoom {
+    Some(_) => None,
+    None => Some(ZoomState {
+      factor: factor.unwrap_or(accessibility_config.zoom_factor).max(1.),
+      scope: scope.unwrap_or(accessibility_config.zoom_scope),
+    }),
+  };
+
+  info!("Setting zoom to {:?}.", state.zoom);
+  sync_zoom(state);
+}
+
+/// Moves the magnified region to the focused window or monitor, or
+/// restores the normal view if zoomed out.
+///
+/// The region is kept within the monitor of the focused container.
+pub fn sync_zoom(state: &WmState) {
+  Platform::update_zoom(
+    state.zoom.and_then(|zoom| zoom_region(zoom, state)),
+  );
+}
+
+fn zoom_region(zoom: ZoomState, state: &WmState) -> Option<Zoom> {
+  let focused = state.focused_container()?;
+  let monitor_rect = focused.monitor()?.to_rect().ok()?;
+
+  let center = match zoom.scope {
+    ZoomScope::Window => focused
+      .as_window_container()
+      .ok()
+      .and_then(|window| window.native().frame_position().ok())
+      .map_or(monitor_rect.center_point(), |rect| rect.center_point()),
+    ZoomScope::Monitor => monitor_rect.center_point(),
+  };
+
+  #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+  let (width, height) = (
+    (monitor_rect.width() as f32 / zoom.factor).round() as i32,
+    (monitor_rect.height() as f32 / zoom.factor).round() as i32,
+  );
+
+  let region = Rect::from_xy(
+    center.x - width / 2,
+    center.y - height / 2,
+    width,
+    height,
+  );
+
+  Some(Zoom {
+    factor: zoom.factor,
+    offset: clamp_to_rect(&region, &monitor_rect),
+  })
+}
+
+/// Gets the top-left corner of `region` after shifting it to lie within
+/// `bounds`.
+fn clamp_to_rect(region: &Rect, bounds: &Rect) -> Point {
+  Point {
+    x: region
+      .left
+      .min(bounds.right - region.width())
+      .max(bounds.left),
+    y: region
+      .top
+      .min(bounds.bottom - region.height())
+      .max(bounds.top),
+  }
+}
@@ -0,0 +1,57 @@
+use tracing::{info, warn};
+use wm_platform::Platform;
+
+use crate::{
+  traits::WindowGetters,
+  user_config::UserConfig,
+  wm_state::{AccessibilityState, WmState},
+};
+
+/// Resolves the high-contrast and reduced-motion adjustments from the
+/// system settings and the user config, and reapplies window effects if
+/// they've changed.
+pub fn update_accessibility(state: &mut WmState, config: &UserConfig) {
+  let accessibility_config = &config.value.general.accessibility;
+
+  let is_high_contrast_enabled = Platform::is_high_contrast_enabled()
+    .unwrap_or_else(|err| {
+      warn!("Failed to get high-contrast setting: {}", err);
+      false
+    });
+
+  let is_reduced_motion_enabled = Platform::is_reduced_motion_enabled()
+    .unwrap_or_else(|err| {
+      warn!("Failed to get animation setting: {}", err);
+      false
+    });
+
+  let accessibility = AccessibilityState {
+    is_high_contrast: accessibility_config
+      .high_contrast
+      .resolve(is_high_contrast_enabled),
+    is_reduced_motion: accessibility_config
+      .reduced_motion
+      .resolve(is_reduced_motion_enabled),
+  };
+
+  // High-contrast colors are reapplied on any change, since the system
+  // colors might have changed (e.g. when switching between high-contrast
+  // themes).
+  if accessibility != state.accessibility || accessibility.is_high_contrast
+  {
+    info!("Updating accessibility adjustments: {:?}", accessibility);
+
+    // Borders are left untouched by window effects if they're disabled in
+    // the config, so reset the high-contrast borders to system defaults.
+    if state.accessibility.is_high_contrast
+      && !accessibility.is_high_contrast
+    {
+      for window in state.windows() {
+        _ = window.native().set_border_color(None);
+      }
+    }
+
+    state.accessibility = accessibility;
+    state.pending_sync.queue_all_effects_update();
+  }
+}
@@ -0,0 +1,78 @@
+use tracing::info;
+use wm_common::{GameModeConfig, WmEvent};
+use wm_platform::{NativeWindow, Platform};
+
+use crate::{user_config::UserConfig, wm_state::WmState};
+
+/// Enters or exits game mode based on whether the foreground window is a
+/// game.
+///
+/// Changes that were deferred while in game mode are applied on exit.
+pub fn update_game_mode(state: &mut WmState, config: &UserConfig) {
+  let game_mode_config = &config.value.general.game_mode;
+  let foreground_window = Platform::foreground_window();
+
+  let game_window = (game_mode_config.enabled
+    && is_game_window(&foreground_window, game_mode_config))
+  .then_some(foreground_window);
+
+  if game_window == state.game_window {
+    return;
+  }
+
+  let process_name = game_window
+    .as_ref()
+    .and_then(|window| window.process_name().ok());
+
+  if let Some(process_name) = &process_name {
+    info!("Entering game mode for {process_name}.");
+  } else {
+    info!("Exiting game mode.");
+  }
+
+  // Redraw windows and reapply effects that were skipped while in game
+  // mode.
+  if game_window.is_none() {
+    state
+      .pending_sync
+      .queue_container_to_redraw(state.root_container.clone())
+      .queue_all_effects_update();
+  }
+
+  state.game_window = game_window;
+
+  state.emit_event(WmEvent::GameModeChanged {
+    is_active: state.game_window.is_some(),
+    process_name,
+  });
+}
+
+/// Whether the window is a game, either by its process name or by being
+/// a borderless window that covers its entire monitor.
+fn is_game_window(window: &NativeWindow, config: &GameModeConfig) -> bool {
+  let Ok(process_name) = window.process_name() else {
+    return false;
+  };
+
+  if config
+    .process_names
+    .iter()
+    .any(|name| name.eq_ignore_ascii_case(&process_name))
+  {
+    return true;
+  }
+
+  // The desktop and the WM's own overlays can also cover the monitor.
+  let is_excluded = *window == Platform::desktop_window()
+    || process_name.eq_ignore_ascii_case("glazewm")
+    || window
+      .class_name()
+      .is_ok_and(|class_name| class_name == "WorkerW");
+
+  config.detect_fullscreen
+    && !is_excluded
+    && !window.is_resizable()
+    && Platform::nearest_monitor(window)
+      .rect()
+      .is_ok_and(|rect| window.is_fullscreen(rect).unwrap_or(false))
+}
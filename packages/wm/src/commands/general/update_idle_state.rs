@@ -0,0 +1,80 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use wm_common::{CommandSource, WmEvent};
+use wm_platform::Platform;
+
+use crate::{
+  user_config::UserConfig, wm::WindowManager, wm_state::WmState,
+};
+
+/// Interval for checking whether the user has returned while idle.
+const ACTIVE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Checks how long it's been since the last keyboard or mouse input, and
+/// emits `UserIdle` or `UserActive` and runs the corresponding commands
+/// if the idle threshold has been crossed.
+///
+/// Schedules the next check via `state.idle_check_deadline`.
+pub fn update_idle_state(
+  state: &mut WmState,
+  config: &mut UserConfig,
+) -> anyhow::Result<()> {
+  let idle_config = config.value.general.idle.clone();
+
+  if !idle_config.enabled || idle_config.threshold_secs == 0 {
+    state.idle_check_deadline = None;
+    state.idle_started_at = None;
+    return Ok(());
+  }
+
+  // Retry after the poll interval if getting the idle duration fails.
+  state.idle_check_deadline = Some(Instant::now() + ACTIVE_POLL_INTERVAL);
+
+  let idle_duration = Platform::idle_duration()?;
+  let threshold = Duration::from_secs(idle_config.threshold_secs);
+  let is_idle = idle_duration >= threshold;
+
+  // Only check again once the threshold could've been reached, unless
+  // already idle, in which case input is polled for.
+  state.idle_check_deadline = Some(if is_idle {
+    Instant::now() + ACTIVE_POLL_INTERVAL
+  } else {
+    Instant::now() + threshold.saturating_sub(idle_duration)
+  });
+
+  let commands = match (is_idle, state.idle_started_at) {
+    (true, None) => {
+      state.idle_started_at = Instant::now().checked_sub(idle_duration);
+
+      state.emit_event(WmEvent::UserIdle {
+        idle_secs: idle_duration.as_secs(),
+      });
+
+      idle_config.idle_commands
+    }
+    (false, Some(idle_started_at)) => {
+      state.idle_started_at = None;
+
+      state.emit_event(WmEvent::UserActive {
+        idle_secs: idle_started_at
+          .elapsed()
+          .saturating_sub(idle_duration)
+          .as_secs(),
+      });
+
+      idle_config.active_commands
+    }
+    _ => return Ok(()),
+  };
+
+  WindowManager::run_commands(
+    &commands,
+    state.focused_container().context("No focused container.")?,
+    Some(CommandSource::Idle),
+    state,
+    config,
+  )?;
+
+  Ok(())
+}
@@ -0,0 +1,116 @@
+use std::{
+  collections::HashSet,
+  time::{Duration, Instant},
+};
+
+use wm_common::{MediaUsage, WindowRuleEvent, WmEvent};
+use wm_platform::{Capability, CapabilityUsers, NativeWindow, Platform};
+
+use crate::{
+  commands::window::run_window_rules,
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Checks which windows are playing audio or using the microphone,
+/// camera, or screen capture. For each window where this has changed,
+/// runs the `media_usage_change` window rules, and emits a
+/// `WindowAudioChanged` event if audio playback has changed.
+///
+/// Schedules the next check via `state.media_check_deadline`.
+pub fn update_media_usage(
+  state: &mut WmState,
+  config: &mut UserConfig,
+) -> anyhow::Result<()> {
+  let poll_interval_ms = config.value.general.media_poll_interval_ms;
+
+  // Media usage of all windows is reset if detection has been disabled.
+  let media_users = if poll_interval_ms == 0 {
+    state.media_check_deadline = None;
+    None
+  } else {
+    state.media_check_deadline =
+      Some(Instant::now() + Duration::from_millis(poll_interval_ms));
+
+    Some(MediaUsers::query()?)
+  };
+
+  for window in state.windows() {
+    // Skip windows that were detached by the rules of a previous window.
+    if window.is_detached() {
+      continue;
+    }
+
+    let media_usage = media_users
+      .as_ref()
+      .map(|users| users.media_usage(&window.native()))
+      .unwrap_or_default();
+
+    let prev_media_usage = window.media_usage();
+
+    if prev_media_usage == media_usage {
+      continue;
+    }
+
+    window.set_media_usage(media_usage);
+
+    if prev_media_usage.is_playing_audio != media_usage.is_playing_audio {
+      state.emit_event(WmEvent::WindowAudioChanged {
+        updated_window: window.to_dto()?,
+        is_playing_audio: media_usage.is_playing_audio,
+      });
+
+      if config.value.window_effects.audio_windows.border.enabled {
+        state.pending_sync.queue_all_effects_update();
+      }
+    }
+
+    run_window_rules(
+      window,
+      &WindowRuleEvent::MediaUsageChange,
+      state,
+      config,
+    )?;
+  }
+
+  Ok(())
+}
+
+/// Processes and apps that are currently playing or capturing media.
+struct MediaUsers {
+  audio_process_ids: HashSet<u32>,
+  microphone: CapabilityUsers,
+  camera: CapabilityUsers,
+  screen_capture: CapabilityUsers,
+}
+
+impl MediaUsers {
+  fn query() -> anyhow::Result<Self> {
+    // The consent store only has keys for capabilities that have been
+    // used at some point, so a missing key means that it's not in use.
+    let capability_users = |capability| {
+      Platform::capability_users(capability).unwrap_or_default()
+    };
+
+    Ok(Self {
+      audio_process_ids: Platform::audio_process_ids()?,
+      microphone: capability_users(Capability::Microphone),
+      camera: capability_users(Capability::Camera),
+      screen_capture: capability_users(Capability::ScreenCapture),
+    })
+  }
+
+  fn media_usage(&self, native_window: &NativeWindow) -> MediaUsage {
+    let process_path = native_window.process_path().unwrap_or_default();
+
+    MediaUsage {
+      is_playing_audio: self
+        .audio_process_ids
+        .contains(&native_window.process_id()),
+      is_using_microphone: self.microphone.contains(&process_path),
+      is_using_camera: self.camera.contains(&process_path),
+      is_recording_screen: self.screen_capture.contains(&process_path),
+    }
+  }
+}
@@ -2,10 +2,12 @@ mod add_monitor;
 mod focus_monitor;
 mod remove_monitor;
 mod sort_monitors;
+mod toggle_monitor_management;
 mod update_monitor;
 
 pub use add_monitor::*;
 pub use focus_monitor::*;
 pub use remove_monitor::*;
 pub use sort_monitors::*;
+pub use toggle_monitor_management::*;
 pub use update_monitor::*;
@@ -0,0 +1,166 @@
+use anyhow::{bail, Context};
+use tracing::{info, warn};
+use wm_common::MonitorRuleConfig;
+use wm_platform::{NativeMonitor, Platform};
+
+use crate::{
+  commands::window::manage_window,
+  events::handle_display_settings_changed, user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Toggles whether the WM manages the monitor at the given index, or the
+/// monitor under the cursor if no index is given.
+///
+/// Workspaces on a monitor that becomes unmanaged are moved to other
+/// monitors. Windows on a monitor that becomes managed are managed.
+pub fn toggle_monitor_management(
+  index: Option<u32>,
+  state: &mut WmState,
+  config: &mut UserConfig,
+) -> anyhow::Result<()> {
+  let native_monitors = Platform::sorted_monitors()?;
+
+  let target_monitor = if let Some(index) = index {
+    native_monitors
+      .get(index as usize)
+      .with_context(|| format!("No monitor at index {index}."))?
+  } else {
+    let cursor_position = Platform::mouse_position()?;
+
+    native_monitors
+      .iter()
+      .find(|monitor| {
+        monitor
+          .rect()
+          .is_ok_and(|rect| rect.contains_point(&cursor_position))
+      })
+      .context("No monitor under cursor.")?
+  };
+
+  let is_managed = !state.unmanaged_monitors.contains(target_monitor);
+  let managed_count = native_monitors
+    .len()
+    .saturating_sub(state.unmanaged_monitors.len());
+
+  if is_managed && managed_count <= 1 {
+    bail!("Cannot unmanage the only managed monitor.");
+  }
+
+  info!(
+    "Toggling management of monitor {}: {}.",
+    target_monitor.device_name()?,
+    !is_managed
+  );
+
+  state
+    .monitor_management_overrides
+    .insert(monitor_key(target_monitor)?, !is_managed);
+
+  handle_display_settings_changed(state, config)?;
+
+  // Manage existing windows on the newly managed monitor.
+  if !is_managed {
+    for native_window in Platform::manageable_windows()? {
+      if state.window_from_native(&native_window).is_none()
+        && !state.ignored_windows.contains(&native_window)
+        && Platform::nearest_monitor(&native_window) == *target_monitor
+      {
+        manage_window(native_window, None, state, config)?;
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Gets the native monitors that the WM should manage, in order of
+/// position. Monitors that shouldn't be managed are stored in
+/// `state.unmanaged_monitors`.
+///
+/// Falls back to managing all monitors if none would otherwise be
+/// managed.
+pub fn managed_native_monitors(
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<Vec<NativeMonitor>> {
+  let (managed_monitors, unmanaged_monitors): (Vec<_>, Vec<_>) =
+    Platform::sorted_monitors()?
+      .into_iter()
+      .enumerate()
+      .partition(|(index, native_monitor)| {
+        is_monitor_managed(native_monitor, *index, state, config)
+      });
+
+  let managed_monitors =
+    managed_monitors.into_iter().map(|(_, monitor)| monitor);
+
+  let unmanaged_monitors =
+    unmanaged_monitors.into_iter().map(|(_, monitor)| monitor);
+
+  if managed_monitors.len() == 0 {
+    warn!("No monitors are managed. Falling back to managing all.");
+    state.unmanaged_monitors = Vec::new();
+    return Ok(unmanaged_monitors.collect());
+  }
+
+  state.unmanaged_monitors = unmanaged_monitors.collect();
+  Ok(managed_monitors.collect())
+}
+
+/// Whether the monitor should be managed based on runtime overrides and
+/// the monitor rules in the user config. Monitors are managed by default.
+fn is_monitor_managed(
+  native_monitor: &NativeMonitor,
+  index: usize,
+  state: &WmState,
+  config: &UserConfig,
+) -> bool {
+  let is_overridden = monitor_key(native_monitor)
+    .ok()
+    .and_then(|key| state.monitor_management_overrides.get(&key).copied());
+
+  is_overridden.unwrap_or_else(|| {
+    config
+      .value
+      .monitor_rules
+      .iter()
+      .find(|rule| is_rule_match(rule, native_monitor, index))
+      .is_none_or(|rule| rule.manage)
+  })
+}
+
+fn is_rule_match(
+  rule: &MonitorRuleConfig,
+  native_monitor: &NativeMonitor,
+  index: usize,
+) -> bool {
+  let is_device_match =
+    rule.match_device_id.as_ref().is_none_or(|device_id| {
+      [
+        native_monitor.hardware_id().ok().flatten(),
+        native_monitor.device_path().ok().flatten(),
+        native_monitor.device_name().ok(),
+      ]
+      .into_iter()
+      .flatten()
+      .any(|id| id.eq_ignore_ascii_case(device_id))
+    });
+
+  let is_index_match = rule
+    .match_index
+    .is_none_or(|match_index| match_index as usize == index);
+
+  (rule.match_device_id.is_some() || rule.match_index.is_some())
+    && is_device_match
+    && is_index_match
+}
+
+/// Key to identify a monitor by across display changes. Monitor handles
+/// can change over time, so the device path is used where available.
+fn monitor_key(native_monitor: &NativeMonitor) -> anyhow::Result<String> {
+  match native_monitor.device_path()? {
+    Some(device_path) => Ok(device_path.clone()),
+    None => Ok(native_monitor.device_name()?.clone()),
+  }
+}
@@ -0,0 +1,72 @@
+use anyhow::{bail, Context};
+use tracing::info;
+use wm_common::InvokeWindowTargetCommand;
+use wm_platform::NativeWindow;
+
+use crate::{
+  commands::window::manage_window, user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Forcibly manages a window that is currently unmanaged, including
+/// windows that have previously been ignored.
+pub fn adopt_window(
+  target: &InvokeWindowTargetCommand,
+  state: &mut WmState,
+  config: &mut UserConfig,
+) -> anyhow::Result<()> {
+  let native_window = match (&target.hwnd, &target.process) {
+    (Some(handle), _) => NativeWindow::new(*handle),
+    (None, Some(process)) => unmanaged_window_by_process(process, state)?,
+    (None, None) => bail!("Either `--hwnd` or `--process` is required."),
+  };
+
+  if state.window_from_native(&native_window).is_some() {
+    bail!("Window {} is already managed.", native_window.handle);
+  }
+
+  if !native_window.is_visible()? {
+    bail!("Window {} is not visible.", native_window.handle);
+  }
+
+  info!("Adopting window: {}", native_window.handle);
+
+  state
+    .ignored_windows
+    .retain(|ignored| *ignored != native_window);
+
+  manage_window(native_window.clone(), None, state, config)?;
+
+  // Window rules (e.g. an `ignore` rule) might have prevented the window
+  // from being managed.
+  if state.window_from_native(&native_window).is_none() {
+    bail!(
+      "Window {} was not managed due to window rules.",
+      native_window.handle
+    );
+  }
+
+  Ok(())
+}
+
+/// Gets the topmost visible window of the given process that isn't
+/// currently managed.
+fn unmanaged_window_by_process(
+  process: &str,
+  state: &WmState,
+) -> anyhow::Result<NativeWindow> {
+  let window =
+    wm_platform::available_windows()?
+      .into_iter()
+      .find(|window| {
+        window.is_visible().unwrap_or(false)
+          && window
+            .process_name()
+            .is_ok_and(|name| name.eq_ignore_ascii_case(process))
+          && state.window_from_native(window).is_none()
+      });
+
+  window.with_context(|| {
+    format!("No unmanaged window found for process '{process}'.")
+  })
+}
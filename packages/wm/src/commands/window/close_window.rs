@@ -0,0 +1,183 @@
+use std::{
+  thread,
+  time::{Duration, Instant},
+};
+
+use tracing::{info, warn};
+use wm_platform::{NativeWindow, Platform};
+
+use crate::{
+  models::{WindowContainer, Workspace},
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Max duration to wait for windows to close before their processes are
+/// terminated when force closing.
+const FORCE_CLOSE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Interval between checks of whether force closed windows have closed.
+const FORCE_CLOSE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Timeout for a window to process a message before it's considered
+/// hung.
+const RESPONSIVE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Closes the given windows.
+///
+/// If `force` is set, the processes of windows that are still open and
+/// not responding after a timeout are terminated. Processes that have
+/// other windows open are never terminated.
+pub fn close_windows(
+  windows: &[WindowContainer],
+  force: bool,
+  state: &WmState,
+) {
+  let (native_windows, terminable_windows) =
+    close_targets(windows, force, state);
+
+  send_close(&native_windows);
+
+  if !terminable_windows.is_empty() {
+    thread::spawn(move || {
+      terminate_unresponsive(&native_windows, &terminable_windows);
+    });
+  }
+}
+
+/// Closes all windows on the given workspace.
+///
+/// Unless disabled via `general.confirm_close_workspace_windows`, a
+/// confirmation dialog is shown first.
+pub fn close_workspace_windows(
+  workspace: &Workspace,
+  force: bool,
+  state: &WmState,
+  config: &UserConfig,
+) {
+  let windows = workspace
+    .descendants()
+    .filter_map(|descendant| descendant.as_window_container().ok())
+    .collect::<Vec<_>>();
+
+  if windows.is_empty() {
+    return;
+  }
+
+  if !config.value.general.confirm_close_workspace_windows {
+    close_windows(&windows, force, state);
+    return;
+  }
+
+  let (native_windows, terminable_windows) =
+    close_targets(&windows, force, state);
+
+  let message = format!(
+    "Close {} window(s) on workspace '{}'?",
+    windows.len(),
+    workspace.config().name
+  );
+
+  // The dialog blocks until dismissed, so it's shown on a separate thread
+  // to avoid blocking the event loop.
+  thread::spawn(move || {
+    if Platform::show_confirm_dialog("Close workspace windows", &message) {
+      send_close(&native_windows);
+      terminate_unresponsive(&native_windows, &terminable_windows);
+    }
+  });
+}
+
+/// Gets the native windows to close, and the subset of them whose
+/// processes may be terminated if they don't close.
+///
+/// Processes with other managed windows that aren't being closed (e.g. a
+/// browser with windows on other workspaces) are excluded.
+fn close_targets(
+  windows: &[WindowContainer],
+  force: bool,
+  state: &WmState,
+) -> (Vec<NativeWindow>, Vec<NativeWindow>) {
+  let native_windows = windows
+    .iter()
+    .map(|window| window.native().clone())
+    .collect::<Vec<_>>();
+
+  if !force {
+    return (native_windows, Vec::new());
+  }
+
+  let other_process_ids = state
+    .windows()
+    .into_iter()
+    .filter(|window| !native_windows.contains(&window.native()))
+    .map(|window| window.native().process_id())
+    .collect::<Vec<_>>();
+
+  let terminable_windows = native_windows
+    .iter()
+    .filter(|window| !other_process_ids.contains(&window.process_id()))
+    .cloned()
+    .collect();
+
+  (native_windows, terminable_windows)
+}
+
+fn send_close(windows: &[NativeWindow]) {
+  for window in windows {
+    // Window handle might no longer be valid here.
+    if let Err(err) = window.close() {
+      warn!("Failed to close window: {:?}", err);
+    }
+  }
+}
+
+/// Waits for the windows to close, and terminates the processes of any
+/// terminable windows that are still open and not responding.
+fn terminate_unresponsive(
+  closing_windows: &[NativeWindow],
+  terminable_windows: &[NativeWindow],
+) {
+  if terminable_windows.is_empty() {
+    return;
+  }
+
+  let closed_at = Instant::now();
+
+  while closed_at.elapsed() < FORCE_CLOSE_TIMEOUT
+    && terminable_windows.iter().any(NativeWindow::is_valid)
+  {
+    thread::sleep(FORCE_CLOSE_POLL_INTERVAL);
+  }
+
+  for window in terminable_windows.iter().filter(|w| w.is_valid()) {
+    // Windows that still respond are likely prompting the user (e.g. to
+    // save unsaved changes), so they're left open.
+    if window.is_responsive(RESPONSIVE_TIMEOUT) {
+      info!("Not terminating responsive window: {}", window.handle);
+      continue;
+    }
+
+    let has_other_windows =
+      window.sibling_process_windows().map_or(true, |siblings| {
+        siblings
+          .iter()
+          .any(|sibling| !closing_windows.contains(sibling))
+      });
+
+    if has_other_windows {
+      warn!(
+        "Not terminating process of window {}, since it has other \
+         windows open.",
+        window.handle
+      );
+      continue;
+    }
+
+    match window.terminate_process() {
+      Ok(()) => info!("Terminated process of window: {}", window.handle),
+      Err(err) => warn!("Failed to terminate process: {:?}", err),
+    }
+  }
+}
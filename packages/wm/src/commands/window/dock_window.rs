@@ -0,0 +1,59 @@
+use anyhow::Context;
+use wm_common::{FloatingStateConfig, WindowState};
+
+use super::update_window_state;
+use crate::{
+  models::WindowContainer,
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Docks the window to its workspace if it matches the workspace's
+/// `dock` config and no other window is docked there yet.
+///
+/// Returns the window, which is changed to floating if it gets docked.
+pub fn dock_window_if_matching(
+  window: WindowContainer,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<WindowContainer> {
+  let workspace = window.workspace().context("No workspace.")?;
+
+  let Some(dock_config) = workspace.config().dock else {
+    return Ok(window);
+  };
+
+  if workspace.dock_window().is_some() {
+    return Ok(window);
+  }
+
+  let window_title = window.native().title()?;
+  let window_class = window.native().class_name()?;
+  let window_process = window.native().process_name()?;
+
+  let is_match = dock_config.match_window.iter().any(|match_config| {
+    match_config.is_match(&window_process, &window_class, &window_title)
+  });
+
+  if !is_match {
+    return Ok(window);
+  }
+
+  let window = update_window_state(
+    window,
+    WindowState::Floating(FloatingStateConfig {
+      centered: false,
+      shown_on_top: false,
+    }),
+    state,
+    config,
+  )?;
+
+  workspace.set_dock_window_id(Some(window.id()));
+
+  // Tiling windows need to be redrawn to make room for the dock.
+  state.pending_sync.queue_container_to_redraw(workspace);
+
+  Ok(window)
+}
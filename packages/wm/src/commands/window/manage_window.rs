@@ -1,21 +1,36 @@
 use anyhow::Context;
 use tracing::info;
 use wm_common::{
-  try_warn, LengthValue, RectDelta, WindowRuleEvent, WindowState, WmEvent,
+  try_warn, LengthValue, MediaUsage, RectDelta, TilingDirection,
+  WindowRuleEvent, WindowState, WmEvent, WorkspaceLayout,
+  WorkspaceTilingDirection,
 };
 use wm_platform::NativeWindow;
 
 use crate::{
   commands::{
-    container::{attach_container, set_focused_descendant},
-    window::run_window_rules,
+    container::{
+      attach_container, set_focused_descendant, wrap_in_split_container,
+    },
+    window::{
+      dock_window_if_matching, move_window_to_app_workspace,
+      run_window_rules,
+    },
+    workspace::{
+      apply_workspace_template, enforce_max_visible_tiles,
+      fills_template_slot,
+    },
   },
+  floating_geometry::FloatingGeometry,
   models::{
-    Container, Monitor, NonTilingWindow, TilingWindow, WindowContainer,
+    Container, Monitor, NonTilingWindow, SplitContainer, TilingWindow,
+    WindowContainer,
+  },
+  traits::{
+    CommonGetters, PositionGetters, TilingDirectionGetters, WindowGetters,
   },
-  traits::{CommonGetters, PositionGetters, WindowGetters},
   user_config::UserConfig,
-  wm_state::WmState,
+  wm_state::{ManagedOrigin, WmState},
 };
 
 pub fn manage_window(
@@ -33,6 +48,8 @@ pub fn manage_window(
   // rules will be run as if the window is focused.
   set_focused_descendant(&window.clone().into(), None);
 
+  let initial_workspace = window.workspace().context("No workspace.")?;
+
   // Window might be detached if `ignore` command has been invoked.
   let updated_window = run_window_rules(
     window.clone(),
@@ -44,10 +61,61 @@ pub fn manage_window(
   if let Some(window) = updated_window {
     info!("New window managed: {window}");
 
+    // Group the window with others of its application, unless a window
+    // rule has already moved it to another workspace.
+    if config.value.general.workspace_per_app
+      && window
+        .workspace()
+        .is_some_and(|workspace| workspace.id() == initial_workspace.id())
+    {
+      move_window_to_app_workspace(window.clone(), state, config)?;
+    }
+
+    let window = dock_window_if_matching(window, state, config)?;
+
+    if fills_template_slot(&window)? {
+      apply_workspace_template(
+        &window.workspace().context("No workspace.")?,
+        false,
+        state,
+        config,
+      )?;
+    }
+
+    state.managed_origins.insert(
+      window.id(),
+      ManagedOrigin {
+        workspace_name: window
+          .workspace()
+          .context("No workspace.")?
+          .config()
+          .name,
+        state: window.state(),
+      },
+    );
+
+    // Restore the last-known floating geometry of the application. This
+    // is done after running window rules, since the window might've been
+    // changed to floating by a rule.
+    if FloatingGeometry::is_tracked(&window, config) {
+      if let Some(rect) = state.floating_geometry.get(&window.native()) {
+        window.set_floating_placement(rect.clone());
+        window.set_has_custom_floating_placement(true);
+      }
+    }
+
     state.emit_event(WmEvent::WindowManaged {
       managed_window: window.to_dto()?,
     });
 
+    // Window count of the workspace has changed.
+    state.emit_event(WmEvent::WorkspaceUpdated {
+      updated_workspace: window
+        .workspace()
+        .context("No workspace.")?
+        .to_dto()?,
+    });
+
     // OS focus should be set to the newly added window in case it's not
     // already focused.
     state.pending_sync.queue_focus_change();
@@ -66,9 +134,18 @@ pub fn manage_window(
       if window.state() == WindowState::Tiling {
         window.parent().context("No parent.")?
       } else {
-        window.into()
+        window.clone().into()
       },
     );
+
+    if window.state() == WindowState::Tiling {
+      enforce_max_visible_tiles(
+        &window.workspace().context("No workspace.")?,
+        Some(&window),
+        state,
+        config,
+      )?;
+    }
   }
 
   Ok(())
@@ -89,14 +166,24 @@ fn create_window(
     .context("No nearest workspace.")?;
 
   let gaps_config = config.value.gaps.clone();
-  let window_state =
+  let mut window_state =
     window_state_to_create(&native_window, &nearest_monitor, config)?;
 
+  // Windows are floating by default on workspaces with a floating layout.
+  let is_floating_layout =
+    is_floating_layout(target_parent.as_ref(), state);
+
+  if is_floating_layout && window_state == WindowState::Tiling {
+    window_state = WindowState::Floating(
+      config.value.window_behavior.state_defaults.floating.clone(),
+    );
+  }
+
   // Attach the new window as the first child of the target parent (if
   // provided), otherwise, add as a sibling of the focused container.
   let (target_parent, target_index) = match target_parent {
     Some(parent) => (parent, 0),
-    None => insertion_target(&window_state, state)?,
+    None => insertion_target(&window_state, state, config)?,
   };
 
   let target_workspace =
@@ -150,6 +237,10 @@ fn create_window(
       gaps_config,
       Vec::new(),
       None,
+      None,
+      MediaUsage::default(),
+      None,
+      None,
     )
     .into(),
     _ => NonTilingWindow::new(
@@ -163,6 +254,10 @@ fn create_window(
       false,
       Vec::new(),
       None,
+      None,
+      MediaUsage::default(),
+      None,
+      None,
     )
     .into(),
   };
@@ -200,10 +295,7 @@ fn window_state_to_create(
     .displayed_workspace()
     .context("No Workspace.")?;
 
-  let monitor_rect = if config
-    .outer_gaps_for_workspace(&nearest_workspace)
-    .is_significant()
-  {
+  let monitor_rect = if nearest_workspace.outer_gaps().is_significant() {
     nearest_monitor.native().working_rect()?.clone()
   } else {
     nearest_monitor.to_rect()?
@@ -230,6 +322,23 @@ fn window_state_to_create(
   Ok(WindowState::default_from_config(&config.value))
 }
 
+/// Whether the window is added to a workspace with a floating layout.
+fn is_floating_layout(
+  target_parent: Option<&Container>,
+  state: &WmState,
+) -> bool {
+  let workspace = match target_parent {
+    Some(parent) => parent.workspace(),
+    None => state
+      .focused_container()
+      .and_then(|focused| focused.workspace()),
+  };
+
+  workspace.is_some_and(|workspace| {
+    workspace.layout() == WorkspaceLayout::Floating
+  })
+}
+
 /// Gets where to insert a new window in the container tree.
 ///
 /// Rules:
@@ -244,6 +353,7 @@ fn window_state_to_create(
 fn insertion_target(
   window_state: &WindowState,
   state: &WmState,
+  config: &UserConfig,
 ) -> anyhow::Result<(Container, usize)> {
   let focused_container =
     state.focused_container().context("No focused container.")?;
@@ -262,6 +372,12 @@ fn insertion_target(
     };
 
     if let Some(sibling) = sibling {
+      if focused_workspace.config().tiling_direction
+        == Some(WorkspaceTilingDirection::Auto)
+      {
+        return auto_insertion_target(&sibling, config);
+      }
+
       return Ok((
         sibling.parent().context("No parent.")?,
         sibling.index() + 1,
@@ -275,3 +391,49 @@ fn insertion_target(
     focused_workspace.child_count(),
   ))
 }
+
+/// Gets where to insert a tiling window next to the given sibling, such
+/// that the sibling gets split along its longer axis.
+///
+/// The sibling's parent has its tiling direction changed if the sibling
+/// is its only child, otherwise the sibling is wrapped in a new split
+/// container.
+fn auto_insertion_target(
+  sibling: &Container,
+  config: &UserConfig,
+) -> anyhow::Result<(Container, usize)> {
+  let sibling_rect = sibling.to_rect()?;
+
+  let tiling_direction = if sibling_rect.height() > sibling_rect.width() {
+    TilingDirection::Vertical
+  } else {
+    TilingDirection::Horizontal
+  };
+
+  let parent = sibling
+    .direction_container()
+    .context("No direction container.")?;
+
+  if parent.tiling_direction() != tiling_direction {
+    if sibling.tiling_siblings().count() == 0 {
+      parent.set_tiling_direction(tiling_direction);
+    } else {
+      let tiling_window = sibling
+        .as_tiling_window()
+        .context("Sibling is not a tiling window.")?;
+
+      let split_container =
+        SplitContainer::new(tiling_direction, config.value.gaps.clone());
+
+      wrap_in_split_container(
+        &split_container,
+        &parent.into(),
+        &[tiling_window.clone().into()],
+      )?;
+
+      return Ok((split_container.into(), 1));
+    }
+  }
+
+  Ok((parent.into(), sibling.index() + 1))
+}
@@ -1,20 +1,38 @@
+mod adopt_window;
+mod close_window;
+mod dock_window;
 mod ignore_window;
 mod manage_window;
 mod move_window_in_direction;
+mod move_window_to_app_workspace;
+mod move_window_to_monitor;
 mod move_window_to_workspace;
+mod peek_window;
+mod release_window;
 mod resize_window;
 mod run_window_rules;
+mod select_window;
+mod send_window_home;
 mod set_window_position;
 mod set_window_size;
 mod unmanage_window;
 mod update_window_state;
 
+pub use adopt_window::*;
+pub use close_window::*;
+pub use dock_window::*;
 pub use ignore_window::*;
 pub use manage_window::*;
 pub use move_window_in_direction::*;
+pub use move_window_to_app_workspace::*;
+pub use move_window_to_monitor::*;
 pub use move_window_to_workspace::*;
+pub use peek_window::*;
+pub use release_window::*;
 pub use resize_window::*;
 pub use run_window_rules::*;
+pub use select_window::*;
+pub use send_window_home::*;
 pub use set_window_position::*;
 pub use set_window_size::*;
 pub use unmanage_window::*;
@@ -33,6 +33,10 @@ pub fn move_window_in_direction(
     }
     WindowContainer::NonTilingWindow(non_tiling_window) => {
       match non_tiling_window.state() {
+        // Docked windows stay in the dock.
+        WindowState::Floating(_) if non_tiling_window.is_docked() => {
+          Ok(())
+        }
         WindowState::Floating(_) => {
           move_floating_window(non_tiling_window, direction, state)
         }
@@ -0,0 +1,55 @@
+use anyhow::Context;
+use tracing::info;
+
+use crate::{
+  commands::{
+    container::set_focused_descendant,
+    window::move_window_to_workspace,
+    workspace::{activate_app_workspace, focus_workspace},
+  },
+  models::{WindowContainer, WorkspaceTarget},
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Moves the window to the workspace of its application, and switches
+/// to that workspace. The workspace is named after the application's
+/// process, and is created if it doesn't exist yet.
+///
+/// Used when `general.workspace_per_app` is enabled.
+pub fn move_window_to_app_workspace(
+  window: WindowContainer,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let workspace_name = window.native().process_name()?;
+  let workspace = window.workspace().context("No workspace.")?;
+
+  if workspace.config().name == workspace_name {
+    return Ok(());
+  }
+
+  if state.workspace_by_name(&workspace_name).is_none() {
+    info!("Creating workspace for application: '{workspace_name}'.");
+
+    let monitor = workspace.monitor().context("No monitor.")?;
+    activate_app_workspace(&workspace_name, monitor, state, config)?;
+  }
+
+  move_window_to_workspace(
+    window.clone(),
+    WorkspaceTarget::Name(workspace_name.clone()),
+    state,
+    config,
+  )?;
+
+  focus_workspace(WorkspaceTarget::Name(workspace_name), state, config)?;
+
+  // Focus the new window rather than the application's last focused
+  // window.
+  set_focused_descendant(&window.into(), None);
+  state.pending_sync.queue_focus_change();
+
+  Ok(())
+}
@@ -0,0 +1,50 @@
+use wm_common::MonitorTarget;
+
+use super::move_window_to_workspace;
+use crate::{
+  commands::container::{move_split_to_workspace, set_focused_descendant},
+  models::{Container, WorkspaceTarget},
+  traits::CommonGetters,
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Moves a window or split container to the displayed workspace of
+/// another monitor.
+///
+/// Focus is moved along with the container if `should_focus` is `true`.
+/// Otherwise, focus stays on the origin monitor.
+pub fn move_window_to_monitor(
+  container: &Container,
+  target: &MonitorTarget,
+  should_focus: bool,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let workspace_target = match target {
+    MonitorTarget::Direction(direction) => {
+      WorkspaceTarget::Direction(direction.clone())
+    }
+    MonitorTarget::Index(index) => WorkspaceTarget::Monitor(*index),
+  };
+
+  match container {
+    Container::Split(split) => {
+      move_split_to_workspace(split, workspace_target, state, config)?;
+    }
+    _ => match container.as_window_container() {
+      Ok(window) => {
+        move_window_to_workspace(window, workspace_target, state, config)?;
+      }
+      _ => return Ok(()),
+    },
+  }
+
+  if should_focus {
+    set_focused_descendant(container, None);
+
+    state.pending_sync.queue_focus_change().queue_cursor_jump();
+  }
+
+  Ok(())
+}
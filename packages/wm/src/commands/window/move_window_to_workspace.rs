@@ -1,6 +1,6 @@
 use anyhow::Context;
 use tracing::info;
-use wm_common::WindowState;
+use wm_common::{WindowState, WmEvent};
 
 use crate::{
   commands::{
@@ -13,6 +13,7 @@ use crate::{
   wm_state::WmState,
 };
 
+#[allow(clippy::too_many_lines)]
 pub fn move_window_to_workspace(
   window: WindowContainer,
   target: WorkspaceTarget,
@@ -139,6 +140,13 @@ pub fn move_window_to_workspace(
       }
     }
 
+    // Window counts of both workspaces have changed.
+    for workspace in [&current_workspace, &target_workspace] {
+      state.emit_event(WmEvent::WorkspaceUpdated {
+        updated_workspace: workspace.to_dto()?,
+      });
+    }
+
     state
       .pending_sync
       .queue_workspace_to_reorder(target_workspace);
@@ -0,0 +1,99 @@
+use wm_common::{OpacityValue, WindowState};
+use wm_platform::{Platform, ZOrder};
+
+use crate::{
+  models::WindowContainer,
+  traits::{CommonGetters, WindowGetters},
+  wm_state::{PeekState, WmState},
+};
+
+/// Opacity of other windows while a window is being peeked at.
+const DIMMED_ALPHA: u8 = 80;
+
+/// Temporarily brings the window under the cursor (or the focused window
+/// if there is none) to the top and dims all other visible windows.
+///
+/// The previous stacking and opacity are restored via `unpeek`.
+pub fn peek_window(state: &mut WmState) -> anyhow::Result<()> {
+  unpeek_window(state);
+
+  let Some(window) = peek_target(state) else {
+    return Ok(());
+  };
+
+  let was_topmost = window.native().is_topmost();
+
+  let prev_z_order = window
+    .native()
+    .window_above()
+    .map_or(ZOrder::Normal, ZOrder::AfterWindow);
+
+  window.native().set_z_order(&ZOrder::TopMost)?;
+
+  let dimmed_windows = state
+    .windows()
+    .into_iter()
+    .filter(|other| {
+      other.id() != window.id()
+        && other.state() != WindowState::Minimized
+        && other
+          .workspace()
+          .is_some_and(|workspace| workspace.is_displayed())
+    })
+    .collect::<Vec<_>>();
+
+  for other in &dimmed_windows {
+    _ = other
+      .native()
+      .set_transparency(&OpacityValue::from_alpha(DIMMED_ALPHA));
+  }
+
+  state.peek = Some(PeekState {
+    window,
+    prev_z_order,
+    was_topmost,
+    dimmed_windows,
+  });
+
+  Ok(())
+}
+
+/// Ends the current peek (if any) by restoring the z-order of the peeked
+/// window and the opacity of dimmed windows.
+pub fn unpeek_window(state: &mut WmState) {
+  let Some(peek) = state.peek.take() else {
+    return;
+  };
+
+  // Only remove the topmost state if it was added by the peek.
+  if !peek.was_topmost {
+    _ = peek.window.native().set_z_order(&ZOrder::Normal);
+  }
+
+  if let ZOrder::AfterWindow(_) = peek.prev_z_order {
+    _ = peek.window.native().set_z_order(&peek.prev_z_order);
+  }
+
+  for window in &peek.dimmed_windows {
+    _ = window
+      .native()
+      .set_transparency(&OpacityValue::from_alpha(u8::MAX));
+  }
+
+  // Re-apply transparency effects that were overridden by the dimming.
+  state.pending_sync.queue_all_effects_update();
+}
+
+/// Gets the window under the cursor, falling back to the focused window.
+fn peek_target(state: &WmState) -> Option<WindowContainer> {
+  Platform::mouse_position()
+    .and_then(|point| Platform::window_from_point(&point))
+    .and_then(|window| Platform::root_ancestor(&window))
+    .ok()
+    .and_then(|root| state.window_from_native(&root))
+    .or_else(|| {
+      state
+        .focused_container()
+        .and_then(|focused| focused.as_window_container().ok())
+    })
+}
@@ -0,0 +1,72 @@
+use anyhow::Context;
+use tracing::info;
+use wm_common::InvokeWindowTargetCommand;
+
+use crate::{
+  commands::window::unmanage_window,
+  models::{Container, WindowContainer},
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Unmanages a window and ignores it until it's either adopted again or
+/// destroyed.
+///
+/// Defaults to the subject container if no target is given.
+pub fn release_window(
+  target: &InvokeWindowTargetCommand,
+  subject_container: &Container,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let window = match (&target.hwnd, &target.process) {
+    (Some(handle), _) => state
+      .windows()
+      .into_iter()
+      .find(|window| window.native().handle == *handle)
+      .with_context(|| format!("Window {handle} is not managed."))?,
+    (None, Some(process)) => managed_window_by_process(process, state)?,
+    (None, None) => subject_container.as_window_container()?,
+  };
+
+  info!("Releasing window: {window}");
+
+  let native_window = window.native().clone();
+  let hide_method = window
+    .hide_method()
+    .unwrap_or_else(|| config.value.general.hide_method.clone());
+
+  unmanage_window(window, state)?;
+
+  if !state.ignored_windows.contains(&native_window) {
+    state.ignored_windows.push(native_window.clone());
+  }
+
+  // The window might be hidden on a workspace that isn't displayed, so
+  // it needs to be shown again and have any effects reset.
+  native_window
+    .set_visible(true, &hide_method)
+    .context("Failed to show released window.")?;
+
+  native_window.cleanup();
+
+  Ok(())
+}
+
+/// Gets the first managed window of the given process.
+fn managed_window_by_process(
+  process: &str,
+  state: &WmState,
+) -> anyhow::Result<WindowContainer> {
+  let window = state.windows().into_iter().find(|window| {
+    window
+      .native()
+      .process_name()
+      .is_ok_and(|name| name.eq_ignore_ascii_case(process))
+  });
+
+  window.with_context(|| {
+    format!("No managed window found for process '{process}'.")
+  })
+}
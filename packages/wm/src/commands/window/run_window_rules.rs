@@ -1,5 +1,6 @@
 use tracing::info;
-use wm_common::WindowRuleEvent;
+use wm_common::{TraceEvent, WindowRuleEvent};
+use wm_platform::{NotificationLevel, Platform};
 
 use crate::{
   models::WindowContainer,
@@ -25,6 +26,26 @@ pub fn run_window_rules(
   for rule in pending_window_rules {
     info!("Running window rule with commands: {:?}.", rule.commands);
 
+    state.emit_trace(|| TraceEvent::WindowRuleMatched {
+      handle: subject_window.native().handle,
+      process_name: subject_window.native().process_name().ok(),
+      rule_event: event_type.clone(),
+      commands: rule.commands.clone(),
+    });
+
+    if config.value.general.notifications.debug_window_rules {
+      Platform::show_notification(
+        "Window rule matched",
+        &format!(
+          "{} ({:?})\nCommands: {:?}",
+          subject_window.native().process_name().unwrap_or_default(),
+          event_type,
+          rule.commands
+        ),
+        NotificationLevel::Info,
+      );
+    }
+
     for command in &rule.commands {
       WindowManager::run_command(
         command,
@@ -0,0 +1,32 @@
+use crate::{models::Container, traits::CommonGetters, wm_state::WmState};
+
+/// Adds the given window to the selection. If a split container is
+/// focused, all of its windows are added instead.
+///
+/// Selected windows can then be operated on together via `select-run`.
+pub fn select_add(container: &Container, state: &mut WmState) {
+  let windows = match container {
+    Container::Split(split) => split
+      .descendants()
+      .filter_map(|descendant| descendant.as_window_container().ok())
+      .collect(),
+    _ => container
+      .as_window_container()
+      .into_iter()
+      .collect::<Vec<_>>(),
+  };
+
+  for window in windows {
+    if !state.selected_window_ids.contains(&window.id()) {
+      state.selected_window_ids.push(window.id());
+    }
+  }
+
+  state.pending_sync.queue_all_effects_update();
+}
+
+/// Removes all windows from the selection.
+pub fn select_clear(state: &mut WmState) {
+  state.selected_window_ids.clear();
+  state.pending_sync.queue_all_effects_update();
+}
@@ -0,0 +1,58 @@
+use anyhow::Context;
+use tracing::info;
+
+use crate::{
+  commands::window::move_window_to_workspace,
+  models::{WindowContainer, Workspace, WorkspaceTarget},
+  traits::CommonGetters,
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Moves the window to the home workspace from the first window rule
+/// that matches it and has a `home_workspace`.
+///
+/// Does nothing if no rule defines a home workspace for the window, or
+/// if the window is already on it.
+pub fn send_window_home(
+  window: WindowContainer,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let Some(home_workspace) = config.home_workspace(&window)? else {
+    return Ok(());
+  };
+
+  let workspace = window.workspace().context("No workspace.")?;
+
+  if workspace.config().name == home_workspace {
+    return Ok(());
+  }
+
+  info!("Sending window home to workspace: '{home_workspace}'.");
+
+  move_window_to_workspace(
+    window,
+    WorkspaceTarget::Name(home_workspace),
+    state,
+    config,
+  )
+}
+
+/// Moves every window on the workspace to its home workspace.
+pub fn gather_windows(
+  workspace: &Workspace,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let windows = workspace
+    .descendants()
+    .filter_map(|descendant| descendant.as_window_container().ok())
+    .collect::<Vec<_>>();
+
+  for window in windows {
+    send_window_home(window, state, config)?;
+  }
+
+  Ok(())
+}
@@ -1,5 +1,5 @@
 use anyhow::Context;
-use wm_common::{Rect, WindowState};
+use wm_common::{Rect, SnapPosition, WindowState};
 
 use crate::{
   models::WindowContainer,
@@ -10,6 +10,7 @@ use crate::{
 pub enum WindowPositionTarget {
   Centered,
   Coordinates(Option<i32>, Option<i32>),
+  Snap(SnapPosition),
 }
 
 pub fn set_window_position(
@@ -32,6 +33,10 @@ pub fn set_window_position(
           placement.height(),
         )
       }
+      WindowPositionTarget::Snap(position) => snap_rect(
+        &window.workspace().context("No workspace.")?.to_rect()?,
+        position,
+      ),
     };
 
     window.set_floating_placement(new_placement);
@@ -46,3 +51,64 @@ pub fn set_window_position(
 
   Ok(())
 }
+
+/// Gets the half or quadrant of the workspace rect to snap a window to.
+fn snap_rect(workspace_rect: &Rect, position: &SnapPosition) -> Rect {
+  let half_width = workspace_rect.width() / 2;
+  let half_height = workspace_rect.height() / 2;
+  let center_x = workspace_rect.left + half_width;
+  let center_y = workspace_rect.top + half_height;
+
+  let (left, top, right, bottom) = match position {
+    SnapPosition::Left => (
+      workspace_rect.left,
+      workspace_rect.top,
+      center_x,
+      workspace_rect.bottom,
+    ),
+    SnapPosition::Right => (
+      center_x,
+      workspace_rect.top,
+      workspace_rect.right,
+      workspace_rect.bottom,
+    ),
+    SnapPosition::Top => (
+      workspace_rect.left,
+      workspace_rect.top,
+      workspace_rect.right,
+      center_y,
+    ),
+    SnapPosition::Bottom => (
+      workspace_rect.left,
+      center_y,
+      workspace_rect.right,
+      workspace_rect.bottom,
+    ),
+    SnapPosition::TopLeft => {
+      (workspace_rect.left, workspace_rect.top, center_x, center_y)
+    }
+    SnapPosition::TopRight => {
+      (center_x, workspace_rect.top, workspace_rect.right, center_y)
+    }
+    SnapPosition::BottomLeft => (
+      workspace_rect.left,
+      center_y,
+      center_x,
+      workspace_rect.bottom,
+    ),
+    SnapPosition::BottomRight => (
+      center_x,
+      center_y,
+      workspace_rect.right,
+      workspace_rect.bottom,
+    ),
+    SnapPosition::Full => (
+      workspace_rect.left,
+      workspace_rect.top,
+      workspace_rect.right,
+      workspace_rect.bottom,
+    ),
+  };
+
+  Rect::from_ltrb(left, top, right, bottom)
+}
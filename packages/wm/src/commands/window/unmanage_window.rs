@@ -22,6 +22,8 @@ pub fn unmanage_window(
   // Get container to switch focus to after the window has been removed.
   let focus_target = state.focus_target_after_removal(&window.clone());
 
+  let workspace = window.workspace();
+
   detach_container(window.clone().into())?;
 
   // After detaching the container, flatten any redundant split containers.
@@ -31,11 +33,28 @@ pub fn unmanage_window(
     flatten_child_split_containers(ancestor)?;
   }
 
+  state.window_placements.remove(&window.id());
+  state.selected_window_ids.retain(|id| *id != window.id());
+  state.placeholder_slots.remove(&window.id());
+  state.managed_origins.remove(&window.id());
+  state.offscreen_windows.remove(&window.native());
+
+  state
+    .unresponsive_windows
+    .retain(|native| *native != *window.native());
+
   state.emit_event(WmEvent::WindowUnmanaged {
     unmanaged_id: window.id(),
     unmanaged_handle: window.native().handle,
   });
 
+  // Window count of the workspace has changed.
+  if let Some(workspace) = workspace {
+    state.emit_event(WmEvent::WorkspaceUpdated {
+      updated_workspace: workspace.to_dto()?,
+    });
+  }
+
   // Reassign focus to suitable target.
   if let Some(focus_target) = focus_target {
     set_focused_descendant(&focus_target, None);
@@ -0,0 +1,89 @@
+use tracing::info;
+
+use super::{activate_workspace, deactivate_workspace};
+use crate::{
+  commands::container::set_focused_descendant, traits::CommonGetters,
+  user_config::UserConfig, wm_state::WmState,
+};
+
+/// Displays the workspaces that have `active_on_startup` enabled on
+/// their monitors.
+///
+/// Workspaces are shown on the monitor they're bound to, or otherwise on
+/// the first monitor. Only the first such workspace is used per monitor.
+/// Should be run before any windows are managed, so that existing windows
+/// are assigned to the displayed workspaces.
+pub fn activate_startup_workspaces(
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let startup_configs = config
+    .value
+    .workspaces
+    .iter()
+    .filter(|workspace_config| workspace_config.active_on_startup);
+
+  let mut handled_monitor_ids = Vec::new();
+
+  for workspace_config in startup_configs {
+    let existing_workspace =
+      state.workspace_by_name(&workspace_config.name);
+
+    let target_monitor = match workspace_config.bind_to_monitor {
+      Some(index) => state
+        .monitors()
+        .into_iter()
+        .find(|monitor| monitor.index() == index as usize),
+      None => existing_workspace
+        .as_ref()
+        .and_then(CommonGetters::monitor)
+        .or_else(|| state.monitors().into_iter().next()),
+    };
+
+    let Some(target_monitor) = target_monitor else {
+      continue;
+    };
+
+    if handled_monitor_ids.contains(&target_monitor.id()) {
+      continue;
+    }
+
+    handled_monitor_ids.push(target_monitor.id());
+
+    let previous_workspace = target_monitor.displayed_workspace();
+
+    if existing_workspace.is_none() {
+      activate_workspace(
+        Some(&workspace_config.name),
+        Some(target_monitor.clone()),
+        state,
+        config,
+      )?;
+    }
+
+    let Some(workspace) = state.workspace_by_name(&workspace_config.name)
+    else {
+      continue;
+    };
+
+    info!("Displaying startup workspace: {workspace}");
+
+    set_focused_descendant(
+      &workspace.clone().into(),
+      Some(&target_monitor.into()),
+    );
+
+    // Remove the workspace that was displayed before, since it'd
+    // otherwise be left empty in the background.
+    if let Some(previous_workspace) = previous_workspace {
+      if previous_workspace.id() != workspace.id()
+        && !previous_workspace.config().keep_alive
+        && !previous_workspace.has_children()
+      {
+        deactivate_workspace(previous_workspace, state)?;
+      }
+    }
+  }
+
+  Ok(())
+}
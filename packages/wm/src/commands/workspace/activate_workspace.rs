@@ -1,6 +1,8 @@
 use anyhow::Context;
 use tracing::info;
-use wm_common::{TilingDirection, WmEvent, WorkspaceConfig};
+use wm_common::{
+  TilingDirection, WmEvent, WorkspaceConfig, WorkspaceTilingDirection,
+};
 
 use super::sort_workspaces;
 use crate::{
@@ -31,6 +33,42 @@ pub fn activate_workspace(
     config,
   )?;
 
+  activate_workspace_with_config(
+    &workspace_config,
+    target_monitor,
+    state,
+    config,
+  )
+}
+
+/// Activates a workspace for an application on the target monitor. App
+/// workspaces are created via `general.workspace_per_app`, and have a
+/// default config since they aren't in the user config.
+pub fn activate_app_workspace(
+  workspace_name: &str,
+  target_monitor: Monitor,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let workspace_config = WorkspaceConfig {
+    name: workspace_name.to_string(),
+    ..WorkspaceConfig::default()
+  };
+
+  activate_workspace_with_config(
+    &workspace_config,
+    Some(target_monitor),
+    state,
+    config,
+  )
+}
+
+fn activate_workspace_with_config(
+  workspace_config: &WorkspaceConfig,
+  target_monitor: Option<Monitor>,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
   let target_monitor = target_monitor
     .or_else(|| {
       workspace_config
@@ -51,15 +89,23 @@ pub fn activate_workspace(
 
   let monitor_rect = target_monitor.to_rect()?;
 
-  let tiling_direction = if monitor_rect.height() > monitor_rect.width() {
-    TilingDirection::Vertical
-  } else {
-    TilingDirection::Horizontal
+  let tiling_direction = match workspace_config.tiling_direction {
+    Some(WorkspaceTilingDirection::Horizontal) => {
+      TilingDirection::Horizontal
+    }
+    Some(WorkspaceTilingDirection::Vertical) => TilingDirection::Vertical,
+    Some(WorkspaceTilingDirection::Auto) | None => {
+      if monitor_rect.height() > monitor_rect.width() {
+        TilingDirection::Vertical
+      } else {
+        TilingDirection::Horizontal
+      }
+    }
   };
 
   let workspace = Workspace::new(
     workspace_config.clone(),
-    config.value.gaps.clone(),
+    config.gaps_for_workspace(workspace_config),
     tiling_direction,
   );
 
@@ -0,0 +1,34 @@
+use wm_common::{Delta, GapKind, LengthValue};
+
+use super::update_workspace_gaps;
+use crate::{
+  models::Workspace, user_config::UserConfig, wm_state::WmState,
+};
+
+/// Increases or decreases a gap of the given workspace.
+///
+/// If no workspace is provided, the gap is adjusted for all workspaces
+/// and for workspaces that get activated later on. The change lasts until
+/// the config is reloaded.
+pub fn adjust_workspace_gaps(
+  workspace: Option<Workspace>,
+  gap: &GapKind,
+  delta: &Delta<LengthValue>,
+  state: &mut WmState,
+  config: &mut UserConfig,
+) -> anyhow::Result<()> {
+  let workspaces = if let Some(workspace) = workspace {
+    vec![workspace]
+  } else {
+    gap.adjust(&mut config.value.gaps, delta)?;
+    state.workspaces()
+  };
+
+  for workspace in workspaces {
+    let mut gaps_config = workspace.gaps_config();
+    gap.adjust(&mut gaps_config, delta)?;
+    update_workspace_gaps(&workspace, gaps_config, state)?;
+  }
+
+  Ok(())
+}
@@ -0,0 +1,371 @@
+use anyhow::Context;
+use wm_common::{
+  LengthValue, MediaUsage, RectDelta, TilingDirection, WindowState,
+  WorkspaceTemplateConfig,
+};
+use wm_platform::Platform;
+
+use crate::{
+  commands::container::{
+    attach_container, detach_container, set_focused_descendant,
+  },
+  models::{
+    SplitContainer, TilingContainer, TilingWindow, WindowContainer,
+    Workspace,
+  },
+  traits::{
+    CommonGetters, PositionGetters, TilingDirectionGetters,
+    TilingSizeGetters, WindowGetters,
+  },
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Rearranges the tiling windows of a workspace according to its
+/// `template` config.
+///
+/// Each slot of the template is filled by the first matching window.
+/// Slots with a `placeholder` are otherwise held by an existing
+/// placeholder window, or a new one if `should_create_placeholders` is
+/// set. Containers of the template that end up empty are left out, and
+/// windows that don't fill a slot are placed after the template.
+pub fn apply_workspace_template(
+  workspace: &Workspace,
+  should_create_placeholders: bool,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let Some(template) = workspace.config().template else {
+    return Ok(());
+  };
+
+  let (placeholders, mut unassigned_windows): (Vec<_>, Vec<_>) = workspace
+    .descendants()
+    .filter_map(|descendant| descendant.as_tiling_window().cloned())
+    .partition(|window| {
+      state.placeholder_slots.contains_key(&window.id())
+    });
+
+  let slots = template.slots();
+
+  let mut slot_windows = slots
+    .iter()
+    .map(|slot| take_matching_window(slot, &mut unassigned_windows))
+    .collect::<anyhow::Result<Vec<_>>>()?;
+
+  if slot_windows.iter().all(Option::is_none)
+    && !should_create_placeholders
+  {
+    return Ok(());
+  }
+
+  fill_placeholder_slots(
+    &slots,
+    &mut slot_windows,
+    placeholders,
+    should_create_placeholders,
+    workspace,
+    state,
+    config,
+  )?;
+
+  // Windows in the order they were last focused, so that the focus order
+  // can be restored after rearranging.
+  let focus_order = workspace
+    .descendant_focus_order()
+    .filter(|descendant| descendant.as_window_container().is_ok())
+    .collect::<Vec<_>>();
+
+  for window in slot_windows.iter().flatten().chain(&unassigned_windows) {
+    if !window.is_detached() {
+      detach_container(window.clone().into())?;
+    }
+  }
+
+  if let Some(direction) = &template.direction {
+    workspace.set_tiling_direction(direction.clone());
+  }
+
+  // A template without children is a single slot.
+  let template_children = if template.children.is_empty() {
+    slot_windows
+      .into_iter()
+      .flatten()
+      .map(|window| (window.into(), 1.))
+      .collect()
+  } else {
+    build_children(
+      &template,
+      &workspace.tiling_direction(),
+      &mut slot_windows.into_iter(),
+      config,
+    )?
+  };
+
+  #[allow(clippy::cast_precision_loss)]
+  let template_share = template_children.len() as f32
+    / (template_children.len() + unassigned_windows.len()) as f32;
+
+  for (index, (container, size)) in template_children.iter().enumerate() {
+    attach_container(
+      &container.clone().into(),
+      &workspace.clone().into(),
+      Some(index),
+    )?;
+
+    container.set_tiling_size(size * template_share);
+  }
+
+  #[allow(clippy::cast_precision_loss)]
+  let unassigned_size =
+    1. / (template_children.len() + unassigned_windows.len()) as f32;
+
+  for window in &unassigned_windows {
+    attach_container(
+      &window.clone().into(),
+      &workspace.clone().into(),
+      None,
+    )?;
+
+    window.set_tiling_size(unassigned_size);
+  }
+
+  for window in focus_order.iter().rev() {
+    set_focused_descendant(window, Some(&workspace.clone().into()));
+  }
+
+  state
+    .pending_sync
+    .queue_container_to_redraw(workspace.clone());
+
+  Ok(())
+}
+
+/// Whether the window fills one of the slots in the template of its
+/// workspace.
+pub fn fills_template_slot(
+  window: &WindowContainer,
+) -> anyhow::Result<bool> {
+  if window.state() != WindowState::Tiling {
+    return Ok(false);
+  }
+
+  let workspace = window.workspace().context("No workspace.")?;
+
+  let Some(template) = workspace.config().template else {
+    return Ok(false);
+  };
+
+  let window_title = window.native().title()?;
+  let window_class = window.native().class_name()?;
+  let window_process = window.native().process_name()?;
+
+  Ok(template.slots().iter().any(|slot| {
+    slot.match_window.iter().any(|match_config| {
+      match_config.is_match(&window_process, &window_class, &window_title)
+    })
+  }))
+}
+
+/// Fills slots that have no matching window with their placeholder.
+/// Existing placeholders are reused, and new ones are only created if
+/// `should_create_placeholders` is set.
+///
+/// Placeholders that are no longer needed are removed.
+fn fill_placeholder_slots(
+  slots: &[&WorkspaceTemplateConfig],
+  slot_windows: &mut [Option<TilingWindow>],
+  mut placeholders: Vec<TilingWindow>,
+  should_create_placeholders: bool,
+  workspace: &Workspace,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  for (index, (slot, slot_window)) in
+    slots.iter().zip(slot_windows.iter_mut()).enumerate()
+  {
+    let Some(label) = slot.placeholder.as_ref() else {
+      continue;
+    };
+
+    if slot_window.is_some() {
+      continue;
+    }
+
+    let placeholder_index = placeholders.iter().position(|placeholder| {
+      state.placeholder_slots.get(&placeholder.id()) == Some(&index)
+    });
+
+    *slot_window = match placeholder_index {
+      Some(placeholder_index) => {
+        Some(placeholders.remove(placeholder_index))
+      }
+      None if should_create_placeholders => {
+        Some(create_placeholder(label, index, workspace, state, config)?)
+      }
+      None => None,
+    };
+  }
+
+  // Remove placeholders whose slot has been filled by a window.
+  for placeholder in placeholders {
+    detach_container(placeholder.clone().into())?;
+    state.placeholder_slots.remove(&placeholder.id());
+    placeholder.native().close()?;
+  }
+
+  Ok(())
+}
+
+/// Creates a placeholder window for the slot at the given index.
+fn create_placeholder(
+  label: &str,
+  slot_index: usize,
+  workspace: &Workspace,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<TilingWindow> {
+  let native_window = Platform::create_placeholder_window(label)?;
+
+  let placeholder = TilingWindow::new(
+    None,
+    native_window,
+    None,
+    RectDelta::new(
+      LengthValue::from_px(0),
+      LengthValue::from_px(0),
+      LengthValue::from_px(0),
+      LengthValue::from_px(0),
+    ),
+    workspace.to_rect()?,
+    false,
+    config.value.gaps.clone(),
+    Vec::new(),
+    None,
+    Some(label.to_string()),
+    MediaUsage::default(),
+    None,
+    None,
+  );
+
+  state.placeholder_slots.insert(placeholder.id(), slot_index);
+
+  Ok(placeholder)
+}
+
+/// Removes and returns the first window that matches the slot.
+fn take_matching_window(
+  slot: &WorkspaceTemplateConfig,
+  windows: &mut Vec<TilingWindow>,
+) -> anyhow::Result<Option<TilingWindow>> {
+  for (index, window) in windows.iter().enumerate() {
+    let window_title = window.native().title()?;
+    let window_class = window.native().class_name()?;
+    let window_process = window.native().process_name()?;
+
+    let is_match = slot.match_window.iter().any(|match_config| {
+      match_config.is_match(&window_process, &window_class, &window_title)
+    });
+
+    if is_match {
+      return Ok(Some(windows.remove(index)));
+    }
+  }
+
+  Ok(None)
+}
+
+/// Creates the containers for the children of a template node, along
+/// with their tiling sizes relative to each other.
+///
+/// Slot windows are consumed in the same order as
+/// `WorkspaceTemplateConfig::slots`.
+fn build_children(
+  node: &WorkspaceTemplateConfig,
+  direction: &TilingDirection,
+  slot_windows: &mut impl Iterator<Item = Option<TilingWindow>>,
+  config: &UserConfig,
+) -> anyhow::Result<Vec<(TilingContainer, f32)>> {
+  let mut children = Vec::new();
+
+  for child in &node.children {
+    if let Some(container) =
+      build_node(child, direction, slot_windows, config)?
+    {
+      children.push((container, child.size.clone()));
+    }
+  }
+
+  // Children without a size share the space that's left over.
+  let sized_total = children
+    .iter()
+    .filter_map(|(_, size)| size.as_ref().map(|size| size.amount))
+    .sum::<f32>();
+
+  #[allow(clippy::cast_precision_loss)]
+  let unsized_size = (1. - sized_total).max(0.)
+    / children.iter().filter(|(_, size)| size.is_none()).count() as f32;
+
+  let sizes = children
+    .iter()
+    .map(|(_, size)| {
+      size.as_ref().map_or(unsized_size, |size| size.amount)
+    })
+    .collect::<Vec<_>>();
+
+  let total = sizes.iter().sum::<f32>();
+
+  Ok(
+    children
+      .into_iter()
+      .zip(sizes)
+      .map(|((container, _), size)| {
+        let size = if total > 0. { size / total } else { 0. };
+        (container, size)
+      })
+      .collect(),
+  )
+}
+
+/// Creates the container for a template node. Returns `None` if none of
+/// its slots have been filled.
+///
+/// Split containers with only a single child are left out in favor of
+/// the child itself.
+fn build_node(
+  node: &WorkspaceTemplateConfig,
+  parent_direction: &TilingDirection,
+  slot_windows: &mut impl Iterator<Item = Option<TilingWindow>>,
+  config: &UserConfig,
+) -> anyhow::Result<Option<TilingContainer>> {
+  if node.children.is_empty() {
+    return Ok(slot_windows.next().flatten().map(Into::into));
+  }
+
+  let direction = node
+    .direction
+    .clone()
+    .unwrap_or_else(|| parent_direction.inverse());
+
+  let mut children =
+    build_children(node, &direction, slot_windows, config)?;
+
+  if children.len() <= 1 {
+    return Ok(children.pop().map(|(container, _)| container));
+  }
+
+  let split_container =
+    SplitContainer::new(direction, config.value.gaps.clone());
+
+  for (container, size) in &children {
+    attach_container(
+      &container.clone().into(),
+      &split_container.clone().into(),
+      None,
+    )?;
+
+    container.set_tiling_size(*size);
+  }
+
+  Ok(Some(split_container.into()))
+}
@@ -0,0 +1,48 @@
+use anyhow::Context;
+
+use super::focus_workspace;
+use crate::{
+  models::WorkspaceTarget, traits::CommonGetters, user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Focuses the workspace at the given index of a monitor's workspaces,
+/// ordered by their position in the user config. Defaults to the
+/// focused monitor.
+///
+/// Unlike focusing a workspace by name, nothing happens if the workspace
+/// is already focused.
+pub fn focus_workspace_at_index(
+  index: usize,
+  monitor_index: Option<usize>,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let monitor = state
+    .monitor_at_index_or_focused(monitor_index)
+    .context("Monitor was not found.")?;
+
+  let mut workspaces = monitor.workspaces();
+  config.sort_workspaces(&mut workspaces);
+
+  let workspace = workspaces.get(index).with_context(|| {
+    format!("No workspace at index {index} on the monitor.")
+  })?;
+
+  let is_focused = state
+    .focused_container()
+    .and_then(|focused| focused.workspace())
+    .is_some_and(|focused_workspace| {
+      focused_workspace.id() == workspace.id()
+    });
+
+  if is_focused {
+    return Ok(());
+  }
+
+  focus_workspace(
+    WorkspaceTarget::Name(workspace.config().name),
+    state,
+    config,
+  )
+}
@@ -1,11 +1,29 @@
+mod activate_startup_workspaces;
 mod activate_workspace;
+mod adjust_workspace_gaps;
+mod apply_workspace_template;
 mod deactivate_workspace;
 mod focus_workspace;
+mod focus_workspace_at_index;
 mod move_workspace_in_direction;
+mod overflow_tiles;
+mod scroll_to_container;
+mod scroll_workspace;
+mod set_workspace_gaps;
 mod sort_workspaces;
+mod toggle_workspace_layout;
 
+pub use activate_startup_workspaces::*;
 pub use activate_workspace::*;
+pub use adjust_workspace_gaps::*;
+pub use apply_workspace_template::*;
 pub use deactivate_workspace::*;
 pub use focus_workspace::*;
+pub use focus_workspace_at_index::*;
 pub use move_workspace_in_direction::*;
+pub use overflow_tiles::*;
+pub use scroll_to_container::*;
+pub use scroll_workspace::*;
+pub use set_workspace_gaps::*;
 pub use sort_workspaces::*;
+pub use toggle_workspace_layout::*;
@@ -0,0 +1,125 @@
+use anyhow::Context;
+use tracing::info;
+use wm_common::WindowState;
+
+use crate::{
+  commands::{
+    container::set_focused_descendant, window::update_window_state,
+  },
+  models::{WindowContainer, Workspace},
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Minimizes the least recently focused tiling windows of the workspace
+/// until it's within its `max_visible_tiles` limit. Minimized windows are
+/// added to the workspace's overflow stack.
+///
+/// The `exempt_window` is never moved to the overflow stack (e.g. a
+/// window that was just added).
+pub fn enforce_max_visible_tiles(
+  workspace: &Workspace,
+  exempt_window: Option<&WindowContainer>,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let Some(max_visible_tiles) = workspace.config().max_visible_tiles
+  else {
+    return Ok(());
+  };
+
+  let mut tiling_windows = workspace
+    .descendant_focus_order()
+    .filter_map(|descendant| descendant.as_tiling_window().cloned())
+    .collect::<Vec<_>>();
+
+  // Keep the exempt window visible by moving it to the front.
+  if let Some(exempt_window) = exempt_window {
+    tiling_windows.sort_by_key(|window| window.id() != exempt_window.id());
+  }
+
+  let windows_to_overflow = tiling_windows
+    .into_iter()
+    .skip(max_visible_tiles)
+    .map(WindowContainer::from);
+
+  let mut overflow_window_ids = workspace.overflow_window_ids();
+
+  for window in windows_to_overflow {
+    info!("Moving window to overflow stack: {window}");
+
+    overflow_window_ids.push_front(window.id());
+    update_window_state(window, WindowState::Minimized, state, config)?;
+  }
+
+  workspace.set_overflow_window_ids(overflow_window_ids);
+
+  Ok(())
+}
+
+/// Swaps the focused tiling window of the workspace with the next window
+/// in its overflow stack.
+pub fn focus_overflow_next(
+  workspace: &Workspace,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let mut overflow_window_ids = workspace.overflow_window_ids();
+
+  // Skip windows that have since been closed, moved elsewhere, or
+  // restored.
+  let next_window = std::iter::from_fn(|| overflow_window_ids.pop_front())
+    .find_map(|id| {
+      workspace
+        .descendants()
+        .filter_map(|descendant| descendant.as_window_container().ok())
+        .find(|window| {
+          window.id() == id && window.state() == WindowState::Minimized
+        })
+    });
+
+  let Some(next_window) = next_window else {
+    workspace.set_overflow_window_ids(overflow_window_ids);
+    return Ok(());
+  };
+
+  let focused_window = workspace
+    .descendant_focus_order()
+    .find_map(|descendant| descendant.as_tiling_window().cloned());
+
+  // Tiling windows are restored from their minimized state on redraw.
+  let next_window =
+    update_window_state(next_window, WindowState::Tiling, state, config)?;
+
+  set_focused_descendant(&next_window.clone().into(), None);
+  state.pending_sync.queue_focus_change().queue_cursor_jump();
+
+  if let Some(focused_window) = focused_window {
+    overflow_window_ids.push_back(focused_window.id());
+    update_window_state(
+      focused_window.into(),
+      WindowState::Minimized,
+      state,
+      config,
+    )?;
+  }
+
+  workspace.set_overflow_window_ids(overflow_window_ids);
+
+  Ok(())
+}
+
+/// Removes the window from the overflow stack of its workspace, e.g.
+/// when it has been restored by the user.
+pub fn remove_from_overflow(
+  window: &WindowContainer,
+) -> anyhow::Result<()> {
+  let workspace = window.workspace().context("No workspace.")?;
+
+  let mut overflow_window_ids = workspace.overflow_window_ids();
+  overflow_window_ids.retain(|id| *id != window.id());
+  workspace.set_overflow_window_ids(overflow_window_ids);
+
+  Ok(())
+}
@@ -0,0 +1,62 @@
+use anyhow::Context;
+use wm_common::TilingDirection;
+
+use crate::{
+  models::Container,
+  traits::{CommonGetters, PositionGetters, TilingDirectionGetters},
+  wm_state::WmState,
+};
+
+/// Pans the view of a scrolling workspace so that the column containing
+/// the given container is fully visible.
+///
+/// Does nothing if the container isn't in a scrolling workspace or is
+/// already visible.
+pub fn scroll_to_container(
+  container: &Container,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  let workspace = container.workspace().context("No workspace.")?;
+
+  if !workspace.config().scrolling
+    || workspace.tiling_direction() != TilingDirection::Horizontal
+  {
+    return Ok(());
+  }
+
+  // Get the top-level tiling container that the container is in.
+  let Some(column) = container
+    .self_and_ancestors()
+    .find(|ancestor| {
+      ancestor
+        .parent()
+        .is_some_and(|parent| parent.id() == workspace.id())
+    })
+    .and_then(|column| column.as_tiling_container().ok())
+  else {
+    return Ok(());
+  };
+
+  let workspace_rect = workspace.to_rect()?;
+  let column_rect = column.to_rect()?;
+
+  // Offset that's currently in effect, which can differ from the stored
+  // offset if it has since been clamped.
+  let scroll_offset = workspace_rect.x() - workspace.tiling_rect()?.x();
+
+  let scroll_delta = if column_rect.left < workspace_rect.left {
+    column_rect.left - workspace_rect.left
+  } else if column_rect.right > workspace_rect.right {
+    column_rect.right - workspace_rect.right
+  } else {
+    0
+  };
+
+  workspace.set_scroll_offset(scroll_offset + scroll_delta);
+
+  if scroll_delta != 0 {
+    state.pending_sync.queue_container_to_redraw(workspace);
+  }
+
+  Ok(())
+}
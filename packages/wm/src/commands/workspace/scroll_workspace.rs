@@ -0,0 +1,45 @@
+use anyhow::Context;
+use wm_common::ScrollDirection;
+
+use super::focus_workspace;
+use crate::{
+  models::WorkspaceTarget, traits::CommonGetters, user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Focuses the next or previous workspace on a monitor, relative to the
+/// workspace it displays. Defaults to the focused monitor.
+///
+/// Wraps around at either end, and does nothing if the monitor has a
+/// single workspace.
+pub fn scroll_workspace(
+  direction: &ScrollDirection,
+  monitor_index: Option<usize>,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let displayed_workspace = state
+    .monitor_at_index_or_focused(monitor_index)
+    .context("Monitor was not found.")?
+    .displayed_workspace()
+    .context("No workspace is displayed on the monitor.")?;
+
+  let target = match direction {
+    ScrollDirection::Next => WorkspaceTarget::NextActiveInMonitor,
+    ScrollDirection::Prev => WorkspaceTarget::PreviousActiveInMonitor,
+  };
+
+  let (_, target_workspace) =
+    state.workspace_by_target(&displayed_workspace, target, config)?;
+
+  match target_workspace {
+    Some(workspace) if workspace.id() != displayed_workspace.id() => {
+      focus_workspace(
+        WorkspaceTarget::Name(workspace.config().name),
+        state,
+        config,
+      )
+    }
+    _ => Ok(()),
+  }
+}
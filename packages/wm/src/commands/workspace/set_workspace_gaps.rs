@@ -0,0 +1,52 @@
+use wm_common::{GapValue, GapsConfig, WmEvent};
+
+use crate::{
+  models::Workspace,
+  traits::{CommonGetters, TilingSizeGetters},
+  wm_state::WmState,
+};
+
+/// Changes the gaps of a workspace at runtime. Overrides the gaps from
+/// the user config until the workspace is deactivated or the config is
+/// reloaded.
+pub fn set_workspace_gaps(
+  workspace: &Workspace,
+  gap_values: &[GapValue],
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  let mut gaps_config = workspace.gaps_config();
+
+  for gap_value in gap_values {
+    gap_value.apply(&mut gaps_config);
+  }
+
+  update_workspace_gaps(workspace, gaps_config, state)
+}
+
+/// Replaces the gaps config of a workspace and its tiling containers, and
+/// redraws the workspace.
+pub fn update_workspace_gaps(
+  workspace: &Workspace,
+  gaps_config: GapsConfig,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  let tiling_containers = workspace
+    .descendants()
+    .filter_map(|descendant| descendant.as_tiling_container().ok());
+
+  for container in tiling_containers {
+    container.set_gaps_config(gaps_config.clone());
+  }
+
+  workspace.set_gaps_config(gaps_config);
+
+  state
+    .pending_sync
+    .queue_container_to_redraw(workspace.clone());
+
+  state.emit_event(WmEvent::WorkspaceUpdated {
+    updated_workspace: workspace.to_dto()?,
+  });
+
+  Ok(())
+}
@@ -0,0 +1,24 @@
+use wm_common::{WmEvent, WorkspaceLayout};
+
+use crate::{models::Workspace, wm_state::WmState};
+
+/// Toggles whether new windows in the workspace are tiled or floating.
+///
+/// Existing windows in the workspace are left as is.
+pub fn toggle_workspace_layout(
+  workspace: &Workspace,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  let layout = match workspace.layout() {
+    WorkspaceLayout::Tiling => WorkspaceLayout::Floating,
+    WorkspaceLayout::Floating => WorkspaceLayout::Tiling,
+  };
+
+  workspace.set_layout(layout);
+
+  state.emit_event(WmEvent::WorkspaceUpdated {
+    updated_workspace: workspace.to_dto()?,
+  });
+
+  Ok(())
+}
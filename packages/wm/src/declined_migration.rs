@@ -0,0 +1,59 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  time::UNIX_EPOCH,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+/// Config that the user declined to migrate, identified by its path and
+/// modification time.
+///
+/// Persisted to `~/.glzr/glazewm/declined_migration.json`, so that the
+/// migration prompt isn't shown again on every start (e.g. on restarts by
+/// the watchdog) until the config is changed.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclinedMigration {
+  config_path: PathBuf,
+  modified_at: u64,
+}
+
+impl DeclinedMigration {
+  /// Gets the entry for the current state of the config file. Returns
+  /// `None` if its modification time can't be read.
+  pub fn for_config(config_path: &Path) -> Option<Self> {
+    let modified_at = fs::metadata(config_path)
+      .and_then(|metadata| metadata.modified())
+      .ok()?
+      .duration_since(UNIX_EPOCH)
+      .ok()?
+      .as_secs();
+
+    Some(Self {
+      config_path: config_path.to_path_buf(),
+      modified_at,
+    })
+  }
+
+  /// Whether the migration of the config has been declined before, and
+  /// the config hasn't changed since.
+  pub fn is_saved(&self) -> bool {
+    Self::path()
+      .and_then(|path| fs::read_to_string(path).ok())
+      .and_then(|json| serde_json::from_str::<Self>(&json).ok())
+      .is_some_and(|saved| saved == *self)
+  }
+
+  pub fn save(&self) -> anyhow::Result<()> {
+    let path = Self::path().context("No home directory.")?;
+    fs::write(path, serde_json::to_string_pretty(self)?)?;
+    Ok(())
+  }
+
+  fn path() -> Option<PathBuf> {
+    home::home_dir()
+      .map(|home| home.join(".glzr/glazewm/declined_migration.json"))
+  }
+}
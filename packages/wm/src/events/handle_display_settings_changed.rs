@@ -1,10 +1,10 @@
 use anyhow::Context;
 use tracing::info;
-use wm_platform::Platform;
 
 use crate::{
   commands::monitor::{
-    add_monitor, remove_monitor, sort_monitors, update_monitor,
+    add_monitor, managed_native_monitors, remove_monitor, sort_monitors,
+    update_monitor,
   },
   traits::{CommonGetters, PositionGetters, WindowGetters},
   user_config::UserConfig,
@@ -17,7 +17,7 @@ pub fn handle_display_settings_changed(
 ) -> anyhow::Result<()> {
   info!("Display settings changed.");
 
-  let native_monitors = Platform::sorted_monitors()?;
+  let native_monitors = managed_native_monitors(state, config)?;
 
   let hardware_ids = native_monitors
     .iter()
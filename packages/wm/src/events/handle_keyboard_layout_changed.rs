@@ -0,0 +1,17 @@
+use tracing::info;
+use wm_common::WmEvent;
+use wm_platform::KeyboardLayout;
+
+use crate::wm_state::WmState;
+
+pub fn handle_keyboard_layout_changed(
+  keyboard_layout: KeyboardLayout,
+  state: &WmState,
+) {
+  info!("Keyboard layout changed: {}", keyboard_layout.locale_name);
+
+  state.emit_event(WmEvent::KeyboardLayoutChanged {
+    locale_name: keyboard_layout.locale_name,
+    layout_handle: keyboard_layout.handle,
+  });
+}
@@ -14,6 +14,10 @@ pub fn handle_window_destroyed(
 ) -> anyhow::Result<()> {
   let found_window = state.window_from_native(native_window);
 
+  state
+    .ignored_windows
+    .retain(|ignored| ignored != native_window);
+
   // Unmanage the window if it's currently managed.
   if let Some(window) = found_window {
     let workspace = window.workspace().context("No workspace.")?;
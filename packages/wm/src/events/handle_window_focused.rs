@@ -1,11 +1,14 @@
 use anyhow::Context;
 use tracing::info;
-use wm_common::{DisplayState, WindowRuleEvent, WmEvent};
+use wm_common::{
+  DisplayState, HiddenWindowActivation, WindowRuleEvent, WmEvent,
+};
 use wm_platform::{NativeWindow, Platform};
 
 use crate::{
   commands::{
-    container::set_focused_descendant, window::run_window_rules,
+    container::set_focused_descendant,
+    window::{move_window_to_workspace, run_window_rules},
     workspace::focus_workspace,
   },
   models::WorkspaceTarget,
@@ -70,16 +73,41 @@ pub fn handle_window_focused(
 
     // Handle focus events from windows on hidden workspaces. For example,
     // if Discord is forcefully shown by the OS when it's on a hidden
-    // workspace, switch focus to Discord's workspace.
-    if window.display_state() == DisplayState::Hidden {
-      info!("Focusing off-screen window: {window}");
-
-      focus_workspace(
-        WorkspaceTarget::Name(workspace.config().name),
-        state,
-        config,
-      )?;
-    }
+    // workspace, either switch focus to Discord's workspace or bring
+    // Discord to the focused workspace.
+    let workspace = if window.display_state() == DisplayState::Hidden {
+      match config.value.general.hidden_window_activation {
+        HiddenWindowActivation::SwitchWorkspace => {
+          info!("Focusing off-screen window: {window}");
+
+          focus_workspace(
+            WorkspaceTarget::Name(workspace.config().name),
+            state,
+            config,
+          )?;
+
+          workspace
+        }
+        HiddenWindowActivation::MoveWindow => {
+          let focused_workspace = focused_container
+            .workspace()
+            .context("No focused workspace.")?;
+
+          info!("Moving off-screen window to focused workspace: {window}");
+
+          move_window_to_workspace(
+            window.clone(),
+            WorkspaceTarget::Name(focused_workspace.config().name),
+            state,
+            config,
+          )?;
+
+          focused_workspace
+        }
+      }
+    } else {
+      workspace
+    };
 
     // Update the WM's focus state.
     set_focused_descendant(&window.clone().into(), None);
@@ -98,6 +126,7 @@ pub fn handle_window_focused(
     // Broadcast the focus change event.
     state.emit_event(WmEvent::FocusChanged {
       focused_container: window.to_dto()?,
+      focused_window: Some(window.to_focused_window_dto()?),
     });
   }
 
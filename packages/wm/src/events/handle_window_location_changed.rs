@@ -1,8 +1,9 @@
 use anyhow::Context;
 use tracing::info;
 use wm_common::{
-  try_warn, ActiveDrag, ActiveDragOperation, FloatingStateConfig,
-  FullscreenStateConfig, Rect, WindowState,
+  try_warn, ActiveDrag, ActiveDragOperation, DisplayState,
+  FloatingStateConfig, FullscreenStateConfig, HideMethod, Rect,
+  WindowState,
 };
 use wm_platform::NativeWindow;
 
@@ -43,6 +44,18 @@ pub fn handle_window_location_changed(
       return Ok(());
     }
 
+    let hide_method = window
+      .hide_method()
+      .unwrap_or_else(|| config.value.general.hide_method.clone());
+
+    // Ignore events for windows on hidden workspaces that have been moved
+    // off-screen via the `offscreen` hide method.
+    if window.display_state() == DisplayState::Hidden
+      && hide_method == HideMethod::Offscreen
+    {
+      return Ok(());
+    }
+
     let is_minimized = try_warn!(window.native().refresh_is_minimized());
 
     // Ignore events for minimized windows. Let them be handled by the
@@ -70,10 +83,7 @@ pub fn handle_window_location_changed(
       .displayed_workspace()
       .context("No Workspace")?;
 
-    let monitor_rect = if config
-      .outer_gaps_for_workspace(&nearest_workspace)
-      .is_significant()
-    {
+    let monitor_rect = if nearest_workspace.outer_gaps().is_significant() {
       nearest_monitor.native().working_rect()?.clone()
     } else {
       nearest_monitor.to_rect()?
@@ -1,10 +1,16 @@
+use anyhow::Context;
 use tracing::info;
 use wm_common::{try_warn, WindowState};
 use wm_platform::NativeWindow;
 
 use crate::{
-  commands::window::update_window_state, traits::WindowGetters,
-  user_config::UserConfig, wm_state::WmState,
+  commands::{
+    window::update_window_state,
+    workspace::{enforce_max_visible_tiles, remove_from_overflow},
+  },
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
 };
 
 pub fn handle_window_minimize_ended(
@@ -25,7 +31,21 @@ pub fn handle_window_minimize_ended(
         .prev_state()
         .unwrap_or(WindowState::default_from_config(&config.value));
 
-      update_window_state(window.clone(), target_state, state, config)?;
+      let window =
+        update_window_state(window.clone(), target_state, state, config)?;
+
+      // Restoring a window from the overflow stack can push another
+      // window into it instead.
+      remove_from_overflow(&window)?;
+
+      if window.state() == WindowState::Tiling {
+        enforce_max_visible_tiles(
+          &window.workspace().context("No workspace.")?,
+          Some(&window),
+          state,
+          config,
+        )?;
+      }
     }
   }
 
@@ -11,6 +11,7 @@ use crate::{
     container::{move_container_within_tree, wrap_in_split_container},
     window::{resize_window, update_window_state},
   },
+  floating_geometry::FloatingGeometry,
   models::{
     DirectionContainer, NonTilingWindow, SplitContainer, TilingContainer,
     WindowContainer,
@@ -50,6 +51,14 @@ pub fn handle_window_moved_or_resized_end(
 
     match &window {
       WindowContainer::NonTilingWindow(window) => {
+        // Remember the geometry of floating windows, so that it can be
+        // restored when the application is reopened.
+        if FloatingGeometry::is_tracked(&window.clone().into(), config) {
+          state
+            .floating_geometry
+            .update(&window.native(), new_rect.clone());
+        }
+
         if let Some(active_drag) = window.active_drag() {
           if active_drag.is_from_tiling
             && active_drag.operation == Some(ActiveDragOperation::Moving)
@@ -1,10 +1,15 @@
 use tracing::info;
-use wm_common::DisplayState;
+use wm_common::{DisplayState, TraceEvent};
 use wm_platform::NativeWindow;
 
 use crate::{
-  commands::window::manage_window, traits::WindowGetters,
-  user_config::UserConfig, wm_state::WmState,
+  commands::{
+    container::set_focused_descendant, general::launch_origin_workspace,
+    window::manage_window,
+  },
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
 };
 
 pub fn handle_window_shown(
@@ -26,12 +31,87 @@ pub fn handle_window_shown(
       }
     }
     None => {
-      // If the window is not managed, manage it.
-      if native_window.is_manageable().unwrap_or(false) {
-        manage_window(native_window, None, state, config)?;
+      // Defer managing the window while a workspace switch is in flight,
+      // since it'd otherwise race with the windows being shown or hidden.
+      if state.ignored_windows.contains(&native_window) {
+        info!("Ignoring window that was previously released.");
+      } else if state.is_display_transition_pending() {
+        info!("Deferring window shown during workspace switch.");
+
+        if !state.pending_shown_windows.contains(&native_window) {
+          state.pending_shown_windows.push(native_window);
+        }
+      } else if native_window.is_manageable().unwrap_or(false) {
+        let is_managed_workspace = !state
+          .is_on_unmanaged_monitor(&native_window)
+          && state
+            .nearest_monitor(&native_window)
+            .and_then(|monitor| monitor.displayed_workspace())
+            .is_some_and(|workspace| {
+              config.is_managed_workspace(&workspace)
+            });
+
+        // If the window is not managed, manage it. Windows outside of the
+        // monitors and workspaces the WM is restricted to are left to
+        // other window managers.
+        if is_managed_workspace {
+          manage_launched_window(native_window, state, config)?;
+        } else {
+          info!("Ignoring window outside of managed workspaces.");
+        }
+      } else {
+        state.emit_trace(|| TraceEvent::WindowNotManageable {
+          handle: native_window.handle,
+          process_name: native_window.process_name().ok(),
+          class_name: native_window.class_name().ok(),
+          title: native_window.title().ok(),
+        });
       }
     }
   }
 
   Ok(())
 }
+
+/// Manages the window, opening it on the workspace it was launched from
+/// if that workspace is no longer focused.
+fn manage_launched_window(
+  native_window: NativeWindow,
+  state: &mut WmState,
+  config: &mut UserConfig,
+) -> anyhow::Result<()> {
+  let focused_container = state.focused_container();
+
+  let origin_workspace =
+    launch_origin_workspace(&native_window, state, config)?.filter(
+      |workspace| {
+        focused_container
+          .as_ref()
+          .and_then(CommonGetters::workspace)
+          .is_none_or(|focused| focused.id() != workspace.id())
+      },
+    );
+
+  let Some(origin_workspace) = origin_workspace else {
+    return manage_window(native_window, None, state, config);
+  };
+
+  info!("Opening window on launch workspace: {origin_workspace}");
+  manage_window(
+    native_window,
+    Some(origin_workspace.into()),
+    state,
+    config,
+  )?;
+
+  // Keep focus on the current workspace rather than following the window
+  // to its origin workspace.
+  if let Some(focused_container) = focused_container {
+    if !focused_container.is_detached() {
+      set_focused_descendant(&focused_container, None);
+      state.pending_sync.queue_focus_change();
+    }
+  }
+
+  Ok(())
+}
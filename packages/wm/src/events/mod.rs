@@ -1,4 +1,5 @@
 mod handle_display_settings_changed;
+mod handle_keyboard_layout_changed;
 mod handle_mouse_move;
 mod handle_window_destroyed;
 mod handle_window_focused;
@@ -12,6 +13,7 @@ mod handle_window_shown;
 mod handle_window_title_changed;
 
 pub use handle_display_settings_changed::*;
+pub use handle_keyboard_layout_changed::*;
 pub use handle_mouse_move::*;
 pub use handle_window_destroyed::*;
 pub use handle_window_focused::*;
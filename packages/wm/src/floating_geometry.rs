@@ -0,0 +1,104 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use anyhow::Context;
+use tracing::warn;
+use wm_common::{Rect, WindowState, WorkspaceLayout};
+use wm_platform::NativeWindow;
+
+use crate::{
+  models::WindowContainer,
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+};
+
+/// Last-known floating geometry of windows, keyed by application
+/// identity (process name + window class).
+///
+/// Persisted to `~/.glzr/glazewm/floating_geometry.json`, so that
+/// windows are restored to the same geometry across sessions.
+#[derive(Debug, Default)]
+pub struct FloatingGeometry {
+  path: Option<PathBuf>,
+  rects: HashMap<String, Rect>,
+}
+
+impl FloatingGeometry {
+  /// Reads the persisted geometry from disk. Starts out empty if the file
+  /// doesn't exist or is invalid.
+  pub fn load() -> Self {
+    let path = home::home_dir()
+      .map(|home| home.join(".glzr/glazewm/floating_geometry.json"));
+
+    let rects = path
+      .as_ref()
+      .filter(|path| path.exists())
+      .and_then(|path| match Self::read(path) {
+        Ok(rects) => Some(rects),
+        Err(err) => {
+          warn!("Failed to read floating geometry: {err}");
+          None
+        }
+      })
+      .unwrap_or_default();
+
+    Self { path, rects }
+  }
+
+  /// Whether the floating geometry of the window is remembered. This is
+  /// the case for floating windows if `remember_floating_geometry` is
+  /// enabled, or if they're on a workspace with a floating layout.
+  pub fn is_tracked(
+    window: &WindowContainer,
+    config: &UserConfig,
+  ) -> bool {
+    let is_floating_layout = window.workspace().is_some_and(|workspace| {
+      workspace.layout() == WorkspaceLayout::Floating
+    });
+
+    matches!(window.state(), WindowState::Floating(_))
+      && (config.value.window_behavior.remember_floating_geometry
+        || is_floating_layout)
+  }
+
+  /// Gets the last-known geometry for the window's application.
+  pub fn get(&self, native_window: &NativeWindow) -> Option<&Rect> {
+    Self::key(native_window).and_then(|key| self.rects.get(&key))
+  }
+
+  /// Updates the geometry for the window's application and persists it
+  /// to disk.
+  pub fn update(&mut self, native_window: &NativeWindow, rect: Rect) {
+    let Some(key) = Self::key(native_window) else {
+      return;
+    };
+
+    if self.rects.get(&key) == Some(&rect) {
+      return;
+    }
+
+    self.rects.insert(key, rect);
+
+    if let Err(err) = self.save() {
+      warn!("Failed to save floating geometry: {err}");
+    }
+  }
+
+  /// Gets the identity of the window's application, of format
+  /// `<process name>/<class name>`.
+  fn key(native_window: &NativeWindow) -> Option<String> {
+    let process_name = native_window.process_name().ok()?;
+    let class_name = native_window.class_name().ok()?;
+    Some(format!("{process_name}/{class_name}"))
+  }
+
+  fn read(path: &PathBuf) -> anyhow::Result<HashMap<String, Rect>> {
+    let json = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+  }
+
+  fn save(&self) -> anyhow::Result<()> {
+    let path = self.path.as_ref().context("No home directory.")?;
+    fs::write(path, serde_json::to_string_pretty(&self.rects)?)?;
+    Ok(())
+  }
+}
@@ -0,0 +1,314 @@
+use std::net::SocketAddr;
+
+use anyhow::Context;
+use serde_json::json;
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::{TcpListener, TcpStream},
+  sync::{
+    broadcast::{self, error::RecvError},
+    mpsc, oneshot,
+  },
+  task::{self, JoinSet},
+};
+use tracing::{info, warn};
+use wm_common::{HttpServerConfig, WmEvent};
+
+use crate::{traits::CommonGetters, wm::WindowManager};
+
+/// Maximum size of an incoming HTTP request head in bytes.
+const MAX_REQUEST_SIZE: usize = 8192;
+
+/// Sender for requesting a snapshot of the WM state from the main loop.
+type StateRequestTx =
+  mpsc::UnboundedSender<oneshot::Sender<serde_json::Value>>;
+
+/// Optional localhost HTTP server for debugging and visualization tools.
+///
+/// Serves the following routes:
+///  * `GET /state`: Container tree and other WM state as JSON.
+///  * `GET /events`: Stream of WM events as server-sent events.
+///
+/// Requests are rejected unless their `Host` and `Origin` headers refer
+/// to localhost, so that websites can't access the server via DNS
+/// rebinding.
+pub struct HttpServer {
+  /// Receiver for state snapshot requests. The snapshot is created in the
+  /// main loop, since the WM state isn't shared across threads.
+  pub state_request_rx:
+    mpsc::UnboundedReceiver<oneshot::Sender<serde_json::Value>>,
+  state_request_tx: StateRequestTx,
+  event_tx: broadcast::Sender<WmEvent>,
+  abort_handle: Option<task::AbortHandle>,
+  port: Option<u16>,
+}
+
+impl HttpServer {
+  pub fn new() -> Self {
+    let (state_request_tx, state_request_rx) = mpsc::unbounded_channel();
+    let (event_tx, _) = broadcast::channel(64);
+
+    Self {
+      state_request_rx,
+      state_request_tx,
+      event_tx,
+      abort_handle: None,
+      port: None,
+    }
+  }
+
+  /// Starts, restarts, or stops the server to match the given config.
+  pub fn update(
+    &mut self,
+    config: &HttpServerConfig,
+  ) -> anyhow::Result<()> {
+    let target_port = config.enabled.then_some(config.port);
+
+    if self.port == target_port {
+      return Ok(());
+    }
+
+    self.stop();
+
+    if let Some(port) = target_port {
+      self.start(port)?;
+    }
+
+    Ok(())
+  }
+
+  fn start(&mut self, port: u16) -> anyhow::Result<()> {
+    let server_addr = SocketAddr::from(([127, 0, 0, 1], port));
+
+    let listener =
+      std::net::TcpListener::bind(server_addr).with_context(|| {
+        format!("Unable to bind HTTP server to port {port}.")
+      })?;
+
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+    info!("HTTP server started on: '{}'.", server_addr);
+
+    let state_request_tx = self.state_request_tx.clone();
+    let event_tx = self.event_tx.clone();
+
+    let task = task::spawn(async move {
+      // Connection tasks are aborted once the set is dropped, i.e. when
+      // the server is stopped.
+      let mut connections = JoinSet::new();
+
+      loop {
+        tokio::select! {
+          accepted = listener.accept() => {
+            let Ok((stream, _)) = accepted else {
+              break;
+            };
+
+            let state_request_tx = state_request_tx.clone();
+            let event_rx = event_tx.subscribe();
+
+            connections.spawn(async move {
+              if let Err(err) = Self::handle_connection(
+                stream,
+                state_request_tx,
+                event_rx,
+              )
+              .await
+              {
+                warn!("Error handling HTTP connection: {}", err);
+              }
+            });
+          }
+          // Clean up connections that have been closed.
+          Some(_) = connections.join_next() => {}
+        }
+      }
+    });
+
+    self.abort_handle = Some(task.abort_handle());
+    self.port = Some(port);
+
+    Ok(())
+  }
+
+  async fn handle_connection(
+    mut stream: TcpStream,
+    state_request_tx: StateRequestTx,
+    mut event_rx: broadcast::Receiver<WmEvent>,
+  ) -> anyhow::Result<()> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 1024];
+
+    // Read until the end of the request head. Request bodies are ignored.
+    while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+      let read_count = stream.read(&mut chunk).await?;
+
+      if read_count == 0 || buffer.len() > MAX_REQUEST_SIZE {
+        return Ok(());
+      }
+
+      buffer.extend_from_slice(&chunk[..read_count]);
+    }
+
+    let request = String::from_utf8_lossy(&buffer);
+    let mut request_line = request.split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+
+    if !Self::is_local_request(&request) {
+      return Self::write_response(
+        &mut stream,
+        "403 Forbidden",
+        "text/plain",
+        "Forbidden.",
+      )
+      .await;
+    }
+
+    match (method, path) {
+      ("GET", "/state") => {
+        let (response_tx, response_rx) = oneshot::channel();
+        state_request_tx.send(response_tx)?;
+        let state = response_rx.await?;
+
+        Self::write_response(
+          &mut stream,
+          "200 OK",
+          "application/json",
+          &serde_json::to_string(&state)?,
+        )
+        .await
+      }
+      ("GET", "/events") => {
+        stream
+          .write_all(
+            b"HTTP/1.1 200 OK\r\n\
+              Content-Type: text/event-stream\r\n\
+              Cache-Control: no-cache\r\n\
+              Connection: keep-alive\r\n\r\n",
+          )
+          .await?;
+
+        loop {
+          let event = tokio::select! {
+            event = event_rx.recv() => event,
+            // Clients don't send anything after the request, so reading
+            // only completes once the client disconnects.
+            _ = stream.read(&mut chunk) => break Ok(()),
+          };
+
+          match event {
+            Ok(event) => {
+              let event_json = serde_json::to_string(&event)?;
+              stream
+                .write_all(format!("data: {event_json}\n\n").as_bytes())
+                .await?;
+            }
+            // Skip events that were missed due to a slow client.
+            Err(RecvError::Lagged(_)) => {}
+            Err(RecvError::Closed) => break Ok(()),
+          }
+        }
+      }
+      _ => {
+        Self::write_response(
+          &mut stream,
+          "404 Not Found",
+          "text/plain",
+          "Not found.",
+        )
+        .await
+      }
+    }
+  }
+
+  /// Whether the `Host` header, and the `Origin` header if present,
+  /// refer to localhost.
+  fn is_local_request(request: &str) -> bool {
+    let header = |name: &str| {
+      request.lines().skip(1).find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim().eq_ignore_ascii_case(name).then(|| value.trim())
+      })
+    };
+
+    let is_local_host = |host: &str| {
+      let hostname = host
+        .rsplit_once(':')
+        .filter(|(_, port)| port.parse::<u16>().is_ok())
+        .map_or(host, |(hostname, _)| hostname);
+
+      matches!(hostname, "localhost" | "127.0.0.1")
+    };
+
+    let is_host_valid = header("host").is_some_and(is_local_host);
+
+    let is_origin_valid = header("origin").is_none_or(|origin| {
+      origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+        .is_some_and(is_local_host)
+    });
+
+    is_host_valid && is_origin_valid
+  }
+
+  async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+  ) -> anyhow::Result<()> {
+    let response = format!(
+      "HTTP/1.1 {status}\r\n\
+       Content-Type: {content_type}\r\n\
+       Content-Length: {}\r\n\
+       Connection: close\r\n\r\n\
+       {body}",
+      body.len()
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+  }
+
+  /// Creates a snapshot of the WM state to respond with on `GET /state`.
+  pub fn state_snapshot(
+    wm: &WindowManager,
+    pending_event_count: usize,
+  ) -> anyhow::Result<serde_json::Value> {
+    Ok(json!({
+      "root": wm.state.root_container.to_dto()?,
+      "focusedContainerId": wm
+        .state
+        .focused_container()
+        .map(|container| container.id()),
+      "bindingModes": wm.state.binding_modes,
+      "isPaused": wm.state.is_paused,
+      "pendingEventCount": pending_event_count,
+    }))
+  }
+
+  /// Forwards a WM event to clients connected to `GET /events`.
+  pub fn process_event(&self, event: WmEvent) {
+    if self.port.is_some() {
+      // Sending fails if there are no connected clients, which is fine.
+      let _ = self.event_tx.send(event);
+    }
+  }
+
+  pub fn stop(&mut self) {
+    if let Some(abort_handle) = self.abort_handle.take() {
+      info!("Shutting down HTTP server.");
+      abort_handle.abort();
+    }
+
+    self.port = None;
+  }
+}
+
+impl Drop for HttpServer {
+  fn drop(&mut self) {
+    self.stop();
+  }
+}
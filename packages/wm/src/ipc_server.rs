@@ -1,4 +1,4 @@
-use std::{iter, net::SocketAddr};
+use std::{collections::VecDeque, iter, net::SocketAddr};
 
 use anyhow::{bail, Context};
 use clap::Parser;
@@ -13,14 +13,25 @@ use tracing::{info, warn};
 use uuid::Uuid;
 use wm_common::{
   AppCommand, AppMetadataData, BindingModesData, ClientResponseData,
-  ClientResponseMessage, CommandData, EventSubscribeData,
-  EventSubscriptionMessage, FocusedData, MonitorsData, QueryCommand,
-  ServerMessage, SubscribableEvent, TilingDirectionData, WindowsData,
-  WmEvent, WorkspacesData, DEFAULT_IPC_PORT,
+  ClientResponseMessage, CommandData, CommandSource, ContainerDto,
+  CustomEventData, EventSubscribeData, EventSubscriptionMessage,
+  ExplainWindowData, ExportRulesData, ExportedWindowRule, FocusAssistData,
+  FocusedData, FocusedWindowData, GapsData, HelloData, InvokeCommand,
+  IpcEncoding, KeyboardLayoutData, LogLevelData, LogsData,
+  MenuWindowEntry, MonitorsData, QueryCommand, ServerMessage, StatsData,
+  SubscribableEvent, TilingDirectionData, TimersData,
+  WindowListForMenuData, WindowState, WindowThumbnailData, WindowsData,
+  WmEvent, WorkspacesData, DEFAULT_IPC_PORT, IPC_CAPABILITIES,
+  IPC_PROTOCOL_VERSION,
 };
+use wm_platform::{NativeWindow, Platform};
 
 use crate::{
-  traits::{CommonGetters, TilingDirectionGetters},
+  base64::to_base64,
+  logging::{recent_logs, set_log_filter},
+  models::WindowContainer,
+  msgpack::to_msgpack,
+  traits::{CommonGetters, TilingDirectionGetters, WindowGetters},
   user_config::UserConfig,
   wm::WindowManager,
 };
@@ -29,11 +40,19 @@ pub struct IpcServer {
   abort_handle: task::AbortHandle,
   pub message_rx: mpsc::UnboundedReceiver<(
     String,
-    mpsc::UnboundedSender<Message>,
+    CommandSource,
+    mpsc::UnboundedSender<ServerMessage>,
     broadcast::Sender<()>,
   )>,
-  _event_rx: broadcast::Receiver<(SubscribableEvent, WmEvent)>,
-  event_tx: broadcast::Sender<(SubscribableEvent, WmEvent)>,
+  _event_rx: broadcast::Receiver<(u64, SubscribableEvent, WmEvent)>,
+  event_tx: broadcast::Sender<(u64, SubscribableEvent, WmEvent)>,
+
+  /// Recent events with their sequence numbers, for replaying to new
+  /// subscribers.
+  event_buffer: VecDeque<(u64, SubscribableEvent, WmEvent)>,
+
+  /// Sequence number to assign to the next emitted event.
+  next_sequence: u64,
   _unsubscribe_rx: broadcast::Receiver<Uuid>,
   unsubscribe_tx: broadcast::Sender<Uuid>,
 }
@@ -67,6 +86,8 @@ impl IpcServer {
       #[allow(clippy::used_underscore_binding)]
       _event_rx,
       event_tx,
+      event_buffer: VecDeque::new(),
+      next_sequence: 0,
       message_rx,
       unsubscribe_tx,
       #[allow(clippy::used_underscore_binding)]
@@ -79,7 +100,8 @@ impl IpcServer {
     addr: SocketAddr,
     message_tx: mpsc::UnboundedSender<(
       String,
-      mpsc::UnboundedSender<Message>,
+      CommandSource,
+      mpsc::UnboundedSender<ServerMessage>,
       broadcast::Sender<()>,
     )>,
   ) -> anyhow::Result<()> {
@@ -93,11 +115,29 @@ impl IpcServer {
     let (response_tx, mut response_rx) = mpsc::unbounded_channel();
     let (disconnection_tx, _) = broadcast::channel(16);
 
+    // Messages are encoded as JSON until the client requests otherwise
+    // via the `hello` handshake.
+    let mut encoding = IpcEncoding::Json;
+
+    // Source of the connection's commands. This is tracked here rather
+    // than passed with each command, so that command strings from
+    // untrusted input (e.g. `glazewm://` URLs) can't change it.
+    let mut source = CommandSource::Ipc;
+
     let res = async {
       loop {
         tokio::select! {
           Some(response) = response_rx.recv() => {
-            outgoing.send(response).await?;
+            outgoing.send(Self::encode_message(&response, encoding)?).await?;
+
+            if let ServerMessage::ClientResponse(ClientResponseMessage {
+              data: Some(ClientResponseData::Hello(hello)),
+              ..
+            }) = &response
+            {
+              encoding = hello.encoding;
+              source = hello.source;
+            }
           }
           message = incoming.next() => {
             match message {
@@ -105,6 +145,7 @@ impl IpcServer {
                 if message.is_text() || message.is_binary() {
                   message_tx.send((
                     message.to_text()?.to_string(),
+                    source,
                     response_tx.clone(),
                     disconnection_tx.clone(),
                   ))?;
@@ -134,7 +175,8 @@ impl IpcServer {
   pub fn process_message(
     &self,
     message: String,
-    response_tx: &mpsc::UnboundedSender<Message>,
+    source: CommandSource,
+    response_tx: &mpsc::UnboundedSender<ServerMessage>,
     disconnection_tx: &broadcast::Sender<()>,
     wm: &mut WindowManager,
     config: &mut UserConfig,
@@ -149,6 +191,7 @@ impl IpcServer {
         .and_then(|app_command| {
           self.handle_app_command(
             app_command,
+            source,
             response_tx,
             disconnection_tx,
             wm,
@@ -158,7 +201,7 @@ impl IpcServer {
 
     // Respond to the client with the result of the command.
     response_tx
-      .send(Self::to_client_response_msg(message, response_data)?)
+      .send(Self::to_client_response_msg(message, response_data))
       .map_err(|err| {
         anyhow::anyhow!("Failed to send response: {}", err)
       })?;
@@ -170,7 +213,8 @@ impl IpcServer {
   fn handle_app_command(
     &self,
     app_command: AppCommand,
-    response_tx: &mpsc::UnboundedSender<Message>,
+    source: CommandSource,
+    response_tx: &mpsc::UnboundedSender<ServerMessage>,
     disconnection_tx: &broadcast::Sender<()>,
     wm: &mut WindowManager,
     config: &mut UserConfig,
@@ -182,8 +226,8 @@ impl IpcServer {
             windows: wm
               .state
               .windows()
-              .into_iter()
-              .map(|window| window.to_dto())
+              .iter()
+              .map(|window| Self::window_dto(window, config))
               .try_collect()?,
           })
         }
@@ -215,16 +259,45 @@ impl IpcServer {
         QueryCommand::Focused => {
           let focused_container = wm
             .state
-            .focused_container()
+            .focused_subject()
             .context("No focused container.")?;
 
-          ClientResponseData::Focused(FocusedData {
-            focused: focused_container.to_dto()?,
+          let focused = match focused_container.as_window_container() {
+            Ok(window) => Self::window_dto(&window, config)?,
+            Err(_) => focused_container.to_dto()?,
+          };
+
+          ClientResponseData::Focused(FocusedData { focused })
+        }
+        QueryCommand::FocusedWindow => {
+          let focused_window = wm
+            .state
+            .focused_container()
+            .and_then(|focused| focused.as_window_container().ok())
+            .map(|window| window.to_focused_window_dto())
+            .transpose()?;
+
+          ClientResponseData::FocusedWindow(FocusedWindowData {
+            focused_window,
+          })
+        }
+        QueryCommand::Gaps => {
+          let workspace = wm
+            .state
+            .focused_container()
+            .and_then(|focused| focused.workspace())
+            .context("No focused workspace.")?;
+
+          ClientResponseData::Gaps(GapsData {
+            workspace_name: workspace.config().name,
+            workspace_gaps: workspace.gaps_config(),
+            global_gaps: config.value.gaps.clone(),
           })
         }
         QueryCommand::AppMetadata => {
           ClientResponseData::AppMetadata(AppMetadataData {
             version: env!("VERSION_NUMBER").to_string(),
+            is_elevated: Platform::is_elevated()?,
           })
         }
         QueryCommand::TilingDirection => {
@@ -242,7 +315,129 @@ impl IpcServer {
         QueryCommand::Paused => {
           ClientResponseData::Paused(wm.state.is_paused)
         }
+        QueryCommand::KeyboardLayout => {
+          let keyboard_layout = Platform::keyboard_layout();
+
+          ClientResponseData::KeyboardLayout(KeyboardLayoutData {
+            locale_name: keyboard_layout.locale_name,
+            layout_handle: keyboard_layout.handle,
+          })
+        }
+        QueryCommand::FocusAssist => {
+          ClientResponseData::FocusAssist(FocusAssistData {
+            focus_assist_mode: Platform::focus_assist_mode()?,
+          })
+        }
+        QueryCommand::Logs { tail } => {
+          ClientResponseData::Logs(LogsData {
+            logs: recent_logs(tail),
+          })
+        }
+        QueryCommand::Stats => ClientResponseData::Stats(StatsData {
+          stats: wm.state.perf_stats.clone(),
+        }),
+        QueryCommand::Timers => ClientResponseData::Timers(TimersData {
+          timers: wm.state.scheduler.timer_statuses(),
+        }),
+        QueryCommand::WindowListForMenu => {
+          ClientResponseData::WindowListForMenu(
+            Self::window_list_for_menu(wm, config),
+          )
+        }
+        QueryCommand::WindowThumbnail {
+          container_id,
+          max_size,
+        } => {
+          let window = wm
+            .state
+            .container_by_id(container_id)
+            .and_then(|container| container.as_window_container().ok())
+            .with_context(|| {
+              format!(
+                "No window found with the given ID '{container_id}'."
+              )
+            })?;
+
+          let native = window.native().clone();
+          let thumbnail =
+            Platform::capture_window_thumbnail(&native, max_size)?;
+
+          ClientResponseData::WindowThumbnail(WindowThumbnailData {
+            container_id,
+            thumbnail_handle: native.handle,
+            thumbnail_width: thumbnail.width,
+            thumbnail_height: thumbnail.height,
+            png_base64: to_base64(&thumbnail.png),
+          })
+        }
       },
+      AppCommand::Hello {
+        client_name,
+        client_version,
+        encoding,
+        source: new_source,
+      } => {
+        info!(
+          "IPC client hello: name={}, version={}.",
+          client_name.as_deref().unwrap_or("unknown"),
+          client_version.as_deref().unwrap_or("unknown"),
+        );
+
+        // Other sources are reserved for commands run by the WM itself.
+        if let Some(new_source) = new_source {
+          if source != CommandSource::Ipc {
+            bail!("Command source is already set for this connection.");
+          }
+
+          if !matches!(new_source, CommandSource::Cli | CommandSource::Url)
+          {
+            bail!("Command source must be `cli` or `url`.");
+          }
+        }
+
+        ClientResponseData::Hello(HelloData {
+          encoding: encoding.unwrap_or_default(),
+          source: new_source.unwrap_or(source),
+          version: env!("VERSION_NUMBER").to_string(),
+          protocol_version: IPC_PROTOCOL_VERSION,
+          capabilities: IPC_CAPABILITIES
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        })
+      }
+      AppCommand::EmitCustomEvent { name, payload } => {
+        let payload = payload.join(" ");
+
+        wm.state.emit_event(WmEvent::CustomEvent {
+          name: name.clone(),
+          payload: match payload.as_str() {
+            "" => serde_json::Value::Null,
+            _ => serde_json::from_str(&payload)
+              .unwrap_or(serde_json::Value::String(payload)),
+          },
+        });
+
+        ClientResponseData::CustomEvent(CustomEventData {
+          event_name: name,
+        })
+      }
+      AppCommand::SetLogLevel { filter } => {
+        set_log_filter(&filter)?;
+        ClientResponseData::LogLevel(LogLevelData { filter })
+      }
+      AppCommand::ExportRules => {
+        ClientResponseData::ExportRules(Self::export_rules(wm, config)?)
+      }
+      AppCommand::ExplainWindow { hwnd, .. } => {
+        let handle = hwnd.context("No window handle given.")?;
+
+        ClientResponseData::ExplainWindow(Self::explain_window(
+          &NativeWindow::new(handle),
+          wm,
+          config,
+        )?)
+      }
       AppCommand::Command {
         subject_container_id,
         command,
@@ -250,6 +445,7 @@ impl IpcServer {
         let subject_container_id = wm.process_commands(
           &vec![command],
           subject_container_id,
+          source,
           config,
         )?;
 
@@ -257,10 +453,48 @@ impl IpcServer {
           subject_container_id,
         })
       }
-      AppCommand::Sub { events } => {
+      AppCommand::Sub {
+        events,
+        replay_from,
+      } => {
         let subscription_id = Uuid::new_v4();
         info!("New event subscription {}: {:?}", subscription_id, events);
 
+        let is_subscribed = move |event_type: &SubscribableEvent| {
+          events.contains(event_type)
+            || events.contains(&SubscribableEvent::All)
+        };
+
+        // Events are emitted on the same thread as IPC messages are
+        // processed, so no events can be missed between the replay and
+        // the subscription.
+        let (replayed_events, has_missed_events) = match replay_from {
+          Some(replay_from) => {
+            let replayed_events = self
+              .event_buffer
+              .iter()
+              .filter(|(sequence, event_type, _)| {
+                *sequence >= replay_from && is_subscribed(event_type)
+              })
+              .map(|(sequence, _, event)| {
+                Self::event_subscription_msg(
+                  subscription_id,
+                  *sequence,
+                  event.clone(),
+                )
+              })
+              .collect();
+
+            let oldest_sequence = self
+              .event_buffer
+              .front()
+              .map_or(self.next_sequence, |(sequence, ..)| *sequence);
+
+            (replayed_events, replay_from < oldest_sequence)
+          }
+          None => (Vec::new(), false),
+        };
+
         let response_tx = response_tx.clone();
         let mut event_rx = self.event_tx.subscribe();
         let mut unsubscribe_rx = self.unsubscribe_tx.subscribe();
@@ -277,18 +511,18 @@ impl IpcServer {
                   break;
                 }
               }
-              Ok((event_type, event)) = event_rx.recv() => {
+              Ok((sequence, event_type, event)) = event_rx.recv() => {
                 // Check whether the event is one of the subscribed events.
-                if events.contains(&event_type)
-                  || events.contains(&SubscribableEvent::All)
-                {
-                  let res = Self::to_event_subscription_msg(
-                    subscription_id,
-                    event,
-                  )
-                  .map(|event_msg| response_tx.send(event_msg));
-
-                  if let Err(err) = res {
+                if is_subscribed(&event_type) {
+                  let event_msg = ServerMessage::EventSubscription(
+                    Self::event_subscription_msg(
+                      subscription_id,
+                      sequence,
+                      event,
+                    ),
+                  );
+
+                  if let Err(err) = response_tx.send(event_msg) {
                     warn!("Error emitting WM event: {}", err);
                     break;
                   }
@@ -300,6 +534,8 @@ impl IpcServer {
 
         ClientResponseData::EventSubscribe(EventSubscribeData {
           subscription_id,
+          replayed_events,
+          has_missed_events,
         })
       }
       AppCommand::Unsub { subscription_id } => {
@@ -310,69 +546,282 @@ impl IpcServer {
 
         ClientResponseData::EventUnsubscribe
       }
-      AppCommand::Start { .. } => bail!("Unsupported IPC command."),
+      AppCommand::Start { .. }
+      | AppCommand::MigrateConfig { .. }
+      | AppCommand::ImportI3 { .. }
+      | AppCommand::ImportKomorebi { .. }
+      | AppCommand::CheckConfig { .. }
+      | AppCommand::Doctor { .. }
+      | AppCommand::Autostart { .. }
+      | AppCommand::Protocol { .. }
+      | AppCommand::OpenUrl { .. }
+      | AppCommand::Pick { .. } => {
+        bail!("Unsupported IPC command.")
+      }
     };
 
     Ok(response_data)
   }
 
+  /// Gets the properties of a window and the results of evaluating each
+  /// window rule against it.
+  fn explain_window(
+    native_window: &NativeWindow,
+    wm: &WindowManager,
+    config: &UserConfig,
+  ) -> anyhow::Result<ExplainWindowData> {
+    if !native_window.is_valid() {
+      bail!("No window found with handle {}.", native_window.handle);
+    }
+
+    let managed_window = wm.state.window_from_native(native_window);
+
+    let done_rules = managed_window
+      .as_ref()
+      .map(WindowGetters::done_window_rules)
+      .unwrap_or_default();
+
+    Ok(ExplainWindowData {
+      handle: native_window.handle,
+      title: native_window.title()?,
+      class_name: native_window.class_name()?,
+      process_name: native_window.process_name()?,
+      process_path: native_window.process_path()?,
+      styles: native_window.style_names(),
+      extended_styles: native_window.ex_style_names(),
+      is_elevated: native_window.is_elevated().ok(),
+      is_manageable: native_window.is_manageable().unwrap_or(false),
+      is_ignored: wm.state.ignored_windows.contains(native_window),
+      managed_id: managed_window.as_ref().map(CommonGetters::id),
+      window_state: managed_window.as_ref().map(WindowGetters::state),
+      window_rules: config.window_rule_matches(
+        native_window,
+        managed_window
+          .as_ref()
+          .map(WindowGetters::media_usage)
+          .unwrap_or_default(),
+        &done_rules,
+      )?,
+    })
+  }
+
+  /// Gets the adjustments made to windows since they were managed, as
+  /// window rules that would reproduce them.
+  fn export_rules(
+    wm: &WindowManager,
+    config: &UserConfig,
+  ) -> anyhow::Result<ExportRulesData> {
+    let mut exported_rules = Vec::new();
+
+    for window in wm.state.windows() {
+      let Some(origin) = wm.state.managed_origins.get(&window.id()) else {
+        continue;
+      };
+
+      // Only changes between tiling and floating are exported, since
+      // fullscreen and minimized are usually temporary.
+      let state_command = match (&origin.state, window.state()) {
+        (WindowState::Tiling, WindowState::Floating(_)) => {
+          Some("set-floating".to_string())
+        }
+        (WindowState::Floating(_), WindowState::Tiling) => {
+          Some("set-tiling".to_string())
+        }
+        _ => None,
+      };
+
+      let workspace_name =
+        window.workspace().context("No workspace.")?.config().name;
+
+      let workspace_command = (workspace_name != origin.workspace_name)
+        .then(|| format!("move --workspace {workspace_name}"));
+
+      for command in state_command.into_iter().chain(workspace_command) {
+        exported_rules.push(ExportedWindowRule {
+          command,
+          window_process: window.native().process_name()?,
+          window_class: window.native().class_name()?,
+        });
+      }
+    }
+
+    // Windows ignored by a window rule are left out. Ignored windows
+    // that have since been destroyed are skipped.
+    for native_window in &wm.state.ignored_windows {
+      let is_rule_ignored = config
+        .has_matching_rule_command(native_window, |command| {
+          matches!(command, InvokeCommand::Ignore)
+        });
+
+      if let Ok(false) = is_rule_ignored {
+        exported_rules.push(ExportedWindowRule {
+          command: "ignore".to_string(),
+          window_process: native_window.process_name()?,
+          window_class: native_window.class_name()?,
+        });
+      }
+    }
+
+    Ok(ExportRulesData { exported_rules })
+  }
+
+  /// Gets the DTO of a window, including the CPU and memory usage of its
+  /// process if `general.window_process_stats` is enabled.
+  fn window_dto(
+    window: &WindowContainer,
+    config: &UserConfig,
+  ) -> anyhow::Result<ContainerDto> {
+    let mut dto = window.to_dto()?;
+
+    if config.value.general.window_process_stats {
+      if let ContainerDto::Window(window_dto) = &mut dto {
+        window_dto.process_stats = window.native().process_stats().ok();
+      }
+    }
+
+    Ok(dto)
+  }
+
+  /// Gets all windows ordered by workspace, and by their position in the
+  /// tree within each workspace.
+  fn window_list_for_menu(
+    wm: &WindowManager,
+    config: &UserConfig,
+  ) -> WindowListForMenuData {
+    let focused_container = wm.state.focused_container();
+    let mut menu_windows = Vec::new();
+
+    for workspace in wm.state.sorted_workspaces(config) {
+      let workspace_config = workspace.config();
+
+      let windows = workspace
+        .descendants()
+        .filter_map(|descendant| descendant.as_window_container().ok());
+
+      for window in windows {
+        let native_window = window.native();
+
+        menu_windows.push(MenuWindowEntry {
+          container_id: window.id(),
+          title: native_window.title().unwrap_or_default(),
+          process_name: native_window.process_name().unwrap_or_default(),
+          workspace_name: workspace_config.name.clone(),
+          workspace_label: workspace_config
+            .display_name
+            .clone()
+            .unwrap_or(workspace_config.name.clone()),
+          is_focused: focused_container
+            .as_ref()
+            .is_some_and(|focused| focused.id() == window.id()),
+          is_minimized: window.state() == WindowState::Minimized,
+          focus_command: format!("focus --container-id {}", window.id()),
+        });
+      }
+    }
+
+    WindowListForMenuData { menu_windows }
+  }
+
   fn to_client_response_msg(
     client_message: String,
     response_data: anyhow::Result<ClientResponseData>,
-  ) -> anyhow::Result<Message> {
+  ) -> ServerMessage {
     let error = response_data.as_ref().err().map(ToString::to_string);
     let success = response_data.as_ref().is_ok();
 
-    let message = ServerMessage::ClientResponse(ClientResponseMessage {
+    ServerMessage::ClientResponse(ClientResponseMessage {
       client_message,
       data: response_data.ok(),
       error,
       success,
-    });
+    })
+  }
 
-    let message_json = serde_json::to_string(&message)?;
-    Ok(Message::Text(message_json.into()))
+  /// Serializes a message to send to a client. Serialization is done on
+  /// the connection's task rather than the main thread.
+  fn encode_message(
+    message: &ServerMessage,
+    encoding: IpcEncoding,
+  ) -> anyhow::Result<Message> {
+    Ok(match encoding {
+      IpcEncoding::Json => {
+        Message::Text(serde_json::to_string(message)?.into())
+      }
+      IpcEncoding::Msgpack => Message::Binary(to_msgpack(message)?.into()),
+    })
   }
 
-  fn to_event_subscription_msg(
+  fn event_subscription_msg(
     subscription_id: Uuid,
+    sequence: u64,
     event: WmEvent,
-  ) -> anyhow::Result<Message> {
-    let message =
-      ServerMessage::EventSubscription(EventSubscriptionMessage {
-        data: Some(event),
-        error: None,
-        subscription_id,
-        success: true,
-      });
-
-    let message_json = serde_json::to_string(&message)?;
-    Ok(Message::Text(message_json.into()))
+  ) -> EventSubscriptionMessage {
+    EventSubscriptionMessage {
+      data: Some(event),
+      error: None,
+      sequence,
+      subscription_id,
+      success: true,
+    }
   }
 
-  pub fn process_event(&mut self, event: WmEvent) -> anyhow::Result<()> {
+  /// Broadcasts the event to subscribers, and adds it to the buffer of
+  /// recent events.
+  pub fn process_event(
+    &mut self,
+    event: WmEvent,
+    event_buffer_size: usize,
+  ) -> anyhow::Result<()> {
     let event_type = match event {
       WmEvent::ApplicationExiting => SubscribableEvent::ApplicationExiting,
+      WmEvent::BindingModeEntered { .. } => {
+        SubscribableEvent::BindingModeEntered
+      }
+      WmEvent::BindingModeExited { .. } => {
+        SubscribableEvent::BindingModeExited
+      }
       WmEvent::BindingModesChanged { .. } => {
         SubscribableEvent::BindingModesChanged
       }
+      WmEvent::CommandInvoked { .. } => SubscribableEvent::CommandInvoked,
+      WmEvent::CustomEvent { .. } => SubscribableEvent::CustomEvent,
+      WmEvent::ForeignWmDetected { .. } => {
+        SubscribableEvent::ForeignWmDetected
+      }
       WmEvent::FocusChanged { .. } => SubscribableEvent::FocusChanged,
       WmEvent::FocusedContainerMoved { .. } => {
         SubscribableEvent::FocusedContainerMoved
       }
+      WmEvent::GameModeChanged { .. } => {
+        SubscribableEvent::GameModeChanged
+      }
+      WmEvent::KeyboardLayoutChanged { .. } => {
+        SubscribableEvent::KeyboardLayoutChanged
+      }
       WmEvent::MonitorAdded { .. } => SubscribableEvent::MonitorAdded,
       WmEvent::MonitorUpdated { .. } => SubscribableEvent::MonitorUpdated,
       WmEvent::MonitorRemoved { .. } => SubscribableEvent::MonitorRemoved,
       WmEvent::TilingDirectionChanged { .. } => {
         SubscribableEvent::TilingDirectionChanged
       }
+      WmEvent::UserActive { .. } => SubscribableEvent::UserActive,
+      WmEvent::UserIdle { .. } => SubscribableEvent::UserIdle,
       WmEvent::UserConfigChanged { .. } => {
         SubscribableEvent::UserConfigChanged
       }
+      WmEvent::WindowAudioChanged { .. } => {
+        SubscribableEvent::WindowAudioChanged
+      }
       WmEvent::WindowManaged { .. } => SubscribableEvent::WindowManaged,
       WmEvent::WindowUnmanaged { .. } => {
         SubscribableEvent::WindowUnmanaged
       }
+      WmEvent::WindowUnresponsive { .. } => {
+        SubscribableEvent::WindowUnresponsive
+      }
+      WmEvent::WindowResponsive { .. } => {
+        SubscribableEvent::WindowResponsive
+      }
       WmEvent::WorkspaceActivated { .. } => {
         SubscribableEvent::WorkspaceActivated
       }
@@ -383,11 +832,28 @@ impl IpcServer {
         SubscribableEvent::WorkspaceUpdated
       }
       WmEvent::PauseChanged { .. } => SubscribableEvent::PauseChanged,
+      WmEvent::MouseEventsChanged { .. } => {
+        SubscribableEvent::MouseEventsChanged
+      }
+      WmEvent::Trace { .. } => SubscribableEvent::Trace,
     };
 
+    let sequence = self.next_sequence;
+    self.next_sequence += 1;
+
+    self.event_buffer.push_back((
+      sequence,
+      event_type.clone(),
+      event.clone(),
+    ));
+
+    while self.event_buffer.len() > event_buffer_size {
+      self.event_buffer.pop_front();
+    }
+
     self
       .event_tx
-      .send((event_type, event))
+      .send((sequence, event_type, event))
       .map_err(|err| anyhow::anyhow!("Failed to send event: {}", err))?;
 
     Ok(())
@@ -0,0 +1,166 @@
+use std::{
+  collections::VecDeque,
+  io,
+  sync::{Mutex, OnceLock},
+};
+
+use anyhow::Context;
+use tracing::{info, Level};
+use tracing_appender::rolling::{self, Rotation};
+use tracing_subscriber::{
+  fmt::{self, writer::MakeWriterExt},
+  layer::SubscriberExt,
+  reload, EnvFilter, Registry,
+};
+use wm_common::Verbosity;
+
+/// Maximum number of recent log lines to keep in memory for
+/// `query logs`.
+const MAX_BUFFERED_LOGS: usize = 1000;
+
+/// Number of daily log files to keep before the oldest is deleted.
+const MAX_LOG_FILES: usize = 7;
+
+/// Handle for changing the log filter at runtime.
+static LOG_FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> =
+  OnceLock::new();
+
+/// Recently emitted log lines, oldest first.
+static LOG_BUFFER: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Initialize logging with the specified verbosity level.
+///
+/// Logs are saved to daily rotated files in `~/.glzr/glazewm/logs/`, and
+/// error logs are additionally saved to `~/.glzr/glazewm/errors.log`.
+pub fn setup_logging(verbosity: &Verbosity) -> anyhow::Result<()> {
+  let log_dir = home::home_dir()
+    .context("Unable to get home directory.")?
+    .join(".glzr/glazewm/");
+
+  let error_writer =
+    tracing_appender::rolling::never(&log_dir, "errors.log");
+
+  let file_writer = rolling::Builder::new()
+    .rotation(Rotation::DAILY)
+    .filename_prefix("glazewm")
+    .filename_suffix("log")
+    .max_log_files(MAX_LOG_FILES)
+    .build(log_dir.join("logs"))
+    .context("Unable to create log file.")?;
+
+  let (filter, filter_handle) =
+    reload::Layer::new(EnvFilter::new(verbosity.level().to_string()));
+
+  let subscriber = tracing_subscriber::registry()
+    .with(filter)
+    .with(
+      // Output to stdout with the current log filter.
+      fmt::Layer::new().with_writer(std::io::stdout),
+    )
+    .with(
+      // Output to rotated log files.
+      fmt::Layer::new().with_ansi(false).with_writer(file_writer),
+    )
+    .with(
+      // Output to in-memory buffer for `query logs`.
+      fmt::Layer::new()
+        .with_ansi(false)
+        .with_writer(|| LogBufferWriter),
+    )
+    .with(
+      // Output to error log file.
+      fmt::Layer::new()
+        .with_ansi(false)
+        .with_writer(error_writer.with_max_level(Level::ERROR)),
+    );
+
+  tracing::subscriber::set_global_default(subscriber)?;
+  let _ = LOG_FILTER_HANDLE.set(filter_handle);
+
+  info!(
+    "Starting WM with log level {:?}.",
+    verbosity.level().to_string()
+  );
+
+  Ok(())
+}
+
+/// Initialize logging for the watchdog process with the specified
+/// verbosity level.
+///
+/// The WM child process writes its own logs, so the watchdog only logs
+/// to stdout and to separate daily rotated files in
+/// `~/.glzr/glazewm/logs/`.
+pub fn setup_watchdog_logging(
+  verbosity: &Verbosity,
+) -> anyhow::Result<()> {
+  let file_writer = rolling::Builder::new()
+    .rotation(Rotation::DAILY)
+    .filename_prefix("watchdog")
+    .filename_suffix("log")
+    .max_log_files(MAX_LOG_FILES)
+    .build(
+      home::home_dir()
+        .context("Unable to get home directory.")?
+        .join(".glzr/glazewm/logs"),
+    )
+    .context("Unable to create log file.")?;
+
+  let subscriber = tracing_subscriber::registry()
+    .with(EnvFilter::new(verbosity.level().to_string()))
+    .with(fmt::Layer::new().with_writer(std::io::stdout))
+    .with(fmt::Layer::new().with_ansi(false).with_writer(file_writer));
+
+  tracing::subscriber::set_global_default(subscriber)?;
+
+  Ok(())
+}
+
+/// Changes the log filter at runtime.
+///
+/// Accepts either a log level (e.g. `debug`) or comma-separated filter
+/// directives for per-module levels (e.g. `info,wm_platform=trace`).
+pub fn set_log_filter(directives: &str) -> anyhow::Result<()> {
+  let filter = EnvFilter::try_new(directives)
+    .with_context(|| format!("Invalid log filter '{directives}'."))?;
+
+  LOG_FILTER_HANDLE
+    .get()
+    .context("Logging is not initialized.")?
+    .reload(filter)?;
+
+  info!("Changed log filter to '{}'.", directives);
+
+  Ok(())
+}
+
+/// Gets the most recent log lines, oldest first.
+///
+/// Returns all buffered log lines if `tail` is `None`.
+pub fn recent_logs(tail: Option<usize>) -> Vec<String> {
+  let buffer = LOG_BUFFER.lock().unwrap();
+  let skip_count = buffer.len().saturating_sub(tail.unwrap_or(usize::MAX));
+
+  buffer.iter().skip(skip_count).cloned().collect()
+}
+
+/// Writer that appends formatted log lines to `LOG_BUFFER`.
+struct LogBufferWriter;
+
+impl io::Write for LogBufferWriter {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let line = String::from_utf8_lossy(buf).trim_end().to_string();
+    let mut buffer = LOG_BUFFER.lock().unwrap();
+
+    if buffer.len() >= MAX_BUFFERED_LOGS {
+      buffer.pop_front();
+    }
+
+    buffer.push_back(line);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
@@ -9,34 +9,68 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![feature(iterator_try_collect)]
 
-use std::{env, path::PathBuf};
+use std::{
+  env,
+  path::{Path, PathBuf},
+  time::Duration,
+};
 
 use anyhow::{Context, Error};
-use tokio::{process::Command, signal};
-use tracing::{debug, error, info, warn, Level};
-use tracing_subscriber::{
-  fmt::{self, writer::MakeWriterExt},
-  layer::SubscriberExt,
+use tokio::{
+  process::Command,
+  signal,
+  time::{self, Instant},
+};
+use tracing::{debug, error, info, warn};
+use wm_common::{
+  is_config_outdated, migrate_config_file, resolve_config_path,
+  AppCommand, BindingModeConfig, CommandSource, Verbosity, WmEvent,
+};
+use wm_ipc_client::IpcClient;
+use wm_platform::{
+  NotificationLevel, Platform, PlatformEvent, SingleInstance,
 };
-use wm_common::{AppCommand, InvokeCommand, Verbosity, WmEvent};
-use wm_platform::Platform;
 
 use crate::{
-  ipc_server::IpcServer, sys_tray::SystemTray, user_config::UserConfig,
+  commands::general::{
+    announce_event, detect_foreign_wms, update_focus_assist,
+  },
+  declined_migration::DeclinedMigration,
+  http_server::HttpServer,
+  ipc_server::IpcServer,
+  logging::{setup_logging, setup_watchdog_logging},
+  sys_tray::{SystemTray, SystemTrayState},
+  user_config::UserConfig,
+  watchdog::run_watchdog,
   wm::WindowManager,
 };
 
+mod base64;
 mod commands;
+mod declined_migration;
 mod events;
+mod floating_geometry;
+mod http_server;
 mod ipc_server;
+mod logging;
 mod models;
+mod msgpack;
+mod offscreen_windows;
 mod pending_sync;
+mod scheduler;
 mod sys_tray;
 mod traits;
 mod user_config;
+mod watchdog;
 mod wm;
 mod wm_state;
 
+/// Max duration to wait for a running instance to exit when replacing it.
+const REPLACE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Interval between checks of whether a replaced instance has exited.
+const REPLACE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Main entry point for the application.
 ///
 /// Conditionally starts the WM or runs a CLI command based on the given
@@ -49,9 +83,16 @@ async fn main() -> anyhow::Result<()> {
   match app_command {
     AppCommand::Start {
       config_path,
+      replace,
+      with_watchdog,
       verbosity,
     } => {
-      let res = start_wm(config_path, verbosity).await;
+      if with_watchdog {
+        setup_watchdog_logging(&verbosity)?;
+        return run_watchdog(&args).await;
+      }
+
+      let res = start_wm(config_path, replace, verbosity).await;
 
       // If unable to start the WM, the error is fatal and a message dialog
       // is shown.
@@ -66,17 +107,27 @@ async fn main() -> anyhow::Result<()> {
   }
 }
 
+#[allow(clippy::too_many_lines)]
 async fn start_wm(
   config_path: Option<PathBuf>,
+  replace: bool,
   verbosity: Verbosity,
 ) -> anyhow::Result<()> {
   setup_logging(&verbosity)?;
 
   // Ensure that only one instance of the WM is running.
-  let _single_instance = Platform::new_single_instance()?;
+  let _single_instance = if replace {
+    replace_running_instance().await?
+  } else {
+    Platform::new_single_instance()?
+  };
+
+  // Offer to migrate the user config if it uses outdated options.
+  let config_path = resolve_config_path(config_path)?;
+  prompt_config_migration(&config_path);
 
   // Parse and validate user config.
-  let mut config = UserConfig::new(config_path)?;
+  let mut config = UserConfig::new(Some(config_path))?;
 
   // Start watcher process for restoring hidden windows on crash.
   start_watcher_process()?;
@@ -88,12 +139,31 @@ async fn start_wm(
 
   let mut ipc_server = IpcServer::start().await?;
 
+  // Start the HTTP server if enabled in the user config.
+  let mut http_server = HttpServer::new();
+  http_server.update(&config.value.general.http_server)?;
+
   // Start listening for platform events after populating initial state.
   let mut event_listener = Platform::start_event_listener(&config.value)?;
 
-  // Run startup commands.
+  // Run startup commands. The subsequent platform sync also applies the
+  // window effects that were deferred during startup.
   let startup_commands = config.value.general.startup_commands.clone();
-  wm.process_commands(&startup_commands, None, &mut config)?;
+  wm.process_commands(
+    &startup_commands,
+    None,
+    CommandSource::Startup,
+    &mut config,
+  )?;
+
+  detect_foreign_wms(&wm.state, &config);
+
+  // Deadline for handling a pending display change. Display changes are
+  // debounced since they often arrive in bursts (e.g. on dock/undock).
+  let mut display_change_deadline: Option<Instant> = None;
+
+  // Keybindings that are disabled for the focused workspace or window.
+  let mut disabled_keybindings = Vec::new();
 
   loop {
     let res = tokio::select! {
@@ -109,12 +179,67 @@ async fn start_wm(
         info!("Received SIGINT signal.");
         break;
       },
-      Some(event) = event_listener.event_rx.recv() => {
+      event = event_listener.next_event() => {
         debug!("Received platform event: {:?}", event);
-        wm.process_event(event, &mut config)
+
+        wm.state
+          .perf_stats
+          .event_queue_depth
+          .record(event_listener.pending_count());
+
+        let debounce_ms =
+          config.value.general.display_change_debounce_ms;
+
+        if matches!(event, PlatformEvent::DisplaySettingsChanged)
+          && debounce_ms > 0
+        {
+          display_change_deadline =
+            Some(Instant::now() + Duration::from_millis(debounce_ms));
+
+          Ok(())
+        } else {
+          wm.process_event(event, &mut config)
+        }
+      },
+      () = time::sleep_until(
+        display_change_deadline.unwrap_or_else(Instant::now)
+      ), if display_change_deadline.is_some() => {
+        display_change_deadline = None;
+        wm.process_event(PlatformEvent::DisplaySettingsChanged, &mut config)
+      },
+      () = time::sleep_until(
+        wm.state
+          .pending_shown_windows_deadline()
+          .map_or_else(Instant::now, Instant::from_std)
+      ), if wm.state.pending_shown_windows_deadline().is_some() => {
+        wm.process_pending_shown_windows(&mut config)
+      },
+      () = time::sleep_until(
+        wm.state
+          .scheduler
+          .next_deadline()
+          .map_or_else(Instant::now, Instant::from_std)
+      ), if wm.state.scheduler.next_deadline().is_some() => {
+        wm.process_due_timers(&mut config);
+        Ok(())
+      },
+      () = time::sleep_until(
+        wm.state
+          .idle_check_deadline
+          .map_or_else(Instant::now, Instant::from_std)
+      ), if wm.state.idle_check_deadline.is_some() => {
+        wm.process_idle_check(&mut config)
+      },
+      () = time::sleep_until(
+        wm.state
+          .media_check_deadline
+          .map_or_else(Instant::now, Instant::from_std)
+      ), if wm.state.media_check_deadline.is_some() => {
+        wm.process_media_check(&mut config)
       },
       Some((
         message,
+        source,
         response_tx,
         disconnection_tx
       )) = ipc_server.message_rx.recv() => {
@@ -122,6 +247,7 @@ async fn start_wm(
 
         if let Err(err) = ipc_server.process_message(
           message,
+          source,
           &response_tx,
           &disconnection_tx,
           &mut wm,
@@ -135,6 +261,28 @@ async fn start_wm(
       Some(wm_event) = wm.event_rx.recv() => {
         debug!("Received WM event: {:?}", wm_event);
 
+        let prev_disabled_keybindings = disabled_keybindings.clone();
+
+        if matches!(
+          wm_event,
+          WmEvent::FocusChanged { .. } | WmEvent::UserConfigChanged { .. }
+        ) {
+          disabled_keybindings = wm
+            .state
+            .focused_container()
+            .map(|container| config.disabled_keybindings(&container))
+            .transpose()
+            .unwrap_or_else(|err| {
+              warn!("Failed to get disabled keybindings: {:?}", err);
+              None
+            })
+            .unwrap_or_default();
+
+          if let Err(err) = update_focus_assist(&mut wm.state) {
+            warn!("Failed to update Focus Assist: {:?}", err);
+          }
+        }
+
         // Update event listener when keyboard or mouse listener needs to
         // be changed.
         if matches!(
@@ -142,26 +290,86 @@ async fn start_wm(
           WmEvent::UserConfigChanged { .. }
             | WmEvent::BindingModesChanged { .. }
             | WmEvent::PauseChanged { .. }
-        ) {
+            | WmEvent::MouseEventsChanged { .. }
+            | WmEvent::GameModeChanged { .. }
+        ) || disabled_keybindings != prev_disabled_keybindings
+        {
+          // Keybindings are paused the same as when the WM is paused if
+          // set to in the game mode config.
+          let is_keybindings_paused = wm.state.is_paused
+            || (wm.state.game_window.is_some()
+              && config.value.general.game_mode.pause_keybindings);
+
           event_listener.update(
             &config.value,
             &wm.state.binding_modes,
-            wm.state.is_paused,
+            is_keybindings_paused,
+            wm.state.is_mouse_events_enabled,
+            &disabled_keybindings,
           );
         }
 
-        if let Err(err) = ipc_server.process_event(wm_event) {
+        if let WmEvent::BindingModesChanged { new_binding_modes } =
+          &wm_event
+        {
+          notify_binding_modes_changed(new_binding_modes, &config);
+        }
+
+        announce_event(&wm_event, &mut wm.state, &config);
+
+        if matches!(
+          wm_event,
+          WmEvent::PauseChanged { .. }
+            | WmEvent::MouseEventsChanged { .. }
+        ) {
+          tray.update(SystemTrayState {
+            is_paused: wm.state.is_paused,
+            is_mouse_events_enabled: wm.state.is_mouse_events_enabled,
+          });
+        }
+
+        // Start or stop the HTTP server if its config has changed.
+        if matches!(wm_event, WmEvent::UserConfigChanged { .. }) {
+          if let Err(err) =
+            http_server.update(&config.value.general.http_server)
+          {
+            warn!("{:?}", err);
+          }
+        }
+
+        http_server.process_event(wm_event.clone());
+
+        if let Err(err) = ipc_server.process_event(
+          wm_event,
+          config.value.general.ipc_event_buffer_size,
+        ) {
           error!("{:?}", err);
         }
 
         Ok(())
       },
-      Some(()) = tray.config_reload_rx.recv() => {
+      Some(response_tx) = http_server.state_request_rx.recv() => {
+        HttpServer::state_snapshot(&wm, event_listener.pending_count())
+          .map(|snapshot| {
+            let _ = response_tx.send(snapshot);
+          })
+      },
+      Some(native_window) = wm.window_responsive_rx.recv() => {
+        wm.process_window_responsive(&native_window, &config)
+      },
+      Some(command) = wm.overlay_command_rx.recv() => {
+        info!("Received overlay command: {}", command);
+        wm.process_overlay_command(&command, &mut config)
+      },
+      Some(command) = tray.command_rx.recv() => {
+        info!("Received tray command: {:?}", command);
         wm.process_commands(
-          &vec![InvokeCommand::WmReloadConfig],
+          &vec![command],
           None,
+          CommandSource::Tray,
           &mut config,
-        ).map(|_| ())
+        )
+        .map(|_| ())
       },
     };
 
@@ -174,37 +382,142 @@ async fn start_wm(
   run_cleanup(&mut wm, &mut config, &mut ipc_server)
 }
 
-/// Initialize logging with the specified verbosity level.
+/// Shows a notification for the active binding mode if enabled in the
+/// user config.
+fn notify_binding_modes_changed(
+  new_binding_modes: &[BindingModeConfig],
+  config: &UserConfig,
+) {
+  if !config.value.general.notifications.binding_mode_changes {
+    return;
+  }
+
+  let message = match new_binding_modes.first() {
+    Some(binding_mode) => format!(
+      "Enabled '{}'.",
+      binding_mode
+        .display_name
+        .as_ref()
+        .unwrap_or(&binding_mode.name)
+    ),
+    None => "Disabled.".to_string(),
+  };
+
+  Platform::show_notification(
+    "Binding mode",
+    &message,
+    NotificationLevel::Info,
+  );
+}
+
+/// Prompts to migrate the user config to the current config schema if
+/// it's outdated. Declining is remembered until the config is changed.
 ///
-/// Error logs are saved to `~/.glzr/glazewm/errors.log`.
-fn setup_logging(verbosity: &Verbosity) -> anyhow::Result<()> {
-  let error_log_dir = home::home_dir()
-    .context("Unable to get home directory.")?
-    .join(".glzr/glazewm/");
-
-  let error_writer =
-    tracing_appender::rolling::never(error_log_dir, "errors.log");
-
-  let subscriber = tracing_subscriber::registry()
-    .with(
-      // Output to stdout with specified verbosity level.
-      fmt::Layer::new()
-        .with_writer(std::io::stdout.with_max_level(verbosity.level())),
-    )
-    .with(
-      // Output to error log file.
-      fmt::Layer::new()
-        .with_writer(error_writer.with_max_level(Level::ERROR)),
-    );
-
-  tracing::subscriber::set_global_default(subscriber)?;
-
-  info!(
-    "Starting WM with log level {:?}.",
-    verbosity.level().to_string()
+/// Failures are logged rather than propagated, since the config might
+/// still be parseable without migrating.
+fn prompt_config_migration(config_path: &Path) {
+  if !config_path.exists()
+    || !is_config_outdated(config_path).unwrap_or(false)
+  {
+    return;
+  }
+
+  // Don't prompt again if the migration was declined, unless the config
+  // has been changed since.
+  let declined_migration = DeclinedMigration::for_config(config_path);
+
+  if declined_migration
+    .as_ref()
+    .is_some_and(DeclinedMigration::is_saved)
+  {
+    info!("Skipping migration prompt, since it was declined before.");
+    return;
+  }
+
+  let should_migrate = Platform::show_confirm_dialog(
+    "Outdated config",
+    &format!(
+      "The config at '{}' uses options from an older version of \
+       GlazeWM.\n\nMigrate it to the current format? A backup of the \
+       original config will be saved.",
+      config_path.display()
+    ),
   );
 
-  Ok(())
+  if !should_migrate {
+    if let Some(Err(err)) =
+      declined_migration.map(|declined| declined.save())
+    {
+      warn!("Failed to save declined migration: {:?}", err);
+    }
+
+    return;
+  }
+
+  match migrate_config_file(config_path) {
+    Ok(migration) => {
+      info!(
+        "Migrated config with {} change(s):",
+        migration.changes.len()
+      );
+
+      for change in &migration.changes {
+        info!("  - {change}");
+      }
+
+      let backup_message = migration
+        .backup_path
+        .map(|path| format!(" Backup saved to '{}'.", path.display()))
+        .unwrap_or_default();
+
+      Platform::show_notification(
+        "Config migrated",
+        &format!(
+          "Applied {} change(s) to the config.{backup_message}",
+          migration.changes.len()
+        ),
+        NotificationLevel::Info,
+      );
+    }
+    Err(err) => warn!("Failed to migrate config: {:?}", err),
+  }
+}
+
+/// Asks an already running instance of the WM to exit, and waits for it
+/// to release its windows before taking over.
+async fn replace_running_instance() -> anyhow::Result<SingleInstance> {
+  if let Ok(single_instance) = Platform::new_single_instance() {
+    return Ok(single_instance);
+  }
+
+  info!("Replacing the running instance of the WM.");
+
+  let mut client = IpcClient::connect()
+    .await
+    .context("Failed to connect to the running instance.")?;
+
+  let message = "command wm-exit";
+  client.send(message).await?;
+
+  // The connection might get closed before the response is received,
+  // which is fine since the instance is exiting regardless.
+  let _ = client.client_response(message).await;
+
+  // The running instance releases the single instance mutex only after
+  // its windows have been restored.
+  let deadline = Instant::now() + REPLACE_TIMEOUT;
+
+  loop {
+    match Platform::new_single_instance() {
+      Ok(single_instance) => return Ok(single_instance),
+      Err(err) if Instant::now() >= deadline => {
+        return Err(err.context(
+          "Timed out waiting for the running instance to exit.",
+        ));
+      }
+      Err(_) => time::sleep(REPLACE_POLL_INTERVAL).await,
+    }
+  }
 }
 
 /// Launches watcher binary. This is a separate process that is responsible
@@ -236,7 +549,27 @@ fn run_cleanup(
 
   // Run shutdown commands.
   let shutdown_commands = config.value.general.shutdown_commands.clone();
-  wm.process_commands(&shutdown_commands, None, config)?;
+  wm.process_commands(
+    &shutdown_commands,
+    None,
+    CommandSource::Shutdown,
+    config,
+  )?;
+
+  // Restore the Focus Assist profile if it was changed by a workspace.
+  if let Some(mode) = wm.state.focus_assist_restore_mode.take() {
+    if let Err(err) = Platform::set_focus_assist_mode(mode) {
+      warn!("Failed to restore Focus Assist: {:?}", err);
+    }
+  }
+
+  // Release the space reserved by the status bars.
+  Platform::close_status_bars();
+
+  // Restore the normal view if the screen is magnified.
+  if wm.state.zoom.is_some() {
+    Platform::update_zoom(None);
+  }
 
   wm.state.emit_event(WmEvent::ApplicationExiting);
 
@@ -244,7 +577,9 @@ fn run_cleanup(
   while let Ok(wm_event) = wm.event_rx.try_recv() {
     info!("Emitting WM event before shutting down: {:?}", wm_event);
 
-    if let Err(err) = ipc_server.process_event(wm_event) {
+    if let Err(err) = ipc_server
+      .process_event(wm_event, config.value.general.ipc_event_buffer_size)
+    {
       warn!("{:?}", err);
     }
   }
@@ -7,8 +7,9 @@ use ambassador::Delegate;
 use enum_as_inner::EnumAsInner;
 use uuid::Uuid;
 use wm_common::{
-  ActiveDrag, ContainerDto, Direction, DisplayState, GapsConfig, Rect,
-  RectDelta, TilingDirection, WindowRuleConfig, WindowState,
+  ActiveDrag, ContainerDto, Direction, DisplayState, FocusedWindowDto,
+  GapsConfig, HideMethod, MediaUsage, Rect, RectDelta, SizeConstraints,
+  TilingDirection, WindowRuleConfig, WindowState,
 };
 use wm_platform::NativeWindow;
 
@@ -139,7 +140,7 @@ impl std::fmt::Display for WindowContainer {
     // Truncate title if longer than 20 chars. Need to use `chars()`
     // instead of byte slices to handle invalid byte indices.
     let title = if title.len() > 20 {
-      format!("{}...", &title.chars().take(17).collect::<String>())
+      format!("{}...", title.chars().take(17).collect::<String>())
     } else {
       title
     };
@@ -152,6 +153,24 @@ impl std::fmt::Display for WindowContainer {
   }
 }
 
+impl WindowContainer {
+  pub fn to_focused_window_dto(&self) -> anyhow::Result<FocusedWindowDto> {
+    let native = self.native();
+
+    Ok(FocusedWindowDto {
+      id: self.id(),
+      handle: native.handle,
+      title: native.title()?,
+      class_name: native.class_name()?,
+      process_name: native.process_name()?,
+      process_path: native.process_path()?,
+      workspace_name: self
+        .workspace()
+        .map(|workspace| workspace.config().name),
+    })
+  }
+}
+
 impl PartialEq for DirectionContainer {
   fn eq(&self, other: &Self) -> bool {
     self.id() == other.id()
@@ -7,8 +7,9 @@ use std::{
 use anyhow::Context;
 use uuid::Uuid;
 use wm_common::{
-  ActiveDrag, ContainerDto, DisplayState, GapsConfig, Rect, RectDelta,
-  WindowDto, WindowRuleConfig, WindowState,
+  ActiveDrag, ContainerDto, DisplayState, GapsConfig, HideMethod,
+  MediaUsage, Rect, RectDelta, SizeConstraints, WindowDto,
+  WindowRuleConfig, WindowState,
 };
 use wm_platform::NativeWindow;
 
@@ -40,6 +41,10 @@ struct NonTilingWindowInner {
   has_custom_floating_placement: bool,
   done_window_rules: Vec<WindowRuleConfig>,
   active_drag: Option<ActiveDrag>,
+  badge: Option<String>,
+  media_usage: MediaUsage,
+  hide_method: Option<HideMethod>,
+  size_constraints: Option<SizeConstraints>,
 }
 
 impl NonTilingWindow {
@@ -55,6 +60,10 @@ impl NonTilingWindow {
     has_custom_floating_placement: bool,
     done_window_rules: Vec<WindowRuleConfig>,
     active_drag: Option<ActiveDrag>,
+    badge: Option<String>,
+    media_usage: MediaUsage,
+    hide_method: Option<HideMethod>,
+    size_constraints: Option<SizeConstraints>,
   ) -> Self {
     let window = NonTilingWindowInner {
       id: id.unwrap_or_else(Uuid::new_v4),
@@ -72,6 +81,10 @@ impl NonTilingWindow {
       has_custom_floating_placement,
       done_window_rules,
       active_drag,
+      badge,
+      media_usage,
+      hide_method,
+      size_constraints,
     };
 
     Self(Rc::new(RefCell::new(window)))
@@ -88,6 +101,14 @@ impl NonTilingWindow {
     self.0.borrow_mut().insertion_target = insertion_target;
   }
 
+  /// Whether the window occupies the dock of its workspace.
+  pub fn is_docked(&self) -> bool {
+    self
+      .workspace()
+      .and_then(|workspace| workspace.dock_window())
+      .is_some_and(|dock_window| dock_window.id() == self.id())
+  }
+
   pub fn to_tiling(&self, gaps_config: GapsConfig) -> TilingWindow {
     TilingWindow::new(
       Some(self.id()),
@@ -99,6 +120,10 @@ impl NonTilingWindow {
       gaps_config,
       self.done_window_rules(),
       self.active_drag(),
+      self.badge(),
+      self.media_usage(),
+      self.hide_method(),
+      self.size_constraints(),
     )
   }
 
@@ -124,6 +149,12 @@ impl NonTilingWindow {
       class_name: self.native().class_name()?,
       process_name: self.native().process_name()?,
       active_drag: self.active_drag(),
+      badge: self.badge(),
+      process_stats: None,
+      is_playing_audio: self.media_usage().is_playing_audio,
+      is_using_microphone: self.media_usage().is_using_microphone,
+      is_using_camera: self.media_usage().is_using_camera,
+      is_recording_screen: self.media_usage().is_recording_screen,
     }))
   }
 }
@@ -138,6 +169,11 @@ impl PositionGetters for NonTilingWindow {
       WindowState::Fullscreen(_) => {
         self.monitor().context("No monitor.")?.to_rect()
       }
+      WindowState::Floating(_) if self.is_docked() => self
+        .workspace()
+        .context("No workspace.")?
+        .dock_rect()?
+        .context("No dock rect."),
       _ => Ok(self.floating_placement()),
     }
   }
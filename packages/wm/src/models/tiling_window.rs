@@ -7,8 +7,9 @@ use std::{
 use anyhow::Context;
 use uuid::Uuid;
 use wm_common::{
-  ActiveDrag, ContainerDto, DisplayState, GapsConfig, Rect, RectDelta,
-  TilingDirection, WindowDto, WindowRuleConfig, WindowState,
+  ActiveDrag, ContainerDto, DisplayState, GapsConfig, HideMethod,
+  MediaUsage, Rect, RectDelta, SizeConstraints, TilingDirection,
+  WindowDto, WindowRuleConfig, WindowState,
 };
 use wm_platform::NativeWindow;
 
@@ -46,6 +47,10 @@ struct TilingWindowInner {
   gaps_config: GapsConfig,
   done_window_rules: Vec<WindowRuleConfig>,
   active_drag: Option<ActiveDrag>,
+  badge: Option<String>,
+  media_usage: MediaUsage,
+  hide_method: Option<HideMethod>,
+  size_constraints: Option<SizeConstraints>,
 }
 
 impl TilingWindow {
@@ -60,6 +65,10 @@ impl TilingWindow {
     gaps_config: GapsConfig,
     done_window_rules: Vec<WindowRuleConfig>,
     active_drag: Option<ActiveDrag>,
+    badge: Option<String>,
+    media_usage: MediaUsage,
+    hide_method: Option<HideMethod>,
+    size_constraints: Option<SizeConstraints>,
   ) -> Self {
     let window = TilingWindowInner {
       id: id.unwrap_or_else(Uuid::new_v4),
@@ -78,6 +87,10 @@ impl TilingWindow {
       gaps_config,
       done_window_rules,
       active_drag,
+      badge,
+      media_usage,
+      hide_method,
+      size_constraints,
     };
 
     Self(Rc::new(RefCell::new(window)))
@@ -99,6 +112,10 @@ impl TilingWindow {
       self.has_custom_floating_placement(),
       self.done_window_rules(),
       self.active_drag(),
+      self.badge(),
+      self.media_usage(),
+      self.hide_method(),
+      self.size_constraints(),
     )
   }
 
@@ -124,6 +141,12 @@ impl TilingWindow {
       class_name: self.native().class_name()?,
       process_name: self.native().process_name()?,
       active_drag: self.active_drag(),
+      badge: self.badge(),
+      process_stats: None,
+      is_playing_audio: self.media_usage().is_playing_audio,
+      is_using_microphone: self.media_usage().is_using_microphone,
+      is_using_camera: self.media_usage().is_using_camera,
+      is_recording_screen: self.media_usage().is_recording_screen,
     }))
   }
 }
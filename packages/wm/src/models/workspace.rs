@@ -7,17 +7,21 @@ use std::{
 use anyhow::Context;
 use uuid::Uuid;
 use wm_common::{
-  ContainerDto, GapsConfig, Rect, TilingDirection, WorkspaceConfig,
-  WorkspaceDto,
+  ContainerDto, DockSide, GapsConfig, LengthUnit, LengthValue, Rect,
+  RectDelta, TilingDirection, WindowState, WorkspaceConfig, WorkspaceDto,
+  WorkspaceLayout,
 };
 
 use crate::{
   impl_common_getters, impl_container_debug,
   impl_tiling_direction_getters,
   models::{
-    Container, DirectionContainer, TilingContainer, WindowContainer,
+    Container, DirectionContainer, NonTilingWindow, TilingContainer,
+    WindowContainer,
+  },
+  traits::{
+    CommonGetters, PositionGetters, TilingDirectionGetters, WindowGetters,
   },
-  traits::{CommonGetters, PositionGetters, TilingDirectionGetters},
 };
 
 #[derive(Clone)]
@@ -32,6 +36,10 @@ struct WorkspaceInner {
   config: WorkspaceConfig,
   gaps_config: GapsConfig,
   tiling_direction: TilingDirection,
+  layout: WorkspaceLayout,
+  scroll_offset: i32,
+  overflow_window_ids: VecDeque<Uuid>,
+  dock_window_id: Option<Uuid>,
 }
 
 impl Workspace {
@@ -45,9 +53,13 @@ impl Workspace {
       parent: None,
       children: VecDeque::new(),
       child_focus_order: VecDeque::new(),
+      layout: config.layout.clone(),
       config,
       gaps_config,
       tiling_direction,
+      scroll_offset: 0,
+      overflow_window_ids: VecDeque::new(),
+      dock_window_id: None,
     };
 
     Self(Rc::new(RefCell::new(workspace)))
@@ -63,6 +75,16 @@ impl Workspace {
     self.0.borrow_mut().config = config;
   }
 
+  /// Whether new windows in the workspace are tiled or floating.
+  /// Defaults to the layout in the workspace config.
+  pub fn layout(&self) -> WorkspaceLayout {
+    self.0.borrow().layout.clone()
+  }
+
+  pub fn set_layout(&self, layout: WorkspaceLayout) {
+    self.0.borrow_mut().layout = layout;
+  }
+
   /// Whether the workspace is currently displayed by the parent monitor.
   pub fn is_displayed(&self) -> bool {
     self
@@ -71,10 +93,220 @@ impl Workspace {
       .is_some_and(|workspace| workspace.id() == self.id())
   }
 
+  /// Whether the workspace has at most one tiling window. Used for
+  /// single window gaps and smart gaps.
+  pub fn has_single_tiling_window(&self) -> bool {
+    self
+      .descendants()
+      .filter(|descendant| {
+        matches!(descendant, Container::TilingWindow(_))
+      })
+      .nth(1)
+      .is_none()
+  }
+
+  /// Gaps config of the workspace. Can differ from the user config if
+  /// overridden for the workspace.
+  pub fn gaps_config(&self) -> GapsConfig {
+    self.0.borrow().gaps_config.clone()
+  }
+
+  /// Gap between the workspace and the edges of its monitor.
+  pub fn outer_gaps(&self) -> RectDelta {
+    self
+      .gaps_config()
+      .effective_outer_gap(self.has_single_tiling_window())
+  }
+
   pub fn set_gaps_config(&self, gaps_config: GapsConfig) {
     self.0.borrow_mut().gaps_config = gaps_config;
   }
 
+  /// Gets the area that tiling windows in the workspace are laid out in.
+  ///
+  /// Same as the workspace rect excluding the dock, unless
+  /// `max_column_width` is set, in which case columns are capped in width
+  /// and centered horizontally. With `scrolling` enabled, columns are
+  /// instead laid out on a strip that can extend past the edges of the
+  /// workspace.
+  pub fn tiling_rect(&self) -> anyhow::Result<Rect> {
+    let rect = self.undocked_rect()?;
+    let config = self.config();
+
+    if config.max_column_width.is_none() && !config.scrolling {
+      return Ok(rect);
+    }
+
+    let column_count = self.tiling_children().count();
+
+    if column_count == 0
+      || self.tiling_direction() != TilingDirection::Horizontal
+    {
+      return Ok(rect);
+    }
+
+    let monitor =
+      self.monitor().context("Workspace has no parent monitor.")?;
+    let monitor_rect = monitor.to_rect()?;
+    let gaps_config = self.gaps_config();
+
+    let scale_factor = if gaps_config.scale_with_dpi {
+      monitor.native().scale_factor()?
+    } else {
+      1.
+    };
+
+    // Columns on scrolling workspaces default to half of the monitor.
+    let column_width = config
+      .max_column_width
+      .unwrap_or(LengthValue {
+        amount: 0.5,
+        unit: LengthUnit::Percentage,
+      })
+      .to_px(monitor_rect.width(), Some(scale_factor));
+
+    let inner_gap = gaps_config
+      .inner_gap
+      .to_px(monitor_rect.height(), Some(scale_factor));
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let column_count = column_count as i32;
+    let used_width =
+      column_width * column_count + inner_gap * (column_count - 1);
+
+    if used_width <= rect.width() || !config.scrolling {
+      return Ok(Rect::from_xy(
+        rect.x() + (rect.width() - used_width).max(0) / 2,
+        rect.y(),
+        used_width.min(rect.width()),
+        rect.height(),
+      ));
+    }
+
+    // Clamp the offset in case columns have been removed since it was
+    // last updated.
+    let scroll_offset =
+      self.scroll_offset().clamp(0, used_width - rect.width());
+
+    Ok(Rect::from_xy(
+      rect.x() - scroll_offset,
+      rect.y(),
+      used_width,
+      rect.height(),
+    ))
+  }
+
+  /// Horizontal offset in pixels of the strip of columns on a scrolling
+  /// workspace.
+  pub fn scroll_offset(&self) -> i32 {
+    self.0.borrow().scroll_offset
+  }
+
+  pub fn set_scroll_offset(&self, scroll_offset: i32) {
+    self.0.borrow_mut().scroll_offset = scroll_offset;
+  }
+
+  /// IDs of windows that have been minimized to stay within
+  /// `max_visible_tiles`. Ordered from first to last to be restored.
+  pub fn overflow_window_ids(&self) -> VecDeque<Uuid> {
+    self.0.borrow().overflow_window_ids.clone()
+  }
+
+  pub fn set_overflow_window_ids(&self, ids: VecDeque<Uuid>) {
+    self.0.borrow_mut().overflow_window_ids = ids;
+  }
+
+  /// Floating window that occupies the dock of the workspace, if the
+  /// workspace has a `dock` config.
+  pub fn dock_window(&self) -> Option<NonTilingWindow> {
+    let dock_window_id = self.0.borrow().dock_window_id?;
+    self.config().dock?;
+
+    self
+      .children()
+      .into_iter()
+      .find(|child| child.id() == dock_window_id)
+      .and_then(|child| child.as_non_tiling_window().cloned())
+      .filter(|window| matches!(window.state(), WindowState::Floating(_)))
+  }
+
+  pub fn set_dock_window_id(&self, id: Option<Uuid>) {
+    self.0.borrow_mut().dock_window_id = id;
+  }
+
+  /// Gets the width of the dock and the gap next to it in pixels. Is
+  /// `None` if no window is docked.
+  fn dock_width(&self) -> anyhow::Result<Option<(i32, i32)>> {
+    let Some(dock_config) = self.config().dock else {
+      return Ok(None);
+    };
+
+    if self.dock_window().is_none() {
+      return Ok(None);
+    }
+
+    let monitor =
+      self.monitor().context("Workspace has no parent monitor.")?;
+    let monitor_rect = monitor.to_rect()?;
+    let gaps_config = self.gaps_config();
+
+    let scale_factor = if gaps_config.scale_with_dpi {
+      monitor.native().scale_factor()?
+    } else {
+      1.
+    };
+
+    let width = dock_config
+      .width
+      .to_px(monitor_rect.width(), Some(scale_factor));
+
+    let inner_gap = gaps_config
+      .inner_gap
+      .to_px(monitor_rect.width(), Some(scale_factor));
+
+    Ok(Some((width, inner_gap)))
+  }
+
+  /// Gets the area of the dock. Is `None` if no window is docked.
+  pub fn dock_rect(&self) -> anyhow::Result<Option<Rect>> {
+    let Some((width, _)) = self.dock_width()? else {
+      return Ok(None);
+    };
+
+    let rect = self.to_rect()?;
+    let width = width.min(rect.width());
+
+    let x = match self.config().dock.map(|dock| dock.side) {
+      Some(DockSide::Right) => rect.x() + rect.width() - width,
+      _ => rect.x(),
+    };
+
+    Ok(Some(Rect::from_xy(x, rect.y(), width, rect.height())))
+  }
+
+  /// Gets the workspace rect excluding the dock and the gap next to it.
+  fn undocked_rect(&self) -> anyhow::Result<Rect> {
+    let rect = self.to_rect()?;
+
+    let Some((width, inner_gap)) = self.dock_width()? else {
+      return Ok(rect);
+    };
+
+    let docked_width = (width + inner_gap).min(rect.width());
+
+    let x = match self.config().dock.map(|dock| dock.side) {
+      Some(DockSide::Left) => rect.x() + docked_width,
+      _ => rect.x(),
+    };
+
+    Ok(Rect::from_xy(
+      x,
+      rect.y(),
+      rect.width() - docked_width,
+      rect.height(),
+    ))
+  }
+
   pub fn to_dto(&self) -> anyhow::Result<ContainerDto> {
     let rect = self.to_rect()?;
     let config = self.config();
@@ -85,6 +317,16 @@ impl Workspace {
       .map(CommonGetters::to_dto)
       .try_collect()?;
 
+    let windows = self
+      .descendants()
+      .filter_map(|descendant| descendant.as_window_container().ok())
+      .collect::<Vec<_>>();
+
+    let tiling_window_count = windows
+      .iter()
+      .filter(|window| window.state() == WindowState::Tiling)
+      .count();
+
     Ok(ContainerDto::Workspace(WorkspaceDto {
       id: self.id(),
       name: config.name,
@@ -94,11 +336,15 @@ impl Workspace {
       child_focus_order: self.0.borrow().child_focus_order.clone().into(),
       has_focus: self.has_focus(None),
       is_displayed: self.is_displayed(),
+      window_count: windows.len(),
+      tiling_window_count,
+      is_empty: windows.is_empty(),
       width: rect.width(),
       height: rect.height(),
       x: rect.x(),
       y: rect.y(),
       tiling_direction: self.tiling_direction(),
+      layout: self.layout(),
     }))
   }
 }
@@ -125,24 +371,44 @@ impl PositionGetters for Workspace {
       .context("Failed to get working area of parent monitor.")?
       .delta(&monitor.to_rect()?);
 
-    let is_single_window = self.tiling_children().nth(1).is_none();
+    let gaps = self.outer_gaps();
+    let monitor_rect = monitor.to_rect()?;
 
-    let gaps = if is_single_window {
-      gaps_config
-        .single_window_outer_gap
-        .as_ref()
-        .unwrap_or(&gaps_config.outer_gap)
-    } else {
-      &gaps_config.outer_gap
-    };
+    let rect = monitor_rect
+      // Scale the gaps if `scale_with_dpi` is enabled.
+      .apply_inverse_delta(&gaps, Some(scale_factor))
+      .apply_delta(&working_delta, None);
 
-    Ok(
-      monitor
-        .to_rect()?
-        // Scale the gaps if `scale_with_dpi` is enabled.
-        .apply_inverse_delta(gaps, Some(scale_factor))
-        .apply_delta(&working_delta, None),
-    )
+    Ok(match &self.0.borrow().config.reserve {
+      None => rect,
+      Some(reserve) => {
+        let reserve = reserve.to_rect_delta();
+
+        // Resolve the reserved area against the full monitor size, so
+        // that percentages aren't affected by gaps.
+        rect.apply_inverse_delta(
+          &RectDelta::new(
+            LengthValue::from_px(
+              reserve.left.to_px(monitor_rect.width(), Some(scale_factor)),
+            ),
+            LengthValue::from_px(
+              reserve.top.to_px(monitor_rect.height(), Some(scale_factor)),
+            ),
+            LengthValue::from_px(
+              reserve
+                .right
+                .to_px(monitor_rect.width(), Some(scale_factor)),
+            ),
+            LengthValue::from_px(
+              reserve
+                .bottom
+                .to_px(monitor_rect.height(), Some(scale_factor)),
+            ),
+          ),
+          None,
+        )
+      }
+    })
   }
 }
 
@@ -11,4 +11,5 @@ pub enum WorkspaceTarget {
   Previous,
   #[allow(dead_code)]
   Direction(Direction),
+  Monitor(usize),
 }
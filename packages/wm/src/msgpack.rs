@@ -0,0 +1,72 @@
+use serde::Serialize;
+
+/// Encodes a value as `MessagePack`.
+///
+/// Structs are encoded as maps with named fields, and types with a
+/// separate human-readable form (e.g. UUIDs) use that form, so that the
+/// output has the same shape as the JSON encoding of the value.
+pub fn to_msgpack<T: Serialize>(value: &T) -> anyhow::Result<Vec<u8>> {
+  let mut buffer = Vec::new();
+
+  value.serialize(
+    &mut rmp_serde::Serializer::new(&mut buffer)
+      .with_struct_map()
+      .with_human_readable(),
+  )?;
+
+  Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::Value;
+  use uuid::Uuid;
+  use wm_common::{
+    ClientResponseData, ClientResponseMessage, CommandData, CommandSource,
+    EventSubscriptionMessage, ServerMessage, WmEvent,
+  };
+
+  use super::to_msgpack;
+
+  /// Decodes the `MessagePack` encoding of the message and compares it
+  /// against its JSON encoding.
+  fn assert_round_trip(message: &ServerMessage) {
+    let encoded = to_msgpack(message).unwrap();
+    let decoded = rmp_serde::from_slice::<Value>(&encoded).unwrap();
+
+    assert_eq!(decoded, serde_json::to_value(message).unwrap());
+  }
+
+  #[test]
+  fn client_response_matches_json() {
+    assert_round_trip(&ServerMessage::ClientResponse(
+      ClientResponseMessage {
+        client_message: "command focus --next-workspace".to_string(),
+        data: Some(ClientResponseData::Command(CommandData {
+          subject_container_id: Uuid::new_v4(),
+        })),
+        error: None,
+        success: true,
+      },
+    ));
+  }
+
+  #[test]
+  fn event_subscription_matches_json() {
+    assert_round_trip(&ServerMessage::EventSubscription(
+      EventSubscriptionMessage {
+        data: Some(WmEvent::CommandInvoked {
+          source: CommandSource::Ipc,
+          command: "wm-cycle-focus".to_string(),
+          subject_container_id: Uuid::new_v4(),
+          success: false,
+          error: Some("No window to focus.".to_string()),
+        }),
+        error: None,
+        sequence: u64::MAX,
+        subscription_id: Uuid::new_v4(),
+        success: true,
+      },
+    ));
+  }
+}
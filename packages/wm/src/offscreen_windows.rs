@@ -0,0 +1,133 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use anyhow::Context;
+use tracing::{info, warn};
+use wm_common::{HideMethod, Rect, WindowState};
+use wm_platform::{NativeWindow, WindowPosBatch, ZOrder};
+
+/// Windows that are currently moved off-screen by the `offscreen` hide
+/// method, along with their on-screen position, keyed by window handle.
+///
+/// Persisted to `~/.glzr/glazewm/offscreen_windows.json`, so that the
+/// windows can be moved back on-screen on the next startup if the WM
+/// exits without restoring them (e.g. after a crash).
+#[derive(Debug, Default)]
+pub struct OffscreenWindows {
+  path: Option<PathBuf>,
+  rects: HashMap<isize, Rect>,
+
+  /// Whether the windows have changed since they were last saved.
+  has_changes: bool,
+}
+
+impl OffscreenWindows {
+  /// Reads the persisted windows from disk. Starts out empty if the file
+  /// doesn't exist or is invalid.
+  pub fn load() -> Self {
+    let path = home::home_dir()
+      .map(|home| home.join(".glzr/glazewm/offscreen_windows.json"));
+
+    let rects = path
+      .as_ref()
+      .filter(|path| path.exists())
+      .and_then(|path| match Self::read(path) {
+        Ok(rects) => Some(rects),
+        Err(err) => {
+          warn!("Failed to read off-screen windows: {err}");
+          None
+        }
+      })
+      .unwrap_or_default();
+
+    Self {
+      path,
+      rects,
+      has_changes: false,
+    }
+  }
+
+  /// Moves windows that were left off-screen by a previous run back to
+  /// their on-screen position.
+  pub fn restore_all(&mut self) {
+    if self.rects.is_empty() {
+      self.save_changes();
+      return;
+    }
+
+    let mut batch = WindowPosBatch::new();
+
+    for (handle, rect) in self.rects.drain() {
+      let native_window = NativeWindow::new(handle);
+
+      // Skip windows that have since been closed or moved back by other
+      // means. Window handles can be reused, so the current position is
+      // checked as well.
+      if !native_window.is_valid()
+        || !native_window.is_offscreen().unwrap_or(false)
+      {
+        continue;
+      }
+
+      info!("Restoring off-screen window: {handle}");
+
+      _ = native_window.set_position(
+        &mut batch,
+        &WindowState::Tiling,
+        &rect,
+        &ZOrder::Normal,
+        true,
+        &HideMethod::Offscreen,
+        false,
+      );
+    }
+
+    batch.apply();
+
+    self.has_changes = true;
+    self.save_changes();
+  }
+
+  /// Records that the window has been moved off-screen from the given
+  /// on-screen position. Persisted on the next call to `save_changes`.
+  pub fn insert(&mut self, native_window: &NativeWindow, rect: Rect) {
+    if self.rects.get(&native_window.handle) == Some(&rect) {
+      return;
+    }
+
+    self.rects.insert(native_window.handle, rect);
+    self.has_changes = true;
+  }
+
+  /// Records that the window is no longer off-screen. Persisted on the
+  /// next call to `save_changes`.
+  pub fn remove(&mut self, native_window: &NativeWindow) {
+    if self.rects.remove(&native_window.handle).is_some() {
+      self.has_changes = true;
+    }
+  }
+
+  /// Writes the windows to disk if they've changed since they were last
+  /// saved.
+  pub fn save_changes(&mut self) {
+    if !self.has_changes {
+      return;
+    }
+
+    self.has_changes = false;
+
+    if let Err(err) = self.save() {
+      warn!("Failed to save off-screen windows: {err}");
+    }
+  }
+
+  fn read(path: &PathBuf) -> anyhow::Result<HashMap<isize, Rect>> {
+    let json = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&json)?)
+  }
+
+  fn save(&self) -> anyhow::Result<()> {
+    let path = self.path.as_ref().context("No home directory.")?;
+    fs::write(path, serde_json::to_string_pretty(&self.rects)?)?;
+    Ok(())
+  }
+}
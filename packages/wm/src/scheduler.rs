@@ -0,0 +1,163 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use wm_common::{InvokeCommand, TimerConfig, TimerStatus};
+use wm_platform::Platform;
+
+/// Number of seconds in a day.
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Times of day that are closer than this are treated as having already
+/// passed. Prevents a timer from running twice if it fires slightly
+/// before its scheduled time.
+const TIME_OF_DAY_TOLERANCE: Duration = Duration::from_secs(1);
+
+/// Timer from the user config and when it's next due to run.
+struct ScheduledTimer {
+  config: TimerConfig,
+
+  /// Whether the timer is enabled. Initially set from the user config,
+  /// but can be changed via commands.
+  is_enabled: bool,
+
+  /// Time at which the timer is next due. `None` if the timer is
+  /// disabled.
+  next_run: Option<Instant>,
+}
+
+/// Keeps track of when the timers in the `timers` config section are
+/// due to run.
+#[derive(Default)]
+pub struct Scheduler {
+  timers: Vec<ScheduledTimer>,
+}
+
+impl Scheduler {
+  /// Replaces the scheduled timers with the timers from the user config.
+  pub fn update(&mut self, timer_configs: &[TimerConfig]) {
+    self.timers = timer_configs
+      .iter()
+      .map(|config| ScheduledTimer {
+        config: config.clone(),
+        is_enabled: config.enabled,
+        next_run: config.enabled.then(|| next_run(config)).flatten(),
+      })
+      .collect();
+  }
+
+  /// Earliest time at which a timer is due to run.
+  pub fn next_deadline(&self) -> Option<Instant> {
+    self.timers.iter().filter_map(|timer| timer.next_run).min()
+  }
+
+  /// Gets the timers that are due to run, and schedules their next runs.
+  pub fn take_due_timers(&mut self) -> Vec<TimerConfig> {
+    let now = Instant::now();
+
+    self
+      .timers
+      .iter_mut()
+      .filter(|timer| {
+        timer.next_run.is_some_and(|next_run| next_run <= now)
+      })
+      .map(|timer| {
+        timer.next_run = next_run(&timer.config);
+        timer.config.clone()
+      })
+      .collect()
+  }
+
+  /// Enables or disables the timer with the given name. Toggles the
+  /// timer if `is_enabled` is `None`.
+  pub fn set_enabled(
+    &mut self,
+    name: &str,
+    is_enabled: Option<bool>,
+  ) -> anyhow::Result<()> {
+    let timer = self
+      .timers
+      .iter_mut()
+      .find(|timer| timer.config.name == name)
+      .with_context(|| {
+        format!("No timer found with the name '{name}'.")
+      })?;
+
+    let is_enabled = is_enabled.unwrap_or(!timer.is_enabled);
+
+    // Keep the existing schedule if the timer is already enabled.
+    if is_enabled != timer.is_enabled {
+      timer.is_enabled = is_enabled;
+      timer.next_run =
+        is_enabled.then(|| next_run(&timer.config)).flatten();
+    }
+
+    Ok(())
+  }
+
+  /// Gets the commands of the timer with the given name.
+  pub fn timer_commands(
+    &self,
+    name: &str,
+  ) -> anyhow::Result<Vec<InvokeCommand>> {
+    self
+      .timers
+      .iter()
+      .find(|timer| timer.config.name == name)
+      .map(|timer| timer.config.commands.clone())
+      .with_context(|| format!("No timer found with the name '{name}'."))
+  }
+
+  /// Gets the status of all timers for IPC.
+  pub fn timer_statuses(&self) -> Vec<TimerStatus> {
+    let now = Instant::now();
+
+    self
+      .timers
+      .iter()
+      .map(|timer| TimerStatus {
+        name: timer.config.name.clone(),
+        is_enabled: timer.is_enabled,
+        next_run_in_ms: timer.next_run.map(|next_run| {
+          u64::try_from(
+            next_run.saturating_duration_since(now).as_millis(),
+          )
+          .unwrap_or(u64::MAX)
+        }),
+      })
+      .collect()
+  }
+}
+
+/// Gets the time at which the timer is next due, based on its interval
+/// and times of day.
+///
+/// Returns `None` if the timer has neither an interval nor times of day.
+fn next_run(config: &TimerConfig) -> Option<Instant> {
+  let now = Instant::now();
+
+  let interval_run = config
+    .interval_secs
+    .filter(|secs| *secs > 0)
+    .map(|secs| now + Duration::from_secs(secs));
+
+  let time_of_day = Platform::local_time_of_day();
+
+  let time_of_day_run = config
+    .at
+    .iter()
+    .map(|at| {
+      let at = Duration::from_secs(u64::from(at.secs_since_midnight()));
+
+      // Times that have already passed today are run tomorrow.
+      if at > time_of_day + TIME_OF_DAY_TOLERANCE {
+        now + at.saturating_sub(time_of_day)
+      } else {
+        now
+          + (at + Duration::from_secs(SECS_PER_DAY))
+            .saturating_sub(time_of_day)
+      }
+    })
+    .min();
+
+  [interval_run, time_of_day_run].into_iter().flatten().min()
+}
@@ -7,32 +7,59 @@ use tray_icon::{
   menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem},
   Icon, TrayIconBuilder,
 };
+use wm_common::InvokeCommand;
 use wm_platform::Platform;
 
 /// Ordinal to `IDI_ICON` definition in embedded resource file.
 const IDI_ICON: u16 = 32512;
 
+/// Runtime state of the WM that is reflected in the tray menu.
+#[derive(Clone, Copy, Debug)]
+pub struct SystemTrayState {
+  pub is_paused: bool,
+  pub is_mouse_events_enabled: bool,
+}
+
 pub struct SystemTray {
-  pub config_reload_rx: mpsc::UnboundedReceiver<()>,
+  /// Receiver for WM commands triggered via the tray menu.
+  pub command_rx: mpsc::UnboundedReceiver<InvokeCommand>,
   pub exit_rx: mpsc::UnboundedReceiver<()>,
+  state_tx: mpsc::UnboundedSender<SystemTrayState>,
   icon_thread: Option<JoinHandle<anyhow::Result<()>>>,
 }
 
 impl SystemTray {
   pub fn new(config_path: &Path) -> anyhow::Result<Self> {
     let (exit_tx, exit_rx) = mpsc::unbounded_channel();
-    let (config_reload_tx, config_reload_rx) = mpsc::unbounded_channel();
+    let (command_tx, command_rx) = mpsc::unbounded_channel();
+    let (state_tx, mut state_rx) =
+      mpsc::unbounded_channel::<SystemTrayState>();
+
+    let config_path = config_path.to_owned();
     let config_dir = config_path
       .parent()
       .context("Invalid config path.")?
       .to_owned();
 
+    let error_log_path = home::home_dir()
+      .context("Unable to get home directory.")?
+      .join(".glzr/glazewm/errors.log");
+
     let icon_thread = std::thread::spawn(move || {
+      let pause_item = CheckMenuItem::new("Pause", true, false, None);
+
+      let mouse_events_item =
+        CheckMenuItem::new("Mouse events", true, true, None);
+
       let reload_config_item = MenuItem::new("Reload config", true, None);
 
+      let config_file_item = MenuItem::new("Open config file", true, None);
+
       let config_dir_item =
         MenuItem::new("Show config folder", true, None);
 
+      let error_log_item = MenuItem::new("Show error logs", true, None);
+
       let mut animations_enabled =
         Platform::window_animations_enabled().unwrap_or(true);
 
@@ -47,8 +74,14 @@ impl SystemTray {
 
       let tray_menu = Menu::new();
       tray_menu.append_items(&[
+        &pause_item,
+        &mouse_events_item,
+        &PredefinedMenuItem::separator(),
         &reload_config_item,
+        &config_file_item,
         &config_dir_item,
+        &error_log_item,
+        &PredefinedMenuItem::separator(),
         &animations_item,
         &PredefinedMenuItem::separator(),
         &exit_item,
@@ -65,11 +98,20 @@ impl SystemTray {
 
       loop {
         if let Ok(event) = menu_event_rx.try_recv() {
-          if event.id == reload_config_item.id() {
-            config_reload_tx.send(())?;
+          if event.id == pause_item.id() {
+            command_tx.send(InvokeCommand::WmTogglePause)?;
+          } else if event.id == mouse_events_item.id() {
+            command_tx.send(InvokeCommand::WmToggleMouseEvents)?;
+          } else if event.id == reload_config_item.id() {
+            command_tx.send(InvokeCommand::WmReloadConfig)?;
+          } else if event.id == config_file_item.id() {
+            // Open config file with its default application.
+            let _ = Platform::open_file_explorer(&config_path);
           } else if event.id == config_dir_item.id() {
             // Open config directory in File Explorer.
             let _ = Platform::open_file_explorer(&config_dir);
+          } else if event.id == error_log_item.id() {
+            Self::open_error_log(&error_log_path);
           } else if event.id == animations_item.id() {
             // Toggle window animations globally.
             let _ =
@@ -81,6 +123,13 @@ impl SystemTray {
           }
         }
 
+        // Keep checkboxes in sync with changes made outside of the tray
+        // menu (e.g. pausing via a keybinding).
+        while let Ok(state) = state_rx.try_recv() {
+          pause_item.set_checked(state.is_paused);
+          mouse_events_item.set_checked(state.is_mouse_events_enabled);
+        }
+
         // Run message loop with a delay of 16ms (60fps).
         if Platform::run_message_cycle().is_err() {
           break;
@@ -93,12 +142,34 @@ impl SystemTray {
     });
 
     Ok(Self {
-      config_reload_rx,
+      command_rx,
       exit_rx,
+      state_tx,
       icon_thread: Some(icon_thread),
     })
   }
 
+  /// Updates the tray menu to reflect the current WM state.
+  pub fn update(&self, state: SystemTrayState) {
+    if let Err(err) = self.state_tx.send(state) {
+      warn!("Failed to update system tray: {}", err);
+    }
+  }
+
+  /// Opens the error log file, or its parent directory if no errors have
+  /// been logged yet.
+  fn open_error_log(error_log_path: &Path) {
+    let path = if error_log_path.exists() {
+      Some(error_log_path)
+    } else {
+      error_log_path.parent()
+    };
+
+    if let Some(path) = path {
+      let _ = Platform::open_file_explorer(&path.to_path_buf());
+    }
+  }
+
   /// Destroys the system tray icon and stops its associated message loop.
   pub fn destroy(&mut self) -> anyhow::Result<()> {
     info!("Shutting down system tray.");
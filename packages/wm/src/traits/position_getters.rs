@@ -1,11 +1,181 @@
+use std::{cell::RefCell, collections::HashMap};
+
 use ambassador::delegatable_trait;
-use wm_common::Rect;
+use uuid::Uuid;
+use wm_common::{Rect, SizeConstraints};
+
+use super::{
+  CommonGetters, TilingDirectionGetters, TilingSizeGetters, WindowGetters,
+};
+use crate::models::{DirectionContainer, TilingContainer};
 
 #[delegatable_trait]
 pub trait PositionGetters {
   fn to_rect(&self) -> anyhow::Result<Rect>;
 }
 
+thread_local! {
+  /// Rects of tiling containers that have been computed during the
+  /// current layout pass. Is `None` if no layout pass is in progress.
+  static LAYOUT_CACHE: RefCell<Option<HashMap<Uuid, Rect>>> =
+    const { RefCell::new(None) };
+}
+
+/// Memoizes the rects of tiling containers for as long as it's held.
+///
+/// The rect of a tiling container is derived from the rects of its parent
+/// and previous siblings, so without caching, the same rects are computed
+/// many times over when redrawing a large number of windows. The
+/// container tree must not be modified while the cache is held.
+pub struct LayoutCache;
+
+impl LayoutCache {
+  pub fn enable() -> Self {
+    LAYOUT_CACHE.with_borrow_mut(|cache| *cache = Some(HashMap::new()));
+    Self
+  }
+
+  /// Gets the cached rect of a container, or computes it if it's not
+  /// cached. Caching is skipped if no `LayoutCache` is held.
+  pub fn get_or_compute(
+    id: Uuid,
+    compute: impl FnOnce() -> anyhow::Result<Rect>,
+  ) -> anyhow::Result<Rect> {
+    let cached_rect = LAYOUT_CACHE.with_borrow(|cache| {
+      cache.as_ref().and_then(|cache| cache.get(&id).cloned())
+    });
+
+    if let Some(rect) = cached_rect {
+      return Ok(rect);
+    }
+
+    let rect = compute()?;
+
+    LAYOUT_CACHE.with_borrow_mut(|cache| {
+      if let Some(cache) = cache {
+        cache.insert(id, rect.clone());
+      }
+    });
+
+    Ok(rect)
+  }
+}
+
+impl Drop for LayoutCache {
+  fn drop(&mut self) {
+    LAYOUT_CACHE.with_borrow_mut(|cache| *cache = None);
+  }
+}
+
+/// Gets the size constraints of a tiling container. Only tiling windows
+/// can be constrained.
+fn size_constraints(
+  container: &TilingContainer,
+) -> Option<SizeConstraints> {
+  match container {
+    TilingContainer::TilingWindow(window) => window.size_constraints(),
+    TilingContainer::Split(_) => None,
+  }
+}
+
+/// Gets the length of a tiling container along its parent's tiling
+/// direction, if any of the parent's children have size constraints.
+///
+/// Constrained windows are clamped to their bounds, and the difference
+/// is absorbed by their unclamped siblings in proportion to their tiling
+/// sizes.
+#[allow(clippy::cast_precision_loss)]
+pub fn constrained_tiling_length(
+  container_id: Uuid,
+  parent: &DirectionContainer,
+  available_length: i32,
+  cross_length: i32,
+) -> Option<f32> {
+  let children = parent.tiling_children().collect::<Vec<_>>();
+
+  let bounds = children
+    .iter()
+    .map(|child| {
+      size_constraints(child).map(|constraints| {
+        let (min, max) = constraints.length_bounds(
+          &parent.tiling_direction(),
+          available_length,
+          cross_length,
+        );
+
+        (min as f32, max as f32)
+      })
+    })
+    .collect::<Vec<_>>();
+
+  if bounds.iter().all(Option::is_none) {
+    return None;
+  }
+
+  let mut lengths = children
+    .iter()
+    .map(|child| child.tiling_size() * available_length as f32)
+    .collect::<Vec<_>>();
+
+  let mut is_clamped = vec![false; children.len()];
+
+  // Absorbing the slack can push a sibling past its own bounds, so
+  // clamping is repeated until all lengths are within bounds.
+  for _ in 0..children.len() {
+    let mut slack = 0.;
+
+    for (index, bounds) in bounds.iter().enumerate() {
+      if let Some((min, max)) = bounds {
+        let length = lengths[index];
+        let clamped_length = length.clamp(*min, *max);
+
+        if !is_clamped[index] && (clamped_length - length).abs() > 0.5 {
+          slack += length - clamped_length;
+          lengths[index] = clamped_length;
+          is_clamped[index] = true;
+        }
+      }
+    }
+
+    let unclamped_size = children
+      .iter()
+      .zip(&is_clamped)
+      .filter(|(_, is_clamped)| !**is_clamped)
+      .map(|(child, _)| child.tiling_size())
+      .sum::<f32>();
+
+    if slack == 0. || unclamped_size == 0. {
+      break;
+    }
+
+    for (index, child) in children.iter().enumerate() {
+      if !is_clamped[index] {
+        lengths[index] += slack * child.tiling_size() / unclamped_size;
+      }
+    }
+  }
+
+  children
+    .iter()
+    .position(|child| child.id() == container_id)
+    .map(|index| lengths[index])
+}
+
+/// Gets the length of a tiling container in the axis opposite to its
+/// parent's tiling direction, along with its offset for it to be
+/// centered within the parent.
+pub fn constrained_cross_length(
+  container: &TilingContainer,
+  parent: &DirectionContainer,
+  cross_length: i32,
+) -> (i32, i32) {
+  let length = size_constraints(container).map_or(cross_length, |c| {
+    c.cross_length(&parent.tiling_direction(), cross_length)
+  });
+
+  (length, (cross_length - length) / 2)
+}
+
 /// Implements the `PositionGetters` trait for tiling containers that can
 /// be resized. This is used by `SplitContainer` and `TilingWindow`.
 ///
@@ -16,70 +186,124 @@ macro_rules! impl_position_getters_as_resizable {
   ($struct_name:ident) => {
     impl PositionGetters for $struct_name {
       fn to_rect(&self) -> anyhow::Result<Rect> {
-        let parent = self
-          .parent()
-          .and_then(|parent| parent.as_direction_container().ok())
-          .context("Parent does not have a tiling direction.")?;
-
-        let parent_rect = parent.to_rect()?;
-
-        let (horizontal_gap, vertical_gap) = self.inner_gaps()?;
-        let inner_gap = match parent.tiling_direction() {
-          TilingDirection::Vertical => vertical_gap,
-          TilingDirection::Horizontal => horizontal_gap,
-        };
-
-        #[allow(
-          clippy::cast_precision_loss,
-          clippy::cast_possible_truncation,
-          clippy::cast_possible_wrap
-        )]
-        let (width, height) = match parent.tiling_direction() {
-          TilingDirection::Vertical => {
-            let available_height = parent_rect.height()
-              - inner_gap * self.tiling_siblings().count() as i32;
-
-            let height =
-              (self.tiling_size() * available_height as f32) as i32;
-
-            (parent_rect.width(), height)
-          }
-          TilingDirection::Horizontal => {
-            let available_width = parent_rect.width()
-              - inner_gap * self.tiling_siblings().count() as i32;
-
-            let width =
-              (available_width as f32 * self.tiling_size()).round() as i32;
-
-            (width, parent_rect.height())
-          }
-        };
-
-        let (x, y) = {
-          let mut prev_siblings = self
-            .prev_siblings()
-            .filter_map(|sibling| sibling.as_tiling_container().ok());
-
-          match prev_siblings.next() {
-            None => (parent_rect.x(), parent_rect.y()),
-            Some(sibling) => {
-              let sibling_rect = sibling.to_rect()?;
-
-              match parent.tiling_direction() {
-                TilingDirection::Vertical => (
-                  parent_rect.x(),
-                  sibling_rect.y() + sibling_rect.height() + inner_gap,
-                ),
-                TilingDirection::Horizontal => (
-                  sibling_rect.x() + sibling_rect.width() + inner_gap,
-                  parent_rect.y(),
-                ),
+        $crate::traits::LayoutCache::get_or_compute(self.id(), || {
+          let parent = self
+            .parent()
+            .and_then(|parent| parent.as_direction_container().ok())
+            .context("Parent does not have a tiling direction.")?;
+
+          // Workspaces can lay out their tiling children in a narrower
+          // area than their own rect.
+          let parent_rect = match &parent {
+            $crate::models::DirectionContainer::Workspace(workspace) => {
+              workspace.tiling_rect()?
+            }
+            _ => parent.to_rect()?,
+          };
+
+          let (horizontal_gap, vertical_gap) = self.inner_gaps()?;
+          let inner_gap = match parent.tiling_direction() {
+            TilingDirection::Vertical => vertical_gap,
+            TilingDirection::Horizontal => horizontal_gap,
+          };
+
+          #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_possible_wrap
+          )]
+          let (width, height) = match parent.tiling_direction() {
+            TilingDirection::Vertical => {
+              let available_height = parent_rect.height()
+                - inner_gap * self.tiling_siblings().count() as i32;
+
+              let height = $crate::traits::constrained_tiling_length(
+                self.id(),
+                &parent,
+                available_height,
+                parent_rect.width(),
+              )
+              .unwrap_or(self.tiling_size() * available_height as f32)
+                as i32;
+
+              (parent_rect.width(), height)
+            }
+            TilingDirection::Horizontal => {
+              let available_width = parent_rect.width()
+                - inner_gap * self.tiling_siblings().count() as i32;
+
+              let width = $crate::traits::constrained_tiling_length(
+                self.id(),
+                &parent,
+                available_width,
+                parent_rect.height(),
+              )
+              .unwrap_or(available_width as f32 * self.tiling_size())
+              .round() as i32;
+
+              (width, parent_rect.height())
+            }
+          };
+
+          // Windows with a max width or height narrower than their parent
+          // are centered in the other axis.
+          let (width, height, cross_offset) = {
+            let (cross_length, cross_offset) =
+              $crate::traits::constrained_cross_length(
+                &self.clone().into(),
+                &parent,
+                match parent.tiling_direction() {
+                  TilingDirection::Vertical => width,
+                  TilingDirection::Horizontal => height,
+                },
+              );
+
+            match parent.tiling_direction() {
+              TilingDirection::Vertical => {
+                (cross_length, height, cross_offset)
+              }
+              TilingDirection::Horizontal => {
+                (width, cross_length, cross_offset)
+              }
+            }
+          };
+
+          let (x, y) = {
+            let mut prev_siblings = self
+              .prev_siblings()
+              .filter_map(|sibling| sibling.as_tiling_container().ok());
+
+            let main_position = match prev_siblings.next() {
+              None => match parent.tiling_direction() {
+                TilingDirection::Vertical => parent_rect.y(),
+                TilingDirection::Horizontal => parent_rect.x(),
+              },
+              Some(sibling) => {
+                let sibling_rect = sibling.to_rect()?;
+
+                match parent.tiling_direction() {
+                  TilingDirection::Vertical => {
+                    sibling_rect.y() + sibling_rect.height() + inner_gap
+                  }
+                  TilingDirection::Horizontal => {
+                    sibling_rect.x() + sibling_rect.width() + inner_gap
+                  }
+                }
+              }
+            };
+
+            match parent.tiling_direction() {
+              TilingDirection::Vertical => {
+                (parent_rect.x() + cross_offset, main_position)
+              }
+              TilingDirection::Horizontal => {
+                (main_position, parent_rect.y() + cross_offset)
               }
             }
-          }
-        };
+          };
 
-        Ok(Rect::from_xy(x, y, width, height))
+          Ok(Rect::from_xy(x, y, width, height))
+        })
       }
     }
   };
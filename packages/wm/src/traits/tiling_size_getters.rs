@@ -20,10 +20,16 @@ pub trait TilingSizeGetters: CommonGetters {
   fn set_gaps_config(&self, gaps_config: GapsConfig);
 
   /// Gets the horizontal and vertical gaps between windows in pixels.
+  ///
+  /// Uses the gaps config of the parent workspace, since it can be
+  /// overridden per workspace.
   fn inner_gaps(&self) -> anyhow::Result<(i32, i32)> {
     let monitor = self.monitor().context("No monitor.")?;
     let monitor_rect = monitor.to_rect()?;
-    let gaps_config = self.gaps_config();
+    let gaps_config = self.workspace().map_or_else(
+      || self.gaps_config().clone(),
+      |workspace| workspace.gaps_config(),
+    );
 
     let scale_factor = if gaps_config.scale_with_dpi {
       monitor.native().scale_factor()?
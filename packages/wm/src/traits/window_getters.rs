@@ -2,8 +2,8 @@ use std::cell::Ref;
 
 use ambassador::delegatable_trait;
 use wm_common::{
-  ActiveDrag, DisplayState, LengthValue, Rect, RectDelta,
-  WindowRuleConfig, WindowState,
+  ActiveDrag, DisplayState, HideMethod, LengthValue, MediaUsage, Rect,
+  RectDelta, SizeConstraints, WindowRuleConfig, WindowState,
 };
 use wm_platform::NativeWindow;
 
@@ -120,6 +120,32 @@ pub trait WindowGetters {
   fn active_drag(&self) -> Option<ActiveDrag>;
 
   fn set_active_drag(&self, active_drag: Option<ActiveDrag>);
+
+  /// Short custom name shown in place of the window title by status
+  /// bars (e.g. `FF` for Firefox). Doesn't affect the actual window.
+  fn badge(&self) -> Option<String>;
+
+  fn set_badge(&self, badge: Option<String>);
+
+  /// Media that the window's process is playing or capturing, as of
+  /// the last check.
+  fn media_usage(&self) -> MediaUsage;
+
+  fn set_media_usage(&self, media_usage: MediaUsage);
+
+  /// Hide method to use for the window instead of the one in the user
+  /// config.
+  fn hide_method(&self) -> Option<HideMethod>;
+
+  fn set_hide_method(&self, hide_method: Option<HideMethod>);
+
+  /// Constraints on the size of the window when it's tiling.
+  fn size_constraints(&self) -> Option<SizeConstraints>;
+
+  fn set_size_constraints(
+    &self,
+    size_constraints: Option<SizeConstraints>,
+  );
 }
 
 /// Implements the `WindowGetters` trait for a given struct.
@@ -217,6 +243,41 @@ macro_rules! impl_window_getters {
       fn set_active_drag(&self, active_drag: Option<ActiveDrag>) {
         self.0.borrow_mut().active_drag = active_drag;
       }
+
+      fn badge(&self) -> Option<String> {
+        self.0.borrow().badge.clone()
+      }
+
+      fn set_badge(&self, badge: Option<String>) {
+        self.0.borrow_mut().badge = badge;
+      }
+
+      fn media_usage(&self) -> MediaUsage {
+        self.0.borrow().media_usage
+      }
+
+      fn set_media_usage(&self, media_usage: MediaUsage) {
+        self.0.borrow_mut().media_usage = media_usage;
+      }
+
+      fn hide_method(&self) -> Option<HideMethod> {
+        self.0.borrow().hide_method.clone()
+      }
+
+      fn set_hide_method(&self, hide_method: Option<HideMethod>) {
+        self.0.borrow_mut().hide_method = hide_method;
+      }
+
+      fn size_constraints(&self) -> Option<SizeConstraints> {
+        self.0.borrow().size_constraints.clone()
+      }
+
+      fn set_size_constraints(
+        &self,
+        size_constraints: Option<SizeConstraints>,
+      ) {
+        self.0.borrow_mut().size_constraints = size_constraints;
+      }
     }
   };
 }
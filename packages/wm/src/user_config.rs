@@ -1,20 +1,23 @@
-use std::{collections::HashMap, env, fs, path::PathBuf};
+use std::{collections::HashMap, fs, path::PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::Context;
+use tracing::warn;
 use wm_common::{
-  InvokeCommand, MatchType, ParsedConfig, WindowMatchConfig,
-  WindowRuleConfig, WindowRuleEvent, WorkspaceConfig,
+  create_config_file, resolve_config_path, validate_config, ConfigFormat,
+  GapsConfig, InvokeCommand, KeybindingConfig, MatchType, MediaUsage,
+  ParsedConfig, WindowEffectsConfig, WindowMatchConfig, WindowMatchResult,
+  WindowRuleConfig, WindowRuleEvent, WindowRuleMatchData,
+  WindowSwitcherScope, WorkspaceConfig,
+};
+use wm_platform::{
+  KeyboardHook, NativeWindow, NotificationLevel, Platform,
 };
 
 use crate::{
-  models::{Monitor, WindowContainer, Workspace},
+  models::{Container, Monitor, WindowContainer, Workspace},
   traits::{CommonGetters, WindowGetters},
 };
 
-/// Resource string for the sample config file.
-const SAMPLE_CONFIG: &str =
-  include_str!("../../../resources/assets/sample-config.yaml");
-
 #[derive(Debug)]
 pub struct UserConfig {
   /// Path to the user config file.
@@ -35,15 +38,9 @@ impl UserConfig {
   /// Creates an instance of `UserConfig`. Reads and validates the user
   /// config from the given path.
   ///
-  /// Creates a new config file from sample if it doesn't exist.
+  /// Creates a new config file if it doesn't exist.
   pub fn new(config_path: Option<PathBuf>) -> anyhow::Result<Self> {
-    let default_config_path = home::home_dir()
-      .context("Unable to get home directory.")?
-      .join(".glzr/glazewm/config.yaml");
-
-    let config_path = config_path
-      .or_else(|| env::var("GLAZEWM_CONFIG_PATH").ok().map(PathBuf::from))
-      .unwrap_or(default_config_path);
+    let config_path = resolve_config_path(config_path)?;
 
     let (config_value, config_str) = Self::read(&config_path)?;
 
@@ -59,38 +56,71 @@ impl UserConfig {
 
   /// Reads and validates the user config from the given path.
   ///
-  /// Creates a new config file from sample if it doesn't exist.
+  /// Creates a new config file if it doesn't exist.
   fn read(
     config_path: &PathBuf,
   ) -> anyhow::Result<(ParsedConfig, String)> {
     if !config_path.exists() {
-      Self::create_sample(config_path)?;
+      create_config_file(config_path)?;
     }
 
     let config_str = fs::read_to_string(config_path)
       .context("Unable to read config file.")?;
 
-    // TODO: Improve error formatting of serde_yaml errors. Something
-    // similar to https://github.com/AlexanderThaller/format_serde_error
-    let config_value = serde_yaml::from_str(&config_str)?;
+    let mut config_value =
+      ConfigFormat::from_path(config_path).parse(&config_str)?;
+
+    Self::add_default_keybindings(&mut config_value);
+    Self::notify_config_problems(&config_value);
 
     Ok((config_value, config_str))
   }
 
-  /// Initializes a new config file from the sample config resource.
-  fn create_sample(config_path: &PathBuf) -> Result<()> {
-    let parent_dir =
-      config_path.parent().context("Invalid config path.")?;
+  /// Adds keybindings that are enabled via the general config, unless the
+  /// same keys are already bound by the user.
+  fn add_default_keybindings(config_value: &mut ParsedConfig) {
+    if !config_value.general.replace_alt_tab {
+      return;
+    }
 
-    fs::create_dir_all(parent_dir).with_context(|| {
-      format!("Unable to create directory {}.", &config_path.display())
-    })?;
+    let is_alt_tab_bound = config_value
+      .keybindings
+      .iter()
+      .flat_map(|keybinding| &keybinding.bindings)
+      .any(|binding| binding.eq_ignore_ascii_case("alt+tab"));
+
+    // Shift+tab is handled by the switcher itself once it's shown.
+    if !is_alt_tab_bound {
+      config_value.keybindings.push(KeybindingConfig {
+        bindings: vec!["alt+tab".to_string()],
+        commands: vec![InvokeCommand::ShowWindowSwitcher {
+          scope: WindowSwitcherScope::Workspace,
+        }],
+        ..KeybindingConfig::default()
+      });
+    }
+  }
 
-    fs::write(config_path, SAMPLE_CONFIG).with_context(|| {
-      format!("Unable to write to {}.", config_path.display())
-    })?;
+  /// Logs problems in the config that don't prevent it from loading, and
+  /// shows them as a notification if enabled.
+  fn notify_config_problems(config_value: &ParsedConfig) {
+    let problems = validate_config(config_value, |key| {
+      KeyboardHook::key_to_vk_code(key).is_some()
+    });
 
-    Ok(())
+    for problem in &problems {
+      warn!("Config problem: {}", problem);
+    }
+
+    if !problems.is_empty()
+      && config_value.general.notifications.config_errors
+    {
+      Platform::show_notification(
+        &format!("Config has {} problem(s)", problems.len()),
+        &problems.join("\n"),
+        NotificationLevel::Warning,
+      );
+    }
   }
 
   pub fn reload(&mut self) -> anyhow::Result<()> {
@@ -140,6 +170,8 @@ impl UserConfig {
       ],
       on: vec![WindowRuleEvent::Manage],
       run_once: true,
+      disable_keybindings: Vec::new(),
+      home_workspace: None,
     });
 
     // Default ignore rules.
@@ -188,6 +220,8 @@ impl UserConfig {
       ],
       on: vec![WindowRuleEvent::Manage],
       run_once: true,
+      disable_keybindings: Vec::new(),
+      home_workspace: None,
     });
 
     window_rules
@@ -239,25 +273,13 @@ impl UserConfig {
           return false;
         }
 
-        // Check if the window matches the rule.
-        rule.match_window.iter().any(|match_config| {
-          let is_process_match = match_config
-            .window_process
-            .as_ref()
-            .is_none_or(|match_type| match_type.is_match(&window_process));
-
-          let is_class_match = match_config
-            .window_class
-            .as_ref()
-            .is_none_or(|match_type| match_type.is_match(&window_class));
-
-          let is_title_match = match_config
-            .window_title
-            .as_ref()
-            .is_none_or(|match_type| match_type.is_match(&window_title));
-
-          is_process_match && is_class_match && is_title_match
-        })
+        Self::is_rule_match(
+          rule,
+          &window_process,
+          &window_class,
+          &window_title,
+          window.media_usage(),
+        )
       })
       .cloned()
       .collect::<Vec<_>>();
@@ -265,6 +287,211 @@ impl UserConfig {
     Ok(pending_window_rules)
   }
 
+  /// Home workspace of the window from the first window rule that
+  /// matches it and has a `home_workspace`.
+  pub fn home_workspace(
+    &self,
+    window: &WindowContainer,
+  ) -> anyhow::Result<Option<String>> {
+    let window_title = window.native().title()?;
+    let window_class = window.native().class_name()?;
+    let window_process = window.native().process_name()?;
+
+    let home_workspace = self
+      .value
+      .window_rules
+      .iter()
+      .filter(|rule| {
+        Self::is_rule_match(
+          rule,
+          &window_process,
+          &window_class,
+          &window_title,
+          window.media_usage(),
+        )
+      })
+      .find_map(|rule| rule.home_workspace.clone());
+
+    Ok(home_workspace)
+  }
+
+  /// Whether any window rule that matches the window (including the
+  /// default ones) has a command that satisfies the predicate.
+  ///
+  /// The window is treated as not playing or capturing any media, since
+  /// this is only tracked for managed windows.
+  pub fn has_matching_rule_command(
+    &self,
+    native_window: &NativeWindow,
+    predicate: impl Fn(&InvokeCommand) -> bool,
+  ) -> anyhow::Result<bool> {
+    let window_title = native_window.title()?;
+    let window_class = native_window.class_name()?;
+    let window_process = native_window.process_name()?;
+
+    Ok(self.window_rules_by_event.values().flatten().any(|rule| {
+      rule.commands.iter().any(&predicate)
+        && Self::is_rule_match(
+          rule,
+          &window_process,
+          &window_class,
+          &window_title,
+          MediaUsage::default(),
+        )
+    }))
+  }
+
+  /// Checks if a window with the given process, class, title, and media
+  /// usage matches the window rule.
+  fn is_rule_match(
+    rule: &WindowRuleConfig,
+    window_process: &str,
+    window_class: &str,
+    window_title: &str,
+    media_usage: MediaUsage,
+  ) -> bool {
+    rule.match_window.iter().any(|match_config| {
+      match_config.is_match(window_process, window_class, window_title)
+        && match_config.is_media_usage_match(media_usage)
+    })
+  }
+
+  /// Evaluates every window rule (including the default ones) against
+  /// the given window, for diagnosing why a rule does or doesn't apply.
+  ///
+  /// Rules that have already run are determined from `done_rules`.
+  pub fn window_rule_matches(
+    &self,
+    native_window: &NativeWindow,
+    media_usage: MediaUsage,
+    done_rules: &[WindowRuleConfig],
+  ) -> anyhow::Result<Vec<WindowRuleMatchData>> {
+    let window_title = native_window.title()?;
+    let window_class = native_window.class_name()?;
+    let window_process = native_window.process_name()?;
+
+    let default_window_rules = Self::default_window_rules(&self.value);
+
+    let all_window_rules = self
+      .value
+      .window_rules
+      .iter()
+      .map(|rule| (rule, false))
+      .chain(default_window_rules.iter().map(|rule| (rule, true)));
+
+    let window_rule_matches = all_window_rules
+      .map(|(rule, is_default)| {
+        let match_results = rule
+          .match_window
+          .iter()
+          .map(|match_config| WindowMatchResult {
+            window_process: match_config
+              .window_process
+              .as_ref()
+              .map(|match_type| match_type.is_match(&window_process)),
+            window_class: match_config
+              .window_class
+              .as_ref()
+              .map(|match_type| match_type.is_match(&window_class)),
+            window_title: match_config
+              .window_title
+              .as_ref()
+              .map(|match_type| match_type.is_match(&window_title)),
+            is_playing_audio: match_config
+              .is_playing_audio
+              .map(|value| value == media_usage.is_playing_audio),
+            is_using_microphone: match_config
+              .is_using_microphone
+              .map(|value| value == media_usage.is_using_microphone),
+            is_using_camera: match_config
+              .is_using_camera
+              .map(|value| value == media_usage.is_using_camera),
+            is_recording_screen: match_config
+              .is_recording_screen
+              .map(|value| value == media_usage.is_recording_screen),
+          })
+          .collect();
+
+        WindowRuleMatchData {
+          commands: rule
+            .commands
+            .iter()
+            .map(InvokeCommand::to_command_string)
+            .collect(),
+          on: rule.on.clone(),
+          is_default,
+          is_match: Self::is_rule_match(
+            rule,
+            &window_process,
+            &window_class,
+            &window_title,
+            media_usage,
+          ),
+          is_done: rule.run_once && done_rules.contains(rule),
+          match_results,
+        }
+      })
+      .collect();
+
+    Ok(window_rule_matches)
+  }
+
+  /// Gets the keybindings to disable while the given container is
+  /// focused, based on its workspace and any matching window rules.
+  pub fn disabled_keybindings(
+    &self,
+    container: &Container,
+  ) -> anyhow::Result<Vec<String>> {
+    let mut disabled_keybindings = container
+      .workspace()
+      .map(|workspace| workspace.config().disable_keybindings)
+      .unwrap_or_default();
+
+    if let Ok(window) = container.as_window_container() {
+      let window_title = window.native().title()?;
+      let window_class = window.native().class_name()?;
+      let window_process = window.native().process_name()?;
+
+      for rule in &self.value.window_rules {
+        if !rule.disable_keybindings.is_empty()
+          && Self::is_rule_match(
+            rule,
+            &window_process,
+            &window_class,
+            &window_title,
+            window.media_usage(),
+          )
+        {
+          disabled_keybindings.extend(rule.disable_keybindings.clone());
+        }
+      }
+    }
+
+    Ok(disabled_keybindings)
+  }
+
+  /// Whether windows on the given workspace should be managed. Users can
+  /// restrict the WM to specific monitors and workspaces, so that it can
+  /// run alongside another window manager.
+  pub fn is_managed_workspace(&self, workspace: &Workspace) -> bool {
+    let coexistence = &self.value.general.coexistence;
+
+    let is_managed_monitor = coexistence.managed_monitors.is_empty()
+      || workspace.monitor().is_some_and(|monitor| {
+        coexistence
+          .managed_monitors
+          .iter()
+          .any(|&index| monitor.index() == index as usize)
+      });
+
+    let is_managed_name = coexistence.managed_workspaces.is_empty()
+      || coexistence
+        .managed_workspaces
+        .contains(&workspace.config().name);
+
+    is_managed_monitor && is_managed_name
+  }
+
   pub fn inactive_workspace_configs(
     &self,
     active_workspaces: &[Workspace],
@@ -326,27 +553,46 @@ impl UserConfig {
       .position(|config| config.name == workspace_name)
   }
 
+  /// Sorts workspaces by the order of their configs. Workspaces without
+  /// a config (i.e. app workspaces) are sorted last.
   pub fn sort_workspaces(&self, workspaces: &mut [Workspace]) {
     workspaces.sort_by_key(|workspace| {
-      self.workspace_config_index(&workspace.config().name)
+      self
+        .workspace_config_index(&workspace.config().name)
+        .unwrap_or(usize::MAX)
     });
   }
 
-  pub fn outer_gaps_for_workspace(
+  /// Whether the workspace was created for an application via
+  /// `general.workspace_per_app`, rather than from a workspace config.
+  pub fn is_app_workspace(&self, workspace: &Workspace) -> bool {
+    self.value.general.workspace_per_app
+      && self
+        .workspace_config_index(&workspace.config().name)
+        .is_none()
+  }
+
+  /// Gets the gaps config for a workspace, taking into account any
+  /// override in its workspace config.
+  pub fn gaps_for_workspace(
     &self,
-    workspace: &Workspace,
-  ) -> &wm_common::RectDelta {
-    let is_single_window = workspace.tiling_children().nth(1).is_none();
+    workspace_config: &WorkspaceConfig,
+  ) -> GapsConfig {
+    workspace_config
+      .gaps
+      .clone()
+      .unwrap_or_else(|| self.value.gaps.clone())
+  }
 
-    if is_single_window {
-      self
-        .value
-        .gaps
-        .single_window_outer_gap
-        .as_ref()
-        .unwrap_or(&self.value.gaps.outer_gap)
-    } else {
-      &self.value.gaps.outer_gap
-    }
+  /// Gets the window effects config for a workspace, taking into account
+  /// any override in its workspace config.
+  pub fn window_effects_for_workspace(
+    &self,
+    workspace_config: &WorkspaceConfig,
+  ) -> WindowEffectsConfig {
+    workspace_config
+      .window_effects
+      .clone()
+      .unwrap_or_else(|| self.value.window_effects.clone())
   }
 }
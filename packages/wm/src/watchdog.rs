@@ -0,0 +1,108 @@
+use std::{
+  collections::VecDeque,
+  env,
+  time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use tokio::{process::Command, time};
+use tracing::{error, info, warn};
+use wm_platform::Platform;
+
+/// CLI flag that starts the WM under the watchdog.
+pub const WATCHDOG_FLAG: &str = "--with-watchdog";
+
+/// Delay before restarting the WM after its first crash. The delay is
+/// doubled on each subsequent crash.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Max delay before restarting the WM.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Duration the WM needs to run for before the restart delay is reset to
+/// `INITIAL_BACKOFF`.
+const STABLE_RUN_DURATION: Duration = Duration::from_mins(1);
+
+/// Number of crashes within `CRASH_LOOP_WINDOW` after which the watchdog
+/// stops restarting the WM.
+const MAX_CRASHES: usize = 5;
+
+const CRASH_LOOP_WINDOW: Duration = Duration::from_mins(2);
+
+/// Exit code of the WM when it returns an error, which happens when it
+/// fails to start (e.g. due to an invalid config). The WM shows an error
+/// dialog in that case, and restarting it would fail the same way.
+const FATAL_ERROR_EXIT_CODE: i32 = 1;
+
+/// Runs the WM as a child process, and restarts it if it crashes.
+///
+/// Hidden windows are restored by the watcher process when the WM
+/// crashes, so the restarted WM re-manages them on startup. Other state
+/// (e.g. which workspace a window was on) is not recovered. Restarts are
+/// delayed with an exponential backoff, and the watchdog gives up if the
+/// WM keeps crashing.
+pub async fn run_watchdog(args: &[String]) -> anyhow::Result<()> {
+  let exe_path = env::current_exe()?;
+
+  // Forward all arguments except for the watchdog flag to the WM.
+  let wm_args = args
+    .iter()
+    .skip(1)
+    .filter(|arg| *arg != WATCHDOG_FLAG)
+    .collect::<Vec<_>>();
+
+  let mut backoff = INITIAL_BACKOFF;
+  let mut crash_timestamps = VecDeque::new();
+
+  loop {
+    let start_time = Instant::now();
+
+    let status = Command::new(&exe_path)
+      .args(&wm_args)
+      .status()
+      .await
+      .context("Failed to start WM process.")?;
+
+    if status.success() {
+      info!("WM exited successfully. Stopping watchdog.");
+      return Ok(());
+    }
+
+    if status.code() == Some(FATAL_ERROR_EXIT_CODE) {
+      info!("WM exited with a fatal error. Stopping watchdog.");
+      return Ok(());
+    }
+
+    warn!("WM exited unexpectedly with {status}.");
+
+    // Reset the backoff if the WM ran for a while before crashing.
+    if start_time.elapsed() >= STABLE_RUN_DURATION {
+      backoff = INITIAL_BACKOFF;
+    }
+
+    crash_timestamps.push_back(Instant::now());
+    crash_timestamps.retain(|timestamp: &Instant| {
+      timestamp.elapsed() < CRASH_LOOP_WINDOW
+    });
+
+    if crash_timestamps.len() >= MAX_CRASHES {
+      error!("WM is crashing repeatedly. Stopping watchdog.");
+
+      Platform::show_error_dialog(
+        "Fatal error",
+        &format!(
+          "GlazeWM crashed {MAX_CRASHES} times within {} seconds and \
+           won't be restarted. Check the logs in \
+           `%userprofile%/.glzr/glazewm/` for details.",
+          CRASH_LOOP_WINDOW.as_secs()
+        ),
+      );
+
+      return Ok(());
+    }
+
+    info!("Restarting WM in {}ms.", backoff.as_millis());
+    time::sleep(backoff).await;
+    backoff = (backoff * 2).min(MAX_BACKOFF);
+  }
+}
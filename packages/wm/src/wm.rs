@@ -1,48 +1,83 @@
+use std::{iter, time::Instant};
+
 use anyhow::{bail, Context};
+use clap::Parser;
 use tokio::sync::mpsc::{self};
-use tracing::warn;
+use tracing::{info, warn};
 use uuid::Uuid;
 use wm_common::{
-  FloatingStateConfig, FullscreenStateConfig, InvokeCommand, LengthValue,
-  RectDelta, TitleBarVisibility, WindowState, WmEvent,
+  AppCommand, CommandSource, FloatingStateConfig, FocusAssistMode,
+  FullscreenStateConfig, InvokeCommand, InvokeIfCommand,
+  InvokeMoveCommand, LengthValue, RectDelta, TitleBarVisibility,
+  TraceEvent, WindowState, WmEvent,
 };
-use wm_platform::PlatformEvent;
+use wm_platform::{NativeWindow, Platform, PlatformEvent};
 
 use crate::{
   commands::{
     container::{
-      focus_container_by_id, focus_in_direction, set_tiling_direction,
-      toggle_tiling_direction,
+      cycle_ratio, focus_child, focus_container_by_id, focus_in_direction,
+      focus_parent, move_split_in_direction, move_split_to_workspace,
+      resize_split_container, set_split_container_size,
+      set_tiling_direction, toggle_tiling_direction,
     },
     general::{
       cycle_focus, disable_binding_mode, enable_binding_mode,
-      platform_sync, reload_config, shell_exec, toggle_pause,
+      platform_sync, reload_config, run_timer, send_keys,
+      set_focus_assist, set_timer_enabled, shell_exec,
+      show_command_palette, show_keybindings, show_menu, show_overview,
+      show_window_hints, show_window_switcher, sync_focus_frame,
+      sync_resize_guides, sync_status_bars, sync_zoom,
+      toggle_binding_mode, toggle_focus_assist, toggle_mouse_events,
+      toggle_pause, toggle_trace, toggle_zoom, update_accessibility,
+      update_game_mode, update_idle_state, update_media_usage,
     },
-    monitor::focus_monitor,
+    monitor::{focus_monitor, toggle_monitor_management},
     window::{
-      ignore_window, move_window_in_direction, move_window_to_workspace,
-      resize_window, set_window_position, set_window_size,
-      update_window_state, WindowPositionTarget,
+      adopt_window, close_windows, close_workspace_windows,
+      gather_windows, ignore_window, move_window_in_direction,
+      move_window_to_monitor, move_window_to_workspace, peek_window,
+      release_window, resize_window, select_add, select_clear,
+      send_window_home, set_window_position, set_window_size,
+      unpeek_window, update_window_state, WindowPositionTarget,
+    },
+    workspace::{
+      adjust_workspace_gaps, apply_workspace_template,
+      focus_overflow_next, focus_workspace, focus_workspace_at_index,
+      move_workspace_in_direction, scroll_workspace, set_workspace_gaps,
+      toggle_workspace_layout,
     },
-    workspace::{focus_workspace, move_workspace_in_direction},
   },
   events::{
-    handle_display_settings_changed, handle_mouse_move,
-    handle_window_destroyed, handle_window_focused, handle_window_hidden,
-    handle_window_location_changed, handle_window_minimize_ended,
-    handle_window_minimized, handle_window_moved_or_resized_end,
+    handle_display_settings_changed, handle_keyboard_layout_changed,
+    handle_mouse_move, handle_window_destroyed, handle_window_focused,
+    handle_window_hidden, handle_window_location_changed,
+    handle_window_minimize_ended, handle_window_minimized,
+    handle_window_moved_or_resized_end,
     handle_window_moved_or_resized_start, handle_window_shown,
     handle_window_title_changed,
   },
-  models::{Container, WorkspaceTarget},
+  models::{Container, SplitContainer, WindowContainer, WorkspaceTarget},
   traits::{CommonGetters, WindowGetters},
   user_config::UserConfig,
   wm_state::WmState,
 };
 
+/// Commands that can't be run via `glazewm://` URLs, even if they're
+/// listed in `general.url_commands`.
+const URL_DENIED_COMMANDS: [&str; 5] = [
+  "shell-exec",
+  "send-keys",
+  "close",
+  "close-workspace-windows",
+  "wm-exit",
+];
+
 pub struct WindowManager {
   pub event_rx: mpsc::UnboundedReceiver<WmEvent>,
   pub exit_rx: mpsc::UnboundedReceiver<()>,
+  pub window_responsive_rx: mpsc::UnboundedReceiver<NativeWindow>,
+  pub overlay_command_rx: mpsc::UnboundedReceiver<String>,
   pub state: WmState,
 }
 
@@ -50,30 +85,258 @@ impl WindowManager {
   pub fn new(config: &mut UserConfig) -> anyhow::Result<Self> {
     let (event_tx, event_rx) = mpsc::unbounded_channel();
     let (exit_tx, exit_rx) = mpsc::unbounded_channel();
-
-    let mut state = WmState::new(event_tx, exit_tx);
+    let (window_responsive_tx, window_responsive_rx) =
+      mpsc::unbounded_channel();
+
+    let (overlay_command_tx, overlay_command_rx) =
+      mpsc::unbounded_channel();
+
+    let mut state = WmState::new(
+      event_tx,
+      exit_tx,
+      window_responsive_tx,
+      overlay_command_tx,
+    );
     state.populate(config)?;
 
     Ok(Self {
       event_rx,
       exit_rx,
+      window_responsive_rx,
+      overlay_command_rx,
       state,
     })
   }
 
+  /// Redraws a window that has become responsive again after being
+  /// marked as unresponsive.
+  pub fn process_window_responsive(
+    &mut self,
+    native_window: &NativeWindow,
+    config: &UserConfig,
+  ) -> anyhow::Result<()> {
+    let state = &mut self.state;
+
+    state
+      .unresponsive_windows
+      .retain(|window| window != native_window);
+
+    if let Some(window) = state.window_from_native(native_window) {
+      info!("Window is responding again: {window}");
+
+      state.emit_event(WmEvent::WindowResponsive {
+        responsive_window: window.to_dto()?,
+      });
+
+      state.pending_sync.queue_container_to_redraw(window);
+
+      if !state.is_paused {
+        platform_sync(state, config)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Manages windows that were shown during a workspace switch, once the
+  /// switch has completed or timed out.
+  pub fn process_pending_shown_windows(
+    &mut self,
+    config: &mut UserConfig,
+  ) -> anyhow::Result<()> {
+    let state = &mut self.state;
+
+    if state.is_display_transition_pending() {
+      return Ok(());
+    }
+
+    Self::manage_pending_shown_windows(state, config)?;
+
+    if !state.is_paused && state.pending_sync.has_changes() {
+      platform_sync(state, config)?;
+    }
+
+    Ok(())
+  }
+
+  /// Updates whether the user is idle, and runs the idle or active
+  /// commands if it has changed.
+  pub fn process_idle_check(
+    &mut self,
+    config: &mut UserConfig,
+  ) -> anyhow::Result<()> {
+    let state = &mut self.state;
+
+    update_idle_state(state, config)?;
+
+    if !state.is_paused && state.pending_sync.has_changes() {
+      platform_sync(state, config)?;
+    }
+
+    Ok(())
+  }
+
+  /// Updates which windows are playing audio or using the microphone,
+  /// camera, or screen capture.
+  pub fn process_media_check(
+    &mut self,
+    config: &mut UserConfig,
+  ) -> anyhow::Result<()> {
+    let state = &mut self.state;
+
+    update_media_usage(state, config)?;
+
+    if !state.is_paused && state.pending_sync.has_changes() {
+      platform_sync(state, config)?;
+    }
+
+    Ok(())
+  }
+
+  /// Runs the commands of timers that are due.
+  pub fn process_due_timers(&mut self, config: &mut UserConfig) {
+    for timer in self.state.scheduler.take_due_timers() {
+      info!("Running timer: {}", timer.name);
+
+      if let Err(err) = self.process_commands(
+        &timer.commands,
+        None,
+        CommandSource::Timer,
+        config,
+      ) {
+        warn!("Failed to run timer '{}': {:?}", timer.name, err);
+      }
+    }
+  }
+
+  fn manage_pending_shown_windows(
+    state: &mut WmState,
+    config: &mut UserConfig,
+  ) -> anyhow::Result<()> {
+    let pending_windows = std::mem::take(&mut state.pending_shown_windows);
+
+    for native_window in pending_windows {
+      // Skip windows that have since been closed or hidden.
+      if !native_window.is_valid()
+        || !native_window.is_visible().unwrap_or(false)
+      {
+        continue;
+      }
+
+      handle_window_shown(native_window, state, config)?;
+    }
+
+    Ok(())
+  }
+
   pub fn process_event(
     &mut self,
     event: PlatformEvent,
     config: &mut UserConfig,
+  ) -> anyhow::Result<()> {
+    let start_time = Instant::now();
+    let event_type = event.event_type();
+
+    let res = self.handle_platform_event(event, config);
+
+    self
+      .state
+      .perf_stats
+      .record_platform_event(event_type, start_time.elapsed());
+
+    res
+  }
+
+  #[allow(clippy::too_many_lines)]
+  fn handle_platform_event(
+    &mut self,
+    event: PlatformEvent,
+    config: &mut UserConfig,
   ) -> anyhow::Result<()> {
     let state = &mut self.state;
 
+    // Windows might have been moved or resized outside of the WM, so
+    // ensure they're repositioned on the next redraw.
+    match &event {
+      PlatformEvent::DisplaySettingsChanged => {
+        state.mark_container_dirty(&state.root_container.clone().into());
+      }
+      PlatformEvent::WindowHidden(window)
+      | PlatformEvent::WindowLocationChanged(window)
+      | PlatformEvent::WindowMinimized(window)
+      | PlatformEvent::WindowMinimizeEnded(window)
+      | PlatformEvent::WindowMovedOrResizedEnd(window)
+      | PlatformEvent::WindowShown(window) => {
+        state.invalidate_window_placement(window);
+      }
+      _ => {}
+    }
+
+    // Mouse move events are omitted since they would flood the trace.
+    if !matches!(event, PlatformEvent::MouseMove(_)) {
+      state.emit_trace(|| TraceEvent::PlatformEventReceived {
+        event: format!("{event:?}"),
+      });
+    }
+
+    // Game mode is updated whenever the foreground window might have
+    // changed, or when it might've entered or exited fullscreen.
+    let should_update_game_mode = match &event {
+      PlatformEvent::WindowFocused(_)
+      | PlatformEvent::WindowDestroyed(_)
+      | PlatformEvent::WindowMinimized(_) => true,
+      PlatformEvent::WindowLocationChanged(window) => {
+        *window == Platform::foreground_window()
+      }
+      _ => false,
+    };
+
+    // Mouse moves can't change what the status bars and focus frame
+    // show.
+    let should_sync_status_bars =
+      !matches!(event, PlatformEvent::MouseMove(_));
+
     match event {
+      PlatformEvent::AccessibilitySettingsChanged => {
+        update_accessibility(state, config);
+        Ok(())
+      }
       PlatformEvent::DisplaySettingsChanged => {
         handle_display_settings_changed(state, config)
       }
+      PlatformEvent::KeyboardLayoutChanged(keyboard_layout) => {
+        handle_keyboard_layout_changed(keyboard_layout, state);
+        Ok(())
+      }
       PlatformEvent::KeybindingTriggered(kb_config) => {
-        self.process_commands(&kb_config.commands, None, config)?;
+        self.process_commands(
+          &kb_config.commands,
+          None,
+          CommandSource::Keybinding,
+          config,
+        )?;
+
+        // Return early since we don't want to redraw twice.
+        return Ok(());
+      }
+      PlatformEvent::KeybindingReleased(kb_config) => {
+        self.process_commands(
+          &kb_config.release_commands,
+          None,
+          CommandSource::Keybinding,
+          config,
+        )?;
+
+        // Return early since we don't want to redraw twice.
+        return Ok(());
+      }
+      PlatformEvent::ControllerTriggered(commands) => {
+        self.process_commands(
+          &commands,
+          None,
+          CommandSource::Controller,
+          config,
+        )?;
 
         // Return early since we don't want to redraw twice.
         return Ok(());
@@ -114,10 +377,27 @@ impl WindowManager {
       }
     }?;
 
+    if should_update_game_mode {
+      update_game_mode(state, config);
+    }
+
+    if !state.pending_shown_windows.is_empty()
+      && !state.is_display_transition_pending()
+    {
+      Self::manage_pending_shown_windows(state, config)?;
+    }
+
     if !state.is_paused && state.pending_sync.has_changes() {
       platform_sync(state, config)?;
     }
 
+    if should_sync_status_bars {
+      sync_status_bars(state, config)?;
+      sync_focus_frame(state, config);
+      sync_resize_guides(state);
+      sync_zoom(state);
+    }
+
     Ok(())
   }
 
@@ -125,8 +405,17 @@ impl WindowManager {
     &mut self,
     commands: &Vec<InvokeCommand>,
     subject_container_id: Option<Uuid>,
+    source: CommandSource,
     config: &mut UserConfig,
   ) -> anyhow::Result<Uuid> {
+    let start_time = Instant::now();
+
+    if source == CommandSource::Url {
+      for command in commands {
+        Self::check_url_command(command, config)?;
+      }
+    }
+
     let state = &mut self.state;
 
     // Get the container to run WM commands with.
@@ -135,13 +424,14 @@ impl WindowManager {
         format!("No container found with the given ID '{id}'.")
       })?,
       None => state
-        .focused_container()
+        .focused_subject()
         .context("No subject container for command.")?,
     };
 
     let new_subject_container_id = WindowManager::run_commands(
       commands,
       subject_container,
+      Some(source),
       state,
       config,
     )?;
@@ -150,24 +440,109 @@ impl WindowManager {
       platform_sync(state, config)?;
     }
 
+    sync_status_bars(state, config)?;
+    sync_focus_frame(state, config);
+    sync_resize_guides(state);
+    sync_zoom(state);
+    state.perf_stats.commands.record(start_time.elapsed());
+
     Ok(new_subject_container_id)
   }
 
+  /// Checks that a command can be run via a `glazewm://` URL.
+  ///
+  /// Since any web page or document can link to such a URL, only
+  /// commands that the user has allowed via `general.url_commands` can
+  /// be run. Commands that run programs, send keys, close windows, or
+  /// exit the WM are never allowed.
+  fn check_url_command(
+    command: &InvokeCommand,
+    config: &UserConfig,
+  ) -> anyhow::Result<()> {
+    let name = command.name();
+
+    if URL_DENIED_COMMANDS.contains(&name.as_str())
+      || !config.value.general.url_commands.contains(&name)
+    {
+      bail!("Command `{name}` can't be run via URLs.");
+    }
+
+    // Commands such as `if` can run further commands.
+    for nested_command in command.nested_commands()? {
+      Self::check_url_command(&nested_command, config)?;
+    }
+
+    Ok(())
+  }
+
+  /// Runs a command that was selected in an overlay (e.g. the command
+  /// palette).
+  ///
+  /// Commands can be prefixed with `command --id <ID>` to run them with
+  /// a specific subject container, as with the CLI.
+  pub fn process_overlay_command(
+    &mut self,
+    command: &str,
+    config: &mut UserConfig,
+  ) -> anyhow::Result<()> {
+    let (subject_container_id, command) = match AppCommand::try_parse_from(
+      iter::once("").chain(command.split_whitespace()),
+    ) {
+      Ok(AppCommand::Command {
+        subject_container_id,
+        command,
+        ..
+      }) => (subject_container_id, command),
+      _ => (
+        None,
+        InvokeCommand::parse_command(command)
+          .with_context(|| format!("Invalid command '{command}'."))?,
+      ),
+    };
+
+    self
+      .process_commands(
+        &vec![command],
+        subject_container_id,
+        CommandSource::Overlay,
+        config,
+      )
+      .map(|_| ())
+  }
+
+  /// Runs the commands in order, stopping at the first error.
+  ///
+  /// A `CommandInvoked` event is emitted for each command if a source is
+  /// given. Commands that are run as part of another command (e.g. the
+  /// branches of an `if` command) have no source.
   pub fn run_commands(
     commands: &Vec<InvokeCommand>,
     subject_container: Container,
+    source: Option<CommandSource>,
     state: &mut WmState,
     config: &mut UserConfig,
   ) -> anyhow::Result<Uuid> {
     let mut current_subject_container = subject_container;
 
     for command in commands {
-      WindowManager::run_command(
+      let res = WindowManager::run_command(
         command,
         current_subject_container.clone(),
         state,
         config,
-      )?;
+      );
+
+      if let Some(source) = source {
+        state.emit_event(WmEvent::CommandInvoked {
+          source,
+          command: command.to_command_string(),
+          subject_container_id: current_subject_container.id(),
+          success: res.is_ok(),
+          error: res.as_ref().err().map(ToString::to_string),
+        });
+      }
+
+      res?;
 
       // Update the subject container in case the container type changes.
       // For example, when going from a tiling to a floating window.
@@ -201,7 +576,15 @@ impl WindowManager {
       bail!("Cannot run command because subject container is detached.");
     }
 
+    state.emit_trace(|| TraceEvent::CommandRun {
+      command: command.clone(),
+      subject_container_id: subject_container.id(),
+    });
+
     match &command {
+      InvokeCommand::ActivateWorkspaceAtIndex { index, monitor } => {
+        focus_workspace_at_index(*index, *monitor, state, config)
+      }
       InvokeCommand::AdjustBorders(args) => {
         match subject_container.as_window_container() {
           Ok(window) => {
@@ -221,22 +604,94 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
-      InvokeCommand::Close => {
-        match subject_container.as_window_container() {
-          Ok(window) => {
-            // Window handle might no longer be valid here.
-            if let Err(err) = window.native().close() {
-              warn!("Failed to close window: {:?}", err);
-            }
+      InvokeCommand::AdoptWindow(args) => {
+        adopt_window(args, state, config)
+      }
+      InvokeCommand::AdjustGaps {
+        gap,
+        amount,
+        global,
+      } => {
+        let workspace = match global {
+          true => None,
+          false => {
+            Some(subject_container.workspace().context("No workspace.")?)
+          }
+        };
 
-            Ok(())
+        adjust_workspace_gaps(workspace, gap, amount, state, config)
+      }
+      InvokeCommand::ApplyWorkspaceTemplate { workspace } => {
+        let workspace = match workspace {
+          Some(workspace_name) => {
+            state.workspace_by_name(workspace_name).with_context(|| {
+              format!("Workspace '{workspace_name}' is not active.")
+            })?
           }
+          None => {
+            subject_container.workspace().context("No workspace.")?
+          }
+        };
+
+        apply_workspace_template(&workspace, true, state, config)
+      }
+      InvokeCommand::Center => {
+        match subject_container.as_window_container() {
+          Ok(window) => set_window_position(
+            window,
+            &WindowPositionTarget::Centered,
+            state,
+          ),
+          _ => Ok(()),
+        }
+      }
+      InvokeCommand::Close { force } => {
+        // Close all windows of a focused split container.
+        let windows: Vec<WindowContainer> = match &subject_container {
+          Container::Split(split) => split
+            .descendants()
+            .filter_map(|descendant| descendant.as_window_container().ok())
+            .collect(),
+          _ => subject_container
+            .as_window_container()
+            .into_iter()
+            .collect(),
+        };
+
+        close_windows(&windows, *force, state);
+        Ok(())
+      }
+      InvokeCommand::CloseWorkspaceWindows { force } => {
+        let workspace =
+          subject_container.workspace().context("No workspace.")?;
+
+        close_workspace_windows(&workspace, *force, state, config);
+        Ok(())
+      }
+      InvokeCommand::CycleRatio { ratios } => {
+        let ratios = ratios
+          .as_ref()
+          .unwrap_or(&config.value.general.cycle_ratios);
+
+        match subject_container.as_tiling_container() {
+          Ok(container) => cycle_ratio(&container, ratios, state),
           _ => Ok(()),
         }
       }
+      InvokeCommand::FocusOverflowNext => {
+        let workspace =
+          subject_container.workspace().context("No workspace.")?;
+
+        focus_overflow_next(&workspace, state, config)
+      }
       InvokeCommand::Focus(args) => {
         if let Some(direction) = &args.direction {
-          focus_in_direction(&subject_container, direction, state)?;
+          focus_in_direction(
+            &subject_container,
+            direction,
+            state,
+            config,
+          )?;
         }
 
         if let Some(direction) = &args.workspace_in_direction {
@@ -299,6 +754,43 @@ impl WindowManager {
           )?;
         }
 
+        if args.parent {
+          focus_parent(&subject_container, state)?;
+        }
+
+        if args.child {
+          focus_child(&subject_container, state)?;
+        }
+
+        Ok(())
+      }
+      InvokeCommand::If(args) => {
+        let (then_commands, else_commands) = args.parse_branches()?;
+
+        let commands = if Self::is_condition_met(args, &subject_container)?
+        {
+          then_commands
+        } else {
+          else_commands
+        };
+
+        Self::run_commands(
+          &commands,
+          subject_container,
+          None,
+          state,
+          config,
+        )?;
+        Ok(())
+      }
+      InvokeCommand::GatherWindows => {
+        let workspace =
+          subject_container.workspace().context("No workspace.")?;
+
+        gather_windows(&workspace, state, config)
+      }
+      InvokeCommand::HintWindows => {
+        show_window_hints(state);
         Ok(())
       }
       InvokeCommand::Ignore => {
@@ -308,6 +800,10 @@ impl WindowManager {
         }
       }
       InvokeCommand::Move(args) => {
+        if let Container::Split(split) = &subject_container {
+          return Self::move_split(split, args, state, config);
+        }
+
         match subject_container.as_window_container() {
           Ok(window) => {
             if let Some(direction) = &args.direction {
@@ -405,12 +901,24 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::MoveToMonitor { target, focus } => {
+        move_window_to_monitor(
+          &subject_container,
+          target,
+          *focus,
+          state,
+          config,
+        )
+      }
       InvokeCommand::MoveWorkspace { direction } => {
         let workspace =
           subject_container.workspace().context("No workspace.")?;
 
         move_workspace_in_direction(&workspace, direction, state, config)
       }
+      InvokeCommand::Noop => Ok(()),
+      InvokeCommand::Overview => show_overview(state),
+      InvokeCommand::Peek => peek_window(state),
       InvokeCommand::Position(args) => {
         match subject_container.as_window_container() {
           Ok(window) => {
@@ -431,7 +939,19 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::ReleaseWindow(args) => {
+        release_window(args, &subject_container, state, config)
+      }
       InvokeCommand::Resize(args) => {
+        if let Container::Split(split) = &subject_container {
+          return resize_split_container(
+            split,
+            args.width.clone(),
+            args.height.clone(),
+            state,
+          );
+        }
+
         match subject_container.as_window_container() {
           Ok(window) => resize_window(
             &window,
@@ -442,6 +962,60 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::ResizeTo { width, height } => {
+        match subject_container.as_window_container() {
+          Ok(window) => set_window_size(
+            window,
+            Some(width.clone()),
+            Some(height.clone()),
+            state,
+          ),
+          _ => Ok(()),
+        }
+      }
+      InvokeCommand::ScrollWorkspace { direction, monitor } => {
+        scroll_workspace(direction, *monitor, state, config)
+      }
+      InvokeCommand::SelectAdd => {
+        select_add(&subject_container, state);
+        Ok(())
+      }
+      InvokeCommand::SelectClear => {
+        select_clear(state);
+        Ok(())
+      }
+      InvokeCommand::SelectRun { command } => {
+        let command = command.join(" ");
+        let command = InvokeCommand::parse_command(&command)
+          .with_context(|| format!("Invalid command '{command}'."))?;
+
+        for window in state.selected_windows() {
+          // Windows can get detached by a previous run of the command
+          // (e.g. when closing a window).
+          if !window.is_detached() {
+            Self::run_command(&command, window.into(), state, config)?;
+          }
+        }
+
+        Ok(())
+      }
+      InvokeCommand::SetGaps { workspace, gaps } => {
+        let workspace = match workspace {
+          Some(workspace_name) => {
+            state.workspace_by_name(workspace_name).with_context(|| {
+              format!("Workspace '{workspace_name}' is not active.")
+            })?
+          }
+          None => {
+            subject_container.workspace().context("No workspace.")?
+          }
+        };
+
+        set_workspace_gaps(&workspace, gaps, state)
+      }
+      InvokeCommand::SetFocusAssist { mode } => {
+        set_focus_assist(*mode, state)
+      }
       InvokeCommand::SetFloating {
         centered,
         shown_on_top,
@@ -551,6 +1125,45 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::SetBadge { badge } => {
+        match subject_container.as_window_container() {
+          Ok(window) => {
+            let badge = badge.join(" ");
+            window.set_badge((!badge.is_empty()).then_some(badge));
+            Ok(())
+          }
+          _ => Ok(()),
+        }
+      }
+      InvokeCommand::SetHideMethod { method } => {
+        match subject_container.as_window_container() {
+          Ok(window) => {
+            window.set_hide_method(Some(method.clone()));
+            Ok(())
+          }
+          _ => Ok(()),
+        }
+      }
+      InvokeCommand::SetSizeConstraints(size_constraints) => {
+        match subject_container.as_window_container() {
+          Ok(window) => {
+            window.set_size_constraints(
+              (!size_constraints.is_empty())
+                .then(|| size_constraints.clone()),
+            );
+
+            // Siblings need to be redrawn to absorb the size difference.
+            if let Some(tiling_window) = window.as_tiling_window() {
+              state.pending_sync.queue_container_to_redraw(
+                tiling_window.parent().context("No parent.")?,
+              );
+            }
+
+            Ok(())
+          }
+          _ => Ok(()),
+        }
+      }
       InvokeCommand::SetTitleBarVisibility { visibility } => {
         match subject_container.as_window_container() {
           Ok(window) => {
@@ -578,11 +1191,34 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::SendHome => {
+        match subject_container.as_window_container() {
+          Ok(window) => send_window_home(window, state, config),
+          _ => Ok(()),
+        }
+      }
+      InvokeCommand::SendKeys { keys } => send_keys(keys),
       InvokeCommand::ShellExec {
         hide_window,
         command,
-      } => shell_exec(&command.join(" "), *hide_window),
+      } => shell_exec(&command.join(" "), *hide_window, state, config),
+      InvokeCommand::ShowKeybindings => {
+        show_keybindings(state, config);
+        Ok(())
+      }
+      InvokeCommand::ShowWindowSwitcher { scope } => {
+        show_window_switcher(scope, state)
+      }
       InvokeCommand::Size(args) => {
+        if let Container::Split(split) = &subject_container {
+          return set_split_container_size(
+            split,
+            args.width.clone(),
+            args.height.clone(),
+            state,
+          );
+        }
+
         match subject_container.as_window_container() {
           Ok(window) => set_window_size(
             window,
@@ -593,6 +1229,16 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::Snap { position } => {
+        match subject_container.as_window_container() {
+          Ok(window) => set_window_position(
+            window,
+            &WindowPositionTarget::Snap(position.clone()),
+            state,
+          ),
+          _ => Ok(()),
+        }
+      }
       InvokeCommand::ToggleFloating {
         centered,
         shown_on_top,
@@ -627,6 +1273,10 @@ impl WindowManager {
         }
         _ => Ok(()),
       },
+      InvokeCommand::ToggleFocusAssist { mode } => toggle_focus_assist(
+        mode.unwrap_or(FocusAssistMode::PriorityOnly),
+        state,
+      ),
       InvokeCommand::ToggleFullscreen {
         maximized,
         shown_on_top,
@@ -669,6 +1319,9 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::ToggleMonitorManagement { index } => {
+        toggle_monitor_management(*index, state, config)
+      }
       InvokeCommand::ToggleTiling => {
         match subject_container.as_window_container() {
           Ok(window) => {
@@ -687,6 +1340,16 @@ impl WindowManager {
       InvokeCommand::ToggleTilingDirection => {
         toggle_tiling_direction(subject_container, state, config)
       }
+      InvokeCommand::ToggleWorkspaceLayout => {
+        let workspace =
+          subject_container.workspace().context("No workspace.")?;
+
+        toggle_workspace_layout(&workspace, state)
+      }
+      InvokeCommand::ToggleZoom { factor, scope } => {
+        toggle_zoom(*factor, *scope, state, config);
+        Ok(())
+      }
       InvokeCommand::SetTilingDirection { tiling_direction } => {
         set_tiling_direction(
           subject_container,
@@ -695,6 +1358,10 @@ impl WindowManager {
           tiling_direction,
         )
       }
+      InvokeCommand::Unpeek => {
+        unpeek_window(state);
+        Ok(())
+      }
       InvokeCommand::WmCycleFocus {
         omit_floating,
         omit_fullscreen,
@@ -709,25 +1376,116 @@ impl WindowManager {
         config,
       ),
       InvokeCommand::WmDisableBindingMode { name } => {
-        disable_binding_mode(name, state);
+        disable_binding_mode(name.as_deref(), state);
         Ok(())
       }
+      InvokeCommand::WmDisableTimer { name } => {
+        set_timer_enabled(name, Some(false), state)
+      }
       InvokeCommand::WmEnableBindingMode { name } => {
         enable_binding_mode(name, state, config)
       }
+      InvokeCommand::WmEnableTimer { name } => {
+        set_timer_enabled(name, Some(true), state)
+      }
       InvokeCommand::WmExit => state.emit_exit(),
       InvokeCommand::WmRedraw => {
-        state
-          .pending_sync
-          .queue_container_to_redraw(state.root_container.clone());
-
+        state.mark_container_dirty(&state.root_container.clone().into());
         Ok(())
       }
       InvokeCommand::WmReloadConfig => reload_config(state, config),
+      InvokeCommand::WmRunTimer { name } => {
+        run_timer(name, subject_container, state, config)
+      }
+      InvokeCommand::WmShowCommandPalette => {
+        show_command_palette(state, config);
+        Ok(())
+      }
+      InvokeCommand::WmShowMenu { name } => show_menu(name, state, config),
+      InvokeCommand::WmToggleBindingMode { name } => {
+        toggle_binding_mode(name, state, config)
+      }
+      InvokeCommand::WmToggleMouseEvents => {
+        toggle_mouse_events(state);
+        Ok(())
+      }
       InvokeCommand::WmTogglePause => {
         toggle_pause(state);
         Ok(())
       }
+      InvokeCommand::WmToggleTimer { name } => {
+        set_timer_enabled(name, None, state)
+      }
+      InvokeCommand::WmToggleTrace => {
+        toggle_trace(state);
+        Ok(())
+      }
     }
   }
+
+  /// Runs the `move` command with a split container as its subject.
+  fn move_split(
+    split: &SplitContainer,
+    args: &InvokeMoveCommand,
+    state: &mut WmState,
+    config: &UserConfig,
+  ) -> anyhow::Result<()> {
+    if let Some(direction) = &args.direction {
+      return move_split_in_direction(split, direction, state, config);
+    }
+
+    let target = if let Some(direction) = &args.workspace_in_direction {
+      WorkspaceTarget::Direction(direction.clone())
+    } else if let Some(name) = &args.workspace {
+      WorkspaceTarget::Name(name.clone())
+    } else if args.next_active_workspace {
+      WorkspaceTarget::NextActive
+    } else if args.prev_active_workspace {
+      WorkspaceTarget::PreviousActive
+    } else if args.next_workspace {
+      WorkspaceTarget::Next
+    } else if args.prev_workspace {
+      WorkspaceTarget::Previous
+    } else if args.recent_workspace {
+      WorkspaceTarget::Recent
+    } else if args.next_active_workspace_on_monitor {
+      WorkspaceTarget::NextActiveInMonitor
+    } else if args.prev_active_workspace_on_monitor {
+      WorkspaceTarget::PreviousActiveInMonitor
+    } else {
+      return Ok(());
+    };
+
+    move_split_to_workspace(split, target, state, config)
+  }
+
+  /// Evaluates the condition of an `if` command against the subject
+  /// container. All given conditions need to be met.
+  fn is_condition_met(
+    args: &InvokeIfCommand,
+    subject_container: &Container,
+  ) -> anyhow::Result<bool> {
+    let window = subject_container.as_window_container().ok();
+    let window_state = window.as_ref().map(WindowGetters::state);
+
+    let mut is_met = (!args.floating
+      || matches!(window_state, Some(WindowState::Floating(_))))
+      && (!args.tiling || window_state == Some(WindowState::Tiling))
+      && (!args.fullscreen
+        || matches!(window_state, Some(WindowState::Fullscreen(_))))
+      && (!args.minimized || window_state == Some(WindowState::Minimized));
+
+    if let Some(process) = &args.process {
+      is_met = is_met
+        && match &window {
+          Some(window) => window
+            .native()
+            .process_name()?
+            .eq_ignore_ascii_case(process),
+          None => false,
+        };
+    }
+
+    Ok(is_met != args.not)
+  }
 }
@@ -1,28 +1,112 @@
-use std::time::Instant;
+use std::{
+  collections::HashMap,
+  time::{Duration, Instant},
+};
 
 use anyhow::Context;
-use tokio::sync::mpsc::{self};
+use tokio::{sync::mpsc, task};
 use tracing::warn;
 use uuid::Uuid;
 use wm_common::{
-  BindingModeConfig, Direction, Point, WindowState, WmEvent,
+  BindingModeConfig, Direction, DisplayState, FocusAssistMode, HideMethod,
+  PerfStats, Point, Rect, StartupWindowAssignment, TraceEvent,
+  WindowState, WmEvent, ZoomScope,
+};
+use wm_platform::{
+  NativeMonitor, NativeWindow, Platform, WindowPosBatch, ZOrder,
 };
-use wm_platform::{NativeMonitor, NativeWindow, Platform};
 
 use crate::{
   commands::{
-    container::set_focused_descendant, general::platform_sync,
-    monitor::add_monitor, window::manage_window,
+    container::set_focused_descendant,
+    general::{platform_sync, update_accessibility},
+    monitor::{add_monitor, managed_native_monitors},
+    window::{manage_window, move_window_to_workspace},
+    workspace::activate_startup_workspaces,
   },
+  floating_geometry::FloatingGeometry,
   models::{
-    Container, Monitor, RootContainer, WindowContainer, Workspace,
-    WorkspaceTarget,
+    Container, Monitor, RootContainer, SplitContainer, WindowContainer,
+    Workspace, WorkspaceTarget,
   },
+  offscreen_windows::OffscreenWindows,
   pending_sync::PendingSync,
+  scheduler::Scheduler,
   traits::{CommonGetters, PositionGetters, WindowGetters},
   user_config::UserConfig,
 };
 
+/// Timeout for an unresponsive window to process a message before it's
+/// polled again.
+const RESPONSIVE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Interval between polls of an unresponsive window.
+const RESPONSIVE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Max duration that windows are considered to be transitioning between
+/// shown and hidden. Guards against shown/hidden events that never
+/// arrive.
+const DISPLAY_TRANSITION_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Position and state of a window as of when it was last repositioned.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowPlacement {
+  pub rect: Rect,
+  pub state: WindowState,
+  pub display_state: DisplayState,
+  pub z_order: ZOrder,
+}
+
+/// Workspace that was focused when a process was launched via
+/// `shell-exec`.
+#[derive(Clone, Debug)]
+pub struct LaunchOrigin {
+  pub process_id: u32,
+  pub workspace_name: String,
+  pub launched_at: Instant,
+}
+
+/// Workspace and state of a window once it's managed and its window
+/// rules have run. Used to find adjustments made to the window since.
+#[derive(Clone, Debug)]
+pub struct ManagedOrigin {
+  pub workspace_name: String,
+  pub state: WindowState,
+}
+
+/// Window that is temporarily revealed via the `peek` command.
+#[derive(Clone, Debug)]
+pub struct PeekState {
+  pub window: WindowContainer,
+
+  /// Z-order to restore the peeked window to once the peek ends.
+  pub prev_z_order: ZOrder,
+
+  /// Whether the peeked window was topmost before the peek, in which case
+  /// it's kept topmost once the peek ends.
+  pub was_topmost: bool,
+
+  /// Other windows that are dimmed while the peek is active.
+  pub dimmed_windows: Vec<WindowContainer>,
+}
+
+/// Accessibility adjustments that are currently applied, as resolved
+/// from the system settings and `general.accessibility` in the user
+/// config.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AccessibilityState {
+  pub is_high_contrast: bool,
+  pub is_reduced_motion: bool,
+}
+
+/// Screen magnification enabled via `toggle-zoom`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZoomState {
+  pub factor: f32,
+  pub scope: ZoomScope,
+}
+
+#[allow(clippy::struct_excessive_bools)]
 pub struct WmState {
   /// Root node of the container tree. Monitors are the children of the
   /// root node, followed by workspaces, then split containers/windows.
@@ -30,18 +114,52 @@ pub struct WmState {
 
   pub pending_sync: PendingSync,
 
+  /// Timers from the `timers` config section and when they're next due.
+  pub scheduler: Scheduler,
+
   /// Name of the most recently focused workspace.
   ///
   /// Used for the `general.toggle_workspace_on_refocus` option on
   /// workspace focus.
   pub recent_workspace_name: Option<String>,
 
-  /// The previously focused window that had focus effects applied.
+  /// ID of the workspace that was focused as of the last focus change.
+  ///
+  /// Used to announce workspace switches to screen readers.
+  pub announced_workspace_id: Option<Uuid>,
+
+  /// The previously focused windows that had focus effects applied.
+  /// Contains multiple windows if a split container was focused.
   ///
   /// Used to efficiently update window effects by only removing focus
-  /// effects from the previous window rather than all windows when focus
+  /// effects from the previous windows rather than all windows when focus
   /// changes.
-  pub prev_effects_window: Option<WindowContainer>,
+  pub prev_effects_windows: Vec<WindowContainer>,
+
+  /// Split container that is focused as a whole via `focus --parent`,
+  /// along with the ID of the focused container it was selected from.
+  ///
+  /// Use `focused_split` to get the split container, since the selection
+  /// no longer applies once focus moves to a different container.
+  pub split_focus: Option<(Uuid, SplitContainer)>,
+
+  /// IDs of windows selected via `select-add`, in order of selection.
+  ///
+  /// IDs are stored rather than containers, since a window's container
+  /// is replaced when its state changes (e.g. tiling -> floating).
+  pub selected_window_ids: Vec<Uuid>,
+
+  /// Last-known floating geometry of windows on floating layout
+  /// workspaces.
+  pub floating_geometry: FloatingGeometry,
+
+  /// Windows that are currently moved off-screen via the `offscreen`
+  /// hide method.
+  pub offscreen_windows: OffscreenWindows,
+
+  /// Window that is currently revealed via `peek`. Reverted via
+  /// `unpeek`.
+  pub peek: Option<PeekState>,
 
   /// Time since a previously focused window was unmanaged or minimized.
   ///
@@ -51,16 +169,98 @@ pub struct WmState {
   /// Configs of currently enabled binding modes.
   pub binding_modes: Vec<BindingModeConfig>,
 
+  /// Workspaces that recently launched processes were launched from.
+  /// Windows of these processes are opened on the origin workspace.
+  pub launch_origins: Vec<LaunchOrigin>,
+
   /// Windows that the WM should ignore. Windows can be added via the
   /// `ignore` command.
   pub ignored_windows: Vec<NativeWindow>,
 
+  /// Monitors that the WM doesn't manage, either due to `monitor_rules`
+  /// in the user config or via the `toggle-monitor-management` command.
+  /// Windows on these monitors are ignored.
+  pub unmanaged_monitors: Vec<NativeMonitor>,
+
+  /// Whether monitors are managed as toggled at runtime, keyed by
+  /// device path. Takes precedence over `monitor_rules`.
+  pub monitor_management_overrides: HashMap<String, bool>,
+
+  /// Placements of windows as of when they were last repositioned.
+  ///
+  /// Used to skip repositioning windows on redraw if their placement is
+  /// unchanged.
+  pub window_placements: HashMap<Uuid, WindowPlacement>,
+
+  /// Placeholder windows from workspace templates, keyed by window ID.
+  /// Values are the index of the template slot that the placeholder
+  /// holds.
+  pub placeholder_slots: HashMap<Uuid, usize>,
+
+  /// Workspace and state of managed windows once they were managed,
+  /// keyed by window ID.
+  pub managed_origins: HashMap<Uuid, ManagedOrigin>,
+
+  /// Windows that have stopped responding. These are skipped when
+  /// redrawing until they respond again.
+  pub unresponsive_windows: Vec<NativeWindow>,
+
+  /// Windows that were shown while other windows were being shown or
+  /// hidden (e.g. during a workspace switch). These are managed once the
+  /// transition completes, so that they're not hidden along with the
+  /// workspace being switched away from.
+  pub pending_shown_windows: Vec<NativeWindow>,
+
+  /// Time at which windows last started transitioning between shown and
+  /// hidden.
+  pub display_transition_timestamp: Option<Instant>,
+
   /// Whether the WM is paused.
   pub is_paused: bool,
 
+  /// Focused game window while in game mode. Parts of the WM are paused
+  /// while in game mode, as set by `general.game_mode` in the user
+  /// config.
+  pub game_window: Option<NativeWindow>,
+
+  /// Accessibility adjustments that are currently applied. Updated when
+  /// the system settings or the user config change.
+  pub accessibility: AccessibilityState,
+
+  /// Screen magnification that follows focus, or `None` if zoomed out.
+  pub zoom: Option<ZoomState>,
+
+  /// Time at which the user became idle, as set by `general.idle` in the
+  /// user config. `None` if the user isn't idle.
+  pub idle_started_at: Option<Instant>,
+
+  /// Time at which to next check whether the user is idle. `None` if
+  /// idle detection is disabled.
+  pub idle_check_deadline: Option<Instant>,
+
+  /// Time at which to next check which windows are playing audio or
+  /// using the microphone, camera, or screen capture. `None` if media
+  /// detection is disabled.
+  pub media_check_deadline: Option<Instant>,
+
+  /// Focus Assist profile to restore once a workspace with the
+  /// `focus_assist` option is no longer focused.
+  pub focus_assist_restore_mode: Option<FocusAssistMode>,
+
+  /// Whether mouse events (e.g. for focus follows cursor) are enabled.
+  /// Can be toggled at runtime via the `wm-toggle-mouse-events` command.
+  pub is_mouse_events_enabled: bool,
+
+  /// Whether trace events are emitted for debugging. Can be toggled at
+  /// runtime via the `wm-toggle-trace` command.
+  pub is_trace_enabled: bool,
+
   /// Whether the OS focused window is the same as the WM focused window.
   pub is_focus_synced: bool,
 
+  /// Performance counters for the event loop. Exposed via `query stats`.
+  pub perf_stats: PerfStats,
+
   /// Whether the initial state has been populated.
   has_initialized: bool,
 
@@ -69,26 +269,63 @@ pub struct WmState {
 
   /// Sender for gracefully shutting down the WM.
   exit_tx: mpsc::UnboundedSender<()>,
+
+  /// Sender for windows that have become responsive again.
+  window_responsive_tx: mpsc::UnboundedSender<NativeWindow>,
+
+  /// Sender for commands selected in overlays (e.g. the command palette
+  /// or window switcher).
+  overlay_command_tx: mpsc::UnboundedSender<String>,
 }
 
 impl WmState {
   pub fn new(
     event_tx: mpsc::UnboundedSender<WmEvent>,
     exit_tx: mpsc::UnboundedSender<()>,
+    window_responsive_tx: mpsc::UnboundedSender<NativeWindow>,
+    overlay_command_tx: mpsc::UnboundedSender<String>,
   ) -> Self {
     Self {
       root_container: RootContainer::new(),
       pending_sync: PendingSync::default(),
-      prev_effects_window: None,
+      scheduler: Scheduler::default(),
+      prev_effects_windows: Vec::new(),
+      split_focus: None,
+      selected_window_ids: Vec::new(),
+      peek: None,
+      floating_geometry: FloatingGeometry::load(),
+      offscreen_windows: OffscreenWindows::load(),
       recent_workspace_name: None,
+      announced_workspace_id: None,
       unmanaged_or_minimized_timestamp: None,
       binding_modes: Vec::new(),
+      launch_origins: Vec::new(),
       ignored_windows: Vec::new(),
+      unmanaged_monitors: Vec::new(),
+      monitor_management_overrides: HashMap::new(),
+      window_placements: HashMap::new(),
+      placeholder_slots: HashMap::new(),
+      managed_origins: HashMap::new(),
+      unresponsive_windows: Vec::new(),
+      pending_shown_windows: Vec::new(),
+      display_transition_timestamp: None,
       is_paused: false,
+      game_window: None,
+      accessibility: AccessibilityState::default(),
+      zoom: None,
+      idle_started_at: None,
+      idle_check_deadline: Some(Instant::now()),
+      media_check_deadline: Some(Instant::now()),
+      focus_assist_restore_mode: None,
+      is_mouse_events_enabled: true,
+      is_trace_enabled: false,
       is_focus_synced: false,
+      perf_stats: PerfStats::default(),
       has_initialized: false,
       event_tx,
       exit_tx,
+      window_responsive_tx,
+      overlay_command_tx,
     }
   }
 
@@ -101,27 +338,72 @@ impl WmState {
     // Get the originally focused window when the WM was started.
     let foreground_window = Platform::foreground_window();
 
+    self.scheduler.update(&config.value.timers);
+
+    // Bring back windows that a previous run left off-screen (e.g. if it
+    // crashed), so that they can be managed as usual.
+    self.offscreen_windows.restore_all();
+
     // Create a monitor, and consequently a workspace, for each detected
     // native monitor.
-    for native_monitor in Platform::sorted_monitors()? {
+    for native_monitor in managed_native_monitors(self, config)? {
       add_monitor(native_monitor, self, config)?;
     }
 
+    activate_startup_workspaces(self, config)?;
+
+    let assignment =
+      config.value.general.startup_window_assignment.clone();
+
+    let active_workspace = self
+      .nearest_monitor(&foreground_window)
+      .or_else(|| self.monitors().into_iter().next())
+      .and_then(|monitor| monitor.displayed_workspace());
+
     // Manage windows in reverse z-order (bottom to top). This helps to
     // preserve the original stacking order.
     for native_window in Platform::manageable_windows()?.into_iter().rev()
     {
-      let nearest_workspace = self
-        .nearest_monitor(&native_window)
-        .and_then(|m| m.displayed_workspace());
+      if self.is_on_unmanaged_monitor(&native_window) {
+        continue;
+      }
 
-      if let Some(workspace) = nearest_workspace {
+      let target_workspace = match assignment {
+        StartupWindowAssignment::ActiveWorkspace => {
+          active_workspace.clone()
+        }
+        _ => self
+          .nearest_monitor(&native_window)
+          .and_then(|m| m.displayed_workspace()),
+      }
+      .filter(|workspace| config.is_managed_workspace(workspace));
+
+      if let Some(workspace) = target_workspace {
         manage_window(
-          native_window,
-          Some(workspace.into()),
+          native_window.clone(),
+          Some(workspace.clone().into()),
           self,
           config,
         )?;
+
+        // Undo any moves to other workspaces by window rules.
+        if assignment == StartupWindowAssignment::Monitor {
+          let moved_window =
+            self.window_from_native(&native_window).filter(|window| {
+              window
+                .workspace()
+                .is_some_and(|current| current.id() != workspace.id())
+            });
+
+          if let Some(window) = moved_window {
+            move_window_to_workspace(
+              window,
+              WorkspaceTarget::Name(workspace.config().name),
+              self,
+              config,
+            )?;
+          }
+        }
       }
     }
 
@@ -135,10 +417,7 @@ impl WmState {
     set_focused_descendant(&container_to_focus, None);
     self.is_focus_synced = true;
 
-    self
-      .pending_sync
-      .queue_focus_change()
-      .queue_all_effects_update();
+    self.pending_sync.queue_focus_change();
 
     for workspace in self.workspaces() {
       self.pending_sync.queue_workspace_to_reorder(workspace);
@@ -147,6 +426,12 @@ impl WmState {
     platform_sync(self, config)?;
     self.has_initialized = true;
 
+    // Window effects are non-essential, so they're left pending until the
+    // next platform sync (i.e. after startup commands are run) rather
+    // than delaying the initial layout.
+    self.pending_sync.queue_all_effects_update();
+    update_accessibility(self, config);
+
     Ok(())
   }
 
@@ -162,6 +447,20 @@ impl WmState {
       .collect()
   }
 
+  /// Gets the monitor at the given index, or the monitor of the focused
+  /// container if no index is given.
+  pub fn monitor_at_index_or_focused(
+    &self,
+    monitor_index: Option<usize>,
+  ) -> Option<Monitor> {
+    match monitor_index {
+      Some(index) => self.monitors().get(index).cloned(),
+      None => self
+        .focused_container()
+        .and_then(|focused| focused.monitor()),
+    }
+  }
+
   /// Gets workspaces sorted by their position in the user config.
   pub fn sorted_workspaces(&self, config: &UserConfig) -> Vec<Workspace> {
     let mut workspaces = self.workspaces();
@@ -190,6 +489,16 @@ impl WmState {
       .or(self.monitors().first().cloned())
   }
 
+  /// Whether the window is on a monitor that the WM doesn't manage.
+  pub fn is_on_unmanaged_monitor(
+    &self,
+    native_window: &NativeWindow,
+  ) -> bool {
+    self
+      .unmanaged_monitors
+      .contains(&Platform::nearest_monitor(native_window))
+  }
+
   /// Gets monitor that corresponds to the given `NativeMonitor`.
   pub fn monitor_from_native(
     &self,
@@ -248,6 +557,54 @@ impl WmState {
     Ok(closest_monitor)
   }
 
+  /// Gets the monitor to wrap around to when there's no monitor in the
+  /// given direction. This is the furthest monitor in the opposite
+  /// direction, which can be the origin monitor itself.
+  ///
+  /// If `is_global` is `false`, only monitors that are in line with the
+  /// origin monitor are considered. Otherwise, ties are broken by the
+  /// distance to the origin monitor on the perpendicular axis.
+  pub fn wrapping_monitor_in_direction(
+    &self,
+    origin_monitor: &Monitor,
+    direction: &Direction,
+    is_global: bool,
+  ) -> anyhow::Result<Option<Monitor>> {
+    let origin_rect = origin_monitor.native().rect()?.clone();
+
+    let monitors_with_rect = self
+      .monitors()
+      .into_iter()
+      .map(|monitor| {
+        let rect = monitor.native().rect()?.clone();
+        anyhow::Ok((monitor, rect))
+      })
+      .try_collect::<Vec<_>>()?;
+
+    let furthest_monitor = monitors_with_rect
+      .into_iter()
+      .filter(|(_, rect)| {
+        is_global
+          || match direction {
+            Direction::Left | Direction::Right => {
+              rect.has_overlap_y(&origin_rect)
+            }
+            Direction::Up | Direction::Down => {
+              rect.has_overlap_x(&origin_rect)
+            }
+          }
+      })
+      .min_by_key(|(_, rect)| match direction {
+        Direction::Right => (rect.x(), (rect.y() - origin_rect.y()).abs()),
+        Direction::Left => (-rect.x(), (rect.y() - origin_rect.y()).abs()),
+        Direction::Down => (rect.y(), (rect.x() - origin_rect.x()).abs()),
+        Direction::Up => (-rect.y(), (rect.x() - origin_rect.x()).abs()),
+      })
+      .map(|(monitor, _)| monitor);
+
+    Ok(furthest_monitor)
+  }
+
   /// Gets window that corresponds to the given `NativeWindow`.
   pub fn window_from_native(
     &self,
@@ -259,6 +616,70 @@ impl WmState {
       .find(|window| &*window.native() == native_window)
   }
 
+  /// Forces the window to be repositioned on its next redraw, since it
+  /// might have been moved outside of the WM.
+  pub fn invalidate_window_placement(
+    &mut self,
+    native_window: &NativeWindow,
+  ) {
+    if let Some(window) = self.window_from_native(native_window) {
+      self.window_placements.remove(&window.id());
+    }
+  }
+
+  /// Marks a container as dirty, so that its windows are repositioned on
+  /// the next redraw even if their placement is unchanged.
+  ///
+  /// Dirtiness propagates down to all descendant windows, and up to the
+  /// parent, since the layout of the container's siblings depends on it.
+  /// The parent is queued for redraw as a result.
+  pub fn mark_container_dirty(&mut self, container: &Container) {
+    for descendant in container.self_and_descendants() {
+      self.window_placements.remove(&descendant.id());
+    }
+
+    let redraw_container = container
+      .parent()
+      .map_or_else(|| container.clone(), Into::into);
+
+    self
+      .pending_sync
+      .queue_container_to_redraw(redraw_container);
+  }
+
+  /// Whether any windows are currently transitioning between shown and
+  /// hidden (i.e. a workspace switch is in flight).
+  pub fn is_display_transition_pending(&self) -> bool {
+    let is_recent =
+      self.display_transition_timestamp.is_some_and(|timestamp| {
+        timestamp.elapsed() < DISPLAY_TRANSITION_TIMEOUT
+      });
+
+    is_recent
+      && self.windows().iter().any(|window| {
+        matches!(
+          window.display_state(),
+          DisplayState::Showing | DisplayState::Hiding
+        )
+      })
+  }
+
+  /// Time after which pending shown windows are managed, even if the
+  /// display state transition hasn't completed.
+  pub fn pending_shown_windows_deadline(&self) -> Option<Instant> {
+    if self.pending_shown_windows.is_empty() {
+      return None;
+    }
+
+    Some(
+      self
+        .display_transition_timestamp
+        .map_or_else(Instant::now, |timestamp| {
+          timestamp + DISPLAY_TRANSITION_TIMEOUT
+        }),
+    )
+  }
+
   pub fn workspace_by_name(
     &self,
     workspace_name: &str,
@@ -443,6 +864,22 @@ impl WmState {
           target_workspace,
         )
       }
+      WorkspaceTarget::Monitor(index) => {
+        let target_workspace = self
+          .monitors()
+          .get(index)
+          .with_context(|| {
+            format!("Monitor at index {index} was not found.")
+          })?
+          .displayed_workspace();
+
+        (
+          target_workspace
+            .as_ref()
+            .map(|workspace| workspace.config().name),
+          target_workspace,
+        )
+      }
     };
 
     Ok((name, workspace))
@@ -470,6 +907,44 @@ impl WmState {
     self.root_container.descendant_focus_order().next()
   }
 
+  /// Gets the split container that is focused as a whole via `focus
+  /// --parent`.
+  ///
+  /// Returns `None` if focus has since moved to a different container, or
+  /// if the split container no longer contains the focused container.
+  pub fn focused_split(&self) -> Option<SplitContainer> {
+    let (focused_id, split) = self.split_focus.as_ref()?;
+    let focused = self.focused_container()?;
+
+    let is_valid = focused.id() == *focused_id
+      && !split.is_detached()
+      && focused
+        .ancestors()
+        .any(|ancestor| ancestor.id() == split.id());
+
+    is_valid.then(|| split.clone())
+  }
+
+  /// Gets windows selected via `select-add` that are still managed.
+  pub fn selected_windows(&self) -> Vec<WindowContainer> {
+    self
+      .selected_window_ids
+      .iter()
+      .filter_map(|id| self.container_by_id(*id))
+      .filter_map(|container| container.as_window_container().ok())
+      .collect()
+  }
+
+  /// Gets the container to run WM commands with when no subject container
+  /// is given. This is the focused split container if there is one, and
+  /// otherwise the focused container.
+  pub fn focused_subject(&self) -> Option<Container> {
+    self
+      .focused_split()
+      .map(Into::into)
+      .or_else(|| self.focused_container())
+  }
+
   /// Emits a WM event through an MSPC channel.
   ///
   /// Does not emit events while the WM is paused or populating initial
@@ -477,8 +952,14 @@ impl WmState {
   /// from being emitted via IPC server before the initial state is
   /// prepared.
   pub fn emit_event(&self, event: WmEvent) {
+    // Custom events are passed through while paused, since they're sent
+    // by the user rather than reflecting changes to the WM state.
     if self.has_initialized
-      && (!self.is_paused || matches!(event, WmEvent::PauseChanged { .. }))
+      && (!self.is_paused
+        || matches!(
+          event,
+          WmEvent::PauseChanged { .. } | WmEvent::CustomEvent { .. }
+        ))
     {
       if let Err(err) = self.event_tx.send(event) {
         warn!("Failed to send event: {}", err);
@@ -486,6 +967,57 @@ impl WmState {
     }
   }
 
+  /// Emits a trace event if trace mode is enabled.
+  ///
+  /// The trace event is lazily created, since creating it can be
+  /// relatively expensive (e.g. formatting or querying window info).
+  pub fn emit_trace(&self, trace: impl FnOnce() -> TraceEvent) {
+    if self.is_trace_enabled {
+      self.emit_event(WmEvent::Trace { trace: trace() });
+    }
+  }
+
+  /// Marks a window as unresponsive, so that it's skipped on redraw.
+  ///
+  /// The window is polled on a worker thread until it responds, after
+  /// which it's sent through an MSPC channel to be redrawn.
+  pub fn mark_window_unresponsive(
+    &mut self,
+    window: &WindowContainer,
+  ) -> anyhow::Result<()> {
+    if self.unresponsive_windows.contains(&window.native()) {
+      return Ok(());
+    }
+
+    warn!("Window is not responding: {window}");
+    self.unresponsive_windows.push(window.native().clone());
+
+    self.emit_event(WmEvent::WindowUnresponsive {
+      unresponsive_window: window.to_dto()?,
+    });
+
+    let native = window.native().clone();
+    let window_responsive_tx = self.window_responsive_tx.clone();
+
+    task::spawn_blocking(move || {
+      while native.is_valid() {
+        if native.is_responsive(RESPONSIVE_TIMEOUT) {
+          let _ = window_responsive_tx.send(native);
+          return;
+        }
+
+        std::thread::sleep(RESPONSIVE_POLL_INTERVAL);
+      }
+    });
+
+    Ok(())
+  }
+
+  /// Gets a sender for commands selected in overlays.
+  pub fn overlay_command_tx(&self) -> mpsc::UnboundedSender<String> {
+    self.overlay_command_tx.clone()
+  }
+
   /// Starts graceful shutdown via an MSPC channel.
   pub fn emit_exit(&self) -> anyhow::Result<()> {
     self.exit_tx.send(())?;
@@ -559,8 +1091,7 @@ impl WmState {
       .filter(|descendant| {
         descendant
           .to_rect()
-          .map(|rect| rect.contains_point(point))
-          .unwrap_or(false)
+          .is_ok_and(|rect| rect.contains_point(point))
       })
       .collect()
   }
@@ -573,8 +1104,7 @@ impl WmState {
       .find(|monitor| {
         monitor
           .to_rect()
-          .map(|rect| rect.contains_point(point))
-          .unwrap_or(false)
+          .is_ok_and(|rect| rect.contains_point(point))
       })
       .cloned()
   }
@@ -582,6 +1112,35 @@ impl WmState {
 
 impl Drop for WmState {
   fn drop(&mut self) {
+    // Move windows on hidden workspaces back onto their monitor, since
+    // they might've been moved off-screen.
+    let mut batch = WindowPosBatch::new();
+
+    for window in self.windows() {
+      if window.display_state() != DisplayState::Hidden {
+        continue;
+      }
+
+      let rect = window.to_rect().and_then(|rect| {
+        Ok(rect.apply_delta(&window.total_border_delta()?, None))
+      });
+
+      if let Ok(rect) = rect {
+        _ = window.native().set_position(
+          &mut batch,
+          &window.state(),
+          &rect,
+          &ZOrder::Normal,
+          true,
+          &HideMethod::Offscreen,
+          false,
+        );
+      }
+    }
+
+    batch.apply();
+    self.offscreen_windows.restore_all();
+
     let managed_windows = self
       .windows()
       .into_iter()